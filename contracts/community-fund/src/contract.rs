@@ -1,4 +1,5 @@
 use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use white_whale::address::validate_addr;
 use white_whale::community_fund::msg::{ConfigResponse, ExecuteMsg, QueryMsg};
 
 use crate::commands;
@@ -18,8 +19,8 @@ pub fn instantiate(
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
-    let whale_token_addr = deps.api.addr_validate(&msg.whale_token_addr)?;
+) -> CommunityFundResult {
+    let whale_token_addr = validate_addr(deps.api, "whale_token_addr", &msg.whale_token_addr)?;
 
     let state = State { whale_token_addr };
 
@@ -2,12 +2,16 @@ use thiserror::Error;
 
 use cosmwasm_std::{StdError, Uint128};
 use cw_controllers::AdminError;
+use white_whale::error::CommonError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum CommunityFundError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
@@ -2,6 +2,7 @@ use cosmwasm_std::testing::{mock_dependencies, mock_env, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{Api, DepsMut, MessageInfo};
 
 use white_whale::community_fund::msg::ExecuteMsg;
+use white_whale::error::CommonError;
 
 use crate::contract::{execute, instantiate};
 use crate::error::CommunityFundError;
@@ -53,6 +54,25 @@ fn successful_initialization() {
     );
 }
 
+#[test]
+fn unsuccessful_initialization_invalid_address() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        whale_token_addr: "x".to_string(),
+    };
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(TEST_CREATOR).unwrap(),
+        funds: vec![],
+    };
+    match instantiate(deps.as_mut(), mock_env(), info, msg) {
+        Err(CommunityFundError::Common(CommonError::InvalidAddress { field, .. })) => {
+            assert_eq!(field, "whale_token_addr")
+        }
+        _ => panic!("Must return CommonError::InvalidAddress"),
+    }
+}
+
 #[test]
 fn unsuccessful_set_admin() {
     let mut deps = mock_dependencies(&[]);
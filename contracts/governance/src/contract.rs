@@ -1,26 +1,46 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Binary, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdResult, Uint128, WasmMsg,
+    from_binary, from_slice, to_binary, Addr, BankMsg, Binary, CanonicalAddr, Coin, CosmosMsg,
+    Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, Storage, SubMsg, Uint128,
+    WasmMsg,
 };
+use cw2::{set_contract_version, CONTRACT};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
+use semver::Version;
+use serde::de::IgnoredAny;
+use terraswap::asset::{Asset, AssetInfo};
 use terraswap::querier::query_token_balance;
 
+use crate::delegation::{delegate_voting_power, query_delegations, undelegate_voting_power};
 use crate::error::ContractError;
-use crate::staking::{query_staker, stake_voting_tokens, withdraw_voting_tokens};
+use crate::migrations::{migrate_poll_indexes, migrate_unversioned_state, INDEXED_POLLS_VERSION};
+use crate::staking::{
+    prune_locked_balance, query_staker, stake_voting_tokens, withdraw_voting_tokens,
+};
+use crate::staking_rewards::{claim_rewards, distribute_rewards, query_staker_rewards};
 use crate::validators::{
-    validate_poll_description, validate_poll_link, validate_poll_period, validate_poll_title,
-    validate_quorum, validate_threshold,
+    validate_config, validate_poll_category, validate_poll_description, validate_poll_execute_msgs,
+    validate_poll_link, validate_poll_metadata_hash, validate_poll_title, validate_poll_type,
+    validate_self_execute_msg,
 };
-use white_whale::governance::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::voting_rewards::{claim_voting_rewards, query_pending_voting_rewards};
+use white_whale::address::validate_addr;
+use white_whale::events::{WhaleEvent, GOVERNANCE};
+use white_whale::governance::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use white_whale::governance::state::{
-    bank_read, bank_store, config_read, config_store, poll_indexer_store, poll_read, poll_store,
-    poll_voter_read, poll_voter_store, read_poll_voters, read_polls, state_read, state_store,
-    Config, ConfigResponse, Cw20HookMsg, ExecuteData, OrderBy, Poll, PollExecuteMsg, PollResponse,
-    PollStatus, PollsResponse, State, StateResponse, VoteOption, VoterInfo, VotersResponse,
-    VotersResponseItem,
+    polls, read_poll_voters, read_polls, CategoryParams, Config, ConfigResponse, Cw20HookMsg,
+    DepositSlashPolicy, ExecuteData, OrderBy, Poll, PollCategory, PollExecutable,
+    PollExecutablesResponse, PollExecuteMsg, PollResponse, PollStatus, PollType, PollsResponse,
+    QuorumMode, State, StateResponse, VoteOption, VoterInfo, VoterResponse, VoterRewardPool,
+    VotersResponse, VotersResponseItem, BANK, CONFIG, DELEGATED_SHARES, DELEGATIONS, POLL_VOTERS,
+    STATE, TMP_POLL_ID, VOTER_REWARD_POOLS,
+};
+use white_whale::pause::{
+    assert_not_paused, query_paused_scopes, update_paused_scopes, PauseScope,
 };
+use white_whale::treasury::msg::ExecuteMsg as TreasuryExecuteMsg;
 
 pub(crate) const MAX_QUORUM: Decimal = Decimal::one();
 pub(crate) const MAX_THRESHOLD: Decimal = Decimal::one();
@@ -30,6 +50,16 @@ pub(crate) const MIN_DESC_LENGTH: usize = 4;
 pub(crate) const MAX_DESC_LENGTH: usize = 1024;
 pub(crate) const MIN_LINK_LENGTH: usize = 12;
 pub(crate) const MAX_LINK_LENGTH: usize = 128;
+pub(crate) const MIN_METADATA_HASH_LENGTH: usize = 32;
+pub(crate) const MAX_METADATA_HASH_LENGTH: usize = 128;
+pub(crate) const MAX_POLL_EXECUTE_MSGS: usize = 16;
+pub(crate) const DEFAULT_PROTECTED_METHODS: &[&str] = &["register_contracts", "execute_poll_msgs"];
+
+pub(crate) const POLL_EXECUTE_REPLY_ID: u64 = 1;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:ww-governance";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -38,9 +68,34 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    validate_quorum(msg.quorum)?;
-    validate_threshold(msg.threshold)?;
-    validate_poll_period(msg.timelock_period, msg.expiration_period)?;
+    // Use CW2 to set the contract version, this is needed for migrations
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let deposit_slash_policy = msg
+        .deposit_slash_policy
+        .unwrap_or(DepositSlashPolicy::DistributeToStakers);
+    let treasury_addr = match msg.treasury_addr {
+        Some(addr) => {
+            validate_addr(deps.api, "treasury_addr", &addr)?;
+            Some(deps.api.addr_canonicalize(&addr)?)
+        }
+        None => None,
+    };
+    let voter_reward_ratio = msg.voter_reward_ratio.unwrap_or_else(Decimal::zero);
+
+    let protected_methods = msg
+        .protected_methods
+        .unwrap_or_else(default_protected_methods);
+
+    let category_params = msg.category_params.unwrap_or_default();
+
+    let guardian = match msg.guardian {
+        Some(guardian) => {
+            validate_addr(deps.api, "guardian", &guardian)?;
+            Some(deps.api.addr_canonicalize(&guardian)?)
+        }
+        None => None,
+    };
 
     let config = Config {
         whale_token: CanonicalAddr::from(vec![]),
@@ -52,7 +107,16 @@ pub fn instantiate(
         expiration_period: msg.expiration_period,
         proposal_deposit: msg.proposal_deposit,
         snapshot_period: msg.snapshot_period,
+        deposit_slash_policy,
+        treasury_addr,
+        quorum_mode: msg.quorum_mode.unwrap_or(QuorumMode::EndPoll),
+        voter_reward_ratio,
+        protected_methods,
+        category_params,
+        guardian,
+        native_proposal_deposit: msg.native_proposal_deposit,
     };
+    validate_config(&config)?;
 
     let state = State {
         contract_addr: deps.api.addr_canonicalize(env.contract.address.as_str())?,
@@ -60,16 +124,60 @@ pub fn instantiate(
         total_share: Uint128::zero(),
         total_deposit: Uint128::zero(),
     };
-    config_store(deps.storage).save(&config)?;
-    state_store(deps.storage).save(&state)?;
+    CONFIG.save(deps.storage, &config)?;
+    STATE.save(deps.storage, &state)?;
 
     Ok(Response::default())
 }
 
+/// Migrates the contract state in place. Contracts deployed before the contract version was tracked
+/// have no version stored and get their state migrated to the current layout first.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let version: Version = CONTRACT_VERSION.parse()?;
+    let storage_version: Option<Version> = match CONTRACT.may_load(deps.storage)? {
+        Some(contract_version) => {
+            if contract_version.contract != CONTRACT_NAME {
+                return Err(ContractError::InvalidMigrationContract(
+                    contract_version.contract,
+                    CONTRACT_NAME.to_string(),
+                ));
+            }
+            Some(contract_version.version.parse()?)
+        }
+        None => {
+            migrate_unversioned_state(deps.storage)?;
+            None
+        }
+    };
+
+    let indexed_polls_version: Version = INDEXED_POLLS_VERSION.parse()?;
+    if storage_version.as_ref().map_or(true, |storage_version| {
+        *storage_version < indexed_polls_version
+    }) {
+        migrate_poll_indexes(deps.storage)?;
+    }
+
+    if storage_version.map_or(true, |storage_version| storage_version < version) {
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(WhaleEvent::new(GOVERNANCE, "migrate")
+        .add_attribute("version", CONTRACT_VERSION)
+        .into_response())
+}
+
 // Routers; here is a separate router which handles Execution of functions on the contract or performs a contract Query
 // Each router function defines a number of handlers using Rust's pattern matching to
 // designated how each ExecutionMsg or QueryMsg will be handled.
 
+pub(crate) fn default_protected_methods() -> Vec<String> {
+    DEFAULT_PROTECTED_METHODS
+        .iter()
+        .map(|method| method.to_string())
+        .collect()
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -80,16 +188,52 @@ pub fn execute(
     match msg {
         // Handle 'payable' functionalities
         ExecuteMsg::Receive(msg) => receive_cw20(deps, _env, info, msg),
+        ExecuteMsg::CreatePoll {
+            title,
+            description,
+            link,
+            metadata_hash,
+            execute_msgs,
+            poll_type,
+            category,
+        } => create_native_poll(
+            deps,
+            _env,
+            info,
+            title,
+            description,
+            link,
+            metadata_hash,
+            execute_msgs,
+            poll_type,
+            category,
+        ),
         ExecuteMsg::CastVote {
             poll_id,
             vote,
             amount,
         } => cast_vote(deps, _env, info, poll_id, vote, amount),
+        ExecuteMsg::ChangeVote {
+            poll_id,
+            vote,
+            amount,
+        } => change_vote(deps, _env, info, poll_id, vote, amount),
         // Mark a poll as ended
         ExecuteMsg::EndPoll { poll_id } => end_poll(deps, _env, poll_id),
         // Execute the associated messages of a passed poll
         ExecuteMsg::ExecutePoll { poll_id } => execute_poll(deps, _env, poll_id),
+        ExecuteMsg::ExecutePollMsgs { poll_id } => execute_poll_msgs(deps, _env, info, poll_id),
+        ExecuteMsg::RetryExecution { poll_id } => retry_execution(deps, _env, poll_id),
         ExecuteMsg::ExpirePoll { poll_id } => expire_poll(deps, _env, poll_id),
+        ExecuteMsg::ExpirePolls { limit } => expire_polls(deps, _env, limit),
+        ExecuteMsg::CancelPoll { poll_id } => cancel_poll(deps, info, poll_id),
+        ExecuteMsg::VetoPoll { poll_id, reason } => veto_poll(deps, _env, info, poll_id, reason),
+        ExecuteMsg::PauseScopes { scopes } => set_paused_scopes(deps, info, scopes, true),
+        ExecuteMsg::UnpauseScopes { scopes } => set_paused_scopes(deps, info, scopes, false),
+        ExecuteMsg::DelegateVotingPower { delegate } => delegate_voting_power(deps, info, delegate),
+        ExecuteMsg::UndelegateVotingPower {} => undelegate_voting_power(deps, info),
+        ExecuteMsg::ClaimVotingRewards {} => claim_voting_rewards(deps, info),
+        ExecuteMsg::ClaimRewards {} => claim_rewards(deps, info),
         ExecuteMsg::RegisterContracts { whale_token } => register_contracts(deps, whale_token),
         ExecuteMsg::SnapshotPoll { poll_id } => snapshot_poll(deps, _env, poll_id),
         ExecuteMsg::WithdrawVotingTokens { amount } => withdraw_voting_tokens(deps, info, amount),
@@ -102,8 +246,17 @@ pub fn execute(
             expiration_period,
             proposal_deposit,
             snapshot_period,
+            deposit_slash_policy,
+            treasury_addr,
+            quorum_mode,
+            voter_reward_ratio,
+            protected_methods,
+            category_params,
+            guardian,
+            native_proposal_deposit,
         } => update_config(
             deps,
+            _env,
             info,
             owner,
             quorum,
@@ -113,6 +266,14 @@ pub fn execute(
             expiration_period,
             proposal_deposit,
             snapshot_period,
+            deposit_slash_policy,
+            treasury_addr,
+            quorum_mode,
+            voter_reward_ratio,
+            protected_methods,
+            category_params,
+            guardian,
+            native_proposal_deposit,
         ),
     }
 }
@@ -122,7 +283,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
     match msg {
         QueryMsg::Config {} => Ok(to_binary(&query_config(deps)?)?),
         QueryMsg::State {} => Ok(to_binary(&query_state(deps)?)?),
+        QueryMsg::PausedScopes {} => Ok(to_binary(&query_paused_scopes(deps.storage)?)?),
         QueryMsg::Staker { address } => Ok(to_binary(&query_staker(deps, address)?)?),
+        QueryMsg::PendingVotingRewards { address } => {
+            Ok(to_binary(&query_pending_voting_rewards(deps, address)?)?)
+        }
+        QueryMsg::StakerRewards { address } => {
+            Ok(to_binary(&query_staker_rewards(deps, address)?)?)
+        }
         QueryMsg::Poll { poll_id } => Ok(to_binary(&query_poll(deps, poll_id)?)?),
         QueryMsg::Polls {
             filter,
@@ -148,19 +316,38 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
             limit,
             order_by,
         )?)?),
+        QueryMsg::Voter { poll_id, address } => {
+            Ok(to_binary(&query_voter(deps, poll_id, address)?)?)
+        }
+        QueryMsg::PollExecutables { poll_id } => {
+            Ok(to_binary(&query_poll_executables(deps, poll_id)?)?)
+        }
+        QueryMsg::Delegations {
+            delegator,
+            delegate,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_delegations(
+            deps,
+            delegator,
+            delegate,
+            start_after,
+            limit,
+        )?)?),
     }
 }
 
 // ExecutionMsg handlers
 
 pub fn register_contracts(deps: DepsMut, whale_token: String) -> Result<Response, ContractError> {
-    let mut config: Config = config_read(deps.storage).load()?;
+    let mut config: Config = CONFIG.load(deps.storage)?;
     if config.whale_token != CanonicalAddr::from(vec![]) {
         return Err(ContractError::Unauthorized {});
     }
 
+    validate_addr(deps.api, "whale_token", &whale_token)?;
     config.whale_token = deps.api.addr_canonicalize(&whale_token)?;
-    config_store(deps.storage).save(&config)?;
+    CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::default())
 }
@@ -174,7 +361,7 @@ pub fn receive_cw20(
     cw20_msg: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
     // only asset contract can execute this message
-    let config: Config = config_read(deps.storage).load()?;
+    let config: Config = CONFIG.load(deps.storage)?;
     if config.whale_token != deps.api.addr_canonicalize(info.sender.as_str())? {
         return Err(ContractError::Unauthorized {});
     }
@@ -188,57 +375,89 @@ pub fn receive_cw20(
             title,
             description,
             link,
+            metadata_hash,
             execute_msgs,
+            poll_type,
+            category,
         }) => create_poll(
             deps,
             env,
             cw20_msg.sender,
             cw20_msg.amount,
+            None,
             title,
             description,
             link,
+            metadata_hash,
             execute_msgs,
+            poll_type,
+            category,
         ),
+        Ok(Cw20HookMsg::CreateWhitelistPoll { dapp_addr }) => {
+            create_whitelist_poll(deps, env, cw20_msg.sender, cw20_msg.amount, dapp_addr)
+        }
+        Ok(Cw20HookMsg::DistributeRewards {}) => {
+            distribute_rewards(deps, cw20_msg.sender, cw20_msg.amount)
+        }
         _ => Err(ContractError::DataShouldBeGiven {}),
     }
 }
 
-/// create a new poll
+/// create a new poll, funded with a native deposit when `deposit_denom` is set and WHALE otherwise
 #[allow(clippy::too_many_arguments)]
 pub fn create_poll(
     deps: DepsMut,
     env: Env,
     proposer: String,
     deposit_amount: Uint128,
+    deposit_denom: Option<String>,
     title: String,
     description: String,
     link: Option<String>,
+    metadata_hash: Option<String>,
     execute_msgs: Option<Vec<PollExecuteMsg>>,
+    poll_type: Option<PollType>,
+    category: Option<PollCategory>,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, PauseScope::Polls)?;
     validate_poll_title(&title)?;
     validate_poll_description(&description)?;
     validate_poll_link(&link)?;
-
-    let config: Config = config_store(deps.storage).load()?;
-    if deposit_amount < config.proposal_deposit {
+    validate_poll_metadata_hash(&metadata_hash)?;
+    let poll_type = validate_poll_type(poll_type, &execute_msgs)?;
+    let category = validate_poll_category(category, &poll_type)?;
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let params = config.poll_params(&category);
+    // the minimum of native deposits is checked by create_native_poll
+    if deposit_denom.is_none() && deposit_amount < params.proposal_deposit {
         return Err(ContractError::InsufficientProposalDeposit(
-            config.proposal_deposit.u128(),
+            params.proposal_deposit.u128(),
         ));
     }
 
-    let mut state: State = state_store(deps.storage).load()?;
+    let mut state: State = STATE.load(deps.storage)?;
     let poll_id = state.poll_count + 1;
 
-    // Increase poll count & total deposit amount
+    // Increase poll count & total deposit amount, native deposits are not part of the WHALE balance
     state.poll_count += 1;
-    state.total_deposit += deposit_amount;
+    if deposit_denom.is_none() {
+        state.total_deposit += deposit_amount;
+    }
 
     let mut data_list: Vec<ExecuteData> = vec![];
-    let all_execute_data = if let Some(exe_msgs) = execute_msgs {
+    let all_execute_data = if poll_type == PollType::Text {
+        None
+    } else if let Some(exe_msgs) = execute_msgs {
+        validate_poll_execute_msgs(&exe_msgs)?;
         for msgs in exe_msgs {
+            let contract = deps.api.addr_canonicalize(&msgs.contract)?;
+            if contract == state.contract_addr {
+                validate_self_execute_msg(&msgs.msg, &config.protected_methods)?;
+            }
             let execute_data = ExecuteData {
                 order: msgs.order,
-                contract: deps.api.addr_canonicalize(&msgs.contract)?,
+                contract,
                 msg: msgs.msg,
             };
             data_list.push(execute_data)
@@ -248,6 +467,21 @@ pub fn create_poll(
         None
     };
 
+    // Record the staked balance now so late stakers don't change the quorum denominator.
+    // The deposit of this poll was already transferred to the contract and is excluded.
+    let staked_amount_at_creation = if config.quorum_mode == QuorumMode::PollCreation {
+        Some(
+            query_token_balance(
+                &deps.querier,
+                deps.api.addr_humanize(&config.whale_token)?,
+                deps.api.addr_humanize(&state.contract_addr)?,
+            )?
+            .checked_sub(state.total_deposit)?,
+        )
+    } else {
+        None
+    };
+
     let sender_address_raw = deps.api.addr_canonicalize(&proposer)?;
     let new_poll = Poll {
         id: poll_id,
@@ -255,34 +489,126 @@ pub fn create_poll(
         status: PollStatus::InProgress,
         yes_votes: Uint128::zero(),
         no_votes: Uint128::zero(),
-        end_height: env.block.height + config.voting_period,
+        abstain_votes: Uint128::zero(),
+        end_height: env.block.height + params.voting_period,
         title,
         description,
         link,
+        metadata_hash,
         execute_data: all_execute_data,
         deposit_amount,
         total_balance_at_end_poll: None,
         staked_amount: None,
+        staked_amount_at_creation,
+        poll_type,
+        category,
+        deposit_denom,
     };
 
-    poll_store(deps.storage).save(&poll_id.to_be_bytes(), &new_poll)?;
-    poll_indexer_store(deps.storage, &PollStatus::InProgress)
-        .save(&poll_id.to_be_bytes(), &true)?;
+    polls().save(deps.storage, U64Key::new(poll_id), &new_poll)?;
 
-    state_store(deps.storage).save(&state)?;
+    STATE.save(deps.storage, &state)?;
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "create_poll"),
-        (
+    Ok(WhaleEvent::new(GOVERNANCE, "create_poll")
+        .add_attribute(
             "creator",
             deps.api
                 .addr_humanize(&new_poll.creator)?
                 .to_string()
                 .as_str(),
-        ),
-        ("poll_id", &poll_id.to_string()),
-        ("end_height", new_poll.end_height.to_string().as_str()),
-    ]))
+        )
+        .add_attribute("poll_id", poll_id.to_string())
+        .add_attribute("end_height", new_poll.end_height.to_string())
+        .into_response())
+}
+
+/// create a new poll funded with the native proposal deposit sent along with the message, so polls
+/// can still be created when WHALE transfers are not possible
+#[allow(clippy::too_many_arguments)]
+pub fn create_native_poll(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    link: Option<String>,
+    metadata_hash: Option<String>,
+    execute_msgs: Option<Vec<PollExecuteMsg>>,
+    poll_type: Option<PollType>,
+    category: Option<PollCategory>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let native_deposit = config
+        .native_proposal_deposit
+        .ok_or(ContractError::NoNativeProposalDeposit {})?;
+
+    let deposit_amount = match info.funds.as_slice() {
+        [coin] if coin.denom == native_deposit.denom => coin.amount,
+        _ => {
+            return Err(ContractError::InvalidNativeProposalDeposit(
+                native_deposit.denom,
+            ))
+        }
+    };
+    if deposit_amount < native_deposit.amount {
+        return Err(ContractError::InsufficientProposalDeposit(
+            native_deposit.amount.u128(),
+        ));
+    }
+
+    create_poll(
+        deps,
+        env,
+        info.sender.to_string(),
+        deposit_amount,
+        Some(native_deposit.denom),
+        title,
+        description,
+        link,
+        metadata_hash,
+        execute_msgs,
+        poll_type,
+        category,
+    )
+}
+
+/// create a poll whitelisting a dapp on the treasury once executed
+pub fn create_whitelist_poll(
+    deps: DepsMut,
+    env: Env,
+    proposer: String,
+    deposit_amount: Uint128,
+    dapp_addr: String,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, PauseScope::Polls)?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    let treasury_addr = config
+        .treasury_addr
+        .ok_or(ContractError::NoTreasuryToWhitelistIn {})?;
+    let dapp = deps.api.addr_validate(&dapp_addr)?;
+
+    let execute_msgs = vec![PollExecuteMsg {
+        order: 1u64,
+        contract: deps.api.addr_humanize(&treasury_addr)?.to_string(),
+        msg: to_binary(&TreasuryExecuteMsg::AddDApp {
+            dapp: dapp.to_string(),
+        })?,
+    }];
+
+    create_poll(
+        deps,
+        env,
+        proposer,
+        deposit_amount,
+        None,
+        "Whitelist treasury dapp".to_string(),
+        format!("Adds {} to the whitelisted dapps of the treasury", dapp),
+        None,
+        None,
+        Some(execute_msgs),
+        Some(PollType::Executable),
+        Some(PollCategory::Whitelist),
+    )
 }
 
 /// end a poll
@@ -290,7 +616,7 @@ pub fn create_poll(
 /// By default a Poll is considered rejected when ending. The weight of votes and the quorum of the vote is considered before declaring a Poll as passed.
 /// Before the function completes, state is saved any leftover deposit amount is sent back to the poll creator and a response is returned.
 pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, ContractError> {
-    let mut a_poll: Poll = poll_store(deps.storage).load(&poll_id.to_be_bytes())?;
+    let mut a_poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
 
     if a_poll.status != PollStatus::InProgress {
         return Err(ContractError::PollNotInProgress {});
@@ -302,8 +628,11 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
 
     let no = a_poll.no_votes.u128();
     let yes = a_poll.yes_votes.u128();
+    let abstain = a_poll.abstain_votes.u128();
 
-    let tallied_weight = yes + no;
+    // Abstain votes count toward the quorum but not toward the threshold
+    let tallied_weight = yes + no + abstain;
+    let decisive_weight = yes + no;
 
     let mut poll_status = PollStatus::Rejected;
     #[allow(unused_mut)]
@@ -311,16 +640,18 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
     let mut passed = false;
 
     let mut messages: Vec<CosmosMsg> = vec![];
-    let config: Config = config_read(deps.storage).load()?;
-    let mut state: State = state_read(deps.storage).load()?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    let params = config.poll_params(&a_poll.category);
+    let mut state: State = STATE.load(deps.storage)?;
 
     let (quorum, staked_weight) = if state.total_share.u128() == 0 {
         (Decimal::zero(), Uint128::zero())
     } else if let Some(staked_amount) = a_poll.staked_amount {
-        (
-            Decimal::from_ratio(tallied_weight, staked_amount),
-            staked_amount,
-        )
+        (quorum_ratio(tallied_weight, staked_amount), staked_amount)
+    } else if let (QuorumMode::PollCreation, Some(staked_amount)) =
+        (&config.quorum_mode, a_poll.staked_amount_at_creation)
+    {
+        (quorum_ratio(tallied_weight, staked_amount), staked_amount)
     } else {
         let staked_weight = query_token_balance(
             &deps.querier,
@@ -329,21 +660,81 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
         )?
         .checked_sub(state.total_deposit)?;
 
-        (
-            Decimal::from_ratio(tallied_weight, staked_weight),
-            staked_weight,
-        )
+        (quorum_ratio(tallied_weight, staked_weight), staked_weight)
     };
 
-    if tallied_weight == 0 || quorum < config.quorum {
+    let mut voter_reward = Uint128::zero();
+    if tallied_weight == 0 || quorum < params.quorum {
         // Quorum: More than quorum of the total staked tokens at the end of the voting
         // period need to have participated in the vote.
         rejected_reason = "Quorum not reached";
+
+        // Part of the deposit is kept as reward for the voters of the poll, voter rewards are paid
+        // in WHALE so native deposits are not shared with the voters
+        if tallied_weight != 0 && a_poll.deposit_denom.is_none() {
+            voter_reward = a_poll.deposit_amount * config.voter_reward_ratio;
+        }
+        if !voter_reward.is_zero() {
+            VOTER_REWARD_POOLS.save(
+                deps.storage,
+                U64Key::new(poll_id),
+                &VoterRewardPool {
+                    reward: voter_reward,
+                    total_weight: Uint128::from(tallied_weight),
+                },
+            )?;
+        }
+
+        // Slash the rest of the deposit according to the configured policy
+        let slashed_amount = a_poll.deposit_amount.checked_sub(voter_reward)?;
+        if !slashed_amount.is_zero() {
+            match config.deposit_slash_policy {
+                DepositSlashPolicy::Burn => messages.push(deposit_burn_msg(
+                    deps.as_ref(),
+                    &config,
+                    &a_poll,
+                    slashed_amount,
+                )?),
+                DepositSlashPolicy::SendToTreasury => {
+                    let treasury_addr = config
+                        .treasury_addr
+                        .as_ref()
+                        .ok_or(ContractError::NoTreasuryAddr {})?;
+                    messages.push(deposit_transfer_msg(
+                        deps.as_ref(),
+                        &config,
+                        &a_poll,
+                        slashed_amount,
+                        deps.api.addr_humanize(treasury_addr)?,
+                    )?)
+                }
+                // Native deposits can't be shared with the stakers, they go to the treasury if
+                // there is one and are burned otherwise
+                DepositSlashPolicy::DistributeToStakers if a_poll.deposit_denom.is_some() => {
+                    messages.push(match &config.treasury_addr {
+                        Some(treasury_addr) => deposit_transfer_msg(
+                            deps.as_ref(),
+                            &config,
+                            &a_poll,
+                            slashed_amount,
+                            deps.api.addr_humanize(treasury_addr)?,
+                        )?,
+                        None => deposit_burn_msg(deps.as_ref(), &config, &a_poll, slashed_amount)?,
+                    })
+                }
+                // The deposit stays in the contract and is no longer accounted as deposit
+                DepositSlashPolicy::DistributeToStakers => {}
+            }
+        }
     } else {
-        if Decimal::from_ratio(yes, tallied_weight) > config.threshold {
+        if decisive_weight != 0 && Decimal::from_ratio(yes, decisive_weight) > params.threshold {
             //Threshold: More than 50% of the tokens that participated in the vote
             // (after excluding “Abstain” votes) need to have voted in favor of the proposal (“Yes”).
-            poll_status = PollStatus::Passed;
+            // Text polls have nothing to execute, so their lifecycle ends here
+            poll_status = match a_poll.poll_type {
+                PollType::Text => PollStatus::Executed,
+                PollType::Executable => PollStatus::Passed,
+            };
             rejected_reason = "Poll Passed";
             passed = true;
         } else {
@@ -352,51 +743,51 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
 
         // Refunds deposit only when quorum is reached
         if !a_poll.deposit_amount.is_zero() {
-            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: deps.api.addr_humanize(&config.whale_token)?.to_string(),
-                funds: vec![],
-                msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: deps.api.addr_humanize(&a_poll.creator)?.to_string(),
-                    amount: a_poll.deposit_amount,
-                })?,
-            }))
+            messages.push(deposit_transfer_msg(
+                deps.as_ref(),
+                &config,
+                &a_poll,
+                a_poll.deposit_amount,
+                deps.api.addr_humanize(&a_poll.creator)?,
+            )?)
         }
     }
 
-    // Decrease total deposit amount
-    state.total_deposit = state.total_deposit.checked_sub(a_poll.deposit_amount)?;
-    state_store(deps.storage).save(&state)?;
-
-    // Update poll indexer
-    poll_indexer_store(deps.storage, &PollStatus::InProgress).remove(&a_poll.id.to_be_bytes());
-    poll_indexer_store(deps.storage, &poll_status).save(&a_poll.id.to_be_bytes(), &true)?;
+    // Decrease total deposit amount, voter rewards stay out of the staked balance until claimed
+    if a_poll.deposit_denom.is_none() {
+        state.total_deposit = state
+            .total_deposit
+            .checked_sub(a_poll.deposit_amount.checked_sub(voter_reward)?)?;
+        STATE.save(deps.storage, &state)?;
+    }
 
     // Update poll status
     a_poll.status = poll_status;
     a_poll.total_balance_at_end_poll = Some(staked_weight);
-    poll_store(deps.storage).save(&poll_id.to_be_bytes(), &a_poll)?;
-
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        ("action", "end_poll"),
-        ("poll_id", &poll_id.to_string()),
-        ("rejected_reason", rejected_reason),
-        ("passed", &passed.to_string()),
-    ]))
+    polls().save(deps.storage, U64Key::new(poll_id), &a_poll)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(
+        WhaleEvent::new(GOVERNANCE, "end_poll")
+            .add_attribute("poll_id", poll_id.to_string())
+            .add_attribute("rejected_reason", rejected_reason)
+            .add_attribute("passed", passed.to_string()),
+    ))
 }
 
 /// execute_poll exposes the ability to execute the Messages which were defined on Polls creation if the Poll was deemed successful.
 ///
 /// The fn first performs a number of checks to ensure the Poll indeed has passed and enough of an effective delay has elapsed
 /// for the Messages to be executed. Provided these conditions are met the poll is declared in a Executed state
-/// and the execution data that was provided when the poll was created is prepared as a number of CosmosMsg/WasmMsg(s) before being sent for execution.
+/// and the execution data that was provided when the poll was created is executed through a submessage to the contract itself.
+/// If any of the messages fails the poll is moved to the FailedExecution state and can be retried with RetryExecution.
 ///
 ///
 /// It is important to note that execute poll only handles the execution of predefined messages
 /// which are associated with a Passed poll. This ensures the actions taken by a successful Poll are
 /// well known and predefined.
 pub fn execute_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, ContractError> {
-    let config: Config = config_read(deps.storage).load()?;
-    let mut a_poll: Poll = poll_store(deps.storage).load(&poll_id.to_be_bytes())?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut a_poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
 
     if a_poll.status != PollStatus::Passed {
         return Err(ContractError::PollNotPassed {});
@@ -406,11 +797,84 @@ pub fn execute_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, C
         return Err(ContractError::TimelockNotExpired {});
     }
 
-    poll_indexer_store(deps.storage, &PollStatus::Passed).remove(&poll_id.to_be_bytes());
-    poll_indexer_store(deps.storage, &PollStatus::Executed).save(&poll_id.to_be_bytes(), &true)?;
+    let execute_poll_msgs = dispatch_poll_execution(deps.storage, &env, &mut a_poll)?;
 
-    a_poll.status = PollStatus::Executed;
-    poll_store(deps.storage).save(&poll_id.to_be_bytes(), &a_poll)?;
+    Ok(Response::new()
+        .add_submessage(execute_poll_msgs)
+        .add_attributes(
+            WhaleEvent::new(GOVERNANCE, "execute_poll")
+                .add_attribute("poll_id", poll_id.to_string()),
+        ))
+}
+
+/// Retries the execution of a poll whose messages failed to execute, as long as the poll has not
+/// reached its expiration period.
+pub fn retry_execution(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut a_poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
+
+    if a_poll.status != PollStatus::FailedExecution {
+        return Err(ContractError::PollNotFailed {});
+    }
+
+    if a_poll.end_height + config.expiration_period <= env.block.height {
+        return Err(ContractError::PollExecutionExpired {});
+    }
+
+    let execute_poll_msgs = dispatch_poll_execution(deps.storage, &env, &mut a_poll)?;
+
+    Ok(Response::new()
+        .add_submessage(execute_poll_msgs)
+        .add_attributes(
+            WhaleEvent::new(GOVERNANCE, "retry_execution")
+                .add_attribute("poll_id", poll_id.to_string()),
+        ))
+}
+
+/// Marks the poll as executed and returns the submessage executing its messages. The messages run in
+/// a call to the contract itself so they succeed or fail together; a failure is caught by [reply],
+/// which moves the poll to the failed_execution status instead of reverting the transaction.
+fn dispatch_poll_execution(
+    storage: &mut dyn Storage,
+    env: &Env,
+    a_poll: &mut Poll,
+) -> Result<SubMsg, ContractError> {
+    if a_poll.execute_data.is_none() {
+        return Err(ContractError::NoExecuteData {});
+    }
+
+    update_poll_status(storage, a_poll, PollStatus::Executed)?;
+    TMP_POLL_ID.save(storage, &a_poll.id)?;
+
+    Ok(SubMsg::reply_on_error(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::ExecutePollMsgs { poll_id: a_poll.id })?,
+            funds: vec![],
+        }),
+        POLL_EXECUTE_REPLY_ID,
+    ))
+}
+
+/// Returns the messages of an executed poll, sorted by their order. Only the contract itself may
+/// call it, as part of [execute_poll] or [retry_execution].
+pub fn execute_poll_msgs(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Only the poll dispatched by execute_poll or retry_execution, so a poll's messages can't
+    // execute another poll
+    let a_poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
+    if a_poll.status != PollStatus::Executed || TMP_POLL_ID.may_load(deps.storage)? != Some(poll_id)
+    {
+        return Err(ContractError::PollNotExecuting {});
+    }
 
     let mut messages: Vec<CosmosMsg> = vec![];
     if let Some(all_msgs) = a_poll.execute_data {
@@ -427,10 +891,30 @@ pub fn execute_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, C
         return Err(ContractError::NoExecuteData {});
     }
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        ("action", "execute_poll"),
-        ("poll_id", poll_id.to_string().as_str()),
-    ]))
+    Ok(Response::new().add_messages(messages).add_attributes(
+        WhaleEvent::new(GOVERNANCE, "execute_poll_msgs")
+            .add_attribute("poll_id", poll_id.to_string()),
+    ))
+}
+
+/// Handles the failed execution of a poll's messages by moving the poll to the failed_execution
+/// status, from which the execution can be retried.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != POLL_EXECUTE_REPLY_ID {
+        return Err(ContractError::InvalidReplyId(msg.id));
+    }
+
+    let poll_id = TMP_POLL_ID.load(deps.storage)?;
+    let mut a_poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
+    update_poll_status(deps.storage, &mut a_poll, PollStatus::FailedExecution)?;
+
+    let error = msg.result.into_result().err().unwrap_or_default();
+
+    Ok(WhaleEvent::new(GOVERNANCE, "failed_poll_execution")
+        .add_attribute("poll_id", poll_id.to_string())
+        .add_attribute("error", error.as_str())
+        .into_response())
 }
 
 // Voting
@@ -451,28 +935,40 @@ pub fn cast_vote(
     vote: VoteOption,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, PauseScope::Polls)?;
     let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
-    let config = config_read(deps.storage).load()?;
-    let state = state_read(deps.storage).load()?;
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
     if poll_id == 0 || state.poll_count < poll_id {
         return Err(ContractError::PollNotFound {});
     }
 
-    let mut a_poll: Poll = poll_store(deps.storage).load(&poll_id.to_be_bytes())?;
+    let mut a_poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
     if a_poll.status != PollStatus::InProgress || env.block.height > a_poll.end_height {
         return Err(ContractError::PollNotInProgress {});
     }
 
     // Check the voter already has a vote on the poll
-    if poll_voter_read(deps.storage, poll_id)
-        .load(sender_address_raw.as_slice())
+    if POLL_VOTERS
+        .load(
+            deps.storage,
+            (U64Key::new(poll_id), sender_address_raw.as_slice()),
+        )
         .is_ok()
     {
         return Err(ContractError::AlreadyVoted {});
     }
 
-    let key = &sender_address_raw.as_slice();
-    let mut token_manager = bank_read(deps.storage).may_load(key)?.unwrap_or_default();
+    let key = sender_address_raw.as_slice();
+    if DELEGATIONS.may_load(deps.storage, key)?.is_some() {
+        return Err(ContractError::VotingPowerDelegated {});
+    }
+
+    let mut token_manager = BANK.may_load(deps.storage, key)?.unwrap_or_default();
+    // the voting weight includes the share delegated to the voter
+    let delegated_share = DELEGATED_SHARES
+        .may_load(deps.storage, key)?
+        .unwrap_or_default();
 
     // convert share to amount
     let total_share = state.total_share;
@@ -483,32 +979,33 @@ pub fn cast_vote(
     )?
     .checked_sub(state.total_deposit)?;
 
-    if token_manager
-        .share
-        .multiply_ratio(total_balance, total_share)
-        < amount
-    {
+    if (token_manager.share + delegated_share).multiply_ratio(total_balance, total_share) < amount {
         return Err(ContractError::InsufficientStaked {});
     }
 
     // update tally info
-    if VoteOption::Yes == vote {
-        a_poll.yes_votes += amount;
-    } else {
-        a_poll.no_votes += amount;
+    match vote {
+        VoteOption::Yes => a_poll.yes_votes += amount,
+        VoteOption::No => a_poll.no_votes += amount,
+        VoteOption::Abstain => a_poll.abstain_votes += amount,
     }
 
     let vote_info = VoterInfo {
         vote,
         balance: amount,
     };
+    prune_locked_balance(deps.storage, &mut token_manager, &sender_address_raw)?;
     token_manager
         .locked_balance
         .push((poll_id, vote_info.clone()));
-    bank_store(deps.storage).save(key, &token_manager)?;
+    BANK.save(deps.storage, key, &token_manager)?;
 
     // store poll voter && and update poll data
-    poll_voter_store(deps.storage, poll_id).save(sender_address_raw.as_slice(), &vote_info)?;
+    POLL_VOTERS.save(
+        deps.storage,
+        (U64Key::new(poll_id), sender_address_raw.as_slice()),
+        &vote_info,
+    )?;
 
     // processing snapshot
     let time_to_end = a_poll.end_height - env.block.height;
@@ -517,21 +1014,114 @@ pub fn cast_vote(
         a_poll.staked_amount = Some(total_balance);
     }
 
-    poll_store(deps.storage).save(&poll_id.to_be_bytes(), &a_poll)?;
+    polls().save(deps.storage, U64Key::new(poll_id), &a_poll)?;
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "cast_vote"),
-        ("poll_id", poll_id.to_string().as_str()),
-        ("amount", amount.to_string().as_str()),
-        ("voter", info.sender.as_str()),
-        ("vote_option", vote_info.vote.to_string().as_str()),
-    ]))
+    Ok(WhaleEvent::new(GOVERNANCE, "cast_vote")
+        .add_attribute("poll_id", poll_id.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("voter", info.sender.as_str())
+        .add_attribute("vote_option", vote_info.vote.to_string())
+        .into_response())
+}
+
+/// change_vote replaces the vote of a voter on a poll in progress. The previous vote is taken out of
+/// the tally and the new one added, so votes can only be changed before the snapshot period starts.
+pub fn change_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+    vote: VoteOption,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, PauseScope::Polls)?;
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    let mut a_poll: Poll = match polls().may_load(deps.storage, U64Key::new(poll_id))? {
+        Some(poll) => poll,
+        None => return Err(ContractError::PollNotFound {}),
+    };
+    if a_poll.status != PollStatus::InProgress || env.block.height > a_poll.end_height {
+        return Err(ContractError::PollNotInProgress {});
+    }
+
+    if a_poll.staked_amount.is_some()
+        || a_poll.end_height - env.block.height < config.snapshot_period
+    {
+        return Err(ContractError::VoteChangeSnapshotPeriod {});
+    }
+
+    let previous_vote = match POLL_VOTERS.may_load(
+        deps.storage,
+        (U64Key::new(poll_id), sender_address_raw.as_slice()),
+    )? {
+        Some(voter_info) => voter_info,
+        None => return Err(ContractError::NotVoted {}),
+    };
+
+    let key = sender_address_raw.as_slice();
+    let mut token_manager = BANK.may_load(deps.storage, key)?.unwrap_or_default();
+    let delegated_share = DELEGATED_SHARES
+        .may_load(deps.storage, key)?
+        .unwrap_or_default();
+
+    let total_balance = query_token_balance(
+        &deps.querier,
+        deps.api.addr_humanize(&config.whale_token)?,
+        deps.api.addr_humanize(&state.contract_addr)?,
+    )?
+    .checked_sub(state.total_deposit)?;
+
+    if (token_manager.share + delegated_share).multiply_ratio(total_balance, state.total_share)
+        < amount
+    {
+        return Err(ContractError::InsufficientStaked {});
+    }
+
+    // move the weight from the previous vote to the new one
+    match previous_vote.vote {
+        VoteOption::Yes => {
+            a_poll.yes_votes = a_poll.yes_votes.checked_sub(previous_vote.balance)?
+        }
+        VoteOption::No => a_poll.no_votes = a_poll.no_votes.checked_sub(previous_vote.balance)?,
+        VoteOption::Abstain => {
+            a_poll.abstain_votes = a_poll.abstain_votes.checked_sub(previous_vote.balance)?
+        }
+    }
+    match vote {
+        VoteOption::Yes => a_poll.yes_votes += amount,
+        VoteOption::No => a_poll.no_votes += amount,
+        VoteOption::Abstain => a_poll.abstain_votes += amount,
+    }
+
+    let vote_info = VoterInfo {
+        vote,
+        balance: amount,
+    };
+    for (locked_poll_id, locked_vote_info) in token_manager.locked_balance.iter_mut() {
+        if *locked_poll_id == poll_id {
+            *locked_vote_info = vote_info.clone();
+        }
+    }
+    BANK.save(deps.storage, key, &token_manager)?;
+
+    POLL_VOTERS.save(deps.storage, (U64Key::new(poll_id), key), &vote_info)?;
+    polls().save(deps.storage, U64Key::new(poll_id), &a_poll)?;
+
+    Ok(WhaleEvent::new(GOVERNANCE, "change_vote")
+        .add_attribute("poll_id", poll_id.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("voter", info.sender.as_str())
+        .add_attribute("vote_option", vote_info.vote.to_string())
+        .into_response())
 }
 
 /// ExpirePoll is used to make the poll as expired state for querying purpose
 pub fn expire_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, ContractError> {
-    let config: Config = config_read(deps.storage).load()?;
-    let mut a_poll: Poll = poll_store(deps.storage).load(&poll_id.to_be_bytes())?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut a_poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
 
     if a_poll.status != PollStatus::Passed {
         return Err(ContractError::PollNotPassed {});
@@ -545,16 +1135,214 @@ pub fn expire_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Co
         return Err(ContractError::PollNotExpired {});
     }
 
-    poll_indexer_store(deps.storage, &PollStatus::Passed).remove(&poll_id.to_be_bytes());
-    poll_indexer_store(deps.storage, &PollStatus::Expired).save(&poll_id.to_be_bytes(), &true)?;
+    update_poll_status(deps.storage, &mut a_poll, PollStatus::Expired)?;
+
+    Ok(WhaleEvent::new(GOVERNANCE, "expire_poll")
+        .add_attribute("poll_id", poll_id.to_string())
+        .into_response())
+}
+
+/// ExpirePolls expires, in one go, the oldest passed polls whose expiration period is over.
+/// Text polls are skipped as they have nothing to expire.
+pub fn expire_polls(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let passed_polls = read_polls(
+        deps.storage,
+        Some(PollStatus::Passed),
+        None,
+        limit,
+        Some(OrderBy::Asc),
+    )?;
+
+    let mut expired_poll_ids: Vec<String> = vec![];
+    for mut a_poll in passed_polls {
+        if a_poll.execute_data.is_none()
+            || a_poll.end_height + config.expiration_period > env.block.height
+        {
+            continue;
+        }
+
+        update_poll_status(deps.storage, &mut a_poll, PollStatus::Expired)?;
+        expired_poll_ids.push(a_poll.id.to_string());
+    }
+
+    Ok(WhaleEvent::new(GOVERNANCE, "expire_polls")
+        .add_attribute("poll_ids", expired_poll_ids.join(",").as_str())
+        .into_response())
+}
+
+/// cancel_poll allows the creator of a poll to withdraw it while it is in progress and nobody voted on it yet.
+/// The proposal deposit is refunded to the creator.
+pub fn cancel_poll(
+    deps: DepsMut,
+    info: MessageInfo,
+    poll_id: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut a_poll: Poll = match polls().may_load(deps.storage, U64Key::new(poll_id))? {
+        Some(poll) => poll,
+        None => return Err(ContractError::PollNotFound {}),
+    };
+
+    if a_poll.creator != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if a_poll.status != PollStatus::InProgress {
+        return Err(ContractError::PollNotInProgress {});
+    }
+
+    let has_voters = !read_poll_voters(deps.storage, poll_id, None, Some(1), None)?.is_empty();
+    if has_voters || !(a_poll.yes_votes + a_poll.no_votes + a_poll.abstain_votes).is_zero() {
+        return Err(ContractError::PollHasVotes {});
+    }
+
+    if a_poll.deposit_denom.is_none() {
+        let mut state: State = STATE.load(deps.storage)?;
+        state.total_deposit = state.total_deposit.checked_sub(a_poll.deposit_amount)?;
+        STATE.save(deps.storage, &state)?;
+    }
+
+    update_poll_status(deps.storage, &mut a_poll, PollStatus::Canceled)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !a_poll.deposit_amount.is_zero() {
+        messages.push(deposit_transfer_msg(
+            deps.as_ref(),
+            &config,
+            &a_poll,
+            a_poll.deposit_amount,
+            info.sender,
+        )?)
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(
+        WhaleEvent::new(GOVERNANCE, "cancel_poll").add_attribute("poll_id", poll_id.to_string()),
+    ))
+}
+
+/// veto_poll allows the guardian to stop a passed poll from being executed while it is in its
+/// timelock period, acting as a circuit breaker against malicious proposals. The poll is moved to
+/// the vetoed status and the reason is logged.
+pub fn veto_poll(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+    reason: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.guardian != Some(deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut a_poll: Poll = match polls().may_load(deps.storage, U64Key::new(poll_id))? {
+        Some(poll) => poll,
+        None => return Err(ContractError::PollNotFound {}),
+    };
+
+    if a_poll.status != PollStatus::Passed {
+        return Err(ContractError::PollNotPassed {});
+    }
+
+    if a_poll.end_height + config.timelock_period <= env.block.height {
+        return Err(ContractError::TimelockExpired {});
+    }
+
+    update_poll_status(deps.storage, &mut a_poll, PollStatus::Vetoed)?;
+
+    Ok(WhaleEvent::new(GOVERNANCE, "veto_poll")
+        .add_attribute("poll_id", poll_id.to_string())
+        .add_attribute("reason", reason)
+        .into_response())
+}
+
+/// set_paused_scopes pauses or unpauses function scopes of the contract. Both the owner and the
+/// guardian can call it, as an owner set to this contract itself could not unpause the polls scope
+/// through a poll.
+pub fn set_paused_scopes(
+    deps: DepsMut,
+    info: MessageInfo,
+    scopes: Vec<PauseScope>,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if config.owner != sender && config.guardian != Some(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(update_paused_scopes(deps.storage, scopes, paused)?)
+}
+
+/// Share of the staked weight that voted, an empty stake never reaches quorum
+fn quorum_ratio(tallied_weight: u128, staked_weight: Uint128) -> Decimal {
+    if staked_weight.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(tallied_weight, staked_weight)
+    }
+}
+
+/// Moves a poll to a new status, keeping the poll indexer in sync
+fn update_poll_status(
+    storage: &mut dyn Storage,
+    a_poll: &mut Poll,
+    status: PollStatus,
+) -> StdResult<()> {
+    a_poll.status = status;
+    polls().save(storage, U64Key::new(a_poll.id), a_poll)
+}
 
-    a_poll.status = PollStatus::Expired;
-    poll_store(deps.storage).save(&poll_id.to_be_bytes(), &a_poll)?;
+/// Returns the message sending `amount` of the deposit of a poll to `recipient`, in WHALE or in the
+/// native denom the deposit was made in
+fn deposit_transfer_msg(
+    deps: Deps,
+    config: &Config,
+    a_poll: &Poll,
+    amount: Uint128,
+    recipient: Addr,
+) -> StdResult<CosmosMsg> {
+    match &a_poll.deposit_denom {
+        Some(denom) => Asset {
+            info: AssetInfo::NativeToken {
+                denom: denom.clone(),
+            },
+            amount,
+        }
+        .into_msg(&deps.querier, recipient),
+        None => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.whale_token)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+        })),
+    }
+}
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "expire_poll"),
-        ("poll_id", poll_id.to_string().as_str()),
-    ]))
+/// Returns the message burning `amount` of the deposit of a poll
+fn deposit_burn_msg(
+    deps: Deps,
+    config: &Config,
+    a_poll: &Poll,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    match &a_poll.deposit_denom {
+        Some(denom) => Ok(CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![Coin::new(amount.u128(), denom)],
+        })),
+        None => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.whale_token)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        })),
+    }
 }
 
 // Query Handlers
@@ -562,7 +1350,7 @@ pub fn expire_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Co
 /// query_config allows for the query of the currently set configuration values
 /// which influence Polls such as the quorum needed and the minimum voting peroid before a poll can be ended
 fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
-    let config: Config = config_read(deps.storage).load()?;
+    let config: Config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
         owner: deps.api.addr_humanize(&config.owner)?.to_string(),
         whale_token: deps.api.addr_humanize(&config.whale_token)?.to_string(),
@@ -573,12 +1361,28 @@ fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
         expiration_period: config.expiration_period,
         proposal_deposit: config.proposal_deposit,
         snapshot_period: config.snapshot_period,
+        deposit_slash_policy: config.deposit_slash_policy,
+        treasury_addr: config
+            .treasury_addr
+            .map(|addr| deps.api.addr_humanize(&addr))
+            .transpose()?
+            .map(|addr| addr.to_string()),
+        quorum_mode: config.quorum_mode,
+        voter_reward_ratio: config.voter_reward_ratio,
+        protected_methods: config.protected_methods,
+        category_params: config.category_params,
+        guardian: config
+            .guardian
+            .map(|addr| deps.api.addr_humanize(&addr))
+            .transpose()?
+            .map(|addr| addr.to_string()),
+        native_proposal_deposit: config.native_proposal_deposit,
     })
 }
 
 /// query_state allows for the query of dynamic state values such as the poll count and how much has been deposited
 fn query_state(deps: Deps) -> Result<StateResponse, ContractError> {
-    let state: State = state_read(deps.storage).load()?;
+    let state: State = STATE.load(deps.storage)?;
     Ok(StateResponse {
         poll_count: state.poll_count,
         total_share: state.total_share,
@@ -588,43 +1392,15 @@ fn query_state(deps: Deps) -> Result<StateResponse, ContractError> {
 
 /// query_poll allows for the query of a given poll by supplying its poll_id
 fn query_poll(deps: Deps, poll_id: u64) -> Result<PollResponse, ContractError> {
-    let poll = match poll_read(deps.storage).may_load(&poll_id.to_be_bytes())? {
-        Some(poll) => Some(poll),
+    let poll = match polls().may_load(deps.storage, U64Key::new(poll_id))? {
+        Some(poll) => poll,
         None => return Err(ContractError::PollNotFound {}),
-    }
-    .unwrap();
-
-    let mut data_list: Vec<PollExecuteMsg> = vec![];
+    };
 
-    Ok(PollResponse {
-        id: poll.id,
-        creator: deps.api.addr_humanize(&poll.creator)?.to_string(),
-        status: poll.status,
-        end_height: poll.end_height,
-        title: poll.title,
-        description: poll.description,
-        link: poll.link,
-        deposit_amount: poll.deposit_amount,
-        execute_data: if let Some(exe_msgs) = poll.execute_data.clone() {
-            for msg in exe_msgs {
-                let execute_data = PollExecuteMsg {
-                    order: msg.order,
-                    contract: deps.api.addr_humanize(&msg.contract)?.to_string(),
-                    msg: msg.msg,
-                };
-                data_list.push(execute_data)
-            }
-            Some(data_list)
-        } else {
-            None
-        },
-        yes_votes: poll.yes_votes,
-        no_votes: poll.no_votes,
-        staked_amount: poll.staked_amount,
-        total_balance_at_end_poll: poll.total_balance_at_end_poll,
-    })
+    Ok(poll_to_response(deps, &poll)?)
 }
 
+/// query_polls returns a page of polls, optionally filtered by their status through the poll indexer
 fn query_polls(
     deps: Deps,
     filter: Option<PollStatus>,
@@ -636,37 +1412,7 @@ fn query_polls(
 
     let poll_responses: StdResult<Vec<PollResponse>> = polls
         .iter()
-        .map(|poll| {
-            Ok(PollResponse {
-                id: poll.id,
-                creator: deps.api.addr_humanize(&poll.creator)?.to_string(),
-                status: poll.status.clone(),
-                end_height: poll.end_height,
-                title: poll.title.to_string(),
-                description: poll.description.to_string(),
-                link: poll.link.clone(),
-                deposit_amount: poll.deposit_amount,
-                execute_data: if let Some(exe_msgs) = poll.execute_data.clone() {
-                    let mut data_list: Vec<PollExecuteMsg> = vec![];
-
-                    for msg in exe_msgs {
-                        let execute_data = PollExecuteMsg {
-                            order: msg.order,
-                            contract: deps.api.addr_humanize(&msg.contract)?.to_string(),
-                            msg: msg.msg,
-                        };
-                        data_list.push(execute_data)
-                    }
-                    Some(data_list)
-                } else {
-                    None
-                },
-                yes_votes: poll.yes_votes,
-                no_votes: poll.no_votes,
-                staked_amount: poll.staked_amount,
-                total_balance_at_end_poll: poll.total_balance_at_end_poll,
-            })
-        })
+        .map(|poll| poll_to_response(deps, poll))
         .collect();
 
     Ok(PollsResponse {
@@ -674,6 +1420,47 @@ fn query_polls(
     })
 }
 
+/// Converts a stored [Poll] into its human readable [PollResponse]
+fn poll_to_response(deps: Deps, poll: &Poll) -> StdResult<PollResponse> {
+    let execute_data = match poll.execute_data.clone() {
+        Some(exe_msgs) => Some(
+            exe_msgs
+                .into_iter()
+                .map(|msg| {
+                    Ok(PollExecuteMsg {
+                        order: msg.order,
+                        contract: deps.api.addr_humanize(&msg.contract)?.to_string(),
+                        msg: msg.msg,
+                    })
+                })
+                .collect::<StdResult<Vec<PollExecuteMsg>>>()?,
+        ),
+        None => None,
+    };
+
+    Ok(PollResponse {
+        id: poll.id,
+        creator: deps.api.addr_humanize(&poll.creator)?.to_string(),
+        status: poll.status.clone(),
+        poll_type: poll.poll_type.clone(),
+        category: poll.category.clone(),
+        end_height: poll.end_height,
+        title: poll.title.to_string(),
+        description: poll.description.to_string(),
+        link: poll.link.clone(),
+        metadata_hash: poll.metadata_hash.clone(),
+        deposit_amount: poll.deposit_amount,
+        deposit_denom: poll.deposit_denom.clone(),
+        execute_data,
+        yes_votes: poll.yes_votes,
+        no_votes: poll.no_votes,
+        abstain_votes: poll.abstain_votes,
+        staked_amount: poll.staked_amount,
+        staked_amount_at_creation: poll.staked_amount_at_creation,
+        total_balance_at_end_poll: poll.total_balance_at_end_poll,
+    })
+}
+
 fn query_voters(
     deps: Deps,
     poll_id: u64,
@@ -681,7 +1468,7 @@ fn query_voters(
     limit: Option<u32>,
     order_by: Option<OrderBy>,
 ) -> Result<VotersResponse, ContractError> {
-    let poll: Poll = match poll_read(deps.storage).may_load(&poll_id.to_be_bytes())? {
+    let poll: Poll = match polls().may_load(deps.storage, U64Key::new(poll_id))? {
         Some(poll) => Some(poll),
         None => return Err(ContractError::PollNotFound {}),
     }
@@ -717,12 +1504,72 @@ fn query_voters(
     })
 }
 
-/// SnapshotPoll is used to take a snapshot of the staked amount for quorum calculation
+/// query_voter returns the vote receipt of an address on a poll. The receipt of an ended poll is
+/// kept until the locked balance of the voter is pruned, on its next stake, withdrawal or vote.
+fn query_voter(deps: Deps, poll_id: u64, address: String) -> Result<VoterResponse, ContractError> {
+    let poll = match polls().may_load(deps.storage, U64Key::new(poll_id))? {
+        Some(poll) => poll,
+        None => return Err(ContractError::PollNotFound {}),
+    };
+
+    let voter_raw = deps.api.addr_canonicalize(&address)?;
+    let voter_info = POLL_VOTERS
+        .may_load(deps.storage, (U64Key::new(poll_id), voter_raw.as_slice()))?
+        .ok_or(ContractError::NotVoted {})?;
+
+    Ok(VoterResponse {
+        poll_id,
+        voter: address,
+        vote: voter_info.vote,
+        balance: voter_info.balance,
+        poll_ended: poll.status != PollStatus::InProgress,
+    })
+}
+
+/// query_poll_executables returns the messages of a poll in the order they are executed, so voters
+/// can inspect them before voting. Text polls have no messages.
+fn query_poll_executables(
+    deps: Deps,
+    poll_id: u64,
+) -> Result<PollExecutablesResponse, ContractError> {
+    let poll = match polls().may_load(deps.storage, U64Key::new(poll_id))? {
+        Some(poll) => poll,
+        None => return Err(ContractError::PollNotFound {}),
+    };
+
+    let mut msgs = poll.execute_data.unwrap_or_default();
+    msgs.sort();
+    let executables = msgs
+        .into_iter()
+        .map(|msg| {
+            // Messages are json encoded by convention, anything else is returned undecoded
+            let decoded_msg = match from_slice::<IgnoredAny>(msg.msg.as_slice()) {
+                Ok(_) => String::from_utf8(msg.msg.to_vec()).ok(),
+                Err(_) => None,
+            };
+            Ok(PollExecutable {
+                order: msg.order,
+                contract: deps.api.addr_humanize(&msg.contract)?.to_string(),
+                msg: msg.msg,
+                decoded_msg,
+            })
+        })
+        .collect::<StdResult<Vec<PollExecutable>>>()?;
+
+    Ok(PollExecutablesResponse {
+        poll_id,
+        executables,
+    })
+}
+
+/// SnapshotPoll is used to take a snapshot of the staked amount for quorum calculation.
+/// Anyone can call it once the poll enters its snapshot period, which stops stake from being inflated
+/// right before the poll ends.
 pub fn snapshot_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, ContractError> {
-    let config: Config = config_read(deps.storage).load()?;
-    let mut a_poll: Poll = poll_store(deps.storage).load(&poll_id.to_be_bytes())?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut a_poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
 
-    if a_poll.status != PollStatus::InProgress {
+    if a_poll.status != PollStatus::InProgress || env.block.height > a_poll.end_height {
         return Err(ContractError::PollNotInProgress {});
     }
 
@@ -737,7 +1584,7 @@ pub fn snapshot_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response,
     }
 
     // store the current staked amount for quorum calculation
-    let state: State = state_store(deps.storage).load()?;
+    let state: State = STATE.load(deps.storage)?;
 
     let staked_amount = query_token_balance(
         &deps.querier,
@@ -748,18 +1595,20 @@ pub fn snapshot_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response,
 
     a_poll.staked_amount = Some(staked_amount);
 
-    poll_store(deps.storage).save(&poll_id.to_be_bytes(), &a_poll)?;
+    polls().save(deps.storage, U64Key::new(poll_id), &a_poll)?;
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "snapshot_poll"),
-        attr("poll_id", poll_id.to_string().as_str()),
-        attr("staked_amount", staked_amount.to_string().as_str()),
-    ]))
+    Ok(WhaleEvent::new(GOVERNANCE, "snapshot_poll")
+        .add_attribute("poll_id", poll_id.to_string())
+        .add_attribute("staked_amount", staked_amount.to_string())
+        .into_response())
 }
 
+/// update_config changes the poll parameters of the contract. Only the contract itself may call it,
+/// meaning a config change has to be proposed, voted on and executed through a poll.
 #[allow(clippy::too_many_arguments)]
 pub fn update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     owner: Option<String>,
     quorum: Option<Decimal>,
@@ -769,24 +1618,31 @@ pub fn update_config(
     expiration_period: Option<u64>,
     proposal_deposit: Option<Uint128>,
     snapshot_period: Option<u64>,
+    deposit_slash_policy: Option<DepositSlashPolicy>,
+    treasury_addr: Option<String>,
+    quorum_mode: Option<QuorumMode>,
+    voter_reward_ratio: Option<Decimal>,
+    protected_methods: Option<Vec<String>>,
+    category_params: Option<Vec<(PollCategory, CategoryParams)>>,
+    guardian: Option<String>,
+    native_proposal_deposit: Option<Coin>,
 ) -> Result<Response, ContractError> {
-    let api = deps.api;
-    config_store(deps.storage).update(|mut config| {
-        if config.owner != api.addr_canonicalize(info.sender.as_str())? {
-            return Err(ContractError::Unauthorized {});
-        }
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
 
+    let api = deps.api;
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
         if let Some(owner) = owner {
+            validate_addr(api, "owner", &owner)?;
             config.owner = api.addr_canonicalize(&owner)?;
         }
 
         if let Some(quorum) = quorum {
-            validate_quorum(quorum)?;
             config.quorum = quorum;
         }
 
         if let Some(threshold) = threshold {
-            validate_threshold(threshold)?;
             config.threshold = threshold;
         }
 
@@ -802,8 +1658,6 @@ pub fn update_config(
             config.expiration_period = expiration_period;
         }
 
-        validate_poll_period(config.timelock_period, config.expiration_period)?;
-
         if let Some(proposal_deposit) = proposal_deposit {
             config.proposal_deposit = proposal_deposit;
         }
@@ -812,8 +1666,43 @@ pub fn update_config(
             config.snapshot_period = period;
         }
 
+        if let Some(treasury_addr) = treasury_addr {
+            validate_addr(api, "treasury_addr", &treasury_addr)?;
+            config.treasury_addr = Some(api.addr_canonicalize(&treasury_addr)?);
+        }
+
+        if let Some(deposit_slash_policy) = deposit_slash_policy {
+            config.deposit_slash_policy = deposit_slash_policy;
+        }
+
+        if let Some(quorum_mode) = quorum_mode {
+            config.quorum_mode = quorum_mode;
+        }
+
+        if let Some(voter_reward_ratio) = voter_reward_ratio {
+            config.voter_reward_ratio = voter_reward_ratio;
+        }
+
+        if let Some(protected_methods) = protected_methods {
+            config.protected_methods = protected_methods;
+        }
+
+        if let Some(category_params) = category_params {
+            config.category_params = category_params;
+        }
+
+        if let Some(guardian) = guardian {
+            validate_addr(api, "guardian", &guardian)?;
+            config.guardian = Some(api.addr_canonicalize(&guardian)?);
+        }
+
+        if let Some(native_proposal_deposit) = native_proposal_deposit {
+            config.native_proposal_deposit = Some(native_proposal_deposit);
+        }
+
+        validate_config(&config)?;
         Ok(config)
     })?;
 
-    Ok(Response::new().add_attributes(vec![("action", "update_config")]))
+    Ok(WhaleEvent::new(GOVERNANCE, "update_config").into_response())
 }
@@ -0,0 +1,176 @@
+use crate::error::ContractError;
+use crate::staking::{assert_delegated_share_unlocked, prune_locked_balance};
+use terraswap::querier::query_token_balance;
+use white_whale::events::{WhaleEvent, GOVERNANCE};
+use white_whale::governance::state::{
+    read_delegations, read_delegators, DelegationsResponse, DelegationsResponseItem, BANK, CONFIG,
+    DELEGATED_SHARES, DELEGATIONS, DELEGATORS, STATE,
+};
+
+use cosmwasm_std::{
+    CanonicalAddr, Deps, DepsMut, MessageInfo, Response, StdResult, Storage, Uint128,
+};
+
+/// Delegates the voting power of the sender's stake to `delegate`. The delegated share is added to
+/// the voting weight of the delegate when casting votes, and the sender can no longer vote until it
+/// undelegates. A stake with votes on polls in progress can not be delegated.
+pub fn delegate_voting_power(
+    deps: DepsMut,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let delegator_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let delegate_raw = deps.api.addr_canonicalize(&delegate)?;
+    if delegator_raw == delegate_raw {
+        return Err(ContractError::InvalidDelegate {});
+    }
+
+    if DELEGATIONS
+        .may_load(deps.storage, delegator_raw.as_slice())?
+        .is_some()
+    {
+        return Err(ContractError::AlreadyDelegated {});
+    }
+
+    let mut token_manager = match BANK.may_load(deps.storage, delegator_raw.as_slice())? {
+        Some(token_manager) if !token_manager.share.is_zero() => token_manager,
+        _ => return Err(ContractError::NothingStaked {}),
+    };
+
+    // votes already cast with this stake must not be counted again through the delegate
+    prune_locked_balance(deps.storage, &mut token_manager, &delegator_raw)?;
+    if !token_manager.locked_balance.is_empty() {
+        return Err(ContractError::VotingPowerLocked {});
+    }
+    BANK.save(deps.storage, delegator_raw.as_slice(), &token_manager)?;
+
+    DELEGATIONS.save(deps.storage, delegator_raw.as_slice(), &delegate_raw)?;
+    DELEGATORS.save(
+        deps.storage,
+        (delegate_raw.as_slice(), delegator_raw.as_slice()),
+        &true,
+    )?;
+    update_delegated_share(deps.storage, &delegate_raw, token_manager.share, true)?;
+
+    Ok(WhaleEvent::new(GOVERNANCE, "delegate_voting_power")
+        .add_attribute("delegator", info.sender.as_str())
+        .add_attribute("delegate", delegate.as_str())
+        .add_attribute("share", token_manager.share.to_string())
+        .into_response())
+}
+
+/// Takes back the voting power delegated by the sender. Not possible while the delegate needs the
+/// delegated voting power to cover its votes on polls in progress.
+pub fn undelegate_voting_power(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let delegator_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let delegate_raw = match DELEGATIONS.may_load(deps.storage, delegator_raw.as_slice())? {
+        Some(delegate_raw) => delegate_raw,
+        None => return Err(ContractError::NotDelegated {}),
+    };
+
+    let share = BANK
+        .may_load(deps.storage, delegator_raw.as_slice())?
+        .unwrap_or_default()
+        .share;
+
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let total_balance = query_token_balance(
+        &deps.querier,
+        deps.api.addr_humanize(&config.whale_token)?,
+        deps.api.addr_humanize(&state.contract_addr)?,
+    )?
+    .checked_sub(state.total_deposit)?;
+    assert_delegated_share_unlocked(
+        deps.storage,
+        &delegate_raw,
+        share,
+        state.total_share,
+        total_balance,
+    )?;
+
+    DELEGATIONS.remove(deps.storage, delegator_raw.as_slice());
+    DELEGATORS.remove(
+        deps.storage,
+        (delegate_raw.as_slice(), delegator_raw.as_slice()),
+    );
+    update_delegated_share(deps.storage, &delegate_raw, share, false)?;
+
+    Ok(WhaleEvent::new(GOVERNANCE, "undelegate_voting_power")
+        .add_attribute("delegator", info.sender.as_str())
+        .add_attribute("share", share.to_string())
+        .into_response())
+}
+
+/// Adds or removes share from the total share delegated to a delegate
+pub(crate) fn update_delegated_share(
+    storage: &mut dyn Storage,
+    delegate: &CanonicalAddr,
+    share: Uint128,
+    add: bool,
+) -> Result<(), ContractError> {
+    let delegated_share = DELEGATED_SHARES
+        .may_load(storage, delegate.as_slice())?
+        .unwrap_or_default();
+    let delegated_share = if add {
+        delegated_share.checked_add(share)?
+    } else {
+        delegated_share.checked_sub(share)?
+    };
+
+    if delegated_share.is_zero() {
+        DELEGATED_SHARES.remove(storage, delegate.as_slice());
+    } else {
+        DELEGATED_SHARES.save(storage, delegate.as_slice(), &delegated_share)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the delegation of `delegator` if given, otherwise the delegators of `delegate` if given,
+/// otherwise all delegations.
+pub fn query_delegations(
+    deps: Deps,
+    delegator: Option<String>,
+    delegate: Option<String>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<DelegationsResponse, ContractError> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_canonicalize(&addr))
+        .transpose()?;
+    let delegate = delegate
+        .map(|addr| deps.api.addr_canonicalize(&addr))
+        .transpose()?;
+
+    let delegations: Vec<(CanonicalAddr, CanonicalAddr)> = if let Some(delegator) = delegator {
+        let delegator_raw = deps.api.addr_canonicalize(&delegator)?;
+        DELEGATIONS
+            .may_load(deps.storage, delegator_raw.as_slice())?
+            .filter(|delegate_raw| delegate.as_ref().map_or(true, |d| d == delegate_raw))
+            .map(|delegate_raw| vec![(delegator_raw, delegate_raw)])
+            .unwrap_or_default()
+    } else if let Some(delegate_raw) = delegate {
+        read_delegators(deps.storage, &delegate_raw, start_after, limit)?
+            .into_iter()
+            .map(|delegator_raw| (delegator_raw, delegate_raw.clone()))
+            .collect()
+    } else {
+        read_delegations(deps.storage, start_after, limit)?
+    };
+
+    let delegations = delegations
+        .iter()
+        .map(|(delegator_raw, delegate_raw)| {
+            Ok(DelegationsResponseItem {
+                delegator: deps.api.addr_humanize(delegator_raw)?.to_string(),
+                delegate: deps.api.addr_humanize(delegate_raw)?.to_string(),
+            })
+        })
+        .collect::<StdResult<Vec<DelegationsResponseItem>>>()?;
+
+    Ok(DelegationsResponse { delegations })
+}
@@ -1,23 +1,47 @@
 use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
+use white_whale::error::CommonError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     OverflowError(#[from] OverflowError),
 
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
+    #[error("Cannot migrate {0}, the contract must be {1}")]
+    InvalidMigrationContract(String, String),
+
     #[error("Must deposit more than {0} token")]
     InsufficientProposalDeposit(u128),
 
+    #[error(
+        "No native proposal deposit is configured, polls must be created with a WHALE deposit"
+    )]
+    NoNativeProposalDeposit {},
+
+    #[error("The proposal deposit must be sent in {0} only")]
+    InvalidNativeProposalDeposit(String),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
     #[error("User has already voted")]
     AlreadyVoted {},
 
+    #[error("User has not voted")]
+    NotVoted {},
+
+    #[error("Votes can not be changed once the snapshot period has started")]
+    VoteChangeSnapshotPeriod {},
+
     #[error("Data should be given")]
     DataShouldBeGiven {},
 
@@ -48,21 +72,69 @@ pub enum ContractError {
     #[error("Poll is not in passed status")]
     PollNotPassed {},
 
+    #[error("Poll is not in failed execution status")]
+    PollNotFailed {},
+
+    #[error("Poll is not being executed")]
+    PollNotExecuting {},
+
+    #[error("Poll can not be executed after its expiration period")]
+    PollExecutionExpired {},
+
+    #[error("Invalid reply id {0}")]
+    InvalidReplyId(u64),
+
+    #[error("Poll can not be canceled as it already has votes")]
+    PollHasVotes {},
+
     #[error("Voting period has not expired")]
     PollVotingPeriod {},
 
-    #[error("Quorum must be between [0 and {0}]")]
+    #[error("Quorum must be between (0 and {0}]")]
     PollQuorumInvalidValue(String),
 
-    #[error("Threshold must be between [0 and {0}]")]
+    #[error("Threshold must be between (0 and {0}]")]
     PollThresholdInvalidValue(String),
 
+    #[error("Voter reward ratio must be between [0 and {0}]")]
+    VoterRewardRatioInvalidValue(String),
+
+    #[error("No voting rewards to claim")]
+    NoVotingRewards {},
+
+    #[error("No staking rewards to claim")]
+    NoStakingRewards {},
+
+    #[error("Text polls can not have execute messages")]
+    TextPollExecuteMsgs {},
+
+    #[error("Executable polls must have execute messages")]
+    ExecutablePollNoExecuteMsgs {},
+
+    #[error("Text polls must be in the text category and executable polls in another category")]
+    InvalidPollCategory {},
+
+    #[error("Poll parameters of the {0} category are set more than once")]
+    DuplicateCategoryParams(String),
+
+    #[error("Poll can not have more than {0} execute messages")]
+    TooManyPollExecuteMsgs(usize),
+
+    #[error("Poll execute messages must have unique orders, {0} is used more than once")]
+    DuplicatePollExecuteMsgOrder(u64),
+
+    #[error("Poll execute messages can not call the protected method {0}")]
+    ProtectedPollExecuteMsg(String),
+
     #[error("Poll link too short, must be at least {0} characters")]
     PollLinkInvalidShort(usize),
 
     #[error("Poll link too long, must be at most {0} characters")]
     PollLinkInvalidLong(usize),
 
+    #[error("Poll metadata hash must be {0} to {1} alphanumeric characters")]
+    PollMetadataHashInvalid(usize, usize),
+
     #[error("Poll title too short, must be at least {0} characters")]
     PollTitleInvalidShort(usize),
 
@@ -84,6 +156,45 @@ pub enum ContractError {
     #[error("Timelock period has not expired")]
     TimelockNotExpired {},
 
-    #[error("Invalid poll period. Expiration period is earlier than the timelock period.")]
+    #[error("Polls can only be vetoed during their timelock period")]
+    TimelockExpired {},
+
+    #[error("Invalid poll period. Expiration period must be later than the timelock period.")]
     InvalidPollPeriod {},
+
+    #[error("Invalid period. Periods must be greater than zero.")]
+    InvalidZeroPeriod {},
+
+    #[error("Invalid snapshot period. Snapshot period must be shorter than the voting period.")]
+    InvalidSnapshotPeriod {},
+
+    #[error("Proposal deposit must be greater than zero")]
+    InvalidZeroProposalDeposit {},
+
+    #[error("Cannot delegate voting power to yourself")]
+    InvalidDelegate {},
+
+    #[error("Voting power is already delegated")]
+    AlreadyDelegated {},
+
+    #[error("Voting power is not delegated")]
+    NotDelegated {},
+
+    #[error("Voting power is delegated, undelegate it to vote")]
+    VotingPowerDelegated {},
+
+    #[error("Voting power is locked in polls in progress")]
+    VotingPowerLocked {},
+
+    #[error("A treasury address is required to send slashed deposits to the treasury")]
+    NoTreasuryAddr {},
+
+    #[error("A treasury address is required to create whitelist polls")]
+    NoTreasuryToWhitelistIn {},
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
 }
@@ -1,10 +1,14 @@
 pub use crate::error::ContractError;
 
 pub mod contract;
+mod delegation;
 mod error;
+mod migrations;
 mod staking;
+mod staking_rewards;
 
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod tests;
 mod validators;
+mod voting_rewards;
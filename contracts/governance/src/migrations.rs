@@ -0,0 +1,144 @@
+use cosmwasm_std::{CanonicalAddr, Decimal, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::contract::default_protected_methods;
+use white_whale::governance::state::{
+    polls, Config, DepositSlashPolicy, ExecuteData, Poll, PollCategory, PollStatus, PollType,
+    QuorumMode, CONFIG,
+};
+
+/// First version storing the polls in an indexed map
+pub(crate) const INDEXED_POLLS_VERSION: &str = "0.2.0";
+
+// Storage of the unversioned state, the raw keys must match the keys used by the current state
+const CONFIG_V0: Item<ConfigV0> = Item::new("\u{0}\u{6}config");
+const POLLS_V0: Map<U64Key, PollV0> = Map::new("poll");
+// Status index of the polls replaced by the indexed map, keyed by status and poll id
+const LEGACY_POLL_INDEXER: Map<(&[u8], U64Key), bool> = Map::new("poll_indexer");
+
+/// Config as stored by contracts deployed before the contract version was tracked
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigV0 {
+    pub owner: CanonicalAddr,
+    pub whale_token: CanonicalAddr,
+    pub quorum: Decimal,
+    pub threshold: Decimal,
+    pub voting_period: u64,
+    pub timelock_period: u64,
+    pub expiration_period: u64,
+    pub proposal_deposit: Uint128,
+    pub snapshot_period: u64,
+}
+
+/// Poll as stored by contracts deployed before the contract version was tracked
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PollV0 {
+    pub id: u64,
+    pub creator: CanonicalAddr,
+    pub status: PollStatus,
+    pub yes_votes: Uint128,
+    pub no_votes: Uint128,
+    pub end_height: u64,
+    pub title: String,
+    pub description: String,
+    pub link: Option<String>,
+    pub execute_data: Option<Vec<ExecuteData>>,
+    pub deposit_amount: Uint128,
+    pub total_balance_at_end_poll: Option<Uint128>,
+    pub staked_amount: Option<Uint128>,
+}
+
+/// Migrates the state of a contract which has no contract version stored to the current layout.
+/// Polls get an empty abstain tally and the default category of their type, the config gets the
+/// default deposit slash policy, quorum mode and protected methods, without voter rewards,
+/// category parameters, guardian or native proposal deposit.
+pub fn migrate_unversioned_state(storage: &mut dyn Storage) -> StdResult<()> {
+    let config: ConfigV0 = CONFIG_V0.load(storage)?;
+    CONFIG.save(
+        storage,
+        &Config {
+            owner: config.owner,
+            whale_token: config.whale_token,
+            quorum: config.quorum,
+            threshold: config.threshold,
+            voting_period: config.voting_period,
+            timelock_period: config.timelock_period,
+            expiration_period: config.expiration_period,
+            proposal_deposit: config.proposal_deposit,
+            snapshot_period: config.snapshot_period,
+            deposit_slash_policy: DepositSlashPolicy::DistributeToStakers,
+            treasury_addr: None,
+            quorum_mode: QuorumMode::EndPoll,
+            voter_reward_ratio: Decimal::zero(),
+            protected_methods: default_protected_methods(),
+            category_params: vec![],
+            guardian: None,
+            native_proposal_deposit: None,
+        },
+    )?;
+
+    let legacy_polls: Vec<PollV0> = POLLS_V0
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, poll)| poll))
+        .collect::<StdResult<Vec<PollV0>>>()?;
+
+    for poll in legacy_polls {
+        let (poll_type, category) = if poll.execute_data.is_some() {
+            (PollType::Executable, PollCategory::ParameterChange)
+        } else {
+            (PollType::Text, PollCategory::Text)
+        };
+        // the legacy poll is removed first as the indexed map can't read it to update the indexes
+        POLLS_V0.remove(storage, U64Key::new(poll.id));
+        polls().save(
+            storage,
+            U64Key::new(poll.id),
+            &Poll {
+                id: poll.id,
+                creator: poll.creator,
+                status: poll.status,
+                yes_votes: poll.yes_votes,
+                no_votes: poll.no_votes,
+                abstain_votes: Uint128::zero(),
+                end_height: poll.end_height,
+                title: poll.title,
+                description: poll.description,
+                link: poll.link,
+                metadata_hash: None,
+                execute_data: poll.execute_data,
+                deposit_amount: poll.deposit_amount,
+                total_balance_at_end_poll: poll.total_balance_at_end_poll,
+                staked_amount: poll.staked_amount,
+                staked_amount_at_creation: None,
+                poll_type,
+                category,
+                deposit_denom: None,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Moves the polls of a contract predating [INDEXED_POLLS_VERSION] to the indexed map. The polls keep
+/// their raw keys and are saved again to build the status and creator indexes, which replace the
+/// legacy status indexer.
+pub fn migrate_poll_indexes(storage: &mut dyn Storage) -> StdResult<()> {
+    let polls = polls();
+    let all_polls: Vec<Poll> = polls
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, poll)| poll))
+        .collect::<StdResult<Vec<Poll>>>()?;
+
+    for poll in all_polls {
+        LEGACY_POLL_INDEXER.remove(
+            storage,
+            (poll.status.to_string().as_bytes(), U64Key::new(poll.id)),
+        );
+        polls.save(storage, U64Key::new(poll.id), &poll)?;
+    }
+
+    Ok(())
+}
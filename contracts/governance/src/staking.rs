@@ -1,7 +1,11 @@
+use crate::delegation::update_delegated_share;
 use crate::error::ContractError;
+use crate::staking_rewards::update_staker_rewards;
+use crate::voting_rewards::voter_reward;
+use white_whale::events::{WhaleEvent, GOVERNANCE};
 use white_whale::governance::state::{
-    bank_read, bank_store, config_read, config_store, poll_read, poll_voter_store, state_read,
-    state_store, Config, Poll, State, TokenManager,
+    polls, Config, Poll, State, TokenManager, BANK, CONFIG, DELEGATED_SHARES, DELEGATIONS,
+    PENDING_VOTING_REWARDS, POLL_VOTERS, STATE,
 };
 
 use cosmwasm_std::{
@@ -9,6 +13,7 @@ use cosmwasm_std::{
     Storage, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::U64Key;
 use terraswap::querier::query_token_balance;
 use white_whale::governance::state::{PollStatus, StakerResponse};
 
@@ -22,11 +27,11 @@ pub fn stake_voting_tokens(
     }
 
     let sender_address_raw = deps.api.addr_canonicalize(sender.as_str())?;
-    let key = &sender_address_raw.as_slice();
+    let key = sender_address_raw.as_slice();
 
-    let mut token_manager = bank_read(deps.storage).may_load(key)?.unwrap_or_default();
-    let config: Config = config_store(deps.storage).load()?;
-    let mut state: State = state_store(deps.storage).load()?;
+    let mut token_manager = BANK.may_load(deps.storage, key)?.unwrap_or_default();
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut state: State = STATE.load(deps.storage)?;
 
     // balance already increased, so subtract deposit amount
     let total_balance = query_token_balance(
@@ -42,18 +47,22 @@ pub fn stake_voting_tokens(
         amount.multiply_ratio(state.total_share, total_balance)
     };
 
+    update_staker_rewards(deps.storage, &sender_address_raw, token_manager.share)?;
     token_manager.share += share;
     state.total_share += share;
 
-    state_store(deps.storage).save(&state)?;
-    bank_store(deps.storage).save(key, &token_manager)?;
+    if let Some(delegate) = DELEGATIONS.may_load(deps.storage, key)? {
+        update_delegated_share(deps.storage, &delegate, share, true)?;
+    }
+
+    STATE.save(deps.storage, &state)?;
+    BANK.save(deps.storage, key, &token_manager)?;
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "staking"),
-        ("sender", sender.as_str()),
-        ("share", share.to_string().as_str()),
-        ("amount", amount.to_string().as_str()),
-    ]))
+    Ok(WhaleEvent::new(GOVERNANCE, "staking")
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("share", share.to_string())
+        .add_attribute("amount", amount.to_string())
+        .into_response())
 }
 
 // Withdraw amount if not staked. By default all funds will be withdrawn.
@@ -65,9 +74,9 @@ pub fn withdraw_voting_tokens(
     let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let key = sender_address_raw.as_slice();
 
-    if let Some(mut token_manager) = bank_read(deps.storage).may_load(key)? {
-        let config: Config = config_store(deps.storage).load()?;
-        let mut state: State = state_store(deps.storage).load()?;
+    if let Some(mut token_manager) = BANK.may_load(deps.storage, key)? {
+        let config: Config = CONFIG.load(deps.storage)?;
+        let mut state: State = STATE.load(deps.storage)?;
 
         // Load total share & total balance except proposal deposit amount
         let total_share = state.total_share.u128();
@@ -79,14 +88,22 @@ pub fn withdraw_voting_tokens(
         .checked_sub(state.total_deposit)?
         .u128();
 
+        if total_share == 0 || total_balance == 0 {
+            return Err(ContractError::NothingStaked {});
+        }
+
+        if amount.map_or(false, |v| v.is_zero()) {
+            return Err(ContractError::InvalidWithdrawAmount {});
+        }
+
         let locked_balance =
-            compute_locked_balance(deps.storage, &mut token_manager, &sender_address_raw);
+            compute_locked_balance(deps.storage, &mut token_manager, &sender_address_raw)?;
         let locked_share = locked_balance * total_share / total_balance;
         let user_share = token_manager.share.u128();
 
         let withdraw_share = amount
             .map(|v| std::cmp::max(v.multiply_ratio(total_share, total_balance).u128(), 1u128))
-            .unwrap_or_else(|| user_share - locked_share);
+            .unwrap_or_else(|| user_share.saturating_sub(locked_share));
         let withdraw_amount = amount
             .map(|v| v.u128())
             .unwrap_or_else(|| withdraw_share * total_balance / total_share);
@@ -94,13 +111,30 @@ pub fn withdraw_voting_tokens(
         if locked_share + withdraw_share > user_share {
             Err(ContractError::InvalidWithdrawAmount {})
         } else {
+            // the delegate may have voted with the delegated stake on polls in progress
+            let delegate = DELEGATIONS.may_load(deps.storage, key)?;
+            if let Some(delegate) = &delegate {
+                assert_delegated_share_unlocked(
+                    deps.storage,
+                    delegate,
+                    withdraw_share.into(),
+                    state.total_share,
+                    total_balance.into(),
+                )?;
+            }
+
+            update_staker_rewards(deps.storage, &sender_address_raw, token_manager.share)?;
             let share = user_share - withdraw_share;
             token_manager.share = Uint128::from(share);
 
-            bank_store(deps.storage).save(key, &token_manager)?;
+            BANK.save(deps.storage, key, &token_manager)?;
 
             state.total_share = Uint128::from(total_share - withdraw_share);
-            state_store(deps.storage).save(&state)?;
+            STATE.save(deps.storage, &state)?;
+
+            if let Some(delegate) = delegate {
+                update_delegated_share(deps.storage, &delegate, withdraw_share.into(), false)?;
+            }
 
             send_tokens(
                 deps,
@@ -121,24 +155,84 @@ fn compute_locked_balance(
     storage: &mut dyn Storage,
     token_manager: &mut TokenManager,
     voter: &CanonicalAddr,
-) -> u128 {
-    token_manager.locked_balance.retain(|(poll_id, _)| {
-        let poll: Poll = poll_read(storage).load(&poll_id.to_be_bytes()).unwrap();
+) -> StdResult<u128> {
+    prune_locked_balance(storage, token_manager, voter)?;
 
-        if poll.status != PollStatus::InProgress {
-            // remove voter info from the poll
-            poll_voter_store(storage, *poll_id).remove(voter.as_slice());
-        }
-
-        poll.status == PollStatus::InProgress
-    });
-
-    token_manager
+    Ok(token_manager
         .locked_balance
         .iter()
         .map(|(_, v)| v.balance.u128())
         .max()
-        .unwrap_or_default()
+        .unwrap_or_default())
+}
+
+/// Removes the locked balances and the voter info of the polls which are no longer in progress,
+/// unlocking the tokens that were voted with on them and crediting the voting rewards of those polls.
+pub(crate) fn prune_locked_balance(
+    storage: &mut dyn Storage,
+    token_manager: &mut TokenManager,
+    voter: &CanonicalAddr,
+) -> StdResult<()> {
+    let mut locked_balance = vec![];
+    let mut reward = Uint128::zero();
+    for (poll_id, voter_info) in token_manager.locked_balance.drain(..) {
+        let poll: Poll = polls().load(storage, U64Key::new(poll_id))?;
+
+        if poll.status == PollStatus::InProgress {
+            locked_balance.push((poll_id, voter_info));
+        } else {
+            // remove voter info from the poll
+            POLL_VOTERS.remove(storage, (U64Key::new(poll_id), voter.as_slice()));
+            reward += voter_reward(storage, poll_id, voter_info.balance)?;
+        }
+    }
+    token_manager.locked_balance = locked_balance;
+
+    if !reward.is_zero() {
+        PENDING_VOTING_REWARDS.update(storage, voter.as_slice(), |pending| -> StdResult<_> {
+            Ok(pending.unwrap_or_default() + reward)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Fails when taking `share` out of the share delegated to `delegate` would leave the delegate less
+/// voting power than it voted with on polls in progress. Only the delegated share already tallied
+/// is locked, the rest can be undelegated or withdrawn while the polls are in progress.
+pub(crate) fn assert_delegated_share_unlocked(
+    storage: &dyn Storage,
+    delegate: &CanonicalAddr,
+    share: Uint128,
+    total_share: Uint128,
+    total_balance: Uint128,
+) -> Result<(), ContractError> {
+    if share.is_zero() {
+        return Ok(());
+    }
+
+    let token_manager = BANK
+        .may_load(storage, delegate.as_slice())?
+        .unwrap_or_default();
+    let mut locked_balance = Uint128::zero();
+    for (poll_id, voter_info) in token_manager.locked_balance.iter() {
+        let poll: Poll = polls().load(storage, U64Key::new(*poll_id))?;
+        if poll.status == PollStatus::InProgress {
+            locked_balance = std::cmp::max(locked_balance, voter_info.balance);
+        }
+    }
+
+    let delegated_share = DELEGATED_SHARES
+        .may_load(storage, delegate.as_slice())?
+        .unwrap_or_default();
+    let voting_power = (token_manager.share + delegated_share)
+        .checked_sub(share)?
+        .multiply_ratio(total_balance, total_share);
+    if voting_power < locked_balance {
+        return Err(ContractError::VotingPowerLocked {});
+    }
+
+    Ok(())
 }
 
 fn send_tokens(
@@ -160,26 +254,24 @@ fn send_tokens(
             })?,
             funds: vec![],
         })])
-        .add_attributes(vec![
-            ("action", action),
-            ("recipient", recipient_human.as_str()),
-            ("amount", amount.to_string().as_str()),
-        ]))
+        .add_attributes(
+            WhaleEvent::new(GOVERNANCE, action)
+                .add_attribute("recipient", recipient_human.as_str())
+                .add_attribute("amount", amount.to_string()),
+        ))
 }
 
 pub fn query_staker(deps: Deps, address: String) -> StdResult<StakerResponse> {
     let addr_raw = deps.api.addr_canonicalize(&address).unwrap();
-    let config: Config = config_read(deps.storage).load()?;
-    let state: State = state_read(deps.storage).load()?;
-    let mut token_manager = bank_read(deps.storage)
-        .may_load(addr_raw.as_slice())?
+    let config: Config = CONFIG.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
+    let mut token_manager = BANK
+        .may_load(deps.storage, addr_raw.as_slice())?
         .unwrap_or_default();
 
     // filter out not in-progress polls
     token_manager.locked_balance.retain(|(poll_id, _)| {
-        let poll: Poll = poll_read(deps.storage)
-            .load(&poll_id.to_be_bytes())
-            .unwrap();
+        let poll: Poll = polls().load(deps.storage, U64Key::new(*poll_id)).unwrap();
 
         poll.status == PollStatus::InProgress
     });
@@ -200,6 +292,12 @@ pub fn query_staker(deps: Deps, address: String) -> StdResult<StakerResponse> {
             Uint128::zero()
         },
         share: token_manager.share,
+        locked_amount: token_manager
+            .locked_balance
+            .iter()
+            .map(|(_, v)| v.balance)
+            .max()
+            .unwrap_or_default(),
         locked_balance: token_manager.locked_balance,
     })
 }
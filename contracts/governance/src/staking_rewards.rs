@@ -0,0 +1,117 @@
+use crate::error::ContractError;
+use white_whale::events::{WhaleEvent, GOVERNANCE};
+use white_whale::governance::state::{
+    Config, StakerRewardInfo, StakerRewardsResponse, State, TokenManager, BANK, CONFIG,
+    REWARD_INDEX, STAKER_REWARDS, STATE,
+};
+
+use cosmwasm_std::{
+    to_binary, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, MessageInfo, Response, StdResult,
+    Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+/// Returns the reward info of a staker with the rewards accrued by `share` since its last update
+fn accrued_rewards(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+    share: Uint128,
+) -> StdResult<StakerRewardInfo> {
+    let reward_index = REWARD_INDEX.may_load(storage)?.unwrap_or_default();
+    let mut reward_info = STAKER_REWARDS
+        .may_load(storage, staker.as_slice())?
+        .unwrap_or_default();
+
+    // the index only grows, shares are credited with the rewards distributed since the last update
+    let accrued = share * (reward_index - reward_info.reward_index);
+    reward_info.pending_rewards += accrued;
+    reward_info.reward_index = reward_index;
+    Ok(reward_info)
+}
+
+/// Credits a staker with the rewards accrued by its current share. Has to be called before the
+/// share of the staker changes.
+pub(crate) fn update_staker_rewards(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    share: Uint128,
+) -> StdResult<()> {
+    let reward_info = accrued_rewards(storage, staker, share)?;
+    STAKER_REWARDS.save(storage, staker.as_slice(), &reward_info)
+}
+
+/// Distributes `amount` to the stakers pro-rata to their share by increasing the reward index.
+pub fn distribute_rewards(
+    deps: DepsMut,
+    sender: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    let mut state: State = STATE.load(deps.storage)?;
+    if state.total_share.is_zero() {
+        return Err(ContractError::NothingStaked {});
+    }
+
+    REWARD_INDEX.update(deps.storage, |index| -> StdResult<_> {
+        Ok(index + Decimal::from_ratio(amount, state.total_share))
+    })?;
+
+    // the rewards are kept out of the staked balance as part of the total deposit until claimed
+    state.total_deposit += amount;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(WhaleEvent::new(GOVERNANCE, "distribute_rewards")
+        .add_attribute("sender", sender)
+        .add_attribute("amount", amount.to_string())
+        .into_response())
+}
+
+/// Sends the sender its staking rewards.
+pub fn claim_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let key = sender_address_raw.as_slice();
+
+    let token_manager: TokenManager = BANK.may_load(deps.storage, key)?.unwrap_or_default();
+    let mut reward_info = accrued_rewards(deps.storage, &sender_address_raw, token_manager.share)?;
+    let amount = reward_info.pending_rewards;
+    if amount.is_zero() {
+        return Err(ContractError::NoStakingRewards {});
+    }
+    reward_info.pending_rewards = Uint128::zero();
+    STAKER_REWARDS.save(deps.storage, key, &reward_info)?;
+
+    let mut state: State = STATE.load(deps.storage)?;
+    state.total_deposit = state.total_deposit.checked_sub(amount)?;
+    STATE.save(deps.storage, &state)?;
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.whale_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }))
+        .add_attributes(
+            WhaleEvent::new(GOVERNANCE, "claim_rewards")
+                .add_attribute("recipient", info.sender.as_str())
+                .add_attribute("amount", amount.to_string()),
+        ))
+}
+
+pub fn query_staker_rewards(deps: Deps, address: String) -> StdResult<StakerRewardsResponse> {
+    let addr_raw = deps.api.addr_canonicalize(&address)?;
+    let token_manager = BANK
+        .may_load(deps.storage, addr_raw.as_slice())?
+        .unwrap_or_default();
+    let reward_info = accrued_rewards(deps.storage, &addr_raw, token_manager.share)?;
+
+    Ok(StakerRewardsResponse {
+        pending_rewards: reward_info.pending_rewards,
+    })
+}
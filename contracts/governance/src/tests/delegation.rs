@@ -0,0 +1,374 @@
+use crate::contract::{execute, query};
+use crate::tests::common::{
+    DEFAULT_PROPOSAL_DEPOSIT, DEFAULT_VOTING_PERIOD, TEST_CREATOR, TEST_VOTER, TEST_VOTER_2,
+    TEST_VOTER_3, VOTING_TOKEN,
+};
+use crate::tests::mock_querier::{mock_dependencies, WasmMockQuerier};
+use crate::tests::{instantiate, poll};
+use crate::ContractError;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{attr, from_binary, to_binary, OwnedDeps, Uint128};
+use cw20::Cw20ReceiveMsg;
+use white_whale::governance::msg::{ExecuteMsg, QueryMsg};
+use white_whale::governance::state::{
+    Cw20HookMsg, DelegationsResponse, DelegationsResponseItem, VoteOption,
+};
+
+fn stake_voting_tokens(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    voter: &str,
+    amount: u128,
+    contract_balance: u128,
+) {
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(contract_balance),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: voter.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+}
+
+fn create_poll(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) {
+    let msg = poll::create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+}
+
+#[test]
+fn delegate_cast_vote_with_delegated_voting_power() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    stake_voting_tokens(&mut deps, TEST_VOTER, 100, 100);
+    stake_voting_tokens(&mut deps, TEST_VOTER_2, 50, 150);
+
+    let msg = ExecuteMsg::DelegateVotingPower {
+        delegate: TEST_VOTER.to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "delegate_voting_power"),
+            attr("module", "governance"),
+            attr("delegator", TEST_VOTER_2),
+            attr("delegate", TEST_VOTER),
+            attr("share", "50"),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Delegations {
+            delegator: None,
+            delegate: Some(TEST_VOTER.to_string()),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let response: DelegationsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        response.delegations,
+        vec![DelegationsResponseItem {
+            delegator: TEST_VOTER_2.to_string(),
+            delegate: TEST_VOTER.to_string(),
+        }]
+    );
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(150 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    create_poll(&mut deps);
+
+    // the delegator can no longer vote itself
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::from(50u128),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VotingPowerDelegated {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(151u128),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InsufficientStaked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(150u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    poll::assert_cast_vote_success(TEST_VOTER, 150, 1, VoteOption::Yes, res);
+
+    // the delegated stake stays locked while the delegate's votes are in progress
+    let msg = ExecuteMsg::UndelegateVotingPower {};
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VotingPowerLocked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::WithdrawVotingTokens { amount: None };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VotingPowerLocked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let mut env = mock_env();
+    env.block.height += DEFAULT_VOTING_PERIOD;
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    execute(deps.as_mut(), env, mock_info(TEST_CREATOR, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UndelegateVotingPower {};
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "undelegate_voting_power"),
+            attr("module", "governance"),
+            attr("delegator", TEST_VOTER_2),
+            attr("share", "50"),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Delegations {
+            delegator: Some(TEST_VOTER_2.to_string()),
+            delegate: None,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let response: DelegationsResponse = from_binary(&res).unwrap();
+    assert_eq!(response.delegations, vec![]);
+}
+
+#[test]
+fn undelegate_share_not_tallied_by_delegate() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    stake_voting_tokens(&mut deps, TEST_VOTER, 100, 100);
+    stake_voting_tokens(&mut deps, TEST_VOTER_2, 50, 150);
+
+    let msg = ExecuteMsg::DelegateVotingPower {
+        delegate: TEST_VOTER.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(150 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    create_poll(&mut deps);
+
+    // the delegate only votes with 20 of the delegated 50
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(120u128),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UndelegateVotingPower {};
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VotingPowerLocked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::from(31u128)),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VotingPowerLocked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    // the untallied 30 can be withdrawn
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::from(30u128)),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(120 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+
+    // the remaining delegated stake backs the tallied votes
+    let msg = ExecuteMsg::UndelegateVotingPower {};
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VotingPowerLocked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn delegated_share_follows_delegator_stake() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    stake_voting_tokens(&mut deps, TEST_VOTER, 100, 100);
+    stake_voting_tokens(&mut deps, TEST_VOTER_2, 50, 150);
+
+    let msg = ExecuteMsg::DelegateVotingPower {
+        delegate: TEST_VOTER.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+
+    stake_voting_tokens(&mut deps, TEST_VOTER_2, 50, 200);
+
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::from(20u128)),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(180 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    create_poll(&mut deps);
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(181u128),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InsufficientStaked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(180u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    poll::assert_cast_vote_success(TEST_VOTER, 180, 1, VoteOption::Yes, res);
+}
+
+#[test]
+fn fails_invalid_delegations() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    let msg = ExecuteMsg::DelegateVotingPower {
+        delegate: TEST_VOTER_2.to_string(),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NothingStaked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    stake_voting_tokens(&mut deps, TEST_VOTER, 100, 100);
+
+    let msg = ExecuteMsg::DelegateVotingPower {
+        delegate: TEST_VOTER.to_string(),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidDelegate {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::UndelegateVotingPower {};
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotDelegated {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::DelegateVotingPower {
+        delegate: TEST_VOTER_2.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::DelegateVotingPower {
+        delegate: TEST_VOTER_3.to_string(),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::AlreadyDelegated {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fails_delegate_with_votes_in_progress() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    stake_voting_tokens(&mut deps, TEST_VOTER, 100, 100);
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(100 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    create_poll(&mut deps);
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(100u128),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::DelegateVotingPower {
+        delegate: TEST_VOTER_2.to_string(),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VotingPowerLocked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
@@ -10,8 +10,12 @@ use crate::tests::common::{
 use crate::tests::mock_querier::mock_dependencies;
 use crate::tests::poll::mock_register_voting_token;
 use crate::ContractError;
+use white_whale::error::CommonError;
 use white_whale::governance::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use white_whale::governance::state::{config_read, state_read, Config, ConfigResponse, State};
+use white_whale::governance::state::{
+    CategoryParams, Config, ConfigResponse, DepositSlashPolicy, PollCategory, QuorumMode, State,
+    StateResponse, CONFIG, STATE,
+};
 
 pub(crate) fn instantiate_msg() -> InstantiateMsg {
     InstantiateMsg {
@@ -22,6 +26,14 @@ pub(crate) fn instantiate_msg() -> InstantiateMsg {
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     }
 }
 
@@ -37,6 +49,14 @@ pub fn mock_instantiate(deps: DepsMut) {
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     };
 
     let info = mock_info(TEST_CREATOR, &[]);
@@ -56,7 +76,7 @@ fn successful_initialization() {
     let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
     assert_eq!(0, res.messages.len());
 
-    let config: Config = config_read(deps.as_ref().storage).load().unwrap();
+    let config: Config = CONFIG.load(deps.as_ref().storage).unwrap();
     assert_eq!(
         config,
         Config {
@@ -68,7 +88,18 @@ fn successful_initialization() {
             timelock_period: DEFAULT_TIMELOCK_PERIOD,
             expiration_period: DEFAULT_EXPIRATION_PERIOD,
             proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
-            snapshot_period: DEFAULT_FIX_PERIOD
+            snapshot_period: DEFAULT_FIX_PERIOD,
+            deposit_slash_policy: DepositSlashPolicy::DistributeToStakers,
+            treasury_addr: None,
+            quorum_mode: QuorumMode::EndPoll,
+            voter_reward_ratio: Decimal::zero(),
+            protected_methods: vec![
+                "register_contracts".to_string(),
+                "execute_poll_msgs".to_string()
+            ],
+            category_params: vec![],
+            guardian: None,
+            native_proposal_deposit: None,
         }
     );
 
@@ -76,13 +107,13 @@ fn successful_initialization() {
         whale_token: VOTING_TOKEN.to_string(),
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    let config: Config = config_read(deps.as_ref().storage).load().unwrap();
+    let config: Config = CONFIG.load(deps.as_ref().storage).unwrap();
     assert_eq!(
         config.whale_token,
         deps.api.addr_canonicalize(&VOTING_TOKEN).unwrap()
     );
 
-    let state: State = state_read(deps.as_ref().storage).load().unwrap();
+    let state: State = STATE.load(deps.as_ref().storage).unwrap();
     assert_eq!(
         state,
         State {
@@ -94,6 +125,55 @@ fn successful_initialization() {
     );
 }
 
+/**
+ * Tests the config and state queries right after instantiation.
+ */
+#[test]
+fn query_config_and_state() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: TEST_CREATOR.to_string(),
+            whale_token: VOTING_TOKEN.to_string(),
+            quorum: Decimal::percent(DEFAULT_QUORUM),
+            threshold: Decimal::percent(DEFAULT_THRESHOLD),
+            voting_period: DEFAULT_VOTING_PERIOD,
+            timelock_period: DEFAULT_TIMELOCK_PERIOD,
+            expiration_period: DEFAULT_EXPIRATION_PERIOD,
+            proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            snapshot_period: DEFAULT_FIX_PERIOD,
+            deposit_slash_policy: DepositSlashPolicy::DistributeToStakers,
+            treasury_addr: None,
+            quorum_mode: QuorumMode::EndPoll,
+            voter_reward_ratio: Decimal::zero(),
+            protected_methods: vec![
+                "register_contracts".to_string(),
+                "execute_poll_msgs".to_string()
+            ],
+            category_params: vec![],
+            guardian: None,
+            native_proposal_deposit: None,
+        }
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        state,
+        StateResponse {
+            poll_count: 0,
+            total_share: Uint128::zero(),
+            total_deposit: Uint128::zero(),
+        }
+    );
+}
+
 /**
  * Tests unsuccessful instantiation of the contract.
  */
@@ -138,6 +218,179 @@ fn invalid_poll_period_fails_initialization() {
     }
 }
 
+#[test]
+fn invalid_snapshot_period_fails_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.snapshot_period = msg.voting_period;
+
+    let info = mock_info(TEST_CREATOR, &coins(2, VOTING_TOKEN));
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSnapshotPeriod {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn zero_config_values_fail_initialization() {
+    let info = mock_info(TEST_CREATOR, &coins(2, VOTING_TOKEN));
+
+    let mut msg = instantiate_msg();
+    msg.quorum = Decimal::zero();
+    let res = instantiate(
+        mock_dependencies(&[]).as_mut(),
+        mock_env(),
+        info.clone(),
+        msg,
+    );
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollQuorumInvalidValue(_)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let mut msg = instantiate_msg();
+    msg.voting_period = 0;
+    let res = instantiate(
+        mock_dependencies(&[]).as_mut(),
+        mock_env(),
+        info.clone(),
+        msg,
+    );
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidZeroPeriod {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let mut msg = instantiate_msg();
+    msg.proposal_deposit = Uint128::zero();
+    let res = instantiate(mock_dependencies(&[]).as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidZeroProposalDeposit {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn invalid_deposit_slash_policy_fails_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.deposit_slash_policy = Some(DepositSlashPolicy::SendToTreasury);
+
+    let info = mock_info(TEST_CREATOR, &coins(2, VOTING_TOKEN));
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NoTreasuryAddr {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn invalid_voter_reward_ratio_fails_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.voter_reward_ratio = Some(Decimal::percent(101));
+
+    let info = mock_info(TEST_CREATOR, &coins(2, VOTING_TOKEN));
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VoterRewardRatioInvalidValue(_)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn invalid_treasury_addr_fails_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.treasury_addr = Some("x".to_string());
+
+    let info = mock_info(TEST_CREATOR, &coins(2, VOTING_TOKEN));
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Common(CommonError::InvalidAddress { field, .. })) => {
+            assert_eq!(field, "treasury_addr")
+        }
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn invalid_category_params_fails_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.category_params = Some(vec![
+        (PollCategory::Spend, CategoryParams::default()),
+        (PollCategory::Spend, CategoryParams::default()),
+    ]);
+
+    let info = mock_info(TEST_CREATOR, &coins(2, VOTING_TOKEN));
+    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::DuplicateCategoryParams(category)) => assert_eq!(category, "spend"),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let mut msg = instantiate_msg();
+    msg.category_params = Some(vec![(
+        PollCategory::Spend,
+        CategoryParams {
+            threshold: Some(Decimal::percent(101)),
+            ..CategoryParams::default()
+        },
+    )]);
+    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollThresholdInvalidValue(_)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    // the category voting period must be longer than the snapshot period
+    let mut msg = instantiate_msg();
+    msg.category_params = Some(vec![(
+        PollCategory::Spend,
+        CategoryParams {
+            voting_period: Some(DEFAULT_FIX_PERIOD),
+            ..CategoryParams::default()
+        },
+    )]);
+    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSnapshotPeriod {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let mut msg = instantiate_msg();
+    msg.category_params = Some(vec![(
+        PollCategory::Spend,
+        CategoryParams {
+            proposal_deposit: Some(Uint128::zero()),
+            ..CategoryParams::default()
+        },
+    )]);
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidZeroProposalDeposit {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
 /**
  * Tests updating the configuration of the contract.
  */
@@ -148,7 +401,7 @@ fn successful_update_config() {
     mock_register_voting_token(deps.as_mut());
 
     // update owner
-    let info = mock_info(TEST_CREATOR, &[]);
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
     let msg = ExecuteMsg::UpdateConfig {
         owner: Some("addr0001".to_string()),
         quorum: None,
@@ -158,6 +411,14 @@ fn successful_update_config() {
         expiration_period: None,
         proposal_deposit: None,
         snapshot_period: None,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -173,8 +434,8 @@ fn successful_update_config() {
     assert_eq!(DEFAULT_TIMELOCK_PERIOD, config.timelock_period);
     assert_eq!(DEFAULT_PROPOSAL_DEPOSIT, config.proposal_deposit.u128());
 
-    // update left items with the new owner
-    let info = mock_info("addr0001", &[]);
+    // update left items, still through the contract itself
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
     let msg = ExecuteMsg::UpdateConfig {
         owner: None,
         quorum: Some(Decimal::percent(20)),
@@ -184,6 +445,20 @@ fn successful_update_config() {
         expiration_period: Some(30000u64),
         proposal_deposit: Some(Uint128::from(123u128)),
         snapshot_period: Some(11),
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: Some(vec![]),
+        category_params: Some(vec![(
+            PollCategory::Spend,
+            CategoryParams {
+                quorum: Some(Decimal::percent(50)),
+                ..CategoryParams::default()
+            },
+        )]),
+        guardian: Some("guardian".to_string()),
+        native_proposal_deposit: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -200,6 +475,18 @@ fn successful_update_config() {
     assert_eq!(30000u64, config.expiration_period);
     assert_eq!(123u128, config.proposal_deposit.u128());
     assert_eq!(11u64, config.snapshot_period);
+    assert!(config.protected_methods.is_empty());
+    assert_eq!(
+        config.category_params,
+        vec![(
+            PollCategory::Spend,
+            CategoryParams {
+                quorum: Some(Decimal::percent(50)),
+                ..CategoryParams::default()
+            },
+        )]
+    );
+    assert_eq!(config.guardian, Some("guardian".to_string()));
 }
 
 #[test]
@@ -208,23 +495,67 @@ fn unsuccessful_update_config() {
     mock_instantiate(deps.as_mut());
     mock_register_voting_token(deps.as_mut());
 
-    // Unauthorized user
-    let info = mock_info("unauthorized_addr", &[]);
+    // Unauthorized user, even the owner can only update the config through a poll
+    for sender in ["unauthorized_addr", TEST_CREATOR] {
+        let info = mock_info(sender, &[]);
+        let msg = ExecuteMsg::UpdateConfig {
+            owner: None,
+            quorum: None,
+            threshold: None,
+            voting_period: None,
+            timelock_period: None,
+            expiration_period: None,
+            proposal_deposit: None,
+            snapshot_period: None,
+            deposit_slash_policy: None,
+            treasury_addr: None,
+            quorum_mode: None,
+            voter_reward_ratio: None,
+            protected_methods: None,
+            category_params: None,
+            guardian: None,
+            native_proposal_deposit: None,
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        match res {
+            Err(ContractError::Unauthorized {}) => (),
+            _ => panic!("Must return unauthorized error"),
+        }
+    }
+}
+
+#[test]
+fn unsuccessful_update_config_zero_period() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
     let msg = ExecuteMsg::UpdateConfig {
         owner: None,
         quorum: None,
         threshold: None,
-        voting_period: None,
+        voting_period: Some(0u64),
         timelock_period: None,
         expiration_period: None,
         proposal_deposit: None,
         snapshot_period: None,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     match res {
-        Err(ContractError::Unauthorized {}) => (),
-        _ => panic!("Must return unauthorized error"),
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidZeroPeriod {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
     }
 }
 
@@ -235,7 +566,7 @@ fn unsuccessful_update_config_invalid_poll_period() {
     mock_register_voting_token(deps.as_mut());
 
     // update invalid timelock and expiration periods
-    let info = mock_info(TEST_CREATOR, &[]);
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
     let msg = ExecuteMsg::UpdateConfig {
         owner: None,
         quorum: None,
@@ -245,6 +576,14 @@ fn unsuccessful_update_config_invalid_poll_period() {
         expiration_period: Some(10000u64),
         proposal_deposit: None,
         snapshot_period: None,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -255,6 +594,41 @@ fn unsuccessful_update_config_invalid_poll_period() {
     }
 }
 
+#[test]
+fn unsuccessful_update_config_invalid_snapshot_period() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    // the snapshot period must stay shorter than the shortened voting period
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        quorum: None,
+        threshold: None,
+        voting_period: Some(DEFAULT_FIX_PERIOD),
+        timelock_period: None,
+        expiration_period: None,
+        proposal_deposit: None,
+        snapshot_period: None,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSnapshotPeriod {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
 #[test]
 fn unsuccessful_update_config_invalid_quorum() {
     let mut deps = mock_dependencies(&[]);
@@ -262,7 +636,7 @@ fn unsuccessful_update_config_invalid_quorum() {
     mock_register_voting_token(deps.as_mut());
 
     // invalid quorum, MAX_QUORUM: Decimal = Decimal::one();
-    let info = mock_info(TEST_CREATOR, &[]);
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
     let msg = ExecuteMsg::UpdateConfig {
         owner: None,
         quorum: Some(Decimal::from_ratio(
@@ -275,6 +649,14 @@ fn unsuccessful_update_config_invalid_quorum() {
         expiration_period: None,
         proposal_deposit: None,
         snapshot_period: None,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -292,7 +674,7 @@ fn unsuccessful_update_config_invalid_threshold() {
     mock_register_voting_token(deps.as_mut());
 
     // invalid threshold, MAX_THRESHOLD: Decimal = Decimal::one();
-    let info = mock_info(TEST_CREATOR, &[]);
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
     let msg = ExecuteMsg::UpdateConfig {
         owner: None,
         quorum: None,
@@ -305,6 +687,14 @@ fn unsuccessful_update_config_invalid_threshold() {
         expiration_period: None,
         proposal_deposit: None,
         snapshot_period: None,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -314,3 +704,39 @@ fn unsuccessful_update_config_invalid_threshold() {
         Err(e) => panic!("Unexpected error: {:?}", e),
     }
 }
+
+#[test]
+fn unsuccessful_update_config_invalid_address() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some("x".to_string()),
+        quorum: None,
+        threshold: None,
+        voting_period: None,
+        timelock_period: None,
+        expiration_period: None,
+        proposal_deposit: None,
+        snapshot_period: None,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Common(CommonError::InvalidAddress { field, .. })) => {
+            assert_eq!(field, "owner")
+        }
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
@@ -2,13 +2,13 @@
 
 use cosmwasm_std::{to_binary, Addr, BlockInfo, Decimal, Empty, Timestamp, Uint128};
 
-use white_whale::governance::msg::{ExecuteMsg, InstantiateMsg};
+use white_whale::governance::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
 use crate::tests::common::{
     DEFAULT_EXPIRATION_PERIOD, DEFAULT_FIX_PERIOD, DEFAULT_QUORUM, DEFAULT_THRESHOLD,
     DEFAULT_TIMELOCK_PERIOD, DEFAULT_VOTING_PERIOD,
 };
-use white_whale::governance::state::{Cw20HookMsg, PollExecuteMsg, VoteOption};
+use white_whale::governance::state::{Cw20HookMsg, PollExecuteMsg, PollResponse, VoteOption};
 
 use crate::tests::tswap_mock::{contract_receiver_mock, MockInstantiateMsg};
 use stablecoin_vault::contract::{execute, instantiate, query, reply};
@@ -53,7 +53,8 @@ pub fn contract_gov() -> Box<dyn Contract<Empty>> {
         crate::contract::execute,
         crate::contract::instantiate,
         crate::contract::query,
-    );
+    )
+    .with_reply(crate::contract::reply);
     Box::new(contract)
 }
 
@@ -110,6 +111,14 @@ fn gov_can_update_the_stable_cap_parameter_of_vault() {
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::new(1),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     };
 
     // Store whale token which is a CW20 and get its code ID
@@ -236,7 +245,10 @@ fn gov_can_update_the_stable_cap_parameter_of_vault() {
         title: "test".to_string(),
         description: "test".to_string(),
         link: None,
+        metadata_hash: None,
         execute_msgs: Some(execute_msgs.clone()),
+        poll_type: None,
+        category: None,
     };
     let send_msg = Cw20ExecuteMsg::Send {
         contract: gov_addr.to_string(),
@@ -345,6 +357,14 @@ fn gov_can_update_vault_config_through_polls() {
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::new(1),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
     };
 
     // Store whale token which is a CW20 and get its code ID
@@ -470,7 +490,10 @@ fn gov_can_update_vault_config_through_polls() {
         title: "test".to_string(),
         description: "test".to_string(),
         link: None,
+        metadata_hash: None,
         execute_msgs: Some(execute_msgs.clone()),
+        poll_type: None,
+        category: None,
     };
     let send_msg = Cw20ExecuteMsg::Send {
         contract: gov_addr.to_string(),
@@ -526,3 +549,136 @@ fn gov_can_update_vault_config_through_polls() {
         .query_wasm_smart(vault_addr.clone(), &config_msg)
         .unwrap();
 }
+
+#[test]
+// a staker can cast a single vote on a poll, limited to the amount they have staked
+fn gov_staker_can_cast_vote_once_within_stake() {
+    let owner = Addr::unchecked("owner");
+    let gov_staker = Addr::unchecked("gov_staker");
+    let mut router = mock_app();
+    let gov_id = router.store_code(contract_gov());
+    let whale_token_id = router.store_code(contract_whale_token());
+
+    router.set_block(BlockInfo {
+        height: 0,
+        time: Timestamp::from_seconds(1000),
+        chain_id: "terra-cosmwasm-testnet".to_string(),
+    });
+
+    let gov_msg = InstantiateMsg {
+        quorum: Decimal::percent(DEFAULT_QUORUM),
+        threshold: Decimal::percent(DEFAULT_THRESHOLD),
+        voting_period: DEFAULT_VOTING_PERIOD,
+        timelock_period: DEFAULT_TIMELOCK_PERIOD,
+        expiration_period: DEFAULT_EXPIRATION_PERIOD,
+        proposal_deposit: Uint128::new(1),
+        snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_slash_policy: None,
+        treasury_addr: None,
+        quorum_mode: None,
+        voter_reward_ratio: None,
+        protected_methods: None,
+        category_params: None,
+        guardian: None,
+        native_proposal_deposit: None,
+    };
+    let msg = cw20_base::msg::InstantiateMsg {
+        name: "White Whale".to_string(),
+        symbol: "WHALE".to_string(),
+        decimals: 2,
+        initial_balances: vec![Cw20Coin {
+            address: gov_staker.to_string(),
+            amount: Uint128::new(5000),
+        }],
+        mint: None,
+        marketing: None,
+    };
+    let whale_token_instance = router
+        .instantiate_contract(whale_token_id, owner.clone(), &msg, &[], "WHALE", None)
+        .unwrap();
+    let gov_addr = router
+        .instantiate_contract(gov_id, owner.clone(), &gov_msg, &[], "GOV", None)
+        .unwrap();
+
+    let msg = ExecuteMsg::RegisterContracts {
+        whale_token: whale_token_instance.to_string(),
+    };
+    router
+        .execute_contract(gov_staker.clone(), gov_addr.clone(), &msg, &[])
+        .unwrap();
+
+    // Stake 1000 WHALE
+    let send_msg = Cw20ExecuteMsg::Send {
+        contract: gov_addr.to_string(),
+        amount: Uint128::new(1000),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    };
+    router
+        .execute_contract(
+            gov_staker.clone(),
+            whale_token_instance.clone(),
+            &send_msg,
+            &[],
+        )
+        .unwrap();
+
+    // Create a text poll
+    let create_msg = Cw20HookMsg::CreatePoll {
+        title: "test".to_string(),
+        description: "test".to_string(),
+        link: None,
+        metadata_hash: None,
+        execute_msgs: None,
+        poll_type: None,
+        category: None,
+    };
+    let send_msg = Cw20ExecuteMsg::Send {
+        contract: gov_addr.to_string(),
+        amount: Uint128::new(4000),
+        msg: to_binary(&create_msg).unwrap(),
+    };
+    router
+        .execute_contract(
+            gov_staker.clone(),
+            whale_token_instance.clone(),
+            &send_msg,
+            &[],
+        )
+        .unwrap();
+
+    // Voting with more than the staked amount fails
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::new(1001),
+    };
+    assert!(router
+        .execute_contract(gov_staker.clone(), gov_addr.clone(), &msg, &[])
+        .is_err());
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::new(1000),
+    };
+    router
+        .execute_contract(gov_staker.clone(), gov_addr.clone(), &msg, &[])
+        .unwrap();
+
+    // Voting a second time fails
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::new(1),
+    };
+    assert!(router
+        .execute_contract(gov_staker.clone(), gov_addr.clone(), &msg, &[])
+        .is_err());
+
+    let poll: PollResponse = router
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::Poll { poll_id: 1 })
+        .unwrap();
+    assert_eq!(poll.yes_votes, Uint128::new(1000));
+    assert_eq!(poll.no_votes, Uint128::zero());
+}
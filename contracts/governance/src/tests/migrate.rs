@@ -0,0 +1,202 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Api, Decimal, Uint128};
+use cosmwasm_storage::{bucket, bucket_read, singleton, Bucket, ReadonlyBucket};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::U64Key;
+
+use crate::contract::migrate;
+use crate::migrations::{ConfigV0, PollV0};
+use crate::tests::common::{
+    DEFAULT_EXPIRATION_PERIOD, DEFAULT_FIX_PERIOD, DEFAULT_PROPOSAL_DEPOSIT, DEFAULT_QUORUM,
+    DEFAULT_THRESHOLD, DEFAULT_TIMELOCK_PERIOD, DEFAULT_VOTING_PERIOD, TEST_CREATOR, VOTING_TOKEN,
+};
+use crate::tests::instantiate::mock_instantiate;
+use crate::tests::mock_querier::mock_dependencies;
+use crate::ContractError;
+use white_whale::governance::msg::MigrateMsg;
+use white_whale::governance::state::{
+    polls, read_polls, DepositSlashPolicy, Poll, PollCategory, PollStatus, PollType, QuorumMode,
+    CONFIG,
+};
+
+#[test]
+fn migrate_unversioned_state() {
+    let mut deps = mock_dependencies(&[]);
+
+    let creator = deps.api.addr_canonicalize(TEST_CREATOR).unwrap();
+    singleton(&mut deps.storage, b"config")
+        .save(&ConfigV0 {
+            owner: creator.clone(),
+            whale_token: deps.api.addr_canonicalize(VOTING_TOKEN).unwrap(),
+            quorum: Decimal::percent(DEFAULT_QUORUM),
+            threshold: Decimal::percent(DEFAULT_THRESHOLD),
+            voting_period: DEFAULT_VOTING_PERIOD,
+            timelock_period: DEFAULT_TIMELOCK_PERIOD,
+            expiration_period: DEFAULT_EXPIRATION_PERIOD,
+            proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            snapshot_period: DEFAULT_FIX_PERIOD,
+        })
+        .unwrap();
+    bucket(&mut deps.storage, b"poll")
+        .save(
+            &1u64.to_be_bytes(),
+            &PollV0 {
+                id: 1u64,
+                creator,
+                status: PollStatus::InProgress,
+                yes_votes: Uint128::from(10u128),
+                no_votes: Uint128::from(5u128),
+                end_height: 100u64,
+                title: "title".to_string(),
+                description: "description".to_string(),
+                link: None,
+                execute_data: None,
+                deposit_amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+                total_balance_at_end_poll: None,
+                staked_amount: None,
+            },
+        )
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let poll = polls().load(&deps.storage, U64Key::new(1u64)).unwrap();
+    assert_eq!(poll.yes_votes, Uint128::from(10u128));
+    assert_eq!(poll.no_votes, Uint128::from(5u128));
+    assert_eq!(poll.abstain_votes, Uint128::zero());
+    assert_eq!(poll.poll_type, PollType::Text);
+    assert_eq!(poll.category, PollCategory::Text);
+    let in_progress_polls = read_polls(
+        &deps.storage,
+        Some(PollStatus::InProgress),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(in_progress_polls, vec![poll]);
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.quorum, Decimal::percent(DEFAULT_QUORUM));
+    assert_eq!(
+        config.deposit_slash_policy,
+        DepositSlashPolicy::DistributeToStakers
+    );
+    assert_eq!(config.treasury_addr, None);
+    assert_eq!(config.quorum_mode, QuorumMode::EndPoll);
+    assert_eq!(config.voter_reward_ratio, Decimal::zero());
+    assert_eq!(
+        config.protected_methods,
+        vec![
+            "register_contracts".to_string(),
+            "execute_poll_msgs".to_string()
+        ]
+    );
+    assert_eq!(config.category_params, vec![]);
+    assert_eq!(config.guardian, None);
+
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn migrate_versioned_state() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    set_contract_version(&mut deps.storage, "crates.io:ww-governance", "0.0.1").unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    // the state was already up to date
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap().owner,
+        deps.api.addr_canonicalize(TEST_CREATOR).unwrap()
+    );
+}
+
+#[test]
+fn migrate_poll_indexes() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    set_contract_version(&mut deps.storage, "crates.io:ww-governance", "0.1.0").unwrap();
+
+    // a poll stored with the legacy bucket and status indexer
+    let poll = Poll {
+        id: 1u64,
+        creator: deps.api.addr_canonicalize(TEST_CREATOR).unwrap(),
+        status: PollStatus::Passed,
+        yes_votes: Uint128::from(10u128),
+        no_votes: Uint128::zero(),
+        abstain_votes: Uint128::zero(),
+        end_height: 100u64,
+        title: "title".to_string(),
+        description: "description".to_string(),
+        link: None,
+        metadata_hash: None,
+        execute_data: None,
+        deposit_amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        total_balance_at_end_poll: None,
+        staked_amount: None,
+        staked_amount_at_creation: None,
+        deposit_denom: None,
+        poll_type: PollType::Text,
+        category: PollCategory::Text,
+    };
+    bucket(&mut deps.storage, b"poll")
+        .save(&1u64.to_be_bytes(), &poll)
+        .unwrap();
+    Bucket::multilevel(&mut deps.storage, &[b"poll_indexer", b"Passed"])
+        .save(&1u64.to_be_bytes(), &true)
+        .unwrap();
+    assert!(
+        read_polls(&deps.storage, Some(PollStatus::Passed), None, None, None)
+            .unwrap()
+            .is_empty()
+    );
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    // the poll kept its raw key and is now indexed
+    let stored: Poll = bucket_read(&deps.storage, b"poll")
+        .load(&1u64.to_be_bytes())
+        .unwrap();
+    assert_eq!(stored, poll);
+    let passed_polls =
+        read_polls(&deps.storage, Some(PollStatus::Passed), None, None, None).unwrap();
+    assert_eq!(passed_polls, vec![poll]);
+    let legacy_index: Option<bool> =
+        ReadonlyBucket::multilevel(&deps.storage, &[b"poll_indexer", b"Passed"])
+            .may_load(&1u64.to_be_bytes())
+            .unwrap();
+    assert_eq!(legacy_index, None);
+}
+
+#[test]
+fn fails_migrate_other_contract() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    set_contract_version(&mut deps.storage, "crates.io:ww-treasury", "0.0.1").unwrap();
+
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidMigrationContract(contract, _)) => {
+            assert_eq!(contract, "crates.io:ww-treasury")
+        }
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fails_migrate_invalid_stored_version() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    set_contract_version(&mut deps.storage, "crates.io:ww-governance", "not_semver").unwrap();
+
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::SemVer(_)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
@@ -1,9 +1,13 @@
 mod common;
+mod delegation;
 pub mod instantiate;
 mod integration_test;
+mod migrate;
 mod mock_querier;
+mod native_deposit;
 mod poll;
 mod staking;
+mod staking_rewards;
 pub mod tswap_mock;
 mod validators;
 mod voting;
@@ -0,0 +1,166 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coin, coins, from_binary, BankMsg, CosmosMsg, DepsMut, Uint128};
+
+use crate::contract::{execute, instantiate, query};
+use crate::tests::common::{mock_env_height, DEFAULT_VOTING_PERIOD, TEST_CREATOR};
+use crate::tests::instantiate::instantiate_msg;
+use crate::tests::mock_querier::mock_dependencies;
+use crate::tests::poll::mock_register_voting_token;
+use crate::ContractError;
+use white_whale::governance::msg::{ExecuteMsg, QueryMsg};
+use white_whale::governance::state::{PollResponse, PollStatus, StateResponse};
+
+const NATIVE_DEPOSIT: u128 = 100u128;
+
+fn mock_instantiate_native_deposit(deps: DepsMut) {
+    let mut msg = instantiate_msg();
+    msg.native_proposal_deposit = Some(coin(NATIVE_DEPOSIT, "uusd"));
+    instantiate(deps, mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+}
+
+fn create_poll_msg() -> ExecuteMsg {
+    ExecuteMsg::CreatePoll {
+        title: "test".to_string(),
+        description: "test".to_string(),
+        link: None,
+        metadata_hash: None,
+        execute_msgs: None,
+        poll_type: None,
+        category: None,
+    }
+}
+
+#[test]
+fn create_and_cancel_native_deposit_poll() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate_native_deposit(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &coins(NATIVE_DEPOSIT, "uusd")),
+        create_poll_msg(),
+    )
+    .unwrap();
+
+    let poll: PollResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(poll.deposit_amount, Uint128::from(NATIVE_DEPOSIT));
+    assert_eq!(poll.deposit_denom, Some("uusd".to_string()));
+
+    // native deposits are not part of the WHALE deposits
+    let state: StateResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap();
+    assert!(state.total_deposit.is_zero());
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::CancelPoll { poll_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: TEST_CREATOR.to_string(),
+            amount: coins(NATIVE_DEPOSIT, "uusd"),
+        })
+    );
+}
+
+#[test]
+fn end_poll_burns_native_deposit() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate_native_deposit(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &coins(NATIVE_DEPOSIT, "uusd")),
+        create_poll_msg(),
+    )
+    .unwrap();
+
+    // without a treasury, native deposits of polls not reaching quorum are burned
+    let env = mock_env_height(mock_env().block.height + DEFAULT_VOTING_PERIOD, 10000);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::EndPoll { poll_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: coins(NATIVE_DEPOSIT, "uusd"),
+        })
+    );
+
+    let poll: PollResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(poll.status, PollStatus::Rejected);
+}
+
+#[test]
+fn fails_create_native_deposit_poll() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+    mock_register_voting_token(deps.as_mut());
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &coins(NATIVE_DEPOSIT, "uusd")),
+        create_poll_msg(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NoNativeProposalDeposit {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate_native_deposit(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    for funds in [
+        vec![],
+        coins(NATIVE_DEPOSIT, "uluna"),
+        vec![coin(NATIVE_DEPOSIT, "uusd"), coin(NATIVE_DEPOSIT, "uluna")],
+    ] {
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(TEST_CREATOR, &funds),
+            create_poll_msg(),
+        ) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidNativeProposalDeposit(denom)) => assert_eq!(denom, "uusd"),
+            Err(e) => panic!("Unexpected error: {}", e),
+        }
+    }
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &coins(NATIVE_DEPOSIT - 1, "uusd")),
+        create_poll_msg(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InsufficientProposalDeposit(amount)) => {
+            assert_eq!(amount, NATIVE_DEPOSIT)
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
@@ -1,12 +1,13 @@
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, coins, from_binary, to_binary, Addr, Api, CosmosMsg, Deps, DepsMut, Response, SubMsg,
-    Uint128, WasmMsg,
+    attr, coins, from_binary, to_binary, Addr, Api, Binary, ContractResult, CosmosMsg, Decimal,
+    Deps, DepsMut, Reply, Response, SubMsg, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
 use terraswap::querier::query_token_balance;
 
-use crate::contract::{execute, query};
+use crate::contract::{execute, query, reply, MAX_POLL_EXECUTE_MSGS, POLL_EXECUTE_REPLY_ID};
 use crate::tests::common::{
     mock_env_height, DEFAULT_EXPIRATION_PERIOD, DEFAULT_PROPOSAL_DEPOSIT, DEFAULT_TIMELOCK_PERIOD,
     DEFAULT_VOTING_PERIOD, TEST_CREATOR, TEST_VOTER, TEST_VOTER_2, TEST_VOTER_3, VOTING_TOKEN,
@@ -14,11 +15,15 @@ use crate::tests::common::{
 use crate::tests::mock_querier::mock_dependencies;
 use crate::tests::{common, instantiate};
 use crate::ContractError;
+use white_whale::error::CommonError;
 use white_whale::governance::msg::{ExecuteMsg, QueryMsg};
 use white_whale::governance::state::{
-    bank_read, poll_voter_read, state_read, Cw20HookMsg, OrderBy, PollExecuteMsg, PollResponse,
-    PollStatus, PollsResponse, StakerResponse, State, VoteOption, VoterInfo, VotersResponse,
+    polls, CategoryParams, Cw20HookMsg, DepositSlashPolicy, ExecuteData, OrderBy,
+    PendingVotingRewardsResponse, Poll, PollCategory, PollExecutablesResponse, PollExecuteMsg,
+    PollResponse, PollStatus, PollType, PollsResponse, QuorumMode, StakerResponse, State,
+    VoteOption, VoterInfo, VotersResponse, BANK, POLL_VOTERS, STATE,
 };
+use white_whale::pause::{PauseScope, PausedScopesResponse};
 
 pub fn mock_register_voting_token(deps: DepsMut) {
     let info = mock_info(TEST_CREATOR, &[]);
@@ -42,7 +47,10 @@ pub fn create_poll_msg(
             title,
             description,
             link,
+            metadata_hash: None,
             execute_msgs: execute_msg,
+            poll_type: None,
+            category: None,
         })
         .unwrap(),
     })
@@ -62,6 +70,7 @@ pub fn assert_create_poll_result(
         execute_res.attributes,
         vec![
             attr("action", "create_poll"),
+            attr("module", "governance"),
             attr("creator", creator),
             attr("poll_id", poll_id.to_string()),
             attr("end_height", end_height.to_string()),
@@ -69,7 +78,7 @@ pub fn assert_create_poll_result(
     );
 
     //confirm poll count
-    let state: State = state_read(deps.storage).load().unwrap();
+    let state: State = STATE.load(deps.storage).unwrap();
     assert_eq!(
         state,
         State {
@@ -93,11 +102,11 @@ pub fn assert_stake_tokens_result(
     deps: Deps,
 ) {
     assert_eq!(
-        execute_res.attributes.get(2).expect("no log"),
+        execute_res.attributes.get(3).expect("no log"),
         &attr("share", new_share.to_string())
     );
 
-    let state: State = state_read(deps.storage).load().unwrap();
+    let state: State = STATE.load(deps.storage).unwrap();
     assert_eq!(
         state,
         State {
@@ -123,6 +132,7 @@ pub fn assert_cast_vote_success(
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", poll_id.to_string()),
             attr("amount", amount.to_string()),
             attr("voter", voter),
@@ -198,6 +208,216 @@ fn add_several_execute_msgs() {
     let response_execute_data = value.execute_data.unwrap();
     assert_eq!(response_execute_data.len(), 3);
     assert_eq!(response_execute_data, execute_msgs);
+
+    // the executables are returned in execution order with their json decoded
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::PollExecutables { poll_id: 1 },
+    )
+    .unwrap();
+    let value: PollExecutablesResponse = from_binary(&res).unwrap();
+    assert_eq!(value.poll_id, 1);
+    assert_eq!(
+        value
+            .executables
+            .iter()
+            .map(|executable| executable.order)
+            .collect::<Vec<u64>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(value.executables[0].contract, VOTING_TOKEN);
+    assert_eq!(value.executables[0].msg, execute_msgs[0].msg);
+    assert_eq!(
+        value.executables[0].decoded_msg,
+        Some(r#"{"burn":{"amount":"123"}}"#.to_string())
+    );
+}
+
+#[test]
+fn fails_create_poll_duplicate_execute_msg_order() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let execute_msgs: Vec<PollExecuteMsg> = vec![1u64, 2u64, 1u64]
+        .into_iter()
+        .map(|order| PollExecuteMsg {
+            order,
+            contract: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(order as u128),
+            })
+            .unwrap(),
+        })
+        .collect();
+    let msg = create_poll_msg(
+        "test".to_string(),
+        "test".to_string(),
+        None,
+        Some(execute_msgs),
+    );
+
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::DuplicatePollExecuteMsgOrder(1u64)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fails_create_poll_too_many_execute_msgs() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let execute_msgs: Vec<PollExecuteMsg> = (0..=MAX_POLL_EXECUTE_MSGS as u64)
+        .map(|order| PollExecuteMsg {
+            order,
+            contract: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(1),
+            })
+            .unwrap(),
+        })
+        .collect();
+    let msg = create_poll_msg(
+        "test".to_string(),
+        "test".to_string(),
+        None,
+        Some(execute_msgs),
+    );
+
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::TooManyPollExecuteMsgs(MAX_POLL_EXECUTE_MSGS)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fails_create_poll_calling_protected_method() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let self_execute_msg = |msg: &ExecuteMsg| {
+        Some(vec![PollExecuteMsg {
+            order: 1u64,
+            contract: MOCK_CONTRACT_ADDR.to_string(),
+            msg: to_binary(msg).unwrap(),
+        }])
+    };
+
+    let msg = create_poll_msg(
+        "test".to_string(),
+        "test".to_string(),
+        None,
+        self_execute_msg(&ExecuteMsg::RegisterContracts {
+            whale_token: "attacker_token".to_string(),
+        }),
+    );
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ProtectedPollExecuteMsg(method)) => {
+            assert_eq!(method, "register_contracts")
+        }
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = create_poll_msg(
+        "test".to_string(),
+        "test".to_string(),
+        None,
+        self_execute_msg(&ExecuteMsg::ExecutePollMsgs { poll_id: 2 }),
+    );
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ProtectedPollExecuteMsg(method)) => {
+            assert_eq!(method, "execute_poll_msgs")
+        }
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    // other methods of the contract can still be called by polls
+    let msg = create_poll_msg(
+        "test".to_string(),
+        "test".to_string(),
+        None,
+        self_execute_msg(&ExecuteMsg::ExpirePolls { limit: None }),
+    );
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+}
+
+#[test]
+fn create_whitelist_poll() {
+    let mut deps = mock_dependencies(&[]);
+    let mut msg = instantiate::instantiate_msg();
+    msg.treasury_addr = Some("treasury".to_string());
+    crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+        .unwrap();
+    mock_register_voting_token(deps.as_mut());
+    let env = mock_env_height(0, 10000);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        msg: to_binary(&Cw20HookMsg::CreateWhitelistPoll {
+            dapp_addr: "dapp".to_string(),
+        })
+        .unwrap(),
+    });
+    let execute_res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_create_poll_result(
+        1,
+        env.block.height + DEFAULT_VOTING_PERIOD,
+        TEST_CREATOR,
+        execute_res,
+        deps.as_ref(),
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.poll_type, PollType::Executable);
+    assert_eq!(value.category, PollCategory::Whitelist);
+    assert_eq!(
+        value.execute_data,
+        Some(vec![PollExecuteMsg {
+            order: 1u64,
+            contract: "treasury".to_string(),
+            msg: to_binary(&white_whale::treasury::msg::ExecuteMsg::AddDApp {
+                dapp: "dapp".to_string(),
+            })
+            .unwrap(),
+        }])
+    );
+}
+
+#[test]
+fn fails_create_whitelist_poll_without_treasury() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        msg: to_binary(&Cw20HookMsg::CreateWhitelistPoll {
+            dapp_addr: "dapp".to_string(),
+        })
+        .unwrap(),
+    });
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NoTreasuryToWhitelistIn {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
 }
 
 #[test]
@@ -241,6 +461,67 @@ fn create_poll_no_quorum() {
     );
 }
 
+#[test]
+fn fails_create_poll_when_polls_scope_paused() {
+    let mut deps = mock_dependencies(&[]);
+    let mut msg = instantiate::instantiate_msg();
+    msg.guardian = Some("guardian".to_string());
+    crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+        .unwrap();
+    mock_register_voting_token(deps.as_mut());
+
+    let pause_msg = || ExecuteMsg::PauseScopes {
+        scopes: vec![PauseScope::Polls],
+    };
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_VOTER, &[]),
+        pause_msg(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Unauthorized {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        pause_msg(),
+    )
+    .unwrap();
+    let res: PausedScopesResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PausedScopes {}).unwrap()).unwrap();
+    assert_eq!(res.scopes, vec![PauseScope::Polls]);
+
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    let env = mock_env_height(0, 10000);
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg.clone(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Common(CommonError::ScopePaused {
+            scope: PauseScope::Polls,
+        })) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    // the owner can unpause the scope as well
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::UnpauseScopes {
+            scopes: vec![PauseScope::Polls],
+        },
+    )
+    .unwrap();
+    execute(deps.as_mut(), env, mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+}
+
 #[test]
 fn fails_create_poll_invalid_short_title() {
     let mut deps = mock_dependencies(&[]);
@@ -361,6 +642,49 @@ fn fails_create_poll_invalid_long_link() {
     }
 }
 
+#[test]
+fn create_poll_with_metadata_hash() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let create_msg = |metadata_hash: &str| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: TEST_CREATOR.to_string(),
+            amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            msg: to_binary(&Cw20HookMsg::CreatePoll {
+                title: "valid title".to_string(),
+                description: "valid description".to_string(),
+                link: None,
+                metadata_hash: Some(metadata_hash.to_string()),
+                execute_msgs: None,
+                poll_type: None,
+                category: None,
+            })
+            .unwrap(),
+        })
+    };
+    let info = mock_info(VOTING_TOKEN, &[]);
+
+    // not a content-addressed hash
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        create_msg("ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollMetadataHashInvalid(..)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let cid = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+    execute(deps.as_mut(), mock_env(), info, create_msg(cid)).unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let poll: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(poll.metadata_hash, Some(cid.to_string()));
+}
+
 #[test]
 fn fails_create_poll_invalid_deposit() {
     let mut deps = mock_dependencies(&[]);
@@ -374,7 +698,10 @@ fn fails_create_poll_invalid_deposit() {
             title: "TESTTEST".to_string(),
             description: "TESTTEST".to_string(),
             link: None,
+            metadata_hash: None,
             execute_msgs: None,
+            poll_type: None,
+            category: None,
         })
         .unwrap(),
     });
@@ -469,6 +796,7 @@ fn expire_poll() {
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", POLL_ID.to_string().as_str()),
             attr("amount", "1000"),
             attr("voter", TEST_VOTER),
@@ -503,6 +831,7 @@ fn expire_poll() {
         execute_res.attributes,
         vec![
             attr("action", "end_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("rejected_reason", "Poll Passed"),
             attr("passed", "true"),
@@ -561,44 +890,378 @@ fn expire_poll() {
  * end_poll Tests
  */
 #[test]
-fn fails_end_poll_before_end_height() {
+fn expire_polls() {
     let mut deps = mock_dependencies(&[]);
     instantiate::mock_instantiate(deps.as_mut());
     mock_register_voting_token(deps.as_mut());
-    let env = common::mock_env_height(0, 10000);
-    let info = mock_info(VOTING_TOKEN, &[]);
 
-    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    // make fake passed polls: two executable ones ending at different heights and a text poll
+    for (poll_id, end_height, execute_data) in [
+        (1u64, 0u64, true),
+        (2u64, 0u64, false),
+        (3u64, 10_000u64, true),
+    ] {
+        polls()
+            .save(
+                &mut deps.storage,
+                U64Key::new(poll_id),
+                &Poll {
+                    id: poll_id,
+                    creator: deps.api.addr_canonicalize(TEST_CREATOR).unwrap(),
+                    status: PollStatus::Passed,
+                    yes_votes: Uint128::zero(),
+                    no_votes: Uint128::zero(),
+                    abstain_votes: Uint128::zero(),
+                    end_height,
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    link: None,
+                    metadata_hash: None,
+                    execute_data: if execute_data {
+                        Some(vec![ExecuteData {
+                            order: 1u64,
+                            contract: deps.api.addr_canonicalize(VOTING_TOKEN).unwrap(),
+                            msg: Binary::default(),
+                        }])
+                    } else {
+                        None
+                    },
+                    deposit_amount: Uint128::zero(),
+                    total_balance_at_end_poll: None,
+                    staked_amount: None,
+                    staked_amount_at_creation: None,
+                    deposit_denom: None,
+                    poll_type: if execute_data {
+                        PollType::Executable
+                    } else {
+                        PollType::Text
+                    },
+                    category: if execute_data {
+                        PollCategory::ParameterChange
+                    } else {
+                        PollCategory::Text
+                    },
+                },
+            )
+            .unwrap();
+    }
 
-    let execute_res = execute(deps.as_mut(), env, info, msg).unwrap();
-    assert_create_poll_result(
-        1,
-        DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
+    let env = mock_env_height(DEFAULT_EXPIRATION_PERIOD, 10000);
+    let execute_res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(TEST_VOTER, &[]),
+        ExecuteMsg::ExpirePolls { limit: None },
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "expire_polls"),
+            attr("module", "governance"),
+            attr("poll_ids", "1")
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Polls {
+            filter: Some(PollStatus::Expired),
+            start_after: None,
+            limit: None,
+            order_by: None,
+        },
+    )
+    .unwrap();
+    let response: PollsResponse = from_binary(&res).unwrap();
+    assert_eq!(response.polls.len(), 1);
+    assert_eq!(response.polls[0].id, 1u64);
+    assert_eq!(response.polls[0].status, PollStatus::Expired);
+
+    let res = query(
         deps.as_ref(),
+        mock_env(),
+        QueryMsg::Polls {
+            filter: Some(PollStatus::Passed),
+            start_after: None,
+            limit: None,
+            order_by: Some(OrderBy::Asc),
+        },
+    )
+    .unwrap();
+    let response: PollsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        response
+            .polls
+            .iter()
+            .map(|poll| poll.id)
+            .collect::<Vec<u64>>(),
+        vec![2u64, 3u64]
     );
+}
 
-    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
-    let value: PollResponse = from_binary(&res).unwrap();
-    assert_eq!(DEFAULT_VOTING_PERIOD, value.end_height);
+#[test]
+fn veto_poll() {
+    const END_HEIGHT: u64 = 10_000;
+    let mut deps = mock_dependencies(&[]);
+    let mut msg = instantiate::instantiate_msg();
+    msg.guardian = Some("guardian".to_string());
+    crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+        .unwrap();
+    mock_register_voting_token(deps.as_mut());
 
-    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
-    let env = common::mock_env_height(0, 10000);
-    let info = mock_info(TEST_CREATOR, &[]);
-    let execute_res = execute(deps.as_mut(), env, info, msg);
+    // make a fake passed poll
+    polls()
+        .save(
+            &mut deps.storage,
+            U64Key::new(1u64),
+            &Poll {
+                id: 1u64,
+                creator: deps.api.addr_canonicalize(TEST_CREATOR).unwrap(),
+                status: PollStatus::Passed,
+                yes_votes: Uint128::zero(),
+                no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                end_height: END_HEIGHT,
+                title: "title".to_string(),
+                description: "description".to_string(),
+                link: None,
+                metadata_hash: None,
+                execute_data: Some(vec![ExecuteData {
+                    order: 1u64,
+                    contract: deps.api.addr_canonicalize(VOTING_TOKEN).unwrap(),
+                    msg: Binary::default(),
+                }]),
+                deposit_amount: Uint128::zero(),
+                total_balance_at_end_poll: None,
+                staked_amount: None,
+                staked_amount_at_creation: None,
+                deposit_denom: None,
+                poll_type: PollType::Executable,
+                category: PollCategory::ParameterChange,
+            },
+        )
+        .unwrap();
 
-    match execute_res {
+    let veto_msg = || ExecuteMsg::VetoPoll {
+        poll_id: 1u64,
+        reason: "malicious proposal".to_string(),
+    };
+
+    // only the guardian can veto
+    let env = mock_env_height(END_HEIGHT, 10000);
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        veto_msg(),
+    ) {
         Ok(_) => panic!("Must return error"),
-        Err(ContractError::PollVotingPeriod {}) => (),
+        Err(ContractError::Unauthorized {}) => (),
         Err(e) => panic!("Unexpected error: {:?}", e),
     }
-}
 
-#[test]
-fn successful_end_poll() {
-    const POLL_START_HEIGHT: u64 = 1000;
-    const POLL_ID: u64 = 1;
+    // the poll can't be vetoed once it can be executed
+    let late_env = mock_env_height(END_HEIGHT + DEFAULT_TIMELOCK_PERIOD, 10000);
+    match execute(
+        deps.as_mut(),
+        late_env.clone(),
+        mock_info("guardian", &[]),
+        veto_msg(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::TimelockExpired {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let execute_res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("guardian", &[]),
+        veto_msg(),
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "veto_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1"),
+            attr("reason", "malicious proposal"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.status, PollStatus::Vetoed);
+
+    // the vetoed poll can't be executed nor vetoed again
+    match execute(
+        deps.as_mut(),
+        late_env,
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::ExecutePoll { poll_id: 1 },
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollNotPassed {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+    match execute(deps.as_mut(), env, mock_info("guardian", &[]), veto_msg()) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollNotPassed {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn cancel_poll() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+    let env = mock_env_height(0, 10000);
+
+    for _ in 0..2 {
+        let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(VOTING_TOKEN, &[]),
+            msg,
+        )
+        .unwrap();
+    }
+
+    // only the creator can cancel the poll
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TEST_VOTER, &[]),
+        ExecuteMsg::CancelPoll { poll_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let execute_res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::CancelPoll { poll_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "cancel_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1")
+        ]
+    );
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_CREATOR.to_string(),
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.status, PollStatus::Canceled);
+    let state: State = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(state.total_deposit, Uint128::from(DEFAULT_PROPOSAL_DEPOSIT));
+
+    // a canceled poll can't be canceled again
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::CancelPoll { poll_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PollNotInProgress {});
+
+    // a poll with votes can't be canceled
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(10u128 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(10u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 2,
+        vote: VoteOption::No,
+        amount: Uint128::from(10u128),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::CancelPoll { poll_id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PollHasVotes {});
+}
+
+#[test]
+fn fails_end_poll_before_end_height() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+    let env = common::mock_env_height(0, 10000);
+    let info = mock_info(VOTING_TOKEN, &[]);
+
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+
+    let execute_res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_create_poll_result(
+        1,
+        DEFAULT_VOTING_PERIOD,
+        TEST_CREATOR,
+        execute_res,
+        deps.as_ref(),
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(DEFAULT_VOTING_PERIOD, value.end_height);
+
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let env = common::mock_env_height(0, 10000);
+    let info = mock_info(TEST_CREATOR, &[]);
+    let execute_res = execute(deps.as_mut(), env, info, msg);
+
+    match execute_res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollVotingPeriod {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn successful_end_poll() {
+    const POLL_START_HEIGHT: u64 = 1000;
+    const POLL_ID: u64 = 1;
     let stake_amount = 1000;
 
     let mut deps = mock_dependencies(&coins(1000, VOTING_TOKEN));
@@ -702,6 +1365,7 @@ fn successful_end_poll() {
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", POLL_ID.to_string().as_str()),
             attr("amount", "1000"),
             attr("voter", TEST_VOTER),
@@ -739,6 +1403,7 @@ fn successful_end_poll() {
         execute_res.attributes,
         vec![
             attr("action", "end_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("rejected_reason", "Poll Passed"),
             attr("passed", "true"),
@@ -782,7 +1447,36 @@ fn successful_end_poll() {
 
     creator_env.block.height += DEFAULT_TIMELOCK_PERIOD;
     let msg = ExecuteMsg::ExecutePoll { poll_id: 1 };
-    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    let execute_res = execute(deps.as_mut(), creator_env.clone(), creator_info, msg).unwrap();
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::reply_on_error(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&ExecuteMsg::ExecutePollMsgs { poll_id: 1 }).unwrap(),
+                funds: vec![],
+            }),
+            POLL_EXECUTE_REPLY_ID,
+        )]
+    );
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "execute_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1")
+        ]
+    );
+
+    // the poll messages are executed by the contract itself
+    let msg = ExecuteMsg::ExecutePollMsgs { poll_id: 1 };
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env,
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
     assert_eq!(
         execute_res.messages,
         vec![
@@ -805,7 +1499,11 @@ fn successful_end_poll() {
     );
     assert_eq!(
         execute_res.attributes,
-        vec![attr("action", "execute_poll"), attr("poll_id", "1")]
+        vec![
+            attr("action", "execute_poll_msgs"),
+            attr("module", "governance"),
+            attr("poll_id", "1")
+        ]
     );
 
     // Query executed polls
@@ -881,14 +1579,18 @@ fn successful_end_poll() {
         StakerResponse {
             balance: Uint128::from(stake_amount),
             share: Uint128::from(stake_amount),
+            locked_amount: Uint128::zero(),
             locked_balance: vec![],
         }
     );
 
     // But the data is still in the store
     let voter_addr_raw = deps.api.addr_canonicalize(TEST_VOTER).unwrap();
-    let voter = poll_voter_read(&deps.storage, 1u64)
-        .load(&voter_addr_raw.as_slice())
+    let voter = POLL_VOTERS
+        .load(
+            &deps.storage,
+            (U64Key::new(1u64), voter_addr_raw.as_slice()),
+        )
         .unwrap();
     assert_eq!(
         voter,
@@ -898,9 +1600,7 @@ fn successful_end_poll() {
         }
     );
 
-    let token_manager = bank_read(&deps.storage)
-        .load(&voter_addr_raw.as_slice())
-        .unwrap();
+    let token_manager = BANK.load(&deps.storage, voter_addr_raw.as_slice()).unwrap();
     assert_eq!(
         token_manager.locked_balance,
         vec![(
@@ -979,6 +1679,7 @@ fn end_poll_zero_quorum() {
         execute_res.attributes,
         vec![
             attr("action", "end_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("rejected_reason", "Quorum not reached"),
             attr("passed", "false"),
@@ -1051,6 +1752,7 @@ fn end_poll_quorum_rejected() {
         execute_res.attributes,
         vec![
             attr("action", "create_poll"),
+            attr("module", "governance"),
             attr("creator", TEST_CREATOR),
             attr("poll_id", "1"),
             attr("end_height", "22345"),
@@ -1095,6 +1797,7 @@ fn end_poll_quorum_rejected() {
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("amount", "10"),
             attr("voter", TEST_VOTER),
@@ -1107,160 +1810,931 @@ fn end_poll_quorum_rejected() {
     creator_info.sender = Addr::unchecked(TEST_CREATOR);
     creator_env.block.height += DEFAULT_VOTING_PERIOD;
 
-    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
-    assert_eq!(
-        execute_res.attributes,
-        vec![
-            attr("action", "end_poll"),
-            attr("poll_id", "1"),
-            attr("rejected_reason", "Quorum not reached"),
-            attr("passed", "false"),
-        ]
+    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", "Quorum not reached"),
+            attr("passed", "false"),
+        ]
+    );
+}
+
+#[test]
+fn end_poll_quorum_rejected_slashes_deposit() {
+    let slash_msgs = vec![
+        (
+            DepositSlashPolicy::Burn,
+            None,
+            Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            },
+        ),
+        (
+            DepositSlashPolicy::SendToTreasury,
+            Some("treasury".to_string()),
+            Cw20ExecuteMsg::Transfer {
+                recipient: "treasury".to_string(),
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            },
+        ),
+    ];
+
+    for (deposit_slash_policy, treasury_addr, expected_msg) in slash_msgs {
+        let mut deps = mock_dependencies(&[]);
+        let mut msg = instantiate::instantiate_msg();
+        msg.deposit_slash_policy = Some(deposit_slash_policy);
+        msg.treasury_addr = treasury_addr;
+        crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+            .unwrap();
+        mock_register_voting_token(deps.as_mut());
+
+        let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+        let mut env = mock_env();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(VOTING_TOKEN, &[]),
+            msg,
+        )
+        .unwrap();
+
+        // nobody voted, the quorum is not reached
+        env.block.height += DEFAULT_VOTING_PERIOD;
+        let execute_res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(TEST_CREATOR, &[]),
+            ExecuteMsg::EndPoll { poll_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            execute_res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: VOTING_TOKEN.to_string(),
+                msg: to_binary(&expected_msg).unwrap(),
+                funds: vec![],
+            }))]
+        );
+
+        let state: State = STATE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(state.total_deposit, Uint128::zero());
+    }
+}
+
+#[test]
+fn end_poll_quorum_rejected_rewards_voters() {
+    let mut deps = mock_dependencies(&[]);
+    let mut msg = instantiate::instantiate_msg();
+    msg.deposit_slash_policy = Some(DepositSlashPolicy::Burn);
+    msg.voter_reward_ratio = Some(Decimal::percent(50));
+    crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+        .unwrap();
+    mock_register_voting_token(deps.as_mut());
+
+    let mut total_staked = 0u128;
+    for (voter, stake) in [
+        (TEST_VOTER, 10u128),
+        (TEST_VOTER_2, 30u128),
+        (TEST_VOTER_3, 1000u128),
+    ] {
+        total_staked += stake;
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(total_staked),
+            )],
+        )]);
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: voter.to_string(),
+            amount: Uint128::from(stake),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+    }
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(total_staked + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+    for (voter, vote, amount) in [
+        (TEST_VOTER, VoteOption::Yes, 10u128),
+        (TEST_VOTER_2, VoteOption::No, 30u128),
+    ] {
+        let msg = ExecuteMsg::CastVote {
+            poll_id: 1,
+            vote,
+            amount: Uint128::from(amount),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(voter, &[]), msg).unwrap();
+    }
+
+    // half of the deposit is burned, the other half goes to the voters
+    let mut env = mock_env();
+    env.block.height += DEFAULT_VOTING_PERIOD;
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let execute_res = execute(deps.as_mut(), env, mock_info(TEST_CREATOR, &[]), msg).unwrap();
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT / 2),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    let state: State = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        state.total_deposit,
+        Uint128::from(DEFAULT_PROPOSAL_DEPOSIT / 2)
+    );
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(total_staked + DEFAULT_PROPOSAL_DEPOSIT / 2),
+        )],
+    )]);
+
+    // rewards are shared pro-rata to the voted weight
+    for (voter, reward) in [
+        (TEST_VOTER, DEFAULT_PROPOSAL_DEPOSIT / 8),
+        (TEST_VOTER_2, DEFAULT_PROPOSAL_DEPOSIT * 3 / 8),
+        (TEST_VOTER_3, 0u128),
+    ] {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PendingVotingRewards {
+                address: voter.to_string(),
+            },
+        )
+        .unwrap();
+        let response: PendingVotingRewardsResponse = from_binary(&res).unwrap();
+        assert_eq!(response.pending_voting_rewards, Uint128::from(reward));
+    }
+
+    let msg = ExecuteMsg::ClaimVotingRewards {};
+    let execute_res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_VOTER.to_string(),
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT / 8),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "claim_voting_rewards"),
+            attr("module", "governance"),
+            attr("recipient", TEST_VOTER),
+            attr("amount", (DEFAULT_PROPOSAL_DEPOSIT / 8).to_string()),
+        ]
+    );
+    let state: State = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        state.total_deposit,
+        Uint128::from(DEFAULT_PROPOSAL_DEPOSIT * 3 / 8)
+    );
+
+    for voter in [TEST_VOTER, TEST_VOTER_3] {
+        let msg = ExecuteMsg::ClaimVotingRewards {};
+        match execute(deps.as_mut(), mock_env(), mock_info(voter, &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NoVotingRewards {}) => (),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
+
+#[test]
+fn end_poll_quorum_rejected_noting_staked() {
+    let mut deps = mock_dependencies(&coins(100, VOTING_TOKEN));
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    let mut creator_env = mock_env();
+    let mut creator_info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env.clone(),
+        creator_info.clone(),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("module", "governance"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr("end_height", "22345"),
+        ]
+    );
+
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+
+    creator_info.sender = Addr::unchecked(TEST_CREATOR);
+    creator_env.block.height += DEFAULT_VOTING_PERIOD;
+
+    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", "Quorum not reached"),
+            attr("passed", "false"),
+        ]
+    );
+}
+
+#[test]
+fn end_poll_nay_rejected() {
+    let voter1_stake = 100;
+    let voter2_stake = 1000;
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+    let mut creator_env = mock_env();
+    let mut creator_info = mock_info(VOTING_TOKEN, &coins(2, VOTING_TOKEN));
+
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env.clone(),
+        creator_info.clone(),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("module", "governance"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr("end_height", "22345"),
+        ]
+    );
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from((voter1_stake + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(voter1_stake as u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_stake_tokens_result(
+        voter1_stake,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        voter1_stake,
+        1,
+        execute_res,
+        deps.as_ref(),
+    );
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from((voter1_stake + voter2_stake + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER_2.to_string(),
+        amount: Uint128::from(voter2_stake as u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_stake_tokens_result(
+        voter1_stake + voter2_stake,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        voter2_stake,
+        1,
+        execute_res,
+        deps.as_ref(),
+    );
+
+    let info = mock_info(TEST_VOTER_2, &[]);
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::from(voter2_stake),
+    };
+    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_cast_vote_success(TEST_VOTER_2, voter2_stake, 1, VoteOption::No, execute_res);
+
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+
+    creator_info.sender = Addr::unchecked(TEST_CREATOR);
+    creator_env.block.height += DEFAULT_VOTING_PERIOD;
+    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", "Threshold not reached"),
+            attr("passed", "false"),
+        ]
+    );
+}
+
+#[test]
+fn end_poll_abstain_counts_toward_quorum_only() {
+    let voter1_stake = 100;
+    let voter2_stake = 1000;
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+    let mut creator_env = mock_env();
+    let mut creator_info = mock_info(VOTING_TOKEN, &coins(2, VOTING_TOKEN));
+
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(
+        deps.as_mut(),
+        creator_env.clone(),
+        creator_info.clone(),
+        msg,
+    )
+    .unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from((voter1_stake + voter2_stake + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+        )],
+    )]);
+
+    for (voter, stake) in [(TEST_VOTER, voter1_stake), (TEST_VOTER_2, voter2_stake)] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: voter.to_string(),
+            amount: Uint128::from(stake as u128),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        let info = mock_info(VOTING_TOKEN, &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    let info = mock_info(TEST_VOTER, &[]);
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(voter1_stake),
+    };
+    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_cast_vote_success(TEST_VOTER, voter1_stake, 1, VoteOption::Yes, execute_res);
+
+    let info = mock_info(TEST_VOTER_2, &[]);
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Abstain,
+        amount: Uint128::from(voter2_stake),
+    };
+    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_cast_vote_success(
+        TEST_VOTER_2,
+        voter2_stake,
+        1,
+        VoteOption::Abstain,
+        execute_res,
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.yes_votes.u128(), voter1_stake);
+    assert_eq!(value.no_votes.u128(), 0u128);
+    assert_eq!(value.abstain_votes.u128(), voter2_stake);
+
+    // Without the abstain votes the quorum (30%) would not be reached, and the
+    // yes votes are the only decisive votes so the threshold is met.
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    creator_info.sender = Addr::unchecked(TEST_CREATOR);
+    creator_env.block.height += DEFAULT_VOTING_PERIOD;
+    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", "Poll Passed"),
+            attr("passed", "true"),
+        ]
+    );
+}
+
+#[test]
+fn passed_text_poll_ends_executed() {
+    let stake_amount = 1000;
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.poll_type, PollType::Text);
+    assert_eq!(value.category, PollCategory::Text);
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from((stake_amount + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+        )],
+    )]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount as u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(stake_amount as u128),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let mut env = mock_env();
+    env.block.height += DEFAULT_VOTING_PERIOD;
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let execute_res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", "Poll Passed"),
+            attr("passed", "true"),
+        ]
+    );
+
+    // the text poll has nothing left to execute
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.status, PollStatus::Executed);
+
+    env.block.height += DEFAULT_TIMELOCK_PERIOD;
+    let msg = ExecuteMsg::ExecutePoll { poll_id: 1 };
+    match execute(deps.as_mut(), env, mock_info(TEST_CREATOR, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollNotPassed {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fails_create_poll_mismatching_poll_type() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let execute_msgs = vec![PollExecuteMsg {
+        order: 1u64,
+        contract: VOTING_TOKEN.to_string(),
+        msg: Binary::default(),
+    }];
+    let create_msg = |execute_msgs: Option<Vec<PollExecuteMsg>>, poll_type: PollType| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: TEST_CREATOR.to_string(),
+            amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            msg: to_binary(&Cw20HookMsg::CreatePoll {
+                title: "test".to_string(),
+                description: "test".to_string(),
+                link: None,
+                metadata_hash: None,
+                execute_msgs,
+                poll_type: Some(poll_type),
+                category: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    let msg = create_msg(Some(execute_msgs.clone()), PollType::Text);
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::TextPollExecuteMsgs {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = create_msg(None, PollType::Executable);
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ExecutablePollNoExecuteMsgs {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = create_msg(Some(execute_msgs), PollType::Executable);
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.poll_type, PollType::Executable);
+}
+
+fn create_category_poll_msg(category: PollCategory, deposit: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(deposit),
+        msg: to_binary(&Cw20HookMsg::CreatePoll {
+            title: "test".to_string(),
+            description: "test".to_string(),
+            link: None,
+            metadata_hash: None,
+            execute_msgs: Some(vec![PollExecuteMsg {
+                order: 1u64,
+                contract: VOTING_TOKEN.to_string(),
+                msg: Binary::default(),
+            }]),
+            poll_type: None,
+            category: Some(category),
+        })
+        .unwrap(),
+    })
+}
+
+#[test]
+fn create_poll_with_category_params() {
+    let mut deps = mock_dependencies(&[]);
+    let mut msg = instantiate::instantiate_msg();
+    msg.category_params = Some(vec![(
+        PollCategory::Spend,
+        CategoryParams {
+            voting_period: Some(2 * DEFAULT_VOTING_PERIOD),
+            proposal_deposit: Some(Uint128::from(2 * DEFAULT_PROPOSAL_DEPOSIT)),
+            ..CategoryParams::default()
+        },
+    )]);
+    crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+        .unwrap();
+    mock_register_voting_token(deps.as_mut());
+    let env = mock_env_height(0, 10000);
+
+    // spend polls require the deposit of the category
+    let msg = create_category_poll_msg(PollCategory::Spend, DEFAULT_PROPOSAL_DEPOSIT);
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InsufficientProposalDeposit(deposit)) => {
+            assert_eq!(deposit, 2 * DEFAULT_PROPOSAL_DEPOSIT)
+        }
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = create_category_poll_msg(PollCategory::Spend, 2 * DEFAULT_PROPOSAL_DEPOSIT);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.category, PollCategory::Spend);
+    assert_eq!(
+        value.end_height,
+        env.block.height + 2 * DEFAULT_VOTING_PERIOD
+    );
+
+    // other categories keep the config parameters
+    let msg = create_category_poll_msg(PollCategory::Whitelist, DEFAULT_PROPOSAL_DEPOSIT);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 2 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.category, PollCategory::Whitelist);
+    assert_eq!(value.end_height, env.block.height + DEFAULT_VOTING_PERIOD);
+}
+
+#[test]
+fn fails_create_poll_mismatching_category() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let msg = create_category_poll_msg(PollCategory::Text, DEFAULT_PROPOSAL_DEPOSIT);
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidPollCategory {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        msg: to_binary(&Cw20HookMsg::CreatePoll {
+            title: "test".to_string(),
+            description: "test".to_string(),
+            link: None,
+            metadata_hash: None,
+            execute_msgs: None,
+            poll_type: None,
+            category: Some(PollCategory::Spend),
+        })
+        .unwrap(),
+    });
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidPollCategory {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    // executable polls default to the parameter change category
+    let msg = create_poll_msg(
+        "test".to_string(),
+        "test".to_string(),
+        None,
+        Some(vec![PollExecuteMsg {
+            order: 1u64,
+            contract: VOTING_TOKEN.to_string(),
+            msg: Binary::default(),
+        }]),
     );
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.category, PollCategory::ParameterChange);
 }
 
 #[test]
-fn end_poll_quorum_rejected_noting_staked() {
-    let mut deps = mock_dependencies(&coins(100, VOTING_TOKEN));
-    instantiate::mock_instantiate(deps.as_mut());
-    mock_register_voting_token(deps.as_mut());
+fn end_poll_with_category_threshold() {
+    let stake_amount = 1000u128;
+    let cases = [
+        (PollCategory::ParameterChange, "Poll Passed", "true"),
+        (PollCategory::Spend, "Threshold not reached", "false"),
+    ];
 
-    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
-    let mut creator_env = mock_env();
-    let mut creator_info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(
-        deps.as_mut(),
-        creator_env.clone(),
-        creator_info.clone(),
-        msg,
-    )
-    .unwrap();
-    assert_eq!(
-        execute_res.attributes,
-        vec![
-            attr("action", "create_poll"),
-            attr("creator", TEST_CREATOR),
-            attr("poll_id", "1"),
-            attr("end_height", "22345"),
-        ]
-    );
+    for (category, rejected_reason, passed) in cases {
+        let mut deps = mock_dependencies(&[]);
+        let mut msg = instantiate::instantiate_msg();
+        msg.category_params = Some(vec![(
+            PollCategory::Spend,
+            CategoryParams {
+                threshold: Some(Decimal::percent(90)),
+                ..CategoryParams::default()
+            },
+        )]);
+        crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+            .unwrap();
+        mock_register_voting_token(deps.as_mut());
+
+        let msg = create_category_poll_msg(category, DEFAULT_PROPOSAL_DEPOSIT);
+        execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(2 * stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
+            )],
+        )]);
+        for voter in [TEST_VOTER, TEST_VOTER_2] {
+            let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: voter.to_string(),
+                amount: Uint128::from(stake_amount),
+                msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+            });
+            execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+        }
 
-    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+        // 2/3 of the votes are in favor, above the config threshold but below the spend threshold
+        let msg = ExecuteMsg::CastVote {
+            poll_id: 1,
+            vote: VoteOption::Yes,
+            amount: Uint128::from(stake_amount),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+        let msg = ExecuteMsg::CastVote {
+            poll_id: 1,
+            vote: VoteOption::No,
+            amount: Uint128::from(stake_amount / 2),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.height += DEFAULT_VOTING_PERIOD;
+        let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+        let execute_res = execute(deps.as_mut(), env, mock_info(TEST_CREATOR, &[]), msg).unwrap();
+        assert_eq!(
+            execute_res.attributes,
+            vec![
+                attr("action", "end_poll"),
+                attr("module", "governance"),
+                attr("poll_id", "1"),
+                attr("rejected_reason", rejected_reason),
+                attr("passed", passed),
+            ]
+        );
+    }
+}
 
-    creator_info.sender = Addr::unchecked(TEST_CREATOR);
-    creator_env.block.height += DEFAULT_VOTING_PERIOD;
+#[test]
+fn end_poll_quorum_against_poll_creation_stake() {
+    let voter1_stake = 100u128;
+    let voter2_stake = 1000u128;
+    let cases = [
+        (QuorumMode::EndPoll, "Quorum not reached", "false"),
+        (QuorumMode::PollCreation, "Poll Passed", "true"),
+    ];
 
-    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
-    assert_eq!(
-        execute_res.attributes,
-        vec![
-            attr("action", "end_poll"),
-            attr("poll_id", "1"),
-            attr("rejected_reason", "Quorum not reached"),
-            attr("passed", "false"),
-        ]
-    );
+    for (quorum_mode, rejected_reason, passed) in cases {
+        let mut deps = mock_dependencies(&[]);
+        let mut msg = instantiate::instantiate_msg();
+        msg.quorum_mode = Some(quorum_mode.clone());
+        crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+            .unwrap();
+        mock_register_voting_token(deps.as_mut());
+
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(voter1_stake),
+            )],
+        )]);
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: TEST_VOTER.to_string(),
+            amount: Uint128::from(voter1_stake),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(voter1_stake + DEFAULT_PROPOSAL_DEPOSIT),
+            )],
+        )]);
+        let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+        execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+        let value: PollResponse = from_binary(&res).unwrap();
+        match quorum_mode {
+            QuorumMode::EndPoll => assert_eq!(value.staked_amount_at_creation, None),
+            QuorumMode::PollCreation => assert_eq!(
+                value.staked_amount_at_creation,
+                Some(Uint128::from(voter1_stake))
+            ),
+        }
+
+        let msg = ExecuteMsg::CastVote {
+            poll_id: 1,
+            vote: VoteOption::Yes,
+            amount: Uint128::from(voter1_stake),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+        // A late staker increases the live staked balance below the quorum
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(voter1_stake + voter2_stake + DEFAULT_PROPOSAL_DEPOSIT),
+            )],
+        )]);
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: TEST_VOTER_2.to_string(),
+            amount: Uint128::from(voter2_stake),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.height += DEFAULT_VOTING_PERIOD;
+        let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+        let execute_res = execute(deps.as_mut(), env, mock_info(TEST_CREATOR, &[]), msg).unwrap();
+        assert_eq!(
+            execute_res.attributes,
+            vec![
+                attr("action", "end_poll"),
+                attr("module", "governance"),
+                attr("poll_id", "1"),
+                attr("rejected_reason", rejected_reason),
+                attr("passed", passed),
+            ]
+        );
+    }
 }
 
 #[test]
-fn end_poll_nay_rejected() {
-    let voter1_stake = 100;
-    let voter2_stake = 1000;
+fn end_poll_no_stake_at_poll_creation() {
+    let stake_amount = 100u128;
     let mut deps = mock_dependencies(&[]);
-    instantiate::mock_instantiate(deps.as_mut());
+    let mut msg = instantiate::instantiate_msg();
+    msg.quorum_mode = Some(QuorumMode::PollCreation);
+    crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg)
+        .unwrap();
     mock_register_voting_token(deps.as_mut());
-    let mut creator_env = mock_env();
-    let mut creator_info = mock_info(VOTING_TOKEN, &coins(2, VOTING_TOKEN));
-
-    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
-
-    let execute_res = execute(
-        deps.as_mut(),
-        creator_env.clone(),
-        creator_info.clone(),
-        msg,
-    )
-    .unwrap();
-    assert_eq!(
-        execute_res.attributes,
-        vec![
-            attr("action", "create_poll"),
-            attr("creator", TEST_CREATOR),
-            attr("poll_id", "1"),
-            attr("end_height", "22345"),
-        ]
-    );
 
+    // Nothing is staked yet when the poll is created
     deps.querier.with_token_balances(&[(
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((voter1_stake + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
 
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(voter1_stake as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        voter1_stake,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        voter1_stake,
-        1,
-        execute_res,
-        deps.as_ref(),
-    );
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.staked_amount_at_creation, Some(Uint128::zero()));
 
     deps.querier.with_token_balances(&[(
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((voter1_stake + voter2_stake + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
-
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER_2.to_string(),
-        amount: Uint128::from(voter2_stake as u128),
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
         msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
     });
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
 
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        voter1_stake + voter2_stake,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        voter2_stake,
-        1,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let info = mock_info(TEST_VOTER_2, &[]);
     let msg = ExecuteMsg::CastVote {
         poll_id: 1,
-        vote: VoteOption::No,
-        amount: Uint128::from(voter2_stake),
+        vote: VoteOption::Yes,
+        amount: Uint128::from(stake_amount),
     };
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_cast_vote_success(TEST_VOTER_2, voter2_stake, 1, VoteOption::No, execute_res);
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
 
+    let mut env = mock_env();
+    env.block.height += DEFAULT_VOTING_PERIOD;
     let msg = ExecuteMsg::EndPoll { poll_id: 1 };
-
-    creator_info.sender = Addr::unchecked(TEST_CREATOR);
-    creator_env.block.height += DEFAULT_VOTING_PERIOD;
-    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    let execute_res = execute(deps.as_mut(), env, mock_info(TEST_CREATOR, &[]), msg).unwrap();
     assert_eq!(
         execute_res.attributes,
         vec![
             attr("action", "end_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
-            attr("rejected_reason", "Threshold not reached"),
+            attr("rejected_reason", "Quorum not reached"),
             attr("passed", "false"),
         ]
     );
@@ -1391,6 +2865,7 @@ fn execute_poll_with_order() {
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", POLL_ID.to_string().as_str()),
             attr("amount", "1000"),
             attr("voter", TEST_VOTER),
@@ -1414,6 +2889,7 @@ fn execute_poll_with_order() {
         execute_res.attributes,
         vec![
             attr("action", "end_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("rejected_reason", "Poll Passed"),
             attr("passed", "true"),
@@ -1443,7 +2919,36 @@ fn execute_poll_with_order() {
 
     creator_env.block.height += DEFAULT_TIMELOCK_PERIOD;
     let msg = ExecuteMsg::ExecutePoll { poll_id: 1 };
-    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    let execute_res = execute(deps.as_mut(), creator_env.clone(), creator_info, msg).unwrap();
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::reply_on_error(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&ExecuteMsg::ExecutePollMsgs { poll_id: 1 }).unwrap(),
+                funds: vec![],
+            }),
+            POLL_EXECUTE_REPLY_ID,
+        )]
+    );
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "execute_poll"),
+            attr("module", "governance"),
+            attr("poll_id", "1")
+        ]
+    );
+
+    // the poll messages are executed by the contract itself
+    let msg = ExecuteMsg::ExecutePollMsgs { poll_id: 1 };
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env,
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
     assert_eq!(
         execute_res.messages,
         vec![
@@ -1476,8 +2981,148 @@ fn execute_poll_with_order() {
     );
     assert_eq!(
         execute_res.attributes,
-        vec![attr("action", "execute_poll"), attr("poll_id", "1")]
+        vec![
+            attr("action", "execute_poll_msgs"),
+            attr("module", "governance"),
+            attr("poll_id", "1")
+        ]
+    );
+}
+
+#[test]
+fn failed_poll_execution_can_be_retried() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    // make a fake passed poll
+    polls()
+        .save(
+            &mut deps.storage,
+            U64Key::new(1u64),
+            &Poll {
+                id: 1u64,
+                creator: deps.api.addr_canonicalize(TEST_CREATOR).unwrap(),
+                status: PollStatus::Passed,
+                yes_votes: Uint128::zero(),
+                no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                end_height: 0u64,
+                title: "title".to_string(),
+                description: "description".to_string(),
+                link: None,
+                metadata_hash: None,
+                execute_data: Some(vec![ExecuteData {
+                    order: 1u64,
+                    contract: deps.api.addr_canonicalize(VOTING_TOKEN).unwrap(),
+                    msg: Binary::default(),
+                }]),
+                deposit_amount: Uint128::zero(),
+                total_balance_at_end_poll: None,
+                staked_amount: None,
+                staked_amount_at_creation: None,
+                deposit_denom: None,
+                poll_type: PollType::Executable,
+                category: PollCategory::ParameterChange,
+            },
+        )
+        .unwrap();
+
+    let env = mock_env_height(DEFAULT_TIMELOCK_PERIOD, 10000);
+    let msg = ExecuteMsg::ExecutePollMsgs { poll_id: 1 };
+    match execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Unauthorized {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    // the contract only executes the messages of the poll being executed
+    let msg = ExecuteMsg::ExecutePollMsgs { poll_id: 1 };
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollNotExecuting {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::RetryExecution { poll_id: 1 };
+    match execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollNotFailed {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::ExecutePoll { poll_id: 1 };
+    execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let failed_reply = Reply {
+        id: POLL_EXECUTE_REPLY_ID,
+        result: ContractResult::Err("execution failed".to_string()),
+    };
+    let res = reply(deps.as_mut(), env.clone(), failed_reply.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "failed_poll_execution"),
+            attr("module", "governance"),
+            attr("poll_id", "1"),
+            attr("error", "execution failed"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.status, PollStatus::FailedExecution);
+
+    let msg = ExecuteMsg::RetryExecution { poll_id: 1 };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_error(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&ExecuteMsg::ExecutePollMsgs { poll_id: 1 }).unwrap(),
+                funds: vec![],
+            }),
+            POLL_EXECUTE_REPLY_ID,
+        )]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "retry_execution"),
+            attr("module", "governance"),
+            attr("poll_id", "1")
+        ]
     );
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.status, PollStatus::Executed);
+
+    // once expired a failed poll can no longer be retried
+    reply(deps.as_mut(), env, failed_reply).unwrap();
+    let env = mock_env_height(DEFAULT_EXPIRATION_PERIOD, 10000);
+    let msg = ExecuteMsg::RetryExecution { poll_id: 1 };
+    match execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::PollExecutionExpired {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let invalid_reply = Reply {
+        id: 2u64,
+        result: ContractResult::Err("execution failed".to_string()),
+    };
+    match reply(deps.as_mut(), env, invalid_reply) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidReplyId(2u64)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
 }
 
 #[test]
@@ -1502,6 +3147,7 @@ fn snapshot_poll() {
         execute_res.attributes,
         vec![
             attr("action", "create_poll"),
+            attr("module", "governance"),
             attr("creator", TEST_CREATOR),
             attr("poll_id", "1"),
             attr("end_height", "22345"),
@@ -1541,6 +3187,7 @@ fn snapshot_poll() {
         fix_res.attributes,
         vec![
             attr("action", "snapshot_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("staked_amount", stake_amount.to_string().as_str()),
         ]
@@ -1557,6 +3204,29 @@ fn snapshot_poll() {
     assert_eq!(ContractError::SnapshotAlreadyOccurred {}, snapshot_error);
 }
 
+#[test]
+fn fails_snapshot_poll_after_end_height() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    let mut env = mock_env();
+    let info = mock_info(VOTING_TOKEN, &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // the voting period is over, there is nothing left to snapshot
+    env.block.height += DEFAULT_VOTING_PERIOD + 1;
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(TEST_VOTER, &[]),
+        ExecuteMsg::SnapshotPoll { poll_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::PollNotInProgress {}, err);
+}
+
 #[test]
 fn successful_cast_vote_with_snapshot() {
     let mut deps = mock_dependencies(&[]);
@@ -1790,6 +3460,7 @@ fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", POLL_ID.to_string().as_str()),
             attr("amount", "1000"),
             attr("voter", TEST_VOTER),
@@ -1834,6 +3505,7 @@ fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", POLL_ID.to_string().as_str()),
             attr("amount", "1000"),
             attr("voter", TEST_VOTER_2),
@@ -1852,6 +3524,7 @@ fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
         execute_res.attributes,
         vec![
             attr("action", "end_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("rejected_reason", "Quorum not reached"),
             attr("passed", "false"),
@@ -1959,6 +3632,7 @@ fn successful_end_poll_with_controlled_quorum() {
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", POLL_ID.to_string().as_str()),
             attr("amount", "1000"),
             attr("voter", TEST_VOTER),
@@ -1981,6 +3655,7 @@ fn successful_end_poll_with_controlled_quorum() {
         fix_res.attributes,
         vec![
             attr("action", "snapshot_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("staked_amount", stake_amount.to_string().as_str()),
         ]
@@ -2018,6 +3693,7 @@ fn successful_end_poll_with_controlled_quorum() {
         execute_res.attributes,
         vec![
             attr("action", "cast_vote"),
+            attr("module", "governance"),
             attr("poll_id", POLL_ID.to_string().as_str()),
             attr("amount", "8000"),
             attr("voter", TEST_VOTER_2),
@@ -2036,6 +3712,7 @@ fn successful_end_poll_with_controlled_quorum() {
         execute_res.attributes,
         vec![
             attr("action", "end_poll"),
+            attr("module", "governance"),
             attr("poll_id", "1"),
             attr("rejected_reason", "Poll Passed"),
             attr("passed", "true"),
@@ -2094,3 +3771,57 @@ fn fails_query_poll() {
         Err(_) => panic!("Unknown error"),
     };
 }
+
+#[test]
+fn query_polls_with_pagination() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+    let env = mock_env_height(0, 10000);
+
+    for _ in 0..3 {
+        let info = mock_info(VOTING_TOKEN, &[]);
+        let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let query_poll_ids = |filter: Option<PollStatus>,
+                          start_after: Option<u64>,
+                          limit: Option<u32>,
+                          order_by: Option<OrderBy>|
+     -> Vec<u64> {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Polls {
+                filter,
+                start_after,
+                limit,
+                order_by,
+            },
+        )
+        .unwrap();
+        let response: PollsResponse = from_binary(&res).unwrap();
+        response.polls.iter().map(|poll| poll.id).collect()
+    };
+
+    // descending is the default order
+    assert_eq!(query_poll_ids(None, None, None, None), vec![3, 2, 1]);
+    assert_eq!(
+        query_poll_ids(None, None, None, Some(OrderBy::Asc)),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        query_poll_ids(None, Some(1), Some(1), Some(OrderBy::Asc)),
+        vec![2]
+    );
+    assert_eq!(query_poll_ids(None, Some(3), None, None), vec![2, 1]);
+    assert_eq!(
+        query_poll_ids(Some(PollStatus::InProgress), Some(2), None, None),
+        vec![1]
+    );
+    assert_eq!(
+        query_poll_ids(Some(PollStatus::Passed), None, None, None),
+        Vec::<u64>::new()
+    );
+}
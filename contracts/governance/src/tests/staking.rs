@@ -55,6 +55,7 @@ fn share_calculation() {
         res.attributes,
         vec![
             attr("action", "staking"),
+            attr("module", "governance"),
             attr("sender", TEST_VOTER),
             attr("share", "50"),
             attr("amount", "100"),
@@ -70,6 +71,7 @@ fn share_calculation() {
         res.attributes,
         vec![
             attr("action", "withdraw"),
+            attr("module", "governance"),
             attr("recipient", TEST_VOTER),
             attr("amount", "100"),
         ]
@@ -0,0 +1,212 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{from_binary, to_binary, CosmosMsg, Deps, DepsMut, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, query};
+use crate::tests::common::{TEST_VOTER, TEST_VOTER_2, VOTING_TOKEN};
+use crate::tests::mock_querier::mock_dependencies;
+use crate::tests::{instantiate, poll};
+use crate::ContractError;
+use white_whale::governance::msg::{ExecuteMsg, QueryMsg};
+use white_whale::governance::state::{Cw20HookMsg, StakerResponse, StakerRewardsResponse};
+
+fn receive(deps: DepsMut, sender: &str, amount: u128, hook: Cw20HookMsg) {
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_binary(&hook).unwrap(),
+    });
+    execute(deps, mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+}
+
+fn staker_rewards(deps: Deps, address: &str) -> Uint128 {
+    let res: StakerRewardsResponse = from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::StakerRewards {
+                address: address.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    res.pending_rewards
+}
+
+#[test]
+fn distribute_and_claim_rewards() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    // the balance of the contract is already increased when the hooks are executed
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+    )]);
+    receive(
+        deps.as_mut(),
+        TEST_VOTER,
+        100,
+        Cw20HookMsg::StakeVotingTokens {},
+    );
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(400u128))],
+    )]);
+    receive(
+        deps.as_mut(),
+        TEST_VOTER_2,
+        300,
+        Cw20HookMsg::StakeVotingTokens {},
+    );
+
+    // the rewards are shared pro-rata to the stake
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(800u128))],
+    )]);
+    receive(
+        deps.as_mut(),
+        "treasury",
+        400,
+        Cw20HookMsg::DistributeRewards {},
+    );
+    assert_eq!(
+        staker_rewards(deps.as_ref(), TEST_VOTER),
+        Uint128::from(100u128)
+    );
+    assert_eq!(
+        staker_rewards(deps.as_ref(), TEST_VOTER_2),
+        Uint128::from(300u128)
+    );
+
+    // the rewards don't increase the staked balance
+    let res: StakerResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Staker {
+                address: TEST_VOTER.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.balance, Uint128::from(100u128));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_VOTER, &[]),
+        ExecuteMsg::ClaimRewards {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_VOTER.to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert!(staker_rewards(deps.as_ref(), TEST_VOTER).is_zero());
+
+    // nothing left to claim
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_VOTER, &[]),
+        ExecuteMsg::ClaimRewards {},
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NoStakingRewards {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn late_stakers_do_not_get_earlier_rewards() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+    )]);
+    receive(
+        deps.as_mut(),
+        TEST_VOTER,
+        100,
+        Cw20HookMsg::StakeVotingTokens {},
+    );
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(150u128))],
+    )]);
+    receive(
+        deps.as_mut(),
+        "treasury",
+        50,
+        Cw20HookMsg::DistributeRewards {},
+    );
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(250u128))],
+    )]);
+    receive(
+        deps.as_mut(),
+        TEST_VOTER_2,
+        100,
+        Cw20HookMsg::StakeVotingTokens {},
+    );
+    assert_eq!(
+        staker_rewards(deps.as_ref(), TEST_VOTER),
+        Uint128::from(50u128)
+    );
+    assert!(staker_rewards(deps.as_ref(), TEST_VOTER_2).is_zero());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(350u128))],
+    )]);
+    receive(
+        deps.as_mut(),
+        "treasury",
+        100,
+        Cw20HookMsg::DistributeRewards {},
+    );
+    assert_eq!(
+        staker_rewards(deps.as_ref(), TEST_VOTER),
+        Uint128::from(100u128)
+    );
+    assert_eq!(
+        staker_rewards(deps.as_ref(), TEST_VOTER_2),
+        Uint128::from(50u128)
+    );
+}
+
+#[test]
+fn fails_distribute_rewards_without_stakers() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "treasury".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::DistributeRewards {}).unwrap(),
+    });
+    match execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NothingStaked {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
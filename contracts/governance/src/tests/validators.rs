@@ -1,14 +1,17 @@
 use crate::contract::{
-    MAX_DESC_LENGTH, MAX_LINK_LENGTH, MAX_TITLE_LENGTH, MIN_DESC_LENGTH, MIN_LINK_LENGTH,
-    MIN_TITLE_LENGTH,
+    MAX_DESC_LENGTH, MAX_LINK_LENGTH, MAX_METADATA_HASH_LENGTH, MAX_TITLE_LENGTH, MIN_DESC_LENGTH,
+    MIN_LINK_LENGTH, MIN_METADATA_HASH_LENGTH, MIN_TITLE_LENGTH,
 };
 use crate::tests::common::{DEFAULT_EXPIRATION_PERIOD, DEFAULT_TIMELOCK_PERIOD};
 use crate::validators::{
-    validate_decimal_value, validate_poll_description, validate_poll_link, validate_poll_period,
-    validate_poll_title, validate_quorum, validate_threshold,
+    validate_decimal_value, validate_poll_description, validate_poll_link,
+    validate_poll_metadata_hash, validate_poll_period, validate_poll_title, validate_poll_type,
+    validate_proposal_deposit, validate_quorum, validate_snapshot_period, validate_threshold,
 };
-use cosmwasm_std::Decimal;
+use crate::ContractError;
+use cosmwasm_std::{Binary, Decimal, Uint128};
 use std::str::FromStr;
+use white_whale::governance::state::{PollExecuteMsg, PollType};
 
 /**
  * Tests [validate_decimal_value]
@@ -61,11 +64,22 @@ fn invalid_quorum_2() {
 }
 
 /**
- * Tests [validate_quorum] with valid values, i.e. between [0,1].
+ * Tests [validate_quorum] with a zero value.
+ */
+#[test]
+fn invalid_quorum_zero() {
+    match validate_quorum(Decimal::zero()) {
+        Err(ContractError::PollQuorumInvalidValue(_)) => (),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
+
+/**
+ * Tests [validate_quorum] with valid values, i.e. between (0,1].
  */
 #[test]
 fn valid_quorum() {
-    let mut valid = validate_quorum(Decimal::zero()).unwrap();
+    let mut valid = validate_quorum(Decimal::permille(1)).unwrap();
     assert_eq!(valid, ());
 
     valid = validate_quorum(Decimal::one()).unwrap();
@@ -98,11 +112,22 @@ fn invalid_threshold_2() {
 }
 
 /**
- * Tests [validate_threshold] with valid values, i.e. between [0,1].
+ * Tests [validate_threshold] with a zero value.
+ */
+#[test]
+fn invalid_threshold_zero() {
+    match validate_threshold(Decimal::zero()) {
+        Err(ContractError::PollThresholdInvalidValue(_)) => (),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
+
+/**
+ * Tests [validate_threshold] with valid values, i.e. between (0,1].
  */
 #[test]
 fn valid_threshold() {
-    let mut valid = validate_threshold(Decimal::zero()).unwrap();
+    let mut valid = validate_threshold(Decimal::permille(1)).unwrap();
     assert_eq!(valid, ());
 
     valid = validate_threshold(Decimal::one()).unwrap();
@@ -149,6 +174,44 @@ fn valid_link() {
     assert_eq!(valid, ());
 }
 
+/**
+ * Tests [validate_poll_metadata_hash]
+ */
+
+/**
+ * Tests [validate_poll_metadata_hash] with invalid lengths and characters.
+ */
+#[test]
+fn invalid_metadata_hash() {
+    for metadata_hash in [
+        "a".repeat(MIN_METADATA_HASH_LENGTH - 1),
+        "a".repeat(MAX_METADATA_HASH_LENGTH + 1),
+        format!("{}/", "a".repeat(MIN_METADATA_HASH_LENGTH)),
+    ] {
+        match validate_poll_metadata_hash(&Some(metadata_hash)) {
+            Err(ContractError::PollMetadataHashInvalid(..)) => (),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+    }
+}
+
+/**
+ * Tests [validate_poll_metadata_hash] with an IPFS CID, a hex digest and no hash.
+ */
+#[test]
+fn valid_metadata_hash() {
+    validate_poll_metadata_hash(&Some(
+        "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string(),
+    ))
+    .unwrap();
+    validate_poll_metadata_hash(&Some(
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+    ))
+    .unwrap();
+    validate_poll_metadata_hash(&Some("ab".repeat(32))).unwrap();
+    validate_poll_metadata_hash(&None).unwrap();
+}
+
 /**
  * Tests [validate_poll_title]
  */
@@ -257,4 +320,75 @@ fn invalid_poll_period() {
     let timelock_period = 20000u64;
     let expiration_period = 10000u64;
     validate_poll_period(timelock_period, expiration_period).unwrap_err();
+    validate_poll_period(timelock_period, timelock_period).unwrap_err();
+}
+
+/**
+ * Tests [validate_snapshot_period], the snapshot period must be non-zero and shorter than the
+ * voting period.
+ */
+#[test]
+fn snapshot_period() {
+    validate_snapshot_period(10, 100).unwrap();
+    match validate_snapshot_period(100, 100) {
+        Err(ContractError::InvalidSnapshotPeriod {}) => (),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+    match validate_snapshot_period(0, 100) {
+        Err(ContractError::InvalidZeroPeriod {}) => (),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
+
+/**
+ * Tests [validate_proposal_deposit], the deposit must be greater than zero.
+ */
+#[test]
+fn proposal_deposit() {
+    validate_proposal_deposit(Uint128::new(1)).unwrap();
+    match validate_proposal_deposit(Uint128::zero()) {
+        Err(ContractError::InvalidZeroProposalDeposit {}) => (),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
+
+/**
+ * Tests [validate_poll_type] defaults, i.e. polls with execute messages are executable and text polls otherwise.
+ */
+#[test]
+fn poll_type_defaults() {
+    let execute_msgs = Some(vec![PollExecuteMsg {
+        order: 1u64,
+        contract: "contract".to_string(),
+        msg: Binary::default(),
+    }]);
+    assert_eq!(
+        validate_poll_type(None, &execute_msgs).unwrap(),
+        PollType::Executable
+    );
+    assert_eq!(validate_poll_type(None, &None).unwrap(), PollType::Text);
+    assert_eq!(
+        validate_poll_type(None, &Some(vec![])).unwrap(),
+        PollType::Text
+    );
+}
+
+/**
+ * Tests [validate_poll_type] with a poll type not matching the execute messages.
+ */
+#[test]
+fn invalid_poll_type() {
+    let execute_msgs = Some(vec![PollExecuteMsg {
+        order: 1u64,
+        contract: "contract".to_string(),
+        msg: Binary::default(),
+    }]);
+    assert_eq!(
+        validate_poll_type(Some(PollType::Text), &execute_msgs).unwrap_err(),
+        ContractError::TextPollExecuteMsgs {}
+    );
+    assert_eq!(
+        validate_poll_type(Some(PollType::Executable), &None).unwrap_err(),
+        ContractError::ExecutablePollNoExecuteMsgs {}
+    );
 }
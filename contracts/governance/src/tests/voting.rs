@@ -1,20 +1,22 @@
 use crate::contract::{execute, query};
 use crate::tests::common::{
-    DEFAULT_PROPOSAL_DEPOSIT, DEFAULT_VOTING_PERIOD, TEST_CREATOR, TEST_VOTER, VOTING_TOKEN,
+    DEFAULT_FIX_PERIOD, DEFAULT_PROPOSAL_DEPOSIT, DEFAULT_VOTING_PERIOD, TEST_CREATOR, TEST_VOTER,
+    TEST_VOTER_2, VOTING_TOKEN,
 };
 use crate::tests::mock_querier::mock_dependencies;
 use crate::tests::{common, instantiate, poll};
 use crate::ContractError;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    coins, from_binary, to_binary, Api, CanonicalAddr, CosmosMsg, SubMsg, Uint128, WasmMsg,
+    attr, coins, from_binary, to_binary, Api, CanonicalAddr, CosmosMsg, SubMsg, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
 use white_whale::governance::msg::{ExecuteMsg, QueryMsg};
 use white_whale::governance::state::{
-    bank_read, bank_store, poll_store, poll_voter_read, poll_voter_store, state_read, Cw20HookMsg,
-    OrderBy, Poll, PollStatus, StakerResponse, State, TokenManager, VoteOption, VoterInfo,
-    VotersResponse, VotersResponseItem,
+    polls, Cw20HookMsg, OrderBy, Poll, PollCategory, PollResponse, PollStatus, PollType,
+    StakerResponse, State, TokenManager, VoteOption, VoterInfo, VoterResponse, VotersResponse,
+    VotersResponseItem, BANK, POLL_VOTERS, STATE,
 };
 
 #[test]
@@ -158,6 +160,7 @@ fn successful_cast_vote() {
         StakerResponse {
             balance: Uint128::from(22u128),
             share: Uint128::from(11u128),
+            locked_amount: Uint128::from(amount),
             locked_balance: vec![(
                 1u64,
                 VoterInfo {
@@ -203,6 +206,41 @@ fn successful_cast_vote() {
     .unwrap();
     let response: VotersResponse = from_binary(&res).unwrap();
     assert_eq!(response.voters.len(), 0);
+
+    // Query the vote receipt
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Voter {
+            poll_id: 1u64,
+            address: TEST_VOTER.to_string(),
+        },
+    )
+    .unwrap();
+    let response: VoterResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        response,
+        VoterResponse {
+            poll_id: 1u64,
+            voter: TEST_VOTER.to_string(),
+            vote: VoteOption::Yes,
+            balance: Uint128::from(amount),
+            poll_ended: false,
+        }
+    );
+
+    match query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Voter {
+            poll_id: 1u64,
+            address: TEST_VOTER_2.to_string(),
+        },
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotVoted {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
 }
 
 #[test]
@@ -226,7 +264,7 @@ fn successful_withdraw_voting_tokens() {
     let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     poll::assert_stake_tokens_result(11, 0, 11, 0, execute_res, deps.as_ref());
 
-    let state: State = state_read(deps.as_ref().storage).load().unwrap();
+    let state: State = STATE.load(deps.as_ref().storage).unwrap();
     assert_eq!(
         state,
         State {
@@ -264,7 +302,7 @@ fn successful_withdraw_voting_tokens() {
         }))
     );
 
-    let state: State = state_read(deps.as_ref().storage).load().unwrap();
+    let state: State = STATE.load(deps.as_ref().storage).unwrap();
     assert_eq!(
         state,
         State {
@@ -297,7 +335,7 @@ fn successful_withdraw_voting_tokens_all() {
     let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     poll::assert_stake_tokens_result(11, 0, 11, 0, execute_res, deps.as_ref());
 
-    let state: State = state_read(deps.as_ref().storage).load().unwrap();
+    let state: State = STATE.load(deps.as_ref().storage).unwrap();
     assert_eq!(
         state,
         State {
@@ -333,7 +371,7 @@ fn successful_withdraw_voting_tokens_all() {
         }))
     );
 
-    let state: State = state_read(deps.as_ref().storage).load().unwrap();
+    let state: State = STATE.load(deps.as_ref().storage).unwrap();
     assert_eq!(
         state,
         State {
@@ -367,91 +405,107 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
     poll::assert_stake_tokens_result(11, 0, 11, 0, execute_res, deps.as_ref());
 
     // make fake polls; one in progress & one in passed
-    poll_store(&mut deps.storage)
+    polls()
         .save(
-            &1u64.to_be_bytes(),
+            &mut deps.storage,
+            U64Key::new(1u64),
             &Poll {
                 id: 1u64,
                 creator: CanonicalAddr::from(vec![]),
                 status: PollStatus::InProgress,
                 yes_votes: Uint128::zero(),
                 no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
                 end_height: 0u64,
                 title: "title".to_string(),
                 description: "description".to_string(),
                 deposit_amount: Uint128::zero(),
                 link: None,
+                metadata_hash: None,
                 execute_data: None,
                 total_balance_at_end_poll: None,
                 staked_amount: None,
+                staked_amount_at_creation: None,
+                deposit_denom: None,
+                poll_type: PollType::Text,
+                category: PollCategory::Text,
             },
         )
         .unwrap();
 
-    poll_store(&mut deps.storage)
+    polls()
         .save(
-            &2u64.to_be_bytes(),
+            &mut deps.storage,
+            U64Key::new(2u64),
             &Poll {
                 id: 1u64,
                 creator: CanonicalAddr::from(vec![]),
                 status: PollStatus::Passed,
                 yes_votes: Uint128::zero(),
                 no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
                 end_height: 0u64,
                 title: "title".to_string(),
                 description: "description".to_string(),
                 deposit_amount: Uint128::zero(),
                 link: None,
+                metadata_hash: None,
                 execute_data: None,
                 total_balance_at_end_poll: None,
                 staked_amount: None,
+                staked_amount_at_creation: None,
+                deposit_denom: None,
+                poll_type: PollType::Text,
+                category: PollCategory::Text,
             },
         )
         .unwrap();
 
     let voter_addr_raw = deps.api.addr_canonicalize(TEST_VOTER).unwrap();
-    poll_voter_store(&mut deps.storage, 1u64)
+    POLL_VOTERS
         .save(
-            &voter_addr_raw.as_slice(),
+            &mut deps.storage,
+            (U64Key::new(1u64), voter_addr_raw.as_slice()),
             &VoterInfo {
                 vote: VoteOption::Yes,
                 balance: Uint128::from(5u128),
             },
         )
         .unwrap();
-    poll_voter_store(&mut deps.storage, 2u64)
+    POLL_VOTERS
         .save(
-            &voter_addr_raw.as_slice(),
+            &mut deps.storage,
+            (U64Key::new(2u64), voter_addr_raw.as_slice()),
             &VoterInfo {
                 vote: VoteOption::Yes,
                 balance: Uint128::from(5u128),
             },
         )
         .unwrap();
-    bank_store(&mut deps.storage)
-        .save(
-            &voter_addr_raw.as_slice(),
-            &TokenManager {
-                share: Uint128::from(11u128),
-                locked_balance: vec![
-                    (
-                        1u64,
-                        VoterInfo {
-                            vote: VoteOption::Yes,
-                            balance: Uint128::from(5u128),
-                        },
-                    ),
-                    (
-                        2u64,
-                        VoterInfo {
-                            vote: VoteOption::Yes,
-                            balance: Uint128::from(5u128),
-                        },
-                    ),
-                ],
-            },
-        )
-        .unwrap();
+    BANK.save(
+        &mut deps.storage,
+        voter_addr_raw.as_slice(),
+        &TokenManager {
+            share: Uint128::from(11u128),
+            locked_balance: vec![
+                (
+                    1u64,
+                    VoterInfo {
+                        vote: VoteOption::Yes,
+                        balance: Uint128::from(5u128),
+                    },
+                ),
+                (
+                    2u64,
+                    VoterInfo {
+                        vote: VoteOption::Yes,
+                        balance: Uint128::from(5u128),
+                    },
+                ),
+            ],
+        },
+    )
+    .unwrap();
 
     // withdraw voting token must remove not in-progress votes infos from the store
     let info = mock_info(TEST_VOTER, &[]);
@@ -460,8 +514,11 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
     };
 
     let _ = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    let voter = poll_voter_read(&deps.storage, 1u64)
-        .load(&voter_addr_raw.as_slice())
+    let voter = POLL_VOTERS
+        .load(
+            &deps.storage,
+            (U64Key::new(1u64), voter_addr_raw.as_slice()),
+        )
         .unwrap();
     assert_eq!(
         voter,
@@ -470,13 +527,14 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
             balance: Uint128::from(5u128),
         }
     );
-    assert!(poll_voter_read(&deps.storage, 2u64)
-        .load(&voter_addr_raw.as_slice())
+    assert!(POLL_VOTERS
+        .load(
+            &deps.storage,
+            (U64Key::new(2u64), voter_addr_raw.as_slice())
+        )
         .is_err(),);
 
-    let token_manager = bank_read(&deps.storage)
-        .load(&voter_addr_raw.as_slice())
-        .unwrap();
+    let token_manager = BANK.load(&deps.storage, voter_addr_raw.as_slice()).unwrap();
     assert_eq!(
         token_manager.locked_balance,
         vec![(
@@ -489,6 +547,123 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
     );
 }
 
+#[test]
+fn cast_vote_unlocks_not_in_progress_poll_balances() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    let env = common::mock_env_height(0, 10000);
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let msg = poll::create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), env, info, msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(11u128 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(11u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+    let info = mock_info(VOTING_TOKEN, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // make a fake passed poll the voter still has a locked balance in
+    polls()
+        .save(
+            &mut deps.storage,
+            U64Key::new(2u64),
+            &Poll {
+                id: 2u64,
+                creator: CanonicalAddr::from(vec![]),
+                status: PollStatus::Passed,
+                yes_votes: Uint128::from(5u128),
+                no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                end_height: 0u64,
+                title: "title".to_string(),
+                description: "description".to_string(),
+                deposit_amount: Uint128::zero(),
+                link: None,
+                metadata_hash: None,
+                execute_data: None,
+                total_balance_at_end_poll: None,
+                staked_amount: None,
+                staked_amount_at_creation: None,
+                deposit_denom: None,
+                poll_type: PollType::Text,
+                category: PollCategory::Text,
+            },
+        )
+        .unwrap();
+    let voter_addr_raw = deps.api.addr_canonicalize(TEST_VOTER).unwrap();
+    let passed_vote = VoterInfo {
+        vote: VoteOption::Yes,
+        balance: Uint128::from(5u128),
+    };
+    POLL_VOTERS
+        .save(
+            &mut deps.storage,
+            (U64Key::new(2u64), voter_addr_raw.as_slice()),
+            &passed_vote,
+        )
+        .unwrap();
+    BANK.save(
+        &mut deps.storage,
+        voter_addr_raw.as_slice(),
+        &TokenManager {
+            share: Uint128::from(11u128),
+            locked_balance: vec![(2u64, passed_vote)],
+        },
+    )
+    .unwrap();
+
+    let env = common::mock_env_height(0, 10000);
+    let info = mock_info(TEST_VOTER, &[]);
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::from(10u128),
+    };
+    execute(deps.as_mut(), env, info, msg).unwrap();
+
+    // only the in progress poll keeps the balance locked
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Staker {
+            address: TEST_VOTER.to_string(),
+        },
+    )
+    .unwrap();
+    let response: StakerResponse = from_binary(&res).unwrap();
+    assert_eq!(response.locked_amount, Uint128::from(10u128));
+    assert_eq!(
+        BANK.load(&deps.storage, voter_addr_raw.as_slice())
+            .unwrap()
+            .locked_balance,
+        vec![(
+            1u64,
+            VoterInfo {
+                vote: VoteOption::No,
+                balance: Uint128::from(10u128),
+            }
+        )]
+    );
+    assert!(POLL_VOTERS
+        .load(
+            &deps.storage,
+            (U64Key::new(2u64), voter_addr_raw.as_slice())
+        )
+        .is_err());
+}
+
 #[test]
 fn fails_withdraw_voting_tokens_no_stake() {
     let mut deps = mock_dependencies(&[]);
@@ -544,6 +719,83 @@ fn fails_withdraw_too_many_tokens() {
     }
 }
 
+#[test]
+fn fails_withdraw_locked_voting_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    let env = common::mock_env_height(0, 10000);
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let msg = poll::create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), env, info, msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(11u128 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(11u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+    let info = mock_info(VOTING_TOKEN, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let env = common::mock_env_height(0, 10000);
+    let info = mock_info(TEST_VOTER, &[]);
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(10u128),
+    };
+    execute(deps.as_mut(), env, info, msg).unwrap();
+
+    // 10 out of 11 tokens are locked in the in-progress poll
+    let info = mock_info(TEST_VOTER, &[]);
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::from(2u128)),
+    };
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidWithdrawAmount {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let info = mock_info(TEST_VOTER, &[]);
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::zero()),
+    };
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidWithdrawAmount {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    // the unlocked token can still be withdrawn
+    let info = mock_info(TEST_VOTER, &[]);
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::from(1u128)),
+    };
+    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_VOTER.to_string(),
+                amount: Uint128::from(1u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
 #[test]
 fn fails_cast_vote_twice() {
     let mut deps = mock_dependencies(&[]);
@@ -614,6 +866,169 @@ fn fails_cast_vote_twice() {
     }
 }
 
+#[test]
+fn successful_change_vote() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    let msg = poll::create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(150u128 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+
+    for (voter, stake, vote) in [
+        (TEST_VOTER, 100u128, VoteOption::Yes),
+        (TEST_VOTER_2, 50u128, VoteOption::No),
+    ] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: voter.to_string(),
+            amount: Uint128::from(stake),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::CastVote {
+            poll_id: 1,
+            vote,
+            amount: Uint128::from(stake),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(voter, &[]), msg).unwrap();
+    }
+
+    let msg = ExecuteMsg::ChangeVote {
+        poll_id: 1,
+        vote: VoteOption::Abstain,
+        amount: Uint128::from(60u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "change_vote"),
+            attr("module", "governance"),
+            attr("poll_id", "1"),
+            attr("amount", "60"),
+            attr("voter", TEST_VOTER),
+            attr("vote_option", "abstain"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.yes_votes, Uint128::zero());
+    assert_eq!(value.no_votes, Uint128::from(50u128));
+    assert_eq!(value.abstain_votes, Uint128::from(60u128));
+
+    let msg = ExecuteMsg::ChangeVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(50u128),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.yes_votes, Uint128::from(50u128));
+    assert_eq!(value.no_votes, Uint128::zero());
+    assert_eq!(value.abstain_votes, Uint128::from(60u128));
+
+    let voter_info = VoterInfo {
+        vote: VoteOption::Abstain,
+        balance: Uint128::from(60u128),
+    };
+    let voter_addr_raw = deps.api.addr_canonicalize(TEST_VOTER).unwrap();
+    assert_eq!(
+        POLL_VOTERS
+            .load(&deps.storage, (U64Key::new(1), voter_addr_raw.as_slice()))
+            .unwrap(),
+        voter_info
+    );
+    assert_eq!(
+        BANK.load(&deps.storage, voter_addr_raw.as_slice())
+            .unwrap()
+            .locked_balance,
+        vec![(1u64, voter_info)]
+    );
+}
+
+#[test]
+fn fails_change_vote() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate::mock_instantiate(deps.as_mut());
+    poll::mock_register_voting_token(deps.as_mut());
+
+    let msg = poll::create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(100u128 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::ChangeVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::from(100u128),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotVoted {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(100u128),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::ChangeVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::from(101u128),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InsufficientStaked {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let mut env = mock_env();
+    env.block.height += DEFAULT_VOTING_PERIOD - DEFAULT_FIX_PERIOD + 1;
+    let msg = ExecuteMsg::ChangeVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::from(100u128),
+    };
+    match execute(deps.as_mut(), env, mock_info(TEST_VOTER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::VoteChangeSnapshotPeriod {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let value: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(value.yes_votes, Uint128::from(100u128));
+    assert_eq!(value.no_votes, Uint128::zero());
+}
+
 #[test]
 fn fails_cast_vote_without_poll() {
     let mut deps = mock_dependencies(&[]);
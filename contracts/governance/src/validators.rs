@@ -1,12 +1,18 @@
 use core::result::Result::{Err, Ok};
+use std::collections::{BTreeMap, HashSet};
 
-use cosmwasm_std::{Decimal, StdError, StdResult};
+use cosmwasm_std::{from_slice, Binary, CanonicalAddr, Decimal, StdError, StdResult, Uint128};
+use serde::de::IgnoredAny;
 
 use crate::contract::{
-    MAX_DESC_LENGTH, MAX_LINK_LENGTH, MAX_QUORUM, MAX_THRESHOLD, MAX_TITLE_LENGTH, MIN_DESC_LENGTH,
-    MIN_LINK_LENGTH, MIN_TITLE_LENGTH,
+    MAX_DESC_LENGTH, MAX_LINK_LENGTH, MAX_METADATA_HASH_LENGTH, MAX_POLL_EXECUTE_MSGS, MAX_QUORUM,
+    MAX_THRESHOLD, MAX_TITLE_LENGTH, MIN_DESC_LENGTH, MIN_LINK_LENGTH, MIN_METADATA_HASH_LENGTH,
+    MIN_TITLE_LENGTH,
 };
 use crate::ContractError;
+use white_whale::governance::state::{
+    Config, DepositSlashPolicy, PollCategory, PollExecuteMsg, PollType,
+};
 
 /**
  * Validates that the provided [Decimal] value is in between [0,max_value].
@@ -20,29 +26,41 @@ pub(crate) fn validate_decimal_value(value: Decimal, max_value: Decimal) -> StdR
 }
 
 /**
- * Validates the quorum parameter used to instantiate the contract. It should be between [0,1].
+ * Validates the quorum parameter used to instantiate the contract. It should be between (0,1].
  */
 pub fn validate_quorum(quorum: Decimal) -> Result<(), ContractError> {
     match validate_decimal_value(quorum, MAX_QUORUM) {
-        Ok(_) => Ok(()),
-        Err(_) => Err(ContractError::PollQuorumInvalidValue(
+        Ok(_) if !quorum.is_zero() => Ok(()),
+        _ => Err(ContractError::PollQuorumInvalidValue(
             MAX_QUORUM.to_string(),
         )),
     }
 }
 
 /**
- * Validates the threshold parameter used to instantiate the contract. It should be between [0,1].
+ * Validates the threshold parameter used to instantiate the contract. It should be between (0,1].
  */
 pub fn validate_threshold(threshold: Decimal) -> Result<(), ContractError> {
     match validate_decimal_value(threshold, MAX_THRESHOLD) {
-        Ok(_) => Ok(()),
-        Err(_) => Err(ContractError::PollThresholdInvalidValue(
+        Ok(_) if !threshold.is_zero() => Ok(()),
+        _ => Err(ContractError::PollThresholdInvalidValue(
             MAX_THRESHOLD.to_string(),
         )),
     }
 }
 
+/**
+ * Validates the share of forfeited deposits going to voters. It should be between [0,1].
+ */
+pub fn validate_voter_reward_ratio(voter_reward_ratio: Decimal) -> Result<(), ContractError> {
+    match validate_decimal_value(voter_reward_ratio, Decimal::one()) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ContractError::VoterRewardRatioInvalidValue(
+            Decimal::one().to_string(),
+        )),
+    }
+}
+
 /**
  * Validates that the link is valid when creating a poll.
  */
@@ -86,17 +104,210 @@ pub fn validate_poll_description(description: &str) -> Result<(), ContractError>
     }
 }
 
+/**
+ * Validates that the metadata hash is alphanumeric and has the length of a content-addressed hash,
+ * e.g. a hex digest or an IPFS CID.
+ */
+pub fn validate_poll_metadata_hash(metadata_hash: &Option<String>) -> Result<(), ContractError> {
+    if let Some(metadata_hash) = metadata_hash {
+        if metadata_hash.len() < MIN_METADATA_HASH_LENGTH
+            || metadata_hash.len() > MAX_METADATA_HASH_LENGTH
+            || !metadata_hash.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(ContractError::PollMetadataHashInvalid(
+                MIN_METADATA_HASH_LENGTH,
+                MAX_METADATA_HASH_LENGTH,
+            ));
+        }
+    }
+    Ok(())
+}
+
 /**
  * Validates both the expiration_period and timelock_period so that the poll is valid,
- * i.e. it's not expired before it is executed.
+ * i.e. it doesn't expire before or as soon as it can be executed.
  */
 pub fn validate_poll_period(
     timelock_period: u64,
     expiration_period: u64,
 ) -> Result<(), ContractError> {
-    if expiration_period < timelock_period {
+    if expiration_period <= timelock_period {
         return Err(ContractError::InvalidPollPeriod {});
     }
 
     Ok(())
 }
+
+/**
+ * Validates that a period (voting, timelock, expiration or snapshot) is not zero.
+ */
+pub fn validate_nonzero_period(period: u64) -> Result<(), ContractError> {
+    if period == 0 {
+        return Err(ContractError::InvalidZeroPeriod {});
+    }
+
+    Ok(())
+}
+
+/**
+ * Validates that the snapshot period is shorter than the voting period, so that the snapshot
+ * can be taken while the poll is in progress.
+ */
+pub fn validate_snapshot_period(
+    snapshot_period: u64,
+    voting_period: u64,
+) -> Result<(), ContractError> {
+    validate_nonzero_period(snapshot_period)?;
+    if snapshot_period >= voting_period {
+        return Err(ContractError::InvalidSnapshotPeriod {});
+    }
+
+    Ok(())
+}
+
+/**
+ * Validates that polls require a non-zero proposal deposit.
+ */
+pub fn validate_proposal_deposit(proposal_deposit: Uint128) -> Result<(), ContractError> {
+    if proposal_deposit.is_zero() {
+        return Err(ContractError::InvalidZeroProposalDeposit {});
+    }
+
+    Ok(())
+}
+
+/**
+ * Validates the poll parameters of the config, used both on instantiation and on config updates.
+ */
+pub fn validate_config(config: &Config) -> Result<(), ContractError> {
+    validate_quorum(config.quorum)?;
+    validate_threshold(config.threshold)?;
+    validate_nonzero_period(config.voting_period)?;
+    validate_nonzero_period(config.timelock_period)?;
+    validate_poll_period(config.timelock_period, config.expiration_period)?;
+    validate_snapshot_period(config.snapshot_period, config.voting_period)?;
+    validate_proposal_deposit(config.proposal_deposit)?;
+    validate_voter_reward_ratio(config.voter_reward_ratio)?;
+    validate_deposit_slash_policy(&config.deposit_slash_policy, &config.treasury_addr)?;
+    validate_category_params(config)
+}
+
+/**
+ * Validates that a treasury address is set when slashed deposits have to be sent to the treasury.
+ */
+pub fn validate_deposit_slash_policy(
+    policy: &DepositSlashPolicy,
+    treasury_addr: &Option<CanonicalAddr>,
+) -> Result<(), ContractError> {
+    if *policy == DepositSlashPolicy::SendToTreasury && treasury_addr.is_none() {
+        return Err(ContractError::NoTreasuryAddr {});
+    }
+
+    Ok(())
+}
+
+/**
+ * Validates that the poll type matches the execute messages of the poll, defaulting to an
+ * executable poll when messages are given and to a text poll otherwise.
+ */
+pub fn validate_poll_type(
+    poll_type: Option<PollType>,
+    execute_msgs: &Option<Vec<PollExecuteMsg>>,
+) -> Result<PollType, ContractError> {
+    let has_execute_msgs = execute_msgs.as_ref().map_or(false, |msgs| !msgs.is_empty());
+    match poll_type {
+        Some(PollType::Text) if has_execute_msgs => Err(ContractError::TextPollExecuteMsgs {}),
+        Some(PollType::Executable) if !has_execute_msgs => {
+            Err(ContractError::ExecutablePollNoExecuteMsgs {})
+        }
+        Some(poll_type) => Ok(poll_type),
+        None if has_execute_msgs => Ok(PollType::Executable),
+        None => Ok(PollType::Text),
+    }
+}
+
+/**
+ * Validates that the category of the poll matches its type. Text polls are in the text category,
+ * executable polls default to the parameter change category and can't be in the text category.
+ */
+pub fn validate_poll_category(
+    category: Option<PollCategory>,
+    poll_type: &PollType,
+) -> Result<PollCategory, ContractError> {
+    match (poll_type, category) {
+        (PollType::Text, None) | (PollType::Text, Some(PollCategory::Text)) => {
+            Ok(PollCategory::Text)
+        }
+        (PollType::Executable, None) => Ok(PollCategory::ParameterChange),
+        (PollType::Executable, Some(category)) if category != PollCategory::Text => Ok(category),
+        _ => Err(ContractError::InvalidPollCategory {}),
+    }
+}
+
+/**
+ * Validates the poll parameters overridden per category of the config, i.e. each category is set
+ * at most once and the parameters are valid config values.
+ */
+pub fn validate_category_params(config: &Config) -> Result<(), ContractError> {
+    let category_params = &config.category_params;
+    for (i, (category, params)) in category_params.iter().enumerate() {
+        if category_params[..i]
+            .iter()
+            .any(|(other, _)| other == category)
+        {
+            return Err(ContractError::DuplicateCategoryParams(category.to_string()));
+        }
+        if let Some(quorum) = params.quorum {
+            validate_quorum(quorum)?;
+        }
+        if let Some(threshold) = params.threshold {
+            validate_threshold(threshold)?;
+        }
+        if let Some(voting_period) = params.voting_period {
+            validate_snapshot_period(config.snapshot_period, voting_period)?;
+        }
+        if let Some(proposal_deposit) = params.proposal_deposit {
+            validate_proposal_deposit(proposal_deposit)?;
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Validates the execute messages of a poll, i.e. there are at most MAX_POLL_EXECUTE_MSGS of them
+ * and no two messages share the same order.
+ */
+pub fn validate_poll_execute_msgs(execute_msgs: &[PollExecuteMsg]) -> Result<(), ContractError> {
+    if execute_msgs.len() > MAX_POLL_EXECUTE_MSGS {
+        return Err(ContractError::TooManyPollExecuteMsgs(MAX_POLL_EXECUTE_MSGS));
+    }
+
+    let mut orders = HashSet::new();
+    for msg in execute_msgs {
+        if !orders.insert(msg.order) {
+            return Err(ContractError::DuplicatePollExecuteMsgOrder(msg.order));
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Validates that a poll message targeting the contract itself does not call one of the protected methods.
+ */
+pub fn validate_self_execute_msg(
+    msg: &Binary,
+    protected_methods: &[String],
+) -> Result<(), ContractError> {
+    // A message which is not a json object can't call any method of the contract
+    let methods: BTreeMap<String, IgnoredAny> = match from_slice(msg.as_slice()) {
+        Ok(methods) => methods,
+        Err(_) => return Ok(()),
+    };
+    match methods
+        .keys()
+        .find(|method| protected_methods.contains(method))
+    {
+        Some(method) => Err(ContractError::ProtectedPollExecuteMsg(method.to_string())),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,93 @@
+use crate::error::ContractError;
+use crate::staking::prune_locked_balance;
+use white_whale::events::{WhaleEvent, GOVERNANCE};
+use white_whale::governance::state::{
+    polls, Config, PendingVotingRewardsResponse, Poll, PollStatus, State, BANK, CONFIG,
+    PENDING_VOTING_REWARDS, STATE, VOTER_REWARD_POOLS,
+};
+
+use cosmwasm_std::{
+    to_binary, CosmosMsg, Deps, DepsMut, MessageInfo, Response, StdResult, Storage, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::U64Key;
+
+/// Returns the reward of a voter who voted with `balance` on a poll, zero if the poll has no
+/// voter rewards.
+pub(crate) fn voter_reward(
+    storage: &dyn Storage,
+    poll_id: u64,
+    balance: Uint128,
+) -> StdResult<Uint128> {
+    Ok(VOTER_REWARD_POOLS
+        .may_load(storage, U64Key::new(poll_id))?
+        .map(|pool| pool.reward.multiply_ratio(balance, pool.total_weight))
+        .unwrap_or_default())
+}
+
+/// Sends the sender its voting rewards. The rewards of the polls the sender voted on are credited
+/// once the polls are no longer in progress.
+pub fn claim_voting_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let key = sender_address_raw.as_slice();
+
+    if let Some(mut token_manager) = BANK.may_load(deps.storage, key)? {
+        prune_locked_balance(deps.storage, &mut token_manager, &sender_address_raw)?;
+        BANK.save(deps.storage, key, &token_manager)?;
+    }
+
+    let amount = PENDING_VOTING_REWARDS
+        .may_load(deps.storage, key)?
+        .unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NoVotingRewards {});
+    }
+    PENDING_VOTING_REWARDS.remove(deps.storage, key);
+
+    // the rewards were kept out of the staked balance as part of the total deposit
+    let mut state: State = STATE.load(deps.storage)?;
+    state.total_deposit = state.total_deposit.checked_sub(amount)?;
+    STATE.save(deps.storage, &state)?;
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.whale_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }))
+        .add_attributes(
+            WhaleEvent::new(GOVERNANCE, "claim_voting_rewards")
+                .add_attribute("recipient", info.sender.as_str())
+                .add_attribute("amount", amount.to_string()),
+        ))
+}
+
+pub fn query_pending_voting_rewards(
+    deps: Deps,
+    address: String,
+) -> StdResult<PendingVotingRewardsResponse> {
+    let addr_raw = deps.api.addr_canonicalize(&address)?;
+    let mut pending_voting_rewards = PENDING_VOTING_REWARDS
+        .may_load(deps.storage, addr_raw.as_slice())?
+        .unwrap_or_default();
+
+    // add the rewards of the ended polls which were not credited yet
+    let token_manager = BANK
+        .may_load(deps.storage, addr_raw.as_slice())?
+        .unwrap_or_default();
+    for (poll_id, voter_info) in token_manager.locked_balance {
+        let poll: Poll = polls().load(deps.storage, U64Key::new(poll_id))?;
+        if poll.status != PollStatus::InProgress {
+            pending_voting_rewards += voter_reward(deps.storage, poll_id, voter_info.balance)?;
+        }
+    }
+
+    Ok(PendingVotingRewardsResponse {
+        pending_voting_rewards,
+    })
+}
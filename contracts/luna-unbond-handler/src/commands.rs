@@ -6,6 +6,7 @@ use cw20::Cw20ReceiveMsg;
 use terraswap::asset::{Asset, AssetInfo};
 use terraswap::querier::query_balance;
 
+use white_whale::address::validate_addr;
 use white_whale::anchor::{anchor_bluna_unbond_msg, anchor_withdraw_unbonded_msg};
 use white_whale::denom::LUNA_DENOM;
 use white_whale::luna_vault::luna_unbond_handler::msg::{CallbackMsg, Cw20HookMsg};
@@ -145,7 +146,7 @@ pub fn update_state(
     let mut attrs = vec![];
 
     if let Some(owner) = owner {
-        state.owner = Some(deps.api.addr_validate(&owner)?);
+        state.owner = Some(validate_addr(deps.api, "owner", &owner)?);
         attrs.push(("new_owner", owner));
     }
 
@@ -155,7 +156,7 @@ pub fn update_state(
     }
 
     if let Some(memory_contract) = memory_contract {
-        state.memory_contract = deps.api.addr_validate(&memory_contract)?;
+        state.memory_contract = validate_addr(deps.api, "memory_contract", &memory_contract)?;
         attrs.push(("new_memory_contract", memory_contract));
     }
 
@@ -7,6 +7,7 @@ use semver::Version;
 use crate::serde_option::serde_option;
 use crate::state::{State, ADMIN, STATE};
 use crate::{commands, queries, UnbondHandlerError, UnbondHandlerResult};
+use white_whale::address::validate_addr;
 use white_whale::luna_vault::luna_unbond_handler::msg::{
     ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
 };
@@ -28,11 +29,11 @@ pub fn instantiate(
     let mut state = State {
         owner: None,
         expiration_time: None,
-        memory_contract: deps.api.addr_validate(&msg.memory_contract)?,
+        memory_contract: validate_addr(deps.api, "memory_contract", &msg.memory_contract)?,
     };
 
     if let Some(owner) = msg.owner {
-        state.owner = Some(deps.api.addr_validate(&owner)?);
+        state.owner = Some(validate_addr(deps.api, "owner", &owner)?);
 
         let expiration_time = env
             .block
@@ -1,6 +1,7 @@
 use cosmwasm_std::{OverflowError, StdError};
 use cw_controllers::AdminError;
 use thiserror::Error;
+use white_whale::error::CommonError;
 use white_whale::memory::error::MemoryError;
 
 #[derive(Error, Debug, PartialEq)]
@@ -8,6 +9,9 @@ pub enum UnbondHandlerError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
@@ -3,11 +3,13 @@ use cosmwasm_std::{
     Addr, DepsMut, Response,
 };
 use cw2::{ContractVersion, CONTRACT};
+use white_whale::error::CommonError;
 use white_whale::luna_vault::luna_unbond_handler::msg::InstantiateMsg;
 
 use crate::{
     contract::{instantiate, CONTRACT_NAME, CONTRACT_VERSION},
     state::{State, STATE},
+    UnbondHandlerError,
 };
 
 use super::common::{mock_creator_info, TEST_CREATOR, TEST_MEMORY_CONTRACT, TEST_OWNER};
@@ -74,3 +76,36 @@ fn initialization_does_set_version() {
         }
     )
 }
+
+#[test]
+fn initialization_with_invalid_address_fails() {
+    let invalid_msgs = vec![
+        (
+            "owner",
+            InstantiateMsg {
+                owner: Some("x".into()),
+                expires_in: None,
+                memory_contract: TEST_MEMORY_CONTRACT.into(),
+            },
+        ),
+        (
+            "memory_contract",
+            InstantiateMsg {
+                owner: None,
+                expires_in: None,
+                memory_contract: "x".into(),
+            },
+        ),
+    ];
+
+    for (field, msg) in invalid_msgs {
+        let mut deps = mock_dependencies(&[]);
+        match instantiate(deps.as_mut(), mock_env(), mock_creator_info(), msg) {
+            Err(UnbondHandlerError::Common(CommonError::InvalidAddress {
+                field: invalid_field,
+                ..
+            })) => assert_eq!(invalid_field, field),
+            _ => panic!("Must return CommonError::InvalidAddress for {}", field),
+        }
+    }
+}
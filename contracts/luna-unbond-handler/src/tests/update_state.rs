@@ -3,11 +3,13 @@ use cosmwasm_std::{
     testing::{mock_dependencies, mock_env},
     Addr,
 };
+use white_whale::error::CommonError;
 use white_whale::luna_vault::luna_unbond_handler::msg::ExecuteMsg;
 
 use crate::{
     contract::execute,
     state::{State, STATE},
+    UnbondHandlerError,
 };
 
 use super::{
@@ -64,3 +66,26 @@ fn unauthorized_update_state() {
     )
     .unwrap_err();
 }
+
+#[test]
+fn update_state_with_invalid_address_fails() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_creator_info(),
+        ExecuteMsg::UpdateState {
+            owner: None,
+            expiration_time: None,
+            memory_contract: Some("x".into()),
+        },
+    );
+    match res {
+        Err(UnbondHandlerError::Common(CommonError::InvalidAddress { field, .. })) => {
+            assert_eq!(field, "memory_contract")
+        }
+        _ => panic!("Must return CommonError::InvalidAddress"),
+    }
+}
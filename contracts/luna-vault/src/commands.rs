@@ -1,36 +1,52 @@
 use astroport::querier::query_token_balance;
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
-    ReplyOn, Response, SubMsg, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Addr, Attribute, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, ReplyOn, Response, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
 use terraswap::asset::{Asset, AssetInfo};
 use terraswap::querier::query_supply;
 
+use white_whale::address::validate_addr;
+use white_whale::admin::{propose_admin, DEFAULT_ADMIN_PROPOSAL_EXPIRY};
 use white_whale::anchor::anchor_withdraw_unbonded_msg;
 use white_whale::denom::LUNA_DENOM;
+use white_whale::events::{VaultEvent, WhaleEvent, LUNA_VAULT};
 use white_whale::fee::Fee;
 use white_whale::luna_vault::luna_unbond_handler::msg::InstantiateMsg;
-use white_whale::luna_vault::msg::{Cw20HookMsg, UnbondHandlerMsg};
+use white_whale::luna_vault::msg::{Cw20HookMsg, SlashingEvent, UnbondHandlerMsg};
+use white_whale::luna_vault::staking_backend::LiquidStakingBackend;
 use white_whale::memory::queries::query_contract_from_mem;
 use white_whale::memory::{ANCHOR_BLUNA_HUB_ID, LIST_SIZE_LIMIT, PRISM_CLUNA_HUB_ID};
+use white_whale::pause::{assert_not_paused, PauseScope};
 use white_whale::prism::prism_withdraw_unbonded_msg;
+use white_whale::query::terraswap::query_asset_balance;
 use white_whale::query::{anchor, prism};
+use white_whale::swap::{Dex, Swap};
 
-use crate::contract::{VaultResult, INSTANTIATE_UNBOND_HANDLER_REPLY_ID};
+use crate::contract::{VaultResult, INSTANTIATE_UNBOND_HANDLER_REPLY_ID, MAX_TOTAL_FEE};
 use crate::error::LunaVaultError;
 use crate::helpers::{
-    check_fee, compute_total_value, get_lp_token_address, get_share_amount, get_treasury_fee,
-    unbond_bluna_with_handler_msg, update_unbond_handler_state_msg, withdraw_luna_from_handler_msg,
-    ConversionAsset,
+    accrue_fees, compute_total_value, get_exchange_rate, get_lp_token_address, get_share_amount,
+    get_treasury_fee, load_pending_fees, load_staking_backends, unbond_bluna_with_handler_msg,
+    update_unbond_handler_state_msg, withdraw_luna_from_handler_msg, ConversionAsset,
 };
 use crate::pool_info::PoolInfoRaw;
 use crate::queries::{query_unbond_handler_expiration_time, query_withdrawable_unbonded};
+use crate::replies::store_liquidity_token;
 use crate::state::{
-    UnbondDataCache, ADMIN, DEPOSIT_INFO, FEE, POOL_INFO, PROFIT, STATE, UNBOND_CACHE,
-    UNBOND_HANDLERS_ASSIGNED, UNBOND_HANDLERS_AVAILABLE, UNBOND_HANDLER_EXPIRATION_TIMES,
+    PendingFees, StakingBackend, State, UnbondDataCache, ADMIN, BLUNA_EXCHANGE_RATE,
+    DEPOSITS_HALTED, DEPOSIT_INFO, FEE, LUNA_CAP, MAX_SLASHING_THRESHOLD,
+    MAX_UNBOND_EXPIRATION_TIME, MIN_UNBOND_EXPIRATION_TIME, PENDING_FEES, POOL_INFO, PROFIT,
+    SLASHING_EVENTS, SLASHING_EVENT_COUNT, SLASHING_GUARD, SLASHING_THRESHOLD, STAKING_BACKENDS,
+    STATE, UNBOND_CACHE, UNBOND_HANDLERS_ASSIGNED, UNBOND_HANDLERS_AVAILABLE,
+    UNBOND_HANDLER_EXPIRATION_TIME, UNBOND_HANDLER_EXPIRATION_TIMES,
 };
 
+/// Max spread in percent for swapping the compounded rewards into luna
+pub const COMPOUND_MAX_SPREAD: u64 = 1;
+
 /// handler function invoked when the luna-vault contract receives
 /// a transaction. In this case it is triggered when the LP tokens are deposited
 /// into the contract
@@ -42,6 +58,7 @@ pub fn receive_cw20(
 ) -> VaultResult<Response> {
     match from_binary(&cw20_msg.msg)? {
         Cw20HookMsg::Unbond {} => {
+            assert_not_paused(deps.storage, PauseScope::Withdrawals)?;
             // only vLuna token contract can execute this message
             let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
             if deps.api.addr_validate(&msg_info.sender.to_string())? != info.liquidity_token {
@@ -49,6 +66,15 @@ pub fn receive_cw20(
             }
             unbond(deps, env, cw20_msg.amount, cw20_msg.sender)
         }
+        Cw20HookMsg::ProvideLiquidity {} => {
+            // only the bLuna token contract can execute this message
+            let state = STATE.load(deps.storage)?;
+            if msg_info.sender != state.bluna_address {
+                return Err(LunaVaultError::Unauthorized {});
+            }
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            provide_bluna_liquidity(deps, env, cw20_msg.amount, sender)
+        }
     }
 }
 
@@ -59,6 +85,8 @@ pub fn provide_liquidity(
     msg_info: MessageInfo,
     asset: Asset,
 ) -> VaultResult<Response> {
+    assert_deposits_allowed(deps.storage)?;
+    assert_not_paused(deps.storage, PauseScope::Deposits)?;
     let deposit_info = DEPOSIT_INFO.load(deps.storage)?;
     let profit = PROFIT.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
@@ -68,13 +96,11 @@ pub fn provide_liquidity(
         return Err(LunaVaultError::DepositDuringLoan {});
     }
 
-    // Init vector for logging
-    let mut attrs = vec![];
     // Check if deposit matches claimed deposit.
     deposit_info.assert(&asset.info)?;
     asset.assert_sent_native_token_balance(&msg_info)?;
-    attrs.push(("action", String::from("provide_liquidity")));
-    attrs.push(("received funds", asset.to_string()));
+    let event = WhaleEvent::new(LUNA_VAULT, "provide_liquidity")
+        .add_attribute("received_funds", asset.to_string());
 
     // Received deposit to vault
     let deposit: Uint128 = asset.amount;
@@ -82,6 +108,7 @@ pub fn provide_liquidity(
     // Get total value in Vault
     let total_deposits_in_luna =
         compute_total_value(&env, deps.as_ref(), &info)?.total_value_in_luna;
+    assert_luna_cap(deps.storage, total_deposits_in_luna)?;
     // Get total supply of vLuna tokens and calculate share
     let total_share = query_supply(&deps.querier, info.liquidity_token.clone())?;
 
@@ -93,6 +120,11 @@ pub fn provide_liquidity(
     } else {
         deposit.multiply_ratio(total_share, total_deposits_in_luna.checked_sub(deposit)?)
     };
+    let vault_event = VaultEvent::deposit(&env.contract.address, &msg_info.sender, &[asset], share)
+        .exchange_rate(get_exchange_rate(
+            total_deposits_in_luna.checked_sub(deposit)?,
+            total_share,
+        ));
 
     // mint LP token to sender
     let mint_msg = CosmosMsg::Wasm(WasmMsg::Execute {
@@ -104,7 +136,10 @@ pub fn provide_liquidity(
         funds: vec![],
     });
 
-    let response = Response::new().add_attributes(attrs).add_message(mint_msg);
+    let response = Response::new()
+        .add_attributes(event)
+        .add_event(vault_event.into())
+        .add_message(mint_msg);
     // Deposit liquid luna into passive strategy
     deposit_passive_strategy(
         &deps.as_ref(),
@@ -115,6 +150,164 @@ pub fn provide_liquidity(
     )
 }
 
+/// Fails if the vault value after a deposit is above the luna cap
+fn assert_luna_cap(storage: &dyn Storage, value_after_deposit: Uint128) -> VaultResult<()> {
+    if let Some(cap) = LUNA_CAP.may_load(storage)? {
+        if value_after_deposit > cap {
+            return Err(LunaVaultError::VaultCapExceeded { cap });
+        }
+    }
+    Ok(())
+}
+
+// Deposits bLuna into the contract. bLuna is valued 1:1 with Luna, like in [compute_total_value].
+pub fn provide_bluna_liquidity(
+    deps: DepsMut,
+    env: Env,
+    amount: Uint128,
+    sender: Addr,
+) -> VaultResult<Response> {
+    assert_deposits_allowed(deps.storage)?;
+    assert_not_paused(deps.storage, PauseScope::Deposits)?;
+    let profit = PROFIT.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+
+    if profit.last_balance != Uint128::zero() {
+        return Err(LunaVaultError::DepositDuringLoan {});
+    }
+    if amount.is_zero() {
+        return Err(LunaVaultError::InvalidZeroAmount {});
+    }
+
+    // The bLuna has already been transferred, so the deposit is included in the total
+    let total_deposits_in_luna =
+        compute_total_value(&env, deps.as_ref(), &info)?.total_value_in_luna;
+    assert_luna_cap(deps.storage, total_deposits_in_luna)?;
+    let total_share = query_supply(&deps.querier, info.liquidity_token.clone())?;
+
+    let share = if total_share == Uint128::zero()
+        || total_deposits_in_luna.checked_sub(amount)? == Uint128::zero()
+    {
+        // Initial share = collateral amount
+        amount
+    } else {
+        amount.multiply_ratio(total_share, total_deposits_in_luna.checked_sub(amount)?)
+    };
+    let received_funds = format!("{}{}", amount, state.bluna_address);
+    let vault_event = VaultEvent::deposit(
+        &env.contract.address,
+        &sender,
+        &[received_funds.clone()],
+        share,
+    )
+    .exchange_rate(get_exchange_rate(
+        total_deposits_in_luna.checked_sub(amount)?,
+        total_share,
+    ));
+
+    // mint LP token to sender
+    let mint_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: info.liquidity_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: sender.to_string(),
+            amount: share,
+        })?,
+        funds: vec![],
+    });
+
+    let response = Response::new()
+        .add_attributes(
+            WhaleEvent::new(LUNA_VAULT, "provide_liquidity")
+                .add_attribute("received_funds", received_funds),
+        )
+        .add_event(vault_event.into())
+        .add_message(mint_msg);
+    // Deposit the bLuna into passive strategy
+    deposit_bluna_passive_strategy(
+        &deps.as_ref(),
+        amount,
+        state.bluna_address,
+        &state.astro_lp_address,
+        response,
+    )
+}
+
+// Deposits bLuna into the passive strategy (Astroport) -> luna-bluna LP
+fn deposit_bluna_passive_strategy(
+    deps: &Deps,
+    deposit_amount: Uint128,
+    bluna_address: Addr,
+    astro_lp_address: &Addr,
+    response: Response,
+) -> VaultResult<Response> {
+    // split bluna into half so half goes to purchase Luna, remaining half is used as liquidity
+    let swap_amount = deposit_amount.checked_div(Uint128::from(2_u8))?;
+    let bluna_asset = astroport::asset::Asset {
+        amount: deposit_amount.checked_sub(swap_amount)?,
+        info: astroport::asset::AssetInfo::Token {
+            contract_addr: bluna_address.clone(),
+        },
+    };
+
+    // simulate the bluna swap so we know the luna return amount when we later provide liquidity
+    let luna_return: astroport::pair::SimulationResponse = deps.querier.query_wasm_smart(
+        astro_lp_address,
+        &astroport::pair::QueryMsg::Simulation {
+            offer_asset: astroport::asset::Asset {
+                amount: swap_amount,
+                info: bluna_asset.info.clone(),
+            },
+        },
+    )?;
+
+    let luna_asset = astroport::asset::Asset {
+        amount: luna_return.return_amount,
+        info: astroport::asset::AssetInfo::NativeToken {
+            denom: LUNA_DENOM.to_string(),
+        },
+    };
+
+    let luna_purchase_msg: CosmosMsg = Swap::new(
+        Dex::Astroport,
+        astro_lp_address.clone(),
+        Asset {
+            amount: swap_amount,
+            info: AssetInfo::Token {
+                contract_addr: bluna_address.to_string(),
+            },
+        },
+    )
+    .into_msg()?;
+
+    let allowance_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: bluna_address.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+            spender: astro_lp_address.to_string(),
+            amount: bluna_asset.amount,
+            expires: None,
+        })?,
+        funds: vec![],
+    });
+
+    let deposit_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: astro_lp_address.to_string(),
+        msg: to_binary(&astroport::pair::ExecuteMsg::ProvideLiquidity {
+            assets: [luna_asset.clone(), bluna_asset],
+            slippage_tolerance: None,
+            auto_stake: None,
+            receiver: None,
+        })?,
+        funds: vec![Coin::new(luna_asset.amount.u128(), LUNA_DENOM)],
+    });
+
+    Ok(response.add_messages(vec![
+        luna_purchase_msg, // 1. purchase luna
+        allowance_msg,     // 2. allow the pair to pull the bLuna
+        deposit_msg,       // 3. deposit bLuna/Luna to the LP as liquidity
+    ]))
+}
+
 // Deposits Luna into the passive strategy (Astroport) -> luna-bluna LP
 pub(crate) fn deposit_passive_strategy(
     deps: &Deps,
@@ -142,17 +335,23 @@ pub(crate) fn deposit_passive_strategy(
     let bluna_asset = astroport::asset::Asset {
         amount: bluna_return.return_amount,
         info: astroport::asset::AssetInfo::Token {
-            contract_addr: bluna_address,
+            contract_addr: bluna_address.clone(),
         },
     };
 
-    let bluna_purchase_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: astro_lp_address.to_string(),
-        msg: to_binary(&astroport::pair::ExecuteMsg::Swap {
-            offer_asset: luna_asset.clone(),
-            belief_price: None,
-            max_spread: None,
-            to: None,
+    let bluna_purchase_msg: CosmosMsg = Swap::native(
+        Dex::Astroport,
+        astro_lp_address.clone(),
+        Coin::new(luna_asset.amount.u128(), LUNA_DENOM),
+    )
+    .into_msg()?;
+
+    let allowance_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: bluna_address.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+            spender: astro_lp_address.to_string(),
+            amount: bluna_asset.amount,
+            expires: None,
         })?,
         funds: vec![],
     });
@@ -160,17 +359,18 @@ pub(crate) fn deposit_passive_strategy(
     let deposit_msg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: astro_lp_address.to_string(),
         msg: to_binary(&astroport::pair::ExecuteMsg::ProvideLiquidity {
-            assets: [luna_asset, bluna_asset],
+            assets: [luna_asset.clone(), bluna_asset],
             slippage_tolerance: None,
             auto_stake: None,
             receiver: None,
         })?,
-        funds: vec![],
+        funds: vec![Coin::new(luna_asset.amount.u128(), LUNA_DENOM)],
     });
 
     let response = response.add_messages(vec![
         bluna_purchase_msg, // 1. purchase bluna
-        deposit_msg,        // 2. deposit bLuna/Luna to the LP as liquidity
+        allowance_msg,      // 2. allow the pair to pull the bLuna
+        deposit_msg,        // 3. deposit bLuna/Luna to the LP as liquidity
     ]);
 
     Ok(response)
@@ -274,7 +474,7 @@ fn unbond(
     }
 
     // Logging var
-    let mut response = Response::new().add_attribute("action", "unbond");
+    let mut response = Response::new().add_attributes(WhaleEvent::new(LUNA_VAULT, "unbond"));
     let mut attrs = vec![
         ("from", sender.clone()),
         ("burnt_amount", amount.to_string()),
@@ -287,26 +487,14 @@ fn unbond(
     // Calculate share of pool and requested pool value
     let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
     let total_share = query_supply(&deps.querier, info.liquidity_token.clone())?;
+    let total_value_in_luna = compute_total_value(&env, deps.as_ref(), &info)?.total_value_in_luna;
     // Share with fee deducted.
     let share_ratio: Decimal = Decimal::from_ratio(amount - treasury_fee, total_share);
 
     let sender_addr = deps.api.addr_validate(&sender)?;
 
-    // LP token treasury Asset
-    let lp_token_treasury_fee = Asset {
-        info: AssetInfo::Token {
-            contract_addr: info.liquidity_token.to_string(),
-        },
-        amount: treasury_fee,
-    };
-
-    // Construct treasury fee msg.
-    let fee_config = FEE.load(deps.storage)?;
-    let treasury_fee_msg = fee_config.treasury_fee.msg(
-        deps.as_ref(),
-        lp_token_treasury_fee,
-        fee_config.treasury_addr,
-    )?;
+    // The vault keeps the treasury fee until it's collected
+    accrue_fees(deps.storage, treasury_fee, Uint128::zero())?;
 
     // Send Burn message to vluna contract
     let burn_msg = CosmosMsg::Wasm(WasmMsg::Execute {
@@ -422,6 +610,22 @@ fn unbond(
         })?
         .amount;
 
+    // The luna and the unbonding bLuna are paid out through the unbond handler
+    let assets_out = [
+        format!("{}{}", luna_asset.amount, LUNA_DENOM),
+        format!("{}{}", bluna_amount, state.bluna_address),
+    ];
+    response = response.add_event(
+        VaultEvent::withdraw(
+            &env.contract.address,
+            &sender_addr,
+            &assets_out,
+            amount - treasury_fee,
+        )
+        .exchange_rate(get_exchange_rate(total_value_in_luna, total_share))
+        .into(),
+    );
+
     // Check if there's a handler assigned to the user, send luna_amount and bluna_amount + unbond msg to it
     if let Some(unbond_handler) =
         UNBOND_HANDLERS_ASSIGNED.may_load(deps.storage, sender_addr.clone())?
@@ -525,9 +729,7 @@ fn unbond(
         }
     }
 
-    Ok(response
-        .add_messages(vec![burn_msg, treasury_fee_msg])
-        .add_attributes(attrs))
+    Ok(response.add_message(burn_msg).add_attributes(attrs))
 }
 
 /// Withdraws unbonded luna after unbond has been called and the time lock period expired
@@ -537,6 +739,7 @@ pub fn withdraw_unbonded(
     liquidation: bool,
     liquidate_addr: Option<String>,
 ) -> VaultResult<Response> {
+    assert_not_paused(deps.storage, PauseScope::Withdrawals)?;
     let unbond_handler = if liquidation {
         // validate liquidate_addr
         let liquidate_addr = liquidate_addr.ok_or(LunaVaultError::UnbondHandlerError {})?;
@@ -555,22 +758,29 @@ pub fn withdraw_unbonded(
         query_withdrawable_unbonded(deps.as_ref(), unbond_handler.to_string())?.withdrawable;
 
     Ok(Response::new()
-        .add_attributes(vec![
-            attr("action", "withdraw_unbonded"),
-            attr("unbond_handler", unbond_handler.to_string()),
-            attr("withadrawable_amount", withadrawable_amount.to_string()),
-        ])
+        .add_attributes(
+            WhaleEvent::new(LUNA_VAULT, "withdraw_unbonded")
+                .add_attribute("unbond_handler", unbond_handler.to_string())
+                .add_attribute("withdrawable_amount", withadrawable_amount.to_string()),
+        )
         .add_message(withdraw_unbonded_msg))
 }
 
-/// Sets a new admin
-pub fn set_admin(deps: DepsMut, info: MessageInfo, admin: String) -> VaultResult<Response> {
-    let admin_addr = deps.api.addr_validate(&admin)?;
-    let previous_admin = ADMIN.get(deps.as_ref())?.unwrap();
-    ADMIN.execute_update_admin(deps, info, Some(admin_addr))?;
-    Ok(Response::default()
-        .add_attribute("previous admin", previous_admin)
-        .add_attribute("admin", admin))
+/// Proposes a new admin with the default expiry, kept for the deprecated SetAdmin message
+pub fn set_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    admin: String,
+) -> VaultResult<Response> {
+    Ok(propose_admin(
+        deps,
+        &env,
+        &info,
+        &ADMIN,
+        admin,
+        DEFAULT_ADMIN_PROPOSAL_EXPIRY,
+    )?)
 }
 
 /// Sets new fees for vault, flashloan and treasury
@@ -586,19 +796,282 @@ pub fn set_fee(
     let mut fee_config = FEE.load(deps.storage)?;
 
     if let Some(fee) = flash_loan_fee {
-        fee_config.flash_loan_fee = check_fee(fee)?;
+        fee_config.flash_loan_fee = fee;
     }
     if let Some(fee) = treasury_fee {
-        fee_config.treasury_fee = check_fee(fee)?;
+        fee_config.treasury_fee = fee;
     }
     if let Some(fee) = commission_fee {
-        fee_config.commission_fee = check_fee(fee)?;
+        fee_config.commission_fee = fee;
     }
+    fee_config.validate(Decimal::percent(MAX_TOTAL_FEE))?;
 
     FEE.save(deps.storage, &fee_config)?;
     Ok(Response::default())
 }
 
+/// Moves liquid luna of the vault into the passive strategy, callable by the admin or whitelisted contracts
+pub fn deposit_into_lp(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    amount: Uint128,
+) -> VaultResult<Response> {
+    let state = STATE.load(deps.storage)?;
+    assert_admin_or_whitelisted(deps.as_ref(), &state, &msg_info.sender)?;
+    if amount.is_zero() {
+        return Err(LunaVaultError::InvalidZeroAmount {});
+    }
+
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+    let luna_info = info.asset_infos[0].to_normal(deps.api)?;
+    // the accrued commission can't be deposited
+    let luna_amount = query_asset_balance(deps.as_ref(), &luna_info, info.contract_addr)?
+        .saturating_sub(load_pending_fees(deps.storage)?.commission);
+    if luna_amount < amount {
+        return Err(LunaVaultError::Broke {});
+    }
+
+    let response = WhaleEvent::new(LUNA_VAULT, "deposit_into_lp")
+        .add_attribute("amount", amount)
+        .into_response();
+    deposit_passive_strategy(
+        &deps.as_ref(),
+        amount,
+        state.bluna_address,
+        &state.astro_lp_address,
+        response,
+    )
+}
+
+/// Withdraws `share` LP tokens of the passive strategy into liquid luna and bLuna, callable by the admin
+/// or whitelisted contracts
+pub fn withdraw_from_lp(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    share: Uint128,
+) -> VaultResult<Response> {
+    let state = STATE.load(deps.storage)?;
+    assert_admin_or_whitelisted(deps.as_ref(), &state, &msg_info.sender)?;
+    if share.is_zero() {
+        return Err(LunaVaultError::InvalidZeroAmount {});
+    }
+
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+    let astro_lp_info = info.asset_infos[1].to_normal(deps.api)?;
+    let astro_lp_amount = query_asset_balance(deps.as_ref(), &astro_lp_info, info.contract_addr)?;
+    if astro_lp_amount < share {
+        return Err(LunaVaultError::Broke {});
+    }
+
+    let withdraw_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: get_lp_token_address(&deps.as_ref(), state.astro_lp_address.clone())?
+            .into_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: state.astro_lp_address.into_string(),
+            amount: share,
+            msg: to_binary(&astroport::pair::Cw20HookMsg::WithdrawLiquidity {})?,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_attributes(
+            WhaleEvent::new(LUNA_VAULT, "withdraw_from_lp").add_attribute("share", share),
+        )
+        .add_message(withdraw_msg))
+}
+
+/// Registers a liquid staking backend, only the admin can call this
+pub fn add_staking_backend(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    name: String,
+    backend: LiquidStakingBackend,
+    token_address: String,
+    hub_address: String,
+) -> VaultResult<Response> {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    if STAKING_BACKENDS.may_load(deps.storage, &name)?.is_some() {
+        return Err(LunaVaultError::StakingBackendAlreadyExists { name });
+    }
+    // The backends are iterated to compute the vault value, keep their number bounded
+    if load_staking_backends(deps.storage)?.len() >= LIST_SIZE_LIMIT {
+        return Err(LunaVaultError::StakingBackendLimitReached {});
+    }
+
+    STAKING_BACKENDS.save(
+        deps.storage,
+        &name,
+        &StakingBackend {
+            backend,
+            token_address: deps.api.addr_validate(&token_address)?,
+            hub_address: deps.api.addr_validate(&hub_address)?,
+            weight: Decimal::zero(),
+        },
+    )?;
+
+    Ok(WhaleEvent::new(LUNA_VAULT, "add_staking_backend")
+        .add_attribute("name", name)
+        .add_attribute("token_address", token_address)
+        .add_attribute("hub_address", hub_address)
+        .into_response())
+}
+
+/// Removes a staking backend without weight whose token the vault doesn't hold, only the admin can call this
+pub fn remove_staking_backend(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    name: String,
+) -> VaultResult<Response> {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let backend = STAKING_BACKENDS
+        .may_load(deps.storage, &name)?
+        .ok_or_else(|| LunaVaultError::StakingBackendNotFound { name: name.clone() })?;
+    // the vault value of the tokens would be lost with the backend
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+    let balance = query_token_balance(&deps.querier, backend.token_address, info.contract_addr)?;
+    if !backend.weight.is_zero() || !balance.is_zero() {
+        return Err(LunaVaultError::StakingBackendInUse {});
+    }
+
+    STAKING_BACKENDS.remove(deps.storage, &name);
+    Ok(WhaleEvent::new(LUNA_VAULT, "remove_staking_backend")
+        .add_attribute("name", name)
+        .into_response())
+}
+
+/// Sets the weights of the staking backends, backends not listed get a weight of zero.
+/// Only the admin can call this
+pub fn set_staking_backend_weights(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    weights: Vec<(String, Decimal)>,
+) -> VaultResult<Response> {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let backends = load_staking_backends(deps.storage)?;
+    let mut total_weight = Decimal::zero();
+    for (index, (name, weight)) in weights.iter().enumerate() {
+        if !backends
+            .iter()
+            .any(|(backend_name, _)| backend_name == name)
+        {
+            return Err(LunaVaultError::StakingBackendNotFound { name: name.clone() });
+        }
+        if *weight > Decimal::one() || weights[..index].iter().any(|(other, _)| other == name) {
+            return Err(LunaVaultError::InvalidStakingBackendWeights {});
+        }
+        total_weight = total_weight + *weight;
+    }
+    // an empty list disables bonding
+    if !weights.is_empty() && total_weight != Decimal::one() {
+        return Err(LunaVaultError::InvalidStakingBackendWeights {});
+    }
+
+    let mut attrs: Vec<Attribute> = vec![];
+    for (name, mut backend) in backends {
+        backend.weight = weights
+            .iter()
+            .find(|(weight_name, _)| *weight_name == name)
+            .map_or_else(Decimal::zero, |(_, weight)| *weight);
+        attrs.push(attr(name.clone(), backend.weight.to_string()));
+        STAKING_BACKENDS.save(deps.storage, &name, &backend)?;
+    }
+
+    Ok(WhaleEvent::new(LUNA_VAULT, "set_staking_backend_weights")
+        .add_attributes(attrs)
+        .into_response())
+}
+
+/// Bonds `amount` liquid luna of the vault with the staking backends according to their weights,
+/// callable by the admin or whitelisted contracts. Luna lost to rounding stays liquid.
+pub fn bond_luna(deps: DepsMut, msg_info: MessageInfo, amount: Uint128) -> VaultResult<Response> {
+    let state = STATE.load(deps.storage)?;
+    assert_admin_or_whitelisted(deps.as_ref(), &state, &msg_info.sender)?;
+    if amount.is_zero() {
+        return Err(LunaVaultError::InvalidZeroAmount {});
+    }
+
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+    let luna_info = info.asset_infos[0].to_normal(deps.api)?;
+    // the accrued commission can't be bonded
+    let luna_amount = query_asset_balance(deps.as_ref(), &luna_info, info.contract_addr)?
+        .saturating_sub(load_pending_fees(deps.storage)?.commission);
+    if luna_amount < amount {
+        return Err(LunaVaultError::Broke {});
+    }
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut attrs: Vec<Attribute> = vec![];
+    for (name, backend) in load_staking_backends(deps.storage)? {
+        let bond_amount = amount * backend.weight;
+        if bond_amount.is_zero() {
+            continue;
+        }
+        msgs.push(backend.backend.bond_msg(backend.hub_address, bond_amount)?);
+        attrs.push(attr(name, bond_amount));
+    }
+    if msgs.is_empty() {
+        return Err(LunaVaultError::NoStakingBackendWeights {});
+    }
+
+    Ok(WhaleEvent::new(LUNA_VAULT, "bond_luna")
+        .add_attribute("amount", amount)
+        .add_attributes(attrs)
+        .into_response()
+        .add_messages(msgs))
+}
+
+/// Fails if `sender` is neither the admin nor a whitelisted contract
+fn assert_admin_or_whitelisted(deps: Deps, state: &State, sender: &Addr) -> VaultResult<()> {
+    if !state.whitelisted_contracts.contains(sender) && !ADMIN.is_admin(deps, sender)? {
+        return Err(LunaVaultError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Sets the liquidity token if its instantiation reply didn't store it
+pub fn set_lp_token(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    address: String,
+) -> VaultResult<Response> {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let liquidity_token = deps.api.addr_validate(&address)?;
+    store_liquidity_token(deps, liquidity_token)?;
+    Ok(WhaleEvent::new(LUNA_VAULT, "set_lp_token")
+        .add_attribute("liquidity_token_addr", address)
+        .into_response())
+}
+
+/// Updates the luna cap of the vault
+pub fn update_luna_cap(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    cap: Option<Uint128>,
+) -> VaultResult<Response> {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    match cap {
+        Some(cap) => LUNA_CAP.save(deps.storage, &cap)?,
+        None => LUNA_CAP.remove(deps.storage),
+    }
+    Ok(WhaleEvent::new(LUNA_VAULT, "update_luna_cap")
+        .add_attribute(
+            "luna_cap",
+            cap.map_or_else(|| "none".to_string(), |cap| cap.to_string()),
+        )
+        .into_response())
+}
+
 /// Adds a contract to the whitelist
 pub fn add_to_whitelist(
     deps: DepsMut,
@@ -629,7 +1102,9 @@ pub fn add_to_whitelist(
     STATE.save(deps.storage, &state)?;
 
     // Respond and note the change
-    Ok(Response::new().add_attribute("Added contract to whitelist: ", contract_addr))
+    Ok(WhaleEvent::new(LUNA_VAULT, "add_to_whitelist")
+        .add_attribute("contract", contract_addr)
+        .into_response())
 }
 
 /// Removes a contract from the whitelist
@@ -658,7 +1133,9 @@ pub fn remove_from_whitelist(
     STATE.save(deps.storage, &state)?;
 
     // Respond and note the change
-    Ok(Response::new().add_attribute("Removed contract from whitelist: ", contract_addr))
+    Ok(WhaleEvent::new(LUNA_VAULT, "remove_from_whitelist")
+        .add_attribute("contract", contract_addr)
+        .into_response())
 }
 
 /// Updates the contract state
@@ -682,43 +1159,50 @@ pub fn update_state(
     let mut attrs = vec![];
 
     if let Some(bluna_address) = bluna_address {
-        state.bluna_address = api.addr_validate(&bluna_address)?;
-        attrs.push(("new bluna_address", bluna_address));
+        state.bluna_address = validate_addr(api, "bluna_address", &bluna_address)?;
+        attrs.push(("bluna_address", bluna_address));
     }
     if let Some(cluna_address) = cluna_address {
-        state.cluna_address = api.addr_validate(&cluna_address)?;
-        attrs.push(("new cluna_address", cluna_address));
+        state.cluna_address = validate_addr(api, "cluna_address", &cluna_address)?;
+        attrs.push(("cluna_address", cluna_address));
     }
     if let Some(astro_lp_address) = astro_lp_address {
-        state.astro_lp_address = api.addr_validate(&astro_lp_address)?;
-        attrs.push(("new astro_lp_address", astro_lp_address));
+        state.astro_lp_address = validate_addr(api, "astro_lp_address", &astro_lp_address)?;
+        attrs.push(("astro_lp_address", astro_lp_address));
     }
     if let Some(memory_address) = memory_address {
-        state.memory_address = api.addr_validate(&memory_address)?;
-        attrs.push(("new memory_address", memory_address));
+        state.memory_address = validate_addr(api, "memory_address", &memory_address)?;
+        attrs.push(("memory_address", memory_address));
     }
     if let Some(whitelisted_contracts) = whitelisted_contracts {
+        // Same limit as in add_to_whitelist
+        if whitelisted_contracts.len() > LIST_SIZE_LIMIT {
+            return Err(LunaVaultError::WhitelistLimitReached {});
+        }
         let mut contracts = vec![];
         for contract_addr in whitelisted_contracts.clone() {
-            contracts.push(deps.api.addr_validate(&contract_addr)?);
+            let contract_addr = deps.api.addr_validate(&contract_addr)?;
+            if contracts.contains(&contract_addr) {
+                return Err(LunaVaultError::AlreadyWhitelisted {});
+            }
+            contracts.push(contract_addr);
         }
         state.whitelisted_contracts = contracts;
         attrs.push((
-            "new whitelisted_contracts",
+            "whitelisted_contracts",
             format!("{:?}", whitelisted_contracts),
         ));
     }
     if let Some(allow_non_whitelisted) = allow_non_whitelisted {
         state.allow_non_whitelisted = allow_non_whitelisted;
-        attrs.push((
-            "new allow_non_whitelisted",
-            allow_non_whitelisted.to_string(),
-        ));
+        attrs.push(("allow_non_whitelisted", allow_non_whitelisted.to_string()));
     }
 
     STATE.save(deps.storage, &state)?;
 
-    Ok(Response::new().add_attributes(attrs))
+    Ok(WhaleEvent::new(LUNA_VAULT, "update_state")
+        .add_attributes(attrs)
+        .into_response())
 }
 
 pub fn swap_rewards(deps: DepsMut, env: Env, msg_info: MessageInfo) -> VaultResult<Response> {
@@ -728,20 +1212,116 @@ pub fn swap_rewards(deps: DepsMut, env: Env, msg_info: MessageInfo) -> VaultResu
         return Err(LunaVaultError::NotWhitelisted {});
     }
 
-    let mut response = Response::new();
+    let (messages, astro_pending, swap_luna_return) =
+        claim_astro_rewards_msgs(deps.as_ref(), &env, &state, None)?;
+    let response = Response::new().add_messages(messages);
+
+    // Deposit luna into passive strategy
+    let response = deposit_passive_strategy(
+        &deps.as_ref(),
+        swap_luna_return,
+        state.bluna_address,
+        &state.astro_lp_address,
+        response,
+    )?;
 
-    let passive_lp_token_address =
-        get_lp_token_address(&deps.as_ref(), state.astro_lp_address.clone())?;
+    Ok(response.add_attributes(
+        WhaleEvent::new(LUNA_VAULT, "swap_rewards")
+            .add_attribute("astro_swapped", astro_pending)
+            .add_attribute("luna_return", swap_luna_return),
+    ))
+}
 
-    // swap ASTRO rewards for Luna to stay in the vault
-    let pending_tokens: astroport::generator::PendingTokenResponse =
-        deps.querier.query_wasm_smart(
-            state.astro_lp_address.clone(),
-            &astroport::generator::QueryMsg::PendingToken {
-                lp_token: passive_lp_token_address.clone().into_string(),
-                user: env.contract.address.into_string(),
+/// Claims the ASTRO rewards of the passive strategy, swaps them to luna with a max spread of
+/// [COMPOUND_MAX_SPREAD], accrues the commission for the treasury and deposits the remainder back
+/// into the passive strategy. Can be called by anyone.
+pub fn claim_and_compound(deps: DepsMut, env: Env) -> VaultResult<Response> {
+    let state = STATE.load(deps.storage)?;
+    let fees = FEE.load(deps.storage)?;
+
+    let (messages, astro_pending, swap_luna_return) = claim_astro_rewards_msgs(
+        deps.as_ref(),
+        &env,
+        &state,
+        Some(Decimal::percent(COMPOUND_MAX_SPREAD)),
+    )?;
+    if astro_pending.is_zero() {
+        return Err(LunaVaultError::NoPendingRewards {});
+    }
+    let response = Response::new().add_messages(messages);
+
+    // take the commission on the compounded rewards, it stays in the vault until it's collected
+    let commission_amount = fees.commission_fee.compute(swap_luna_return);
+    accrue_fees(deps.storage, Uint128::zero(), commission_amount)?;
+
+    let compounded = swap_luna_return.checked_sub(commission_amount)?;
+    let response = deposit_passive_strategy(
+        &deps.as_ref(),
+        compounded,
+        state.bluna_address,
+        &state.astro_lp_address,
+        response,
+    )?;
+
+    Ok(response.add_attributes(
+        WhaleEvent::new(LUNA_VAULT, "claim_and_compound")
+            .add_attribute("astro_swapped", astro_pending)
+            .add_attribute("luna_return", swap_luna_return)
+            .add_attribute("commission_amount", commission_amount)
+            .add_attribute("compounded", compounded),
+    ))
+}
+
+/// Sends the accrued treasury fee and commission to the treasury. Can be called by anyone.
+pub fn collect_fees(deps: DepsMut) -> VaultResult<Response> {
+    let pending = load_pending_fees(deps.storage)?;
+    if pending.treasury_fee.is_zero() && pending.commission.is_zero() {
+        return Err(LunaVaultError::NoPendingFees {});
+    }
+    let fees = FEE.load(deps.storage)?;
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !pending.treasury_fee.is_zero() {
+        let lp_token_treasury_fee = Asset {
+            info: AssetInfo::Token {
+                contract_addr: info.liquidity_token.to_string(),
             },
-        )?;
+            amount: pending.treasury_fee,
+        };
+        messages.push(fees.treasury_fee.msg(
+            deps.as_ref(),
+            lp_token_treasury_fee,
+            fees.treasury_addr.clone(),
+        )?);
+    }
+    if !pending.commission.is_zero() {
+        let commission = Asset {
+            info: AssetInfo::NativeToken {
+                denom: LUNA_DENOM.to_string(),
+            },
+            amount: pending.commission,
+        };
+        messages.push(commission.into_msg(&deps.querier, fees.treasury_addr)?);
+    }
+    PENDING_FEES.save(deps.storage, &PendingFees::default())?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(
+        WhaleEvent::new(LUNA_VAULT, "collect_fees")
+            .add_attribute("treasury_fee", pending.treasury_fee)
+            .add_attribute("commission", pending.commission),
+    ))
+}
+
+/// Builds the messages claiming the pending ASTRO rewards of the passive strategy and swapping them into luna.
+/// Returns the messages, the pending ASTRO and the simulated luna return of the swap.
+fn claim_astro_rewards_msgs(
+    deps: Deps,
+    env: &Env,
+    state: &State,
+    max_spread: Option<Decimal>,
+) -> VaultResult<(Vec<CosmosMsg>, Uint128, Uint128)> {
+    let passive_lp_token_address = get_lp_token_address(&deps, state.astro_lp_address.clone())?;
 
     // get generator address
     let astro_factory_config: astroport::factory::ConfigResponse = deps.querier.query_wasm_smart(
@@ -752,6 +1332,16 @@ pub fn swap_rewards(deps: DepsMut, env: Env, msg_info: MessageInfo) -> VaultResu
         LunaVaultError::generic_err("Astroport generator was not set in factory config")
     })?;
 
+    // swap ASTRO rewards for Luna to stay in the vault
+    let pending_tokens: astroport::generator::PendingTokenResponse =
+        deps.querier.query_wasm_smart(
+            astro_generator_address.clone(),
+            &astroport::generator::QueryMsg::PendingToken {
+                lp_token: passive_lp_token_address.clone().into_string(),
+                user: env.contract.address.to_string(),
+            },
+        )?;
+
     // get ASTRO token address
     let astro_generator_config: astroport::generator_proxy::ConfigResponse =
         deps.querier.query_wasm_smart(
@@ -767,6 +1357,9 @@ pub fn swap_rewards(deps: DepsMut, env: Env, msg_info: MessageInfo) -> VaultResu
             contract_addr: astro_token_address.clone(),
         },
     };
+    if astro_pending.amount.is_zero() {
+        return Ok((vec![], Uint128::zero(), Uint128::zero()));
+    }
 
     // withdraw ASTRO rewards
     let withdraw_rewards_msg: CosmosMsg = WasmMsg::Execute {
@@ -782,11 +1375,11 @@ pub fn swap_rewards(deps: DepsMut, env: Env, msg_info: MessageInfo) -> VaultResu
     // first, get the address of the pool from Astroport
     // then, perform the swap, and finally perform passive strategy with the gained luna
     let astro_luna_pool_address: astroport::asset::PairInfo = deps.querier.query_wasm_smart(
-        state.astro_factory_address,
+        state.astro_factory_address.clone(),
         &astroport::factory::QueryMsg::Pair {
             asset_infos: [
                 astroport::asset::AssetInfo::Token {
-                    contract_addr: astro_token_address,
+                    contract_addr: astro_token_address.clone(),
                 },
                 astroport::asset::AssetInfo::NativeToken {
                     denom: LUNA_DENOM.to_string(),
@@ -800,36 +1393,26 @@ pub fn swap_rewards(deps: DepsMut, env: Env, msg_info: MessageInfo) -> VaultResu
         astro_luna_pool_address.contract_addr.clone(),
         &astro_pending,
     )?;
-    let swap_luna_return = swap_simulation_response.return_amount;
-
-    let swap_astro_message = WasmMsg::Execute {
-        contract_addr: astro_luna_pool_address.contract_addr.into_string(),
-        msg: to_binary(&astroport::pair::ExecuteMsg::Swap {
-            offer_asset: astro_pending.clone(),
-            belief_price: None,
-            max_spread: None,
-            to: None,
-        })?,
-        funds: vec![],
-    }
-    .into();
-
-    response = response.add_messages([withdraw_rewards_msg, swap_astro_message]);
 
-    // Deposit luna into passive strategy
-    response = deposit_passive_strategy(
-        &deps.as_ref(),
-        swap_luna_return,
-        state.bluna_address,
-        &state.astro_lp_address,
-        response,
-    )?;
-
-    Ok(response.add_attributes(vec![
-        attr("action", "swap_rewards"),
-        attr("astro_swapped", astro_pending.amount),
-        attr("luna_return", swap_luna_return),
-    ]))
+    // ASTRO is a cw20 token, so it's sent to the pair with a swap hook
+    let swap_astro_message = Swap::new(
+        Dex::Astroport,
+        astro_luna_pool_address.contract_addr,
+        Asset {
+            amount: astro_pending.amount,
+            info: AssetInfo::Token {
+                contract_addr: astro_token_address.into_string(),
+            },
+        },
+    )
+    .max_spread(max_spread)
+    .into_msg()?;
+
+    Ok((
+        vec![withdraw_rewards_msg, swap_astro_message],
+        astro_pending.amount,
+        swap_simulation_response.return_amount,
+    ))
 }
 
 pub(crate) fn handle_unbond_handler_msg(
@@ -864,10 +1447,9 @@ pub(crate) fn handle_unbond_handler_msg(
             unbond_handlers_available.push(unbond_handler.clone());
             UNBOND_HANDLERS_AVAILABLE.save(deps.storage, &unbond_handlers_available)?;
 
-            Ok(Response::new().add_attributes(vec![
-                attr("action", "after_unbond_handler_released"),
-                attr("unbond_handler", unbond_handler.to_string()),
-            ]))
+            Ok(WhaleEvent::new(LUNA_VAULT, "after_unbond_handler_released")
+                .add_attribute("unbond_handler", unbond_handler.to_string())
+                .into_response())
         }
     }
 }
@@ -884,14 +1466,183 @@ pub fn withdraw_unbonded_from_flashloan(
         return Err(LunaVaultError::NotWhitelisted {});
     }
 
-    let mut response = Response::new().add_attribute("action", "withdraw_unbonded_from_flashloan");
+    let response = WhaleEvent::new(LUNA_VAULT, "withdraw_unbonded_from_flashloan").into_response();
+    let (response, _, _) = withdraw_unbonded_from_hubs(deps.as_ref(), &env, &state, response)?;
+    Ok(response)
+}
 
-    // get the amount of withdrawable luna from anchor
+/// Claims the luna of the vault's matured unbond batches on Anchor and Prism and reports the
+/// resulting vLuna exchange rate. Can be called by anyone.
+pub fn process_batches(deps: DepsMut, env: Env) -> VaultResult<Response> {
+    let state = STATE.load(deps.storage)?;
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+
+    let response = WhaleEvent::new(LUNA_VAULT, "process_batches").into_response();
+    let (response, withdrawable_from_anchor, withdrawable_from_prism) =
+        withdraw_unbonded_from_hubs(deps.as_ref(), &env, &state, response)?;
+
+    // the claimed luna is accounted for as unbonding value until the withdrawals are executed,
+    // so the vault value doesn't change by processing the batches
+    let total_value = compute_total_value(&env, deps.as_ref(), &info)?;
+    let total_share = query_supply(&deps.querier, info.liquidity_token)?;
+    let exchange_rate = get_exchange_rate(total_value.total_value_in_luna, total_share);
+
+    let slashing_attrs = check_bluna_slashing(deps, &env, &state)?;
+
+    Ok(response.add_attributes(slashing_attrs).add_attributes(vec![
+        attr("anchor_withdrawable", withdrawable_from_anchor),
+        attr("prism_withdrawable", withdrawable_from_prism),
+        attr(
+            "bluna_value_burning_in_luna",
+            total_value.bluna_value_burning_in_luna,
+        ),
+        attr(
+            "cluna_value_burning_in_luna",
+            total_value.cluna_value_burning_in_luna,
+        ),
+        attr("total_value_in_luna", total_value.total_value_in_luna),
+        attr("total_share", total_share),
+        attr("exchange_rate", exchange_rate.to_string()),
+    ]))
+}
+
+/// Compares the bLuna exchange rate to the one seen last time. A drop is recorded as slashing event
+/// and halts deposits if the slashing guard is enabled.
+fn check_bluna_slashing(deps: DepsMut, env: &Env, state: &State) -> VaultResult<Vec<Attribute>> {
     let bluna_hub_address =
         query_contract_from_mem(deps.as_ref(), &state.memory_address, ANCHOR_BLUNA_HUB_ID)?;
+    let bluna_exchange_rate = anchor::query_bluna_exchange_rate(deps.as_ref(), bluna_hub_address)?;
+    let previous_exchange_rate = BLUNA_EXCHANGE_RATE.may_load(deps.storage)?;
+    BLUNA_EXCHANGE_RATE.save(deps.storage, &bluna_exchange_rate)?;
+
+    let mut attrs = vec![attr("bluna_exchange_rate", bluna_exchange_rate.to_string())];
+    let slashing_threshold = SLASHING_THRESHOLD
+        .may_load(deps.storage)?
+        .unwrap_or_else(Decimal::zero);
+    let previous_exchange_rate = match previous_exchange_rate {
+        Some(rate)
+            if rate > bluna_exchange_rate
+                && exceeds_threshold(rate, bluna_exchange_rate, slashing_threshold) =>
+        {
+            rate
+        }
+        _ => return Ok(attrs),
+    };
+
+    let id = SLASHING_EVENT_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    SLASHING_EVENTS.save(
+        deps.storage,
+        U64Key::new(id),
+        &SlashingEvent {
+            time: env.block.time.seconds(),
+            previous_exchange_rate,
+            exchange_rate: bluna_exchange_rate,
+        },
+    )?;
+    SLASHING_EVENT_COUNT.save(deps.storage, &(id + 1))?;
+    attrs.push(attr(
+        "slashing_detected",
+        previous_exchange_rate.to_string(),
+    ));
+
+    if SLASHING_GUARD.may_load(deps.storage)?.unwrap_or_default() {
+        DEPOSITS_HALTED.save(deps.storage, &true)?;
+        attrs.push(attr("deposits_halted", "true"));
+    }
+    Ok(attrs)
+}
+
+/// Returns true if the exchange rate dropped by at least `threshold` relative to the previous rate
+fn exceeds_threshold(previous_rate: Decimal, rate: Decimal, threshold: Decimal) -> bool {
+    // Scales the rates to integers so they can be multiplied by the threshold
+    let unit = Uint128::new(1_000_000_000_000_000_000u128);
+    unit * rate <= (unit * previous_rate) * (Decimal::one() - threshold)
+}
+
+/// Updates the unbond handler expiration time and the slashing threshold
+pub fn update_config(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    unbond_handler_expiration_time: Option<u64>,
+    slashing_threshold: Option<Decimal>,
+) -> VaultResult<Response> {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let mut event = WhaleEvent::new(LUNA_VAULT, "update_config");
+    if let Some(expiration_time) = unbond_handler_expiration_time {
+        if !(MIN_UNBOND_EXPIRATION_TIME..=MAX_UNBOND_EXPIRATION_TIME).contains(&expiration_time) {
+            return Err(LunaVaultError::InvalidUnbondHandlerExpirationTime {
+                min: MIN_UNBOND_EXPIRATION_TIME,
+                max: MAX_UNBOND_EXPIRATION_TIME,
+            });
+        }
+        UNBOND_HANDLER_EXPIRATION_TIME.save(deps.storage, &expiration_time)?;
+        event = event.add_attribute(
+            "unbond_handler_expiration_time",
+            expiration_time.to_string(),
+        );
+    }
+    if let Some(threshold) = slashing_threshold {
+        if threshold > MAX_SLASHING_THRESHOLD {
+            return Err(LunaVaultError::InvalidSlashingThreshold {
+                max: MAX_SLASHING_THRESHOLD,
+            });
+        }
+        SLASHING_THRESHOLD.save(deps.storage, &threshold)?;
+        event = event.add_attribute("slashing_threshold", threshold.to_string());
+    }
+    Ok(event.into_response())
+}
+
+/// Enables or disables halting deposits on detected slashing
+pub fn set_slashing_guard(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    enabled: bool,
+) -> VaultResult<Response> {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    SLASHING_GUARD.save(deps.storage, &enabled)?;
+    Ok(WhaleEvent::new(LUNA_VAULT, "set_slashing_guard")
+        .add_attribute("enabled", enabled.to_string())
+        .into_response())
+}
+
+/// Resumes deposits after a slashing event
+pub fn acknowledge_slashing(deps: DepsMut, msg_info: MessageInfo) -> VaultResult<Response> {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    DEPOSITS_HALTED.remove(deps.storage);
+    Ok(WhaleEvent::new(LUNA_VAULT, "acknowledge_slashing").into_response())
+}
+
+/// Fails if deposits are halted after a slashing event
+fn assert_deposits_allowed(storage: &dyn Storage) -> VaultResult<()> {
+    if DEPOSITS_HALTED.may_load(storage)?.unwrap_or_default() {
+        return Err(LunaVaultError::DepositsHalted {});
+    }
+    Ok(())
+}
+
+/// Adds the messages withdrawing the vault's unbonded luna from Anchor and Prism to the response.
+/// Returns the response and the amounts withdrawn from each hub.
+fn withdraw_unbonded_from_hubs(
+    deps: Deps,
+    env: &Env,
+    state: &State,
+    mut response: Response,
+) -> VaultResult<(Response, Uint128, Uint128)> {
+    // get the amount of withdrawable luna from anchor
+    let bluna_hub_address =
+        query_contract_from_mem(deps, &state.memory_address, ANCHOR_BLUNA_HUB_ID)?;
 
     let withdrawable_from_anchor = anchor::query_withdrawable_unbonded(
-        deps.as_ref(),
+        deps,
         bluna_hub_address.clone(),
         env.contract.address.clone(),
     )?
@@ -904,12 +1655,12 @@ pub fn withdraw_unbonded_from_flashloan(
 
     // get the amount of withdrawable luna from prism
     let cluna_hub_address =
-        query_contract_from_mem(deps.as_ref(), &state.memory_address, PRISM_CLUNA_HUB_ID)?;
+        query_contract_from_mem(deps, &state.memory_address, PRISM_CLUNA_HUB_ID)?;
 
     let withdrawable_from_prism = prism::query_withdrawable_unbonded(
-        deps.as_ref(),
+        deps,
         cluna_hub_address.clone(),
-        env.contract.address,
+        env.contract.address.clone(),
     )?
     .withdrawable;
     if !withdrawable_from_prism.is_zero() {
@@ -918,5 +1669,5 @@ pub fn withdraw_unbonded_from_flashloan(
         response = response.add_message(withdraw_unbonded_msg);
     }
 
-    Ok(response)
+    Ok((response, withdrawable_from_anchor, withdrawable_from_prism))
 }
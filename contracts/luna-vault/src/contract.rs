@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    entry_point, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError,
-    SubMsg, Uint128, WasmMsg,
+    entry_point, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response,
+    StdError, SubMsg, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw20::MinterResponse;
@@ -10,19 +10,24 @@ use serde::Serialize;
 use terraswap::asset::AssetInfo;
 use terraswap::token::InstantiateMsg as TokenInstantiateMsg;
 
+use white_whale::address::validate_addr;
+use white_whale::admin::{accept_admin, propose_admin};
 use white_whale::denom::LUNA_DENOM;
 use white_whale::deposit_info::DepositInfo;
 use white_whale::fee::{Fee, VaultFee};
 use white_whale::luna_vault::msg::VaultQueryMsg as QueryMsg;
 use white_whale::luna_vault::msg::*;
+use white_whale::pause::{is_paused, query_paused_scopes, set_paused, set_paused_scopes};
 
 use crate::commands::set_fee;
 use crate::error::LunaVaultError;
 use crate::helpers::{get_lp_token_address, unwrap_data, unwrap_reply};
 use crate::pool_info::PoolInfoRaw;
 use crate::response::MsgInstantiateContractResponse;
-use crate::state::{ProfitCheck, State, ADMIN, DEPOSIT_INFO, FEE, POOL_INFO, PROFIT, STATE};
-use crate::{commands, flashloan, helpers, queries, replies};
+use crate::state::{
+    ProfitCheck, State, ADMIN, DEPOSIT_INFO, FEE, LUNA_CAP, POOL_INFO, PROFIT, STATE,
+};
+use crate::{commands, flashloan, queries, replies};
 
 const INSTANTIATE_REPLY_ID: u64 = 1u64;
 pub(crate) const INSTANTIATE_UNBOND_HANDLER_REPLY_ID: u64 = 2u64;
@@ -35,6 +40,9 @@ pub(crate) type VaultResult<T> = Result<T, LunaVaultError>;
 const CONTRACT_NAME: &str = "crates.io:ww-luna-vault";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Maximum of the flash loan, treasury and commission fee combined, in percent
+pub const MAX_TOTAL_FEE: u64 = 50;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -45,15 +53,19 @@ pub fn instantiate(
     // Use CW2 to set the contract version, this is needed for migrations
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    let astro_lp_address = deps.api.addr_validate(&msg.astro_lp_address)?;
-    let astro_factory_address = deps.api.addr_validate(&msg.astro_factory_address)?;
+    let astro_lp_address = validate_addr(deps.api, "astro_lp_address", &msg.astro_lp_address)?;
+    let astro_factory_address = validate_addr(
+        deps.api,
+        "astro_factory_address",
+        &msg.astro_factory_address,
+    )?;
 
     let state = State {
-        bluna_address: deps.api.addr_validate(&msg.bluna_address)?,
-        cluna_address: deps.api.addr_validate(&msg.cluna_address)?,
+        bluna_address: validate_addr(deps.api, "bluna_address", &msg.bluna_address)?,
+        cluna_address: validate_addr(deps.api, "cluna_address", &msg.cluna_address)?,
         astro_lp_address: astro_lp_address.clone(),
         astro_factory_address,
-        memory_address: deps.api.addr_validate(&msg.memory_addr)?,
+        memory_address: validate_addr(deps.api, "memory_addr", &msg.memory_addr)?,
         whitelisted_contracts: vec![],
         allow_non_whitelisted: false,
         unbond_handler_code_id: msg.unbond_handler_code_id,
@@ -61,6 +73,9 @@ pub fn instantiate(
 
     // Store the initial config
     STATE.save(deps.storage, &state)?;
+    if let Some(cap) = msg.luna_cap {
+        LUNA_CAP.save(deps.storage, &cap)?;
+    }
 
     // Check if the provided asset is the luna token
     let _underlying_coin_denom = match msg.asset_info.clone() {
@@ -73,25 +88,21 @@ pub fn instantiate(
         }
     };
 
-    DEPOSIT_INFO.save(
-        deps.storage,
-        &DepositInfo {
-            asset_info: msg.asset_info.clone(),
-        },
-    )?;
+    DEPOSIT_INFO.save(deps.storage, &DepositInfo::new(msg.asset_info.clone()))?;
     // Setup the fees system with a fee and other contract addresses
     let fee_config = VaultFee {
-        flash_loan_fee: helpers::check_fee(Fee {
+        flash_loan_fee: Fee {
             share: msg.flash_loan_fee,
-        })?,
-        treasury_fee: helpers::check_fee(Fee {
+        },
+        treasury_fee: Fee {
             share: msg.treasury_fee,
-        })?,
-        commission_fee: helpers::check_fee(Fee {
+        },
+        commission_fee: Fee {
             share: msg.commission_fee,
-        })?,
-        treasury_addr: deps.api.addr_validate(&msg.treasury_addr)?,
+        },
+        treasury_addr: validate_addr(deps.api, "treasury_addr", &msg.treasury_addr)?,
     };
+    fee_config.validate(Decimal::percent(MAX_TOTAL_FEE))?;
 
     FEE.save(deps.storage, &fee_config)?;
 
@@ -178,6 +189,10 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> VaultResult<Response> {
+    if !matches!(msg, ExecuteMsg::Unpause {}) && is_paused(deps.storage)? {
+        return Err(LunaVaultError::Paused {});
+    }
+
     match msg {
         ExecuteMsg::Receive(msg) => commands::receive_cw20(deps, env, info, msg),
         ExecuteMsg::ProvideLiquidity { asset } => {
@@ -187,7 +202,50 @@ pub fn execute(
         ExecuteMsg::WithdrawUnbondedFlashloan {} => {
             commands::withdraw_unbonded_from_flashloan(deps, info, env)
         }
-        ExecuteMsg::SetAdmin { admin } => commands::set_admin(deps, info, admin),
+        ExecuteMsg::ProcessBatches {} => commands::process_batches(deps, env),
+        ExecuteMsg::UpdateLunaCap { cap } => commands::update_luna_cap(deps, info, cap),
+        ExecuteMsg::UpdateConfig {
+            unbond_handler_expiration_time,
+            slashing_threshold,
+        } => commands::update_config(
+            deps,
+            info,
+            unbond_handler_expiration_time,
+            slashing_threshold,
+        ),
+        ExecuteMsg::SetSlashingGuard { enabled } => {
+            commands::set_slashing_guard(deps, info, enabled)
+        }
+        ExecuteMsg::AcknowledgeSlashing {} => commands::acknowledge_slashing(deps, info),
+        ExecuteMsg::SetLpToken { address } => commands::set_lp_token(deps, info, address),
+        ExecuteMsg::DepositIntoLp { amount } => commands::deposit_into_lp(deps, info, amount),
+        ExecuteMsg::WithdrawFromLp { share } => commands::withdraw_from_lp(deps, info, share),
+        ExecuteMsg::AddStakingBackend {
+            name,
+            backend,
+            token_address,
+            hub_address,
+        } => commands::add_staking_backend(deps, info, name, backend, token_address, hub_address),
+        ExecuteMsg::RemoveStakingBackend { name } => {
+            commands::remove_staking_backend(deps, info, name)
+        }
+        ExecuteMsg::SetStakingBackendWeights { weights } => {
+            commands::set_staking_backend_weights(deps, info, weights)
+        }
+        ExecuteMsg::BondLuna { amount } => commands::bond_luna(deps, info, amount),
+        ExecuteMsg::SetAdmin { admin } => commands::set_admin(deps, env, info, admin),
+        ExecuteMsg::ProposeAdmin { admin, expires_in } => {
+            Ok(propose_admin(deps, &env, &info, &ADMIN, admin, expires_in)?)
+        }
+        ExecuteMsg::AcceptAdmin {} => Ok(accept_admin(deps, &env, &info, &ADMIN)?),
+        ExecuteMsg::Pause {} => Ok(set_paused(deps, &info, &ADMIN, true)?),
+        ExecuteMsg::Unpause {} => Ok(set_paused(deps, &info, &ADMIN, false)?),
+        ExecuteMsg::PauseScopes { scopes } => {
+            Ok(set_paused_scopes(deps, &info, &ADMIN, scopes, true)?)
+        }
+        ExecuteMsg::UnpauseScopes { scopes } => {
+            Ok(set_paused_scopes(deps, &info, &ADMIN, scopes, false)?)
+        }
         ExecuteMsg::SetFee {
             flash_loan_fee,
             treasury_fee,
@@ -201,6 +259,8 @@ pub fn execute(
         }
         ExecuteMsg::FlashLoan { payload } => flashloan::handle_flashloan(deps, env, info, payload),
         ExecuteMsg::SwapRewards {} => commands::swap_rewards(deps, env, info),
+        ExecuteMsg::ClaimAndCompound {} => commands::claim_and_compound(deps, env),
+        ExecuteMsg::CollectFees {} => commands::collect_fees(deps),
         ExecuteMsg::UpdateState {
             bluna_address,
             cluna_address,
@@ -229,16 +289,15 @@ pub fn execute(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> VaultResult<Response> {
     let res = unwrap_reply(msg.clone())?;
-    let data = unwrap_data(res.clone())?;
-
-    let response: MsgInstantiateContractResponse = Message::parse_from_bytes(data.as_slice())
-        .map_err(|_| {
-            StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
-        })?;
 
     match msg.id {
-        INSTANTIATE_REPLY_ID => replies::after_token_instantiation(deps, response),
+        INSTANTIATE_REPLY_ID => replies::after_token_instantiation(deps, res),
         INSTANTIATE_UNBOND_HANDLER_REPLY_ID => {
+            let data = unwrap_data(res.clone())?;
+            let response: MsgInstantiateContractResponse =
+                Message::parse_from_bytes(data.as_slice()).map_err(|_| {
+                    StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
+                })?;
             let events = res.events;
             replies::after_unbond_handler_instantiation(deps, response, events)
         }
@@ -253,6 +312,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> VaultResult<Binary> {
         QueryMsg::PoolState {} => to_binary(&queries::try_query_pool_state(env, deps)?),
         QueryMsg::State {} => to_binary(&queries::query_state(deps)?),
         QueryMsg::Fees {} => to_binary(&queries::query_fees(deps)?),
+        QueryMsg::PendingFees {} => to_binary(&queries::query_pending_fees(deps)?),
+        QueryMsg::Whitelist { start_after, limit } => {
+            to_binary(&queries::query_whitelist(deps, start_after, limit)?)
+        }
         QueryMsg::VaultValue {} => to_binary(&queries::query_total_value(env, deps)?),
         QueryMsg::EstimateWithdrawFee { amount } => {
             to_binary(&queries::estimate_withdraw_fee(deps, amount)?)
@@ -262,12 +325,32 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> VaultResult<Binary> {
         QueryMsg::WithdrawableUnbonded { address } => {
             to_binary(&queries::query_withdrawable_unbonded(deps, address)?)
         }
-        QueryMsg::UnbondRequests { address } => {
-            to_binary(&queries::query_unbond_requests(deps, address)?)
+        QueryMsg::UnbondRequests {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&queries::query_unbond_requests(
+            deps,
+            address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AllUnbondHistory { start_after, limit } => to_binary(
+            &queries::query_all_unbond_history(deps, start_after, limit)?,
+        ),
+        QueryMsg::ExchangeRate {} => to_binary(&queries::query_exchange_rate(env, deps)?),
+        QueryMsg::EstimateWithdraw { amount } => {
+            to_binary(&queries::estimate_withdraw(env, deps, amount)?)
         }
         QueryMsg::UnbondHandlerExpirationTime {} => to_binary(
             &queries::query_unbond_handler_expiration_time(deps.storage)?,
         ),
+        QueryMsg::SlashingEvents { start_after, limit } => {
+            to_binary(&queries::query_slashing_events(deps, start_after, limit)?)
+        }
+        QueryMsg::VaultUtilization {} => to_binary(&queries::query_vault_utilization(env, deps)?),
+        QueryMsg::StakingBackends {} => to_binary(&queries::query_staking_backends(deps)?),
+        QueryMsg::PausedScopes {} => to_binary(&query_paused_scopes(deps.storage)?),
     }
 }
 
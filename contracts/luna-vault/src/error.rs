@@ -1,17 +1,29 @@
 use std::num::ParseIntError;
 use thiserror::Error;
 
-use cosmwasm_std::{DivideByZeroError, OverflowError, StdError};
+use cosmwasm_std::{Decimal, DivideByZeroError, OverflowError, StdError, Uint128};
 use cw_controllers::AdminError;
+use white_whale::admin::AdminTransferError;
+use white_whale::error::CommonError;
+use white_whale::fee::FeeError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum LunaVaultError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
+    #[error("{0}")]
+    AdminTransfer(AdminTransferError),
+
+    #[error("{0}")]
+    Fee(FeeError),
+
     #[error("{0}")]
     OverflowError(#[from] OverflowError),
 
@@ -86,6 +98,53 @@ pub enum LunaVaultError {
 
     #[error("Last balance is non-zero, you can only call this function once.")]
     Nonzero {},
+
+    #[error("The contract is paused")]
+    Paused {},
+
+    #[error("Can't deposit a zero amount.")]
+    InvalidZeroAmount {},
+
+    #[error("Deposits are halted until the admin acknowledges the detected slashing.")]
+    DepositsHalted {},
+
+    #[error("The liquidity token has already been set.")]
+    LpTokenAlreadySet {},
+
+    #[error("There are no pending rewards to compound.")]
+    NoPendingRewards {},
+
+    #[error("There are no accrued fees to collect.")]
+    NoPendingFees {},
+
+    #[error("The deposit would bring the vault value over its cap of {cap}")]
+    VaultCapExceeded { cap: Uint128 },
+
+    #[error("A staking backend named {name} already exists.")]
+    StakingBackendAlreadyExists { name: String },
+
+    #[error("There's no staking backend named {name}.")]
+    StakingBackendNotFound { name: String },
+
+    #[error("The staking backend list has reached its limit, can't store more backends.")]
+    StakingBackendLimitReached {},
+
+    #[error(
+        "The staking backend can't be removed while it has a weight or the vault holds its token."
+    )]
+    StakingBackendInUse {},
+
+    #[error("The staking backend weights must be unique and add up to one.")]
+    InvalidStakingBackendWeights {},
+
+    #[error("No staking backend has a weight to bond luna with.")]
+    NoStakingBackendWeights {},
+
+    #[error("The unbond handler expiration time must be between {min} and {max} seconds.")]
+    InvalidUnbondHandlerExpirationTime { min: u64, max: u64 },
+
+    #[error("The slashing threshold can't be higher than {max}.")]
+    InvalidSlashingThreshold { max: Decimal },
 }
 
 impl From<semver::Error> for LunaVaultError {
@@ -103,3 +162,22 @@ impl LunaVaultError {
         })
     }
 }
+
+impl From<AdminTransferError> for LunaVaultError {
+    fn from(err: AdminTransferError) -> Self {
+        match err {
+            AdminTransferError::Std(err) => Self::Std(err),
+            AdminTransferError::Admin(err) => Self::Admin(err),
+            err => Self::AdminTransfer(err),
+        }
+    }
+}
+
+impl From<FeeError> for LunaVaultError {
+    fn from(err: FeeError) -> Self {
+        match err {
+            FeeError::InvalidFee {} => Self::InvalidFee {},
+            err => Self::Fee(err),
+        }
+    }
+}
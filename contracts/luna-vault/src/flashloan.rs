@@ -1,20 +1,22 @@
 use core::result::Result::Err;
 
 use cosmwasm_std::{CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg};
-use terraswap::asset::{Asset, AssetInfo};
+use terraswap::asset::AssetInfo;
 
 use white_whale::anchor::anchor_bluna_unbond_msg;
 use white_whale::denom::LUNA_DENOM;
+use white_whale::events::{WhaleEvent, LUNA_VAULT};
 use white_whale::luna_vault::msg::{CallbackMsg, FlashLoanPayload};
 use white_whale::memory::queries::query_contract_from_mem;
 use white_whale::memory::{ANCHOR_BLUNA_HUB_ID, PRISM_CLUNA_HUB_ID};
+use white_whale::pause::{assert_not_paused, PauseScope};
 use white_whale::prism::prism_cluna_unbond_msg;
 use white_whale::tax::into_msg_without_tax;
 
 use crate::commands::{deposit_passive_strategy, withdraw_passive_strategy};
 use crate::contract::VaultResult;
 use crate::error::LunaVaultError;
-use crate::helpers::{compute_total_value, get_lp_token_address};
+use crate::helpers::{accrue_fees, compute_total_value, get_lp_token_address};
 use crate::pool_info::PoolInfoRaw;
 use crate::state::{FEE, POOL_INFO, PROFIT, STATE};
 
@@ -26,6 +28,7 @@ pub fn handle_flashloan(
     info: MessageInfo,
     payload: FlashLoanPayload,
 ) -> VaultResult<Response> {
+    assert_not_paused(deps.storage, PauseScope::Trading)?;
     let state = STATE.load(deps.storage)?;
     let fees = FEE.load(deps.storage)?;
     let whitelisted_contracts = state.whitelisted_contracts;
@@ -43,9 +46,6 @@ pub fn handle_flashloan(
         whitelisted = true;
     }
 
-    // Do we have enough funds?
-    let pool_info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
-    let total_value = compute_total_value(&env, deps.as_ref(), &pool_info)?.total_value_in_luna;
     let requested_asset = payload.requested_asset;
 
     // check if the request_asset is uluna
@@ -58,6 +58,11 @@ pub fn handle_flashloan(
         }
     };
 
+    // Do we have enough funds?
+    let pool_info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+    let vault_value = compute_total_value(&env, deps.as_ref(), &pool_info)?;
+    let total_value = vault_value.total_value_in_luna;
+
     // Max tax buffer will be 2 transfers of the borrowed assets
     // Passive Strategy -> Vault -> Caller
     let tax_buffer = Uint128::from(2u32) * requested_asset.compute_tax(&deps.querier)?
@@ -67,20 +72,22 @@ pub fn handle_flashloan(
         return Err(LunaVaultError::Broke {});
     }
     // Init response
-    let mut response = Response::new().add_attribute("Action", "Flashloan");
+    let mut response = WhaleEvent::new(LUNA_VAULT, "flash_loan").into_response();
 
-    // withdraw from passive strategy, initially defined as the bLuna-Luna LP. This returns the requested
+    // withdraw from passive strategy, initially defined as the bLuna-Luna LP. This returns the missing
     // amount of luna and deposits the remaining bluna shares taken from the LP directly into the single-sided Astroport LP
-    withdraw_passive_strategy(
-        &deps.as_ref(),
-        requested_asset.amount,
-        AssetInfo::NativeToken {
-            denom: LUNA_DENOM.to_string(),
-        },
-        &get_lp_token_address(&deps.as_ref(), state.astro_lp_address.clone())?,
-        &state.astro_lp_address,
-        response.clone(),
-    )?;
+    if vault_value.luna_amount < requested_asset.amount {
+        response = withdraw_passive_strategy(
+            &deps.as_ref(),
+            requested_asset.amount - vault_value.luna_amount,
+            AssetInfo::NativeToken {
+                denom: LUNA_DENOM.to_string(),
+            },
+            &get_lp_token_address(&deps.as_ref(), state.astro_lp_address.clone())?,
+            &state.astro_lp_address,
+            response,
+        )?;
+    }
 
     // If caller not whitelisted, calculate flashloan fee
     let loan_fee: Uint128 = if whitelisted {
@@ -88,9 +95,8 @@ pub fn handle_flashloan(
     } else {
         fees.flash_loan_fee.compute(requested_asset.amount)
     };
+    response = response.add_attribute("loan_fee", loan_fee.to_string());
 
-    // NOTE: Forget the whitelist and just charge everyone, why should anyone get free flashloans ?
-    fees.flash_loan_fee.compute(requested_asset.amount);
     // Construct transfer of funds msg, tax is accounted for by buffer
     let loan_msg = into_msg_without_tax(requested_asset, info.sender.clone())?;
     response = response.add_message(loan_msg);
@@ -139,7 +145,7 @@ pub fn before_trade(deps: DepsMut, env: Env) -> Result<Vec<(&str, String)>, Luna
 pub fn after_trade(
     deps: DepsMut,
     env: Env,
-    msg_info: MessageInfo,
+    _msg_info: MessageInfo,
     loan_fee: Uint128,
 ) -> VaultResult<Response> {
     let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
@@ -159,14 +165,20 @@ pub fn after_trade(
     PROFIT.save(deps.storage, &conf)?;
 
     let state = STATE.load(deps.storage)?;
-    let mut response = Response::default();
+    let mut response = WhaleEvent::new(LUNA_VAULT, "after_trade").into_response();
+
+    // Commission of the profit for the treasury, it stays in the vault until it's collected
+    let fees = FEE.load(deps.storage)?;
+    let commission_amount = fees.commission_fee.compute(profit);
+    accrue_fees(deps.storage, Uint128::zero(), commission_amount)?;
+    let luna_amount = total_value.luna_amount.saturating_sub(commission_amount);
 
     // check in which asset the flashloan was paid back
-    if total_value.luna_amount > Uint128::zero() {
+    if luna_amount > Uint128::zero() {
         // flashloan was paid back in luna, deposit back to passive strategy
         response = deposit_passive_strategy(
             &deps.as_ref(),
-            total_value.luna_amount,
+            luna_amount,
             state.bluna_address.clone(),
             &state.astro_lp_address,
             response.clone(),
@@ -208,36 +220,15 @@ pub fn after_trade(
         response = response.add_message(cluna_unbond_msg);
     }
 
-    let commission_response = send_commissions(deps.as_ref(), msg_info, profit)?;
     Ok(response
-        // Send commission of profit to Treasury
-        .add_submessages(commission_response.messages)
-        .add_attributes(commission_response.attributes)
+        .add_attribute("profit", profit.to_string())
+        .add_attribute("commission_amount", commission_amount.to_string())
         .add_attribute(
             "total_value_in_luna",
             total_value.total_value_in_luna.to_string(),
         ))
 }
 
-fn send_commissions(deps: Deps, _info: MessageInfo, profit: Uint128) -> VaultResult<Response> {
-    let fees = FEE.load(deps.storage)?;
-
-    let commission_amount = fees.commission_fee.compute(profit);
-
-    // Construct commission msg
-    let refund_asset = Asset {
-        info: AssetInfo::NativeToken {
-            denom: LUNA_DENOM.to_string(),
-        },
-        amount: commission_amount,
-    };
-    let commission_msg = refund_asset.into_msg(&deps.querier, fees.treasury_addr)?;
-
-    Ok(Response::new()
-        .add_attribute("commission_amount", commission_amount.to_string())
-        .add_message(commission_msg))
-}
-
 /// Helper method which encapsulates the requested funds.
 /// This function prevents callers from doing unprofitable actions
 /// with the vault funds and makes sure the funds are returned by
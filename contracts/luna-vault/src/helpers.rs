@@ -2,15 +2,15 @@ use std::fmt;
 
 use astroport::asset::Asset;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, Env, Event, Reply, StdError,
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, Env, Event, Order, Reply, StdError,
     StdResult, Storage, SubMsgExecutionResponse, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use terraswap::querier::query_token_balance;
 
 use white_whale::denom::LUNA_DENOM;
-use white_whale::fee::Fee;
 use white_whale::luna_vault::luna_unbond_handler::msg::Cw20HookMsg::Unbond as UnbondHandlerUnbondMsg;
 use white_whale::luna_vault::luna_unbond_handler::msg::ExecuteMsg;
 use white_whale::luna_vault::luna_unbond_handler::msg::ExecuteMsg::WithdrawUnbonded as UnbondHandlerWithdrawMsg;
@@ -23,7 +23,9 @@ use white_whale::tax::compute_tax;
 use crate::contract::VaultResult;
 use crate::error::LunaVaultError;
 use crate::pool_info::PoolInfoRaw;
-use crate::state::{FEE, STATE};
+use crate::state::{
+    PendingFees, StakingBackend, State, FEE, PENDING_FEES, STAKING_BACKENDS, STATE,
+};
 
 /// Represents the total value in the vault
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -35,15 +37,17 @@ pub struct TotalValue {
     pub cluna_value_in_luna: Uint128,
     pub bluna_value_burning_in_luna: Uint128,
     pub cluna_value_burning_in_luna: Uint128,
+    pub staking_backends_value_in_luna: Uint128,
 }
 
 /// compute total vault value of deposits in LUNA and return a tuple with those values.
 /// (total, luna, astro lp, bluna, cluna)
 pub fn compute_total_value(_env: &Env, deps: Deps, info: &PoolInfoRaw) -> VaultResult<TotalValue> {
     let state = STATE.load(deps.storage)?;
-    // get liquid Luna in the vault
+    // get liquid Luna in the vault, the accrued commission belongs to the treasury
     let luna_info = info.asset_infos[0].to_normal(deps.api)?;
-    let luna_amount = query_asset_balance(deps, &luna_info, info.contract_addr.clone())?;
+    let luna_amount = query_asset_balance(deps, &luna_info, info.contract_addr.clone())?
+        .saturating_sub(load_pending_fees(deps.storage)?.commission);
 
     // get Luna from the passive strategy
     // first, get the amount of LP tokens that we have
@@ -61,12 +65,10 @@ pub fn compute_total_value(_env: &Env, deps: Deps, info: &PoolInfoRaw) -> VaultR
         .iter()
         .fold(Uint128::zero(), |accum, asset| accum + asset.amount);
 
+    // get liquid bLuna in the vault, the bLuna in the LP is already part of the LP value
     // NOTICE: we are assuming that bLuna is equivalent to 1 Luna
-    let bluna_value_in_luna = astro_lp_assets
-        .iter()
-        .find(|asset| asset.info == astroport::asset::token_asset_info(state.bluna_address.clone()))
-        .ok_or_else(|| LunaVaultError::generic_err("Failed to get bLuna asset from astro LP"))?
-        .amount;
+    let bluna_info = info.asset_infos[2].to_normal(deps.api)?;
+    let bluna_value_in_luna = query_asset_balance(deps, &bluna_info, info.contract_addr.clone())?;
 
     // NOTICE: we are assuming that cLuna is equivalent to 1 Luna
     let cluna_info = info.asset_infos[3].to_normal(deps.api)?;
@@ -94,12 +96,16 @@ pub fn compute_total_value(_env: &Env, deps: Deps, info: &PoolInfoRaw) -> VaultR
                 acc + unbond_request.1 // pending unbond amount
             });
 
+    let staking_backends_value_in_luna =
+        compute_staking_backends_value(deps, &state, info.contract_addr.clone())?;
+
     let total_deposits_in_luna = luna_amount
         + astroport_lp_value_in_luna
         + bluna_value_in_luna
         + cluna_value_in_luna
         + bluna_value_burning_in_luna
-        + cluna_value_burning_in_luna;
+        + cluna_value_burning_in_luna
+        + staking_backends_value_in_luna;
     Ok(TotalValue {
         total_value_in_luna: total_deposits_in_luna,
         luna_amount,
@@ -108,9 +114,52 @@ pub fn compute_total_value(_env: &Env, deps: Deps, info: &PoolInfoRaw) -> VaultR
         cluna_value_in_luna,
         bluna_value_burning_in_luna,
         cluna_value_burning_in_luna,
+        staking_backends_value_in_luna,
     })
 }
 
+/// Returns the staking backends with their names, ordered by name
+pub fn load_staking_backends(storage: &dyn Storage) -> StdResult<Vec<(String, StakingBackend)>> {
+    STAKING_BACKENDS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (name, backend) = item?;
+            Ok((String::from_utf8(name)?, backend))
+        })
+        .collect()
+}
+
+/// Luna value of the derivative tokens of the staking backends held by the vault.
+/// The bLuna and cLuna of the vault state are already part of the total value and are skipped.
+fn compute_staking_backends_value(deps: Deps, state: &State, vault: Addr) -> StdResult<Uint128> {
+    let mut value = Uint128::zero();
+    for (_, backend) in load_staking_backends(deps.storage)? {
+        if backend.token_address == state.bluna_address
+            || backend.token_address == state.cluna_address
+        {
+            continue;
+        }
+        let balance = query_token_balance(&deps.querier, backend.token_address, vault.clone())?;
+        if balance.is_zero() {
+            continue;
+        }
+        let exchange_rate = backend
+            .backend
+            .query_exchange_rate(deps, backend.hub_address)?;
+        value += balance * exchange_rate;
+    }
+    Ok(value)
+}
+
+/// Luna value of one vLuna, 1 if no vLuna has been minted yet
+pub fn get_exchange_rate(total_value_in_luna: Uint128, total_share: Uint128) -> Decimal {
+    if total_share.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(total_value_in_luna, total_share)
+    }
+}
+
 pub fn get_withdraw_fee(deps: Deps, amount: Uint128) -> VaultResult<Uint128> {
     let treasury_fee = get_treasury_fee(deps, amount)?;
     //TODO fee from Passive Strategy, i.e. Astroport LP?
@@ -126,20 +175,30 @@ pub fn get_withdraw_fee(deps: Deps, amount: Uint128) -> VaultResult<Uint128> {
     Ok(treasury_fee + astroport_lp_fee + luna_transfer_fee)
 }
 
+/// Returns the fees accrued since the last CollectFees
+pub fn load_pending_fees(storage: &dyn Storage) -> StdResult<PendingFees> {
+    Ok(PENDING_FEES.may_load(storage)?.unwrap_or_default())
+}
+
+/// Adds to the fees owed to the treasury
+pub fn accrue_fees(
+    storage: &mut dyn Storage,
+    treasury_fee: Uint128,
+    commission: Uint128,
+) -> StdResult<PendingFees> {
+    let mut pending = load_pending_fees(storage)?;
+    pending.treasury_fee += treasury_fee;
+    pending.commission += commission;
+    PENDING_FEES.save(storage, &pending)?;
+    Ok(pending)
+}
+
 pub fn get_treasury_fee(deps: Deps, amount: Uint128) -> VaultResult<Uint128> {
     let fee_config = FEE.load(deps.storage)?;
     let fee = fee_config.treasury_fee.compute(amount);
     Ok(fee)
 }
 
-/// Checks that the given [Fee] is valid, i.e. it's lower than 100%
-pub fn check_fee(fee: Fee) -> VaultResult<Fee> {
-    if fee.share >= Decimal::percent(100) {
-        return Err(LunaVaultError::InvalidFee {});
-    }
-    Ok(fee)
-}
-
 pub fn get_lp_token_address(deps: &Deps, pool_address: Addr) -> VaultResult<Addr> {
     println!("Making query");
 
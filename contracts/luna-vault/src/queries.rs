@@ -1,24 +1,39 @@
-use cosmwasm_std::{Coin, Deps, Env, Storage, Uint128};
+use std::convert::TryInto;
+
+use cosmwasm_std::{Coin, Decimal, Deps, Env, Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, U64Key};
 use terraswap::asset::Asset;
 use terraswap::querier::query_supply;
 
 use white_whale::luna_vault::msg::{
-    EstimateWithdrawFeeResponse, FeeResponse, LastBalanceResponse, LastProfitResponse,
-    PoolResponse, ValueResponse,
+    EstimateWithdrawFeeResponse, EstimateWithdrawResponse, ExchangeRateResponse, FeeResponse,
+    LastBalanceResponse, LastProfitResponse, PendingFeesResponse, PoolResponse,
+    SlashingEventsResponse, StakingBackendResponse, StakingBackendsResponse, ValueResponse,
+    VaultUtilizationResponse, WhitelistResponse,
 };
 use white_whale::memory::queries::query_contract_from_mem;
 use white_whale::memory::ANCHOR_BLUNA_HUB_ID;
-use white_whale::query::anchor::{UnbondRequestsResponse, WithdrawableUnbondedResponse};
+use white_whale::query::anchor::{
+    AllHistoryResponse, UnbondRequestsResponse, WithdrawableUnbondedResponse,
+};
 
 use crate::contract::VaultResult;
 use crate::error::LunaVaultError;
-use crate::helpers::{compute_total_value, get_withdraw_fee};
+use crate::helpers::{
+    compute_total_value, get_exchange_rate, get_treasury_fee, get_withdraw_fee, load_pending_fees,
+    load_staking_backends,
+};
 use crate::pool_info::{PoolInfo, PoolInfoRaw};
 use crate::state::{
-    State, DEFAULT_UNBOND_EXPIRATION_TIME, DEPOSIT_INFO, FEE, POOL_INFO, PROFIT, STATE,
-    UNBOND_HANDLERS_ASSIGNED, UNBOND_HANDLER_EXPIRATION_TIME,
+    State, DEFAULT_UNBOND_EXPIRATION_TIME, DEPOSITS_HALTED, DEPOSIT_INFO, FEE, LUNA_CAP, POOL_INFO,
+    PROFIT, SLASHING_EVENTS, SLASHING_GUARD, SLASHING_THRESHOLD, STATE, UNBOND_HANDLERS_ASSIGNED,
+    UNBOND_HANDLER_EXPIRATION_TIME,
 };
 
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
 /// Queries the PoolInfo configuration
 pub fn query_pool_info(deps: Deps) -> VaultResult<PoolInfo> {
     let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
@@ -46,6 +61,45 @@ pub fn query_state(deps: Deps) -> VaultResult<State> {
     Ok(STATE.load(deps.storage)?)
 }
 
+/// Queries the whitelisted contracts in the order they were added
+pub fn query_whitelist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> VaultResult<WhitelistResponse> {
+    let whitelisted_contracts = STATE.load(deps.storage)?.whitelisted_contracts;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let start = match start_after {
+        Some(start_after) => {
+            let start_after = deps.api.addr_validate(&start_after)?;
+            whitelisted_contracts
+                .iter()
+                .position(|addr| *addr == start_after)
+                .map_or(whitelisted_contracts.len(), |index| index + 1)
+        }
+        None => 0,
+    };
+
+    Ok(WhitelistResponse {
+        contracts: whitelisted_contracts
+            .into_iter()
+            .skip(start)
+            .take(limit)
+            .map(|addr| addr.into_string())
+            .collect(),
+    })
+}
+
+/// Queries the fees accrued since the last CollectFees
+pub fn query_pending_fees(deps: Deps) -> VaultResult<PendingFeesResponse> {
+    let pending = load_pending_fees(deps.storage)?;
+    Ok(PendingFeesResponse {
+        treasury_fee: pending.treasury_fee,
+        commission: pending.commission,
+    })
+}
+
 /// Queries Fees
 pub fn query_fees(deps: Deps) -> VaultResult<FeeResponse> {
     Ok(FeeResponse {
@@ -60,6 +114,74 @@ pub fn query_total_value(env: Env, deps: Deps) -> VaultResult<ValueResponse> {
     Ok(ValueResponse { total_luna_value })
 }
 
+/// Queries the vault value against its luna cap
+pub fn query_vault_utilization(env: Env, deps: Deps) -> VaultResult<VaultUtilizationResponse> {
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+    let total_value_in_luna = compute_total_value(&env, deps, &info)?.total_value_in_luna;
+    let luna_cap = LUNA_CAP.may_load(deps.storage)?;
+    let utilization = luna_cap.map(|cap| {
+        if cap.is_zero() {
+            Decimal::one()
+        } else {
+            Decimal::from_ratio(total_value_in_luna, cap)
+        }
+    });
+    Ok(VaultUtilizationResponse {
+        total_value_in_luna,
+        luna_cap,
+        utilization,
+    })
+}
+
+/// Queries the registered liquid staking backends
+pub fn query_staking_backends(deps: Deps) -> VaultResult<StakingBackendsResponse> {
+    Ok(StakingBackendsResponse {
+        backends: load_staking_backends(deps.storage)?
+            .into_iter()
+            .map(|(name, backend)| StakingBackendResponse {
+                name,
+                backend: backend.backend,
+                token_address: backend.token_address.into_string(),
+                hub_address: backend.hub_address.into_string(),
+                weight: backend.weight,
+            })
+            .collect(),
+    })
+}
+
+/// Queries the detected slashing events
+pub fn query_slashing_events(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> VaultResult<SlashingEventsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(U64Key::new(id)));
+
+    let events = SLASHING_EVENTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, event) = item?;
+            let id = u64::from_be_bytes(
+                key.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid slashing event key"))?,
+            );
+            Ok((id, event))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SlashingEventsResponse {
+        events,
+        guard_enabled: SLASHING_GUARD.may_load(deps.storage)?.unwrap_or_default(),
+        deposits_halted: DEPOSITS_HALTED.may_load(deps.storage)?.unwrap_or_default(),
+        slashing_threshold: SLASHING_THRESHOLD
+            .may_load(deps.storage)?
+            .unwrap_or_else(Decimal::zero),
+    })
+}
+
 /// Queries estimated withdrawal fee
 pub fn estimate_withdraw_fee(
     deps: Deps,
@@ -113,7 +235,12 @@ pub fn query_withdrawable_unbonded(
 }
 
 /// Queries unbond requests for the unbond handler associated with the given address
-pub fn query_unbond_requests(deps: Deps, address: String) -> VaultResult<UnbondRequestsResponse> {
+pub fn query_unbond_requests(
+    deps: Deps,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> VaultResult<UnbondRequestsResponse> {
     let address = deps.api.addr_validate(&address)?;
     let unbond_handler = UNBOND_HANDLERS_ASSIGNED
         .may_load(deps.storage, address)?
@@ -124,13 +251,75 @@ pub fn query_unbond_requests(deps: Deps, address: String) -> VaultResult<UnbondR
         query_contract_from_mem(deps, &state.memory_address, ANCHOR_BLUNA_HUB_ID)?;
 
     // query unbond requests on anchor for the given unbond handler
-    Ok(white_whale::query::anchor::query_unbond_requests(
+    let mut response =
+        white_whale::query::anchor::query_unbond_requests(deps, bluna_hub_address, unbond_handler)?;
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    response.requests = response
+        .requests
+        .into_iter()
+        .filter(|(batch_id, _, _)| start_after.map_or(true, |start| *batch_id > start))
+        .take(limit)
+        .collect();
+    Ok(response)
+}
+
+/// Queries the unbond batch history of the anchor bLuna hub
+pub fn query_all_unbond_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> VaultResult<AllHistoryResponse> {
+    let state = STATE.load(deps.storage)?;
+    let bluna_hub_address =
+        query_contract_from_mem(deps, &state.memory_address, ANCHOR_BLUNA_HUB_ID)?;
+
+    // the hub starts listing after `start_from`
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    Ok(white_whale::query::anchor::query_all_history(
         deps,
         bluna_hub_address,
-        unbond_handler,
+        start_after,
+        Some(limit),
     )?)
 }
 
+/// Queries the luna value of one vLuna
+pub fn query_exchange_rate(env: Env, deps: Deps) -> VaultResult<ExchangeRateResponse> {
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+    let total_value_in_luna = compute_total_value(&env, deps, &info)?.total_value_in_luna;
+    let total_share = query_supply(&deps.querier, info.liquidity_token)?;
+
+    Ok(ExchangeRateResponse {
+        exchange_rate: get_exchange_rate(total_value_in_luna, total_share),
+        total_value_in_luna,
+        total_share,
+    })
+}
+
+/// Estimates the luna paid out for unbonding `amount` vLuna
+pub fn estimate_withdraw(
+    env: Env,
+    deps: Deps,
+    amount: Uint128,
+) -> VaultResult<EstimateWithdrawResponse> {
+    let info: PoolInfoRaw = POOL_INFO.load(deps.storage)?;
+    let total_value_in_luna = compute_total_value(&env, deps, &info)?.total_value_in_luna;
+    let total_share = query_supply(&deps.querier, info.liquidity_token)?;
+
+    // the treasury fee is taken in vLuna before unbonding, like in unbond
+    let fee = get_treasury_fee(deps, amount)?;
+    let luna_amount = if total_share.is_zero() {
+        Uint128::zero()
+    } else {
+        amount
+            .checked_sub(fee)?
+            .multiply_ratio(total_value_in_luna, total_share)
+    };
+
+    Ok(EstimateWithdrawResponse { fee, luna_amount })
+}
+
 /// Queries the unbond handler expiration time if set, returns the default value otherwise
 pub fn query_unbond_handler_expiration_time(storage: &dyn Storage) -> VaultResult<u64> {
     let expiration_time = UNBOND_HANDLER_EXPIRATION_TIME.may_load(storage)?;
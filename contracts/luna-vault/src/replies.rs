@@ -1,5 +1,7 @@
-use cosmwasm_std::{attr, DepsMut, Event, Response, StdError};
+use cosmwasm_std::{Addr, DepsMut, Event, Response, StdError, SubMsgExecutionResponse};
+use protobuf::Message;
 
+use white_whale::events::{WhaleEvent, LUNA_VAULT};
 use white_whale::luna_vault::luna_unbond_handler::{EXPIRATION_TIME_KEY, OWNER_KEY};
 
 use crate::contract::VaultResult;
@@ -12,19 +14,46 @@ use crate::state::{
 };
 
 /// Executes after the token contract instantiation occurs successfully
-/// Stores the liquidity token address
+/// Stores the liquidity token address, taken from the reply data or the instantiate event
 pub fn after_token_instantiation(
     deps: DepsMut,
-    response: MsgInstantiateContractResponse,
+    response: SubMsgExecutionResponse,
 ) -> VaultResult<Response> {
-    let liquidity_token = deps.api.addr_validate(response.get_contract_address())?;
+    let contract_address = match response.data {
+        Some(data) => {
+            let instantiate_response: MsgInstantiateContractResponse =
+                Message::parse_from_bytes(data.as_slice()).map_err(|_| {
+                    StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
+                })?;
+            instantiate_response.get_contract_address().to_string()
+        }
+        None => {
+            let event = response
+                .events
+                .iter()
+                .find(|event| event.ty == "instantiate")
+                .ok_or_else(|| StdError::generic_err("cannot find `instantiate` event"))?;
+            get_attribute_value_from_event(event, "_contract_address")?
+        }
+    };
+    let liquidity_token = deps.api.addr_validate(&contract_address)?;
+    store_liquidity_token(deps, liquidity_token.clone())?;
+
+    Ok(WhaleEvent::new(LUNA_VAULT, "set_lp_token")
+        .add_attribute("liquidity_token_addr", liquidity_token.to_string())
+        .into_response())
+}
 
+/// Stores the liquidity token address, fails if it has already been set
+pub fn store_liquidity_token(deps: DepsMut, liquidity_token: Addr) -> VaultResult<()> {
     POOL_INFO.update(deps.storage, |mut meta| -> VaultResult<_> {
-        meta.liquidity_token = liquidity_token.clone();
+        if !meta.liquidity_token.as_str().is_empty() {
+            return Err(LunaVaultError::LpTokenAlreadySet {});
+        }
+        meta.liquidity_token = liquidity_token;
         Ok(meta)
     })?;
-
-    Ok(Response::new().add_attribute("liquidity_token_addr", liquidity_token.to_string()))
+    Ok(())
 }
 
 /// Executes after the unbond contract instantiation occurs successfully
@@ -84,13 +113,13 @@ pub fn after_unbond_handler_instantiation(
 
     Ok(Response::new()
         .add_messages(vec![send_luna_to_handler_msg, unbond_msg])
-        .add_attributes(vec![
-            attr("action", "unbond_handler_instantiate"),
-            attr("owner", owner_string),
-            attr(
-                "unbond_handler_contract",
-                unbond_handler_contract.to_string(),
-            ),
-            attr("expiration_time", expiration_time_string),
-        ]))
+        .add_attributes(
+            WhaleEvent::new(LUNA_VAULT, "unbond_handler_instantiate")
+                .add_attribute("owner", owner_string)
+                .add_attribute(
+                    "unbond_handler_contract",
+                    unbond_handler_contract.to_string(),
+                )
+                .add_attribute("expiration_time", expiration_time_string),
+        ))
 }
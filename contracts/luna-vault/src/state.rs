@@ -1,11 +1,13 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw_controllers::Admin;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Item, Map, U64Key};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use white_whale::deposit_info::DepositInfo;
 use white_whale::fee::VaultFee;
+use white_whale::luna_vault::msg::SlashingEvent;
+use white_whale::luna_vault::staking_backend::LiquidStakingBackend;
 
 use crate::pool_info::PoolInfoRaw;
 
@@ -32,6 +34,26 @@ pub struct ProfitCheck {
     pub last_profit: Uint128,
 }
 
+/// Fees owed to the treasury, they stay in the vault until CollectFees is called
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PendingFees {
+    /// Treasury fee in vLuna, taken on unbond
+    pub treasury_fee: Uint128,
+    /// Commission in luna, taken on flash loan profits and compounded rewards
+    pub commission: Uint128,
+}
+
+/// Liquid staking protocol the vault can bond luna with
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingBackend {
+    pub backend: LiquidStakingBackend,
+    /// The derivative token minted by the hub
+    pub token_address: Addr,
+    pub hub_address: Addr,
+    /// Share of the luna bonded by BondLuna
+    pub weight: Decimal,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UnbondDataCache {
     pub owner: Addr,
@@ -45,6 +67,25 @@ pub const STATE: Item<State> = Item::new("state");
 pub const POOL_INFO: Item<PoolInfoRaw> = Item::new("pool");
 pub const DEPOSIT_INFO: Item<DepositInfo> = Item::new("deposit");
 pub const FEE: Item<VaultFee> = Item::new("fee");
+// Fees accrued since the last CollectFees, the commission isn't part of the vault value
+pub const PENDING_FEES: Item<PendingFees> = Item::new("pending_fees");
+// Maximum value of the vault in luna, not set if deposits are unlimited
+pub const LUNA_CAP: Item<Uint128> = Item::new("luna_cap");
+
+// Slashing detection
+// bLuna exchange rate seen by the last ProcessBatches
+pub const BLUNA_EXCHANGE_RATE: Item<Decimal> = Item::new("bluna_exchange_rate");
+// Detected drops of the bLuna exchange rate, keyed by an incrementing id
+pub const SLASHING_EVENTS: Map<U64Key, SlashingEvent> = Map::new("slashing_events");
+pub const SLASHING_EVENT_COUNT: Item<u64> = Item::new("slashing_event_count");
+// Whether deposits are halted when slashing is detected
+pub const SLASHING_GUARD: Item<bool> = Item::new("slashing_guard");
+// Minimum relative drop of the bLuna exchange rate detected as slashing, any drop if not set
+pub const SLASHING_THRESHOLD: Item<Decimal> = Item::new("slashing_threshold");
+pub const DEPOSITS_HALTED: Item<bool> = Item::new("deposits_halted");
+
+// Liquid staking backends keyed by name
+pub const STAKING_BACKENDS: Map<&str, StakingBackend> = Map::new("staking_backends");
 
 // Unbond handler objects
 
@@ -68,4 +109,9 @@ pub const UNBOND_CACHE: Item<UnbondDataCache> = Item::new("unbond_cache");
 
 // 40 days
 pub const DEFAULT_UNBOND_EXPIRATION_TIME: u64 = 3456000u64;
+// 21 days, handlers can't expire before the unbonding period of the hubs has passed
+pub const MIN_UNBOND_EXPIRATION_TIME: u64 = 1814400u64;
+// 90 days
+pub const MAX_UNBOND_EXPIRATION_TIME: u64 = 7776000u64;
+pub const MAX_SLASHING_THRESHOLD: Decimal = Decimal::percent(10);
 pub const UNBOND_HANDLER_EXPIRATION_TIMES_READ_LIMIT: u32 = 30u32;
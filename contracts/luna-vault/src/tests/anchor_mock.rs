@@ -1,7 +1,7 @@
 use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Empty, Response, Uint128,
-    WasmMsg,
+    attr, from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Empty, Response,
+    Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
 use cw20::Cw20ReceiveMsg;
@@ -11,7 +11,8 @@ use terra_multi_test::{Contract, ContractWrapper};
 use terraswap::asset::{Asset, AssetInfo};
 
 use white_whale::query::anchor::{
-    AnchorQuery, EpochStateResponse, UnbondRequestsResponse, WithdrawableUnbondedResponse,
+    AllHistoryResponse, AnchorQuery, EpochStateResponse, HubStateResponse, UnbondRequestsResponse,
+    WithdrawableUnbondedResponse,
 };
 
 use crate::contract::VaultResult;
@@ -179,6 +180,12 @@ pub fn contract_anchor_mock() -> Box<dyn Contract<Empty>> {
                 AnchorQuery::WithdrawableUnbonded { .. } => {
                     Ok(to_binary(&mock_withdrawable_unbonded())?)
                 }
+                AnchorQuery::State {} => Ok(to_binary(&HubStateResponse {
+                    bluna_exchange_rate: Decimal::one(),
+                })?),
+                AnchorQuery::AllHistory { .. } => {
+                    Ok(to_binary(&AllHistoryResponse { history: vec![] })?)
+                }
             }
         },
     );
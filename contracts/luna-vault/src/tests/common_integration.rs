@@ -31,6 +31,7 @@ pub fn instantiate_msg(
         vault_lp_token_name: None,
         vault_lp_token_symbol: None,
         unbond_handler_code_id: 0,
+        luna_cap: None,
     }
 }
 
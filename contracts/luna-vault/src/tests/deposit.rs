@@ -1,17 +1,19 @@
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{from_binary, to_binary, MessageInfo, ReplyOn, SubMsg, WasmMsg};
-use cosmwasm_std::{Api, Decimal};
+use cosmwasm_std::{from_binary, to_binary, MessageInfo, ReplyOn, SubMsg, Uint128, WasmMsg};
+use cosmwasm_std::{Api, Coin, Decimal};
 
 use crate::contract::{execute, instantiate, query};
-use crate::state::{State, STATE};
-use cw20::MinterResponse;
-use terraswap::asset::AssetInfo;
+use crate::state::{State, DEPOSITS_HALTED, LUNA_CAP, STATE};
+use cw20::{Cw20ReceiveMsg, MinterResponse};
+use terraswap::asset::{Asset, AssetInfo};
 use white_whale::luna_vault::msg::InstantiateMsg as VaultInstantiateMsg;
 
 use terraswap::token::InstantiateMsg as TokenInstantiateMsg;
+use white_whale::error::CommonError;
 use white_whale::fee::*;
 use white_whale::luna_vault::msg::VaultQueryMsg as QueryMsg;
 use white_whale::luna_vault::msg::*;
+use white_whale::pause::{PauseScope, PausedScopesResponse};
 
 use crate::tests::common::{ARB_CONTRACT, TEST_CREATOR};
 
@@ -142,6 +144,31 @@ fn sad_path_update_fee() {
     }
 }
 
+#[test]
+fn unsuccessful_update_fee_total_too_high() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    // every fee is valid on its own but combined they exceed the ceiling
+    let info = mock_info(TEST_CREATOR, &[]);
+    let msg = ExecuteMsg::SetFee {
+        treasury_fee: Some(Fee {
+            share: Decimal::percent(20),
+        }),
+        flash_loan_fee: Some(Fee {
+            share: Decimal::percent(20),
+        }),
+        commission_fee: Some(Fee {
+            share: Decimal::percent(20),
+        }),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    match res {
+        LunaVaultError::Fee(FeeError::TotalFeeTooHigh { .. }) => (),
+        _ => panic!("Must return LunaVaultError::Fee"),
+    }
+}
+
 #[test]
 fn successfull_set_admin() {
     let mut deps = mock_dependencies(&[]);
@@ -182,6 +209,7 @@ fn test_init_with_non_default_vault_lp_token() {
         vault_lp_token_name: Some(custom_token_name.clone()),
         vault_lp_token_symbol: Some(custom_token_symbol.clone()),
         unbond_handler_code_id: 0,
+        luna_cap: None,
     };
 
     // Prepare mock env
@@ -223,3 +251,263 @@ fn test_init_with_non_default_vault_lp_token() {
         }]
     );
 }
+
+#[test]
+fn unsuccessful_bluna_deposit_from_other_token() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    // only the bluna token contract may deposit bluna
+    let info = mock_info("cluna", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::ProvideLiquidity {}).unwrap(),
+    });
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    match res {
+        LunaVaultError::Unauthorized {} => (),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+}
+
+#[test]
+fn unsuccessful_bluna_deposit_zero_amount() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info("bluna", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::zero(),
+        msg: to_binary(&Cw20HookMsg::ProvideLiquidity {}).unwrap(),
+    });
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    match res {
+        LunaVaultError::InvalidZeroAmount {} => (),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+}
+
+#[test]
+fn successful_update_luna_cap() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    assert_eq!(LUNA_CAP.may_load(&deps.storage).unwrap(), None);
+
+    // only the admin can update the cap
+    let msg = ExecuteMsg::UpdateLunaCap {
+        cap: Some(Uint128::from(1000u128)),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("someone", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(LunaVaultError::Admin(_)) => (),
+        _ => panic!("Must return LunaVaultError::Admin"),
+    }
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        LUNA_CAP.load(&deps.storage).unwrap(),
+        Uint128::from(1000u128)
+    );
+
+    // removing the cap makes deposits unlimited again
+    let msg = ExecuteMsg::UpdateLunaCap { cap: None };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(LUNA_CAP.may_load(&deps.storage).unwrap(), None);
+}
+
+#[test]
+fn unsuccessful_lp_management_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info("someone", &[]);
+    let msg = ExecuteMsg::DepositIntoLp {
+        amount: Uint128::from(1000u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(LunaVaultError::Unauthorized {}) => (),
+        _ => panic!("Must return LunaVaultError::Unauthorized"),
+    }
+
+    let msg = ExecuteMsg::WithdrawFromLp {
+        share: Uint128::from(1000u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(LunaVaultError::Unauthorized {}) => (),
+        _ => panic!("Must return LunaVaultError::Unauthorized"),
+    }
+}
+
+#[test]
+fn unsuccessful_lp_management_zero_amount() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    let msg = ExecuteMsg::DepositIntoLp {
+        amount: Uint128::zero(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(LunaVaultError::InvalidZeroAmount {}) => (),
+        _ => panic!("Must return LunaVaultError::InvalidZeroAmount"),
+    }
+
+    let msg = ExecuteMsg::WithdrawFromLp {
+        share: Uint128::zero(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(LunaVaultError::InvalidZeroAmount {}) => (),
+        _ => panic!("Must return LunaVaultError::InvalidZeroAmount"),
+    }
+}
+
+#[test]
+fn unsuccessful_deposit_after_slashing() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    DEPOSITS_HALTED.save(deps.as_mut().storage, &true).unwrap();
+
+    let msg = ExecuteMsg::ProvideLiquidity {
+        asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            amount: Uint128::from(1000u128),
+        },
+    };
+    let info = mock_info(TEST_CREATOR, &[Coin::new(1000, "uluna")]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(LunaVaultError::DepositsHalted {}) => (),
+        _ => panic!("Must return LunaVaultError::DepositsHalted"),
+    }
+
+    // only the admin can resume deposits
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("someone", &[]),
+        ExecuteMsg::AcknowledgeSlashing {},
+    );
+    match res {
+        Err(LunaVaultError::Admin(_)) => (),
+        _ => panic!("Must return LunaVaultError::Admin"),
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::AcknowledgeSlashing {},
+    )
+    .unwrap();
+    let res: SlashingEventsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SlashingEvents {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(!res.deposits_halted);
+    assert!(res.events.is_empty());
+}
+
+#[test]
+fn unsuccessful_deposit_when_deposits_scope_paused() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::PauseScopes {
+            scopes: vec![PauseScope::Deposits],
+        },
+    )
+    .unwrap();
+    let res: PausedScopesResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PausedScopes {}).unwrap()).unwrap();
+    assert!(!res.paused);
+    assert_eq!(res.scopes, vec![PauseScope::Deposits]);
+
+    let msg = ExecuteMsg::ProvideLiquidity {
+        asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            amount: Uint128::from(1000u128),
+        },
+    };
+    let info = mock_info(TEST_CREATOR, &[Coin::new(1000, "uluna")]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    match res {
+        Err(LunaVaultError::Common(CommonError::ScopePaused {
+            scope: PauseScope::Deposits,
+        })) => (),
+        _ => panic!("Must return CommonError::ScopePaused"),
+    }
+
+    // bLuna deposits through the receive hook are halted as well
+    let bluna_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::ProvideLiquidity {}).unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bluna", &[]),
+        bluna_msg,
+    );
+    match res {
+        Err(LunaVaultError::Common(CommonError::ScopePaused {
+            scope: PauseScope::Deposits,
+        })) => (),
+        _ => panic!("Must return CommonError::ScopePaused"),
+    }
+
+    // withdrawals are not affected by the deposits scope
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::WithdrawUnbonded {},
+    );
+    if let Err(LunaVaultError::Common(CommonError::ScopePaused { .. })) = res {
+        panic!("Withdrawals must not be paused")
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::UnpauseScopes {
+            scopes: vec![PauseScope::Deposits],
+        },
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    if let Err(LunaVaultError::Common(CommonError::ScopePaused { .. })) = res {
+        panic!("Deposits must be resumed")
+    }
+}
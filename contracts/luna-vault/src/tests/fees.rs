@@ -0,0 +1,82 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary, to_binary, BankMsg, CosmosMsg, Deps, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+use white_whale::denom::LUNA_DENOM;
+use white_whale::luna_vault::msg::VaultQueryMsg as QueryMsg;
+use white_whale::luna_vault::msg::{ExecuteMsg, PendingFeesResponse};
+
+use crate::contract::{execute, query};
+use crate::error::LunaVaultError;
+use crate::helpers::accrue_fees;
+use crate::state::POOL_INFO;
+use crate::tests::instantiate::mock_instantiate;
+use crate::tests::mock_querier::mock_dependencies;
+
+fn pending_fees(deps: Deps) -> PendingFeesResponse {
+    from_binary(&query(deps, mock_env(), QueryMsg::PendingFees {}).unwrap()).unwrap()
+}
+
+#[test]
+fn unsuccessful_collect_fees_nothing_accrued() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::CollectFees {},
+    );
+    match res {
+        Err(LunaVaultError::NoPendingFees {}) => (),
+        _ => panic!("Must return LunaVaultError::NoPendingFees"),
+    }
+}
+
+#[test]
+fn successful_collect_fees() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    // fees accrue over several operations
+    accrue_fees(deps.as_mut().storage, Uint128::new(100), Uint128::zero()).unwrap();
+    accrue_fees(deps.as_mut().storage, Uint128::new(50), Uint128::new(30)).unwrap();
+    let pending = pending_fees(deps.as_ref());
+    assert_eq!(pending.treasury_fee, Uint128::new(150));
+    assert_eq!(pending.commission, Uint128::new(30));
+
+    // anyone can send the fees to the treasury in one go
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::CollectFees {},
+    )
+    .unwrap();
+    let liquidity_token = POOL_INFO.load(&deps.storage).unwrap().liquidity_token;
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: liquidity_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "warchest".to_string(),
+                amount: Uint128::new(150),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "warchest".to_string(),
+            amount: coins(30, LUNA_DENOM),
+        })
+    );
+
+    let pending = pending_fees(deps.as_ref());
+    assert!(pending.treasury_fee.is_zero());
+    assert!(pending.commission.is_zero());
+}
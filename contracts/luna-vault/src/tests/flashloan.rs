@@ -1,4 +1,5 @@
 use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::Uint128;
 use terraswap::asset::{Asset, AssetInfo};
 
 use white_whale::denom::LUNA_DENOM;
@@ -41,3 +42,33 @@ fn unsuccessful_flashloan_not_whitelisted() {
         _ => panic!("Must return LunaVaultError::NotWhitelisted"),
     }
 }
+
+#[test]
+fn unsuccessful_flashloan_not_luna() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    let msg = ExecuteMsg::AddToWhitelist {
+        contract_addr: TEST_CREATOR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::FlashLoan {
+        payload: FlashLoanPayload {
+            requested_asset: Asset {
+                info: AssetInfo::Token {
+                    contract_addr: "bluna".to_string(),
+                },
+                amount: Uint128::from(1000u128),
+            },
+            callback: Default::default(),
+        },
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(LunaVaultError::NotLunaToken {}) => (),
+        _ => panic!("Must return LunaVaultError::NotLunaToken"),
+    }
+}
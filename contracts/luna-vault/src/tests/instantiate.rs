@@ -1,17 +1,21 @@
 use crate::tests::mock_querier::mock_dependencies;
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{from_binary, to_binary, Addr, DepsMut, MessageInfo, ReplyOn, SubMsg, WasmMsg};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, ContractResult, DepsMut, Event, MessageInfo, Reply, ReplyOn,
+    SubMsg, SubMsgExecutionResponse, WasmMsg,
+};
 use cosmwasm_std::{Api, Decimal};
 use cw20::MinterResponse;
 use terraswap::asset::AssetInfo;
 use terraswap::token::InstantiateMsg as TokenInstantiateMsg;
+use white_whale::error::CommonError;
 use white_whale::fee::*;
 use white_whale::luna_vault::msg::VaultQueryMsg as QueryMsg;
 use white_whale::luna_vault::msg::*;
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, query, reply};
 use crate::error::LunaVaultError;
-use crate::state::{State, FEE, STATE};
+use crate::state::{State, FEE, POOL_INFO, STATE};
 use crate::tests::common::{ARB_CONTRACT, TEST_CREATOR};
 use crate::tests::common_integration::instantiate_msg as vault_msg;
 use white_whale::luna_vault::msg::InstantiateMsg as VaultInstantiateMsg;
@@ -67,6 +71,7 @@ pub fn mock_instantiate_no_asset_info(deps: DepsMut) {
         vault_lp_token_name: None,
         vault_lp_token_symbol: None,
         unbond_handler_code_id: 0,
+        luna_cap: None,
     };
 
     let info = mock_info(TEST_CREATOR, &[]);
@@ -150,6 +155,7 @@ fn unsuccessful_initialization_invalid_fees() {
         vault_lp_token_name: None,
         vault_lp_token_symbol: None,
         unbond_handler_code_id: 0,
+        luna_cap: None,
     };
 
     let info = mock_info(TEST_CREATOR, &[]);
@@ -182,6 +188,7 @@ fn unsuccessful_initialization_invalid_asset() {
         vault_lp_token_name: None,
         vault_lp_token_symbol: None,
         unbond_handler_code_id: 0,
+        luna_cap: None,
     };
 
     let info = mock_info(TEST_CREATOR, &[]);
@@ -192,6 +199,37 @@ fn unsuccessful_initialization_invalid_asset() {
     }
 }
 
+#[test]
+fn unsuccessful_initialization_invalid_address() {
+    let invalid_fields: Vec<(&str, fn(&mut InstantiateMsg))> = vec![
+        ("bluna_address", |msg| msg.bluna_address = "x".to_string()),
+        ("cluna_address", |msg| msg.cluna_address = "x".to_string()),
+        ("astro_lp_address", |msg| {
+            msg.astro_lp_address = "x".to_string()
+        }),
+        ("astro_factory_address", |msg| {
+            msg.astro_factory_address = "x".to_string()
+        }),
+        ("memory_addr", |msg| msg.memory_addr = "x".to_string()),
+        ("treasury_addr", |msg| msg.treasury_addr = "x".to_string()),
+    ];
+
+    for (field, invalidate) in invalid_fields {
+        let mut deps = mock_dependencies(&[]);
+        let mut msg = instantiate_msg();
+        invalidate(&mut msg);
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+        match res {
+            Err(LunaVaultError::Common(CommonError::InvalidAddress {
+                field: invalid_field,
+                ..
+            })) => assert_eq!(invalid_field, field),
+            _ => panic!("Must return CommonError::InvalidAddress for {}", field),
+        }
+    }
+}
+
 /**
  * Tests updating the fees of the contract.
  */
@@ -274,6 +312,80 @@ fn successful_update_fee_unchanged() {
     assert_eq!(original_treasury_fee.share, fees.treasury_fee.share);
 }
 
+#[test]
+fn successful_update_config() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdateConfig {
+        unbond_handler_expiration_time: Some(30 * 24 * 60 * 60),
+        slashing_threshold: Some(Decimal::permille(5)),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("someone", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(LunaVaultError::Admin(_)) => (),
+        _ => panic!("Must return LunaVaultError::Admin"),
+    }
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res: u64 = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UnbondHandlerExpirationTime {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, 30 * 24 * 60 * 60);
+    let res: SlashingEventsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SlashingEvents {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.slashing_threshold, Decimal::permille(5));
+}
+
+#[test]
+fn unsuccessful_update_config_out_of_bounds() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let info = mock_info(TEST_CREATOR, &[]);
+
+    // unbond handlers can't expire before the unbonding period has passed
+    let msg = ExecuteMsg::UpdateConfig {
+        unbond_handler_expiration_time: Some(7 * 24 * 60 * 60),
+        slashing_threshold: None,
+    };
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+        Err(LunaVaultError::InvalidUnbondHandlerExpirationTime { .. }) => (),
+        _ => panic!("Must return LunaVaultError::InvalidUnbondHandlerExpirationTime"),
+    }
+
+    let msg = ExecuteMsg::UpdateConfig {
+        unbond_handler_expiration_time: None,
+        slashing_threshold: Some(Decimal::percent(50)),
+    };
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(LunaVaultError::InvalidSlashingThreshold { .. }) => (),
+        _ => panic!("Must return LunaVaultError::InvalidSlashingThreshold"),
+    }
+}
+
 #[test]
 fn successfull_set_admin() {
     let mut deps = mock_dependencies(&[]);
@@ -332,6 +444,7 @@ fn test_init_with_non_default_vault_lp_token() {
         vault_lp_token_name: Some(custom_token_name.clone()),
         vault_lp_token_symbol: Some(custom_token_symbol.clone()),
         unbond_handler_code_id: 0,
+        luna_cap: None,
     };
 
     // Prepare mock env
@@ -373,3 +486,66 @@ fn test_init_with_non_default_vault_lp_token() {
         }]
     );
 }
+
+fn token_instantiation_reply() -> Reply {
+    Reply {
+        id: u64::from(INSTANTIATE_REPLY_ID),
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![Event::new("instantiate").add_attribute("_contract_address", "vluna")],
+            data: None,
+        }),
+    }
+}
+
+/**
+ * Tests storing the LP token from the instantiation reply and rejecting duplicates.
+ */
+#[test]
+fn lp_token_stored_once_on_reply() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    reply(deps.as_mut(), mock_env(), token_instantiation_reply()).unwrap();
+    let pool_info = POOL_INFO.load(&deps.storage).unwrap();
+    assert_eq!(pool_info.liquidity_token, Addr::unchecked("vluna"));
+
+    let res = reply(deps.as_mut(), mock_env(), token_instantiation_reply());
+    match res {
+        Err(LunaVaultError::LpTokenAlreadySet {}) => (),
+        _ => panic!("Must return LunaVaultError::LpTokenAlreadySet"),
+    }
+}
+
+/**
+ * Tests setting the LP token manually when the reply didn't store it.
+ */
+#[test]
+fn set_lp_token() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::SetLpToken {
+        address: "vluna".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("someone", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(LunaVaultError::Admin(_)) => (),
+        _ => panic!("Must return LunaVaultError::Admin"),
+    }
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    let pool_info = POOL_INFO.load(&deps.storage).unwrap();
+    assert_eq!(pool_info.liquidity_token, Addr::unchecked("vluna"));
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(LunaVaultError::LpTokenAlreadySet {}) => (),
+        _ => panic!("Must return LunaVaultError::LpTokenAlreadySet"),
+    }
+}
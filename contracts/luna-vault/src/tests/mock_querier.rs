@@ -504,6 +504,11 @@ impl WasmMockQuerier {
     //     }
     // }
 
+    // configure the cw20 token balances
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        self.token_querier = TokenQuerier::new(balances);
+    }
+
     // Configure the Astroport pair
     pub fn with_astroport_pairs(&mut self, pairs: &[(&String, &PairInfo)]) {
         self.astroport_factory_querier = AstroportFactoryQuerier::new(pairs);
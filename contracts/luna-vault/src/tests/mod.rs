@@ -10,8 +10,10 @@ mod mock_querier;
 
 mod anchor_mock;
 mod deposit;
+mod fees;
 mod helpers;
 mod query;
+mod staking_backends;
 mod state;
 mod tswap_mock;
 mod whitelist;
@@ -6,6 +6,7 @@ use white_whale::luna_vault::msg::VaultQueryMsg as QueryMsg;
 use white_whale::luna_vault::msg::*;
 
 use crate::contract::query;
+use crate::error::LunaVaultError;
 use crate::tests::instantiate::mock_instantiate;
 use crate::tests::mock_querier::mock_dependencies;
 
@@ -29,3 +30,24 @@ pub fn test_fees_query() {
     assert_eq!(q_res.fees.treasury_fee.share, Decimal::percent(10u64));
     assert_eq!(q_res.fees.flash_loan_fee.share, Decimal::permille(5u64));
 }
+
+#[test]
+pub fn test_unbond_requests_query_without_handler() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let env = mock_env();
+
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::UnbondRequests {
+            address: "someone".to_string(),
+            start_after: None,
+            limit: None,
+        },
+    );
+    match res {
+        Err(LunaVaultError::NoUnbondHandlerAssigned {}) => (),
+        _ => panic!("Must return LunaVaultError::NoUnbondHandlerAssigned"),
+    }
+}
@@ -0,0 +1,241 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{coins, from_binary, to_binary, CosmosMsg, Decimal, DepsMut, Uint128, WasmMsg};
+
+use white_whale::luna_vault::msg::{
+    ExecuteMsg, StakingBackendResponse, StakingBackendsResponse, VaultQueryMsg as QueryMsg,
+};
+use white_whale::luna_vault::staking_backend::LiquidStakingBackend;
+use white_whale::stader::StaderMsg;
+
+use crate::contract::{execute, query};
+use crate::error::LunaVaultError;
+use crate::tests::common::TEST_CREATOR;
+use crate::tests::instantiate::mock_instantiate;
+use crate::tests::mock_querier::mock_dependencies;
+
+fn add_backend(deps: DepsMut, name: &str, backend: LiquidStakingBackend) {
+    execute(
+        deps,
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::AddStakingBackend {
+            name: name.to_string(),
+            backend,
+            token_address: format!("{}_token", name),
+            hub_address: format!("{}_hub", name),
+        },
+    )
+    .unwrap();
+}
+
+fn set_weights(deps: DepsMut, weights: Vec<(&str, Decimal)>) -> Result<(), LunaVaultError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::SetStakingBackendWeights {
+            weights: weights
+                .into_iter()
+                .map(|(name, weight)| (name.to_string(), weight))
+                .collect(),
+        },
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn add_staking_backend() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    add_backend(deps.as_mut(), "lunax", LiquidStakingBackend::Stader);
+
+    let res: StakingBackendsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::StakingBackends {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        res.backends,
+        vec![StakingBackendResponse {
+            name: "lunax".to_string(),
+            backend: LiquidStakingBackend::Stader,
+            token_address: "lunax_token".to_string(),
+            hub_address: "lunax_hub".to_string(),
+            weight: Decimal::zero(),
+        }]
+    );
+
+    let msg = ExecuteMsg::AddStakingBackend {
+        name: "lunax".to_string(),
+        backend: LiquidStakingBackend::Stader,
+        token_address: "lunax_token".to_string(),
+        hub_address: "lunax_hub".to_string(),
+    };
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("unauthorized", &[]),
+        msg.clone(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(LunaVaultError::Admin(_)) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(LunaVaultError::StakingBackendAlreadyExists { name }) => assert_eq!(name, "lunax"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn set_staking_backend_weights() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    add_backend(deps.as_mut(), "bluna", LiquidStakingBackend::Anchor);
+    add_backend(deps.as_mut(), "lunax", LiquidStakingBackend::Stader);
+
+    match set_weights(deps.as_mut(), vec![("cluna", Decimal::one())]) {
+        Err(LunaVaultError::StakingBackendNotFound { name }) => assert_eq!(name, "cluna"),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+    for weights in [
+        vec![
+            ("bluna", Decimal::percent(50)),
+            ("lunax", Decimal::percent(40)),
+        ],
+        vec![
+            ("bluna", Decimal::percent(50)),
+            ("bluna", Decimal::percent(50)),
+        ],
+    ] {
+        match set_weights(deps.as_mut(), weights) {
+            Err(LunaVaultError::InvalidStakingBackendWeights {}) => (),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+    }
+
+    set_weights(
+        deps.as_mut(),
+        vec![
+            ("bluna", Decimal::percent(60)),
+            ("lunax", Decimal::percent(40)),
+        ],
+    )
+    .unwrap();
+    // backends left out of the weights get no allocation
+    set_weights(deps.as_mut(), vec![("lunax", Decimal::one())]).unwrap();
+
+    let res: StakingBackendsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::StakingBackends {}).unwrap())
+            .unwrap();
+    let weights: Vec<(String, Decimal)> = res
+        .backends
+        .into_iter()
+        .map(|backend| (backend.name, backend.weight))
+        .collect();
+    assert_eq!(
+        weights,
+        vec![
+            ("bluna".to_string(), Decimal::zero()),
+            ("lunax".to_string(), Decimal::one()),
+        ]
+    );
+}
+
+#[test]
+fn remove_staking_backend() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    add_backend(deps.as_mut(), "lunax", LiquidStakingBackend::Stader);
+    deps.querier.with_token_balances(&[(
+        &"lunax_token".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::zero())],
+    )]);
+    set_weights(deps.as_mut(), vec![("lunax", Decimal::one())]).unwrap();
+
+    let msg = ExecuteMsg::RemoveStakingBackend {
+        name: "lunax".to_string(),
+    };
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        msg.clone(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(LunaVaultError::StakingBackendInUse {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    set_weights(deps.as_mut(), vec![]).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+
+    let res: StakingBackendsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::StakingBackends {}).unwrap())
+            .unwrap();
+    assert!(res.backends.is_empty());
+}
+
+#[test]
+fn bond_luna() {
+    let mut deps = mock_dependencies(&coins(1000, "uluna"));
+    mock_instantiate(deps.as_mut());
+    add_backend(deps.as_mut(), "bluna", LiquidStakingBackend::Anchor);
+    add_backend(deps.as_mut(), "lunax", LiquidStakingBackend::Stader);
+
+    let msg = ExecuteMsg::BondLuna {
+        amount: Uint128::from(1000u128),
+    };
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        msg.clone(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(LunaVaultError::NoStakingBackendWeights {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    set_weights(
+        deps.as_mut(),
+        vec![
+            ("bluna", Decimal::percent(70)),
+            ("lunax", Decimal::percent(30)),
+        ],
+    )
+    .unwrap();
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("unauthorized", &[]),
+        msg.clone(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(LunaVaultError::Unauthorized {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "lunax_hub".to_string(),
+            msg: to_binary(&StaderMsg::Deposit {}).unwrap(),
+            funds: coins(300, "uluna"),
+        })
+    );
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::BondLuna {
+            amount: Uint128::from(1001u128),
+        },
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(LunaVaultError::Broke {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
@@ -1,6 +1,7 @@
 use cosmwasm_std::testing::mock_env;
 use cosmwasm_std::{Api, MessageInfo};
 
+use white_whale::error::CommonError;
 use white_whale::luna_vault::msg::ExecuteMsg;
 
 use crate::contract::execute;
@@ -71,3 +72,31 @@ fn successful_set_state() {
     );
     assert!(new_state.allow_non_whitelisted);
 }
+
+#[test]
+fn unsuccessful_set_state_invalid_address() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdateState {
+        bluna_address: None,
+        cluna_address: None,
+        astro_lp_address: None,
+        memory_address: Some("x".to_string()),
+        whitelisted_contracts: None,
+        allow_non_whitelisted: None,
+    };
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(TEST_CREATOR).unwrap(),
+        funds: vec![],
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(LunaVaultError::Common(CommonError::InvalidAddress { field, address })) => {
+            assert_eq!(field, "memory_address");
+            assert_eq!(address, "x");
+        }
+        _ => panic!("Must return CommonError::InvalidAddress"),
+    }
+}
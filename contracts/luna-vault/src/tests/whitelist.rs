@@ -1,10 +1,10 @@
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{Api, MessageInfo};
+use cosmwasm_std::{attr, from_binary, Api, MessageInfo};
 
-use white_whale::luna_vault::msg::ExecuteMsg;
+use white_whale::luna_vault::msg::{ExecuteMsg, VaultQueryMsg as QueryMsg, WhitelistResponse};
 use white_whale::memory::LIST_SIZE_LIMIT;
 
-use crate::contract::execute;
+use crate::contract::{execute, query};
 use crate::error::LunaVaultError;
 use crate::state::{State, STATE};
 use crate::tests::common::{ARB_CONTRACT, TEST_CREATOR};
@@ -49,6 +49,14 @@ fn unsuccessful_add_to_whitelist_already_whitelisted() {
 
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
     assert_eq!(0, res.messages.len());
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "add_to_whitelist"),
+            attr("module", "luna_vault"),
+            attr("contract", "contract"),
+        ]
+    );
 
     // repeat the same whitelisting
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -201,3 +209,53 @@ fn successful_remove_from_whitelist() {
     //no contract should be whitelisted
     assert_eq!(0, whitelisted_contracts.len());
 }
+
+#[test]
+fn successful_whitelist_query() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    for contract in ["contract0", "contract1", "contract2"] {
+        let msg = ExecuteMsg::AddToWhitelist {
+            contract_addr: contract.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let msg = QueryMsg::Whitelist {
+        start_after: None,
+        limit: Some(2),
+    };
+    let res: WhitelistResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.contracts, vec!["contract0", "contract1"]);
+
+    let msg = QueryMsg::Whitelist {
+        start_after: Some("contract1".to_string()),
+        limit: None,
+    };
+    let res: WhitelistResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.contracts, vec!["contract2"]);
+}
+
+#[test]
+fn unsuccessful_update_state_duplicate_whitelist() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdateState {
+        bluna_address: None,
+        cluna_address: None,
+        astro_lp_address: None,
+        memory_address: None,
+        whitelisted_contracts: Some(vec![ARB_CONTRACT.to_string(), ARB_CONTRACT.to_string()]),
+        allow_non_whitelisted: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+    match res {
+        Err(LunaVaultError::AlreadyWhitelisted {}) => (),
+        _ => panic!("Must return LunaVaultError::AlreadyWhitelisted"),
+    }
+}
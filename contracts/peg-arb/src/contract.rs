@@ -1,43 +1,50 @@
 use cosmwasm_std::{
-    entry_point, to_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Order, Response, StdResult, WasmMsg,
+    entry_point, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128,
 };
 
 use terra_cosmwasm::{create_swap_msg, TerraMsgWrapper};
-use terraswap::asset::{Asset, AssetInfo};
+use terraswap::asset::AssetInfo;
 
 use terraswap::querier::query_balance;
 
-use white_whale::denom::{LUNA_DENOM, UST_DENOM};
-
-use white_whale::ust_vault::terraswap::create_terraswap_msg;
+use white_whale::swap::{simulate_native_swap, Swap};
 
+use white_whale::denom::LUNA_DENOM;
 use white_whale::deposit_info::ArbBaseAsset;
-use white_whale::luna_vault::msg::{ExecuteMsg as LunaVaultMsg, FlashLoanPayload};
-use white_whale::query::terraswap::simulate_swap as simulate_terraswap_swap;
+use white_whale::flashloan::msg::flash_loan_msg;
 use white_whale::tax::deduct_tax;
-use white_whale::ust_vault::msg::ExecuteMsg as VaultMsg;
-
-use white_whale::flashloan::flashloan::FlashLoanPayload;
 
 use cw2::{get_contract_version, set_contract_version};
 use semver::Version;
 
 use crate::error::StableArbError;
 use white_whale::peg_arb::msg::{
-    ArbDetails, CallbackMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ArbDetails, ArbStats, CallbackMsg, Dex, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolConfig,
+    PoolsResponse, ProfitSplit, QueryMsg, SimulateArbResponse, StateResponse,
 };
 
-use crate::querier::query_market_price;
+use crate::querier::{query_market_price, query_tax_on_top, query_vault_fees};
 
-use crate::state::{State, ADMIN, ARB_BASE_ASSET, POOLS, STATE};
+use crate::state::{
+    load_market_denom, Pool, State, ADMIN, ARB_BASE_ASSET, ARB_CALLER, ARB_STATS, LEGACY_POOLS,
+    MARKET_DENOM, POOLS, STATE, STATE_V0,
+};
+use white_whale::address::validate_addr;
+use white_whale::admin::{accept_admin, propose_admin, DEFAULT_ADMIN_PROPOSAL_EXPIRY};
+use white_whale::events::{WhaleEvent, PEG_ARB};
 use white_whale::memory::LIST_SIZE_LIMIT;
+use white_whale::pause::{
+    assert_not_paused, is_paused, query_paused_scopes, set_paused, set_paused_scopes, PauseScope,
+};
 type VaultResult = Result<Response<TerraMsgWrapper>, StableArbError>;
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:stablecoin-arb-terra";
+pub(crate) const CONTRACT_NAME: &str = "crates.io:stablecoin-arb-terra";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const BPS_DENOMINATOR: u64 = 10_000;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -48,18 +55,17 @@ pub fn instantiate(
     // Use CW2 to set the contract version, this is needed for migrations
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let state = State {
-        vault_address: deps.api.addr_validate(&msg.vault_address)?,
-        seignorage_address: deps.api.addr_validate(&msg.seignorage_address)?,
+        vault_address: validate_addr(deps.api, "vault_address", &msg.vault_address)?,
+        seignorage_address: validate_addr(deps.api, "seignorage_address", &msg.seignorage_address)?,
+        treasury_address: validate_addr(deps.api, "treasury_address", &msg.treasury_address)?,
+        min_profit: msg.min_profit,
+        profit_split: msg.profit_split,
     };
+    validate_profit_split(&state.profit_split)?;
 
     // Store the initial config
     STATE.save(deps.storage, &state)?;
-    ARB_BASE_ASSET.save(
-        deps.storage,
-        &ArbBaseAsset {
-            asset_info: msg.asset_info,
-        },
-    )?;
+    ARB_BASE_ASSET.save(deps.storage, &ArbBaseAsset::new(msg.asset_info))?;
     // Setup the admin as the creator of the contract
     ADMIN.set(deps, Some(info.sender))?;
 
@@ -68,22 +74,60 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> VaultResult {
+    if !matches!(msg, ExecuteMsg::Unpause {}) && is_paused(deps.storage)? {
+        return Err(StableArbError::Paused {});
+    }
+
     match msg {
         ExecuteMsg::ExecuteArb { details, above_peg } => {
+            assert_not_paused(deps.storage, PauseScope::Trading)?;
             call_flashloan(deps, env, info, details, above_peg)
         }
         ExecuteMsg::BelowPegCallback { details } => try_arb_below_peg(deps, env, info, details),
         ExecuteMsg::AbovePegCallback { details } => try_arb_above_peg(deps, env, info, details),
-        ExecuteMsg::SetAdmin { admin } => {
-            let admin_addr = deps.api.addr_validate(&admin)?;
-            let previous_admin = ADMIN.get(deps.as_ref())?.unwrap();
-            ADMIN.execute_update_admin(deps, info, Some(admin_addr))?;
-            Ok(Response::default()
-                .add_attribute("previous admin", previous_admin)
-                .add_attribute("admin", admin))
+        ExecuteMsg::SetAdmin { admin } => Ok(propose_admin(
+            deps,
+            &env,
+            &info,
+            &ADMIN,
+            admin,
+            DEFAULT_ADMIN_PROPOSAL_EXPIRY,
+        )?),
+        ExecuteMsg::ProposeAdmin { admin, expires_in } => {
+            Ok(propose_admin(deps, &env, &info, &ADMIN, admin, expires_in)?)
+        }
+        ExecuteMsg::AcceptAdmin {} => Ok(accept_admin(deps, &env, &info, &ADMIN)?),
+        ExecuteMsg::Pause {} => Ok(set_paused(deps, &info, &ADMIN, true)?),
+        ExecuteMsg::Unpause {} => Ok(set_paused(deps, &info, &ADMIN, false)?),
+        ExecuteMsg::PauseScopes { scopes } => {
+            Ok(set_paused_scopes(deps, &info, &ADMIN, scopes, true)?)
+        }
+        ExecuteMsg::UnpauseScopes { scopes } => {
+            Ok(set_paused_scopes(deps, &info, &ADMIN, scopes, false)?)
         }
         ExecuteMsg::SetVault { vault } => set_vault_addr(deps, info, vault),
-        ExecuteMsg::UpdatePools { to_add, to_remove } => update_pools(deps, to_add, to_remove),
+        ExecuteMsg::UpdateConfig {
+            treasury_address,
+            min_profit,
+            profit_split,
+            min_arb_amount,
+        } => update_config(
+            deps,
+            info,
+            treasury_address,
+            min_profit,
+            profit_split,
+            min_arb_amount,
+        ),
+        ExecuteMsg::UpdatePools { to_add, to_remove } => {
+            update_pools(deps, info, to_add, to_remove)
+        }
+        ExecuteMsg::SetArbAsset {
+            denom,
+            market_denom,
+            min_arb_amount,
+            min_profit,
+        } => set_arb_asset(deps, info, denom, market_denom, min_arb_amount, min_profit),
         ExecuteMsg::Callback(msg) => _handle_callback(deps, env, info, msg),
     }
 }
@@ -93,7 +137,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> V
 //----------------------------------------------------------------------------------------
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> VaultResult {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> VaultResult {
     let version: Version = CONTRACT_VERSION.parse()?;
     let storage_version: Version = get_contract_version(deps.storage)?.version.parse()?;
 
@@ -102,10 +146,66 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> VaultResult {
 
         // If state structure changed in any contract version in the way migration is needed, it
         // should occur here
+        migrate_pools(deps.storage)?;
+        migrate_state(deps, msg)?;
     }
     Ok(Response::default())
 }
 
+/// Adds the treasury, the minimum profit and the profit split to the state of the first version
+fn migrate_state(deps: DepsMut, msg: MigrateMsg) -> Result<(), StableArbError> {
+    // The current state also deserializes as the first version state, so it is checked first
+    if STATE.load(deps.storage).is_ok() {
+        return Ok(());
+    }
+    let state_v0 = STATE_V0.load(deps.storage)?;
+    let treasury_address = msg
+        .treasury_address
+        .ok_or(StableArbError::MissingMigrateParam {
+            param: "treasury_address".to_string(),
+        })?;
+    // The first version sent the whole profit to the treasury
+    let profit_split = msg.profit_split.unwrap_or(ProfitSplit {
+        caller_bps: 0,
+        treasury_bps: BPS_DENOMINATOR,
+        vault_bps: 0,
+    });
+    validate_profit_split(&profit_split)?;
+
+    STATE.save(
+        deps.storage,
+        &State {
+            vault_address: state_v0.vault_address,
+            seignorage_address: state_v0.seignorage_address,
+            treasury_address: validate_addr(deps.api, "treasury_address", &treasury_address)?,
+            min_profit: msg.min_profit.unwrap_or_default(),
+            profit_split,
+        },
+    )?;
+    Ok(())
+}
+
+/// Moves the pools into the registry, they were all terraswap pools
+fn migrate_pools(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy_pools = LEGACY_POOLS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Vec<u8>, Addr)>>>()?;
+
+    for (key, address) in legacy_pools {
+        let name = String::from_utf8(key)?;
+        POOLS.save(
+            storage,
+            name.as_str(),
+            &Pool {
+                address,
+                dex: Dex::Terraswap,
+            },
+        )?;
+        LEGACY_POOLS.remove(storage, name.as_str());
+    }
+    Ok(())
+}
+
 //----------------------------------------------------------------------------------------
 //  PRIVATE FUNCTIONS
 //----------------------------------------------------------------------------------------
@@ -115,9 +215,11 @@ fn _handle_callback(deps: DepsMut, env: Env, info: MessageInfo, msg: CallbackMsg
     if info.sender != env.contract.address {
         return Err(StableArbError::NotCallback {});
     }
+    // Possibility to add more callbacks in future.
     match msg {
-        CallbackMsg::AfterSuccessfulTradeCallback {} => after_successful_trade_callback(deps, env),
-        // Possibility to add more callbacks in future.
+        CallbackMsg::Repay { loan_amount } => {
+            after_successful_trade_callback(deps, env, loan_amount)
+        }
     }
 }
 //----------------------------------------------------------------------------------------
@@ -127,15 +229,39 @@ fn _handle_callback(deps: DepsMut, env: Env, info: MessageInfo, msg: CallbackMsg
 fn call_flashloan(
     deps: DepsMut,
     _env: Env,
-    _msg_info: MessageInfo,
-    details: ArbDetails,
+    msg_info: MessageInfo,
+    mut details: ArbDetails,
     above_peg: bool,
 ) -> VaultResult {
     let state = STATE.load(deps.storage)?;
     let deposit_info = ARB_BASE_ASSET.load(deps.storage)?;
 
-    // Check if requested asset is same as strategy base asset
-    deposit_info.assert(&details.asset.info)?;
+    // Check if requested asset is the strategy base asset and at least the minimum arb amount
+    deposit_info.assert_deposit(&details.asset)?;
+
+    let denom = match &deposit_info.asset_info {
+        // Eventually Can be expanded for CW20s, just remove the Err and replace with LOGIC
+        AssetInfo::Token { .. } => return Err(StableArbError::Unauthorized {}),
+        AssetInfo::NativeToken { denom } => denom.clone(),
+    };
+    let market_denom = load_market_denom(deps.storage)?;
+    if denom == market_denom {
+        return Err(StableArbError::UnsupportedAsset {});
+    }
+
+    // Fix the pool the callback trades on
+    let pool_name = select_pool(
+        deps.as_ref(),
+        &details.pool_name,
+        details.asset.amount,
+        above_peg,
+        &denom,
+        &market_denom,
+    )?;
+    details.pool_name = Some(pool_name.clone());
+
+    // Remember who gets the caller reward
+    ARB_CALLER.save(deps.storage, &msg_info.sender)?;
 
     // Construct callback msg
     let callback_msg = if above_peg {
@@ -148,46 +274,79 @@ fn call_flashloan(
         }
     };
 
-    // Construct payload
-    let payload = FlashLoanPayload {
-        requested_asset: details.asset,
-        callback: to_binary(&callback_msg)?,
-    };
+    // Both vaults lend through the same flash loan interface
+    let flashloan_msg = flash_loan_msg(
+        &state.vault_address,
+        details.asset,
+        to_binary(&callback_msg)?,
+    )?;
+
+    Ok(Response::new()
+        .add_attributes(
+            WhaleEvent::new(PEG_ARB, "execute_arb")
+                .add_attribute("pool", pool_name)
+                .add_attribute("caller", msg_info.sender.as_str()),
+        )
+        .add_message(flashloan_msg))
+}
 
-    let supported_assets = vec![LUNA_DENOM.to_string(), UST_DENOM.to_string()];
+/// Returns `pool_name` if it's registered, otherwise the registered pool with the best quote.
+/// Above peg the pool buys the market denom with the borrowed coin, below peg it sells the market
+/// denom bought on the market.
+fn select_pool(
+    deps: Deps,
+    pool_name: &Option<String>,
+    amount: Uint128,
+    above_peg: bool,
+    denom: &str,
+    market_denom: &str,
+) -> Result<String, StableArbError> {
+    if let Some(pool_name) = pool_name {
+        load_pool(deps, pool_name)?;
+        return Ok(pool_name.clone());
+    }
 
-    match &deposit_info.asset_info {
-        // Eventually Can be expanded for CW20s, just remove the Err and replace with LOGIC
-        AssetInfo::Token { .. } => return Err(StableArbError::Unauthorized {}),
-        AssetInfo::NativeToken { denom } => {
-            if !supported_assets.contains(denom) {
-                return Err(StableArbError::UnsupportedAsset {});
-            }
-            match denom {
-                LUNA_DENOM => {
-                    return Ok(
-                        Response::new().add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                            contract_addr: state.vault_address.to_string(),
-                            msg: to_binary(&LunaVaultMsg::FlashLoan {
-                                payload: payload.clone(),
-                            })?,
-                            funds: vec![],
-                        })),
-                    );
-                }
-                UST_DENOM => {
-                    return Ok(
-                        Response::new().add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                            contract_addr: state.vault_address.to_string(),
-                            msg: to_binary(&VaultMsg::FlashLoan { payload })?,
-                            funds: vec![],
-                        })),
-                    )
-                }
-                _ => return Err(StableArbError::UnsupportedAsset {}),
-            };
+    let lent_coin = deduct_tax(deps, Coin::new(amount.u128(), denom))?;
+    let offer_coin = if above_peg {
+        lent_coin
+    } else {
+        let luna_amount = query_market_price(deps, lent_coin, market_denom.to_string())?;
+        Coin::new(luna_amount.u128(), market_denom)
+    };
+
+    let pools = POOLS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (name, pool) = item?;
+            Ok((String::from_utf8(name)?, pool))
+        })
+        .collect::<StdResult<Vec<(String, Pool)>>>()?;
+
+    // Pools that fail to quote, e.g. because they don't trade the offered coin, are skipped
+    let mut best: Option<(String, Uint128)> = None;
+    for (name, pool) in pools {
+        let quote = match simulate_native_swap(deps, &pool.dex, pool.address, offer_coin.clone()) {
+            Ok(quote) => quote,
+            Err(_) => continue,
+        };
+        if best
+            .as_ref()
+            .map_or(true, |(_, best_quote)| quote > *best_quote)
+        {
+            best = Some((name, quote));
         }
     }
+
+    best.map(|(name, _)| name)
+        .ok_or(StableArbError::NoPoolAvailable {})
+}
+
+fn load_pool(deps: Deps, pool_name: &str) -> Result<Pool, StableArbError> {
+    POOLS
+        .may_load(deps.storage, pool_name)?
+        .ok_or_else(|| StableArbError::UnknownPool {
+            pool: pool_name.to_string(),
+        })
 }
 
 // Attempt to perform an arbitrage operation with the assumption that
@@ -209,13 +368,19 @@ pub fn try_arb_below_peg(
     }
 
     // Set vars
-    let pool_address = POOLS.load(deps.storage, &details.pool_name)?;
+    let pool = load_pool(
+        deps.as_ref(),
+        details
+            .pool_name
+            .as_ref()
+            .ok_or(StableArbError::NoPoolAvailable {})?,
+    )?;
     let denom = deposit_info.get_denom()?;
     let lent_coin = deduct_tax(
         deps.as_ref(),
         Coin::new(details.asset.amount.u128(), denom.clone()),
     )?;
-    let ask_denom = LUNA_DENOM.to_string();
+    let ask_denom = load_market_denom(deps.storage)?;
     let response: Response<TerraMsgWrapper> = Response::new();
 
     // Check if we have enough funds
@@ -238,29 +403,25 @@ pub fn try_arb_below_peg(
     // Market swap msg, swap STABLE -> LUNA
     let swap_msg = create_swap_msg(lent_coin.clone(), ask_denom);
 
-    // Terraswap msg, swap LUNA -> STABLE
-    let terraswap_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: pool_address.to_string(),
-        funds: vec![offer_coin.clone()],
-        msg: to_binary(&create_terraswap_msg(
-            offer_coin,
-            details.belief_price,
-            Some(details.slippage),
-        ))?,
-    });
+    // Pool msg, swap LUNA -> STABLE
+    let terraswap_msg: CosmosMsg<TerraMsgWrapper> =
+        Swap::native(pool.dex, pool.address, offer_coin)
+            .belief_price(Some(details.belief_price))
+            .max_spread(Some(details.slippage))
+            .into_msg()?;
 
-    let logs = vec![
-        ("action", String::from("arb below peg")),
-        ("offer_amount", lent_coin.amount.to_string()),
-        ("expected_luna", expected_luna_received.to_string()),
-    ];
+    let event = WhaleEvent::new(PEG_ARB, "arb_below_peg")
+        .add_attribute("offer_amount", lent_coin.amount.to_string())
+        .add_attribute("expected_luna", expected_luna_received.to_string());
 
     // Create callback, this will send the funds back to the vault.
-    let callback_msg =
-        CallbackMsg::AfterSuccessfulTradeCallback {}.to_cosmos_msg(&env.contract.address)?;
+    let callback_msg = CallbackMsg::Repay {
+        loan_amount: details.asset.amount,
+    }
+    .to_cosmos_msg(&env.contract.address)?;
 
     Ok(response
-        .add_attributes(logs)
+        .add_attributes(event)
         .add_message(swap_msg)
         .add_message(terraswap_msg)
         .add_message(callback_msg))
@@ -284,13 +445,19 @@ pub fn try_arb_above_peg(
     }
 
     // Set vars
-    let pool_address = POOLS.load(deps.storage, &details.pool_name)?;
+    let pool = load_pool(
+        deps.as_ref(),
+        details
+            .pool_name
+            .as_ref()
+            .ok_or(StableArbError::NoPoolAvailable {})?,
+    )?;
     let denom = deposit_info.get_denom()?;
     let lent_coin = deduct_tax(
         deps.as_ref(),
         Coin::new(details.asset.amount.u128(), denom.clone()),
     )?;
-    let ask_denom = LUNA_DENOM.to_string();
+    let ask_denom = load_market_denom(deps.storage)?;
     let response: Response<TerraMsgWrapper> = Response::new();
 
     // Check if we have enough funds
@@ -298,9 +465,13 @@ pub fn try_arb_above_peg(
     if balance < details.asset.amount {
         return Err(StableArbError::Broke {});
     }
-    // Simulate first tx with Terraswap
-    let expected_luna_received =
-        simulate_terraswap_swap(deps.as_ref(), pool_address.clone(), lent_coin.clone())?;
+    // Simulate first tx on the pool
+    let expected_luna_received = simulate_native_swap(
+        deps.as_ref(),
+        &pool.dex,
+        pool.address.clone(),
+        lent_coin.clone(),
+    )?;
 
     // Construct offer for Market Swap
     let offer_coin = Coin {
@@ -308,32 +479,28 @@ pub fn try_arb_above_peg(
         amount: expected_luna_received,
     };
 
-    // Terraswap msg, swap STABLE -> LUNA
-    let terraswap_msg: CosmosMsg<TerraMsgWrapper> = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: pool_address.to_string(),
-        funds: vec![lent_coin.clone()],
-        msg: to_binary(&create_terraswap_msg(
-            lent_coin.clone(),
-            details.belief_price,
-            Some(details.slippage),
-        ))?,
-    });
+    // Pool msg, swap STABLE -> LUNA
+    let terraswap_msg: CosmosMsg<TerraMsgWrapper> =
+        Swap::native(pool.dex, pool.address, lent_coin.clone())
+            .belief_price(Some(details.belief_price))
+            .max_spread(Some(details.slippage))
+            .into_msg()?;
 
     // Market swap msg, swap LUNA -> STABLE
     let swap_msg = create_swap_msg(offer_coin, lent_coin.denom);
 
-    let logs = vec![
-        ("action", String::from("arb above peg")),
-        ("offer_amount", lent_coin.amount.to_string()),
-        ("expected_luna", expected_luna_received.to_string()),
-    ];
+    let event = WhaleEvent::new(PEG_ARB, "arb_above_peg")
+        .add_attribute("offer_amount", lent_coin.amount.to_string())
+        .add_attribute("expected_luna", expected_luna_received.to_string());
 
     // Create callback, this will send the funds back to the vault.
-    let callback_msg =
-        CallbackMsg::AfterSuccessfulTradeCallback {}.to_cosmos_msg(&env.contract.address)?;
+    let callback_msg = CallbackMsg::Repay {
+        loan_amount: details.asset.amount,
+    }
+    .to_cosmos_msg(&env.contract.address)?;
 
     Ok(response
-        .add_attributes(logs)
+        .add_attributes(event)
         .add_message(terraswap_msg)
         .add_message(swap_msg)
         .add_message(callback_msg))
@@ -343,50 +510,143 @@ pub fn try_arb_above_peg(
 //  CALLBACK FUNCTION HANDLERS
 //----------------------------------------------------------------------------------------
 
-// After the arb this function returns the funds to the vault.
-fn after_successful_trade_callback(deps: DepsMut, env: Env) -> VaultResult {
+// After the arb this function repays the loan plus fee to the vault and splits the profit
+// between the caller, the treasury and the vault.
+fn after_successful_trade_callback(deps: DepsMut, env: Env, loan_amount: Uint128) -> VaultResult {
     let state = STATE.load(deps.storage)?;
-    let stable_denom = ARB_BASE_ASSET.load(deps.storage)?.get_denom()?;
-    let stables_in_contract =
-        query_balance(&deps.querier, env.contract.address, stable_denom.clone())?;
-
-    // Send asset back to vault
-    let repay_asset = Asset {
-        info: AssetInfo::NativeToken {
-            denom: stable_denom,
-        },
-        amount: stables_in_contract,
-    };
+    let denom = ARB_BASE_ASSET.load(deps.storage)?.get_denom()?;
+    let balance = query_balance(&deps.querier, env.contract.address, denom.clone())?;
+
+    // The vault has to receive the loan plus fee, the transfer tax is paid on top
+    let loan_fee = query_vault_fees(deps.as_ref(), &state.vault_address)?
+        .flash_loan_fee
+        .compute(loan_amount);
+    let repay_coin = Coin::new((loan_amount + loan_fee).u128(), denom.clone());
+    let repay_amount = repay_coin.amount + query_tax_on_top(deps.as_ref(), &repay_coin)?;
+
+    // Cancel the arb if it doesn't make the minimum profit
+    let profit = balance.saturating_sub(repay_amount);
+    if balance < repay_amount || profit < state.min_profit {
+        return Err(StableArbError::ProfitTooLow {
+            profit,
+            min_profit: state.min_profit,
+        });
+    }
+
+    // The caller of the arb is compensated from the profit
+    let caller = ARB_CALLER.may_load(deps.storage)?;
+    ARB_CALLER.remove(deps.storage);
+    let (caller_reward, treasury_profit, vault_profit) =
+        split_profit(&state.profit_split, profit, caller.is_some());
+
+    let mut stats = ARB_STATS.may_load(deps.storage)?.unwrap_or_default();
+    stats.arb_count += 1;
+    stats.volume += loan_amount;
+    stats.profit += profit;
+    stats.caller_rewards += caller_reward;
+    ARB_STATS.save(deps.storage, &stats)?;
+
+    let mut response = Response::new()
+        .add_attributes(
+            WhaleEvent::new(PEG_ARB, "arb_repayment")
+                .add_attribute("loan_amount", loan_amount.to_string())
+                .add_attribute("loan_fee", loan_fee.to_string())
+                .add_attribute("profit", profit.to_string())
+                .add_attribute("caller_reward", caller_reward.to_string())
+                .add_attribute("treasury_profit", treasury_profit.to_string())
+                .add_attribute("vault_profit", vault_profit.to_string()),
+        )
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: state.vault_address.to_string(),
+            amount: vec![repay_coin],
+        }));
+
+    // Send each share of the profit, the receiver pays the transfer tax
+    let mut shares = vec![(state.treasury_address, treasury_profit)];
+    if let Some(caller) = caller {
+        shares.insert(0, (caller, caller_reward));
+    }
+    shares.push((state.vault_address, vault_profit));
+    for (receiver, amount) in shares.into_iter() {
+        if !amount.is_zero() {
+            response = response.add_message(CosmosMsg::Bank(BankMsg::Send {
+                to_address: receiver.to_string(),
+                amount: vec![deduct_tax(
+                    deps.as_ref(),
+                    Coin::new(amount.u128(), denom.clone()),
+                )?],
+            }));
+        }
+    }
+    Ok(response)
+}
+
+/// Fails if the shares of the profit split don't sum up to 100%
+fn validate_profit_split(profit_split: &ProfitSplit) -> Result<(), StableArbError> {
+    let total = profit_split
+        .caller_bps
+        .checked_add(profit_split.treasury_bps)
+        .and_then(|total| total.checked_add(profit_split.vault_bps));
+    if total != Some(BPS_DENOMINATOR) {
+        return Err(StableArbError::InvalidProfitSplit {});
+    }
+    Ok(())
+}
 
-    Ok(Response::new().add_message(CosmosMsg::Bank(BankMsg::Send {
-        to_address: state.vault_address.to_string(),
-        amount: vec![repay_asset.deduct_tax(&deps.querier)?],
-    })))
+/// Splits `profit` into the caller reward, the treasury profit and the vault profit. The treasury
+/// gets the rounding remainder and the caller share if there is no caller.
+fn split_profit(
+    profit_split: &ProfitSplit,
+    profit: Uint128,
+    has_caller: bool,
+) -> (Uint128, Uint128, Uint128) {
+    let caller_reward = if has_caller {
+        profit.multiply_ratio(profit_split.caller_bps, BPS_DENOMINATOR)
+    } else {
+        Uint128::zero()
+    };
+    let vault_profit = profit.multiply_ratio(profit_split.vault_bps, BPS_DENOMINATOR);
+    (
+        caller_reward,
+        profit - caller_reward - vault_profit,
+        vault_profit,
+    )
 }
 
 pub fn update_pools(
     deps: DepsMut,
-    to_add: Option<Vec<(String, String)>>,
+    msg_info: MessageInfo,
+    to_add: Option<Vec<(String, PoolConfig)>>,
     to_remove: Option<Vec<String>>,
 ) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
     if let Some(pools_to_add) = to_add {
         if POOLS
             .keys(deps.storage, None, None, Order::Ascending)
             .count()
-            >= LIST_SIZE_LIMIT
+            + pools_to_add.len()
+            > LIST_SIZE_LIMIT
         {
             return Err(StableArbError::PoolLimitReached {});
         }
 
-        for (name, new_address) in pools_to_add.into_iter() {
+        for (name, config) in pools_to_add.into_iter() {
             if name.is_empty() {
                 return Err(StableArbError::EmptyPoolName {});
             };
+            if POOLS.may_load(deps.storage, name.as_str())?.is_some() {
+                return Err(StableArbError::PoolAlreadyExists { pool: name });
+            }
             // validate addr
             POOLS.save(
                 deps.storage,
                 name.as_str(),
-                &deps.api.addr_validate(&new_address)?,
+                &Pool {
+                    address: deps.api.addr_validate(&config.address)?,
+                    dex: config.dex,
+                },
             )?;
         }
     }
@@ -397,7 +657,7 @@ pub fn update_pools(
         }
     }
 
-    Ok(Response::new().add_attribute("action", "update pool addresses"))
+    Ok(WhaleEvent::new(PEG_ARB, "update_pools").into_response())
 }
 
 //----------------------------------------------------------------------------------------
@@ -412,12 +672,80 @@ pub fn set_vault_addr(deps: DepsMut, msg_info: MessageInfo, vault_address: Strin
     // Get the old vault
     let previous_vault = state.vault_address.to_string();
     // Store the new vault addr
-    state.vault_address = deps.api.addr_validate(&vault_address)?;
+    state.vault_address = validate_addr(deps.api, "vault_address", &vault_address)?;
     STATE.save(deps.storage, &state)?;
     // Respond and note the previous vault address
-    Ok(Response::new()
-        .add_attribute("new vault", vault_address)
-        .add_attribute("previous vault", previous_vault))
+    Ok(WhaleEvent::new(PEG_ARB, "set_vault")
+        .add_attribute("new_vault", vault_address)
+        .add_attribute("previous_vault", previous_vault)
+        .into_response())
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    treasury_address: Option<String>,
+    min_profit: Option<Uint128>,
+    profit_split: Option<ProfitSplit>,
+    min_arb_amount: Option<Uint128>,
+) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let mut state = STATE.load(deps.storage)?;
+    if let Some(treasury_address) = treasury_address {
+        state.treasury_address = validate_addr(deps.api, "treasury_address", &treasury_address)?;
+    }
+    if let Some(min_profit) = min_profit {
+        state.min_profit = min_profit;
+    }
+    if let Some(profit_split) = profit_split {
+        validate_profit_split(&profit_split)?;
+        state.profit_split = profit_split;
+    }
+    STATE.save(deps.storage, &state)?;
+    if let Some(min_arb_amount) = min_arb_amount {
+        ARB_BASE_ASSET.update(deps.storage, |mut base_asset| -> StdResult<_> {
+            base_asset.min_deposit = min_arb_amount;
+            Ok(base_asset)
+        })?;
+    }
+
+    Ok(WhaleEvent::new(PEG_ARB, "update_config").into_response())
+}
+
+/// Switches the arb to another native denom and the denom it is swapped with on the market
+pub fn set_arb_asset(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    denom: String,
+    market_denom: Option<String>,
+    min_arb_amount: Uint128,
+    min_profit: Uint128,
+) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let market_denom = market_denom.unwrap_or_else(|| LUNA_DENOM.to_string());
+    if denom == market_denom {
+        return Err(StableArbError::UnsupportedAsset {});
+    }
+
+    let mut base_asset = ArbBaseAsset::new(AssetInfo::NativeToken {
+        denom: denom.clone(),
+    });
+    base_asset.min_deposit = min_arb_amount;
+    ARB_BASE_ASSET.save(deps.storage, &base_asset)?;
+    MARKET_DENOM.save(deps.storage, &market_denom)?;
+    STATE.update(deps.storage, |mut state| -> StdResult<_> {
+        state.min_profit = min_profit;
+        Ok(state)
+    })?;
+
+    Ok(WhaleEvent::new(PEG_ARB, "set_arb_asset")
+        .add_attribute("denom", denom)
+        .add_attribute("market_denom", market_denom)
+        .into_response())
 }
 
 //----------------------------------------------------------------------------------------
@@ -428,6 +756,18 @@ pub fn set_vault_addr(deps: DepsMut, msg_info: MessageInfo, vault_address: Strin
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&try_query_config(deps)?),
+        QueryMsg::State {} => to_binary(&try_query_state(deps)?),
+        QueryMsg::Pools {} => to_binary(&try_query_pools(deps)?),
+        QueryMsg::ArbStats {} => to_binary(&try_query_arb_stats(deps)?),
+        QueryMsg::SimulateArb {
+            amount,
+            above_peg,
+            pool,
+        } => to_binary(
+            &try_simulate_arb(deps, amount, above_peg, pool)
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+        QueryMsg::PausedScopes {} => to_binary(&query_paused_scopes(deps.storage)?),
     }
 }
 
@@ -435,3 +775,99 @@ pub fn try_query_config(deps: Deps) -> StdResult<ArbBaseAsset> {
     let info: ArbBaseAsset = ARB_BASE_ASSET.load(deps.storage)?;
     Ok(info)
 }
+
+pub fn try_query_state(deps: Deps) -> StdResult<StateResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(StateResponse {
+        vault_address: state.vault_address.to_string(),
+        seignorage_address: state.seignorage_address.to_string(),
+        treasury_address: state.treasury_address.to_string(),
+        min_profit: state.min_profit,
+        profit_split: state.profit_split,
+        market_denom: load_market_denom(deps.storage)?,
+    })
+}
+
+pub fn try_query_pools(deps: Deps) -> StdResult<PoolsResponse> {
+    let pools = POOLS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (name, pool) = item?;
+            Ok((
+                String::from_utf8(name)?,
+                PoolConfig {
+                    address: pool.address.to_string(),
+                    dex: pool.dex,
+                },
+            ))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(PoolsResponse { pools })
+}
+
+pub fn try_query_arb_stats(deps: Deps) -> StdResult<ArbStats> {
+    Ok(ARB_STATS.may_load(deps.storage)?.unwrap_or_default())
+}
+
+/// Prices an arb of `amount` the way ExecuteArb and the callbacks execute it
+pub fn try_simulate_arb(
+    deps: Deps,
+    amount: Uint128,
+    above_peg: bool,
+    pool: Option<String>,
+) -> Result<SimulateArbResponse, StableArbError> {
+    let state = STATE.load(deps.storage)?;
+    let base_asset = ARB_BASE_ASSET.load(deps.storage)?;
+    let min_arb_amount = base_asset.min_deposit;
+    let denom = base_asset.get_denom()?;
+    let market_denom = load_market_denom(deps.storage)?;
+    let pool_name = select_pool(deps, &pool, amount, above_peg, &denom, &market_denom)?;
+    let pool = load_pool(deps, &pool_name)?;
+
+    // The borrowed coin arrives in full, the transfer tax is paid when swapping it
+    let lent_coin = deduct_tax(deps, Coin::new(amount.u128(), denom.clone()))?;
+    let (luna_amount, return_amount) = if above_peg {
+        // STABLE -> LUNA on the pool, LUNA -> STABLE on the market
+        let luna_amount = simulate_native_swap(deps, &pool.dex, pool.address, lent_coin)?;
+        let return_amount = query_market_price(
+            deps,
+            Coin::new(luna_amount.u128(), market_denom.as_str()),
+            denom.clone(),
+        )?;
+        (luna_amount, return_amount)
+    } else {
+        // STABLE -> LUNA on the market, LUNA -> STABLE on the pool
+        let luna_amount = query_market_price(deps, lent_coin, market_denom.clone())?;
+        let return_amount = simulate_native_swap(
+            deps,
+            &pool.dex,
+            pool.address,
+            Coin::new(luna_amount.u128(), market_denom.as_str()),
+        )?;
+        (luna_amount, return_amount)
+    };
+
+    // Same repayment as in the callback after the trade
+    let loan_fee = query_vault_fees(deps, &state.vault_address)?
+        .flash_loan_fee
+        .compute(amount);
+    let repay_coin = Coin::new((amount + loan_fee).u128(), denom);
+    let repay_amount = repay_coin.amount + query_tax_on_top(deps, &repay_coin)?;
+    let profit = return_amount.saturating_sub(repay_amount);
+    let (caller_reward, treasury_profit, vault_profit) =
+        split_profit(&state.profit_split, profit, true);
+
+    Ok(SimulateArbResponse {
+        pool: pool_name,
+        luna_amount,
+        return_amount,
+        loan_fee,
+        profit,
+        caller_reward,
+        treasury_profit,
+        vault_profit,
+        executable: amount >= min_arb_amount
+            && return_amount >= repay_amount
+            && profit >= state.min_profit,
+    })
+}
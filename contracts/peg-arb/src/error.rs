@@ -1,16 +1,24 @@
 use thiserror::Error;
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use cw_controllers::AdminError;
+use white_whale::admin::AdminTransferError;
+use white_whale::error::CommonError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum StableArbError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
+    #[error("{0}")]
+    AdminTransfer(AdminTransferError),
+
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 
@@ -31,6 +39,30 @@ pub enum StableArbError {
 
     #[error("The pool list has reached its limit, can't store more contracts.")]
     PoolLimitReached {},
+
+    #[error("The contract is paused")]
+    Paused {},
+
+    #[error("The shares of the profit split have to sum up to 10000 basis points")]
+    InvalidProfitSplit {},
+
+    #[error("The pool {pool} is not registered")]
+    UnknownPool { pool: String },
+
+    #[error("The pool {pool} is already registered")]
+    PoolAlreadyExists { pool: String },
+
+    #[error("The migration requires {param} to be set")]
+    MissingMigrateParam { param: String },
+
+    #[error("None of the registered pools can quote the arb")]
+    NoPoolAvailable {},
+
+    #[error("The arb made a profit of {profit}, less than the minimum of {min_profit}")]
+    ProfitTooLow {
+        profit: Uint128,
+        min_profit: Uint128,
+    },
 }
 
 impl From<semver::Error> for StableArbError {
@@ -38,3 +70,13 @@ impl From<semver::Error> for StableArbError {
         Self::SemVer(err.to_string())
     }
 }
+
+impl From<AdminTransferError> for StableArbError {
+    fn from(err: AdminTransferError) -> Self {
+        match err {
+            AdminTransferError::Std(err) => Self::Std(err),
+            AdminTransferError::Admin(err) => Self::Admin(err),
+            err => Self::AdminTransfer(err),
+        }
+    }
+}
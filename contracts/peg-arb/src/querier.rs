@@ -1,6 +1,9 @@
-use cosmwasm_std::{Coin, Decimal, Deps, StdResult, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Deps, StdResult, Uint128};
 use terra_cosmwasm::TerraQuerier;
 
+use white_whale::fee::VaultFee;
+use white_whale::flashloan::msg::{fees_query, FeeResponse};
+
 pub fn from_micro(amount: Uint128) -> Decimal {
     Decimal::from_ratio(amount, Uint128::from(1000000u64))
 }
@@ -10,3 +13,17 @@ pub fn query_market_price(deps: Deps, offer_coin: Coin, ask_denom: String) -> St
     let response = querier.query_swap(offer_coin, ask_denom)?;
     Ok(response.receive.amount)
 }
+
+/// Queries the fees of the vault, every flash loan lender answers the same fee query
+pub fn query_vault_fees(deps: Deps, vault_address: &Addr) -> StdResult<VaultFee> {
+    let response: FeeResponse = deps.querier.query(&fees_query(vault_address)?)?;
+    Ok(response.fees)
+}
+
+/// Returns the tax paid on top of `coin` when sending it
+pub fn query_tax_on_top(deps: Deps, coin: &Coin) -> StdResult<Uint128> {
+    let querier = TerraQuerier::new(&deps.querier);
+    let tax_rate = querier.query_tax_rate()?.rate;
+    let tax_cap = querier.query_tax_cap(coin.denom.to_string())?.cap;
+    Ok(std::cmp::min(coin.amount * tax_rate, tax_cap))
+}
@@ -1,11 +1,13 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
 use cw_controllers::Admin;
 use cw_storage_plus::{Item, Map};
 
+use white_whale::denom::LUNA_DENOM;
 use white_whale::deposit_info::ArbBaseAsset;
+use white_whale::peg_arb::msg::{ArbStats, Dex, ProfitSplit};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 // The Arb State contains configuration options for the vault including
@@ -13,9 +15,41 @@ use white_whale::deposit_info::ArbBaseAsset;
 pub struct State {
     pub vault_address: Addr,
     pub seignorage_address: Addr,
+    pub treasury_address: Addr,
+    pub min_profit: Uint128,
+    pub profit_split: ProfitSplit,
+}
+
+// State stored by the first version, before the profits were sent to the treasury
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateV0 {
+    pub vault_address: Addr,
+    pub seignorage_address: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Pool {
+    pub address: Addr,
+    pub dex: Dex,
 }
 
 pub const ADMIN: Admin = Admin::new("admin");
 pub const STATE: Item<State> = Item::new("\u{0}{5}state");
 pub const ARB_BASE_ASSET: Item<ArbBaseAsset> = Item::new("\u{0}{7}deposit");
-pub const POOLS: Map<&str, Addr> = Map::new("pools");
+pub const POOLS: Map<&str, Pool> = Map::new("pool_registry");
+pub const ARB_STATS: Item<ArbStats> = Item::new("arb_stats");
+// Caller of the arb in progress, receives the caller reward
+pub const ARB_CALLER: Item<Addr> = Item::new("arb_caller");
+// Denom the arb denom is swapped with on the market module, LUNA_DENOM if not set
+pub const MARKET_DENOM: Item<String> = Item::new("market_denom");
+// State stored by the first version, replaced by STATE on migration
+pub const STATE_V0: Item<StateV0> = Item::new("\u{0}{5}state");
+// Pools stored before the registry kept track of the dex, moved to POOLS on migration
+pub const LEGACY_POOLS: Map<&str, Addr> = Map::new("pools");
+
+/// Returns the denom the arb denom is swapped with on the market module
+pub fn load_market_denom(storage: &dyn Storage) -> StdResult<String> {
+    Ok(MARKET_DENOM
+        .may_load(storage)?
+        .unwrap_or_else(|| LUNA_DENOM.to_string()))
+}
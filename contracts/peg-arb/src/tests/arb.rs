@@ -1,13 +1,17 @@
-use crate::tests::common::{POOL_NAME, TEST_CREATOR, VAULT_CONTRACT};
+use crate::tests::common::{
+    ASTROPORT_POOL, ASTROPORT_POOL_NAME, KEEPER, MIN_PROFIT, POOL_NAME, PROFIT_SPLIT, TEST_CREATOR,
+    TREASURY, VAULT_CONTRACT,
+};
 use crate::tests::instantiate::mock_instantiate;
 use crate::tests::mock_querier::mock_dependencies;
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{coins, CosmosMsg, Decimal, Uint128};
+use cosmwasm_std::{coins, from_binary, CosmosMsg, Decimal, Uint128};
 use terra_cosmwasm::TerraRoute;
 use terraswap::asset::{Asset, AssetInfo};
+use white_whale::error::CommonError;
 use white_whale::peg_arb::msg::*;
 
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, query};
 use crate::error::StableArbError;
 
 const OFFER_AMOUNT: u64 = 1000u64;
@@ -27,7 +31,7 @@ fn when_given_a_below_peg_msg_then_handle_returns_first_a_mint_then_a_terraswap_
                 denom: "uusd".to_string(),
             },
         },
-        pool_name: POOL_NAME.to_string(),
+        pool_name: Some(POOL_NAME.to_string()),
         slippage: Decimal::percent(1),
         belief_price: Decimal::percent(420),
     };
@@ -76,7 +80,7 @@ fn when_given_an_above_peg_msg_then_handle_returns_first_a_terraswap_then_a_mint
                 denom: "uusd".to_string(),
             },
         },
-        pool_name: POOL_NAME.to_string(),
+        pool_name: Some(POOL_NAME.to_string()),
         slippage: Decimal::percent(1),
         belief_price: Decimal::percent(420),
     };
@@ -111,7 +115,6 @@ fn when_given_an_above_peg_msg_then_handle_returns_first_a_terraswap_then_a_mint
     }
 }
 
-
 #[test]
 fn when_given_a_wrong_arb_complains() {
     let mut deps = mock_dependencies(&coins(100000000, "uusd"));
@@ -127,7 +130,7 @@ fn when_given_a_wrong_arb_complains() {
                 denom: "ukrt".to_string(),
             },
         },
-        pool_name: POOL_NAME.to_string(),
+        pool_name: Some(POOL_NAME.to_string()),
         slippage: Decimal::percent(1),
         belief_price: Decimal::percent(420),
     };
@@ -135,11 +138,11 @@ fn when_given_a_wrong_arb_complains() {
     // Prepare an AbovePegCallback msg
     let msg = ExecuteMsg::ExecuteArb {
         details: arb_detail,
-        above_peg: true
+        above_peg: true,
     };
 
-    // Ensure the 'caller' is the VAULT_CONTRACT to avoid unauthorized issues
-    let info = mock_info(VAULT_CONTRACT, &[]);
+    // Any keeper can execute an arb
+    let info = mock_info(KEEPER, &[]);
 
     let res = execute(deps.as_mut(), env, info, msg);
     match res {
@@ -148,6 +151,44 @@ fn when_given_a_wrong_arb_complains() {
     }
 }
 
+#[test]
+fn arb_below_min_arb_amount_complains() {
+    let mut deps = mock_dependencies(&coins(100000000, "uusd"));
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdateConfig {
+        treasury_address: None,
+        min_profit: None,
+        profit_split: None,
+        min_arb_amount: Some(Uint128::from(OFFER_AMOUNT + 1)),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::ExecuteArb {
+        details: ArbDetails {
+            asset: Asset {
+                amount: Uint128::from(OFFER_AMOUNT),
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+            },
+            pool_name: Some(POOL_NAME.to_string()),
+            slippage: Decimal::percent(1),
+            belief_price: Decimal::percent(420),
+        },
+        above_peg: false,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info(KEEPER, &[]), msg);
+    match res {
+        Err(StableArbError::Common(CommonError::InsufficientDeposit { required, provided })) => {
+            assert_eq!(required, Uint128::from(OFFER_AMOUNT + 1));
+            assert_eq!(provided, Uint128::from(OFFER_AMOUNT));
+        }
+        _ => panic!("Must return CommonError::InsufficientDeposit"),
+    }
+}
+
 #[test]
 fn peg_arb_can_support_luna_arb_with_differing_msgs() {
     let mut deps = mock_dependencies(&coins(100000000, "uluna"));
@@ -158,6 +199,9 @@ fn peg_arb_can_support_luna_arb_with_differing_msgs() {
         asset_info: AssetInfo::NativeToken {
             denom: "uluna".to_string(),
         },
+        treasury_address: TREASURY.to_string(),
+        profit_split: PROFIT_SPLIT,
+        min_profit: Uint128::from(MIN_PROFIT),
     };
 
     let info = mock_info(TEST_CREATOR, &[]);
@@ -165,7 +209,13 @@ fn peg_arb_can_support_luna_arb_with_differing_msgs() {
         .expect("contract successfully handles InstantiateMsg");
 
     let add_pool_msg = ExecuteMsg::UpdatePools {
-        to_add: Some(vec![(POOL_NAME.to_string(), "terraswap_pool".to_string())]),
+        to_add: Some(vec![(
+            POOL_NAME.to_string(),
+            PoolConfig {
+                address: "terraswap_pool".to_string(),
+                dex: Dex::Terraswap,
+            },
+        )]),
         to_remove: None,
     };
 
@@ -181,7 +231,7 @@ fn peg_arb_can_support_luna_arb_with_differing_msgs() {
                 denom: "uluna".to_string(),
             },
         },
-        pool_name: POOL_NAME.to_string(),
+        pool_name: Some(POOL_NAME.to_string()),
         slippage: Decimal::percent(1),
         belief_price: Decimal::percent(420),
     };
@@ -189,19 +239,17 @@ fn peg_arb_can_support_luna_arb_with_differing_msgs() {
     // Prepare an AbovePegCallback msg
     let msg = ExecuteMsg::ExecuteArb {
         details: arb_detail,
-        above_peg: true
+        above_peg: true,
     };
 
-    // Ensure the 'caller' is the VAULT_CONTRACT to avoid unauthorized issues
-    let info = mock_info(VAULT_CONTRACT, &[]);
+    // Any keeper can execute an arb
+    let info = mock_info(KEEPER, &[]);
 
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
     // We should have gotten 1 messages back in this case
     assert_eq!(1, res.messages.len());
-
 }
 
-
 #[test]
 fn peg_arb_can_support_below_or_above_peg_with_luna() {
     let mut deps = mock_dependencies(&coins(100000000, "uluna"));
@@ -212,6 +260,9 @@ fn peg_arb_can_support_below_or_above_peg_with_luna() {
         asset_info: AssetInfo::NativeToken {
             denom: "uluna".to_string(),
         },
+        treasury_address: TREASURY.to_string(),
+        profit_split: PROFIT_SPLIT,
+        min_profit: Uint128::from(MIN_PROFIT),
     };
 
     let info = mock_info(TEST_CREATOR, &[]);
@@ -219,7 +270,13 @@ fn peg_arb_can_support_below_or_above_peg_with_luna() {
         .expect("contract successfully handles InstantiateMsg");
 
     let add_pool_msg = ExecuteMsg::UpdatePools {
-        to_add: Some(vec![(POOL_NAME.to_string(), "terraswap_pool".to_string())]),
+        to_add: Some(vec![(
+            POOL_NAME.to_string(),
+            PoolConfig {
+                address: "terraswap_pool".to_string(),
+                dex: Dex::Terraswap,
+            },
+        )]),
         to_remove: None,
     };
 
@@ -235,7 +292,7 @@ fn peg_arb_can_support_below_or_above_peg_with_luna() {
                 denom: "uluna".to_string(),
             },
         },
-        pool_name: POOL_NAME.to_string(),
+        pool_name: Some(POOL_NAME.to_string()),
         slippage: Decimal::percent(1),
         belief_price: Decimal::percent(420),
     };
@@ -268,5 +325,85 @@ fn peg_arb_can_support_below_or_above_peg_with_luna() {
         CosmosMsg::Wasm(_wasm_msg) => panic!("unexpected"),
         _ => panic!("unexpected"),
     }
+}
+
+#[test]
+fn simulate_arb_below_peg() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = QueryMsg::SimulateArb {
+        amount: Uint128::from(OFFER_AMOUNT),
+        above_peg: false,
+        pool: Some(POOL_NAME.to_string()),
+    };
+    let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+    let simulation: SimulateArbResponse = from_binary(&res).unwrap();
+
+    // the market returns 1000 luna, which the pool sells for 1_000_000 uusd
+    // the loan of 1000 costs a fee of 5
+    assert_eq!(
+        simulation,
+        SimulateArbResponse {
+            pool: POOL_NAME.to_string(),
+            luna_amount: Uint128::from(1000u64),
+            return_amount: Uint128::from(1_000_000u64),
+            loan_fee: Uint128::from(5u64),
+            profit: Uint128::from(998_995u64),
+            caller_reward: Uint128::from(99_899u64),
+            treasury_profit: Uint128::from(899_096u64),
+            vault_profit: Uint128::zero(),
+            executable: true,
+        }
+    );
+}
+
+#[test]
+fn simulate_arb_below_peg_on_astroport() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdatePools {
+        to_add: Some(vec![(
+            ASTROPORT_POOL_NAME.to_string(),
+            PoolConfig {
+                address: ASTROPORT_POOL.to_string(),
+                dex: Dex::Astroport,
+            },
+        )]),
+        to_remove: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+
+    // the astroport pool is quoted with the astroport simulation query
+    let msg = QueryMsg::SimulateArb {
+        amount: Uint128::from(OFFER_AMOUNT),
+        above_peg: false,
+        pool: Some(ASTROPORT_POOL_NAME.to_string()),
+    };
+    let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+    let simulation: SimulateArbResponse = from_binary(&res).unwrap();
+
+    assert_eq!(simulation.pool, ASTROPORT_POOL_NAME.to_string());
+    assert_eq!(simulation.return_amount, Uint128::from(2_000_000u64));
+    assert_eq!(simulation.profit, Uint128::from(1_998_995u64));
+}
+
+#[test]
+fn simulate_arb_losing_trade() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    // the pool only returns 1_000_000 for a loan of 100_000_000
+    let msg = QueryMsg::SimulateArb {
+        amount: Uint128::from(100_000_000u64),
+        above_peg: false,
+        pool: None,
+    };
+    let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+    let simulation: SimulateArbResponse = from_binary(&res).unwrap();
 
+    assert_eq!(simulation.pool, POOL_NAME.to_string());
+    assert_eq!(simulation.profit, Uint128::zero());
+    assert!(!simulation.executable);
 }
@@ -1,14 +1,17 @@
 use std::str::FromStr;
 
-use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{attr, coins, from_binary, Addr, BankMsg, CosmosMsg, Decimal, Uint128};
 
-use crate::contract::execute;
+use crate::contract::{execute, query};
+use crate::state::ARB_CALLER;
 
 use terraswap::asset::{Asset, AssetInfo};
 
 use crate::error::StableArbError;
-use crate::tests::common::{POOL_NAME, TEST_CREATOR, VAULT_ASSET};
+use crate::tests::common::{
+    KEEPER, MIN_PROFIT, POOL_NAME, TEST_CREATOR, TREASURY, VAULT_ASSET, VAULT_CONTRACT,
+};
 use crate::tests::instantiate::mock_instantiate;
 use crate::tests::mock_querier::mock_dependencies;
 use white_whale::peg_arb::msg::*;
@@ -22,7 +25,9 @@ fn unsuccessful_self_callback() {
 
     let info = mock_info(TEST_CREATOR, &[]);
 
-    let msg = ExecuteMsg::Callback(CallbackMsg::AfterSuccessfulTradeCallback {});
+    let msg = ExecuteMsg::Callback(CallbackMsg::Repay {
+        loan_amount: Uint128::from(OFFER_AMOUNT),
+    });
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     match res {
@@ -47,7 +52,7 @@ fn unsuccessful_vault_callback() {
                 denom: VAULT_ASSET.to_string(),
             },
         },
-        pool_name: POOL_NAME.to_string(),
+        pool_name: Some(POOL_NAME.to_string()),
         slippage: Decimal::percent(1),
         belief_price: Decimal::from_str("420").unwrap(),
     };
@@ -64,3 +69,177 @@ fn unsuccessful_vault_callback() {
         Err(_) => panic!("Unknown Error"),
     }
 }
+
+#[test]
+fn successful_repay_and_send_profit() {
+    // loan of 100 UST which made 2 UST
+    let mut deps = mock_dependencies(&coins(102_000_000u128, VAULT_ASSET));
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let msg = ExecuteMsg::Callback(CallbackMsg::Repay {
+        loan_amount: Uint128::from(OFFER_AMOUNT),
+    });
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(2, res.messages.len());
+    // loan plus the 0.5% flash loan fee goes back to the vault
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: VAULT_CONTRACT.to_string(),
+            amount: coins(100_500_000u128, VAULT_ASSET),
+        })
+    );
+    // the rest is profit for the treasury
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: TREASURY.to_string(),
+            amount: coins(1_500_000u128, VAULT_ASSET),
+        })
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "arb_repayment"),
+            attr("module", "peg_arb"),
+            attr("loan_amount", OFFER_AMOUNT.to_string()),
+            attr("loan_fee", "500000"),
+            attr("profit", "1500000"),
+            attr("caller_reward", "0"),
+            attr("treasury_profit", "1500000"),
+            attr("vault_profit", "0"),
+        ]
+    );
+}
+
+#[test]
+fn unsuccessful_repay_profit_too_low() {
+    // the arb doesn't cover the flash loan fee plus the minimum profit
+    let mut deps = mock_dependencies(&coins(
+        100_500_000u128 + MIN_PROFIT as u128 - 1,
+        VAULT_ASSET,
+    ));
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let msg = ExecuteMsg::Callback(CallbackMsg::Repay {
+        loan_amount: Uint128::from(OFFER_AMOUNT),
+    });
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(StableArbError::ProfitTooLow { .. }) => (),
+        Err(_) => panic!("Unknown Error"),
+    }
+}
+
+#[test]
+fn successful_repay_with_caller_reward() {
+    // loan of 100 UST which made 2 UST
+    let mut deps = mock_dependencies(&coins(102_000_000u128, VAULT_ASSET));
+    mock_instantiate(deps.as_mut());
+    // the keeper executed the arb
+    ARB_CALLER
+        .save(deps.as_mut().storage, &Addr::unchecked(KEEPER))
+        .unwrap();
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let msg = ExecuteMsg::Callback(CallbackMsg::Repay {
+        loan_amount: Uint128::from(OFFER_AMOUNT),
+    });
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(3, res.messages.len());
+    // 10% of the profit goes to the keeper
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: KEEPER.to_string(),
+            amount: coins(150_000u128, VAULT_ASSET),
+        })
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: TREASURY.to_string(),
+            amount: coins(1_350_000u128, VAULT_ASSET),
+        })
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::ArbStats {}).unwrap();
+    let stats: ArbStats = from_binary(&res).unwrap();
+    assert_eq!(
+        stats,
+        ArbStats {
+            arb_count: 1,
+            volume: Uint128::from(OFFER_AMOUNT),
+            profit: Uint128::from(1_500_000u128),
+            caller_rewards: Uint128::from(150_000u128),
+        }
+    );
+}
+
+#[test]
+fn successful_repay_with_profit_split() {
+    // loan of 100 UST which made 2 UST
+    let mut deps = mock_dependencies(&coins(102_000_000u128, VAULT_ASSET));
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::UpdateConfig {
+        treasury_address: None,
+        min_profit: None,
+        profit_split: Some(ProfitSplit {
+            caller_bps: 1_000,
+            treasury_bps: 6_000,
+            vault_bps: 3_000,
+        }),
+        min_arb_amount: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+    ARB_CALLER
+        .save(deps.as_mut().storage, &Addr::unchecked(KEEPER))
+        .unwrap();
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let msg = ExecuteMsg::Callback(CallbackMsg::Repay {
+        loan_amount: Uint128::from(OFFER_AMOUNT),
+    });
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    // the repayment, then one transfer per share of the profit
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|msg| msg.msg.clone())
+            .collect::<Vec<CosmosMsg<_>>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: VAULT_CONTRACT.to_string(),
+                amount: coins(100_500_000u128, VAULT_ASSET),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: KEEPER.to_string(),
+                amount: coins(150_000u128, VAULT_ASSET),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: TREASURY.to_string(),
+                amount: coins(900_000u128, VAULT_ASSET),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: VAULT_CONTRACT.to_string(),
+                amount: coins(450_000u128, VAULT_ASSET),
+            }),
+        ]
+    );
+    assert_eq!(
+        res.attributes[4..],
+        [
+            attr("profit", "1500000"),
+            attr("caller_reward", "150000"),
+            attr("treasury_profit", "900000"),
+            attr("vault_profit", "450000"),
+        ]
+    );
+}
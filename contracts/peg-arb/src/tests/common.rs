@@ -1,4 +1,16 @@
+use white_whale::peg_arb::msg::ProfitSplit;
+
 pub(crate) const VAULT_CONTRACT: &str = "vault_contract_address";
 pub(crate) const TEST_CREATOR: &str = "creator";
 pub(crate) const VAULT_ASSET: &str = "uusd";
 pub(crate) const POOL_NAME: &str = "terraswap_luna_ust";
+pub(crate) const KEEPER: &str = "keeper";
+pub(crate) const TREASURY: &str = "treasury";
+pub(crate) const MIN_PROFIT: u64 = 1000u64;
+pub(crate) const PROFIT_SPLIT: ProfitSplit = ProfitSplit {
+    caller_bps: 1000,
+    treasury_bps: 9000,
+    vault_bps: 0,
+};
+pub(crate) const ASTROPORT_POOL_NAME: &str = "astroport_luna_ust";
+pub(crate) const ASTROPORT_POOL: &str = "astroport_pool";
@@ -1,17 +1,20 @@
 use std::str::FromStr;
 
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{from_binary, CosmosMsg, Decimal, Uint128, WasmMsg};
 
 use crate::contract::execute;
 
 use terraswap::asset::{Asset, AssetInfo};
 
 use crate::error::StableArbError;
-use crate::tests::common::{POOL_NAME, TEST_CREATOR, VAULT_ASSET};
+use crate::tests::common::{
+    ASTROPORT_POOL, ASTROPORT_POOL_NAME, KEEPER, POOL_NAME, TEST_CREATOR, VAULT_ASSET,
+};
 use crate::tests::instantiate::mock_instantiate;
 use crate::tests::mock_querier::mock_dependencies;
 use white_whale::peg_arb::msg::*;
+use white_whale::ust_vault::msg::ExecuteMsg as VaultMsg;
 
 const OFFER_AMOUNT: u64 = 100_000_000u64;
 
@@ -20,7 +23,7 @@ fn successfull_flashloan_call() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
 
-    let info = mock_info(TEST_CREATOR, &[]);
+    let info = mock_info(KEEPER, &[]);
     let arb_detail: ArbDetails = ArbDetails {
         asset: Asset {
             amount: Uint128::from(OFFER_AMOUNT),
@@ -28,7 +31,7 @@ fn successfull_flashloan_call() {
                 denom: VAULT_ASSET.to_string(),
             },
         },
-        pool_name: POOL_NAME.to_string(),
+        pool_name: Some(POOL_NAME.to_string()),
         slippage: Decimal::percent(1),
         belief_price: Decimal::from_str("420").unwrap(),
     };
@@ -47,8 +50,7 @@ fn unsuccessful_flashloan_call_wrong_denom() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
 
-    // update admin
-    let info = mock_info(TEST_CREATOR, &[]);
+    let info = mock_info(KEEPER, &[]);
     let arb_detail: ArbDetails = ArbDetails {
         asset: Asset {
             amount: Uint128::from(OFFER_AMOUNT),
@@ -56,7 +58,7 @@ fn unsuccessful_flashloan_call_wrong_denom() {
                 denom: "ukrt".to_string(),
             },
         },
-        pool_name: POOL_NAME.to_string(),
+        pool_name: Some(POOL_NAME.to_string()),
         slippage: Decimal::percent(1),
         belief_price: Decimal::from_str("420").unwrap(),
     };
@@ -74,3 +76,89 @@ fn unsuccessful_flashloan_call_wrong_denom() {
         Err(_) => panic!("Unknown Error"),
     }
 }
+
+#[test]
+fn successful_flashloan_call_selects_best_pool() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    // register a second pool which quotes better than the terraswap pool
+    let msg = ExecuteMsg::UpdatePools {
+        to_add: Some(vec![(
+            ASTROPORT_POOL_NAME.to_string(),
+            PoolConfig {
+                address: ASTROPORT_POOL.to_string(),
+                dex: Dex::Astroport,
+            },
+        )]),
+        to_remove: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+
+    let info = mock_info(KEEPER, &[]);
+    let arb_detail: ArbDetails = ArbDetails {
+        asset: Asset {
+            amount: Uint128::from(OFFER_AMOUNT),
+            info: AssetInfo::NativeToken {
+                denom: VAULT_ASSET.to_string(),
+            },
+        },
+        pool_name: None,
+        slippage: Decimal::percent(1),
+        belief_price: Decimal::from_str("420").unwrap(),
+    };
+
+    let msg = ExecuteMsg::ExecuteArb {
+        details: arb_detail,
+        above_peg: true,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(1, res.messages.len());
+
+    // the callback trades on the pool with the best quote
+    let payload = match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_binary(msg).unwrap() {
+            VaultMsg::FlashLoan { payload } => payload,
+            _ => panic!("unexpected"),
+        },
+        _ => panic!("unexpected"),
+    };
+    match from_binary(&payload.callback).unwrap() {
+        ExecuteMsg::AbovePegCallback { details } => {
+            assert_eq!(details.pool_name, Some(ASTROPORT_POOL_NAME.to_string()))
+        }
+        _ => panic!("unexpected"),
+    }
+}
+
+#[test]
+fn unsuccessful_flashloan_call_unknown_pool() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(KEEPER, &[]);
+    let arb_detail: ArbDetails = ArbDetails {
+        asset: Asset {
+            amount: Uint128::from(OFFER_AMOUNT),
+            info: AssetInfo::NativeToken {
+                denom: VAULT_ASSET.to_string(),
+            },
+        },
+        pool_name: Some("unknown_pool".to_string()),
+        slippage: Decimal::percent(1),
+        belief_price: Decimal::from_str("420").unwrap(),
+    };
+
+    let msg = ExecuteMsg::ExecuteArb {
+        details: arb_detail,
+        above_peg: true,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(StableArbError::UnknownPool { .. }) => (),
+        Err(_) => panic!("Unknown Error"),
+    }
+}
@@ -1,15 +1,18 @@
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::Api;
 use cosmwasm_std::DepsMut;
+use cosmwasm_std::{from_binary, Api, Uint128};
 
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, query};
+use crate::error::StableArbError;
 use crate::state::{State, ARB_BASE_ASSET, STATE};
 
 use terraswap::asset::AssetInfo;
 
 use white_whale::deposit_info::ArbBaseAsset;
+use white_whale::error::CommonError;
+use white_whale::memory::LIST_SIZE_LIMIT;
 
-use crate::tests::common::{TEST_CREATOR, VAULT_CONTRACT};
+use crate::tests::common::{MIN_PROFIT, PROFIT_SPLIT, TEST_CREATOR, TREASURY, VAULT_CONTRACT};
 use crate::tests::mock_querier::mock_dependencies;
 use white_whale::peg_arb::msg::*;
 
@@ -22,6 +25,9 @@ pub(crate) fn instantiate_msg() -> InstantiateMsg {
         asset_info: AssetInfo::NativeToken {
             denom: "uusd".to_string(),
         },
+        treasury_address: TREASURY.to_string(),
+        profit_split: PROFIT_SPLIT,
+        min_profit: Uint128::from(MIN_PROFIT),
     }
 }
 
@@ -35,6 +41,9 @@ pub fn mock_instantiate(mut deps: DepsMut) {
         asset_info: AssetInfo::NativeToken {
             denom: "uusd".to_string(),
         },
+        treasury_address: TREASURY.to_string(),
+        profit_split: PROFIT_SPLIT,
+        min_profit: Uint128::from(MIN_PROFIT),
     };
 
     let info = mock_info(TEST_CREATOR, &[]);
@@ -42,7 +51,13 @@ pub fn mock_instantiate(mut deps: DepsMut) {
         .expect("contract successfully handles InstantiateMsg");
 
     let add_pool_msg = ExecuteMsg::UpdatePools {
-        to_add: Some(vec![(POOL_NAME.to_string(), "terraswap_pool".to_string())]),
+        to_add: Some(vec![(
+            POOL_NAME.to_string(),
+            PoolConfig {
+                address: "terraswap_pool".to_string(),
+                dex: Dex::Terraswap,
+            },
+        )]),
         to_remove: None,
     };
 
@@ -67,6 +82,9 @@ fn successful_initialization() {
         State {
             vault_address: deps.api.addr_validate(&VAULT_CONTRACT).unwrap(),
             seignorage_address: deps.api.addr_validate(&"seignorage").unwrap(),
+            treasury_address: deps.api.addr_validate(&TREASURY).unwrap(),
+            min_profit: Uint128::from(MIN_PROFIT),
+            profit_split: PROFIT_SPLIT,
         }
     );
 
@@ -79,6 +97,34 @@ fn successful_initialization() {
     );
 }
 
+#[test]
+fn unsuccessful_initialization_invalid_address() {
+    let invalid_fields: Vec<(&str, fn(&mut InstantiateMsg))> = vec![
+        ("vault_address", |msg| msg.vault_address = "x".to_string()),
+        ("seignorage_address", |msg| {
+            msg.seignorage_address = "x".to_string()
+        }),
+        ("treasury_address", |msg| {
+            msg.treasury_address = "x".to_string()
+        }),
+    ];
+
+    for (field, invalidate) in invalid_fields {
+        let mut deps = mock_dependencies(&[]);
+        let mut msg = instantiate_msg();
+        invalidate(&mut msg);
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+        match res {
+            Err(StableArbError::Common(CommonError::InvalidAddress {
+                field: invalid_field,
+                ..
+            })) => assert_eq!(invalid_field, field),
+            _ => panic!("Must return CommonError::InvalidAddress for {}", field),
+        }
+    }
+}
+
 #[test]
 fn successful_set_admin() {
     let mut deps = mock_dependencies(&[]);
@@ -126,3 +172,209 @@ fn unsuccessful_set_vault() {
         Err(_) => (),
     }
 }
+
+#[test]
+fn successful_pools_query() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Pools {}).unwrap();
+    let pools: PoolsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pools.pools,
+        vec![(
+            POOL_NAME.to_string(),
+            PoolConfig {
+                address: "terraswap_pool".to_string(),
+                dex: Dex::Terraswap,
+            },
+        )]
+    );
+}
+
+#[test]
+fn unsuccessful_update_pools_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info("someone", &[]);
+    let msg = ExecuteMsg::UpdatePools {
+        to_add: None,
+        to_remove: Some(vec![POOL_NAME.to_string()]),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Ok(_) => panic!("caller is not admin, should error"),
+        Err(_) => (),
+    }
+}
+
+#[test]
+fn unsuccessful_update_pools_existing_pool() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    let msg = ExecuteMsg::UpdatePools {
+        to_add: Some(vec![(
+            POOL_NAME.to_string(),
+            PoolConfig {
+                address: "other_pool".to_string(),
+                dex: Dex::Astroport,
+            },
+        )]),
+        to_remove: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StableArbError::PoolAlreadyExists { pool }) => assert_eq!(pool, POOL_NAME),
+        _ => panic!("Must return StableArbError::PoolAlreadyExists"),
+    }
+}
+
+#[test]
+fn unsuccessful_update_pools_limit_reached() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    // one pool is already registered, so a full list of new pools exceeds the limit
+    let info = mock_info(TEST_CREATOR, &[]);
+    let msg = ExecuteMsg::UpdatePools {
+        to_add: Some(
+            (0..LIST_SIZE_LIMIT)
+                .map(|i| {
+                    (
+                        format!("pool_{}", i),
+                        PoolConfig {
+                            address: "terraswap_pool".to_string(),
+                            dex: Dex::Terraswap,
+                        },
+                    )
+                })
+                .collect(),
+        ),
+        to_remove: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StableArbError::PoolLimitReached {}) => (),
+        _ => panic!("Must return StableArbError::PoolLimitReached"),
+    }
+}
+
+#[test]
+fn unsuccessful_update_config_invalid_profit_split() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        treasury_address: None,
+        min_profit: None,
+        profit_split: Some(ProfitSplit {
+            caller_bps: 1_000,
+            treasury_bps: 8_000,
+            vault_bps: 1_001,
+        }),
+        min_arb_amount: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StableArbError::InvalidProfitSplit {}) => (),
+        _ => panic!("Must return StableArbError::InvalidProfitSplit"),
+    }
+}
+
+#[test]
+fn unsuccessful_initialization_invalid_profit_split() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.profit_split.caller_bps = 10_001;
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+    match res {
+        Err(StableArbError::InvalidProfitSplit {}) => (),
+        _ => panic!("Must return StableArbError::InvalidProfitSplit"),
+    }
+}
+
+#[test]
+fn unsuccessful_update_config_invalid_treasury_address() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        treasury_address: Some("x".to_string()),
+        min_profit: None,
+        profit_split: None,
+        min_arb_amount: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StableArbError::Common(CommonError::InvalidAddress { field, .. })) => {
+            assert_eq!(field, "treasury_address")
+        }
+        _ => panic!("Must return CommonError::InvalidAddress"),
+    }
+}
+
+#[test]
+fn successful_set_arb_asset() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::SetArbAsset {
+        denom: "ukrw".to_string(),
+        market_denom: None,
+        min_arb_amount: Uint128::from(1_000_000u128),
+        min_profit: Uint128::from(5_000u128),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("someone", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StableArbError::Admin(_)) => (),
+        _ => panic!("Must return StableArbError::Admin"),
+    }
+
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+    let base_asset: ArbBaseAsset = ARB_BASE_ASSET.load(&deps.storage).unwrap();
+    assert_eq!(
+        base_asset.asset_info,
+        AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        },
+    );
+    assert_eq!(base_asset.min_deposit, Uint128::from(1_000_000u128));
+    let res: StateResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(res.min_profit, Uint128::from(5_000u128));
+    assert_eq!(res.market_denom, "uluna");
+}
+
+#[test]
+fn unsuccessful_set_arb_asset_same_market_denom() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::SetArbAsset {
+        denom: "ukrw".to_string(),
+        market_denom: Some("ukrw".to_string()),
+        min_arb_amount: Uint128::zero(),
+        min_profit: Uint128::zero(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+    match res {
+        Err(StableArbError::UnsupportedAsset {}) => (),
+        _ => panic!("Must return StableArbError::UnsupportedAsset"),
+    }
+}
@@ -0,0 +1,162 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Api, DepsMut, Uint128};
+use cw2::set_contract_version;
+
+use terraswap::asset::AssetInfo;
+
+use crate::contract::{migrate, CONTRACT_NAME};
+use crate::error::StableArbError;
+use crate::state::{Pool, State, StateV0, ARB_BASE_ASSET, LEGACY_POOLS, POOLS, STATE, STATE_V0};
+use crate::tests::common::{POOL_NAME, PROFIT_SPLIT, TREASURY, VAULT_CONTRACT};
+use crate::tests::instantiate::mock_instantiate;
+use crate::tests::mock_querier::mock_dependencies;
+use white_whale::deposit_info::ArbBaseAsset;
+use white_whale::peg_arb::msg::{Dex, MigrateMsg, ProfitSplit};
+
+/**
+ * Mocks the storage of the first contract version.
+ */
+fn mock_state_v0(deps: DepsMut) {
+    set_contract_version(deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+    STATE_V0
+        .save(
+            deps.storage,
+            &StateV0 {
+                vault_address: deps.api.addr_validate(VAULT_CONTRACT).unwrap(),
+                seignorage_address: deps.api.addr_validate("seignorage").unwrap(),
+            },
+        )
+        .unwrap();
+    ARB_BASE_ASSET
+        .save(
+            deps.storage,
+            &ArbBaseAsset::new(AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            }),
+        )
+        .unwrap();
+    LEGACY_POOLS
+        .save(
+            deps.storage,
+            POOL_NAME,
+            &deps.api.addr_validate("terraswap_pool").unwrap(),
+        )
+        .unwrap();
+}
+
+#[test]
+fn successful_migrate_state_v0() {
+    let mut deps = mock_dependencies(&[]);
+    mock_state_v0(deps.as_mut());
+
+    let msg = MigrateMsg {
+        treasury_address: Some(TREASURY.to_string()),
+        min_profit: Some(Uint128::from(10u64)),
+        profit_split: None,
+    };
+    migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+    let state: State = STATE.load(&deps.storage).unwrap();
+    assert_eq!(
+        state,
+        State {
+            vault_address: deps.api.addr_validate(VAULT_CONTRACT).unwrap(),
+            seignorage_address: deps.api.addr_validate("seignorage").unwrap(),
+            treasury_address: deps.api.addr_validate(TREASURY).unwrap(),
+            min_profit: Uint128::from(10u64),
+            profit_split: ProfitSplit {
+                caller_bps: 0,
+                treasury_bps: 10_000,
+                vault_bps: 0,
+            },
+        }
+    );
+
+    assert_eq!(
+        POOLS.load(&deps.storage, POOL_NAME).unwrap(),
+        Pool {
+            address: deps.api.addr_validate("terraswap_pool").unwrap(),
+            dex: Dex::Terraswap,
+        }
+    );
+    assert_eq!(
+        LEGACY_POOLS.may_load(&deps.storage, POOL_NAME).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn successful_migrate_state_v0_with_profit_split() {
+    let mut deps = mock_dependencies(&[]);
+    mock_state_v0(deps.as_mut());
+
+    let msg = MigrateMsg {
+        treasury_address: Some(TREASURY.to_string()),
+        min_profit: None,
+        profit_split: Some(PROFIT_SPLIT),
+    };
+    migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+    let state: State = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.min_profit, Uint128::zero());
+    assert_eq!(state.profit_split, PROFIT_SPLIT);
+}
+
+#[test]
+fn unsuccessful_migrate_state_v0_invalid_profit_split() {
+    let mut deps = mock_dependencies(&[]);
+    mock_state_v0(deps.as_mut());
+
+    let msg = MigrateMsg {
+        treasury_address: Some(TREASURY.to_string()),
+        min_profit: None,
+        profit_split: Some(ProfitSplit {
+            caller_bps: 1000,
+            treasury_bps: 1000,
+            vault_bps: 0,
+        }),
+    };
+    let res = migrate(deps.as_mut(), mock_env(), msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(StableArbError::InvalidProfitSplit {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn unsuccessful_migrate_state_v0_without_treasury() {
+    let mut deps = mock_dependencies(&[]);
+    mock_state_v0(deps.as_mut());
+
+    let msg = MigrateMsg {
+        treasury_address: None,
+        min_profit: None,
+        profit_split: None,
+    };
+    let res = migrate(deps.as_mut(), mock_env(), msg);
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(StableArbError::MissingMigrateParam { param }) => {
+            assert_eq!(param, "treasury_address")
+        }
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn migrate_keeps_current_state() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let state: State = STATE.load(&deps.storage).unwrap();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+    let msg = MigrateMsg {
+        treasury_address: Some("new_treasury".to_string()),
+        min_profit: None,
+        profit_split: None,
+    };
+    migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+    assert_eq!(STATE.load(&deps.storage).unwrap(), state);
+}
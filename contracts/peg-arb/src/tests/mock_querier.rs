@@ -1,8 +1,8 @@
 #![allow(dead_code)]
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Api, Binary, Coin, ContractResult, Decimal, OwnedDeps,
-    Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+    from_binary, from_slice, to_binary, Addr, Api, Binary, Coin, ContractResult, Decimal,
+    OwnedDeps, Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
 use cosmwasm_storage::to_length_prefixed;
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
@@ -13,6 +13,13 @@ use terra_cosmwasm::{
 use terraswap::asset::{Asset, AssetInfo, AssetInfoRaw, PairInfo, PairInfoRaw};
 use terraswap::pair::PoolResponse;
 use terraswap::pair::SimulationResponse;
+use white_whale::astroport_helper::{
+    QueryMsg as AstroportQueryMsg, SimulationResponse as AstroportSimulationResponse,
+};
+use white_whale::fee::{Fee, VaultFee};
+use white_whale::ust_vault::msg::FeeResponse;
+
+use crate::tests::common::{ASTROPORT_POOL, TREASURY, VAULT_CONTRACT};
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
@@ -227,6 +234,39 @@ impl WasmMockQuerier {
                     }
 
                     return SystemResult::Ok(ContractResult::Ok(binary_response.unwrap()));
+                } else if contract_addr == ASTROPORT_POOL {
+                    // Only answers the astroport simulation, quoting better than the terraswap pool
+                    return match from_binary(msg) {
+                        Ok(AstroportQueryMsg::Simulation { .. }) => {
+                            let res = AstroportSimulationResponse {
+                                return_amount: Uint128::from(2000000u64),
+                                spread_amount: Uint128::zero(),
+                                commission_amount: Uint128::zero(),
+                            };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        _ => SystemResult::Err(SystemError::InvalidRequest {
+                            error: "not an astroport simulation".to_string(),
+                            request: msg.clone(),
+                        }),
+                    };
+                } else if contract_addr == VAULT_CONTRACT {
+                    // The vault only gets queried for its fees
+                    let res = FeeResponse {
+                        fees: VaultFee {
+                            flash_loan_fee: Fee {
+                                share: Decimal::permille(5),
+                            },
+                            treasury_fee: Fee {
+                                share: Decimal::percent(10),
+                            },
+                            commission_fee: Fee {
+                                share: Decimal::permille(8),
+                            },
+                            treasury_addr: Addr::unchecked(TREASURY),
+                        },
+                    };
+                    return SystemResult::Ok(ContractResult::from(to_binary(&res)));
                 } else {
                     match from_binary(&msg).unwrap() {
                         Cw20QueryMsg::Balance { address } => {
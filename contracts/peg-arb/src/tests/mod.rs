@@ -3,4 +3,5 @@ mod callback;
 mod common;
 mod flashloan;
 mod instantiate;
+mod migrate;
 mod mock_querier;
@@ -12,6 +12,7 @@ use terraswap::pair::Cw20HookMsg;
 use terraswap::querier::{query_balance, query_supply, query_token_balance};
 use terraswap::token::InstantiateMsg as TokenInstantiateMsg;
 
+use white_whale::address::validate_addr;
 use white_whale::anchor::{anchor_deposit_msg, anchor_withdraw_msg};
 use white_whale::deposit_info::DepositInfo;
 use white_whale::fee::{Fee, VaultFee};
@@ -39,13 +40,20 @@ type VaultResult = Result<Response, StableVaultError>;
 const CONTRACT_NAME: &str = "crates.io:stablecoin-vault";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Maximum of the flash loan, treasury and commission fee combined, in percent
+pub const MAX_TOTAL_FEE: u64 = 50;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateMsg) -> VaultResult {
     // Use CW2 to set the contract version, this is needed for migrations
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let state = State {
-        anchor_money_market_address: deps.api.addr_validate(&msg.anchor_money_market_address)?,
-        aust_address: deps.api.addr_validate(&msg.aust_address)?,
+        anchor_money_market_address: validate_addr(
+            deps.api,
+            "anchor_money_market_address",
+            &msg.anchor_money_market_address,
+        )?,
+        aust_address: validate_addr(deps.api, "aust_address", &msg.aust_address)?,
         whitelisted_contracts: vec![],
         allow_non_whitelisted: false,
     };
@@ -57,25 +65,21 @@ pub fn instantiate(deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateM
     if !msg.asset_info.is_native_token() {
         return Err(StableVaultError::NotNativeToken {});
     }
-    DEPOSIT_INFO.save(
-        deps.storage,
-        &DepositInfo {
-            asset_info: msg.asset_info.clone(),
-        },
-    )?;
+    DEPOSIT_INFO.save(deps.storage, &DepositInfo::new(msg.asset_info.clone()))?;
     // Setup the fees system with a fee and other contract addresses
     let fee_config = VaultFee {
-        flash_loan_fee: check_fee(Fee {
+        flash_loan_fee: Fee {
             share: msg.flash_loan_fee,
-        })?,
-        treasury_fee: check_fee(Fee {
+        },
+        treasury_fee: Fee {
             share: msg.treasury_fee,
-        })?,
-        commission_fee: check_fee(Fee {
+        },
+        commission_fee: Fee {
             share: msg.commission_fee,
-        })?,
-        treasury_addr: deps.api.addr_validate(&msg.treasury_addr)?,
+        },
+        treasury_addr: validate_addr(deps.api, "treasury_addr", &msg.treasury_addr)?,
     };
+    fee_config.validate(Decimal::percent(MAX_TOTAL_FEE))?;
 
     FEE.save(deps.storage, &fee_config)?;
 
@@ -750,11 +754,15 @@ pub fn update_state(
     let api = deps.api;
 
     if let Some(anchor_money_market_address) = anchor_money_market_address {
-        state.anchor_money_market_address = api.addr_validate(&anchor_money_market_address)?;
+        state.anchor_money_market_address = validate_addr(
+            api,
+            "anchor_money_market_address",
+            &anchor_money_market_address,
+        )?;
     }
 
     if let Some(aust_address) = aust_address {
-        state.aust_address = api.addr_validate(&aust_address)?;
+        state.aust_address = validate_addr(api, "aust_address", &aust_address)?;
     }
 
     if let Some(allow_non_whitelisted) = allow_non_whitelisted {
@@ -850,27 +858,20 @@ pub fn set_fee(
     let mut fee_config = FEE.load(deps.storage)?;
 
     if let Some(fee) = flash_loan_fee {
-        fee_config.flash_loan_fee = check_fee(fee)?;
+        fee_config.flash_loan_fee = fee;
     }
     if let Some(fee) = treasury_fee {
-        fee_config.treasury_fee = check_fee(fee)?;
+        fee_config.treasury_fee = fee;
     }
     if let Some(fee) = commission_fee {
-        fee_config.commission_fee = check_fee(fee)?;
+        fee_config.commission_fee = fee;
     }
+    fee_config.validate(Decimal::percent(MAX_TOTAL_FEE))?;
 
     FEE.save(deps.storage, &fee_config)?;
     Ok(Response::default())
 }
 
-/// Checks that the given [Fee] is valid, i.e. it's lower than 100%
-fn check_fee(fee: Fee) -> Result<Fee, StableVaultError> {
-    if fee.share >= Decimal::percent(100) {
-        return Err(StableVaultError::InvalidFee {});
-    }
-    Ok(fee)
-}
-
 //----------------------------------------------------------------------------------------
 //  QUERY HANDLERS
 //----------------------------------------------------------------------------------------
@@ -2,12 +2,17 @@ use thiserror::Error;
 
 use cosmwasm_std::{OverflowError, StdError};
 use cw_controllers::AdminError;
+use white_whale::error::CommonError;
+use white_whale::fee::FeeError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum StableVaultError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
@@ -17,6 +22,9 @@ pub enum StableVaultError {
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 
+    #[error("{0}")]
+    Fee(FeeError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -62,3 +70,12 @@ impl From<semver::Error> for StableVaultError {
         Self::SemVer(err.to_string())
     }
 }
+
+impl From<FeeError> for StableVaultError {
+    fn from(err: FeeError) -> Self {
+        match err {
+            FeeError::InvalidFee {} => Self::InvalidFee {},
+            err => Self::Fee(err),
+        }
+    }
+}
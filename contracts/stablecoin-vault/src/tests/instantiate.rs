@@ -5,6 +5,7 @@ use cw20::MinterResponse;
 use terraswap::asset::AssetInfo;
 use terraswap::token::InstantiateMsg as TokenInstantiateMsg;
 
+use white_whale::error::CommonError;
 use white_whale::fee::*;
 use white_whale::ust_vault::msg::VaultQueryMsg as QueryMsg;
 use white_whale::ust_vault::msg::*;
@@ -154,6 +155,32 @@ fn unsuccessful_initialization_invalid_asset() {
 /**
  * Tests updating the fees of the contract.
  */
+#[test]
+fn unsuccessful_initialization_invalid_address() {
+    let invalid_fields: Vec<(&str, fn(&mut InstantiateMsg))> = vec![
+        ("anchor_money_market_address", |msg| {
+            msg.anchor_money_market_address = "x".to_string()
+        }),
+        ("aust_address", |msg| msg.aust_address = "x".to_string()),
+        ("treasury_addr", |msg| msg.treasury_addr = "x".to_string()),
+    ];
+
+    for (field, invalidate) in invalid_fields {
+        let mut deps = mock_dependencies(&[]);
+        let mut msg = instantiate_msg();
+        invalidate(&mut msg);
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+        match res {
+            Err(StableVaultError::Common(CommonError::InvalidAddress {
+                field: invalid_field,
+                ..
+            })) => assert_eq!(invalid_field, field),
+            _ => panic!("Must return CommonError::InvalidAddress for {}", field),
+        }
+    }
+}
+
 #[test]
 fn successful_update_fee() {
     let mut deps = mock_dependencies(&[]);
@@ -1,6 +1,7 @@
 use cosmwasm_std::testing::mock_env;
 use cosmwasm_std::{Api, MessageInfo};
 
+use white_whale::error::CommonError;
 use white_whale::ust_vault::msg::ExecuteMsg;
 
 use crate::contract::execute;
@@ -66,3 +67,28 @@ fn successful_set_state() {
     );
     assert_eq!(new_state.allow_non_whitelisted, true);
 }
+
+#[test]
+fn unsuccessful_set_state_invalid_address() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdateState {
+        anchor_money_market_address: None,
+        aust_address: Some("x".to_string()),
+        allow_non_whitelisted: None,
+    };
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(TEST_CREATOR).unwrap(),
+        funds: vec![],
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StableVaultError::Common(CommonError::InvalidAddress { field, address })) => {
+            assert_eq!(field, "aust_address");
+            assert_eq!(address, "x");
+        }
+        _ => panic!("Must return CommonError::InvalidAddress"),
+    }
+}
@@ -1,11 +1,15 @@
 use cosmwasm_std::StdError;
 use thiserror::Error;
+use white_whale::error::CommonError;
 
 #[derive(Error, Debug)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
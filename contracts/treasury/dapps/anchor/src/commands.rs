@@ -1,16 +1,18 @@
-use cosmwasm_std::{Addr, Coin, CosmosMsg, Deps, Env, Fraction, MessageInfo, Response, StdError, Uint128};
+use cosmwasm_std::{Addr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Fraction, MessageInfo, Response, StdError, Uint128};
 use terraswap::asset::AssetInfo;
+use terraswap::querier::{query_balance, query_token_balance};
 
 use white_whale::anchor::{anchor_bluna_unbond_msg, anchor_deposit_msg, anchor_withdraw_msg, anchor_withdraw_unbonded_msg};
 use white_whale::denom::UST_DENOM;
 use white_whale::memory::{ANCHOR_BLUNA_HUB_ID, BLUNA_TOKEN_MEMORY_ID};
 use white_whale::query::anchor::query_aust_exchange_rate;
 use white_whale::treasury::dapp_base::common::{ANCHOR_MONEY_MARKET_ID, AUST_TOKEN_ID};
-use white_whale::treasury::dapp_base::error::BaseDAppError;
-use white_whale::treasury::dapp_base::state::BASESTATE;
-use white_whale::treasury::msg::send_to_treasury;
+use white_whale::treasury::dapp_base::state::{ADMIN, BASESTATE};
+use white_whale::treasury::helpers::trader_action;
 
-use crate::contract::AnchorResult;
+use crate::contract::{AnchorDApp, AnchorResult};
+use crate::error::AnchorError;
+use crate::state::LIQUID_RATIO;
 
 // Add the custom dapp-specific message commands here
 
@@ -24,11 +26,8 @@ pub fn handle_deposit_stable(
     msg_info: MessageInfo,
     ust_deposit_amount: Uint128,
 ) -> AnchorResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Check if caller is trader.
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {});
-    }
+    // Check if caller is trader
+    let state = AnchorDApp::assert_trader(deps, &msg_info)?;
 
     let treasury_address = &state.treasury_address;
 
@@ -46,7 +45,7 @@ pub fn handle_deposit_stable(
         Coin::new(ust_deposit_amount.u128(), UST_DENOM),
     )?;
     messages.push(deposit_msg);
-    Ok(Response::new().add_message(send_to_treasury(messages, treasury_address)?))
+    Ok(Response::new().add_message(trader_action(messages, treasury_address)?))
 }
 
 /// Constructs and forwards the anchor redeem_stable message for the treasury
@@ -59,11 +58,8 @@ pub fn handle_redeem_stable(
     info: MessageInfo,
     ust_to_withdraw: Uint128,
 ) -> AnchorResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Check if caller is trader.
-    if info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {});
-    }
+    // Check if caller is trader
+    let state = AnchorDApp::assert_trader(deps, &info)?;
 
     let treasury_address = &state.treasury_address;
 
@@ -80,16 +76,16 @@ pub fn handle_redeem_stable(
     let mut messages: Vec<CosmosMsg> = vec![];
 
     let aust_exchange_rate = query_aust_exchange_rate(env, deps, anchor_address.to_string())?;
+    let aust_per_ust = aust_exchange_rate
+        .inv()
+        .ok_or(AnchorError::ZeroExchangeRate {})?;
 
     // Prepare a deposit_msg using the provided info.
     // The anchor dapp will then use this message and pass it to the treasury for execution
-    let withdraw_msg = anchor_withdraw_msg(
-        aust_address,
-        anchor_address,
-        ust_to_withdraw * aust_exchange_rate.inv().unwrap(),
-    )?;
+    let withdraw_msg =
+        anchor_withdraw_msg(aust_address, anchor_address, ust_to_withdraw * aust_per_ust)?;
     messages.push(withdraw_msg);
-    Ok(Response::new().add_message(send_to_treasury(messages, treasury_address)?))
+    Ok(Response::new().add_message(trader_action(messages, treasury_address)?))
 }
 
 
@@ -103,11 +99,8 @@ pub fn handle_unbond(
     msg_info: MessageInfo,
     bluna_amount: Uint128,
 ) -> AnchorResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Check if caller is trader.
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {});
-    }
+    // Check if caller is trader
+    let state = AnchorDApp::assert_trader(deps, &msg_info)?;
 
     let treasury_address = &state.treasury_address;
 
@@ -121,7 +114,7 @@ pub fn handle_unbond(
         .memory
         .query_asset(deps, &String::from(BLUNA_TOKEN_MEMORY_ID))? {
         AssetInfo::Token { contract_addr } => Addr::unchecked(contract_addr),
-        _ => return Err(BaseDAppError::Std(StdError::generic_err("bLuna token asset identified as Native in memory.")))
+        _ => return Err(AnchorError::Std(StdError::generic_err("bLuna token asset identified as Native in memory.")))
     };
 
     let mut messages: Vec<CosmosMsg> = vec![];
@@ -133,7 +126,7 @@ pub fn handle_unbond(
         bluna_amount,
     )?;
     messages.push(unbond_msg);
-    Ok(Response::new().add_message(send_to_treasury(messages, treasury_address)?))
+    Ok(Response::new().add_message(trader_action(messages, treasury_address)?))
 }
 
 /// Constructs and forwards the anchor withdraw unbonded message for the treasury
@@ -145,11 +138,8 @@ pub fn handle_withdraw_unbonded(
     _env: Env,
     msg_info: MessageInfo,
 ) -> AnchorResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Check if caller is trader.
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {});
-    }
+    // Check if caller is trader
+    let state = AnchorDApp::assert_trader(deps, &msg_info)?;
 
     let treasury_address = &state.treasury_address;
 
@@ -163,5 +153,84 @@ pub fn handle_withdraw_unbonded(
     // The anchor dapp will then use this message and pass it to the treasury for execution
     let withdraw_unbonded_msg: CosmosMsg = anchor_withdraw_unbonded_msg(bluna_hub_address)?;
     messages.push(withdraw_unbonded_msg);
-    Ok(Response::new().add_message(send_to_treasury(messages, treasury_address)?))
+    Ok(Response::new().add_message(trader_action(messages, treasury_address)?))
+}
+
+/// Sets the share of the treasury UST that Rebalance keeps liquid
+pub fn set_liquid_ratio(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    liquid_ratio: Decimal,
+) -> AnchorResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    if liquid_ratio > Decimal::one() {
+        return Err(AnchorError::InvalidLiquidRatio {});
+    }
+    LIQUID_RATIO.save(deps.storage, &liquid_ratio)?;
+    Ok(Response::new().add_attribute("liquid_ratio", liquid_ratio.to_string()))
+}
+
+/// Deposits the treasury UST above the liquid target into Anchor or redeems aUST to refill it.
+/// The target is the liquid ratio of the total treasury UST, aUST included at the current exchange rate.
+/// Anyone can call this so a keeper can rebalance the treasury periodically.
+pub fn handle_rebalance(deps: Deps, env: Env) -> AnchorResult {
+    let liquid_ratio = LIQUID_RATIO
+        .may_load(deps.storage)?
+        .ok_or(AnchorError::NoLiquidRatio {})?;
+    let state = BASESTATE.load(deps.storage)?;
+    let treasury_address = &state.treasury_address;
+
+    // Get anchor money market and aUST addresses
+    let anchor_address = state
+        .memory
+        .query_contract(deps, &String::from(ANCHOR_MONEY_MARKET_ID))?;
+    let aust_address = state
+        .memory
+        .query_contract(deps, &String::from(AUST_TOKEN_ID))?;
+
+    let ust_balance = query_balance(
+        &deps.querier,
+        treasury_address.clone(),
+        UST_DENOM.to_string(),
+    )?;
+    let aust_balance = query_token_balance(
+        &deps.querier,
+        aust_address.clone(),
+        treasury_address.clone(),
+    )?;
+    let aust_exchange_rate = query_aust_exchange_rate(env, deps, anchor_address.to_string())?;
+
+    let total_ust = ust_balance + aust_balance * aust_exchange_rate;
+    let liquid_target = total_ust * liquid_ratio;
+
+    let response = Response::new().add_attribute("action", "rebalance");
+    if ust_balance > liquid_target {
+        let deposit_amount = ust_balance - liquid_target;
+        let deposit_msg: CosmosMsg = anchor_deposit_msg(
+            deps,
+            anchor_address,
+            Coin::new(deposit_amount.u128(), UST_DENOM),
+        )?;
+        return Ok(response
+            .add_attribute("deposited", deposit_amount)
+            .add_message(trader_action(vec![deposit_msg], treasury_address)?));
+    }
+
+    // Redeem the shortfall, limited to the aUST the treasury holds
+    let aust_amount = std::cmp::min(
+        (liquid_target - ust_balance)
+            * aust_exchange_rate
+                .inv()
+                .ok_or(AnchorError::ZeroExchangeRate {})?,
+        aust_balance,
+    );
+    if aust_amount.is_zero() {
+        return Ok(response.add_attribute("redeemed", Uint128::zero()));
+    }
+    let withdraw_msg = anchor_withdraw_msg(aust_address, anchor_address, aust_amount)?;
+    Ok(response
+        .add_attribute("redeemed", aust_amount)
+        .add_message(trader_action(vec![withdraw_msg], treasury_address)?))
 }
@@ -1,21 +1,22 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 
-use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
 
-use crate::commands;
 use crate::error::AnchorError;
+use crate::{commands, queries};
 use white_whale::dapps::anchor::msg::{ExecuteMsg, QueryMsg};
-use white_whale::memory::item::Memory;
-use white_whale::treasury::dapp_base::commands::{self as dapp_base_commands, handle_base_init};
-use white_whale::treasury::dapp_base::common::BaseDAppResult;
-use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::dapp::DAppBase;
 use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
-use white_whale::treasury::dapp_base::queries as dapp_base_queries;
-use white_whale::treasury::dapp_base::state::BASESTATE;
-use white_whale::treasury::dapp_base::state::{BaseState, ADMIN};
 
-pub type AnchorResult = Result<Response, BaseDAppError>;
+pub type AnchorResult = Result<Response, AnchorError>;
+
+pub struct AnchorDApp;
+impl DAppBase for AnchorDApp {
+    type Error = AnchorError;
+}
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -23,19 +24,16 @@ pub fn instantiate(
     _env: Env,
     info: MessageInfo,
     msg: BaseInstantiateMsg,
-) -> BaseDAppResult {
-    let base_state = handle_base_init(deps.as_ref(), msg)?;
-
-    BASESTATE.save(deps.storage, &base_state)?;
-    ADMIN.set(deps, Some(info.sender))?;
+) -> AnchorResult {
+    AnchorDApp::instantiate_base(deps, &info, msg)?;
 
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> BaseDAppResult {
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> AnchorResult {
     match msg {
-        ExecuteMsg::Base(message) => dapp_base_commands::handle_base_message(deps, info, message),
+        ExecuteMsg::Base(message) => AnchorDApp::execute_base(deps, env, info, message),
         // handle dapp-specific messages here
         // ExecuteMsg::Custom{} => commands::custom_command(),
         ExecuteMsg::DepositStable { deposit_amount } => {
@@ -50,14 +48,19 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> B
         ExecuteMsg::WithdrawUnbonded {} => {
             commands::handle_withdraw_unbonded(deps.as_ref(), env, info)
         }
+        ExecuteMsg::SetLiquidRatio { liquid_ratio } => {
+            commands::set_liquid_ratio(deps, info, liquid_ratio)
+        }
+        ExecuteMsg::Rebalance {} => commands::handle_rebalance(deps.as_ref(), env),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Base(message) => dapp_base_queries::handle_base_query(deps, message),
+        QueryMsg::Base(message) => AnchorDApp::query_base(deps, message),
         // handle dapp-specific queries here
         // QueryMsg::Custom{} => queries::custom_query(),
+        QueryMsg::LiquidRatio {} => to_binary(&queries::query_liquid_ratio(deps)?),
     }
 }
@@ -1,6 +1,7 @@
 use cosmwasm_std::StdError;
 use cw_controllers::AdminError;
 use thiserror::Error;
+use white_whale::error::CommonError;
 use white_whale::treasury::dapp_base::error::BaseDAppError;
 
 #[derive(Error, Debug, PartialEq)]
@@ -8,9 +9,21 @@ pub enum AnchorError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
     #[error("{0}")]
     BaseDAppError(#[from] BaseDAppError),
+
+    #[error("The liquid ratio must be between 0 and 1")]
+    InvalidLiquidRatio {},
+
+    #[error("No liquid ratio is set, the treasury UST can't be rebalanced")]
+    NoLiquidRatio {},
+
+    #[error("The aUST exchange rate is zero")]
+    ZeroExchangeRate {},
 }
@@ -1,6 +1,8 @@
 mod commands;
 pub mod contract;
 pub mod error;
+mod queries;
+pub mod state;
 
 // #[cfg(test)]
 // #[cfg(not(target_arch = "wasm32"))]
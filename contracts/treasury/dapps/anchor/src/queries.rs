@@ -0,0 +1,11 @@
+use cosmwasm_std::{Deps, StdResult};
+
+use white_whale::dapps::anchor::msg::LiquidRatioResponse;
+
+use crate::state::LIQUID_RATIO;
+
+pub fn query_liquid_ratio(deps: Deps) -> StdResult<LiquidRatioResponse> {
+    Ok(LiquidRatioResponse {
+        liquid_ratio: LIQUID_RATIO.may_load(deps.storage)?,
+    })
+}
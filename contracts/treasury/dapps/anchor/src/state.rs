@@ -0,0 +1,5 @@
+use cosmwasm_std::Decimal;
+use cw_storage_plus::Item;
+
+/// Share of the treasury UST that is kept liquid by Rebalance
+pub const LIQUID_RATIO: Item<Decimal> = Item::new("liquid_ratio");
@@ -0,0 +1,201 @@
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, MessageInfo, Response, StdError, StdResult,
+    Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use terraswap::asset::AssetInfo;
+
+use white_whale::memory::LIST_SIZE_LIMIT;
+use white_whale::query::terraswap::query_asset_balance;
+use white_whale::treasury::dapp_base::common::{ASTROPORT_GENERATOR_ID, ASTRO_TOKEN_ID};
+use white_whale::treasury::dapp_base::dapp::DAppBase;
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
+use white_whale::treasury::helpers::trader_action;
+
+use crate::contract::{AstroportStakingDApp, AstroportStakingResult};
+use crate::error::AstroportStakingError;
+use crate::queries::query_pending_rewards;
+use crate::state::POOLS;
+
+/// Registers an LP token that can be staked in the generator
+pub fn register_pool(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    lp_token_id: String,
+) -> AstroportStakingResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let state = BASESTATE.load(deps.storage)?;
+    // The LP token has to be known to memory
+    lp_token_address(deps.as_ref(), &state, &lp_token_id)?;
+
+    let mut pools = POOLS.load(deps.storage)?;
+    if pools.contains(&lp_token_id) {
+        return Err(AstroportStakingError::AlreadyRegistered { id: lp_token_id });
+    }
+    // This is a limit to prevent potentially running out of gas when doing lookups on the pools list
+    if pools.len() >= LIST_SIZE_LIMIT {
+        return Err(AstroportStakingError::PoolsLimitReached {});
+    }
+    pools.push(lp_token_id.clone());
+    POOLS.save(deps.storage, &pools)?;
+
+    Ok(Response::new().add_attribute("Registered pool:", lp_token_id))
+}
+
+/// Removes a registered LP token
+pub fn remove_pool(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    lp_token_id: String,
+) -> AstroportStakingResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let mut pools = POOLS.load(deps.storage)?;
+    if !pools.contains(&lp_token_id) {
+        return Err(AstroportStakingError::NotRegistered { id: lp_token_id });
+    }
+    pools.retain(|id| *id != lp_token_id);
+    POOLS.save(deps.storage, &pools)?;
+
+    Ok(Response::new().add_attribute("Removed pool:", lp_token_id))
+}
+
+/// Constructs and forwards the generator deposit message for the treasury
+pub fn stake(
+    deps: Deps,
+    msg_info: MessageInfo,
+    lp_token_id: String,
+    amount: Uint128,
+) -> AstroportStakingResult {
+    // Check if caller is trader
+    let state = AstroportStakingDApp::assert_trader(deps, &msg_info)?;
+    let lp_token = registered_lp_token(deps, &state, &lp_token_id)?;
+
+    // Check if the treasury holds enough LP tokens
+    let lp_info = AssetInfo::Token {
+        contract_addr: lp_token.to_string(),
+    };
+    if query_asset_balance(deps, &lp_info, state.treasury_address.clone())? < amount {
+        return Err(BaseDAppError::Broke {}.into());
+    }
+
+    let generator_address = state.memory.query_contract(deps, ASTROPORT_GENERATOR_ID)?;
+    let stake_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: lp_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: generator_address.to_string(),
+            amount,
+            msg: to_binary(&astroport::generator::Cw20HookMsg::Deposit {})?,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new().add_message(trader_action(vec![stake_msg], &state.treasury_address)?))
+}
+
+/// Constructs and forwards the generator withdraw message for the treasury
+pub fn unstake(
+    deps: Deps,
+    msg_info: MessageInfo,
+    lp_token_id: String,
+    amount: Uint128,
+) -> AstroportStakingResult {
+    // Check if caller is trader
+    let state = AstroportStakingDApp::assert_trader(deps, &msg_info)?;
+    // Staked LP tokens can be withdrawn even if the pool is no longer registered
+    let lp_token = lp_token_address(deps, &state, &lp_token_id)?;
+
+    let generator_address = state.memory.query_contract(deps, ASTROPORT_GENERATOR_ID)?;
+    let unstake_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: generator_address.to_string(),
+        msg: to_binary(&astroport::generator::ExecuteMsg::Withdraw {
+            lp_token: lp_token.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new().add_message(trader_action(vec![unstake_msg], &state.treasury_address)?))
+}
+
+/// Constructs and forwards the generator claim message for the treasury.
+/// The pending ASTRO is swapped through `swap_pool_id` if provided, otherwise it stays in the treasury.
+pub fn claim_rewards(
+    deps: Deps,
+    msg_info: MessageInfo,
+    lp_token_id: String,
+    swap_pool_id: Option<String>,
+    max_spread: Option<Decimal>,
+) -> AstroportStakingResult {
+    // Check if caller is trader
+    let state = AstroportStakingDApp::assert_trader(deps, &msg_info)?;
+    let lp_token = lp_token_address(deps, &state, &lp_token_id)?;
+
+    let generator_address = state.memory.query_contract(deps, ASTROPORT_GENERATOR_ID)?;
+    let mut messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: generator_address.to_string(),
+        msg: to_binary(&astroport::generator::ExecuteMsg::ClaimRewards {
+            lp_tokens: vec![lp_token.to_string()],
+        })?,
+        funds: vec![],
+    })];
+
+    // Reinvest the claimed rewards by swapping them
+    if let Some(swap_pool_id) = swap_pool_id {
+        let pending =
+            query_pending_rewards(deps, &generator_address, &lp_token, &state.treasury_address)?;
+        if !pending.is_zero() {
+            let pair_address = state.memory.query_contract(deps, &swap_pool_id)?;
+            let astro_token = match state.memory.query_asset(deps, ASTRO_TOKEN_ID)? {
+                AssetInfo::Token { contract_addr } => contract_addr,
+                AssetInfo::NativeToken { .. } => {
+                    return Err(StdError::generic_err("ASTRO must be a cw20 token").into())
+                }
+            };
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: astro_token,
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: pair_address.to_string(),
+                    amount: pending,
+                    msg: to_binary(&astroport::pair::Cw20HookMsg::Swap {
+                        belief_price: None,
+                        max_spread,
+                        to: None,
+                    })?,
+                })?,
+                funds: vec![],
+            }));
+        }
+    }
+
+    Ok(Response::new().add_message(trader_action(messages, &state.treasury_address)?))
+}
+
+/// Returns the address of a registered LP token
+fn registered_lp_token(
+    deps: Deps,
+    state: &BaseState,
+    lp_token_id: &str,
+) -> Result<Addr, AstroportStakingError> {
+    if !POOLS.load(deps.storage)?.iter().any(|id| id == lp_token_id) {
+        return Err(AstroportStakingError::NotRegistered {
+            id: lp_token_id.to_string(),
+        });
+    }
+    Ok(lp_token_address(deps, state, lp_token_id)?)
+}
+
+/// Resolves the address of an LP token through memory
+pub fn lp_token_address(deps: Deps, state: &BaseState, lp_token_id: &str) -> StdResult<Addr> {
+    match state.memory.query_asset(deps, lp_token_id)? {
+        AssetInfo::Token { contract_addr } => deps.api.addr_validate(&contract_addr),
+        AssetInfo::NativeToken { .. } => Err(StdError::generic_err(format!(
+            "{} is not a cw20 LP token",
+            lp_token_id
+        ))),
+    }
+}
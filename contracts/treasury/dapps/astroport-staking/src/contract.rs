@@ -0,0 +1,70 @@
+use cosmwasm_std::{
+    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+
+use white_whale::dapps::astroport_staking::msg::{ExecuteMsg, QueryMsg};
+use white_whale::treasury::dapp_base::dapp::DAppBase;
+use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
+
+use crate::error::AstroportStakingError;
+use crate::state::POOLS;
+use crate::{commands, queries};
+
+pub type AstroportStakingResult = Result<Response, AstroportStakingError>;
+
+pub struct AstroportStakingDApp;
+impl DAppBase for AstroportStakingDApp {
+    type Error = AstroportStakingError;
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: BaseInstantiateMsg,
+) -> AstroportStakingResult {
+    // Setup the admin as the creator of the contract
+    AstroportStakingDApp::instantiate_base(deps.branch(), &info, msg)?;
+    POOLS.save(deps.storage, &vec![])?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> AstroportStakingResult {
+    match msg {
+        ExecuteMsg::Base(message) => AstroportStakingDApp::execute_base(deps, env, info, message),
+        ExecuteMsg::RegisterPool { lp_token_id } => {
+            commands::register_pool(deps, info, lp_token_id)
+        }
+        ExecuteMsg::RemovePool { lp_token_id } => commands::remove_pool(deps, info, lp_token_id),
+        ExecuteMsg::Stake {
+            lp_token_id,
+            amount,
+        } => commands::stake(deps.as_ref(), info, lp_token_id, amount),
+        ExecuteMsg::Unstake {
+            lp_token_id,
+            amount,
+        } => commands::unstake(deps.as_ref(), info, lp_token_id, amount),
+        ExecuteMsg::ClaimRewards {
+            lp_token_id,
+            swap_pool_id,
+            max_spread,
+        } => commands::claim_rewards(deps.as_ref(), info, lp_token_id, swap_pool_id, max_spread),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Base(message) => AstroportStakingDApp::query_base(deps, message),
+        QueryMsg::Pools {} => to_binary(&queries::query_pools(deps)?),
+        QueryMsg::Staked { lp_token_id } => to_binary(&queries::query_staked(deps, lp_token_id)?),
+    }
+}
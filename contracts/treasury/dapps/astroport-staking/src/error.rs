@@ -0,0 +1,29 @@
+use cosmwasm_std::StdError;
+use cw_controllers::AdminError;
+use thiserror::Error;
+use white_whale::error::CommonError;
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AstroportStakingError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    BaseDAppError(#[from] BaseDAppError),
+
+    #[error("{} is already registered", id)]
+    AlreadyRegistered { id: String },
+
+    #[error("{} is not a registered LP token", id)]
+    NotRegistered { id: String },
+
+    #[error("The pools list has reached its limit, can't add more pools.")]
+    PoolsLimitReached {},
+}
@@ -0,0 +1,9 @@
+mod commands;
+pub mod contract;
+pub mod error;
+pub mod queries;
+pub mod state;
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests;
@@ -0,0 +1,56 @@
+use cosmwasm_std::{Addr, Deps, StdResult, Uint128};
+
+use white_whale::dapps::astroport_staking::msg::{PoolsResponse, StakedResponse};
+use white_whale::treasury::dapp_base::common::ASTROPORT_GENERATOR_ID;
+use white_whale::treasury::dapp_base::state::BASESTATE;
+
+use crate::commands::lp_token_address;
+use crate::state::POOLS;
+
+/// Returns the registered LP tokens
+pub fn query_pools(deps: Deps) -> StdResult<PoolsResponse> {
+    Ok(PoolsResponse {
+        lp_token_ids: POOLS.load(deps.storage)?,
+    })
+}
+
+/// Returns the LP tokens staked by the treasury and the ASTRO it can claim
+pub fn query_staked(deps: Deps, lp_token_id: String) -> StdResult<StakedResponse> {
+    let state = BASESTATE.load(deps.storage)?;
+    let lp_token = lp_token_address(deps, &state, &lp_token_id)?;
+    let generator_address = state.memory.query_contract(deps, ASTROPORT_GENERATOR_ID)?;
+
+    let staked: Uint128 = deps.querier.query_wasm_smart(
+        &generator_address,
+        &astroport::generator::QueryMsg::Deposit {
+            lp_token: lp_token.to_string(),
+            user: state.treasury_address.to_string(),
+        },
+    )?;
+    Ok(StakedResponse {
+        staked,
+        pending_rewards: query_pending_rewards(
+            deps,
+            &generator_address,
+            &lp_token,
+            &state.treasury_address,
+        )?,
+    })
+}
+
+/// Returns the ASTRO `user` can claim for staking `lp_token`
+pub fn query_pending_rewards(
+    deps: Deps,
+    generator_address: &Addr,
+    lp_token: &Addr,
+    user: &Addr,
+) -> StdResult<Uint128> {
+    let pending: astroport::generator::PendingTokenResponse = deps.querier.query_wasm_smart(
+        generator_address,
+        &astroport::generator::QueryMsg::PendingToken {
+            lp_token: lp_token.to_string(),
+            user: user.to_string(),
+        },
+    )?;
+    Ok(pending.pending)
+}
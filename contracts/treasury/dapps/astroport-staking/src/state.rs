@@ -0,0 +1,4 @@
+use cw_storage_plus::Item;
+
+/// Memory ids of the LP tokens that can be staked
+pub const POOLS: Item<Vec<String>> = Item::new("pools");
@@ -0,0 +1,99 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_binary, DepsMut, Uint128};
+use cw_controllers::AdminError;
+
+use white_whale::dapps::astroport_staking::msg::{ExecuteMsg, PoolsResponse, QueryMsg};
+use white_whale::treasury::dapp_base::common_test::{
+    MEMORY_CONTRACT, TEST_CREATOR, TRADER_CONTRACT, TREASURY_CONTRACT,
+};
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::AstroportStakingError;
+
+fn mock_instantiate(deps: DepsMut) {
+    let msg = BaseInstantiateMsg {
+        memory_addr: MEMORY_CONTRACT.to_string(),
+        treasury_address: TREASURY_CONTRACT.to_string(),
+        trader: TRADER_CONTRACT.to_string(),
+    };
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps, mock_env(), info, msg).expect("contract successfully handles InstantiateMsg");
+}
+
+#[test]
+pub fn test_no_pools_after_instantiate() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let res: PoolsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Pools {}).unwrap()).unwrap();
+    assert!(res.lp_token_ids.is_empty());
+}
+
+#[test]
+pub fn test_register_pool_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::RegisterPool {
+        lp_token_id: "whale_ust".to_string(),
+    };
+
+    let info = mock_info("unauthorized", &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(AstroportStakingError::Admin(AdminError::NotAdmin {})) => (),
+        Ok(_) => panic!("Should return unauthorized Error, AdminError::NotAdmin"),
+        _ => panic!("Should return unauthorized Error, AdminError::NotAdmin"),
+    }
+}
+
+#[test]
+pub fn test_remove_unregistered_pool() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::RemovePool {
+        lp_token_id: "whale_ust".to_string(),
+    };
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(AstroportStakingError::NotRegistered { .. }) => (),
+        Ok(_) => panic!("Should return NotRegistered Error"),
+        _ => panic!("Should return NotRegistered Error"),
+    }
+}
+
+#[test]
+pub fn test_stake_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::Stake {
+        lp_token_id: "whale_ust".to_string(),
+        amount: Uint128::from(1u64),
+    };
+
+    let info = mock_info("unauthorized", &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(AstroportStakingError::BaseDAppError(BaseDAppError::Unauthorized {})) => (),
+        Ok(_) => panic!("Should return unauthorized Error, DAppError::Unauthorized"),
+        _ => panic!("Should return unauthorized Error, DAppError::Unauthorized"),
+    }
+}
+
+#[test]
+pub fn test_stake_unregistered_pool() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::Stake {
+        lp_token_id: "whale_ust".to_string(),
+        amount: Uint128::from(1u64),
+    };
+
+    let info = mock_info(TRADER_CONTRACT, &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(AstroportStakingError::NotRegistered { .. }) => (),
+        Ok(_) => panic!("Should return NotRegistered Error"),
+        _ => panic!("Should return NotRegistered Error"),
+    }
+}
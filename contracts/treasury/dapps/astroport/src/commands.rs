@@ -10,11 +10,10 @@ use white_whale::treasury::vault_assets::get_identifier;
 use white_whale::query::terraswap::{query_asset_balance, query_pool};
 use white_whale::treasury::dapp_base::common::PAIR_POSTFIX;
 use white_whale::treasury::dapp_base::error::BaseDAppError;
-use white_whale::treasury::dapp_base::state::BASESTATE;
-use white_whale::treasury::msg::send_to_treasury;
+use white_whale::treasury::helpers::trader_action;
 
 use crate::astroport_msg::{asset_into_swap_msg, deposit_lp_msg};
-use crate::contract::AstroportResult;
+use crate::contract::{AstroportDApp, AstroportResult};
 use crate::error::AstroportError;
 use crate::utils::has_sufficient_balance;
 
@@ -26,11 +25,8 @@ pub fn provide_liquidity(
     pool_id: String,
     amount: Uint128,
 ) -> AstroportResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Check if caller is trader.
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {}.into());
-    }
+    // Check if caller is trader
+    let state = AstroportDApp::assert_trader(deps, &msg_info)?;
 
     let treasury_address = &state.treasury_address;
 
@@ -77,7 +73,7 @@ pub fn provide_liquidity(
     let msgs: Vec<CosmosMsg> =
         deposit_lp_msg(deps, [second_asset, first_asset], pair_address, None)?;
 
-    Ok(Response::new().add_message(send_to_treasury(msgs, treasury_address)?))
+    Ok(Response::new().add_message(trader_action(msgs, treasury_address)?))
 }
 
 /// Constructs and forwards the terraswap provide_liquidity message
@@ -89,11 +85,8 @@ pub fn detailed_provide_liquidity(
     pool_id: String,
     slippage_tolerance: Option<Decimal>,
 ) -> AstroportResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Check if caller is trader.
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {}.into());
-    }
+    // Check if caller is trader
+    let state = AstroportDApp::assert_trader(deps, &msg_info)?;
 
     if assets.len() > 2 {
         return Err(AstroportError::NotTwoAssets {});
@@ -135,7 +128,7 @@ pub fn detailed_provide_liquidity(
     // increase allowance msg for each asset.
     let msgs: Vec<CosmosMsg> = deposit_lp_msg(deps, asset_array, pair_address, slippage_tolerance)?;
 
-    Ok(Response::new().add_message(send_to_treasury(msgs, treasury_address)?))
+    Ok(Response::new().add_message(trader_action(msgs, treasury_address)?))
 }
 
 /// Constructs withdraw liquidity msg and forwards it to treasury
@@ -145,11 +138,8 @@ pub fn withdraw_liquidity(
     lp_token_id: String,
     amount: Uint128,
 ) -> AstroportResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Sender must be trader
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {}.into());
-    }
+    // Check if caller is trader
+    let state = AstroportDApp::assert_trader(deps, &msg_info)?;
     let treasury_address = &state.treasury_address;
 
     // Get lp token address
@@ -180,7 +170,7 @@ pub fn withdraw_liquidity(
         funds: vec![],
     });
 
-    Ok(Response::new().add_message(send_to_treasury(vec![lp_call], treasury_address)?))
+    Ok(Response::new().add_message(trader_action(vec![lp_call], treasury_address)?))
 }
 
 /// Function constructs astroport swap messages and forwards them to the treasury
@@ -197,13 +187,9 @@ pub fn astroport_swap(
     max_spread: Option<Decimal>,
     belief_price: Option<Decimal>,
 ) -> AstroportResult {
-    let state = BASESTATE.load(deps.storage)?;
-    let treasury_address = state.treasury_address;
-
     // Check if caller is trader
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {}.into());
-    }
+    let state = AstroportDApp::assert_trader(deps, &msg_info)?;
+    let treasury_address = state.treasury_address;
 
     // Check if treasury has enough to swap
     has_sufficient_balance(deps, &state.memory, &offer_id, &treasury_address, amount)?;
@@ -225,5 +211,5 @@ pub fn astroport_swap(
         None,
     )?];
 
-    Ok(Response::new().add_message(send_to_treasury(swap_msg, &treasury_address)?))
+    Ok(Response::new().add_message(trader_action(swap_msg, &treasury_address)?))
 }
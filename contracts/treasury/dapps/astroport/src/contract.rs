@@ -3,11 +3,8 @@
 use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 
 use white_whale::memory::item::Memory;
-use white_whale::treasury::dapp_base::commands::{self as dapp_base_commands, handle_base_init};
-use white_whale::treasury::dapp_base::common::BaseDAppResult;
+use white_whale::treasury::dapp_base::dapp::DAppBase;
 use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
-use white_whale::treasury::dapp_base::queries as dapp_base_queries;
-use white_whale::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
 use white_whale::treasury::vault_assets::{get_identifier, VaultAsset};
 
 use crate::commands;
@@ -15,19 +12,20 @@ use crate::error::AstroportError;
 use white_whale::dapps::astroport::msg::{ExecuteMsg, QueryMsg};
 pub type AstroportResult = Result<Response, AstroportError>;
 
+pub struct AstroportDApp;
+impl DAppBase for AstroportDApp {
+    type Error = AstroportError;
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     msg: BaseInstantiateMsg,
-) -> BaseDAppResult {
-    let base_state = handle_base_init(deps.as_ref(), msg)?;
-
-    BASESTATE.save(deps.storage, &base_state)?;
-
+) -> AstroportResult {
     // Setup the admin as the creator of the contract
-    ADMIN.set(deps, Some(info.sender))?;
+    AstroportDApp::instantiate_base(deps, &info, msg)?;
 
     Ok(Response::default())
 }
@@ -71,26 +69,15 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> A
             max_spread,
             belief_price,
         ),
-        ExecuteMsg::Base(message) => {
-            from_base_dapp_result(dapp_base_commands::handle_base_message(deps, info, message))
-        }
+        ExecuteMsg::Base(message) => AstroportDApp::execute_base(deps, env, info, message),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Base(message) => dapp_base_queries::handle_base_query(deps, message),
+        QueryMsg::Base(message) => AstroportDApp::query_base(deps, message),
         // handle dapp-specific queries here
         // QueryMsg::Custom{} => queries::custom_query(),
     }
 }
-
-/// Required to convert BaseDAppResult into AstroportResult
-/// Can't implement the From trait directly
-fn from_base_dapp_result(result: BaseDAppResult) -> AstroportResult {
-    match result {
-        Err(e) => Err(e.into()),
-        Ok(r) => Ok(r),
-    }
-}
@@ -1,6 +1,7 @@
 use cosmwasm_std::StdError;
 use cw_controllers::AdminError;
 use thiserror::Error;
+use white_whale::error::CommonError;
 use white_whale::treasury::dapp_base::error::BaseDAppError;
 
 #[derive(Error, Debug, PartialEq)]
@@ -8,6 +9,9 @@ pub enum AstroportError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
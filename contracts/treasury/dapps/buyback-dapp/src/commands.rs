@@ -2,26 +2,21 @@
 use cosmwasm_std::{to_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg};
 use cw20::{Cw20ExecuteMsg};
 use terraswap::asset::{AssetInfo};
-use white_whale::treasury::dapp_base::error::BaseDAppError;
-use white_whale::treasury::dapp_base::state::BASESTATE;
 use terraswap::pair::{Cw20HookMsg};
-use white_whale::treasury::msg::{send_to_treasury};
+use white_whale::treasury::helpers::trader_action;
 use crate::state::{State, STATE};
 use white_whale::query::terraswap::query_asset_balance;
 
 
 
-use crate::contract::BuyBackResult;
+use crate::contract::{BuyBackDApp, BuyBackResult};
 use crate::error::BuyBackError;
 
 pub fn handle_buyback_whale(deps: DepsMut, _env: Env, msg_info: MessageInfo, amount_to_buy: Uint128) -> BuyBackResult {
-        let state = BASESTATE.load(deps.storage)?;
+        // Check if caller is trader.
+        let state = BuyBackDApp::assert_trader(deps.as_ref(), &msg_info)?;
         let config: State = STATE.load(deps.storage)?;
 
-        // Check if caller is trader.
-        if msg_info.sender != state.trader {
-            return Err(BuyBackError::BaseDAppError(BaseDAppError::Unauthorized {}));
-        }
         // Prepare empty message vec
         let mut messages: Vec<CosmosMsg> = vec![];
         let treasury_address = state.treasury_address;
@@ -59,5 +54,5 @@ pub fn handle_buyback_whale(deps: DepsMut, _env: Env, msg_info: MessageInfo, amo
         println!("{:?}", msg);
 
         messages.push(msg);
-        Ok(Response::new().add_message(send_to_treasury(messages, &treasury_address)?))
+        Ok(Response::new().add_message(trader_action(messages, &treasury_address)?))
     }
@@ -3,12 +3,7 @@
 
 use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 
-use white_whale::treasury::dapp_base::commands::{self as dapp_base_commands, handle_base_init};
-use white_whale::treasury::dapp_base::common::BaseDAppResult;
-use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
-use white_whale::treasury::dapp_base::queries as dapp_base_queries;
-use white_whale::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
-use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::dapp::DAppBase;
 
 use crate::commands;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
@@ -17,15 +12,20 @@ use crate::error::BuyBackError;
 pub type BuyBackResult = Result<Response, BuyBackError>;
 use crate::state::{State, STATE};
 
+pub struct BuyBackDApp;
+impl DAppBase for BuyBackDApp {
+    type Error = BuyBackError;
+}
+
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> BaseDAppResult {
-    let base_state = handle_base_init(deps.as_ref(), msg.base)?;
+) -> BuyBackResult {
+    BuyBackDApp::instantiate_base(deps.branch(), &info, msg.base)?;
 
     let config: State = State {
         whale_vust_lp: msg.whale_vust_lp,
@@ -33,8 +33,6 @@ pub fn instantiate(
         whale_token: msg.whale_token,
     };
     STATE.save(deps.storage, &config)?;
-    BASESTATE.save(deps.storage, &base_state)?;
-    ADMIN.set(deps, Some(info.sender))?;
 
 
     Ok(Response::default())
@@ -43,9 +41,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> BuyBackResult {
     match msg {
-        ExecuteMsg::Base(message) => {
-            from_base_dapp_result(dapp_base_commands::handle_base_message(deps, info, message))
-        }
+        ExecuteMsg::Base(message) => BuyBackDApp::execute_base(deps, env, info, message),
         // handle dapp-specific messages here
         // ExecuteMsg::Custom{} => commands::custom_command(),
         ExecuteMsg::Buyback{ amount } => commands::handle_buyback_whale(deps, env, info, amount),
@@ -55,17 +51,8 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> B
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Base(message) => dapp_base_queries::handle_base_query(deps, message),
+        QueryMsg::Base(message) => BuyBackDApp::query_base(deps, message),
         // handle dapp-specific queries here
         // QueryMsg::Custom{} => queries::custom_query(),
     }
 }
-
-/// Required to convert BaseDAppResult into TerraswapResult
-/// Can't implement the From trait directly
-fn from_base_dapp_result(result: BaseDAppResult) -> BuyBackResult {
-    match result {
-        Err(e) => Err(e.into()),
-        Ok(r) => Ok(r),
-    }
-}
@@ -3,15 +3,20 @@
 
 use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 
-use white_whale::treasury::dapp_base::commands::{self as dapp_base_commands, handle_base_init};
 use white_whale::treasury::dapp_base::common::BaseDAppResult;
+use white_whale::treasury::dapp_base::dapp::DAppBase;
+use white_whale::treasury::dapp_base::error::BaseDAppError;
 use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
-use white_whale::treasury::dapp_base::queries as dapp_base_queries;
-use white_whale::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
 
 use crate::commands;
 use crate::msg::{ExecuteMsg, QueryMsg};
 
+/// Routes the base messages, set Error to the dapp-specific error type
+pub struct TemplateDApp;
+impl DAppBase for TemplateDApp {
+    type Error = BaseDAppError;
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -19,10 +24,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: BaseInstantiateMsg,
 ) -> BaseDAppResult {
-    let base_state = handle_base_init(deps.as_ref(), msg)?;
-
-    BASESTATE.save(deps.storage, &base_state)?;
-    ADMIN.set(deps, Some(info.sender))?;
+    TemplateDApp::instantiate_base(deps, &info, msg)?;
 
     Ok(Response::default())
 }
@@ -30,7 +32,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> BaseDAppResult {
     match msg {
-        ExecuteMsg::Base(message) => dapp_base_commands::handle_base_message(deps, info, message),
+        ExecuteMsg::Base(message) => TemplateDApp::execute_base(deps, env, info, message),
         // handle dapp-specific messages here
         // ExecuteMsg::Custom{} => commands::custom_command(),
     }
@@ -39,7 +41,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> B
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Base(message) => dapp_base_queries::handle_base_query(deps, message),
+        QueryMsg::Base(message) => TemplateDApp::query_base(deps, message),
         // handle dapp-specific queries here
         // QueryMsg::Custom{} => queries::custom_query(),
     }
@@ -1,10 +1,13 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::Api;
 
+use white_whale::error::CommonError;
 use white_whale::memory::item::Memory;
 use white_whale::treasury::dapp_base::common_test::{
     MEMORY_CONTRACT, TEST_CREATOR, TRADER_CONTRACT, TREASURY_CONTRACT,
 };
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
 use white_whale::treasury::dapp_base::state::{BaseState, BASESTATE};
 
 use crate::contract::instantiate;
@@ -33,3 +36,29 @@ fn successful_initialization() {
         }
     );
 }
+
+#[test]
+fn unsuccessful_initialization_invalid_address() {
+    let invalid_fields: Vec<(&str, fn(&mut BaseInstantiateMsg))> = vec![
+        ("memory_addr", |msg| msg.memory_addr = "x".to_string()),
+        ("treasury_address", |msg| {
+            msg.treasury_address = "x".to_string()
+        }),
+        ("trader", |msg| msg.trader = "x".to_string()),
+    ];
+
+    for (field, invalidate) in invalid_fields {
+        let mut deps = mock_dependencies(&[]);
+        let mut msg = instantiate_msg();
+        invalidate(&mut msg);
+
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+        match res {
+            Err(BaseDAppError::Common(CommonError::InvalidAddress {
+                field: invalid_field,
+                ..
+            })) => assert_eq!(invalid_field, field),
+            _ => panic!("Must return CommonError::InvalidAddress for {}", field),
+        }
+    }
+}
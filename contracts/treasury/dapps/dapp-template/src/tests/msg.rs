@@ -1,6 +1,7 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::Addr;
 
+use white_whale::error::CommonError;
 use white_whale::memory::item::Memory;
 use white_whale::treasury::dapp_base::error::BaseDAppError;
 use white_whale::treasury::dapp_base::msg::BaseExecuteMsg;
@@ -177,6 +178,28 @@ pub fn test_successfully_update_config_msg_with_no_parameters() {
     )
 }
 
+#[test]
+pub fn test_unsuccessfully_update_config_msg_with_invalid_address() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let env = mock_env();
+    let msg = ExecuteMsg::Base(BaseExecuteMsg::UpdateConfig {
+        treasury_address: None,
+        trader: Some("x".to_string()),
+        memory: None,
+    });
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+
+    match res {
+        Err(BaseDAppError::Common(CommonError::InvalidAddress { field, .. })) => {
+            assert_eq!(field, "trader")
+        }
+        _ => panic!("Should return CommonError::InvalidAddress"),
+    }
+}
+
 /**
  * BaseExecuteMsg::SetAdmin
  */
@@ -0,0 +1,163 @@
+use cosmwasm_std::{CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use terraswap::asset::Asset;
+
+use white_whale::dapps::lending::msg::CallbackMsg;
+use white_whale::mars::{mars_borrow_msg, mars_deposit_msg, mars_repay_msg, mars_withdraw_msg};
+use white_whale::query::terraswap::query_asset_balance;
+use white_whale::treasury::dapp_base::dapp::DAppBase;
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::state::ADMIN;
+use white_whale::treasury::helpers::trader_action;
+
+use crate::contract::{LendingDApp, LendingResult};
+use crate::error::LendingError;
+use crate::queries::query_position;
+use crate::state::CONFIG;
+
+/// Updates the money market and the minimum health factor, only the admin can call this
+pub fn update_config(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    market_id: Option<String>,
+    min_health_factor: Option<Decimal>,
+) -> LendingResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(market_id) = market_id {
+        config.market_id = market_id;
+    }
+    if let Some(min_health_factor) = min_health_factor {
+        // a position at a health factor of 1 can be liquidated
+        if min_health_factor <= Decimal::one() {
+            return Err(LendingError::InvalidHealthFactor {});
+        }
+        config.min_health_factor = min_health_factor;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("market_id", config.market_id)
+        .add_attribute("min_health_factor", config.min_health_factor.to_string()))
+}
+
+/// Constructs and forwards the deposit of treasury collateral into the money market
+pub fn deposit_collateral(
+    deps: Deps,
+    msg_info: MessageInfo,
+    asset_id: String,
+    amount: Uint128,
+) -> LendingResult {
+    // Check if caller is trader
+    let state = LendingDApp::assert_trader(deps, &msg_info)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let info = state.memory.query_asset(deps, &asset_id)?;
+    if query_asset_balance(deps, &info, state.treasury_address.clone())? < amount {
+        return Err(BaseDAppError::Broke {}.into());
+    }
+
+    let market_address = state.memory.query_contract(deps, &config.market_id)?;
+    let deposit_msg: CosmosMsg = mars_deposit_msg(deps, market_address, Asset { info, amount })?;
+
+    Ok(Response::new().add_message(trader_action(vec![deposit_msg], &state.treasury_address)?))
+}
+
+/// Constructs and forwards the withdrawal of treasury collateral, followed by a health check
+pub fn withdraw_collateral(
+    deps: Deps,
+    env: Env,
+    msg_info: MessageInfo,
+    asset_id: String,
+    amount: Uint128,
+) -> LendingResult {
+    // Check if caller is trader
+    let state = LendingDApp::assert_trader(deps, &msg_info)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let info = state.memory.query_asset(deps, &asset_id)?;
+    let market_address = state.memory.query_contract(deps, &config.market_id)?;
+    let withdraw_msg: CosmosMsg = mars_withdraw_msg(market_address, info, amount)?;
+
+    Ok(Response::new()
+        .add_message(trader_action(vec![withdraw_msg], &state.treasury_address)?)
+        .add_message(CallbackMsg::AssertHealthFactor {}.to_cosmos_msg(&env.contract.address)?))
+}
+
+/// Constructs and forwards a borrow for the treasury, followed by a health check
+pub fn borrow(
+    deps: Deps,
+    env: Env,
+    msg_info: MessageInfo,
+    asset_id: String,
+    amount: Uint128,
+) -> LendingResult {
+    // Check if caller is trader
+    let state = LendingDApp::assert_trader(deps, &msg_info)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let info = state.memory.query_asset(deps, &asset_id)?;
+    let market_address = state.memory.query_contract(deps, &config.market_id)?;
+    let borrow_msg: CosmosMsg = mars_borrow_msg(market_address, info, amount)?;
+
+    Ok(Response::new()
+        .add_message(trader_action(vec![borrow_msg], &state.treasury_address)?)
+        .add_message(CallbackMsg::AssertHealthFactor {}.to_cosmos_msg(&env.contract.address)?))
+}
+
+/// Constructs and forwards the repayment of treasury debt
+pub fn repay(
+    deps: Deps,
+    msg_info: MessageInfo,
+    asset_id: String,
+    amount: Uint128,
+) -> LendingResult {
+    // Check if caller is trader
+    let state = LendingDApp::assert_trader(deps, &msg_info)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let info = state.memory.query_asset(deps, &asset_id)?;
+    if query_asset_balance(deps, &info, state.treasury_address.clone())? < amount {
+        return Err(BaseDAppError::Broke {}.into());
+    }
+
+    let market_address = state.memory.query_contract(deps, &config.market_id)?;
+    let repay_msg: CosmosMsg = mars_repay_msg(deps, market_address, Asset { info, amount })?;
+
+    Ok(Response::new().add_message(trader_action(vec![repay_msg], &state.treasury_address)?))
+}
+
+pub fn handle_callback(
+    deps: Deps,
+    env: Env,
+    msg_info: MessageInfo,
+    msg: CallbackMsg,
+) -> LendingResult {
+    // Callback functions can only be called by this contract itself
+    if msg_info.sender != env.contract.address {
+        return Err(BaseDAppError::Unauthorized {}.into());
+    }
+    match msg {
+        CallbackMsg::AssertHealthFactor {} => assert_health_factor(deps),
+    }
+}
+
+/// Fails if the treasury position is below the minimum health factor
+fn assert_health_factor(deps: Deps) -> LendingResult {
+    let position = query_position(deps)?;
+    if let Some(health_factor) = position.health_factor {
+        if health_factor < position.min_health_factor {
+            return Err(LendingError::HealthFactorTooLow {
+                health_factor,
+                min_health_factor: position.min_health_factor,
+            });
+        }
+    }
+    Ok(Response::new().add_attribute(
+        "health_factor",
+        position
+            .health_factor
+            .map_or_else(|| "none".to_string(), |factor| factor.to_string()),
+    ))
+}
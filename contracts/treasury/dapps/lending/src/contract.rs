@@ -0,0 +1,75 @@
+use cosmwasm_std::{
+    entry_point, to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+
+use white_whale::dapps::lending::msg::{ExecuteMsg, QueryMsg};
+use white_whale::treasury::dapp_base::common::MARS_RED_BANK_ID;
+use white_whale::treasury::dapp_base::dapp::DAppBase;
+use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
+
+use crate::error::LendingError;
+use crate::state::{Config, CONFIG};
+use crate::{commands, queries};
+
+pub type LendingResult = Result<Response, LendingError>;
+
+/// Health factor borrows and withdrawals have to keep by default
+pub const DEFAULT_MIN_HEALTH_FACTOR: u64 = 150;
+
+pub struct LendingDApp;
+impl DAppBase for LendingDApp {
+    type Error = LendingError;
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: BaseInstantiateMsg,
+) -> LendingResult {
+    // Setup the admin as the creator of the contract
+    LendingDApp::instantiate_base(deps.branch(), &info, msg)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            market_id: MARS_RED_BANK_ID.to_string(),
+            min_health_factor: Decimal::percent(DEFAULT_MIN_HEALTH_FACTOR),
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> LendingResult {
+    match msg {
+        ExecuteMsg::Base(message) => LendingDApp::execute_base(deps, env, info, message),
+        ExecuteMsg::UpdateConfig {
+            market_id,
+            min_health_factor,
+        } => commands::update_config(deps, info, market_id, min_health_factor),
+        ExecuteMsg::DepositCollateral { asset_id, amount } => {
+            commands::deposit_collateral(deps.as_ref(), info, asset_id, amount)
+        }
+        ExecuteMsg::WithdrawCollateral { asset_id, amount } => {
+            commands::withdraw_collateral(deps.as_ref(), env, info, asset_id, amount)
+        }
+        ExecuteMsg::Borrow { asset_id, amount } => {
+            commands::borrow(deps.as_ref(), env, info, asset_id, amount)
+        }
+        ExecuteMsg::Repay { asset_id, amount } => {
+            commands::repay(deps.as_ref(), info, asset_id, amount)
+        }
+        ExecuteMsg::Callback(msg) => commands::handle_callback(deps.as_ref(), env, info, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Base(message) => LendingDApp::query_base(deps, message),
+        QueryMsg::Config {} => to_binary(&queries::query_config(deps)?),
+        QueryMsg::Position {} => to_binary(&queries::query_position(deps)?),
+    }
+}
@@ -0,0 +1,31 @@
+use cosmwasm_std::{Decimal, StdError};
+use cw_controllers::AdminError;
+use thiserror::Error;
+use white_whale::error::CommonError;
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum LendingError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    BaseDAppError(#[from] BaseDAppError),
+
+    #[error("The minimum health factor must be above 1")]
+    InvalidHealthFactor {},
+
+    #[error(
+        "The health factor would drop to {health_factor}, below the minimum of {min_health_factor}"
+    )]
+    HealthFactorTooLow {
+        health_factor: Decimal,
+        min_health_factor: Decimal,
+    },
+}
@@ -0,0 +1,48 @@
+use cosmwasm_std::{Decimal, Deps, StdResult};
+
+use white_whale::dapps::lending::msg::{ConfigResponse, PositionResponse};
+use white_whale::query::mars::query_user_position;
+use white_whale::treasury::dapp_base::state::BASESTATE;
+
+use crate::state::CONFIG;
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        market_id: config.market_id,
+        min_health_factor: config.min_health_factor,
+    })
+}
+
+/// Returns the collateral, debt, loan-to-value and health factor of the treasury position
+pub fn query_position(deps: Deps) -> StdResult<PositionResponse> {
+    let state = BASESTATE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let market_address = state.memory.query_contract(deps, &config.market_id)?;
+    let position = query_user_position(deps, market_address, state.treasury_address)?;
+
+    let ltv = if position.total_collateral_in_uusd.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(
+            position.total_debt_in_uusd,
+            position.total_collateral_in_uusd,
+        )
+    };
+    let health_factor = if position.total_debt_in_uusd.is_zero() {
+        None
+    } else {
+        Some(Decimal::from_ratio(
+            position.weighted_liquidation_threshold_in_uusd,
+            position.total_debt_in_uusd,
+        ))
+    };
+
+    Ok(PositionResponse {
+        collateral_value: position.total_collateral_in_uusd,
+        debt_value: position.total_debt_in_uusd,
+        ltv,
+        health_factor,
+        min_health_factor: config.min_health_factor,
+    })
+}
@@ -0,0 +1,14 @@
+use cosmwasm_std::Decimal;
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Memory id of the money market contract
+    pub market_id: String,
+    /// Lowest health factor borrows and withdrawals may leave the treasury position at
+    pub min_health_factor: Decimal,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
@@ -0,0 +1,240 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, ContractResult, CosmosMsg, Decimal, DepsMut, OwnedDeps,
+    SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw_controllers::AdminError;
+use terraswap::asset::AssetInfo;
+
+use white_whale::dapps::lending::msg::{
+    CallbackMsg, ConfigResponse, ExecuteMsg, PositionResponse, QueryMsg,
+};
+use white_whale::mars::{MarsAsset, MarsMsg};
+use white_whale::query::mars::UserPositionResponse;
+use white_whale::treasury::dapp_base::common_test::{
+    MEMORY_CONTRACT, TEST_CREATOR, TRADER_CONTRACT, TREASURY_CONTRACT,
+};
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
+use white_whale::treasury::helpers::trader_action;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::LendingError;
+
+const RED_BANK: &str = "red_bank";
+
+fn mock_instantiate(deps: DepsMut) {
+    let msg = BaseInstantiateMsg {
+        memory_addr: MEMORY_CONTRACT.to_string(),
+        treasury_address: TREASURY_CONTRACT.to_string(),
+        trader: TRADER_CONTRACT.to_string(),
+    };
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps, mock_env(), info, msg).expect("contract successfully handles InstantiateMsg");
+}
+
+/// Mocks memory, resolving every contract to the red bank and every asset to uusd,
+/// and a red bank returning the given treasury position
+fn mock_market(
+    collateral: u128,
+    debt: u128,
+    weighted_liquidation_threshold: u128,
+) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Raw { contract_addr, key } if contract_addr == MEMORY_CONTRACT => {
+            let response = if key.as_slice().starts_with(b"\x00\x09contracts") {
+                to_binary(&RED_BANK)
+            } else {
+                to_binary(&AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                })
+            };
+            SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+        }
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == RED_BANK => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&UserPositionResponse {
+                    total_collateral_in_uusd: Uint128::from(collateral),
+                    total_debt_in_uusd: Uint128::from(debt),
+                    weighted_liquidation_threshold_in_uusd: Uint128::from(
+                        weighted_liquidation_threshold,
+                    ),
+                })
+                .unwrap(),
+            ))
+        }
+        _ => panic!("Unexpected query"),
+    });
+    mock_instantiate(deps.as_mut());
+    deps
+}
+
+#[test]
+pub fn test_update_config() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdateConfig {
+        market_id: None,
+        min_health_factor: Some(Decimal::one()),
+    };
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("unauthorized", &[]),
+        msg.clone(),
+    ) {
+        Err(LendingError::Admin(AdminError::NotAdmin {})) => (),
+        Ok(_) => panic!("Should return unauthorized Error, AdminError::NotAdmin"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+    match execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg) {
+        Err(LendingError::InvalidHealthFactor {}) => (),
+        Ok(_) => panic!("Should return InvalidHealthFactor Error"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::UpdateConfig {
+            market_id: Some("edge_market".to_string()),
+            min_health_factor: Some(Decimal::percent(200)),
+        },
+    )
+    .unwrap();
+    let res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        ConfigResponse {
+            market_id: "edge_market".to_string(),
+            min_health_factor: Decimal::percent(200),
+        }
+    );
+}
+
+#[test]
+pub fn test_borrow_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::Borrow {
+        asset_id: "ust".to_string(),
+        amount: Uint128::from(1u64),
+    };
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("unauthorized", &[]),
+        msg,
+    ) {
+        Err(LendingError::BaseDAppError(BaseDAppError::Unauthorized {})) => (),
+        Ok(_) => panic!("Should return unauthorized Error, DAppError::Unauthorized"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+pub fn test_borrow_is_health_checked() {
+    let mut deps = mock_market(1000, 0, 800);
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TRADER_CONTRACT, &[]),
+        ExecuteMsg::Borrow {
+            asset_id: "ust".to_string(),
+            amount: Uint128::from(100u64),
+        },
+    )
+    .unwrap();
+
+    let borrow_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: RED_BANK.to_string(),
+        msg: to_binary(&MarsMsg::Borrow {
+            asset: MarsAsset::Native {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::from(100u64),
+            recipient: None,
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+    assert_eq!(
+        res.messages[0].msg,
+        trader_action(vec![borrow_msg], &Addr::unchecked(TREASURY_CONTRACT)).unwrap()
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CallbackMsg::AssertHealthFactor {}
+            .to_cosmos_msg(&env.contract.address)
+            .unwrap()
+    );
+}
+
+#[test]
+pub fn test_assert_health_factor() {
+    let env = mock_env();
+    let msg = ExecuteMsg::Callback(CallbackMsg::AssertHealthFactor {});
+
+    // health factor of 800 / 600 is below the default minimum of 1.5
+    let mut deps = mock_market(1000, 600, 800);
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TRADER_CONTRACT, &[]),
+        msg.clone(),
+    ) {
+        Err(LendingError::BaseDAppError(BaseDAppError::Unauthorized {})) => (),
+        Ok(_) => panic!("Should return unauthorized Error, DAppError::Unauthorized"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+    let contract_info = mock_info(env.contract.address.as_str(), &[]);
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        contract_info.clone(),
+        msg.clone(),
+    ) {
+        Err(LendingError::HealthFactorTooLow {
+            health_factor,
+            min_health_factor,
+        }) => {
+            assert_eq!(health_factor, Decimal::from_ratio(800u128, 600u128));
+            assert_eq!(min_health_factor, Decimal::percent(150));
+        }
+        Ok(_) => panic!("Should return HealthFactorTooLow Error"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let mut deps = mock_market(1000, 400, 800);
+    execute(deps.as_mut(), env, contract_info, msg).unwrap();
+}
+
+#[test]
+pub fn test_position() {
+    let deps = mock_market(1000, 400, 800);
+    let res: PositionResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Position {}).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        PositionResponse {
+            collateral_value: Uint128::from(1000u64),
+            debt_value: Uint128::from(400u64),
+            ltv: Decimal::percent(40),
+            health_factor: Some(Decimal::percent(200)),
+            min_health_factor: Decimal::percent(150),
+        }
+    );
+
+    let deps = mock_market(1000, 0, 800);
+    let res: PositionResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Position {}).unwrap()).unwrap();
+    assert_eq!(res.health_factor, None);
+}
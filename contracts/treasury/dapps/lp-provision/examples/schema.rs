@@ -0,0 +1,24 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use white_whale::dapps::lp_provision::msg::{
+    ExecuteMsg, PairsResponse, PositionResponse, QueryMsg,
+};
+use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
+use white_whale::treasury::dapp_base::state::BaseState;
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(BaseInstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(BaseState), &out_dir);
+    export_schema(&schema_for!(PairsResponse), &out_dir);
+    export_schema(&schema_for!(PositionResponse), &out_dir);
+}
@@ -0,0 +1,196 @@
+use cosmwasm_std::{
+    Addr, Decimal, Deps, DepsMut, MessageInfo, Order, Response, StdError, StdResult, Uint128,
+};
+use terraswap::asset::{Asset, AssetInfo};
+use terraswap::pair::PoolResponse;
+use terraswap::querier::query_token_balance;
+
+use white_whale::dapps::lp_provision::msg::{Dex, PairConfig};
+use white_whale::memory::LIST_SIZE_LIMIT;
+use white_whale::query::astroport::query_pool as query_astroport_pool;
+use white_whale::query::terraswap::{query_asset_balance, query_pool};
+use white_whale::treasury::dapp_base::dapp::DAppBase;
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
+use white_whale::treasury::helpers::trader_action;
+
+use crate::contract::{LpProvisionDApp, LpProvisionResult};
+use crate::error::LpProvisionError;
+use crate::lp_msg::{deposit_lp_msg, withdraw_lp_msg};
+use crate::state::PAIRS;
+
+/// Allows providing liquidity to the pair registered in memory as `pool_id`
+pub fn set_pair(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    pool_id: String,
+    config: PairConfig,
+) -> LpProvisionResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let state = BASESTATE.load(deps.storage)?;
+    // The pair and its LP token have to be known to memory
+    state.memory.query_contract(deps.as_ref(), &pool_id)?;
+    lp_token_address(deps.as_ref(), &state, &config.lp_token_id)?;
+
+    // This is a limit to prevent potentially running out of gas when listing the pairs
+    if PAIRS.may_load(deps.storage, &pool_id)?.is_none()
+        && PAIRS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count()
+            >= LIST_SIZE_LIMIT
+    {
+        return Err(LpProvisionError::PairsLimitReached {});
+    }
+    PAIRS.save(deps.storage, &pool_id, &config)?;
+
+    Ok(Response::new().add_attribute("Set pair:", pool_id))
+}
+
+/// Removes a configured pair
+pub fn remove_pair(deps: DepsMut, msg_info: MessageInfo, pool_id: String) -> LpProvisionResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    registered_pair(deps.as_ref(), &pool_id)?;
+    PAIRS.remove(deps.storage, &pool_id);
+
+    Ok(Response::new().add_attribute("Removed pair:", pool_id))
+}
+
+/// Constructs and forwards the provide liquidity message for the treasury
+/// The slippage tolerance and the LP tokens held afterwards are limited by the pair config
+pub fn provide_liquidity(
+    deps: Deps,
+    msg_info: MessageInfo,
+    pool_id: String,
+    assets: Vec<(String, Uint128)>,
+    slippage_tolerance: Option<Decimal>,
+) -> LpProvisionResult {
+    // Check if caller is trader
+    let state = LpProvisionDApp::assert_trader(deps, &msg_info)?;
+    let pair = registered_pair(deps, &pool_id)?;
+
+    let slippage_tolerance = slippage_tolerance.unwrap_or(pair.max_slippage);
+    if slippage_tolerance > pair.max_slippage {
+        return Err(LpProvisionError::SlippageTooHigh {
+            max: pair.max_slippage,
+        });
+    }
+    if assets.len() != 2 {
+        return Err(LpProvisionError::NotTwoAssets {});
+    }
+
+    let treasury_address = &state.treasury_address;
+    let pair_address = state.memory.query_contract(deps, &pool_id)?;
+    let pool_info = query_pair_pool(deps, &pair.dex, pair_address.clone())?;
+    if pool_info.total_share.is_zero() {
+        return Err(LpProvisionError::EmptyPool {});
+    }
+
+    let mut assets_to_send: Vec<Asset> = vec![];
+    // Share of the LP supply each asset would mint on its own
+    let mut lp_shares: Vec<Uint128> = vec![];
+    for (asset_id, amount) in assets {
+        // Make sure the amount is greater than zero since zero transfers fail
+        if amount.is_zero() {
+            return Err(LpProvisionError::ZeroAmount { asset: asset_id });
+        }
+        let asset_info = state.memory.query_asset(deps, &asset_id)?;
+        let pool_asset = match pool_info.assets.iter().find(|a| a.info == asset_info) {
+            Some(pool_asset) => pool_asset,
+            None => return Err(LpProvisionError::NotInPool { id: asset_id }),
+        };
+        // Check if treasury has enough of this asset
+        if query_asset_balance(deps, &asset_info, treasury_address.clone())? < amount {
+            return Err(BaseDAppError::Broke {}.into());
+        }
+        lp_shares.push(
+            pool_info
+                .total_share
+                .multiply_ratio(amount, pool_asset.amount),
+        );
+        assets_to_send.push(Asset {
+            info: asset_info,
+            amount,
+        });
+    }
+
+    if let Some(cap) = pair.lp_cap {
+        // The pair mints LP tokens according to the smallest share
+        let minted = lp_shares.into_iter().min().unwrap_or_default();
+        let lp_token = lp_token_address(deps, &state, &pair.lp_token_id)?;
+        let held = query_token_balance(&deps.querier, lp_token, treasury_address.clone())?;
+        if held + minted > cap {
+            return Err(LpProvisionError::LpCapReached { cap });
+        }
+    }
+
+    let msgs = deposit_lp_msg(
+        deps,
+        &pair.dex,
+        [assets_to_send[0].clone(), assets_to_send[1].clone()],
+        pair_address,
+        Some(slippage_tolerance),
+    )?;
+
+    Ok(Response::new().add_message(trader_action(msgs, treasury_address)?))
+}
+
+/// Constructs and forwards the withdraw liquidity message for the treasury
+pub fn withdraw_liquidity(
+    deps: Deps,
+    msg_info: MessageInfo,
+    pool_id: String,
+    amount: Uint128,
+) -> LpProvisionResult {
+    // Check if caller is trader
+    let state = LpProvisionDApp::assert_trader(deps, &msg_info)?;
+    let pair = registered_pair(deps, &pool_id)?;
+    // Make sure the amount to withdraw is greater than zero since zero transfers fail
+    if amount.is_zero() {
+        return Err(LpProvisionError::ZeroAmount {
+            asset: pair.lp_token_id,
+        });
+    }
+
+    let treasury_address = &state.treasury_address;
+    let lp_token = lp_token_address(deps, &state, &pair.lp_token_id)?;
+    // Check if the treasury has enough lp tokens
+    if query_token_balance(&deps.querier, lp_token.clone(), treasury_address.clone())? < amount {
+        return Err(BaseDAppError::Broke {}.into());
+    }
+    let pair_address = state.memory.query_contract(deps, &pool_id)?;
+
+    let lp_call = withdraw_lp_msg(lp_token, pair_address, amount)?;
+    Ok(Response::new().add_message(trader_action(vec![lp_call], treasury_address)?))
+}
+
+/// Returns the config of `pool_id` if it is configured
+pub fn registered_pair(deps: Deps, pool_id: &str) -> Result<PairConfig, LpProvisionError> {
+    PAIRS
+        .may_load(deps.storage, pool_id)?
+        .ok_or_else(|| LpProvisionError::NotRegistered {
+            id: pool_id.to_string(),
+        })
+}
+
+/// Resolves the address of the LP token registered in memory as `lp_token_id`
+pub fn lp_token_address(deps: Deps, state: &BaseState, lp_token_id: &str) -> StdResult<Addr> {
+    match state.memory.query_asset(deps, lp_token_id)? {
+        AssetInfo::Token { contract_addr } => Ok(Addr::unchecked(contract_addr)),
+        AssetInfo::NativeToken { .. } => Err(StdError::generic_err(format!(
+            "{} is not a cw20 LP token",
+            lp_token_id
+        ))),
+    }
+}
+
+/// Queries the pool of a pair on `dex`
+pub fn query_pair_pool(deps: Deps, dex: &Dex, pair_address: Addr) -> StdResult<PoolResponse> {
+    match dex {
+        Dex::Terraswap => query_pool(deps, &pair_address),
+        Dex::Astroport => query_astroport_pool(deps, pair_address),
+    }
+}
@@ -0,0 +1,56 @@
+use cosmwasm_std::{
+    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+
+use white_whale::dapps::lp_provision::msg::{ExecuteMsg, QueryMsg};
+use white_whale::treasury::dapp_base::dapp::DAppBase;
+use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
+
+use crate::error::LpProvisionError;
+use crate::{commands, queries};
+
+pub type LpProvisionResult = Result<Response, LpProvisionError>;
+
+pub struct LpProvisionDApp;
+impl DAppBase for LpProvisionDApp {
+    type Error = LpProvisionError;
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: BaseInstantiateMsg,
+) -> LpProvisionResult {
+    // Store the initial config and setup the admin as the creator of the contract
+    LpProvisionDApp::instantiate_base(deps, &info, msg)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> LpProvisionResult {
+    match msg {
+        ExecuteMsg::Base(message) => LpProvisionDApp::execute_base(deps, env, info, message),
+        ExecuteMsg::SetPair { pool_id, config } => commands::set_pair(deps, info, pool_id, config),
+        ExecuteMsg::RemovePair { pool_id } => commands::remove_pair(deps, info, pool_id),
+        ExecuteMsg::ProvideLiquidity {
+            pool_id,
+            assets,
+            slippage_tolerance,
+        } => commands::provide_liquidity(deps.as_ref(), info, pool_id, assets, slippage_tolerance),
+        ExecuteMsg::WithdrawLiquidity { pool_id, amount } => {
+            commands::withdraw_liquidity(deps.as_ref(), info, pool_id, amount)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Base(message) => LpProvisionDApp::query_base(deps, message),
+        QueryMsg::Pairs {} => to_binary(&queries::query_pairs(deps)?),
+        QueryMsg::Position { pool_id } => to_binary(&queries::query_position(deps, pool_id)?),
+    }
+}
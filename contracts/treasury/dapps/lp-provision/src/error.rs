@@ -0,0 +1,44 @@
+use cosmwasm_std::{Decimal, StdError, Uint128};
+use cw_controllers::AdminError;
+use thiserror::Error;
+use white_whale::error::CommonError;
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum LpProvisionError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    BaseDAppError(#[from] BaseDAppError),
+
+    #[error("{} is not a configured pair", id)]
+    NotRegistered { id: String },
+
+    #[error("The pairs list has reached its limit, can't add more pairs.")]
+    PairsLimitReached {},
+
+    #[error("You must provide exactly two assets when adding liquidity")]
+    NotTwoAssets {},
+
+    #[error("You must provide an amount greater than zero for {}", asset)]
+    ZeroAmount { asset: String },
+
+    #[error("{} is not part of the provided pool", id)]
+    NotInPool { id: String },
+
+    #[error("The slippage tolerance can be at most {max}")]
+    SlippageTooHigh { max: Decimal },
+
+    #[error("The treasury can hold at most {cap} LP tokens of this pair")]
+    LpCapReached { cap: Uint128 },
+
+    #[error("Liquidity can't be provided to an empty pool")]
+    EmptyPool {},
+}
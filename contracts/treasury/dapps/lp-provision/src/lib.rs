@@ -0,0 +1,10 @@
+mod commands;
+pub mod contract;
+pub mod error;
+mod lp_msg;
+pub mod queries;
+pub mod state;
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests;
@@ -0,0 +1,76 @@
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Decimal, Deps, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use terraswap::asset::{Asset, AssetInfo};
+use terraswap::pair::{Cw20HookMsg, ExecuteMsg as TerraswapExecuteMsg};
+
+use white_whale::astroport_helper::ExecuteMsg as AstroportExecuteMsg;
+use white_whale::dapps::lp_provision::msg::Dex;
+
+/// Constructs the deposit msgs for a pair on `dex`
+/// Cw20 assets are provided through an allowance, native assets are sent along after tax
+pub fn deposit_lp_msg(
+    deps: Deps,
+    dex: &Dex,
+    mut assets: [Asset; 2],
+    pair_addr: Addr,
+    slippage_tolerance: Option<Decimal>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut coins: Vec<Coin> = vec![];
+    for asset in assets.iter_mut() {
+        match &asset.info {
+            AssetInfo::Token { contract_addr } => {
+                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: pair_addr.to_string(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }));
+            }
+            AssetInfo::NativeToken { .. } => {
+                let coin = asset.deduct_tax(&deps.querier)?;
+                asset.amount = coin.amount;
+                coins.push(coin);
+            }
+        }
+    }
+
+    let lp_msg = match dex {
+        Dex::Terraswap => to_binary(&TerraswapExecuteMsg::ProvideLiquidity {
+            assets,
+            slippage_tolerance,
+            receiver: None,
+        })?,
+        Dex::Astroport => to_binary(&AstroportExecuteMsg::ProvideLiquidity {
+            assets,
+            slippage_tolerance,
+            auto_stake: None,
+            receiver: None,
+        })?,
+    };
+
+    msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pair_addr.to_string(),
+        msg: lp_msg,
+        funds: coins,
+    }));
+
+    Ok(msgs)
+}
+
+/// Constructs the msg that sends `amount` LP tokens to the pair to withdraw the liquidity
+/// Terraswap and Astroport pairs share the withdraw hook
+pub fn withdraw_lp_msg(lp_token: Addr, pair_addr: Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: lp_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: pair_addr.to_string(),
+            amount,
+            msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {})?,
+        })?,
+        funds: vec![],
+    }))
+}
@@ -0,0 +1,63 @@
+use cosmwasm_std::{Deps, Order, StdResult, Uint128};
+use terraswap::asset::Asset;
+use terraswap::querier::query_token_balance;
+
+use white_whale::dapps::lp_provision::msg::{PairsResponse, PositionResponse};
+use white_whale::query::vault::query_holding_value;
+use white_whale::treasury::dapp_base::state::BASESTATE;
+
+use crate::commands::{lp_token_address, query_pair_pool};
+use crate::state::PAIRS;
+
+/// Returns the configured pairs
+pub fn query_pairs(deps: Deps) -> StdResult<PairsResponse> {
+    let pairs = PAIRS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (pool_id, config) = item?;
+            Ok((String::from_utf8(pool_id)?, config))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(PairsResponse { pairs })
+}
+
+/// Returns the LP tokens held by the treasury, the pool assets they represent and their value
+/// The LP token has to be a vault asset of the treasury to be valued
+pub fn query_position(deps: Deps, pool_id: String) -> StdResult<PositionResponse> {
+    let pair = PAIRS.load(deps.storage, &pool_id)?;
+    let state = BASESTATE.load(deps.storage)?;
+    let lp_token = lp_token_address(deps, &state, &pair.lp_token_id)?;
+    let lp_tokens = query_token_balance(
+        &deps.querier,
+        lp_token.clone(),
+        state.treasury_address.clone(),
+    )?;
+
+    let pair_address = state.memory.query_contract(deps, &pool_id)?;
+    let pool_info = query_pair_pool(deps, &pair.dex, pair_address)?;
+    let assets = pool_info
+        .assets
+        .iter()
+        .map(|asset| Asset {
+            info: asset.info.clone(),
+            amount: if pool_info.total_share.is_zero() {
+                Uint128::zero()
+            } else {
+                asset
+                    .amount
+                    .multiply_ratio(lp_tokens, pool_info.total_share)
+            },
+        })
+        .collect();
+
+    Ok(PositionResponse {
+        lp_tokens,
+        assets,
+        value: query_holding_value(
+            deps,
+            &state.treasury_address,
+            lp_token.to_string(),
+            lp_tokens,
+        )?,
+    })
+}
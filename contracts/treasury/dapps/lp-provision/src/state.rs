@@ -0,0 +1,6 @@
+use cw_storage_plus::Map;
+
+use white_whale::dapps::lp_provision::msg::PairConfig;
+
+/// Configs of the pairs liquidity can be provided to, keyed by the memory id of the pair
+pub const PAIRS: Map<&str, PairConfig> = Map::new("pairs");
@@ -0,0 +1,183 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_binary, Decimal, DepsMut, Uint128};
+use cw_controllers::AdminError;
+
+use white_whale::dapps::lp_provision::msg::{Dex, ExecuteMsg, PairConfig, PairsResponse, QueryMsg};
+use white_whale::treasury::dapp_base::common_test::{
+    MEMORY_CONTRACT, TEST_CREATOR, TRADER_CONTRACT, TREASURY_CONTRACT,
+};
+use white_whale::treasury::dapp_base::error::BaseDAppError;
+use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::LpProvisionError;
+use crate::state::PAIRS;
+
+fn mock_instantiate(deps: DepsMut) {
+    let msg = BaseInstantiateMsg {
+        memory_addr: MEMORY_CONTRACT.to_string(),
+        treasury_address: TREASURY_CONTRACT.to_string(),
+        trader: TRADER_CONTRACT.to_string(),
+    };
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps, mock_env(), info, msg).expect("contract successfully handles InstantiateMsg");
+}
+
+fn mock_pair_config() -> PairConfig {
+    PairConfig {
+        dex: Dex::Terraswap,
+        lp_token_id: "whale_ust".to_string(),
+        max_slippage: Decimal::percent(1),
+        lp_cap: None,
+    }
+}
+
+#[test]
+pub fn test_no_pairs_after_instantiate() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let res: PairsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Pairs {}).unwrap()).unwrap();
+    assert!(res.pairs.is_empty());
+}
+
+#[test]
+pub fn test_set_pair_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::SetPair {
+        pool_id: "whale_ust_pair".to_string(),
+        config: mock_pair_config(),
+    };
+
+    let info = mock_info("unauthorized", &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(LpProvisionError::Admin(AdminError::NotAdmin {})) => (),
+        Ok(_) => panic!("Should return unauthorized Error, AdminError::NotAdmin"),
+        _ => panic!("Should return unauthorized Error, AdminError::NotAdmin"),
+    }
+}
+
+#[test]
+pub fn test_remove_unregistered_pair() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::RemovePair {
+        pool_id: "whale_ust_pair".to_string(),
+    };
+
+    let info = mock_info(TEST_CREATOR, &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(LpProvisionError::NotRegistered { .. }) => (),
+        Ok(_) => panic!("Should return NotRegistered Error"),
+        _ => panic!("Should return NotRegistered Error"),
+    }
+}
+
+#[test]
+pub fn test_provide_liquidity_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::ProvideLiquidity {
+        pool_id: "whale_ust_pair".to_string(),
+        assets: vec![
+            ("whale".to_string(), Uint128::from(1u64)),
+            ("ust".to_string(), Uint128::from(1u64)),
+        ],
+        slippage_tolerance: None,
+    };
+
+    let info = mock_info("unauthorized", &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(LpProvisionError::BaseDAppError(BaseDAppError::Unauthorized {})) => (),
+        Ok(_) => panic!("Should return unauthorized Error, DAppError::Unauthorized"),
+        _ => panic!("Should return unauthorized Error, DAppError::Unauthorized"),
+    }
+}
+
+#[test]
+pub fn test_provide_liquidity_unregistered_pair() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    let msg = ExecuteMsg::ProvideLiquidity {
+        pool_id: "whale_ust_pair".to_string(),
+        assets: vec![
+            ("whale".to_string(), Uint128::from(1u64)),
+            ("ust".to_string(), Uint128::from(1u64)),
+        ],
+        slippage_tolerance: None,
+    };
+
+    let info = mock_info(TRADER_CONTRACT, &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(LpProvisionError::NotRegistered { .. }) => (),
+        Ok(_) => panic!("Should return NotRegistered Error"),
+        _ => panic!("Should return NotRegistered Error"),
+    }
+}
+
+#[test]
+pub fn test_provide_liquidity_slippage_too_high() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    PAIRS
+        .save(&mut deps.storage, "whale_ust_pair", &mock_pair_config())
+        .unwrap();
+    let msg = ExecuteMsg::ProvideLiquidity {
+        pool_id: "whale_ust_pair".to_string(),
+        assets: vec![
+            ("whale".to_string(), Uint128::from(1u64)),
+            ("ust".to_string(), Uint128::from(1u64)),
+        ],
+        slippage_tolerance: Some(Decimal::percent(2)),
+    };
+
+    let info = mock_info(TRADER_CONTRACT, &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(LpProvisionError::SlippageTooHigh { .. }) => (),
+        Ok(_) => panic!("Should return SlippageTooHigh Error"),
+        _ => panic!("Should return SlippageTooHigh Error"),
+    }
+}
+
+#[test]
+pub fn test_provide_liquidity_not_two_assets() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    PAIRS
+        .save(&mut deps.storage, "whale_ust_pair", &mock_pair_config())
+        .unwrap();
+    let msg = ExecuteMsg::ProvideLiquidity {
+        pool_id: "whale_ust_pair".to_string(),
+        assets: vec![("whale".to_string(), Uint128::from(1u64))],
+        slippage_tolerance: None,
+    };
+
+    let info = mock_info(TRADER_CONTRACT, &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(LpProvisionError::NotTwoAssets {}) => (),
+        Ok(_) => panic!("Should return NotTwoAssets Error"),
+        _ => panic!("Should return NotTwoAssets Error"),
+    }
+}
+
+#[test]
+pub fn test_withdraw_liquidity_zero_amount() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    PAIRS
+        .save(&mut deps.storage, "whale_ust_pair", &mock_pair_config())
+        .unwrap();
+    let msg = ExecuteMsg::WithdrawLiquidity {
+        pool_id: "whale_ust_pair".to_string(),
+        amount: Uint128::zero(),
+    };
+
+    let info = mock_info(TRADER_CONTRACT, &[]);
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(LpProvisionError::ZeroAmount { .. }) => (),
+        Ok(_) => panic!("Should return ZeroAmount Error"),
+        _ => panic!("Should return ZeroAmount Error"),
+    }
+}
@@ -7,13 +7,13 @@ use terraswap::asset::Asset;
 use terraswap::pair::{Cw20HookMsg, PoolResponse};
 
 use white_whale::query::terraswap::{query_asset_balance, query_pool};
+use white_whale::treasury::dapp_base::cache::query_cached_asset;
 use white_whale::treasury::dapp_base::common::PAIR_POSTFIX;
 use white_whale::treasury::dapp_base::error::BaseDAppError;
-use white_whale::treasury::dapp_base::state::BASESTATE;
-use white_whale::treasury::msg::send_to_treasury;
+use white_whale::treasury::helpers::trader_action;
 use white_whale::treasury::vault_assets::get_identifier;
 
-use crate::contract::TerraswapResult;
+use crate::contract::{TerraswapDApp, TerraswapResult};
 use crate::error::TerraswapError;
 use crate::terraswap_msg::{asset_into_swap_msg, deposit_lp_msg};
 use crate::utils::has_sufficient_balance;
@@ -21,16 +21,14 @@ use crate::utils::has_sufficient_balance;
 /// Constructs and forwards the terraswap provide_liquidity message
 pub fn provide_liquidity(
     deps: Deps,
+    env: &Env,
     msg_info: MessageInfo,
     main_asset_id: String,
     pool_id: String,
     amount: Uint128,
 ) -> TerraswapResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Check if caller is trader.
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {}.into());
-    }
+    // Check if caller is trader
+    let state = TerraswapDApp::assert_trader(deps, &msg_info)?;
 
     let treasury_address = &state.treasury_address;
 
@@ -44,7 +42,7 @@ pub fn provide_liquidity(
 
     let ratio = Decimal::from_ratio(asset_1.amount, asset_2.amount);
 
-    let main_asset_info = state.memory.query_asset(deps, &main_asset_id)?;
+    let main_asset_info = query_cached_asset(deps, env, &state.memory, &main_asset_id)?;
     let main_asset = Asset {
         info: main_asset_info,
         amount,
@@ -77,23 +75,21 @@ pub fn provide_liquidity(
     let msgs: Vec<CosmosMsg> =
         deposit_lp_msg(deps, [second_asset, first_asset], pair_address, None)?;
 
-    Ok(Response::new().add_message(send_to_treasury(msgs, treasury_address)?))
+    Ok(Response::new().add_message(trader_action(msgs, treasury_address)?))
 }
 
 /// Constructs and forwards the terraswap provide_liquidity message
 /// You can provide custom asset amounts
 pub fn detailed_provide_liquidity(
     deps: Deps,
+    env: &Env,
     msg_info: MessageInfo,
     assets: Vec<(String, Uint128)>,
     pool_id: String,
     slippage_tolerance: Option<Decimal>,
 ) -> TerraswapResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Check if caller is trader.
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {}.into());
-    }
+    // Check if caller is trader
+    let state = TerraswapDApp::assert_trader(deps, &msg_info)?;
 
     if assets.len() != 2 {
         return Err(TerraswapError::NotTwoAssets {});
@@ -118,7 +114,7 @@ pub fn detailed_provide_liquidity(
             return Err(TerraswapError::ZeroAmount { asset: asset_token });
         }
 
-        let asset_info = state.memory.query_asset(deps, &asset_token)?;
+        let asset_info = query_cached_asset(deps, env, &state.memory, &asset_token)?;
         // Check if pool contains the asset
         if pool_info.assets.iter().any(|a| a.info == asset_info) {
             let asset_balance = query_asset_balance(deps, &asset_info, treasury_address.clone())?;
@@ -141,21 +137,19 @@ pub fn detailed_provide_liquidity(
     // increase allowance msg for each asset.
     let msgs: Vec<CosmosMsg> = deposit_lp_msg(deps, asset_array, pair_address, slippage_tolerance)?;
 
-    Ok(Response::new().add_message(send_to_treasury(msgs, treasury_address)?))
+    Ok(Response::new().add_message(trader_action(msgs, treasury_address)?))
 }
 
 /// Constructs withdraw liquidity msg and forwards it to treasury
 pub fn withdraw_liquidity(
     deps: Deps,
+    env: &Env,
     msg_info: MessageInfo,
     lp_token_id: String,
     amount: Uint128,
 ) -> TerraswapResult {
-    let state = BASESTATE.load(deps.storage)?;
-    // Sender must be trader
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {}.into());
-    }
+    // Check if caller is trader
+    let state = TerraswapDApp::assert_trader(deps, &msg_info)?;
     // Make sure the amount to withdraw is greater than zero since zero transfers fail
     if amount == Uint128::zero() {
         return Err(TerraswapError::ZeroAmount { asset: lp_token_id });
@@ -164,7 +158,7 @@ pub fn withdraw_liquidity(
     let treasury_address = &state.treasury_address;
 
     // Get lp token address
-    let lp_token = &state.memory.query_asset(deps, &lp_token_id)?;
+    let lp_token = &query_cached_asset(deps, env, &state.memory, &lp_token_id)?;
     let lp_token_address = get_identifier(lp_token);
     // Get pair address
     let pair_address = state
@@ -172,7 +166,7 @@ pub fn withdraw_liquidity(
         .query_contract(deps, &(lp_token_id.clone() + PAIR_POSTFIX))?;
 
     // Check if the treasury has enough lp tokens
-    has_sufficient_balance(deps, &state.memory, &lp_token_id, treasury_address, amount)?;
+    has_sufficient_balance(deps, lp_token, treasury_address, amount)?;
 
     // Msg that gets called on the pair address.
     let withdraw_msg: Binary = to_binary(&Cw20HookMsg::WithdrawLiquidity {})?;
@@ -191,14 +185,14 @@ pub fn withdraw_liquidity(
         funds: vec![],
     });
 
-    Ok(Response::new().add_message(send_to_treasury(vec![lp_call], treasury_address)?))
+    Ok(Response::new().add_message(trader_action(vec![lp_call], treasury_address)?))
 }
 
 /// Function constructs terraswap swap messages and forwards them to the treasury
 #[allow(clippy::too_many_arguments)]
 pub fn terraswap_swap(
     deps: Deps,
-    _env: Env,
+    env: &Env,
     msg_info: MessageInfo,
     offer_id: String,
     pool_id: String,
@@ -206,21 +200,17 @@ pub fn terraswap_swap(
     max_spread: Option<Decimal>,
     belief_price: Option<Decimal>,
 ) -> TerraswapResult {
-    let state = BASESTATE.load(deps.storage)?;
+    // Check if caller is trader
+    let state = TerraswapDApp::assert_trader(deps, &msg_info)?;
     let treasury_address = state.treasury_address;
 
-    // Check if caller is trader
-    if msg_info.sender != state.trader {
-        return Err(BaseDAppError::Unauthorized {}.into());
-    }
+    let offer_asset_info = query_cached_asset(deps, env, &state.memory, &offer_id)?;
 
     // Check if treasury has enough to swap
-    has_sufficient_balance(deps, &state.memory, &offer_id, &treasury_address, amount)?;
+    has_sufficient_balance(deps, &offer_asset_info, &treasury_address, amount)?;
 
     let pair_address = state.memory.query_contract(deps, &pool_id)?;
 
-    let offer_asset_info = state.memory.query_asset(deps, &offer_id)?;
-
     let swap_msg = vec![asset_into_swap_msg(
         deps,
         pair_address,
@@ -234,5 +224,5 @@ pub fn terraswap_swap(
         None,
     )?];
 
-    Ok(Response::new().add_message(send_to_treasury(swap_msg, &treasury_address)?))
+    Ok(Response::new().add_message(trader_action(swap_msg, &treasury_address)?))
 }
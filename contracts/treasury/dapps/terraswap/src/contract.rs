@@ -3,35 +3,25 @@ use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Respons
 use crate::commands;
 use crate::error::TerraswapError;
 use white_whale::dapps::terraswap::msg::{ExecuteMsg, QueryMsg};
-use white_whale::memory::item::Memory;
-use white_whale::treasury::dapp_base::commands as dapp_base_commands;
-use white_whale::treasury::dapp_base::common::BaseDAppResult;
+use white_whale::treasury::dapp_base::dapp::DAppBase;
 use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
-use white_whale::treasury::dapp_base::queries as dapp_base_queries;
-use white_whale::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
 
 pub type TerraswapResult = Result<Response, TerraswapError>;
 
+pub struct TerraswapDApp;
+impl DAppBase for TerraswapDApp {
+    type Error = TerraswapError;
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     msg: BaseInstantiateMsg,
-) -> BaseDAppResult {
-    let state = BaseState {
-        treasury_address: deps.api.addr_validate(&msg.treasury_address)?,
-        trader: deps.api.addr_validate(&msg.trader)?,
-        memory: Memory {
-            address: deps.api.addr_validate(&msg.memory_addr)?,
-        },
-    };
-
-    // Store the initial config
-    BASESTATE.save(deps.storage, &state)?;
-
-    // Setup the admin as the creator of the contract
-    ADMIN.set(deps, Some(info.sender))?;
+) -> TerraswapResult {
+    // Store the initial config and setup the admin as the creator of the contract
+    TerraswapDApp::instantiate_base(deps, &info, msg)?;
 
     Ok(Response::default())
 }
@@ -43,13 +33,14 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> T
             pool_id,
             main_asset_id,
             amount,
-        } => commands::provide_liquidity(deps.as_ref(), info, main_asset_id, pool_id, amount),
+        } => commands::provide_liquidity(deps.as_ref(), &env, info, main_asset_id, pool_id, amount),
         ExecuteMsg::DetailedProvideLiquidity {
             pool_id,
             assets,
             slippage_tolerance,
         } => commands::detailed_provide_liquidity(
             deps.as_ref(),
+            &env,
             info,
             assets,
             pool_id,
@@ -58,7 +49,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> T
         ExecuteMsg::WithdrawLiquidity {
             lp_token_id,
             amount,
-        } => commands::withdraw_liquidity(deps.as_ref(), info, lp_token_id, amount),
+        } => commands::withdraw_liquidity(deps.as_ref(), &env, info, lp_token_id, amount),
         ExecuteMsg::SwapAsset {
             offer_id,
             pool_id,
@@ -67,7 +58,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> T
             belief_price,
         } => commands::terraswap_swap(
             deps.as_ref(),
-            env,
+            &env,
             info,
             offer_id,
             pool_id,
@@ -75,24 +66,13 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> T
             max_spread,
             belief_price,
         ),
-        ExecuteMsg::Base(message) => {
-            from_base_dapp_result(dapp_base_commands::handle_base_message(deps, info, message))
-        }
+        ExecuteMsg::Base(message) => TerraswapDApp::execute_base(deps, env, info, message),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Base(message) => dapp_base_queries::handle_base_query(deps, message),
-    }
-}
-
-/// Required to convert BaseDAppResult into TerraswapResult
-/// Can't implement the From trait directly
-fn from_base_dapp_result(result: BaseDAppResult) -> TerraswapResult {
-    match result {
-        Err(e) => Err(e.into()),
-        Ok(r) => Ok(r),
+        QueryMsg::Base(message) => TerraswapDApp::query_base(deps, message),
     }
 }
@@ -1,20 +1,17 @@
 use cosmwasm_std::{Addr, Deps, Uint128};
-use white_whale::memory::item::Memory;
+use terraswap::asset::AssetInfo;
 use white_whale::query::terraswap::query_asset_balance;
 use white_whale::treasury::dapp_base::error::BaseDAppError;
 
-/// Checks if the given address has enough tokens with a given offer_id
+/// Checks if the given address has enough tokens of the given asset
 pub fn has_sufficient_balance(
     deps: Deps,
-    memory: &Memory,
-    offer_id: &str,
+    info: &AssetInfo,
     address: &Addr,
     required: Uint128,
 ) -> Result<(), BaseDAppError> {
-    // Load asset
-    let info = memory.query_asset(deps, offer_id)?;
     // Get balance and check
-    if query_asset_balance(deps, &info, address.clone())? < required {
+    if query_asset_balance(deps, info, address.clone())? < required {
         return Err(BaseDAppError::Broke {});
     }
     Ok(())
@@ -1,22 +1,37 @@
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, Uint128,
-    WasmMsg,
+    from_binary, Addr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, Storage, Uint128,
 };
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw20::Cw20ReceiveMsg;
 use terraswap::asset::{Asset, AssetInfo};
 
-use white_whale::deposit_info::DepositInfo;
+use white_whale::address::validate_addr;
+use white_whale::deposit_info::{AcceptedAsset, DepositInfo};
+use white_whale::events::{share_exchange_rate, VaultEvent};
+use white_whale::pause::{assert_not_paused, PauseScope};
+use white_whale::swap::{Dex, Swap};
+use white_whale::tax::deduct_tax;
+use white_whale::token::{burn_msg, mint_msg, transfer_from_msg};
+use white_whale::treasury::dapp_base::common::PAIR_POSTFIX;
 use white_whale::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
-use white_whale::treasury::msg::send_to_treasury;
+use white_whale::treasury::helpers::trader_action;
 
 use crate::contract::VaultResult;
 use crate::error::VaultError;
-use crate::state::{Pool, State, FEE, POOL, STATE};
+use crate::state::{
+    DepositCaps, Pool, State, ACCEPTED_DEPOSITS, CLAIMS, DEPOSITS, DEPOSIT_CAPS, DEPOSIT_WHITELIST,
+    EMERGENCY_MODE, FEE, FEE_RECIPIENT, OPEN_DEPOSITS, POOL, REBALANCE_POLICY, STATE,
+    UNBONDING_PERIOD,
+};
 use terraswap::querier::query_supply;
-use white_whale::dapps::vault::msg::DepositHookMsg;
+use white_whale::dapps::vault::msg::{Claim, DepositHookMsg, RebalancePolicy};
 use white_whale::fee::Fee;
 use white_whale::query::terraswap::query_asset_balance;
-use white_whale::query::vault::query_total_value;
+use white_whale::query::vault::{query_holding_value, query_total_value};
+use white_whale::treasury::vault_assets::get_identifier;
+
+/// Maximum withdrawal fee in percent
+const MAX_WITHDRAW_FEE: u64 = 20;
 
 /// handler function invoked when the vault dapp contract receives
 /// a transaction. In this case it is triggered when either a LP tokens received
@@ -28,16 +43,28 @@ pub fn receive_cw20(
     cw20_msg: Cw20ReceiveMsg,
 ) -> VaultResult {
     match from_binary(&cw20_msg.msg)? {
-        DepositHookMsg::WithdrawLiquidity {} => {
+        DepositHookMsg::WithdrawLiquidity {
+            min_out,
+            payout_asset,
+            max_spread,
+        } => {
             let state: State = STATE.load(deps.storage)?;
             if msg_info.sender != state.liquidity_token_addr {
                 return Err(VaultError::NotLPToken {
                     token: msg_info.sender.to_string(),
                 });
             }
-            try_withdraw_liquidity(deps, env, cw20_msg.sender, cw20_msg.amount)
+            try_withdraw_liquidity(
+                deps,
+                env,
+                cw20_msg.sender,
+                cw20_msg.amount,
+                min_out,
+                payout_asset,
+                max_spread,
+            )
         }
-        DepositHookMsg::ProvideLiquidity {} => {
+        DepositHookMsg::ProvideLiquidity { recipient } => {
             // Construct deposit asset
             let asset = Asset {
                 info: AssetInfo::Token {
@@ -45,24 +72,37 @@ pub fn receive_cw20(
                 },
                 amount: cw20_msg.amount,
             };
-            try_provide_liquidity(deps, msg_info, asset, Some(cw20_msg.sender))
+            try_provide_liquidity(deps, env, msg_info, asset, Some(cw20_msg.sender), recipient)
+        }
+        DepositHookMsg::EmergencyWithdraw {} => {
+            let state: State = STATE.load(deps.storage)?;
+            if msg_info.sender != state.liquidity_token_addr {
+                return Err(VaultError::NotLPToken {
+                    token: msg_info.sender.to_string(),
+                });
+            }
+            try_emergency_withdraw(deps, env, cw20_msg.sender, cw20_msg.amount)
         }
     }
 }
 
 /// Called when either providing liquidity with a native token or when providing liquidity
-/// with a CW20.
+/// with a CW20. The LP tokens are minted to `recipient` if set, the deposit caps and the
+/// whitelist apply to the recipient as the owner of the position.
 pub fn try_provide_liquidity(
     deps: DepsMut,
+    env: Env,
     msg_info: MessageInfo,
     asset: Asset,
     sender: Option<String>,
+    recipient: Option<String>,
 ) -> VaultResult {
+    assert_not_emergency(deps.storage)?;
+    assert_not_paused(deps.storage, PauseScope::Deposits)?;
     // Load all needed states
     let pool: Pool = POOL.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
     let base_state: BaseState = BASESTATE.load(deps.storage)?;
-    let memory = base_state.memory;
 
     // Get the liquidity provider address
     let liq_provider = match sender {
@@ -80,62 +120,226 @@ pub fn try_provide_liquidity(
         }
     };
 
-    // Get all the required asset information from the memory contract
-    let assets = memory.query_assets(deps.as_ref(), &pool.assets)?;
-
-    // Construct deposit info
-    let deposit_info = DepositInfo {
-        asset_info: assets.get(&pool.deposit_asset).unwrap().clone(),
+    // Get the address the LP tokens are minted to
+    let recipient = match recipient {
+        Some(recipient) => validate_addr(deps.api, "recipient", &recipient)?,
+        None => liq_provider.clone(),
     };
 
-    // Assert deposited asset and claimed asset infos are the same
-    deposit_info.assert(&asset.info)?;
+    // Assert the deposited asset is accepted and at least its minimum deposit
+    let deposit_info = load_deposit_info(deps.as_ref(), &pool, &base_state)?;
+    deposit_info.assert_deposit(&asset)?;
 
     // Init vector for logging
     let attrs = vec![
         ("Action:", String::from("Deposit to vault")),
         ("Received funds:", asset.to_string()),
+        ("Depositor:", liq_provider.to_string()),
+        ("Recipient:", recipient.to_string()),
     ];
 
-    // Received deposit to vault
-    let deposit: Uint128 = asset.amount;
+    // Received deposit to vault, other accepted assets are valued in the deposit asset
+    let deposit = deposit_value(deps.as_ref(), &deposit_info, &base_state, &asset)?;
 
-    // Get total value in Vault
+    // Get total value in Vault, the deposit is only sent to the vault after this call
     let value = query_total_value(deps.as_ref(), &base_state.treasury_address)?;
+
+    // Enforce the deposit caps
+    track_deposit(deps.storage, &recipient, value, deposit)?;
+
     // Get total supply of LP tokens and calculate share
     let total_share = query_supply(&deps.querier, state.liquidity_token_addr.clone())?;
 
-    let share = if total_share == Uint128::zero() || value.checked_sub(deposit)? == Uint128::zero()
-    {
+    let share = compute_deposit_share(total_share, value, deposit)?;
+
+    // mint LP token to the recipient
+    let mint_lp = mint_msg(&state.liquidity_token_addr, &recipient, share)?;
+    let vault_event =
+        VaultEvent::deposit(&env.contract.address, &recipient, &[asset.clone()], share)
+            .exchange_rate(share_exchange_rate(value, total_share));
+
+    // Send received asset to the vault.
+    let send_to_vault = asset.into_msg(&deps.querier, base_state.treasury_address)?;
+
+    let response = Response::new()
+        .add_attributes(attrs)
+        .add_event(vault_event.into())
+        .add_message(mint_lp)
+        .add_message(send_to_vault);
+
+    Ok(response)
+}
+
+/// Builds the deposit info of the vault from the deposit asset and the accepted deposits
+pub fn load_deposit_info(
+    deps: Deps,
+    pool: &Pool,
+    base_state: &BaseState,
+) -> StdResult<DepositInfo> {
+    let memory = &base_state.memory;
+    let mut deposit_info = DepositInfo::new(memory.query_asset(deps, &pool.deposit_asset)?);
+    let accepted_deposits = ACCEPTED_DEPOSITS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Vec<u8>, Uint128)>>>()?;
+    for (key, min_deposit) in accepted_deposits {
+        let asset_id = String::from_utf8(key)?;
+        if asset_id == pool.deposit_asset {
+            deposit_info.min_deposit = min_deposit;
+        } else {
+            deposit_info.other_assets.push(AcceptedAsset {
+                info: memory.query_asset(deps, &asset_id)?,
+                min_deposit,
+            });
+        }
+    }
+    Ok(deposit_info)
+}
+
+/// Returns the value of an accepted deposit in the deposit asset
+pub fn deposit_value(
+    deps: Deps,
+    deposit_info: &DepositInfo,
+    base_state: &BaseState,
+    asset: &Asset,
+) -> StdResult<Uint128> {
+    if deposit_info.is_main_asset(&asset.info) {
+        return Ok(asset.amount);
+    }
+    query_holding_value(
+        deps,
+        &base_state.treasury_address,
+        get_identifier(&asset.info).clone(),
+        asset.amount,
+    )
+}
+
+/// Computes the amount of LP tokens minted for a deposit of the deposit asset.
+/// `value` is the vault value before the deposit.
+pub fn compute_deposit_share(
+    total_share: Uint128,
+    value: Uint128,
+    deposit: Uint128,
+) -> StdResult<Uint128> {
+    let share = if total_share.is_zero() || value.is_zero() {
         // Initial share = deposit amount
         deposit
     } else {
         // lt: liquidity token
         // lt_to_receive = deposit * lt_price
-        // lt_to_receive = deposit * lt_supply / previous_total_vault_value )
-        // lt_to_receive = deposit * ( lt_supply / ( current_total_vault_value - deposit ) )
-        deposit.multiply_ratio(total_share, value - deposit)
+        // lt_to_receive = deposit * lt_supply / previous_total_vault_value
+        deposit.multiply_ratio(total_share, value)
     };
+    Ok(share)
+}
 
-    // mint LP token to liq_provider
-    let mint_lp = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: state.liquidity_token_addr.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Mint {
-            recipient: liq_provider.to_string(),
-            amount: share,
-        })?,
-        funds: vec![],
-    });
+/// Provides liquidity with several assets at once. Each asset is valued through the treasury and
+/// LP tokens are minted for the combined value. Cw20 assets are transferred from the sender,
+/// who has to set an allowance for this contract first.
+pub fn try_provide_liquidity_multi(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    assets: Vec<Asset>,
+) -> VaultResult {
+    assert_not_emergency(deps.storage)?;
+    assert_not_paused(deps.storage, PauseScope::Deposits)?;
+    // Load all needed states
+    let pool: Pool = POOL.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let base_state: BaseState = BASESTATE.load(deps.storage)?;
 
-    // Send received asset to the vault.
-    let send_to_vault = asset.into_msg(&deps.querier, base_state.treasury_address)?;
+    // Only the claimable assets of the pool can be deposited
+    let pool_assets = base_state
+        .memory
+        .query_assets(deps.as_ref(), &pool.assets)?;
 
-    let response = Response::new()
+    let mut attrs = vec![("Action:", String::from("Deposit to vault"))];
+    let mut deposit_value = Uint128::zero();
+    let mut deposit_msgs: Vec<CosmosMsg> = vec![];
+    for (i, asset) in assets.iter().enumerate() {
+        if !pool_assets.values().any(|info| *info == asset.info)
+            || assets[..i].iter().any(|other| other.info == asset.info)
+        {
+            return Err(VaultError::InvalidDepositAsset {
+                asset: asset.info.to_string(),
+            });
+        }
+
+        deposit_value = deposit_value.checked_add(query_holding_value(
+            deps.as_ref(),
+            &base_state.treasury_address,
+            get_identifier(&asset.info).clone(),
+            asset.amount,
+        )?)?;
+
+        // Send received asset to the vault.
+        deposit_msgs.push(match &asset.info {
+            AssetInfo::NativeToken { .. } => {
+                asset.assert_sent_native_token_balance(&msg_info)?;
+                asset
+                    .clone()
+                    .into_msg(&deps.querier, base_state.treasury_address.clone())?
+            }
+            AssetInfo::Token { contract_addr } => transfer_from_msg(
+                &Addr::unchecked(contract_addr),
+                &msg_info.sender,
+                &base_state.treasury_address,
+                asset.amount,
+            )?,
+        });
+        attrs.push(("Received funds:", asset.to_string()));
+    }
+
+    // Get total value in Vault, the deposit is only sent to the vault after this call
+    let value = query_total_value(deps.as_ref(), &base_state.treasury_address)?;
+    track_deposit(deps.storage, &msg_info.sender, value, deposit_value)?;
+
+    // Get total supply of LP tokens and calculate share
+    let total_share = query_supply(&deps.querier, state.liquidity_token_addr.clone())?;
+    let share = compute_deposit_share(total_share, value, deposit_value)?;
+
+    // mint LP token to the depositor
+    let mint_lp = mint_msg(&state.liquidity_token_addr, &msg_info.sender, share)?;
+    let vault_event = VaultEvent::deposit(&env.contract.address, &msg_info.sender, &assets, share)
+        .exchange_rate(share_exchange_rate(value, total_share));
+
+    Ok(Response::new()
         .add_attributes(attrs)
+        .add_event(vault_event.into())
         .add_message(mint_lp)
-        .add_message(send_to_vault);
+        .add_messages(deposit_msgs))
+}
 
-    Ok(response)
+/// Checks a deposit against the whitelist and the deposit caps and records it.
+/// `value` is the vault value before the deposit.
+fn track_deposit(
+    storage: &mut dyn Storage,
+    depositor: &Addr,
+    value: Uint128,
+    deposit: Uint128,
+) -> Result<(), VaultError> {
+    let open_deposits = OPEN_DEPOSITS.may_load(storage)?.unwrap_or(true);
+    if !open_deposits && DEPOSIT_WHITELIST.may_load(storage, depositor)?.is_none() {
+        return Err(VaultError::DepositorNotWhitelisted {});
+    }
+
+    let caps = DEPOSIT_CAPS.may_load(storage)?.unwrap_or_default();
+    if let Some(cap) = caps.global {
+        if value.checked_add(deposit)? > cap {
+            return Err(VaultError::GlobalDepositCapReached { cap });
+        }
+    }
+    let deposited = DEPOSITS
+        .may_load(storage, depositor)?
+        .unwrap_or_default()
+        .checked_add(deposit)?;
+    if let Some(cap) = caps.per_address {
+        if deposited > cap {
+            return Err(VaultError::AddressDepositCapReached { cap });
+        }
+    }
+    DEPOSITS.save(storage, depositor, &deposited)?;
+    Ok(())
 }
 
 /// Attempt to withdraw deposits. Fees are calculated and deducted in liquidity tokens.
@@ -144,17 +348,17 @@ pub fn try_provide_liquidity(
 /// Luna holdings are not eligible for withdrawal.
 pub fn try_withdraw_liquidity(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     sender: String,
     amount: Uint128,
+    min_out: Option<Vec<Asset>>,
+    payout_asset: Option<String>,
+    max_spread: Option<Decimal>,
 ) -> VaultResult {
-    let pool: Pool = POOL.load(deps.storage)?;
+    assert_not_paused(deps.storage, PauseScope::Withdrawals)?;
     let state: State = STATE.load(deps.storage)?;
     let base_state: BaseState = BASESTATE.load(deps.storage)?;
-    let memory = base_state.memory;
     let fee: Fee = FEE.load(deps.storage)?;
-    // Get assets
-    let assets = memory.query_assets(deps.as_ref(), &pool.assets)?;
 
     // Logging var
     let mut attrs = vec![
@@ -162,17 +366,39 @@ pub fn try_withdraw_liquidity(
         ("Received liquidity tokens:", amount.to_string()),
     ];
 
-    // Calculate share of pool and requested pool value
-    let total_share: Uint128 = query_supply(&deps.querier, state.liquidity_token_addr.clone())?;
+    // Share price before the withdrawal
+    let total_share = query_supply(&deps.querier, state.liquidity_token_addr.clone())?;
+    let value = query_total_value(deps.as_ref(), &base_state.treasury_address)?;
 
-    // Get treasury fee in LP tokens
-    let treasury_fee = fee.compute(amount);
+    // Get treasury fee in LP tokens and the assets to return
+    let (treasury_fee, pay_back_assets) = compute_withdrawal(deps.as_ref(), amount)?;
 
-    // Share with fee deducted.
-    let share_ratio: Decimal = Decimal::from_ratio(amount - treasury_fee, total_share);
+    // Protect against the treasury composition changing unfavorably
+    for min_asset in min_out.unwrap_or_default() {
+        let paid = pay_back_assets
+            .iter()
+            .find(|asset| asset.info == min_asset.info)
+            .map(|asset| asset.amount)
+            .unwrap_or_default();
+        if paid < min_asset.amount {
+            return Err(VaultError::MinOutNotReached {
+                expected: min_asset.to_string(),
+                paid,
+            });
+        }
+    }
+
+    // Unchecked ok as sender is already validated by VM
+    let vault_event = VaultEvent::withdraw(
+        &env.contract.address,
+        &Addr::unchecked(&sender),
+        &pay_back_assets,
+        amount - treasury_fee,
+    )
+    .exchange_rate(share_exchange_rate(value, total_share));
 
     // Init response
-    let mut response = Response::new();
+    let mut response = Response::new().add_event(vault_event.into());
 
     if !treasury_fee.is_zero() {
         // LP token fee
@@ -184,70 +410,331 @@ pub fn try_withdraw_liquidity(
         };
 
         // Construct treasury fee msg
-        let treasury_fee_msg = fee.msg(
-            deps.as_ref(),
-            lp_token_treasury_fee,
-            base_state.treasury_address.clone(),
-        )?;
+        let fee_recipient = FEE_RECIPIENT
+            .may_load(deps.storage)?
+            .unwrap_or_else(|| base_state.treasury_address.clone());
+        let treasury_fee_msg = fee.msg(deps.as_ref(), lp_token_treasury_fee, fee_recipient)?;
 
         // Transfer fee
         response = response.add_message(treasury_fee_msg);
     }
     attrs.push(("Treasury fee:", treasury_fee.to_string()));
 
-    // Get asset holdings of vault and calculate amount to return
-    let mut pay_back_assets: Vec<Asset> = vec![];
-    // Get asset holdings of vault and calculate amount to return
-    for (_, info) in assets.into_iter() {
-        pay_back_assets.push(Asset {
-            info: info.clone(),
-            amount: share_ratio
-                // query asset held in treasury
-                * query_asset_balance(
-                    deps.as_ref(),
-                    &info.clone(),
-                    base_state.treasury_address.clone(),
-                )
-                .unwrap(),
-        });
+    // LP burn msg
+    // Burn exludes fee
+    let burn_lp = burn_msg(&state.liquidity_token_addr, amount - treasury_fee)?;
+    response = response
+        .add_attribute("Action:", "Withdraw Liquidity")
+        // Burn LP tokens
+        .add_message(burn_lp);
+
+    let unbonding_period = UNBONDING_PERIOD.may_load(deps.storage)?.unwrap_or_default();
+    if unbonding_period == 0 {
+        let (refund_msgs, refund_attrs) = payout_msgs(
+            deps.as_ref(),
+            &base_state,
+            pay_back_assets,
+            payout_asset,
+            max_spread,
+            &sender,
+        )?;
+        attrs.extend(refund_attrs);
+
+        // Msg that gets called on the vault address
+        let vault_refund_msg = trader_action(refund_msgs, &base_state.treasury_address)?;
+
+        // Send treasury funds to owner
+        return Ok(response.add_message(vault_refund_msg).add_attributes(attrs));
     }
 
-    // Construct repay msgs
-    let mut refund_msgs: Vec<CosmosMsg> = vec![];
+    // Reject an invalid payout asset now rather than when claiming
+    resolve_payout_asset(deps.as_ref(), &base_state, payout_asset.clone())?;
+
+    // Move the assets out of the treasury so they no longer count towards its value
+    let mut claim_msgs: Vec<CosmosMsg> = vec![];
+    let mut claim_assets: Vec<Asset> = vec![];
     for asset in pay_back_assets.into_iter() {
-        if asset.amount != Uint128::zero() {
-            // Unchecked ok as sender is already validated by VM
-            refund_msgs.push(
+        if !asset.amount.is_zero() {
+            claim_msgs.push(
                 asset
                     .clone()
-                    .into_msg(&deps.querier, Addr::unchecked(sender.clone()))?,
+                    .into_msg(&deps.querier, env.contract.address.clone())?,
             );
-            attrs.push(("Repaying:", asset.to_string()));
+            // Natives arrive with tax deducted
+            let amount = match &asset.info {
+                AssetInfo::NativeToken { .. } => asset.deduct_tax(&deps.querier)?.amount,
+                AssetInfo::Token { .. } => asset.amount,
+            };
+            claim_assets.push(Asset {
+                info: asset.info,
+                amount,
+            });
         }
     }
 
-    // Msg that gets called on the vault address
-    let vault_refund_msg = send_to_treasury(refund_msgs, &base_state.treasury_address)?;
-
-    // LP burn msg
-    let burn_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: state.liquidity_token_addr.into(),
-        // Burn exludes fee
-        msg: to_binary(&Cw20ExecuteMsg::Burn {
-            amount: (amount - treasury_fee),
-        })?,
-        funds: vec![],
+    // Enqueue the claim
+    let release_height = env.block.height + unbonding_period;
+    // Unchecked ok as sender is already validated by VM
+    let claimer = Addr::unchecked(sender);
+    let mut claims = CLAIMS.may_load(deps.storage, &claimer)?.unwrap_or_default();
+    claims.push(Claim {
+        assets: claim_assets,
+        payout_asset,
+        max_spread,
+        release_height,
     });
+    CLAIMS.save(deps.storage, &claimer, &claims)?;
+    attrs.push(("Claimable at height:", release_height.to_string()));
 
     Ok(response
-        .add_attribute("Action:", "Withdraw Liquidity")
-        // Burn LP tokens
-        .add_message(burn_msg)
-        // Send treasury funds to owner
-        .add_message(vault_refund_msg)
+        .add_message(trader_action(claim_msgs, &base_state.treasury_address)?)
         .add_attributes(attrs))
 }
 
+/// Withdraws `amount` LP tokens in emergency mode. The sender gets the same share of the
+/// treasury balance of each vault asset as the share of the LP supply they burn. No fee is taken
+/// and no asset is valued, so the withdrawal works when the treasury valuation is broken.
+pub fn try_emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    sender: String,
+    amount: Uint128,
+) -> VaultResult {
+    assert_not_paused(deps.storage, PauseScope::Withdrawals)?;
+    if !EMERGENCY_MODE.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(VaultError::NotEmergencyMode {});
+    }
+    let pool: Pool = POOL.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
+    let base_state: BaseState = BASESTATE.load(deps.storage)?;
+    let assets = base_state
+        .memory
+        .query_assets(deps.as_ref(), &pool.assets)?;
+
+    // Supply before the burn
+    let total_share: Uint128 = query_supply(&deps.querier, state.liquidity_token_addr.clone())?;
+
+    let mut attrs = vec![
+        ("Action:", String::from("Emergency withdraw from vault")),
+        ("Received liquidity tokens:", amount.to_string()),
+    ];
+    let mut refund_msgs: Vec<CosmosMsg> = vec![];
+    let mut refund_assets: Vec<Asset> = vec![];
+    for (_, info) in assets.into_iter() {
+        let balance =
+            query_asset_balance(deps.as_ref(), &info, base_state.treasury_address.clone())?;
+        let asset = Asset {
+            info,
+            amount: balance.multiply_ratio(amount, total_share),
+        };
+        if asset.amount.is_zero() {
+            continue;
+        }
+        // Unchecked ok as sender is already validated by VM
+        refund_msgs.push(
+            asset
+                .clone()
+                .into_msg(&deps.querier, Addr::unchecked(&sender))?,
+        );
+        attrs.push(("Repaying:", asset.to_string()));
+        refund_assets.push(asset);
+    }
+
+    let burn_lp = burn_msg(&state.liquidity_token_addr, amount)?;
+    // The assets aren't valued, so there is no exchange rate
+    let vault_event = VaultEvent::withdraw(
+        &env.contract.address,
+        &Addr::unchecked(&sender),
+        &refund_assets,
+        amount,
+    );
+
+    Ok(Response::new()
+        .add_event(vault_event.into())
+        .add_message(burn_lp)
+        .add_message(trader_action(refund_msgs, &base_state.treasury_address)?)
+        .add_attributes(attrs))
+}
+
+/// Pays out all claims of the sender that finished unbonding
+pub fn try_claim(deps: DepsMut, env: Env, msg_info: MessageInfo) -> VaultResult {
+    assert_not_paused(deps.storage, PauseScope::Withdrawals)?;
+    let base_state: BaseState = BASESTATE.load(deps.storage)?;
+    let claims = CLAIMS
+        .may_load(deps.storage, &msg_info.sender)?
+        .unwrap_or_default();
+    let (ready, pending): (Vec<Claim>, Vec<Claim>) = claims
+        .into_iter()
+        .partition(|claim| claim.release_height <= env.block.height);
+    if ready.is_empty() {
+        return Err(VaultError::NothingToClaim {});
+    }
+
+    if pending.is_empty() {
+        CLAIMS.remove(deps.storage, &msg_info.sender);
+    } else {
+        CLAIMS.save(deps.storage, &msg_info.sender, &pending)?;
+    }
+
+    // The claimed assets are held by this contract
+    let mut response = Response::new().add_attribute("Action:", "Claim");
+    for claim in ready.into_iter() {
+        let (msgs, attrs) = payout_msgs(
+            deps.as_ref(),
+            &base_state,
+            claim.assets,
+            claim.payout_asset,
+            claim.max_spread,
+            msg_info.sender.as_str(),
+        )?;
+        response = response.add_messages(msgs).add_attributes(attrs);
+    }
+    Ok(response)
+}
+
+/// Returns the info of the payout asset, which has to be one of the vault assets
+fn resolve_payout_asset(
+    deps: Deps,
+    base_state: &BaseState,
+    payout_asset: Option<String>,
+) -> Result<Option<(String, AssetInfo)>, VaultError> {
+    let payout_asset = match payout_asset {
+        Some(payout_asset) => payout_asset,
+        None => return Ok(None),
+    };
+    let pool: Pool = POOL.load(deps.storage)?;
+    if !pool.assets.contains(&payout_asset) {
+        return Err(VaultError::InvalidPayoutAsset {
+            asset: payout_asset,
+        });
+    }
+    let info = base_state.memory.query_asset(deps, &payout_asset)?;
+    Ok(Some((payout_asset, info)))
+}
+
+/// Constructs the msgs paying `assets` to `recipient`, the assets other than the
+/// payout asset get swapped into it
+fn payout_msgs(
+    deps: Deps,
+    base_state: &BaseState,
+    assets: Vec<Asset>,
+    payout_asset: Option<String>,
+    max_spread: Option<Decimal>,
+    recipient: &str,
+) -> Result<(Vec<CosmosMsg>, Vec<(&'static str, String)>), VaultError> {
+    let payout = resolve_payout_asset(deps, base_state, payout_asset)?;
+    let pool: Pool = POOL.load(deps.storage)?;
+    let asset_names = base_state.memory.query_assets(deps, &pool.assets)?;
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut attrs = vec![];
+    for asset in assets.into_iter() {
+        if asset.amount.is_zero() {
+            continue;
+        }
+        match &payout {
+            Some((payout_name, payout_info)) if *payout_info != asset.info => {
+                let asset_name = asset_names
+                    .iter()
+                    .find(|(_, info)| **info == asset.info)
+                    .map(|(name, _)| name.clone())
+                    .unwrap_or_default();
+                let pair_address = query_payout_pair(deps, base_state, &asset_name, payout_name)?;
+                msgs.push(swap_msg(
+                    deps,
+                    pair_address,
+                    asset.clone(),
+                    max_spread,
+                    recipient.to_string(),
+                )?);
+                attrs.push(("Swapping:", asset.to_string()));
+            }
+            _ => {
+                // Unchecked ok as recipient is already validated by VM
+                msgs.push(
+                    asset
+                        .clone()
+                        .into_msg(&deps.querier, Addr::unchecked(recipient))?,
+                );
+                attrs.push(("Repaying:", asset.to_string()));
+            }
+        }
+    }
+    Ok((msgs, attrs))
+}
+
+/// Finds the pair registered in memory to swap `asset` into `payout_asset`, also used to find the
+/// pairs the assets are rebalanced through
+fn query_payout_pair(
+    deps: Deps,
+    base_state: &BaseState,
+    asset: &str,
+    payout_asset: &str,
+) -> Result<Addr, VaultError> {
+    let pair_names = [
+        format!("{}_{}{}", asset, payout_asset, PAIR_POSTFIX),
+        format!("{}_{}{}", payout_asset, asset, PAIR_POSTFIX),
+    ];
+    for pair_name in pair_names {
+        if let Ok(pair_address) = base_state.memory.query_contract(deps, &pair_name) {
+            return Ok(pair_address);
+        }
+    }
+    Err(VaultError::NoPayoutPair {
+        asset: asset.to_string(),
+    })
+}
+
+/// Constructs a terraswap swap msg on behalf of `to`
+fn swap_msg(
+    deps: Deps,
+    pair_address: Addr,
+    offer_asset: Asset,
+    max_spread: Option<Decimal>,
+    to: String,
+) -> StdResult<CosmosMsg> {
+    let swap = match offer_asset.info.clone() {
+        AssetInfo::NativeToken { denom } => {
+            // deduct tax first
+            let coin = deduct_tax(deps, Coin::new(offer_asset.amount.u128(), denom))?;
+            Swap::native(Dex::Terraswap, pair_address, coin)
+        }
+        AssetInfo::Token { .. } => Swap::new(Dex::Terraswap, pair_address, offer_asset),
+    };
+    swap.max_spread(max_spread).to(to).into_msg()
+}
+
+/// Computes the fee in LP tokens and the assets paid back when withdrawing `amount` LP tokens
+pub fn compute_withdrawal(deps: Deps, amount: Uint128) -> StdResult<(Uint128, Vec<Asset>)> {
+    let pool: Pool = POOL.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
+    let base_state: BaseState = BASESTATE.load(deps.storage)?;
+    let fee: Fee = FEE.load(deps.storage)?;
+    // Get assets
+    let assets = base_state.memory.query_assets(deps, &pool.assets)?;
+
+    // Calculate share of pool and requested pool value
+    let total_share: Uint128 = query_supply(&deps.querier, state.liquidity_token_addr)?;
+
+    // Get treasury fee in LP tokens
+    let treasury_fee = fee.compute(amount);
+
+    // Share with fee deducted.
+    let share_ratio: Decimal = Decimal::from_ratio(amount - treasury_fee, total_share);
+
+    // Get asset holdings of vault and calculate amount to return
+    let mut pay_back_assets: Vec<Asset> = vec![];
+    for (_, info) in assets.into_iter() {
+        pay_back_assets.push(Asset {
+            amount: share_ratio
+                // query asset held in treasury
+                * query_asset_balance(deps, &info, base_state.treasury_address.clone())?,
+            info,
+        });
+    }
+    Ok((treasury_fee, pay_back_assets))
+}
+
 /// Updates the pool information
 pub fn update_pool(
     deps: DepsMut,
@@ -289,14 +776,281 @@ pub fn update_pool(
     Ok(Response::new().add_attribute("Update:", "Successful"))
 }
 
-pub fn set_fee(deps: DepsMut, msg_info: MessageInfo, new_fee: Fee) -> VaultResult {
+pub fn set_fee(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    fee: Option<Fee>,
+    fee_recipient: Option<String>,
+) -> VaultResult {
     // Only the admin should be able to call this
     ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
 
-    if new_fee.share >= Decimal::one() {
-        return Err(VaultError::InvalidFee {});
+    if let Some(fee) = fee {
+        FEE.save(deps.storage, &check_fee(fee)?)?;
+    }
+    if let Some(fee_recipient) = fee_recipient {
+        FEE_RECIPIENT.save(
+            deps.storage,
+            &validate_addr(deps.api, "fee_recipient", &fee_recipient)?,
+        )?;
     }
+    Ok(Response::new().add_attribute("Update:", "Successful"))
+}
+
+/// Checks the withdrawal fee does not exceed MAX_WITHDRAW_FEE
+pub fn check_fee(fee: Fee) -> Result<Fee, VaultError> {
+    fee.validate(Decimal::percent(MAX_WITHDRAW_FEE))?;
+    Ok(fee)
+}
+
+pub fn set_deposit_caps(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    global: Option<Uint128>,
+    per_address: Option<Uint128>,
+) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    DEPOSIT_CAPS.save(
+        deps.storage,
+        &DepositCaps {
+            global,
+            per_address,
+        },
+    )?;
+    Ok(Response::new().add_attribute("Update:", "Successful"))
+}
+
+/// Sets the number of blocks withdrawals have to wait before they can be claimed
+pub fn set_unbonding_period(deps: DepsMut, msg_info: MessageInfo, blocks: u64) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    UNBONDING_PERIOD.save(deps.storage, &blocks)?;
+    Ok(Response::new().add_attribute("Update:", "Successful"))
+}
+
+/// Enables or disables emergency mode
+pub fn set_emergency_mode(deps: DepsMut, msg_info: MessageInfo, enabled: bool) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    EMERGENCY_MODE.save(deps.storage, &enabled)?;
+    Ok(Response::new().add_attribute("Update:", "Successful"))
+}
+
+/// Sets the target weights of the treasury assets, None removes the policy
+pub fn set_rebalance_policy(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    policy: Option<RebalancePolicy>,
+) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let policy = match policy {
+        Some(policy) => policy,
+        None => {
+            REBALANCE_POLICY.remove(deps.storage);
+            return Ok(Response::new().add_attribute("Update:", "Successful"));
+        }
+    };
+
+    let pool: Pool = POOL.load(deps.storage)?;
+    let mut total_weight = Decimal::zero();
+    for (i, target) in policy.targets.iter().enumerate() {
+        if !pool.assets.contains(&target.asset)
+            || policy.targets[..i].iter().any(|t| t.asset == target.asset)
+        {
+            return Err(VaultError::InvalidRebalancePolicy {});
+        }
+        total_weight = total_weight + target.weight;
+    }
+    if total_weight != Decimal::one()
+        || policy.tolerance >= Decimal::one()
+        || policy.max_trade_value.is_zero()
+    {
+        return Err(VaultError::InvalidRebalancePolicy {});
+    }
+
+    REBALANCE_POLICY.save(deps.storage, &policy)?;
+    Ok(Response::new().add_attribute("Update:", "Successful"))
+}
+
+/// Swaps the overweight assets of the rebalance policy into the underweight ones, moving them
+/// towards their target weight. Only trades once an asset deviates more than the tolerance from
+/// its target weight, and at most max_trade_value per call. The values are taken from the treasury.
+pub fn try_rebalance(deps: Deps) -> VaultResult {
+    assert_not_emergency(deps.storage)?;
+    assert_not_paused(deps.storage, PauseScope::Trading)?;
+    let policy = REBALANCE_POLICY
+        .may_load(deps.storage)?
+        .ok_or(VaultError::NoRebalancePolicy {})?;
+    let base_state: BaseState = BASESTATE.load(deps.storage)?;
+    let treasury = &base_state.treasury_address;
+
+    // Value the treasury balance of every asset of the policy
+    let mut holdings = vec![];
+    let mut total_value = Uint128::zero();
+    for target in policy.targets.iter() {
+        let info = base_state.memory.query_asset(deps, &target.asset)?;
+        let balance = query_asset_balance(deps, &info, treasury.clone())?;
+        let value = query_holding_value(deps, treasury, get_identifier(&info).clone(), balance)?;
+        total_value += value;
+        holdings.push((target, info, balance, value));
+    }
+
+    // Split the assets in the ones above and below their target value
+    let band = total_value * policy.tolerance;
+    let mut out_of_band = false;
+    let mut overweight = vec![];
+    let mut underweight = vec![];
+    for (target, info, balance, value) in holdings.into_iter() {
+        let target_value = total_value * target.weight;
+        if value > target_value {
+            out_of_band |= value - target_value > band;
+            overweight.push((
+                target.asset.clone(),
+                info,
+                balance,
+                value,
+                value - target_value,
+            ));
+        } else if target_value > value {
+            out_of_band |= target_value - value > band;
+            underweight.push((target.asset.clone(), target_value - value));
+        }
+    }
+    if !out_of_band {
+        return Err(VaultError::NothingToRebalance {});
+    }
+
+    // Match the excess of the overweight assets with the deficit of the underweight ones
+    let mut remaining_trade_value = policy.max_trade_value;
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut attrs = vec![("Action:", String::from("Rebalance treasury assets"))];
+    let mut underweight = underweight.into_iter().peekable();
+    for (asset_name, info, balance, value, mut excess) in overweight.into_iter() {
+        while let Some((ask_name, deficit)) = underweight.peek_mut() {
+            if excess.is_zero() || remaining_trade_value.is_zero() {
+                break;
+            }
+            let trade_value = excess.min(*deficit).min(remaining_trade_value);
+            excess -= trade_value;
+            *deficit -= trade_value;
+            remaining_trade_value -= trade_value;
+
+            let offer_asset = Asset {
+                info: info.clone(),
+                amount: balance.multiply_ratio(trade_value, value),
+            };
+            if !offer_asset.amount.is_zero() {
+                let pair_address = query_payout_pair(deps, &base_state, &asset_name, ask_name)?;
+                attrs.push(("Swapping:", offer_asset.to_string()));
+                msgs.push(swap_msg(
+                    deps,
+                    pair_address,
+                    offer_asset,
+                    policy.max_spread,
+                    treasury.to_string(),
+                )?);
+            }
+            if deficit.is_zero() {
+                underweight.next();
+            }
+        }
+    }
+    if msgs.is_empty() {
+        return Err(VaultError::NothingToRebalance {});
+    }
+
+    Ok(Response::new()
+        .add_message(trader_action(msgs, treasury)?)
+        .add_attributes(attrs))
+}
+
+fn assert_not_emergency(storage: &dyn Storage) -> Result<(), VaultError> {
+    if EMERGENCY_MODE.may_load(storage)?.unwrap_or_default() {
+        return Err(VaultError::EmergencyMode {});
+    }
+    Ok(())
+}
+
+/// Adds an address to the depositors whitelist
+pub fn add_to_whitelist(deps: DepsMut, msg_info: MessageInfo, address: String) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    if DEPOSIT_WHITELIST
+        .may_load(deps.storage, &address)?
+        .is_some()
+    {
+        return Err(VaultError::AlreadyWhitelisted {});
+    }
+    DEPOSIT_WHITELIST.save(deps.storage, &address, &true)?;
+    Ok(Response::new().add_attribute("Added address to whitelist: ", address))
+}
+
+/// Removes an address from the depositors whitelist
+pub fn remove_from_whitelist(deps: DepsMut, msg_info: MessageInfo, address: String) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    if DEPOSIT_WHITELIST
+        .may_load(deps.storage, &address)?
+        .is_none()
+    {
+        return Err(VaultError::NotWhitelisted {});
+    }
+    DEPOSIT_WHITELIST.remove(deps.storage, &address);
+    Ok(Response::new().add_attribute("Removed address from whitelist: ", address))
+}
+
+/// Accepts deposits of a memory asset of at least `min_deposit`
+pub fn set_accepted_deposit(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    asset_id: String,
+    min_deposit: Uint128,
+) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    // The asset has to be known to memory
+    let base_state = BASESTATE.load(deps.storage)?;
+    base_state.memory.query_asset(deps.as_ref(), &asset_id)?;
+
+    ACCEPTED_DEPOSITS.save(deps.storage, asset_id.as_str(), &min_deposit)?;
+    Ok(Response::new().add_attribute("Update:", "Successful"))
+}
+
+/// Stops accepting deposits of a memory asset set with set_accepted_deposit
+pub fn remove_accepted_deposit(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    asset_id: String,
+) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    if ACCEPTED_DEPOSITS
+        .may_load(deps.storage, asset_id.as_str())?
+        .is_none()
+    {
+        return Err(VaultError::AssetNotPresent { asset: asset_id });
+    }
+    ACCEPTED_DEPOSITS.remove(deps.storage, asset_id.as_str());
+    Ok(Response::new().add_attribute("Update:", "Successful"))
+}
+
+/// Opens deposits to everyone or restricts them to whitelisted addresses
+pub fn set_open_deposits(deps: DepsMut, msg_info: MessageInfo, open_deposits: bool) -> VaultResult {
+    // Only the admin should be able to call this
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
 
-    FEE.save(deps.storage, &new_fee)?;
+    OPEN_DEPOSITS.save(deps.storage, &open_deposits)?;
     Ok(Response::new().add_attribute("Update:", "Successful"))
 }
@@ -13,29 +13,39 @@ use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use terraswap::token::InstantiateMsg as TokenInstantiateMsg;
 
 use white_whale::fee::Fee;
-use white_whale::treasury::dapp_base::commands as dapp_base_commands;
-
-use white_whale::treasury::dapp_base::common::BaseDAppResult;
-use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
-use white_whale::treasury::dapp_base::queries as dapp_base_queries;
-use white_whale::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
+use white_whale::pause::{is_paused, query_paused_scopes, set_paused, set_paused_scopes};
+use white_whale::treasury::dapp_base::dapp::DAppBase;
+use white_whale::treasury::dapp_base::state::ADMIN;
 
 use crate::response::MsgInstantiateContractResponse;
 
 use crate::error::VaultError;
-use crate::state::{Pool, State, FEE, POOL, STATE};
+use crate::state::{Pool, State, EMERGENCY_MODE, FEE, POOL, REBALANCE_POLICY, STATE};
 use crate::{commands, queries};
-use white_whale::dapps::vault::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse};
+use white_whale::dapps::vault::msg::{
+    EmergencyModeResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RebalancePolicyResponse,
+    StateResponse,
+};
 pub type VaultResult = Result<Response, VaultError>;
 
+pub struct VaultDApp;
+impl DAppBase for VaultDApp {
+    type Error = VaultError;
+}
+
 const INSTANTIATE_REPLY_ID: u8 = 1u8;
 
 const DEFAULT_LP_TOKEN_NAME: &str = "Vault LP token";
 const DEFAULT_LP_TOKEN_SYMBOL: &str = "uvLP";
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateMsg) -> VaultResult {
-    let base_state: BaseState = dapp_base_commands::handle_base_init(deps.as_ref(), msg.base)?;
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> VaultResult {
+    VaultDApp::instantiate_base(deps.branch(), &info, msg.base)?;
 
     let state: State = State {
         liquidity_token_addr: Addr::unchecked(""),
@@ -50,7 +60,6 @@ pub fn instantiate(deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateM
         .unwrap_or_else(|| String::from(DEFAULT_LP_TOKEN_SYMBOL));
 
     STATE.save(deps.storage, &state)?;
-    BASESTATE.save(deps.storage, &base_state)?;
     POOL.save(
         deps.storage,
         &Pool {
@@ -58,8 +67,7 @@ pub fn instantiate(deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateM
             assets: vec![msg.deposit_asset],
         },
     )?;
-    FEE.save(deps.storage, &Fee { share: msg.fee })?;
-    ADMIN.set(deps, Some(info.sender))?;
+    FEE.save(deps.storage, &commands::check_fee(Fee { share: msg.fee })?)?;
 
     Ok(Response::new().add_submessage(SubMsg {
         // Create LP token
@@ -88,31 +96,98 @@ pub fn instantiate(deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateM
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> VaultResult {
+    if !matches!(msg, ExecuteMsg::Unpause {}) && is_paused(deps.storage)? {
+        return Err(VaultError::Paused {});
+    }
+
     match msg {
-        ExecuteMsg::Base(message) => {
-            from_base_dapp_result(dapp_base_commands::handle_base_message(deps, info, message))
-        }
+        ExecuteMsg::Base(message) => VaultDApp::execute_base(deps, env, info, message),
         ExecuteMsg::Receive(msg) => commands::receive_cw20(deps, env, info, msg),
-        ExecuteMsg::ProvideLiquidity { asset } => {
-            commands::try_provide_liquidity(deps, info, asset, None)
+        ExecuteMsg::ProvideLiquidity { asset, recipient } => {
+            commands::try_provide_liquidity(deps, env, info, asset, None, recipient)
+        }
+        ExecuteMsg::ProvideLiquidityMulti { assets } => {
+            commands::try_provide_liquidity_multi(deps, env, info, assets)
         }
         ExecuteMsg::UpdatePool {
             deposit_asset,
             assets_to_add,
             assets_to_remove,
         } => commands::update_pool(deps, info, deposit_asset, assets_to_add, assets_to_remove),
-        ExecuteMsg::SetFee { fee } => commands::set_fee(deps, info, fee),
+        ExecuteMsg::SetFee { fee, fee_recipient } => {
+            commands::set_fee(deps, info, fee, fee_recipient)
+        }
+        ExecuteMsg::SetDepositCaps {
+            global,
+            per_address,
+        } => commands::set_deposit_caps(deps, info, global, per_address),
+        ExecuteMsg::SetUnbondingPeriod { blocks } => {
+            commands::set_unbonding_period(deps, info, blocks)
+        }
+        ExecuteMsg::Claim {} => commands::try_claim(deps, env, info),
+        ExecuteMsg::AddToWhitelist { address } => commands::add_to_whitelist(deps, info, address),
+        ExecuteMsg::RemoveFromWhitelist { address } => {
+            commands::remove_from_whitelist(deps, info, address)
+        }
+        ExecuteMsg::SetOpenDeposits { open_deposits } => {
+            commands::set_open_deposits(deps, info, open_deposits)
+        }
+        ExecuteMsg::SetAcceptedDeposit {
+            asset_id,
+            min_deposit,
+        } => commands::set_accepted_deposit(deps, info, asset_id, min_deposit),
+        ExecuteMsg::RemoveAcceptedDeposit { asset_id } => {
+            commands::remove_accepted_deposit(deps, info, asset_id)
+        }
+        ExecuteMsg::SetEmergencyMode { enabled } => {
+            commands::set_emergency_mode(deps, info, enabled)
+        }
+        ExecuteMsg::SetRebalancePolicy { policy } => {
+            commands::set_rebalance_policy(deps, info, policy)
+        }
+        ExecuteMsg::Rebalance {} => commands::try_rebalance(deps.as_ref()),
+        ExecuteMsg::Pause {} => Ok(set_paused(deps, &info, &ADMIN, true)?),
+        ExecuteMsg::Unpause {} => Ok(set_paused(deps, &info, &ADMIN, false)?),
+        ExecuteMsg::PauseScopes { scopes } => {
+            Ok(set_paused_scopes(deps, &info, &ADMIN, scopes, true)?)
+        }
+        ExecuteMsg::UnpauseScopes { scopes } => {
+            Ok(set_paused_scopes(deps, &info, &ADMIN, scopes, false)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Base(message) => dapp_base_queries::handle_base_query(deps, message),
+        QueryMsg::Base(message) => VaultDApp::query_base(deps, message),
         // handle dapp-specific queries here
         QueryMsg::State {} => to_binary(&StateResponse {
             liquidity_token: STATE.load(deps.storage)?.liquidity_token_addr.to_string(),
         }),
+        QueryMsg::DepositCapacity { address } => {
+            to_binary(&queries::query_deposit_capacity(deps, address)?)
+        }
+        QueryMsg::Fees {} => to_binary(&queries::query_fees(deps)?),
+        QueryMsg::SharePrice {} => to_binary(&queries::query_share_price(deps)?),
+        QueryMsg::DepositInfo {} => to_binary(&queries::query_deposit_info(deps)?),
+        QueryMsg::EstimateDeposit { asset } => {
+            to_binary(&queries::query_estimate_deposit(deps, asset)?)
+        }
+        QueryMsg::EstimateWithdraw { amount } => {
+            to_binary(&queries::query_estimate_withdraw(deps, amount)?)
+        }
+        QueryMsg::Whitelisted { address } => to_binary(&queries::query_whitelisted(deps, address)?),
+        QueryMsg::PendingClaims { address } => {
+            to_binary(&queries::query_pending_claims(deps, address)?)
+        }
+        QueryMsg::EmergencyMode {} => to_binary(&EmergencyModeResponse {
+            emergency_mode: EMERGENCY_MODE.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        QueryMsg::RebalancePolicy {} => to_binary(&RebalancePolicyResponse {
+            policy: REBALANCE_POLICY.may_load(deps.storage)?,
+        }),
+        QueryMsg::PausedScopes {} => to_binary(&query_paused_scopes(deps.storage)?),
     }
 }
 
@@ -137,12 +212,3 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
     }
     Ok(Response::default())
 }
-
-/// Required to convert BaseDAppResult into TerraswapResult
-/// Can't implement the From trait directly
-fn from_base_dapp_result(result: BaseDAppResult) -> VaultResult {
-    match result {
-        Err(e) => Err(e.into()),
-        Ok(r) => Ok(r),
-    }
-}
@@ -1,6 +1,8 @@
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{OverflowError, StdError, Uint128};
 use cw_controllers::AdminError;
 use thiserror::Error;
+use white_whale::error::CommonError;
+use white_whale::fee::FeeError;
 use white_whale::treasury::dapp_base::error::BaseDAppError;
 
 #[derive(Error, Debug, PartialEq)]
@@ -8,6 +10,9 @@ pub enum VaultError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
@@ -17,6 +22,9 @@ pub enum VaultError {
     #[error("{0}")]
     Overflow(#[from] OverflowError),
 
+    #[error("{0}")]
+    Fee(FeeError),
+
     #[error("This contract does not implement the cw20 swap function")]
     NoSwapAvailable {},
 
@@ -35,9 +43,66 @@ pub enum VaultError {
     #[error("It's required to use cw20 send message to add liquidity with cw20 tokens")]
     NotUsingCW20Hook {},
 
-    #[error("The provided fee is invalid")]
+    #[error("The provided fee is invalid, it can be at most 20%")]
     InvalidFee {},
 
     #[error("The actual amount of tokens transferred is different from the claimed amount.")]
     InvalidAmount {},
+
+    #[error("The asset {asset} can't be deposited into the vault")]
+    InvalidDepositAsset { asset: String },
+
+    #[error("Withdrawal pays out {paid}, less than the requested minimum of {expected}")]
+    MinOutNotReached { expected: String, paid: Uint128 },
+
+    #[error("The payout asset {asset} is not one of the vault assets")]
+    InvalidPayoutAsset { asset: String },
+
+    #[error("No pair is registered to swap {asset} into the payout asset")]
+    NoPayoutPair { asset: String },
+
+    #[error("No withdrawals have finished unbonding")]
+    NothingToClaim {},
+
+    #[error("Deposits are restricted to whitelisted addresses")]
+    DepositorNotWhitelisted {},
+
+    #[error("The address is already whitelisted")]
+    AlreadyWhitelisted {},
+
+    #[error("The address is not whitelisted")]
+    NotWhitelisted {},
+
+    #[error("The contract is paused")]
+    Paused {},
+
+    #[error("Deposits are disabled while the vault is in emergency mode")]
+    EmergencyMode {},
+
+    #[error("Emergency withdrawals are only possible in emergency mode")]
+    NotEmergencyMode {},
+
+    #[error("The target weights must be distinct vault assets adding up to 1, with a tolerance below 1 and a non zero trade size")]
+    InvalidRebalancePolicy {},
+
+    #[error("No rebalance policy is set")]
+    NoRebalancePolicy {},
+
+    #[error("All assets are within the tolerance of their target weight")]
+    NothingToRebalance {},
+
+    #[error("The deposit would bring the vault value over its cap of {cap}")]
+    GlobalDepositCapReached { cap: Uint128 },
+
+    #[error("The deposit would bring the deposits of this address over the cap of {cap}")]
+    AddressDepositCapReached { cap: Uint128 },
+}
+
+impl From<FeeError> for VaultError {
+    fn from(err: FeeError) -> Self {
+        match err {
+            FeeError::InvalidFee {} => Self::InvalidFee {},
+            err => Self::Fee(err),
+        }
+    }
 }
@@ -1,3 +1,129 @@
+use cosmwasm_std::{Decimal, Deps, StdResult, Uint128};
+use terraswap::asset::Asset;
+use terraswap::querier::query_supply;
+
+use white_whale::dapps::vault::msg::{
+    DepositCapacityResponse, EstimateDepositResponse, EstimateWithdrawResponse, FeeResponse,
+    PendingClaimsResponse, SharePriceResponse, WhitelistedResponse,
+};
+use white_whale::deposit_info::DepositInfo;
+use white_whale::query::vault::query_total_value;
+use white_whale::treasury::dapp_base::state::BASESTATE;
+
+use crate::commands::{
+    compute_deposit_share, compute_withdrawal, deposit_value, load_deposit_info,
+};
+use crate::state::{
+    CLAIMS, DEPOSITS, DEPOSIT_CAPS, DEPOSIT_WHITELIST, FEE, FEE_RECIPIENT, OPEN_DEPOSITS, POOL,
+    STATE, UNBONDING_PERIOD,
+};
+
+/// Returns how much can still be deposited before the caps are reached
+pub fn query_deposit_capacity(deps: Deps, address: String) -> StdResult<DepositCapacityResponse> {
+    let caps = DEPOSIT_CAPS.may_load(deps.storage)?.unwrap_or_default();
+
+    let global_remaining = match caps.global {
+        Some(cap) => {
+            let treasury_address = BASESTATE.load(deps.storage)?.treasury_address;
+            let value = query_total_value(deps, &treasury_address)?;
+            Some(cap.checked_sub(value).unwrap_or_default())
+        }
+        None => None,
+    };
+    let address_remaining = match caps.per_address {
+        Some(cap) => {
+            let address = deps.api.addr_validate(&address)?;
+            let deposited = DEPOSITS
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default();
+            Some(cap.checked_sub(deposited).unwrap_or_default())
+        }
+        None => None,
+    };
+
+    Ok(DepositCapacityResponse {
+        global_remaining,
+        address_remaining,
+    })
+}
+
+/// Returns the withdrawal fee and the address receiving it
+pub fn query_fees(deps: Deps) -> StdResult<FeeResponse> {
+    let fee_recipient = match FEE_RECIPIENT.may_load(deps.storage)? {
+        Some(fee_recipient) => fee_recipient,
+        None => BASESTATE.load(deps.storage)?.treasury_address,
+    };
+    Ok(FeeResponse {
+        fee: FEE.load(deps.storage)?,
+        fee_recipient,
+    })
+}
+
+/// Returns the value of one LP token, one if no LP tokens exist yet
+pub fn query_share_price(deps: Deps) -> StdResult<SharePriceResponse> {
+    let state = STATE.load(deps.storage)?;
+    let base_state = BASESTATE.load(deps.storage)?;
+
+    let total_share = query_supply(&deps.querier, state.liquidity_token_addr)?;
+    let share_price = if total_share.is_zero() {
+        Decimal::one()
+    } else {
+        let value = query_total_value(deps, &base_state.treasury_address)?;
+        Decimal::from_ratio(value, total_share)
+    };
+    Ok(SharePriceResponse { share_price })
+}
+
+/// Returns the LP tokens ProvideLiquidity would mint for `asset`
+pub fn query_estimate_deposit(deps: Deps, asset: Asset) -> StdResult<EstimateDepositResponse> {
+    let pool = POOL.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let base_state = BASESTATE.load(deps.storage)?;
+
+    let deposit_info = load_deposit_info(deps, &pool, &base_state)?;
+    deposit_info.assert_deposit(&asset)?;
+    let deposit = deposit_value(deps, &deposit_info, &base_state, &asset)?;
+
+    let value = query_total_value(deps, &base_state.treasury_address)?;
+    let total_share = query_supply(&deps.querier, state.liquidity_token_addr)?;
+    Ok(EstimateDepositResponse {
+        share: compute_deposit_share(total_share, value, deposit)?,
+    })
+}
+
+/// Returns the assets accepted by ProvideLiquidity and their minimum deposits
+pub fn query_deposit_info(deps: Deps) -> StdResult<DepositInfo> {
+    let pool = POOL.load(deps.storage)?;
+    let base_state = BASESTATE.load(deps.storage)?;
+    load_deposit_info(deps, &pool, &base_state)
+}
+
+/// Returns the fee and the assets paid out for withdrawing `amount` LP tokens
+pub fn query_estimate_withdraw(deps: Deps, amount: Uint128) -> StdResult<EstimateWithdrawResponse> {
+    let (fee, assets) = compute_withdrawal(deps, amount)?;
+    Ok(EstimateWithdrawResponse { fee, assets })
+}
+
+/// Returns whether deposits are open and if `address` is whitelisted
+pub fn query_whitelisted(deps: Deps, address: String) -> StdResult<WhitelistedResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(WhitelistedResponse {
+        open_deposits: OPEN_DEPOSITS.may_load(deps.storage)?.unwrap_or(true),
+        whitelisted: DEPOSIT_WHITELIST
+            .may_load(deps.storage, &address)?
+            .is_some(),
+    })
+}
+
+/// Returns the withdrawals of `address` waiting to be claimed
+pub fn query_pending_claims(deps: Deps, address: String) -> StdResult<PendingClaimsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(PendingClaimsResponse {
+        unbonding_period: UNBONDING_PERIOD.may_load(deps.storage)?.unwrap_or_default(),
+        claims: CLAIMS.may_load(deps.storage, &address)?.unwrap_or_default(),
+    })
+}
+
 // pub fn handle_value_query(deps: Deps, query: ValueQueryMsg) -> StdResult<Binary> {
 //     query_assets_from_mem(deps, memory_addr, '')
 //     if query.asset_info.equal(asset)
@@ -1,8 +1,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use white_whale::dapps::vault::msg::{Claim, RebalancePolicy};
 use white_whale::fee::Fee;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,6 +21,34 @@ pub struct Pool {
     pub assets: Vec<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+/// DepositCaps limit the deposits into the vault, a None cap is unlimited.
+/// global caps the total value of the vault, per_address caps the amount
+/// of the deposit asset, or the value of multi asset deposits, an address can deposit in total.
+pub struct DepositCaps {
+    pub global: Option<Uint128>,
+    pub per_address: Option<Uint128>,
+}
+
 pub const STATE: Item<State> = Item::new("\u{0}{5}state");
 pub const POOL: Item<Pool> = Item::new("\u{0}{4}pool");
 pub const FEE: Item<Fee> = Item::new("\u{0}{3}fee");
+/// Receiver of the withdrawal fee, the treasury if not set
+pub const FEE_RECIPIENT: Item<Addr> = Item::new("fee_recipient");
+pub const DEPOSIT_CAPS: Item<DepositCaps> = Item::new("deposit_caps");
+/// Total amount of the deposit asset deposited per address, withdrawals don't free up capacity
+pub const DEPOSITS: Map<&Addr, Uint128> = Map::new("deposits");
+/// Blocks a withdrawal has to wait before it can be claimed, withdrawals pay out immediately if 0
+pub const UNBONDING_PERIOD: Item<u64> = Item::new("unbonding_period");
+/// Withdrawals waiting to be claimed per address
+pub const CLAIMS: Map<&Addr, Vec<Claim>> = Map::new("claims");
+/// Whether everyone can deposit or only whitelisted addresses, open if not set
+pub const OPEN_DEPOSITS: Item<bool> = Item::new("open_deposits");
+/// Addresses allowed to deposit while deposits are not open
+pub const DEPOSIT_WHITELIST: Map<&Addr, bool> = Map::new("deposit_whitelist");
+/// Minimum deposit per memory asset id, assets other than the deposit asset are only accepted if set
+pub const ACCEPTED_DEPOSITS: Map<&str, Uint128> = Map::new("accepted_deposits");
+/// Whether deposits are halted and LP holders can only withdraw pro-rata, disabled if not set
+pub const EMERGENCY_MODE: Item<bool> = Item::new("emergency_mode");
+/// Target weights of the treasury assets, Rebalance is disabled if not set
+pub const REBALANCE_POLICY: Item<RebalancePolicy> = Item::new("rebalance_policy");
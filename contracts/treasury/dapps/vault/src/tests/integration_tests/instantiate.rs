@@ -9,7 +9,7 @@ use white_whale::dapps::vault::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, State
 
 use white_whale::memory::msg as MemoryMsg;
 use white_whale::treasury::msg as TreasuryMsg;
-use white_whale::treasury::vault_assets::{ValueRef, VaultAsset};
+use white_whale::treasury::vault_assets::{BreachAction, ValueRef, VaultAsset};
 
 use white_whale::treasury::dapp_base::msg::BaseInstantiateMsg;
 
@@ -82,6 +82,9 @@ pub fn init_vault_dapp(app: &mut App, owner: Addr, base_contracts: &BaseContract
                         amount: Uint128::zero(),
                     },
                     value_reference: None,
+                    max_value: None,
+                    max_price_move_per_block: None,
+                    breach_action: BreachAction::Clamp,
                 },
                 // Other asset is WHALE. It's value in uusd is calculated with the provided pool valueref
                 VaultAsset {
@@ -94,6 +97,9 @@ pub fn init_vault_dapp(app: &mut App, owner: Addr, base_contracts: &BaseContract
                     value_reference: Some(ValueRef::Pool {
                         pair_address: base_contracts.whale_ust_pair.clone(),
                     }),
+                    max_value: None,
+                    max_price_move_per_block: None,
+                    breach_action: BreachAction::Clamp,
                 },
             ],
             to_remove: vec![],
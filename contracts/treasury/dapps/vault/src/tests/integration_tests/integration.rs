@@ -1,7 +1,7 @@
 use cosmwasm_std::{to_binary, Addr, Coin, Decimal, Uint128};
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, TokenInfoResponse};
 
-use terra_multi_test::App;
+use terra_multi_test::{App, AppResponse};
 
 use crate::tests::integration_tests::common_integration::{
     init_contracts, mint_some_whale, mock_app,
@@ -9,8 +9,13 @@ use crate::tests::integration_tests::common_integration::{
 use crate::tests::integration_tests::instantiate::{configure_memory, init_vault_dapp};
 use terra_multi_test::Executor;
 use terraswap::asset::Asset;
-use white_whale::dapps::vault::msg::{DepositHookMsg, ExecuteMsg};
+use white_whale::dapps::vault::msg::{
+    DepositCapacityResponse, DepositHookMsg, EmergencyModeResponse, EstimateDepositResponse,
+    EstimateWithdrawResponse, ExecuteMsg, PendingClaimsResponse, QueryMsg, RebalancePolicy,
+    RebalancePolicyResponse, SharePriceResponse, TargetWeight, WhitelistedResponse,
+};
 
+use white_whale::deposit_info::{AcceptedAsset, DepositInfo};
 use white_whale::treasury::dapp_base::common_test::TEST_CREATOR;
 use white_whale::treasury::msg as TreasuryMsg;
 
@@ -62,29 +67,42 @@ fn deposit_and_withdraw_with_native_token() {
                 },
                 amount: Uint128::from(10u64 * MILLION),
             },
+            recipient: None,
         },
         &[],
     )
     .unwrap_err();
 
     // Add UST to treasury through vault dapp contract interaction
-    app.execute_contract(
-        sender.clone(),
-        vault_dapp.clone(),
-        &ExecuteMsg::ProvideLiquidity {
-            asset: Asset {
-                info: terraswap::asset::AssetInfo::NativeToken {
-                    denom: "uusd".to_string(),
+    let res = app
+        .execute_contract(
+            sender.clone(),
+            vault_dapp.clone(),
+            &ExecuteMsg::ProvideLiquidity {
+                asset: Asset {
+                    info: terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::from(10u64 * MILLION),
                 },
-                amount: Uint128::from(10u64 * MILLION),
+                recipient: None,
             },
-        },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(10u64 * MILLION),
-        }],
-    )
-    .unwrap();
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(10u64 * MILLION),
+            }],
+        )
+        .unwrap();
+    assert_eq!(
+        vault_event_attributes(&res, &vault_dapp),
+        vec![
+            ("action", "deposit".to_string()),
+            ("depositor", sender.to_string()),
+            ("assets_in", "10000000uusd".to_string()),
+            ("shares_minted", "10000000".to_string()),
+            ("exchange_rate", "1".to_string()),
+        ]
+    );
 
     // Check treasury Value
     let treasury_res: TreasuryMsg::TotalValueResponse = app
@@ -142,17 +160,29 @@ fn deposit_and_withdraw_with_native_token() {
     );
 
     // Withdraw from vault.
-    app.execute_contract(
-        sender.clone(),
-        vault_l_token.clone(),
-        &cw20::Cw20ExecuteMsg::Send {
-            contract: vault_dapp.to_string(),
-            amount: Uint128::from(10_000_000u128),
-            msg: to_binary(&DepositHookMsg::WithdrawLiquidity {}).unwrap(),
-        },
-        &[],
-    )
-    .unwrap();
+    let res = app
+        .execute_contract(
+            sender.clone(),
+            vault_l_token.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: vault_dapp.to_string(),
+                amount: Uint128::from(10_000_000u128),
+                msg: to_binary(&DepositHookMsg::WithdrawLiquidity {
+                    min_out: None,
+                    payout_asset: None,
+                    max_spread: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    let attributes = vault_event_attributes(&res, &vault_dapp);
+    assert_eq!(attributes[0], ("action", "withdraw".to_string()));
+    // The withdrawal fee isn't burned
+    assert_eq!(attributes[3], ("shares_burned", "9000000".to_string()));
+    // 1_010 UST of value for 10 LP tokens
+    assert_eq!(attributes[4], ("exchange_rate", "101".to_string()));
 
     // We withdrew everthing so own 0 in liquidity tokens
     let owned_locked_value =
@@ -224,6 +254,7 @@ fn deposit_and_withdraw_with_cw20() {
                 },
                 amount: Uint128::from(10u64 * MILLION),
             },
+            recipient: None,
         },
         &[Coin {
             denom: "uusd".to_string(),
@@ -243,6 +274,7 @@ fn deposit_and_withdraw_with_cw20() {
                 },
                 amount: Uint128::from(10u64 * MILLION),
             },
+            recipient: None,
         },
         &[Coin {
             denom: "uusd".to_string(),
@@ -266,7 +298,7 @@ fn deposit_and_withdraw_with_cw20() {
         &Cw20ExecuteMsg::Send {
             contract: vault_dapp.to_string(),
             amount: Uint128::from(10u64 * MILLION),
-            msg: to_binary(&DepositHookMsg::ProvideLiquidity {}).unwrap(),
+            msg: to_binary(&DepositHookMsg::ProvideLiquidity { recipient: None }).unwrap(),
         },
         &[],
     )
@@ -292,6 +324,32 @@ fn deposit_and_withdraw_with_cw20() {
         liquidity_token_value(&app, &vault_l_token, &base_contracts.treasury, &sender);
     assert_eq!(Uint128::from(110u64 * MILLION), owned_locked_value);
 
+    // Withdrawing fails if less than the minimum would be paid out, 45 UST after fees
+    let ust_out = |amount: u64| {
+        Some(vec![Asset {
+            info: terraswap::asset::AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::from(amount * MILLION),
+        }])
+    };
+    app.execute_contract(
+        sender.clone(),
+        vault_l_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: vault_dapp.to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+            msg: to_binary(&DepositHookMsg::WithdrawLiquidity {
+                min_out: ust_out(46),
+                payout_asset: None,
+                max_spread: None,
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap_err();
+
     // Withdraw all from vault.
     app.execute_contract(
         sender.clone(),
@@ -299,7 +357,12 @@ fn deposit_and_withdraw_with_cw20() {
         &cw20::Cw20ExecuteMsg::Send {
             contract: vault_dapp.to_string(),
             amount: Uint128::from(10u64 * MILLION),
-            msg: to_binary(&DepositHookMsg::WithdrawLiquidity {}).unwrap(),
+            msg: to_binary(&DepositHookMsg::WithdrawLiquidity {
+                min_out: ust_out(45),
+                payout_asset: None,
+                max_spread: None,
+            })
+            .unwrap(),
         },
         &[],
     )
@@ -337,6 +400,1155 @@ fn deposit_and_withdraw_with_cw20() {
     assert_eq!(Uint128::from(9u64 * MILLION), whale_balance.balance);
 }
 
+#[test]
+fn deposit_on_behalf_of_recipient() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let recipient = Addr::unchecked("recipient");
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    // Deposit UST with LP tokens minted to the recipient
+    let res = app
+        .execute_contract(
+            sender.clone(),
+            vault_dapp.clone(),
+            &ExecuteMsg::ProvideLiquidity {
+                asset: Asset {
+                    info: terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::from(10u64 * MILLION),
+                },
+                recipient: Some(recipient.to_string()),
+            },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(10u64 * MILLION),
+            }],
+        )
+        .unwrap();
+    let attributes: Vec<_> = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+        .collect();
+    assert!(attributes.contains(&("Depositor:", TEST_CREATOR)));
+    assert!(attributes.contains(&("Recipient:", "recipient")));
+
+    assert_eq!(
+        Uint128::from(10u64 * MILLION),
+        lp_balance(&app, &vault_l_token, &recipient)
+    );
+    assert_eq!(Uint128::zero(), lp_balance(&app, &vault_l_token, &sender));
+
+    // An invalid recipient is rejected
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            asset: Asset {
+                info: terraswap::asset::AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::from(10u64 * MILLION),
+            },
+            recipient: Some("x".to_string()),
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+        }],
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn deposit_on_behalf_of_recipient_with_cw20() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let recipient = Addr::unchecked("recipient");
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+    change_base_to_whale(&mut app, sender.clone(), &base_contracts, &vault_dapp);
+
+    mint_some_whale(
+        &mut app,
+        sender.clone(),
+        base_contracts.whale.clone(),
+        Uint128::from(10u64 * MILLION),
+        sender.to_string(),
+    );
+    app.execute_contract(
+        sender.clone(),
+        base_contracts.whale.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: vault_dapp.to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+            msg: to_binary(&DepositHookMsg::ProvideLiquidity {
+                recipient: Some(recipient.to_string()),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        Uint128::from(10u64 * MILLION),
+        lp_balance(&app, &vault_l_token, &recipient)
+    );
+    assert_eq!(Uint128::zero(), lp_balance(&app, &vault_l_token, &sender));
+}
+
+#[test]
+fn share_price_and_estimates() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, _vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    let ust = |amount: u64| Asset {
+        info: terraswap::asset::AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::from(amount),
+    };
+
+    // First deposit mints one LP token per deposited token
+    let res: EstimateDepositResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &vault_dapp,
+            &QueryMsg::EstimateDeposit {
+                asset: ust(10 * MILLION),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(10 * MILLION), res.share);
+
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            asset: ust(10 * MILLION),
+            recipient: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    // add 2000 WHALE worth 1000 UST to the treasury
+    mint_some_whale(
+        &mut app,
+        sender.clone(),
+        base_contracts.whale.clone(),
+        Uint128::from(2_000u64 * MILLION),
+        base_contracts.treasury.to_string(),
+    );
+
+    let res: SharePriceResponse = app
+        .wrap()
+        .query_wasm_smart(&vault_dapp, &QueryMsg::SharePrice {})
+        .unwrap();
+    assert_eq!(Decimal::from_ratio(101u64, 1u64), res.share_price);
+
+    // 10% fee
+    let res: EstimateWithdrawResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &vault_dapp,
+            &QueryMsg::EstimateWithdraw {
+                amount: Uint128::from(10u64 * MILLION),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(MILLION), res.fee);
+    assert_eq!(
+        vec![
+            ust(9 * MILLION),
+            Asset {
+                info: terraswap::asset::AssetInfo::Token {
+                    contract_addr: base_contracts.whale.to_string(),
+                },
+                amount: Uint128::from(1_800u64 * MILLION),
+            },
+        ],
+        res.assets
+    );
+}
+
+#[test]
+fn deposit_caps() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, _vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    let deposit = |app: &mut App, amount: u64| {
+        app.execute_contract(
+            sender.clone(),
+            vault_dapp.clone(),
+            &ExecuteMsg::ProvideLiquidity {
+                asset: Asset {
+                    info: terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::from(amount * MILLION),
+                },
+                recipient: None,
+            },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(amount * MILLION),
+            }],
+        )
+    };
+
+    // Only the admin can set the caps
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetDepositCaps {
+            global: None,
+            per_address: None,
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetDepositCaps {
+            global: Some(Uint128::from(15u64 * MILLION)),
+            per_address: Some(Uint128::from(8u64 * MILLION)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Over the per address cap
+    deposit(&mut app, 10).unwrap_err();
+    deposit(&mut app, 5).unwrap();
+
+    let capacity: DepositCapacityResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &vault_dapp,
+            &QueryMsg::DepositCapacity {
+                address: sender.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        capacity,
+        DepositCapacityResponse {
+            global_remaining: Some(Uint128::from(10u64 * MILLION)),
+            address_remaining: Some(Uint128::from(3u64 * MILLION)),
+        }
+    );
+
+    // Over the per address cap again
+    deposit(&mut app, 5).unwrap_err();
+    deposit(&mut app, 3).unwrap();
+
+    // Over the global cap
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetDepositCaps {
+            global: Some(Uint128::from(15u64 * MILLION)),
+            per_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+    deposit(&mut app, 8).unwrap_err();
+    deposit(&mut app, 7).unwrap();
+}
+
+#[test]
+fn deposit_whitelist() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, _vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    let deposit = |app: &mut App| {
+        app.execute_contract(
+            sender.clone(),
+            vault_dapp.clone(),
+            &ExecuteMsg::ProvideLiquidity {
+                asset: Asset {
+                    info: terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::from(MILLION),
+                },
+                recipient: None,
+            },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(MILLION),
+            }],
+        )
+    };
+    let whitelisted = |app: &App| -> WhitelistedResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &vault_dapp,
+                &QueryMsg::Whitelisted {
+                    address: sender.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    // Deposits are open by default
+    assert!(whitelisted(&app).open_deposits);
+    deposit(&mut app).unwrap();
+
+    // Only the admin can restrict deposits
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetOpenDeposits {
+            open_deposits: false,
+        },
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetOpenDeposits {
+            open_deposits: false,
+        },
+        &[],
+    )
+    .unwrap();
+    deposit(&mut app).unwrap_err();
+
+    // Whitelisted addresses can still deposit
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::AddToWhitelist {
+            address: sender.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        whitelisted(&app),
+        WhitelistedResponse {
+            open_deposits: false,
+            whitelisted: true,
+        }
+    );
+    deposit(&mut app).unwrap();
+
+    // Can't add twice
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::AddToWhitelist {
+            address: sender.to_string(),
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::RemoveFromWhitelist {
+            address: sender.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    deposit(&mut app).unwrap_err();
+}
+
+#[test]
+fn deposit_multiple_assets() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+    mint_some_whale(
+        &mut app,
+        sender.clone(),
+        base_contracts.whale.clone(),
+        Uint128::from(20u64 * MILLION),
+        sender.to_string(),
+    );
+    // Cw20 deposits are transferred from the sender
+    app.execute_contract(
+        sender.clone(),
+        base_contracts.whale.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: vault_dapp.to_string(),
+            amount: Uint128::from(20u64 * MILLION),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let ust = Asset {
+        info: terraswap::asset::AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::from(10u64 * MILLION),
+    };
+    let whale = Asset {
+        info: terraswap::asset::AssetInfo::Token {
+            contract_addr: base_contracts.whale.to_string(),
+        },
+        amount: Uint128::from(20u64 * MILLION),
+    };
+
+    // The same asset can't be deposited twice
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::ProvideLiquidityMulti {
+            assets: vec![ust.clone(), ust.clone()],
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(20u64 * MILLION),
+        }],
+    )
+    .unwrap_err();
+
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::ProvideLiquidityMulti {
+            assets: vec![ust, whale],
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    // 10 UST + 20 WHALE at 0.5 UST/WHALE
+    let treasury_res: TreasuryMsg::TotalValueResponse = app
+        .wrap()
+        .query_wasm_smart(
+            base_contracts.treasury.clone(),
+            &TreasuryMsg::QueryMsg::TotalValue {},
+        )
+        .unwrap();
+    assert_eq!(20_000_000u128, treasury_res.value.u128());
+
+    let staker_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &vault_l_token,
+            &Cw20QueryMsg::Balance {
+                address: sender.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(20_000_000u128, staker_balance.balance.u128());
+}
+
+#[test]
+fn deposit_accepted_assets() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    mint_some_whale(
+        &mut app,
+        sender.clone(),
+        base_contracts.whale.clone(),
+        Uint128::from(20u64 * MILLION),
+        sender.to_string(),
+    );
+
+    let deposit_whale = |app: &mut App, amount: u64| {
+        app.execute_contract(
+            sender.clone(),
+            base_contracts.whale.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: vault_dapp.to_string(),
+                amount: Uint128::from(amount * MILLION),
+                msg: to_binary(&DepositHookMsg::ProvideLiquidity { recipient: None }).unwrap(),
+            },
+            &[],
+        )
+    };
+
+    // WHALE is not accepted yet
+    deposit_whale(&mut app, 10).unwrap_err();
+
+    // Only the admin can accept assets
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetAcceptedDeposit {
+            asset_id: "whale".to_string(),
+            min_deposit: Uint128::from(5u64 * MILLION),
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetAcceptedDeposit {
+            asset_id: "whale".to_string(),
+            min_deposit: Uint128::from(5u64 * MILLION),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let deposit_info: DepositInfo = app
+        .wrap()
+        .query_wasm_smart(&vault_dapp, &QueryMsg::DepositInfo {})
+        .unwrap();
+    assert_eq!(
+        deposit_info.other_assets,
+        vec![AcceptedAsset {
+            info: terraswap::asset::AssetInfo::Token {
+                contract_addr: base_contracts.whale.to_string(),
+            },
+            min_deposit: Uint128::from(5u64 * MILLION),
+        }]
+    );
+
+    // Below the minimum deposit
+    deposit_whale(&mut app, 4).unwrap_err();
+    deposit_whale(&mut app, 20).unwrap();
+
+    // 20 WHALE at 0.5 UST/WHALE
+    let staker_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &vault_l_token,
+            &Cw20QueryMsg::Balance {
+                address: sender.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(10_000_000u128, staker_balance.balance.u128());
+
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::RemoveAcceptedDeposit {
+            asset_id: "whale".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::RemoveAcceptedDeposit {
+            asset_id: "whale".to_string(),
+        },
+        &[],
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn withdraw_into_payout_asset() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    // Deposit 10 UST
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            asset: Asset {
+                info: terraswap::asset::AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::from(10u64 * MILLION),
+            },
+            recipient: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    // Treasury also holds 20 WHALE
+    mint_some_whale(
+        &mut app,
+        sender.clone(),
+        base_contracts.whale.clone(),
+        Uint128::from(20u64 * MILLION),
+        base_contracts.treasury.to_string(),
+    );
+
+    let withdraw = |payout_asset: &str| cw20::Cw20ExecuteMsg::Send {
+        contract: vault_dapp.to_string(),
+        amount: Uint128::from(10u64 * MILLION),
+        msg: to_binary(&DepositHookMsg::WithdrawLiquidity {
+            min_out: None,
+            payout_asset: Some(payout_asset.to_string()),
+            max_spread: Some(Decimal::percent(10)),
+        })
+        .unwrap(),
+    };
+
+    // Payout asset must be one of the vault assets
+    app.execute_contract(
+        sender.clone(),
+        vault_l_token.clone(),
+        &withdraw("whale_ust"),
+        &[],
+    )
+    .unwrap_err();
+
+    // Withdraw everything as UST, the WHALE share gets swapped
+    app.execute_contract(sender.clone(), vault_l_token.clone(), &withdraw("ust"), &[])
+        .unwrap();
+
+    let whale_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &base_contracts.whale,
+            &Cw20QueryMsg::Balance {
+                address: sender.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::zero(), whale_balance.balance);
+
+    // 90 UST left + 9 UST repaid + swapped 18 WHALE
+    let sender_balance = app
+        .wrap()
+        .query_balance(sender.clone(), "uusd")
+        .unwrap()
+        .amount;
+    assert!(sender_balance > Uint128::from(99u64 * MILLION));
+}
+
+#[test]
+fn unbonding_withdrawals() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    // Only the admin can set the unbonding period
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetUnbondingPeriod { blocks: 10 },
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetUnbondingPeriod { blocks: 10 },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            asset: Asset {
+                info: terraswap::asset::AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::from(10u64 * MILLION),
+            },
+            recipient: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    // Withdraw all, the LP tokens get burned but the UST is queued
+    app.execute_contract(
+        sender.clone(),
+        vault_l_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: vault_dapp.to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+            msg: to_binary(&DepositHookMsg::WithdrawLiquidity {
+                min_out: None,
+                payout_asset: None,
+                max_spread: None,
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let sender_balance = |app: &App| {
+        app.wrap()
+            .query_balance(sender.clone(), "uusd")
+            .unwrap()
+            .amount
+    };
+    assert_eq!(Uint128::from(90u64 * MILLION), sender_balance(&app));
+
+    // 10 UST - 10% fee is waiting to be claimed, outside of the treasury
+    let claims: PendingClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &vault_dapp,
+            &QueryMsg::PendingClaims {
+                address: sender.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(10, claims.unbonding_period);
+    assert_eq!(1, claims.claims.len());
+    assert_eq!(
+        Uint128::from(9u64 * MILLION),
+        claims.claims[0].assets[0].amount
+    );
+    let treasury_res: TreasuryMsg::TotalValueResponse = app
+        .wrap()
+        .query_wasm_smart(
+            base_contracts.treasury.clone(),
+            &TreasuryMsg::QueryMsg::TotalValue {},
+        )
+        .unwrap();
+    assert_eq!(1_000_000u128, treasury_res.value.u128());
+
+    // Can't claim before the unbonding period passed
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::Claim {},
+        &[],
+    )
+    .unwrap_err();
+
+    app.update_block(|block| block.height += 10);
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::Claim {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(Uint128::from(99u64 * MILLION), sender_balance(&app));
+
+    // Claims are removed once paid out
+    let claims: PendingClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &vault_dapp,
+            &QueryMsg::PendingClaims {
+                address: sender.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(claims.claims.is_empty());
+}
+
+#[test]
+fn emergency_withdraw() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, vault_l_token) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    let deposit_msg = ExecuteMsg::ProvideLiquidity {
+        asset: Asset {
+            info: terraswap::asset::AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::from(10u64 * MILLION),
+        },
+        recipient: None,
+    };
+    let deposit_funds = [Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(10u64 * MILLION),
+    }];
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &deposit_msg,
+        &deposit_funds,
+    )
+    .unwrap();
+
+    let emergency_withdraw_msg = cw20::Cw20ExecuteMsg::Send {
+        contract: vault_dapp.to_string(),
+        amount: Uint128::from(5u64 * MILLION),
+        msg: to_binary(&DepositHookMsg::EmergencyWithdraw {}).unwrap(),
+    };
+    // Emergency withdrawals need emergency mode
+    app.execute_contract(
+        sender.clone(),
+        vault_l_token.clone(),
+        &emergency_withdraw_msg,
+        &[],
+    )
+    .unwrap_err();
+
+    // Only the admin can enable emergency mode
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetEmergencyMode { enabled: true },
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetEmergencyMode { enabled: true },
+        &[],
+    )
+    .unwrap();
+    let res: EmergencyModeResponse = app
+        .wrap()
+        .query_wasm_smart(&vault_dapp, &QueryMsg::EmergencyMode {})
+        .unwrap();
+    assert!(res.emergency_mode);
+
+    // Deposits are halted
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &deposit_msg,
+        &deposit_funds,
+    )
+    .unwrap_err();
+
+    // Half of the LP supply pays out half of the treasury balance, without fee
+    app.execute_contract(
+        sender.clone(),
+        vault_l_token.clone(),
+        &emergency_withdraw_msg,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        Uint128::from(95u64 * MILLION),
+        app.wrap()
+            .query_balance(sender.clone(), "uusd")
+            .unwrap()
+            .amount
+    );
+    assert_eq!(
+        Uint128::from(5u64 * MILLION),
+        lp_balance(&app, &vault_l_token, &sender)
+    );
+
+    // Deposits resume once emergency mode is disabled
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetEmergencyMode { enabled: false },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &deposit_msg,
+        &deposit_funds,
+    )
+    .unwrap();
+}
+
+#[test]
+fn rebalance_to_target_weights() {
+    let mut app = mock_app();
+    let sender = Addr::unchecked(TEST_CREATOR);
+    let base_contracts = init_contracts(&mut app);
+    configure_memory(&mut app, sender.clone(), &base_contracts);
+    let (vault_dapp, _) = init_vault_dapp(&mut app, sender.clone(), &base_contracts);
+
+    app.init_bank_balance(
+        &sender,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u64 * MILLION),
+        }],
+    )
+    .unwrap();
+
+    // Treasury holds 10 UST and 20 WHALE worth 10 UST
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            asset: Asset {
+                info: terraswap::asset::AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::from(10u64 * MILLION),
+            },
+            recipient: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10u64 * MILLION),
+        }],
+    )
+    .unwrap();
+    mint_some_whale(
+        &mut app,
+        sender.clone(),
+        base_contracts.whale.clone(),
+        Uint128::from(20u64 * MILLION),
+        base_contracts.treasury.to_string(),
+    );
+
+    let policy = |ust_weight: u64| RebalancePolicy {
+        targets: vec![
+            TargetWeight {
+                asset: "ust".to_string(),
+                weight: Decimal::percent(ust_weight),
+            },
+            TargetWeight {
+                asset: "whale".to_string(),
+                weight: Decimal::percent(100 - ust_weight),
+            },
+        ],
+        tolerance: Decimal::percent(5),
+        max_trade_value: Uint128::from(2u64 * MILLION),
+        max_spread: Some(Decimal::percent(10)),
+    };
+
+    // Nothing to rebalance without a policy
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::Rebalance {},
+        &[],
+    )
+    .unwrap_err();
+
+    // Only the admin can set the policy
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetRebalancePolicy {
+            policy: Some(policy(50)),
+        },
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetRebalancePolicy {
+            policy: Some(policy(50)),
+        },
+        &[],
+    )
+    .unwrap();
+    let res: RebalancePolicyResponse = app
+        .wrap()
+        .query_wasm_smart(&vault_dapp, &QueryMsg::RebalancePolicy {})
+        .unwrap();
+    assert_eq!(res.policy, Some(policy(50)));
+
+    // The assets are at their target weights
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::Rebalance {},
+        &[],
+    )
+    .unwrap_err();
+
+    // WHALE is 30% above its target, anyone can rebalance
+    app.execute_contract(
+        sender.clone(),
+        vault_dapp.clone(),
+        &ExecuteMsg::SetRebalancePolicy {
+            policy: Some(policy(80)),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        vault_dapp.clone(),
+        &ExecuteMsg::Rebalance {},
+        &[],
+    )
+    .unwrap();
+
+    // At most 2 UST worth of WHALE is swapped per call
+    let whale_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &base_contracts.whale,
+            &Cw20QueryMsg::Balance {
+                address: base_contracts.treasury.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(16u64 * MILLION), whale_balance.balance);
+    let ust_balance = app
+        .wrap()
+        .query_balance(base_contracts.treasury.clone(), "uusd")
+        .unwrap()
+        .amount;
+    assert!(ust_balance > Uint128::from(11u64 * MILLION));
+}
+
+/// Returns the payload of the vault event emitted by the vault dapp
+fn vault_event_attributes(res: &AppResponse, vault_dapp: &Addr) -> Vec<(&'static str, String)> {
+    let event = res
+        .events
+        .iter()
+        .find(|event| {
+            event.ty == "wasm-vault"
+                && event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "vault" && attr.value == vault_dapp.as_str())
+        })
+        .expect("vault event");
+    [
+        "action",
+        "depositor",
+        "assets_in",
+        "assets_out",
+        "shares_minted",
+        "shares_burned",
+        "exchange_rate",
+    ]
+    .iter()
+    .flat_map(|key| {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == *key)
+            .map(|attr| (*key, attr.value.clone()))
+    })
+    .collect()
+}
+
+fn lp_balance(app: &App, l_token: &Addr, owner: &Addr) -> Uint128 {
+    let balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            l_token,
+            &Cw20QueryMsg::Balance {
+                address: owner.to_string(),
+            },
+        )
+        .unwrap();
+    balance.balance
+}
+
 fn liquidity_token_value(app: &App, l_token: &Addr, treasury_addr: &Addr, owner: &Addr) -> Uint128 {
     let info_res: TokenInfoResponse = app
         .wrap()
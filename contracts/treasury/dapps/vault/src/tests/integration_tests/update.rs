@@ -8,7 +8,7 @@ use terra_multi_test::Executor;
 use terraswap::asset::Asset;
 
 use white_whale::treasury::msg as TreasuryMsg;
-use white_whale::treasury::vault_assets::{ValueRef, VaultAsset};
+use white_whale::treasury::vault_assets::{BreachAction, ValueRef, VaultAsset};
 
 use super::common_integration::BaseContracts;
 
@@ -35,6 +35,9 @@ pub fn change_base_to_whale(
                     value_reference: Some(ValueRef::Pool {
                         pair_address: base_contracts.whale_ust_pair.clone(),
                     }),
+                    max_value: None,
+                    max_price_move_per_block: None,
+                    breach_action: BreachAction::Clamp,
                 },
                 // Other asset is WHALE. It's value in uusd is calculated with the provided pool valueref
                 VaultAsset {
@@ -45,6 +48,9 @@ pub fn change_base_to_whale(
                         amount: Uint128::zero(),
                     },
                     value_reference: None,
+                    max_value: None,
+                    max_price_move_per_block: None,
+                    breach_action: BreachAction::Clamp,
                 },
             ],
             to_remove: vec![],
@@ -1,11 +1,11 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{Api, Decimal, MessageInfo};
+use cosmwasm_std::{from_binary, Api, Decimal, MessageInfo};
 
-use crate::contract::execute;
+use crate::contract::{execute, query};
 use crate::error::VaultError;
-use crate::tests::common::TEST_CREATOR;
+use crate::tests::common::{TEST_CREATOR, TREASURY_CONTRACT};
 use crate::tests::instantiate::mock_instantiate;
-use white_whale::dapps::vault::msg::ExecuteMsg;
+use white_whale::dapps::vault::msg::{ExecuteMsg, FeeResponse, QueryMsg};
 
 #[test]
 fn unsuccessful_set_fee_unauthorized() {
@@ -13,9 +13,10 @@ fn unsuccessful_set_fee_unauthorized() {
     mock_instantiate(deps.as_mut());
 
     let msg = ExecuteMsg::SetFee {
-        fee: white_whale::fee::Fee {
+        fee: Some(white_whale::fee::Fee {
             share: Decimal::percent(10u64),
-        },
+        }),
+        fee_recipient: None,
     };
     let info = MessageInfo {
         sender: deps.api.addr_validate("unauthorized").unwrap(),
@@ -40,15 +41,63 @@ fn successful_update_fee() {
     mock_instantiate(deps.as_mut());
 
     let msg = ExecuteMsg::SetFee {
-        fee: white_whale::fee::Fee {
+        fee: Some(white_whale::fee::Fee {
             share: Decimal::percent(10u64),
-        },
+        }),
+        fee_recipient: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     assert_eq!(0, res.messages.len());
 }
 
+/**
+ * Tests updating the fee recipient of the contract.
+ */
+#[test]
+fn successful_update_fee_recipient() {
+    let info = mock_info(TEST_CREATOR, &[]);
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    // Fees go to the treasury by default
+    let res: FeeResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap()).unwrap();
+    assert_eq!(res.fee_recipient.as_str(), TREASURY_CONTRACT);
+
+    let msg = ExecuteMsg::SetFee {
+        fee: None,
+        fee_recipient: Some("fee_collector".to_string()),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res: FeeResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap()).unwrap();
+    assert_eq!(res.fee_recipient.as_str(), "fee_collector");
+    assert_eq!(res.fee.share, Decimal::zero());
+}
+
+/**
+ * Tests the fee cap.
+ */
+#[test]
+fn unsuccessful_update_fee_too_high() {
+    let info = mock_info(TEST_CREATOR, &[]);
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let msg = ExecuteMsg::SetFee {
+        fee: Some(white_whale::fee::Fee {
+            share: Decimal::percent(21u64),
+        }),
+        fee_recipient: None,
+    };
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Err(VaultError::InvalidFee {}) => (),
+        _ => panic!("Must return VaultError::InvalidFee"),
+    }
+}
+
 /**
  * Tests updating the pool of the contract.
  */
@@ -1,9 +1,13 @@
-use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response, StdResult};
+use cosmwasm_std::{DepsMut, MessageInfo, Order, Response, StdResult, Storage};
+use cw_storage_plus::Map;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use terraswap::asset::AssetInfo;
 
 use crate::contract::MemoryResult;
 use crate::state::*;
-use white_whale::memory::msg::ExecuteMsg;
+use white_whale::memory::error::MemoryError;
+use white_whale::memory::msg::{EntryInfo, ExecuteMsg};
 
 /// Handles the common base execute messages
 pub fn handle_message(deps: DepsMut, info: MessageInfo, message: ExecuteMsg) -> MemoryResult {
@@ -15,6 +19,25 @@ pub fn handle_message(deps: DepsMut, info: MessageInfo, message: ExecuteMsg) ->
         ExecuteMsg::UpdateAssetAddresses { to_add, to_remove } => {
             update_asset_addresses(deps, info, to_add, to_remove)
         }
+        ExecuteMsg::UpdateAddresses {
+            contracts_to_add,
+            contracts_to_remove,
+            assets_to_add,
+            assets_to_remove,
+        } => update_addresses(
+            deps,
+            info,
+            contracts_to_add,
+            contracts_to_remove,
+            assets_to_add,
+            assets_to_remove,
+        ),
+        ExecuteMsg::DeprecateContract { name, in_favor_of } => {
+            deprecate_contract(deps, info, name, in_favor_of)
+        }
+        ExecuteMsg::DeprecateAsset { name, in_favor_of } => {
+            deprecate_asset(deps, info, name, in_favor_of)
+        }
     }
 }
 
@@ -32,17 +55,7 @@ pub fn update_contract_addresses(
     // Only Admin can call this method
     ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
 
-    for (name, new_address) in to_add.into_iter() {
-        // validate addr
-        let addr = deps.as_ref().api.addr_validate(&new_address)?;
-        // Update function for new or existing keys
-        let insert = |_| -> StdResult<Addr> { Ok(addr) };
-        CONTRACT_ADDRESSES.update(deps.storage, name.as_str(), insert)?;
-    }
-
-    for name in to_remove {
-        CONTRACT_ADDRESSES.remove(deps.storage, name.as_str());
-    }
+    store_contract_addresses(deps, to_add, to_remove)?;
 
     Ok(Response::new().add_attribute("action", "updated contract addresses"))
 }
@@ -57,17 +70,218 @@ pub fn update_asset_addresses(
     // Only Admin can call this method
     ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
 
+    store_asset_addresses(deps, to_add, to_remove)?;
+
+    Ok(Response::new().add_attribute("action", "updated asset addresses"))
+}
+
+/// Adds, updates or removes provided contract and asset addresses at once.
+pub fn update_addresses(
+    mut deps: DepsMut,
+    msg_info: MessageInfo,
+    contracts_to_add: Vec<(String, String)>,
+    contracts_to_remove: Vec<String>,
+    assets_to_add: Vec<(String, AssetInfo)>,
+    assets_to_remove: Vec<String>,
+) -> MemoryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    store_contract_addresses(deps.branch(), contracts_to_add, contracts_to_remove)?;
+    store_asset_addresses(deps, assets_to_add, assets_to_remove)?;
+
+    Ok(Response::new().add_attribute("action", "updated addresses"))
+}
+
+/// Deprecates a contract entry, it resolves to the address of `in_favor_of` from now on.
+pub fn deprecate_contract(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    name: String,
+    in_favor_of: String,
+) -> MemoryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    deprecate_entry(
+        deps.storage,
+        &CONTRACT_ADDRESSES,
+        &CONTRACT_ENTRIES,
+        &name,
+        &in_favor_of,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deprecated contract")
+        .add_attribute("name", name)
+        .add_attribute("in_favor_of", in_favor_of))
+}
+
+/// Deprecates an asset entry, it resolves to the asset of `in_favor_of` from now on.
+pub fn deprecate_asset(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    name: String,
+    in_favor_of: String,
+) -> MemoryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    deprecate_entry(
+        deps.storage,
+        &ASSET_ADDRESSES,
+        &ASSET_ENTRIES,
+        &name,
+        &in_favor_of,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deprecated asset")
+        .add_attribute("name", name)
+        .add_attribute("in_favor_of", in_favor_of))
+}
+
+fn store_contract_addresses(
+    deps: DepsMut,
+    to_add: Vec<(String, String)>,
+    to_remove: Vec<String>,
+) -> Result<(), MemoryError> {
     for (name, new_address) in to_add.into_iter() {
-        // Update function for new or existing keys
-        let insert = |_| -> StdResult<AssetInfo> { Ok(new_address) };
-        ASSET_ADDRESSES.update(deps.storage, name.as_str(), insert)?;
+        // validate addr
+        let addr = deps.as_ref().api.addr_validate(&new_address)?;
+        store_entry(
+            deps.storage,
+            &CONTRACT_ADDRESSES,
+            &CONTRACT_ENTRIES,
+            &name,
+            &addr,
+        )?;
     }
 
     for name in to_remove {
-        ASSET_ADDRESSES.remove(deps.storage, name.as_str());
+        remove_entry(deps.storage, &CONTRACT_ADDRESSES, &CONTRACT_ENTRIES, &name)?;
+    }
+    Ok(())
+}
+
+fn store_asset_addresses(
+    deps: DepsMut,
+    to_add: Vec<(String, AssetInfo)>,
+    to_remove: Vec<String>,
+) -> Result<(), MemoryError> {
+    for (name, new_address) in to_add.into_iter() {
+        store_entry(
+            deps.storage,
+            &ASSET_ADDRESSES,
+            &ASSET_ENTRIES,
+            &name,
+            &new_address,
+        )?;
     }
 
-    Ok(Response::new().add_attribute("action", "updated asset addresses"))
+    for name in to_remove {
+        remove_entry(deps.storage, &ASSET_ADDRESSES, &ASSET_ENTRIES, &name)?;
+    }
+    Ok(())
+}
+
+/// Adds or updates an entry, clearing its deprecation.
+/// Entries deprecated in favor of it are updated as well.
+fn store_entry<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    values: &Map<&str, T>,
+    entries: &Map<&str, EntryInfo>,
+    name: &str,
+    value: &T,
+) -> StdResult<()> {
+    values.save(storage, name, value)?;
+    bump_version(storage, entries, name, None)?;
+
+    for deprecated in deprecated_in_favor_of(storage, entries, name)? {
+        values.save(storage, &deprecated, value)?;
+        bump_version(storage, entries, &deprecated, Some(name.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Removes an entry, entries can't be removed while others are deprecated in favor of them.
+fn remove_entry<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    values: &Map<&str, T>,
+    entries: &Map<&str, EntryInfo>,
+    name: &str,
+) -> Result<(), MemoryError> {
+    if !deprecated_in_favor_of(storage, entries, name)?.is_empty() {
+        return Err(MemoryError::ReplacementInUse {
+            name: name.to_string(),
+        });
+    }
+    values.remove(storage, name);
+    entries.remove(storage, name);
+    Ok(())
+}
+
+/// Points `name` to the value of `in_favor_of`.
+/// The replacement can't be deprecated itself so lookups never have to follow a chain.
+fn deprecate_entry<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    values: &Map<&str, T>,
+    entries: &Map<&str, EntryInfo>,
+    name: &str,
+    in_favor_of: &str,
+) -> Result<(), MemoryError> {
+    if values.may_load(storage, name)?.is_none() {
+        return Err(MemoryError::NotFoundInMemory {});
+    }
+    let replacement = values
+        .may_load(storage, in_favor_of)?
+        .ok_or(MemoryError::NotFoundInMemory {})?;
+    let replacement_deprecated = entries
+        .may_load(storage, in_favor_of)?
+        .map_or(false, |entry| entry.deprecated_in_favor_of.is_some());
+    if name == in_favor_of
+        || replacement_deprecated
+        || !deprecated_in_favor_of(storage, entries, name)?.is_empty()
+    {
+        return Err(MemoryError::InvalidReplacement {
+            name: in_favor_of.to_string(),
+        });
+    }
+
+    values.save(storage, name, &replacement)?;
+    bump_version(storage, entries, name, Some(in_favor_of.to_string()))?;
+    Ok(())
+}
+
+/// Increments the version of the entry, entries stored before versioning start at version 0.
+fn bump_version(
+    storage: &mut dyn Storage,
+    entries: &Map<&str, EntryInfo>,
+    name: &str,
+    deprecated_in_favor_of: Option<String>,
+) -> StdResult<EntryInfo> {
+    entries.update(storage, name, |entry| -> StdResult<EntryInfo> {
+        Ok(EntryInfo {
+            version: entry.map_or(0, |entry| entry.version) + 1,
+            deprecated_in_favor_of,
+        })
+    })
+}
+
+/// Returns the names of the entries deprecated in favor of `name`
+fn deprecated_in_favor_of(
+    storage: &dyn Storage,
+    entries: &Map<&str, EntryInfo>,
+    name: &str,
+) -> StdResult<Vec<String>> {
+    let mut deprecated: Vec<String> = vec![];
+    for item in entries.range(storage, None, None, Order::Ascending) {
+        let (key, entry) = item?;
+        if entry.deprecated_in_favor_of.as_deref() == Some(name) {
+            deprecated.push(String::from_utf8(key)?);
+        }
+    }
+    Ok(deprecated)
 }
 
 pub fn set_admin(deps: DepsMut, info: MessageInfo, admin: String) -> MemoryResult {
@@ -31,5 +31,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::QueryAssets { names } => queries::query_assets(deps, env, names),
         QueryMsg::QueryContracts { names } => queries::query_contract(deps, env, names),
+        QueryMsg::ReverseLookup { address } => queries::query_reverse_lookup(deps, address),
+        QueryMsg::ListAssets { start_after, limit } => {
+            queries::query_list_assets(deps, start_after, limit)
+        }
+        QueryMsg::ListContracts { start_after, limit } => {
+            queries::query_list_contracts(deps, start_after, limit)
+        }
+        QueryMsg::ContractEntry { name } => queries::query_contract_entry(deps, name),
+        QueryMsg::AssetEntry { name } => queries::query_asset_entry(deps, name),
     }
 }
@@ -1,7 +1,14 @@
-use cosmwasm_std::{to_binary, Binary, Deps, Env, StdResult};
+use cosmwasm_std::{to_binary, Binary, Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
 use white_whale::memory::queries::{query_assets_from_mem, query_contracts_from_mem};
+use white_whale::treasury::vault_assets::get_identifier;
 
-use white_whale::memory::msg::{AssetQueryResponse, ContractQueryResponse};
+use crate::state::{ASSET_ADDRESSES, ASSET_ENTRIES, CONTRACT_ADDRESSES, CONTRACT_ENTRIES};
+use white_whale::memory::msg::{AssetQueryResponse, ContractQueryResponse, ReverseLookupResponse};
+
+// settings for pagination
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 pub fn query_assets(deps: Deps, env: Env, asset_names: Vec<String>) -> StdResult<Binary> {
     let assets = query_assets_from_mem(deps, &env.contract.address, &asset_names)?;
@@ -17,3 +24,80 @@ pub fn query_contract(deps: Deps, env: Env, names: Vec<String>) -> StdResult<Bin
         .collect();
     to_binary(&ContractQueryResponse { contracts: vector })
 }
+
+/// Finds the contract and asset names registered for `address`
+/// Scans the whole addressbook, which is kept small by governance
+pub fn query_reverse_lookup(deps: Deps, address: String) -> StdResult<Binary> {
+    let mut contracts: Vec<String> = vec![];
+    for item in CONTRACT_ADDRESSES.range(deps.storage, None, None, Order::Ascending) {
+        let (name, addr) = item?;
+        if addr.as_str() == address {
+            contracts.push(String::from_utf8(name)?);
+        }
+    }
+
+    let mut assets: Vec<String> = vec![];
+    for item in ASSET_ADDRESSES.range(deps.storage, None, None, Order::Ascending) {
+        let (name, info) = item?;
+        if *get_identifier(&info) == address {
+            assets.push(String::from_utf8(name)?);
+        }
+    }
+    to_binary(&ReverseLookupResponse { contracts, assets })
+}
+
+pub fn query_list_assets(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let assets = ASSET_ADDRESSES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (name, info) = item?;
+            Ok((String::from_utf8(name)?, info))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&AssetQueryResponse { assets })
+}
+
+pub fn query_list_contracts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let contracts = CONTRACT_ADDRESSES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (name, addr) = item?;
+            Ok((String::from_utf8(name)?, addr.to_string()))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&ContractQueryResponse { contracts })
+}
+
+pub fn query_contract_entry(deps: Deps, name: String) -> StdResult<Binary> {
+    // The entry has to exist, entries stored before versioning have no entry info
+    CONTRACT_ADDRESSES.load(deps.storage, &name)?;
+    to_binary(
+        &CONTRACT_ENTRIES
+            .may_load(deps.storage, &name)?
+            .unwrap_or_default(),
+    )
+}
+
+pub fn query_asset_entry(deps: Deps, name: String) -> StdResult<Binary> {
+    // The entry has to exist, entries stored before versioning have no entry info
+    ASSET_ADDRESSES.load(deps.storage, &name)?;
+    to_binary(
+        &ASSET_ENTRIES
+            .may_load(deps.storage, &name)?
+            .unwrap_or_default(),
+    )
+}
@@ -2,6 +2,7 @@ use cosmwasm_std::Addr;
 use cw_controllers::Admin;
 use cw_storage_plus::Map;
 use terraswap::asset::AssetInfo;
+use white_whale::memory::msg::EntryInfo;
 
 pub const ADMIN: Admin = Admin::new("admin");
 // stores name and address of tokens and pairs
@@ -10,3 +11,7 @@ pub const ASSET_ADDRESSES: Map<&str, AssetInfo> = Map::new("assets");
 
 // Pair key: "ust_luna_pair"
 pub const CONTRACT_ADDRESSES: Map<&str, Addr> = Map::new("contracts");
+
+// Version and deprecation of the entries, kept apart so the raw queries on the maps above keep working
+pub const ASSET_ENTRIES: Map<&str, EntryInfo> = Map::new("asset_entries");
+pub const CONTRACT_ENTRIES: Map<&str, EntryInfo> = Map::new("contract_entries");
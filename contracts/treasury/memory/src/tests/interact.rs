@@ -1,7 +1,9 @@
 use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, Addr};
 use terraswap::asset::AssetInfo;
 
-use crate::contract::execute;
+use crate::contract::{execute, query};
+use crate::state::CONTRACT_ADDRESSES;
 use white_whale::memory::error::MemoryError;
 use crate::tests::common::TEST_CREATOR;
 
@@ -103,3 +105,230 @@ fn authorized_memory_update() {
         _ => panic!("Should not return Err"),
     }
 }
+
+/**
+ * Test batched memory update and the listing queries
+ */
+#[test]
+fn batched_memory_update() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let env = mock_env();
+    let msg = ExecuteMsg::UpdateAddresses {
+        contracts_to_add: vec![
+            ("whale_ust_pair".to_string(), "pair_address".to_string()),
+            ("old_whale_ust_pair".to_string(), "pair_address".to_string()),
+        ],
+        contracts_to_remove: vec![],
+        assets_to_add: vec![
+            (
+                "whale".to_string(),
+                AssetInfo::Token {
+                    contract_addr: "whale_address".to_string(),
+                },
+            ),
+            (
+                "ust".to_string(),
+                AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+            ),
+        ],
+        assets_to_remove: vec![],
+    };
+
+    // Only the admin can update the memory
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("some_address", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(MemoryError::Admin(_)) => (),
+        Ok(_) => panic!("Should return Admin Err"),
+        _ => panic!("Should return Admin Err"),
+    }
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+
+    let res: ReverseLookupResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseLookup {
+                address: "pair_address".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.contracts,
+        vec![
+            "old_whale_ust_pair".to_string(),
+            "whale_ust_pair".to_string()
+        ]
+    );
+    assert!(res.assets.is_empty());
+
+    let res: ReverseLookupResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseLookup {
+                address: "uusd".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.assets, vec!["ust".to_string()]);
+
+    // Assets are listed by name
+    let res: AssetQueryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ListAssets {
+                start_after: None,
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.assets.len(), 1);
+    assert_eq!(res.assets[0].0, "ust".to_string());
+
+    let res: AssetQueryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ListAssets {
+                start_after: Some("ust".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.assets.len(), 1);
+    assert_eq!(res.assets[0].0, "whale".to_string());
+
+    let res: ContractQueryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::ListContracts {
+                start_after: Some("old_whale_ust_pair".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.contracts,
+        vec![("whale_ust_pair".to_string(), "pair_address".to_string())]
+    );
+}
+
+/**
+ * Test deprecating an entry in favor of another one
+ */
+#[test]
+fn deprecated_memory_entry() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    let env = mock_env();
+    let info = mock_info(TEST_CREATOR, &[]);
+    let msg = ExecuteMsg::UpdateContractAddresses {
+        to_add: vec![
+            (
+                "terraswap_pair".to_string(),
+                "terraswap_address".to_string(),
+            ),
+            (
+                "astroport_pair".to_string(),
+                "astroport_address".to_string(),
+            ),
+        ],
+        to_remove: vec![],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::DeprecateContract {
+        name: "terraswap_pair".to_string(),
+        in_favor_of: "astroport_pair".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // The deprecated entry resolves to the replacement
+    assert_eq!(
+        CONTRACT_ADDRESSES
+            .load(&deps.storage, "terraswap_pair")
+            .unwrap(),
+        Addr::unchecked("astroport_address")
+    );
+    let res: EntryInfo = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ContractEntry {
+                name: "terraswap_pair".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        EntryInfo {
+            version: 2,
+            deprecated_in_favor_of: Some("astroport_pair".to_string()),
+        }
+    );
+
+    // A deprecated entry can't replace another one
+    let msg = ExecuteMsg::DeprecateContract {
+        name: "astroport_pair".to_string(),
+        in_favor_of: "terraswap_pair".to_string(),
+    };
+    match execute(deps.as_mut(), env.clone(), info.clone(), msg) {
+        Err(MemoryError::InvalidReplacement { .. }) => (),
+        Ok(_) => panic!("Should return InvalidReplacement Err"),
+        _ => panic!("Should return InvalidReplacement Err"),
+    }
+
+    // Updates of the replacement are followed
+    let msg = ExecuteMsg::UpdateContractAddresses {
+        to_add: vec![("astroport_pair".to_string(), "new_address".to_string())],
+        to_remove: vec![],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        CONTRACT_ADDRESSES
+            .load(&deps.storage, "terraswap_pair")
+            .unwrap(),
+        Addr::unchecked("new_address")
+    );
+
+    // The replacement can't be removed while it is in use
+    let msg = ExecuteMsg::UpdateContractAddresses {
+        to_add: vec![],
+        to_remove: vec!["astroport_pair".to_string()],
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(MemoryError::ReplacementInUse { .. }) => (),
+        Ok(_) => panic!("Should return ReplacementInUse Err"),
+        _ => panic!("Should return ReplacementInUse Err"),
+    }
+}
@@ -2,26 +2,53 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::Order::Ascending;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
-    StdResult, Uint128,
+    coin, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal, Deps, DepsMut,
+    DistributionMsg, Empty, Env, MessageInfo, Order, Response, StakingMsg, StdError, StdResult,
+    Storage, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::{Bound, U64Key};
 use semver::Version;
-use terraswap::asset::AssetInfo;
+use sha2::{Digest, Sha256};
+use terraswap::asset::{Asset, AssetInfo};
 
+use white_whale::admin::{accept_admin, propose_admin, DEFAULT_ADMIN_PROPOSAL_EXPIRY};
+use white_whale::error::CommonError;
+use white_whale::events::{WhaleEvent, TREASURY};
 use white_whale::memory::LIST_SIZE_LIMIT;
+use white_whale::pause::{
+    assert_not_paused, is_paused, query_paused_scopes, set_paused, set_paused_scopes, PauseScope,
+};
 use white_whale::query::terraswap::query_asset_balance;
+use white_whale::treasury::holdings::{Holding, HoldingKind, HoldingValuation};
 use white_whale::treasury::msg::{
-    ConfigResponse, ExecuteMsg, HoldingValueResponse, InstantiateMsg, MigrateMsg, QueryMsg,
-    TotalValueResponse,
+    ActionHistoryResponse, ConfigResponse, DAppCapabilitiesResponse, DelegationEntry,
+    DistributionConfigResponse, ExecuteMsg, HoldingAmountEntry, HoldingAmountsResponse,
+    HoldingValueResponse, InstantiateMsg, MigrateMsg, OutflowAllowanceResponse,
+    PaginatedTotalValueResponse, PositionsResponse, QueryMsg, SpendsResponse,
+    StakingPositionsResponse, TotalValueBreakdownResponse, TotalValueResponse, ValueBreakdownEntry,
+    ValueHistoryResponse, VaultAssetEntry, VaultAssetsResponse,
+};
+use white_whale::treasury::state::{
+    load_base_asset, ActionLog, DAppAllowance, DAppCapabilities, DistributionRecipient,
+    MsgCategory, Outflow, OutflowLimit, Position, PriceSnapshot, Spend, State, Unbonding,
+    ValueSnapshot, ACTION_LOGS, ACTION_LOG_COUNT, ADMIN, BASE_ASSET, DAPP_ALLOWANCES,
+    DAPP_CAPABILITIES, DELEGATIONS, DISTRIBUTION, HOLDINGS, LUNA_DENOM, OPERATOR, OUTFLOWS,
+    OUTFLOW_LIMITS, POSITIONS, PRICE_SNAPSHOTS, SPENDS, SPEND_COUNT, STATE, TWAPS, UNBONDINGS,
+    VALUE_SNAPSHOTS, VALUE_SNAPSHOT_COUNT, VAULT_ASSETS,
 };
-use white_whale::treasury::state::{State, ADMIN, STATE, VAULT_ASSETS};
-use white_whale::treasury::vault_assets::{get_identifier, VaultAsset};
+use white_whale::treasury::twap::TwapState;
+use white_whale::treasury::vault_assets::{get_identifier, pool_price, ValueRef, VaultAsset};
 
 use crate::error::TreasuryError;
+use crate::migrations::{migrate_vault_assets, VAULT_ASSETS_MIGRATION_VERSION};
 
 type TreasuryResult = Result<Response, TreasuryError>;
 
+/// Identifier of the delegated and unbonding uluna in the total value breakdown
+pub const STAKED_LUNA_IDENTIFIER: &str = "staked_uluna";
+
 /*
     The treasury is the bank account of the protocol. It owns the liquidity and acts as a proxy contract.
     Whitelisted dApps construct messages for this contract. The dApps are controlled by Governance.
@@ -31,6 +58,21 @@ type TreasuryResult = Result<Response, TreasuryError>;
 const CONTRACT_NAME: &str = "crates.io:treasury";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// settings for pagination
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// Minimum number of blocks between two value snapshots
+const SNAPSHOT_INTERVAL: u64 = 600;
+// Number of value snapshots kept, older ones are pruned
+const MAX_VALUE_SNAPSHOTS: u64 = 720;
+// Number of DAppAction logs kept, older ones are pruned
+const MAX_ACTION_LOGS: u64 = 500;
+// Amount of the target denom priced when converting values out of the base asset
+const VALUE_IN_PRECISION: u128 = 1_000_000;
+// Unbonding period of the staking module in seconds
+const UNBONDING_PERIOD: u64 = 21 * 24 * 60 * 60;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -48,21 +90,74 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -> TreasuryResult {
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> TreasuryResult {
+    if !matches!(msg, ExecuteMsg::Unpause {}) && is_paused(deps.storage)? {
+        return Err(TreasuryError::Paused {});
+    }
+
     match msg {
-        ExecuteMsg::DAppAction { msgs } => execute_action(deps, info, msgs),
-        ExecuteMsg::SetAdmin { admin } => {
-            let admin_addr = deps.api.addr_validate(&admin)?;
-            let previous_admin = ADMIN.get(deps.as_ref())?.unwrap();
-            ADMIN.execute_update_admin(deps, info, Some(admin_addr))?;
-            Ok(Response::default()
-                .add_attribute("previous admin", previous_admin)
-                .add_attribute("admin", admin))
+        ExecuteMsg::DAppAction { msgs } => execute_action(deps, env, info, msgs),
+        ExecuteMsg::SetAdmin { admin } => Ok(propose_admin(
+            deps,
+            &env,
+            &info,
+            &ADMIN,
+            admin,
+            DEFAULT_ADMIN_PROPOSAL_EXPIRY,
+        )?),
+        ExecuteMsg::ProposeAdmin { admin, expires_in } => {
+            Ok(propose_admin(deps, &env, &info, &ADMIN, admin, expires_in)?)
+        }
+        ExecuteMsg::AcceptAdmin {} => Ok(accept_admin(deps, &env, &info, &ADMIN)?),
+        ExecuteMsg::Pause {} => Ok(set_paused(deps, &info, &ADMIN, true)?),
+        ExecuteMsg::Unpause {} => Ok(set_paused(deps, &info, &ADMIN, false)?),
+        ExecuteMsg::PauseScopes { scopes } => {
+            Ok(set_paused_scopes(deps, &info, &ADMIN, scopes, true)?)
+        }
+        ExecuteMsg::UnpauseScopes { scopes } => {
+            Ok(set_paused_scopes(deps, &info, &ADMIN, scopes, false)?)
         }
+        ExecuteMsg::SetOperator { operator } => set_operator(deps, info, operator),
         ExecuteMsg::AddDApp { dapp } => add_dapp(deps, info, dapp),
         ExecuteMsg::RemoveDApp { dapp } => remove_dapp(deps, info, dapp),
+        ExecuteMsg::SetDAppCapabilities { dapp, capabilities } => {
+            set_dapp_capabilities(deps, info, dapp, capabilities)
+        }
+        ExecuteMsg::UpdatePosition { id, asset, amount } => {
+            update_position(deps, env, info, id, asset, amount)
+        }
+        ExecuteMsg::ScheduleSpend {
+            recipient,
+            asset,
+            amount,
+            execute_after,
+        } => schedule_spend(deps, env, info, recipient, asset, amount, execute_after),
+        ExecuteMsg::ExecuteSpend { id } => execute_spend(deps, env, id),
+        ExecuteMsg::SetOutflowLimit { asset, limit } => set_outflow_limit(deps, info, asset, limit),
+        ExecuteMsg::SnapshotValue {} => snapshot_value(deps, env),
+        ExecuteMsg::UpdateHoldings { to_add, to_remove } => {
+            assert_admin_or_operator(deps.as_ref(), &info)?;
+            update_holdings(deps, to_add, to_remove)
+        }
+        ExecuteMsg::UpdateTwap { identifier } => update_twap(deps, env, identifier),
         ExecuteMsg::UpdateAssets { to_add, to_remove } => {
-            update_assets(deps, info, to_add, to_remove)
+            assert_admin_or_operator(deps.as_ref(), &info)?;
+            update_assets(deps, env, to_add, to_remove)
+        }
+        ExecuteMsg::SetBaseAsset { asset } => set_base_asset(deps, info, asset),
+        ExecuteMsg::SetDistribution { recipients } => set_distribution(deps, info, recipients),
+        ExecuteMsg::Distribute { asset, amount } => distribute(deps, env, info, asset, amount),
+        ExecuteMsg::Delegate { validator, amount } => delegate(deps, env, info, validator, amount),
+        ExecuteMsg::Undelegate { validator, amount } => {
+            undelegate(deps, env, info, validator, amount)
+        }
+        ExecuteMsg::Redelegate {
+            src_validator,
+            dst_validator,
+            amount,
+        } => redelegate(deps, info, src_validator, dst_validator, amount),
+        ExecuteMsg::WithdrawDelegatorRewards { validator } => {
+            withdraw_delegator_rewards(deps, info, validator)
         }
     }
 }
@@ -70,7 +165,25 @@ pub fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) ->
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> TreasuryResult {
     let version: Version = CONTRACT_VERSION.parse()?;
-    let storage_version: Version = get_contract_version(deps.storage)?.version.parse()?;
+    let contract_version = get_contract_version(deps.storage)?;
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(TreasuryError::InvalidMigrationContract(
+            contract_version.contract,
+            CONTRACT_NAME.to_string(),
+        ));
+    }
+    let storage_version: Version = contract_version.version.parse()?;
+    if storage_version > version {
+        return Err(TreasuryError::MigrationDowngrade(
+            contract_version.version,
+            CONTRACT_VERSION.to_string(),
+        ));
+    }
+
+    let vault_assets_migration_version: Version = VAULT_ASSETS_MIGRATION_VERSION.parse()?;
+    if storage_version < vault_assets_migration_version {
+        migrate_vault_assets(deps.storage)?;
+    }
 
     if storage_version < version {
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -82,9 +195,11 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> TreasuryResult {
 /// This contracts acts as a proxy contract for the dApps
 pub fn execute_action(
     deps: DepsMut,
+    env: Env,
     msg_info: MessageInfo,
     msgs: Vec<CosmosMsg<Empty>>,
 ) -> TreasuryResult {
+    assert_not_paused(deps.storage, PauseScope::Trading)?;
     let state = STATE.load(deps.storage)?;
     if !state
         .dapps
@@ -93,19 +208,435 @@ pub fn execute_action(
         return Err(TreasuryError::SenderNotWhitelisted {});
     }
 
-    Ok(Response::new().add_messages(msgs))
+    if let Some(capabilities) =
+        DAPP_CAPABILITIES.may_load(deps.storage, msg_info.sender.as_str())?
+    {
+        for msg in msgs.iter() {
+            assert_capability(&capabilities, msg)?;
+        }
+    }
+
+    for msg in msgs.iter() {
+        for (identifier, amount) in msg_outflows(msg) {
+            track_outflow(deps.storage, &env, &identifier, amount)?;
+        }
+    }
+
+    track_allowances(deps.storage, &msg_info.sender, &msgs)?;
+
+    let event = WhaleEvent::new(TREASURY, "execute_action")
+        .add_attribute("dapp", msg_info.sender.as_str())
+        .add_attribute("msg_count", msgs.len().to_string());
+    log_action(deps.storage, &env, msg_info.sender, &msgs)?;
+
+    Ok(Response::new().add_messages(msgs).add_attributes(event))
 }
 
-/// Update the stored vault asset information
-pub fn update_assets(
+/// Stores a log of the action, dropping the oldest log once MAX_ACTION_LOGS are stored
+fn log_action(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: Addr,
+    msgs: &[CosmosMsg<Empty>],
+) -> StdResult<()> {
+    let id = ACTION_LOG_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    let msgs_hash = Sha256::digest(to_binary(&msgs)?.as_slice());
+    ACTION_LOGS.save(
+        storage,
+        U64Key::new(id),
+        &ActionLog {
+            id,
+            sender,
+            height: env.block.height,
+            msg_count: msgs.len() as u64,
+            msgs_hash: Binary::from(msgs_hash.as_slice()),
+        },
+    )?;
+    ACTION_LOG_COUNT.save(storage, &id)?;
+    if id > MAX_ACTION_LOGS {
+        ACTION_LOGS.remove(storage, U64Key::new(id - MAX_ACTION_LOGS));
+    }
+    Ok(())
+}
+
+/// Returns the assets leaving the treasury through the message. Cw20 allowance increases count as
+/// outflows, the spender can transfer the tokens out of the treasury without going through it.
+fn msg_outflows(msg: &CosmosMsg<Empty>) -> Vec<(String, Uint128)> {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount
+            .iter()
+            .map(|coin| (coin.denom.clone(), coin.amount))
+            .collect(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) => {
+            // Native funds attached to the execution leave the treasury as well
+            let mut outflows: Vec<(String, Uint128)> = funds
+                .iter()
+                .map(|coin| (coin.denom.clone(), coin.amount))
+                .collect();
+            match from_binary(msg) {
+                Ok(Cw20ExecuteMsg::Transfer { amount, .. })
+                | Ok(Cw20ExecuteMsg::Send { amount, .. })
+                | Ok(Cw20ExecuteMsg::IncreaseAllowance { amount, .. }) => {
+                    outflows.push((contract_addr.clone(), amount))
+                }
+                _ => {}
+            }
+            outflows
+        }
+        _ => vec![],
+    }
+}
+
+/// Records the cw20 allowances granted or decreased through the dApp's messages
+fn track_allowances(
+    storage: &mut dyn Storage,
+    dapp: &Addr,
+    msgs: &[CosmosMsg<Empty>],
+) -> StdResult<()> {
+    let mut allowances = DAPP_ALLOWANCES
+        .may_load(storage, dapp.as_str())?
+        .unwrap_or_default();
+    let mut changed = false;
+    for msg in msgs.iter() {
+        let (contract_addr, msg) = match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => (contract_addr, msg),
+            _ => continue,
+        };
+        match from_binary(msg) {
+            Ok(Cw20ExecuteMsg::IncreaseAllowance {
+                spender, amount, ..
+            }) => {
+                match allowances
+                    .iter_mut()
+                    .find(|a| a.token.as_str() == contract_addr && a.spender == spender)
+                {
+                    Some(allowance) => allowance.amount += amount,
+                    None => allowances.push(DAppAllowance {
+                        token: Addr::unchecked(contract_addr),
+                        spender,
+                        amount,
+                    }),
+                }
+                changed = true;
+            }
+            Ok(Cw20ExecuteMsg::DecreaseAllowance {
+                spender, amount, ..
+            }) => {
+                if let Some(allowance) = allowances
+                    .iter_mut()
+                    .find(|a| a.token.as_str() == contract_addr && a.spender == spender)
+                {
+                    allowance.amount = allowance.amount.saturating_sub(amount);
+                    changed = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    if !changed {
+        return Ok(());
+    }
+    // Fully decreased allowances are removed by the token as well
+    allowances.retain(|a| !a.amount.is_zero());
+    if allowances.is_empty() {
+        DAPP_ALLOWANCES.remove(storage, dapp.as_str());
+        Ok(())
+    } else {
+        DAPP_ALLOWANCES.save(storage, dapp.as_str(), &allowances)
+    }
+}
+
+/// Adds the amount to the outflow of the current period, errors if this exceeds the outflow limit
+fn track_outflow(
+    storage: &mut dyn Storage,
+    env: &Env,
+    identifier: &str,
+    amount: Uint128,
+) -> Result<(), TreasuryError> {
+    let limit = match OUTFLOW_LIMITS.may_load(storage, identifier)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+
+    let mut outflow = current_outflow(storage, env, identifier, &limit)?;
+    let remaining = limit.amount.checked_sub(outflow.amount).unwrap_or_default();
+    if amount > remaining {
+        return Err(TreasuryError::OutflowLimitExceeded {
+            asset: identifier.to_string(),
+            remaining,
+        });
+    }
+    outflow.amount += amount;
+    OUTFLOWS.save(storage, identifier, &outflow)?;
+    Ok(())
+}
+
+/// Returns the outflow of the current period, starting a new period if the previous one has passed
+fn current_outflow(
+    storage: &dyn Storage,
+    env: &Env,
+    identifier: &str,
+    limit: &OutflowLimit,
+) -> StdResult<Outflow> {
+    match OUTFLOWS.may_load(storage, identifier)? {
+        Some(outflow) if env.block.height < outflow.period_start.saturating_add(limit.period) => {
+            Ok(outflow)
+        }
+        _ => Ok(Outflow {
+            period_start: env.block.height,
+            amount: Uint128::zero(),
+        }),
+    }
+}
+
+/// Set or remove the outflow limit of an asset
+pub fn set_outflow_limit(
     deps: DepsMut,
     msg_info: MessageInfo,
-    to_add: Vec<VaultAsset>,
-    to_remove: Vec<AssetInfo>,
+    asset: AssetInfo,
+    limit: Option<OutflowLimit>,
+) -> TreasuryResult {
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let identifier = get_identifier(&asset).as_str();
+    match limit {
+        Some(limit) => {
+            if limit.period == 0 {
+                return Err(TreasuryError::InvalidOutflowPeriod {});
+            }
+            OUTFLOW_LIMITS.save(deps.storage, identifier, &limit)?;
+        }
+        None => {
+            OUTFLOW_LIMITS.remove(deps.storage, identifier);
+            OUTFLOWS.remove(deps.storage, identifier);
+        }
+    }
+
+    Ok(WhaleEvent::new(TREASURY, "set_outflow_limit")
+        .add_attribute("asset", identifier)
+        .into_response())
+}
+
+/// Checks that the message is within the provided dapp capabilities
+fn assert_capability(
+    capabilities: &DAppCapabilities,
+    msg: &CosmosMsg<Empty>,
+) -> Result<(), TreasuryError> {
+    let category = msg_category(msg);
+    if let Some(msg_categories) = &capabilities.msg_categories {
+        if !msg_categories.contains(&category) {
+            return Err(TreasuryError::MsgCategoryNotAllowed(category));
+        }
+    }
+
+    if let Some(contracts) = &capabilities.contracts {
+        if let CosmosMsg::Wasm(wasm_msg) = msg {
+            let target = match wasm_msg {
+                WasmMsg::Execute { contract_addr, .. }
+                | WasmMsg::Migrate { contract_addr, .. }
+                | WasmMsg::UpdateAdmin { contract_addr, .. }
+                | WasmMsg::ClearAdmin { contract_addr } => Some(contract_addr),
+                _ => None,
+            };
+            match target {
+                Some(contract_addr) if contracts.iter().any(|c| c == contract_addr) => {}
+                Some(contract_addr) => {
+                    return Err(TreasuryError::ContractNotAllowed(contract_addr.clone()))
+                }
+                // Instantiating has no target contract, only allow it if the targets are unrestricted
+                None => return Err(TreasuryError::MsgCategoryNotAllowed(category)),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn msg_category(msg: &CosmosMsg<Empty>) -> MsgCategory {
+    match msg {
+        CosmosMsg::Bank(_) => MsgCategory::Bank,
+        CosmosMsg::Wasm(_) => MsgCategory::Wasm,
+        CosmosMsg::Staking(_) => MsgCategory::Staking,
+        CosmosMsg::Distribution(_) => MsgCategory::Distribution,
+        CosmosMsg::Custom(_) => MsgCategory::Custom,
+        _ => MsgCategory::Other,
+    }
+}
+
+/// Announces a transfer out of the treasury, executable once `execute_after` has passed
+pub fn schedule_spend(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    recipient: String,
+    asset: AssetInfo,
+    amount: Uint128,
+    execute_after: u64,
 ) -> TreasuryResult {
     // Only Admin can call this method
     ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
 
+    if execute_after <= env.block.time.seconds() {
+        return Err(TreasuryError::InvalidSpendTimelock {});
+    }
+
+    let id = SPEND_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    let spend = Spend {
+        id,
+        recipient: deps.api.addr_validate(&recipient)?,
+        asset,
+        amount,
+        execute_after,
+        executed: false,
+    };
+    SPENDS.save(deps.storage, U64Key::new(id), &spend)?;
+    SPEND_COUNT.save(deps.storage, &id)?;
+
+    Ok(WhaleEvent::new(TREASURY, "schedule_spend")
+        .add_attribute("spend_id", id.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("execute_after", execute_after.to_string())
+        .into_response())
+}
+
+/// Executes a scheduled spend once its timelock has passed
+pub fn execute_spend(deps: DepsMut, env: Env, id: u64) -> TreasuryResult {
+    assert_not_paused(deps.storage, PauseScope::Withdrawals)?;
+    let mut spend = SPENDS.load(deps.storage, U64Key::new(id))?;
+    if spend.executed {
+        return Err(TreasuryError::SpendAlreadyExecuted {});
+    }
+    if env.block.time.seconds() <= spend.execute_after {
+        return Err(TreasuryError::SpendTimelocked {
+            execute_after: spend.execute_after,
+        });
+    }
+
+    let balance = query_asset_balance(deps.as_ref(), &spend.asset, env.contract.address)?;
+    if balance < spend.amount {
+        return Err(TreasuryError::Broke {
+            balance,
+            requested: spend.amount,
+        });
+    }
+
+    spend.executed = true;
+    SPENDS.save(deps.storage, U64Key::new(id), &spend)?;
+
+    let transfer_msg = Asset {
+        info: spend.asset,
+        amount: spend.amount,
+    }
+    .into_msg(&deps.querier, spend.recipient)?;
+
+    Ok(Response::new().add_message(transfer_msg).add_attributes(
+        WhaleEvent::new(TREASURY, "execute_spend").add_attribute("spend_id", id.to_string()),
+    ))
+}
+
+/// Records the total value of the treasury at the current height
+pub fn snapshot_value(deps: DepsMut, env: Env) -> TreasuryResult {
+    let latest = VALUE_SNAPSHOTS
+        .range(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+    if let Some((_, snapshot)) = latest {
+        let next_height = snapshot.height + SNAPSHOT_INTERVAL;
+        if env.block.height < next_height {
+            return Err(TreasuryError::SnapshotTooEarly { next_height });
+        }
+    }
+
+    let mut count = VALUE_SNAPSHOT_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    // Drop the oldest snapshot once the buffer is full
+    if count >= MAX_VALUE_SNAPSHOTS {
+        let oldest = VALUE_SNAPSHOTS
+            .range(deps.storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?;
+        if let Some((_, snapshot)) = oldest {
+            VALUE_SNAPSHOTS.remove(deps.storage, U64Key::new(snapshot.height));
+            count -= 1;
+        }
+    }
+
+    let height = env.block.height;
+    let value = compute_total_value(deps.as_ref(), env.clone())?;
+    let mut price_snapshots = vec![];
+    // Record the prices the price move limits of the vault assets are checked against
+    for item in VAULT_ASSETS.range(deps.storage, None, None, Order::Ascending) {
+        let (key, vault_asset) = item?;
+        if vault_asset.max_price_move_per_block.is_none() {
+            continue;
+        }
+        price_snapshots.push((
+            String::from_utf8(key)?,
+            PriceSnapshot {
+                height,
+                unit_value: vault_asset.unit_value(deps.as_ref(), &env)?,
+            },
+        ));
+    }
+    for (identifier, snapshot) in price_snapshots {
+        PRICE_SNAPSHOTS.save(deps.storage, identifier.as_str(), &snapshot)?;
+    }
+    VALUE_SNAPSHOTS.save(
+        deps.storage,
+        U64Key::new(height),
+        &ValueSnapshot { height, value },
+    )?;
+    VALUE_SNAPSHOT_COUNT.save(deps.storage, &(count + 1))?;
+
+    Ok(WhaleEvent::new(TREASURY, "snapshot_value")
+        .add_attribute("height", height.to_string())
+        .add_attribute("value", value.to_string())
+        .into_response())
+}
+
+/// Accumulates the pool price of a vault asset valued through ValueRef::Twap
+pub fn update_twap(deps: DepsMut, env: Env, identifier: String) -> TreasuryResult {
+    let vault_asset = VAULT_ASSETS.load(deps.storage, identifier.as_str())?;
+    let (pair_address, window) = match vault_asset.value_reference {
+        Some(ValueRef::Twap {
+            pair_address,
+            window,
+        }) => (pair_address, window),
+        _ => return Err(TreasuryError::NotTwapAsset(identifier)),
+    };
+
+    let (price, quote_asset) = pool_price(deps.as_ref(), &vault_asset.asset.info, &pair_address)?;
+    let now = env.block.time.seconds();
+    let twap_state = match TWAPS.may_load(deps.storage, identifier.as_str())? {
+        Some(mut twap_state) => {
+            twap_state.update(price, now, window)?;
+            twap_state
+        }
+        None => TwapState::new(quote_asset, price, now),
+    };
+    TWAPS.save(deps.storage, identifier.as_str(), &twap_state)?;
+
+    Ok(WhaleEvent::new(TREASURY, "update_twap")
+        .add_attribute("asset", identifier)
+        .add_attribute("price", price.to_string())
+        .into_response())
+}
+
+/// Update the stored vault asset information
+/// Updates the vault assets, the caller is checked by the execute router
+pub fn update_assets(
+    deps: DepsMut,
+    env: Env,
+    to_add: Vec<VaultAsset>,
+    to_remove: Vec<AssetInfo>,
+) -> TreasuryResult {
     // Check the vault size to be within the size limit to prevent running out of gas when doing lookups
     let current_vault_size = VAULT_ASSETS
         .keys(deps.storage, None, None, Ascending)
@@ -117,6 +648,13 @@ pub fn update_assets(
 
     for new_asset in to_add.into_iter() {
         let id = get_identifier(&new_asset.asset.info).as_str();
+        // Prevent counting the same holding twice
+        if HOLDINGS.may_load(deps.storage, id)?.is_some() {
+            return Err(TreasuryError::AlreadyHolding(id.to_string()));
+        }
+        // The value reference might have changed, restart the TWAP and the price snapshot
+        TWAPS.remove(deps.storage, id);
+        PRICE_SNAPSHOTS.remove(deps.storage, id);
         // update function for new or existing keys
         let insert =
             |_vault_asset: Option<VaultAsset>| -> StdResult<VaultAsset> { Ok(new_asset.clone()) };
@@ -124,10 +662,364 @@ pub fn update_assets(
     }
 
     for asset_id in to_remove {
+        // The staked uluna is valued through the uluna vault asset
+        if get_identifier(&asset_id) == LUNA_DENOM && !staked_amount(deps.as_ref(), &env)?.is_zero()
+        {
+            return Err(TreasuryError::StakingAssetInUse {});
+        }
         VAULT_ASSETS.remove(deps.storage, get_identifier(&asset_id).as_str());
+        TWAPS.remove(deps.storage, get_identifier(&asset_id).as_str());
+        PRICE_SNAPSHOTS.remove(deps.storage, get_identifier(&asset_id).as_str());
+    }
+
+    Ok(WhaleEvent::new(TREASURY, "update_cw20_token_list").into_response())
+}
+
+/// Sets the asset all values are denominated in
+pub fn set_base_asset(deps: DepsMut, msg_info: MessageInfo, asset: AssetInfo) -> TreasuryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    // Every value reference resolves to the vault asset without one
+    let id = get_identifier(&asset).as_str();
+    match VAULT_ASSETS.may_load(deps.storage, id)? {
+        Some(VaultAsset {
+            value_reference: None,
+            ..
+        }) => (),
+        _ => return Err(TreasuryError::InvalidBaseAsset(id.to_string())),
+    }
+    BASE_ASSET.save(deps.storage, &asset)?;
+
+    Ok(WhaleEvent::new(TREASURY, "set_base_asset")
+        .add_attribute("base_asset", id)
+        .into_response())
+}
+
+/// Sets the recipients of distributed assets
+pub fn set_distribution(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    recipients: Vec<DistributionRecipient>,
+) -> TreasuryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    if recipients.is_empty()
+        || recipients.len() > LIST_SIZE_LIMIT
+        || recipients.iter().any(|recipient| recipient.weight == 0)
+    {
+        return Err(TreasuryError::InvalidDistribution {});
     }
+    let recipients = recipients
+        .into_iter()
+        .map(|recipient| {
+            Ok(DistributionRecipient {
+                address: deps.api.addr_validate(recipient.address.as_str())?,
+                weight: recipient.weight,
+            })
+        })
+        .collect::<StdResult<Vec<DistributionRecipient>>>()?;
+    DISTRIBUTION.save(deps.storage, &recipients)?;
 
-    Ok(Response::new().add_attribute("action", "update_cw20_token_list"))
+    Ok(WhaleEvent::new(TREASURY, "set_distribution").into_response())
+}
+
+/// Splits an amount of an asset over the distribution recipients, the last recipient receives
+/// the rounding remainder.
+pub fn distribute(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    asset: AssetInfo,
+    amount: Uint128,
+) -> TreasuryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let recipients = DISTRIBUTION
+        .may_load(deps.storage)?
+        .ok_or(TreasuryError::NoDistribution {})?;
+    let balance = query_asset_balance(deps.as_ref(), &asset, env.contract.address)?;
+    if balance < amount {
+        return Err(TreasuryError::Broke {
+            balance,
+            requested: amount,
+        });
+    }
+
+    let total_weight: u64 = recipients.iter().map(|recipient| recipient.weight).sum();
+    let mut remaining = amount;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (i, recipient) in recipients.iter().enumerate() {
+        let share = if i == recipients.len() - 1 {
+            remaining
+        } else {
+            amount.multiply_ratio(recipient.weight, total_weight)
+        };
+        remaining = remaining.checked_sub(share).map_err(StdError::overflow)?;
+        if share.is_zero() {
+            continue;
+        }
+        messages.push(
+            Asset {
+                info: asset.clone(),
+                amount: share,
+            }
+            .into_msg(&deps.querier, recipient.address.clone())?,
+        );
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(
+        WhaleEvent::new(TREASURY, "distribute")
+            .add_attribute("asset", get_identifier(&asset).as_str())
+            .add_attribute("amount", amount.to_string()),
+    ))
+}
+
+/// Delegates uluna held by the treasury to a validator
+pub fn delegate(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    validator: String,
+    amount: Uint128,
+) -> TreasuryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    if amount.is_zero() {
+        return Err(CommonError::InvalidZeroAmount {}.into());
+    }
+    // Delegations are valued through the uluna vault asset
+    if VAULT_ASSETS.may_load(deps.storage, LUNA_DENOM)?.is_none() {
+        return Err(TreasuryError::StakingAssetNotRegistered {});
+    }
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address, LUNA_DENOM)?
+        .amount;
+    if balance < amount {
+        return Err(TreasuryError::Broke {
+            balance,
+            requested: amount,
+        });
+    }
+
+    DELEGATIONS.update(
+        deps.storage,
+        validator.as_str(),
+        |delegated| -> StdResult<_> { Ok(delegated.unwrap_or_default() + amount) },
+    )?;
+
+    Ok(Response::new()
+        .add_message(StakingMsg::Delegate {
+            validator: validator.clone(),
+            amount: coin(amount.u128(), LUNA_DENOM),
+        })
+        .add_attributes(
+            WhaleEvent::new(TREASURY, "delegate")
+                .add_attribute("validator", validator)
+                .add_attribute("amount", amount.to_string()),
+        ))
+}
+
+/// Undelegates uluna from a validator, the amount is tracked as unbonding until the unbonding
+/// period has passed
+pub fn undelegate(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    validator: String,
+    amount: Uint128,
+) -> TreasuryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    if amount.is_zero() {
+        return Err(CommonError::InvalidZeroAmount {}.into());
+    }
+    reduce_delegation(deps.storage, &validator, amount)?;
+
+    let mut unbondings = pending_unbondings(deps.storage, &env)?;
+    unbondings.push(Unbonding {
+        validator: validator.clone(),
+        amount,
+        release_at: env.block.time.plus_seconds(UNBONDING_PERIOD).seconds(),
+    });
+    UNBONDINGS.save(deps.storage, &unbondings)?;
+
+    Ok(Response::new()
+        .add_message(StakingMsg::Undelegate {
+            validator: validator.clone(),
+            amount: coin(amount.u128(), LUNA_DENOM),
+        })
+        .add_attributes(
+            WhaleEvent::new(TREASURY, "undelegate")
+                .add_attribute("validator", validator)
+                .add_attribute("amount", amount.to_string()),
+        ))
+}
+
+/// Moves delegated uluna from one validator to another
+pub fn redelegate(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    src_validator: String,
+    dst_validator: String,
+    amount: Uint128,
+) -> TreasuryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    if amount.is_zero() {
+        return Err(CommonError::InvalidZeroAmount {}.into());
+    }
+    reduce_delegation(deps.storage, &src_validator, amount)?;
+    DELEGATIONS.update(
+        deps.storage,
+        dst_validator.as_str(),
+        |delegated| -> StdResult<_> { Ok(delegated.unwrap_or_default() + amount) },
+    )?;
+
+    Ok(Response::new()
+        .add_message(StakingMsg::Redelegate {
+            src_validator: src_validator.clone(),
+            dst_validator: dst_validator.clone(),
+            amount: coin(amount.u128(), LUNA_DENOM),
+        })
+        .add_attributes(
+            WhaleEvent::new(TREASURY, "redelegate")
+                .add_attribute("src_validator", src_validator)
+                .add_attribute("dst_validator", dst_validator)
+                .add_attribute("amount", amount.to_string()),
+        ))
+}
+
+/// Withdraws the staking rewards accrued with a validator to the treasury
+pub fn withdraw_delegator_rewards(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    validator: String,
+) -> TreasuryResult {
+    // Only Admin can call this method
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    Ok(Response::new()
+        .add_message(DistributionMsg::WithdrawDelegatorReward {
+            validator: validator.clone(),
+        })
+        .add_attributes(
+            WhaleEvent::new(TREASURY, "withdraw_delegator_rewards")
+                .add_attribute("validator", validator),
+        ))
+}
+
+fn reduce_delegation(
+    storage: &mut dyn Storage,
+    validator: &str,
+    amount: Uint128,
+) -> Result<(), TreasuryError> {
+    let delegated = DELEGATIONS
+        .may_load(storage, validator)?
+        .unwrap_or_default();
+    let remaining =
+        delegated
+            .checked_sub(amount)
+            .map_err(|_| TreasuryError::InsufficientDelegation {
+                validator: validator.to_string(),
+                delegated,
+            })?;
+    if remaining.is_zero() {
+        DELEGATIONS.remove(storage, validator);
+    } else {
+        DELEGATIONS.save(storage, validator, &remaining)?;
+    }
+    Ok(())
+}
+
+/// Returns the unbondings that have not been released yet
+fn pending_unbondings(storage: &dyn Storage, env: &Env) -> StdResult<Vec<Unbonding>> {
+    let now = env.block.time.seconds();
+    Ok(UNBONDINGS
+        .may_load(storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|unbonding| unbonding.release_at > now)
+        .collect())
+}
+
+/// Update the holdings that are not tracked as vault assets
+pub fn update_holdings(
+    deps: DepsMut,
+    to_add: Vec<Holding>,
+    to_remove: Vec<String>,
+) -> TreasuryResult {
+    for id in to_remove.iter() {
+        HOLDINGS.remove(deps.storage, id.as_str());
+    }
+
+    for holding in to_add.into_iter() {
+        match &holding.kind {
+            HoldingKind::Native { .. } => {}
+            HoldingKind::Cw20 { contract_addr }
+            | HoldingKind::Cw721 { contract_addr }
+            | HoldingKind::Lp { contract_addr } => {
+                deps.api.addr_validate(contract_addr)?;
+            }
+        }
+        if let (HoldingKind::Cw721 { .. }, HoldingValuation::Reference(_)) =
+            (&holding.kind, &holding.valuation)
+        {
+            return Err(TreasuryError::InvalidHoldingValuation {});
+        }
+
+        let id = holding.kind.identifier().as_str();
+        // Prevent counting the same holding twice
+        if VAULT_ASSETS.may_load(deps.storage, id)?.is_some() {
+            return Err(TreasuryError::AlreadyHolding(id.to_string()));
+        }
+        HOLDINGS.save(deps.storage, id, &holding)?;
+    }
+
+    // The holdings are listed in the config query, limit their amount
+    let holdings_size = HOLDINGS.keys(deps.storage, None, None, Ascending).count();
+    if holdings_size > LIST_SIZE_LIMIT {
+        return Err(TreasuryError::HoldingsLimitReached {});
+    }
+
+    Ok(WhaleEvent::new(TREASURY, "update_holdings").into_response())
+}
+
+/// Sets or removes the operator
+pub fn set_operator(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    operator: Option<String>,
+) -> TreasuryResult {
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let operator = operator
+        .map(|operator| deps.api.addr_validate(&operator))
+        .transpose()?;
+    OPERATOR.set(deps, operator.clone())?;
+
+    Ok(WhaleEvent::new(TREASURY, "set_operator")
+        .add_attribute(
+            "operator",
+            operator
+                .map(|operator| operator.to_string())
+                .unwrap_or_default(),
+        )
+        .into_response())
+}
+
+/// Checks the sender is the admin or the operator
+fn assert_admin_or_operator(deps: Deps, msg_info: &MessageInfo) -> Result<(), TreasuryError> {
+    if OPERATOR.is_admin(deps, &msg_info.sender)? {
+        return Ok(());
+    }
+    Ok(ADMIN.assert_admin(deps, &msg_info.sender)?)
 }
 
 /// Add a contract to the whitelist
@@ -149,10 +1041,13 @@ pub fn add_dapp(deps: DepsMut, msg_info: MessageInfo, dapp: String) -> TreasuryR
     STATE.save(deps.storage, &state)?;
 
     // Respond and note the change
-    Ok(Response::new().add_attribute("Added contract to whitelist: ", dapp))
+    Ok(WhaleEvent::new(TREASURY, "add_dapp")
+        .add_attribute("dapp", dapp)
+        .into_response())
 }
 
-/// Remove a contract from the whitelist
+/// Remove a contract from the whitelist, clearing its capabilities and revoking the cw20
+/// allowances granted through it
 pub fn remove_dapp(deps: DepsMut, msg_info: MessageInfo, dapp: String) -> TreasuryResult {
     ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
 
@@ -165,9 +1060,130 @@ pub fn remove_dapp(deps: DepsMut, msg_info: MessageInfo, dapp: String) -> Treasu
     let dapp_address = deps.api.addr_validate(&dapp)?;
     state.dapps.retain(|addr| *addr != dapp_address);
     STATE.save(deps.storage, &state)?;
+    DAPP_CAPABILITIES.remove(deps.storage, dapp_address.as_str());
+
+    // The funds of the dapp's positions are no longer tracked
+    let position_keys = POSITIONS
+        .prefix(dapp_address.as_bytes())
+        .keys(deps.storage, None, None, Ascending)
+        .collect::<Vec<Vec<u8>>>();
+    for id in position_keys.iter() {
+        POSITIONS.remove(deps.storage, (dapp_address.as_bytes(), id.as_slice()));
+    }
+
+    // Decreasing by at least the remaining allowance makes the token remove it
+    let allowances = DAPP_ALLOWANCES
+        .may_load(deps.storage, dapp_address.as_str())?
+        .unwrap_or_default();
+    DAPP_ALLOWANCES.remove(deps.storage, dapp_address.as_str());
+    let revoke_msgs = allowances
+        .iter()
+        .map(|allowance| {
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: allowance.token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::DecreaseAllowance {
+                    spender: allowance.spender.clone(),
+                    amount: allowance.amount,
+                    expires: None,
+                })?,
+                funds: vec![],
+            }))
+        })
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
 
     // Respond and note the change
-    Ok(Response::new().add_attribute("Removed contract from whitelist: ", dapp))
+    let event = WhaleEvent::new(TREASURY, "remove_dapp")
+        .add_attribute("dapp", dapp)
+        .add_attribute("revoked_allowances", revoke_msgs.len().to_string())
+        .add_attribute("removed_positions", position_keys.len().to_string());
+    Ok(Response::new()
+        .add_messages(revoke_msgs)
+        .add_attributes(event))
+}
+
+/// Sets the amount of an external position of the calling dapp, a zero amount removes it
+pub fn update_position(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    id: String,
+    asset: AssetInfo,
+    amount: Uint128,
+) -> TreasuryResult {
+    let state = STATE.load(deps.storage)?;
+    if !state.dapps.contains(&msg_info.sender) {
+        return Err(TreasuryError::SenderNotWhitelisted {});
+    }
+
+    // The position is valued through the vault asset
+    let identifier = get_identifier(&asset).clone();
+    if VAULT_ASSETS
+        .may_load(deps.storage, identifier.as_str())?
+        .is_none()
+    {
+        return Err(TreasuryError::UnknownPositionAsset(identifier));
+    }
+
+    let key = (msg_info.sender.as_bytes(), id.as_bytes());
+    if amount.is_zero() {
+        POSITIONS.remove(deps.storage, key);
+    } else {
+        POSITIONS.save(
+            deps.storage,
+            key,
+            &Position {
+                dapp: msg_info.sender.clone(),
+                id: id.clone(),
+                asset,
+                amount,
+                updated_at: env.block.height,
+            },
+        )?;
+        // The positions are valued on every total value query, limit their amount
+        let positions_size = POSITIONS.keys(deps.storage, None, None, Ascending).count();
+        if positions_size > LIST_SIZE_LIMIT {
+            return Err(TreasuryError::PositionsLimitReached {});
+        }
+    }
+
+    Ok(WhaleEvent::new(TREASURY, "update_position")
+        .add_attribute("dapp", msg_info.sender.as_str())
+        .add_attribute("id", id)
+        .add_attribute("asset", identifier)
+        .add_attribute("amount", amount.to_string())
+        .into_response())
+}
+
+/// Set or clear the capabilities of a whitelisted dapp
+pub fn set_dapp_capabilities(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    dapp: String,
+    capabilities: Option<DAppCapabilities>,
+) -> TreasuryResult {
+    ADMIN.assert_admin(deps.as_ref(), &msg_info.sender)?;
+
+    let state = STATE.load(deps.storage)?;
+    let dapp_address = deps.api.addr_validate(&dapp)?;
+    if !state.dapps.contains(&dapp_address) {
+        return Err(TreasuryError::NotInList {});
+    }
+
+    match capabilities {
+        Some(capabilities) => {
+            if let Some(contracts) = &capabilities.contracts {
+                for contract in contracts.iter() {
+                    deps.api.addr_validate(contract.as_str())?;
+                }
+            }
+            DAPP_CAPABILITIES.save(deps.storage, dapp_address.as_str(), &capabilities)?
+        }
+        None => DAPP_CAPABILITIES.remove(deps.storage, dapp_address.as_str()),
+    }
+
+    Ok(WhaleEvent::new(TREASURY, "set_dapp_capabilities")
+        .add_attribute("dapp", dapp)
+        .into_response())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -177,41 +1193,275 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TotalValue {} => to_binary(&TotalValueResponse {
             value: compute_total_value(deps, env)?,
         }),
+        QueryMsg::TotalValueBreakdown {} => to_binary(&query_total_value_breakdown(deps, &env)?),
+        QueryMsg::PaginatedTotalValue { start_after, limit } => {
+            let (value, last_asset) = compute_paginated_total_value(deps, env, start_after, limit)?;
+            to_binary(&PaginatedTotalValueResponse { value, last_asset })
+        }
         QueryMsg::HoldingAmount { identifier } => {
-            let vault_asset: VaultAsset = VAULT_ASSETS.load(deps.storage, identifier.as_str())?;
-            to_binary(&query_asset_balance(
-                deps,
-                &vault_asset.asset.info,
-                env.contract.address,
-            )?)
-        }
-        QueryMsg::HoldingValue { identifier } => to_binary(&HoldingValueResponse {
-            value: compute_holding_value(deps, &env, identifier)?,
+            to_binary(&query_holding_amount(deps, &env, identifier)?)
+        }
+        QueryMsg::HoldingAmounts { start_after, limit } => {
+            to_binary(&query_holding_amounts(deps, &env, start_after, limit)?)
+        }
+        QueryMsg::HoldingValue { identifier, amount } => to_binary(&HoldingValueResponse {
+            value: compute_holding_value(deps, &env, identifier, amount)?,
+        }),
+        QueryMsg::ValueIn { identifier, denom } => to_binary(&HoldingValueResponse {
+            value: compute_holding_value_in(deps, &env, identifier, denom)?,
         }),
         QueryMsg::VaultAssetConfig { identifier } => {
             to_binary(&VAULT_ASSETS.load(deps.storage, identifier.as_str())?)
         }
+        QueryMsg::DAppCapabilities { dapp } => to_binary(&DAppCapabilitiesResponse {
+            capabilities: DAPP_CAPABILITIES.may_load(deps.storage, dapp.as_str())?,
+        }),
+        QueryMsg::Spend { id } => to_binary(&SPENDS.load(deps.storage, U64Key::new(id))?),
+        QueryMsg::Spends { start_after, limit } => {
+            to_binary(&query_spends(deps, start_after, limit)?)
+        }
+        QueryMsg::OutflowAllowance { identifier } => {
+            to_binary(&query_outflow_allowance(deps, &env, identifier)?)
+        }
+        QueryMsg::ValueHistory { start_after, limit } => {
+            to_binary(&query_value_history(deps, start_after, limit)?)
+        }
+        QueryMsg::ActionHistory { start_after, limit } => {
+            to_binary(&query_action_history(deps, start_after, limit)?)
+        }
+        QueryMsg::Twap { identifier } => to_binary(&TWAPS.load(deps.storage, identifier.as_str())?),
+        QueryMsg::VaultAssets { start_after, limit } => {
+            to_binary(&query_vault_assets(deps, start_after, limit)?)
+        }
+        QueryMsg::DistributionConfig {} => to_binary(&DistributionConfigResponse {
+            recipients: DISTRIBUTION.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        QueryMsg::StakingPositions {} => to_binary(&query_staking_positions(deps, &env)?),
+        QueryMsg::PausedScopes {} => to_binary(&query_paused_scopes(deps.storage)?),
+        QueryMsg::Positions { dapp } => to_binary(&query_positions(deps, dapp)?),
     }
 }
 
+/// Returns the tracked delegations and pending unbondings
+pub fn query_staking_positions(deps: Deps, env: &Env) -> StdResult<StakingPositionsResponse> {
+    let delegations = DELEGATIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (validator, amount) = item?;
+            Ok(DelegationEntry {
+                validator: String::from_utf8(validator)?,
+                amount,
+            })
+        })
+        .collect::<StdResult<Vec<DelegationEntry>>>()?;
+    Ok(StakingPositionsResponse {
+        delegations,
+        unbondings: pending_unbondings(deps.storage, env)?,
+    })
+}
+
 /// Returns the whitelisted dapps
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let state = STATE.load(deps.storage)?;
     let dapps: Vec<Addr> = state.dapps;
+    let holdings = HOLDINGS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, holding)| holding))
+        .collect::<StdResult<Vec<Holding>>>()?;
     let resp = ConfigResponse {
         dapps: dapps
             .iter()
             .map(|dapp| -> String { dapp.to_string() })
             .collect(),
+        holdings,
+        base_asset: load_base_asset(deps.storage)?,
+        operator: OPERATOR.get(deps)?.map(|operator| operator.to_string()),
     };
     Ok(resp)
 }
 
-/// Returns the value of a specified asset.
-pub fn compute_holding_value(deps: Deps, env: &Env, holding: String) -> StdResult<Uint128> {
+/// Returns a page of the registered vault assets
+pub fn query_vault_assets(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VaultAssetsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.as_str()));
+
+    let assets = VAULT_ASSETS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, vault_asset) = item?;
+            Ok(VaultAssetEntry {
+                identifier: get_identifier(&vault_asset.asset.info).clone(),
+                asset_info: vault_asset.asset.info,
+                value_reference: vault_asset.value_reference,
+            })
+        })
+        .collect::<StdResult<Vec<VaultAssetEntry>>>()?;
+
+    Ok(VaultAssetsResponse { assets })
+}
+
+/// Returns the balance of a vault asset or holding, without valuing it
+pub fn query_holding_amount(deps: Deps, env: &Env, identifier: String) -> StdResult<Uint128> {
+    match VAULT_ASSETS.may_load(deps.storage, identifier.as_str())? {
+        Some(vault_asset) => {
+            query_asset_balance(deps, &vault_asset.asset.info, env.contract.address.clone())
+        }
+        None => HOLDINGS
+            .load(deps.storage, identifier.as_str())?
+            .kind
+            .amount(deps, &env.contract.address),
+    }
+}
+
+/// Returns the balances of a page of the registered vault assets
+pub fn query_holding_amounts(
+    deps: Deps,
+    env: &Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<HoldingAmountsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.as_str()));
+
+    let amounts = VAULT_ASSETS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, vault_asset) = item?;
+            Ok(HoldingAmountEntry {
+                identifier: get_identifier(&vault_asset.asset.info).clone(),
+                amount: query_asset_balance(
+                    deps,
+                    &vault_asset.asset.info,
+                    env.contract.address.clone(),
+                )?,
+                asset_info: vault_asset.asset.info,
+            })
+        })
+        .collect::<StdResult<Vec<HoldingAmountEntry>>>()?;
+
+    Ok(HoldingAmountsResponse { amounts })
+}
+
+/// Returns the outflow limit of an asset and what remains of it in the current period
+pub fn query_outflow_allowance(
+    deps: Deps,
+    env: &Env,
+    identifier: String,
+) -> StdResult<OutflowAllowanceResponse> {
+    let limit = OUTFLOW_LIMITS.may_load(deps.storage, identifier.as_str())?;
+    let remaining = match &limit {
+        Some(limit) => {
+            let outflow = current_outflow(deps.storage, env, identifier.as_str(), limit)?;
+            Some(limit.amount.checked_sub(outflow.amount).unwrap_or_default())
+        }
+        None => None,
+    };
+    Ok(OutflowAllowanceResponse { limit, remaining })
+}
+
+/// Returns a page of the recorded value snapshots
+pub fn query_value_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ValueHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let snapshots = VALUE_SNAPSHOTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, snapshot)| snapshot))
+        .collect::<StdResult<Vec<ValueSnapshot>>>()?;
+
+    Ok(ValueHistoryResponse { snapshots })
+}
+
+/// Returns a page of the logged dapp actions
+pub fn query_action_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ActionHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let actions = ACTION_LOGS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, action)| action))
+        .collect::<StdResult<Vec<ActionLog>>>()?;
+
+    Ok(ActionHistoryResponse { actions })
+}
+
+/// Returns a page of the scheduled spends
+pub fn query_spends(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SpendsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let spends = SPENDS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, spend)| spend))
+        .collect::<StdResult<Vec<Spend>>>()?;
+
+    Ok(SpendsResponse { spends })
+}
+
+/// Returns the value of a specified asset, optionally of a set amount of it.
+pub fn compute_holding_value(
+    deps: Deps,
+    env: &Env,
+    holding: String,
+    amount: Option<Uint128>,
+) -> StdResult<Uint128> {
+    if let Some(other_holding) = HOLDINGS.may_load(deps.storage, holding.as_str())? {
+        if amount.is_some() {
+            return Err(StdError::generic_err(
+                "Only vault assets can be valued at a set amount",
+            ));
+        }
+        return other_holding.value(deps, env);
+    }
     let mut vault_asset: VaultAsset = VAULT_ASSETS.load(deps.storage, holding.as_str())?;
-    let value = vault_asset.value(deps, env, None)?;
-    Ok(value)
+    let value = vault_asset.value(deps, env, amount)?;
+    vault_asset.guard_value(deps, env, value)
+}
+
+/// Returns the value of a specified asset in `denom`, converted from the base asset through
+/// the value reference of `denom`.
+pub fn compute_holding_value_in(
+    deps: Deps,
+    env: &Env,
+    holding: String,
+    denom: String,
+) -> StdResult<Uint128> {
+    let value = compute_holding_value(deps, env, holding, None)?;
+    if *get_identifier(&load_base_asset(deps.storage)?) == denom {
+        return Ok(value);
+    }
+
+    // Price the denom in the base asset
+    let mut denom_asset: VaultAsset = VAULT_ASSETS.load(deps.storage, denom.as_str())?;
+    let price = denom_asset.value(deps, env, Some(Uint128::from(VALUE_IN_PRECISION)))?;
+    if price.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "{} has no value in the base asset",
+            denom
+        )));
+    }
+    Ok(value.multiply_ratio(VALUE_IN_PRECISION, price))
 }
 
 /// Computes the total value locked in this contract
@@ -221,10 +1471,170 @@ pub fn compute_total_value(deps: Deps, env: Env) -> StdResult<Uint128> {
         .range(deps.storage, None, None, Order::Ascending)
         .collect::<StdResult<Vec<(Vec<u8>, VaultAsset)>>>()?;
 
+    let mut total_value = sum_asset_values(deps, &env, &mut all_assets)?;
+    // Add the holdings that are not tracked as vault assets
+    for item in HOLDINGS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, holding) = item?;
+        total_value += holding.value(deps, &env)?;
+    }
+    // Add the uluna that is delegated or still unbonding
+    total_value += staked_value(deps, &env)?;
+    // Add the funds dapps moved into external protocols
+    for item in POSITIONS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, position) = item?;
+        total_value += position_value(deps, &env, &position)?;
+    }
+
+    Ok(total_value)
+}
+
+/// Values a position through the vault asset of its asset, positions of assets which are no
+/// longer vault assets have no value
+fn position_value(deps: Deps, env: &Env, position: &Position) -> StdResult<Uint128> {
+    match VAULT_ASSETS.may_load(deps.storage, get_identifier(&position.asset).as_str())? {
+        Some(mut vault_asset) => {
+            let value = vault_asset.value(deps, env, Some(position.amount))?;
+            vault_asset.guard_value(deps, env, value)
+        }
+        None => Ok(Uint128::zero()),
+    }
+}
+
+/// Returns the external positions, optionally only those of one dapp
+pub fn query_positions(deps: Deps, dapp: Option<String>) -> StdResult<PositionsResponse> {
+    let positions = match dapp {
+        Some(dapp) => {
+            let dapp = deps.api.addr_validate(&dapp)?;
+            POSITIONS
+                .prefix(dapp.as_bytes())
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, position)| position))
+                .collect::<StdResult<Vec<Position>>>()?
+        }
+        None => POSITIONS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, position)| position))
+            .collect::<StdResult<Vec<Position>>>()?,
+    };
+    Ok(PositionsResponse { positions })
+}
+
+/// Returns the amount, value and share of the total value of every vault asset, holding, position
+/// and of the staked uluna. The values sum up to the total value.
+pub fn query_total_value_breakdown(
+    deps: Deps,
+    env: &Env,
+) -> StdResult<TotalValueBreakdownResponse> {
+    let mut entries = vec![];
+    for item in VAULT_ASSETS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, mut vault_asset) = item?;
+        let amount =
+            query_asset_balance(deps, &vault_asset.asset.info, env.contract.address.clone())?;
+        let value = vault_asset.value(deps, env, Some(amount))?;
+        entries.push(ValueBreakdownEntry {
+            identifier: get_identifier(&vault_asset.asset.info).clone(),
+            amount,
+            value: vault_asset.guard_value(deps, env, value)?,
+            share: Decimal::zero(),
+        });
+    }
+    for item in HOLDINGS.range(deps.storage, None, None, Order::Ascending) {
+        let (key, holding) = item?;
+        entries.push(ValueBreakdownEntry {
+            identifier: String::from_utf8(key)?,
+            amount: holding.kind.amount(deps, &env.contract.address)?,
+            value: holding.value(deps, env)?,
+            share: Decimal::zero(),
+        });
+    }
+    for item in POSITIONS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, position) = item?;
+        entries.push(ValueBreakdownEntry {
+            identifier: format!("{}/{}", position.dapp, position.id),
+            amount: position.amount,
+            value: position_value(deps, env, &position)?,
+            share: Decimal::zero(),
+        });
+    }
+    let staked = staked_amount(deps, env)?;
+    if !staked.is_zero() {
+        entries.push(ValueBreakdownEntry {
+            identifier: STAKED_LUNA_IDENTIFIER.to_string(),
+            amount: staked,
+            value: staked_value(deps, env)?,
+            share: Decimal::zero(),
+        });
+    }
+
+    let total_value = entries
+        .iter()
+        .fold(Uint128::zero(), |total, entry| total + entry.value);
+    if !total_value.is_zero() {
+        for entry in entries.iter_mut() {
+            entry.share = Decimal::from_ratio(entry.value, total_value);
+        }
+    }
+
+    Ok(TotalValueBreakdownResponse {
+        total_value,
+        entries,
+    })
+}
+
+/// Sums the delegated and unbonding uluna
+fn staked_amount(deps: Deps, env: &Env) -> StdResult<Uint128> {
+    let mut staked = Uint128::zero();
+    for item in DELEGATIONS.range(deps.storage, None, None, Order::Ascending) {
+        staked += item?.1;
+    }
+    for unbonding in pending_unbondings(deps.storage, env)? {
+        staked += unbonding.amount;
+    }
+    Ok(staked)
+}
+
+/// Values the delegated and unbonding uluna through the uluna vault asset
+fn staked_value(deps: Deps, env: &Env) -> StdResult<Uint128> {
+    let staked = staked_amount(deps, env)?;
+    if staked.is_zero() {
+        return Ok(staked);
+    }
+    VAULT_ASSETS
+        .load(deps.storage, LUNA_DENOM)?
+        .value(deps, env, Some(staked))
+}
+
+/// Computes the value of a page of assets, returning it together with the identifier of the last asset
+pub fn compute_paginated_total_value(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<(Uint128, Option<String>)> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.as_str()));
+
+    let mut assets = VAULT_ASSETS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<(Vec<u8>, VaultAsset)>>>()?;
+
+    let last_asset = assets
+        .last()
+        .map(|(_, vault_asset)| get_identifier(&vault_asset.asset.info).clone());
+    Ok((sum_asset_values(deps, &env, &mut assets)?, last_asset))
+}
+
+fn sum_asset_values(
+    deps: Deps,
+    env: &Env,
+    assets: &mut [(Vec<u8>, VaultAsset)],
+) -> StdResult<Uint128> {
     let mut total_value = Uint128::zero();
     // Calculate their value iteratively
-    for vault_asset_entry in all_assets.iter_mut() {
-        total_value += vault_asset_entry.1.value(deps, &env, None)?;
+    for (_, vault_asset) in assets.iter_mut() {
+        let value = vault_asset.value(deps, env, None)?;
+        total_value += vault_asset.guard_value(deps, env, value)?;
     }
 
     Ok(total_value)
@@ -1,18 +1,33 @@
 use cosmwasm_std::{StdError, Uint128};
 use cw_controllers::AdminError;
 use thiserror::Error;
+use white_whale::admin::AdminTransferError;
+use white_whale::error::CommonError;
+use white_whale::treasury::state::MsgCategory;
 
 #[derive(Error, Debug)]
 pub enum TreasuryError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
+    #[error("{0}")]
+    AdminTransfer(AdminTransferError),
+
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 
+    #[error("Cannot migrate {0}, the contract must be {1}")]
+    InvalidMigrationContract(String, String),
+
+    #[error("Cannot migrate from version {0} to the older version {1}")]
+    MigrationDowngrade(String, String),
+
     #[error("DApp is already whitelisted")]
     AlreadyInList {},
 
@@ -25,6 +40,57 @@ pub enum TreasuryError {
     #[error("Sender is not whitelisted")]
     SenderNotWhitelisted {},
 
+    #[error("DApp is not allowed to send {0:?} messages")]
+    MsgCategoryNotAllowed(MsgCategory),
+
+    #[error("DApp is not allowed to target contract {0}")]
+    ContractNotAllowed(String),
+
+    #[error("Spends must be scheduled after the current block time")]
+    InvalidSpendTimelock {},
+
+    #[error("Spend can not be executed before {execute_after}")]
+    SpendTimelocked { execute_after: u64 },
+
+    #[error("Spend has already been executed")]
+    SpendAlreadyExecuted {},
+
+    #[error("Outflow limit of {asset} exceeded, {remaining} remaining this period")]
+    OutflowLimitExceeded { asset: String, remaining: Uint128 },
+
+    #[error("Outflow limit period must be at least one block")]
+    InvalidOutflowPeriod {},
+
+    #[error("Value snapshots can only be taken every interval, next at height {next_height}")]
+    SnapshotTooEarly { next_height: u64 },
+
+    #[error("{0} is already registered as a vault asset or holding")]
+    AlreadyHolding(String),
+
+    #[error("Cw721 holdings can't be valued through a ValueRef")]
+    InvalidHoldingValuation {},
+
+    #[error("The Holdings list has reached its limit, can't add more Holdings.")]
+    HoldingsLimitReached {},
+
+    #[error("{0} is not a vault asset, positions can only be held in vault assets")]
+    UnknownPositionAsset(String),
+
+    #[error("The positions list has reached its limit, can't add more positions.")]
+    PositionsLimitReached {},
+
+    #[error("{0} is not valued through a TWAP")]
+    NotTwapAsset(String),
+
+    #[error("The base asset {0} must be a vault asset without a value reference")]
+    InvalidBaseAsset(String),
+
+    #[error("A distribution needs at least one recipient, each with a non-zero weight")]
+    InvalidDistribution {},
+
+    #[error("No distribution recipients have been set")]
+    NoDistribution {},
+
     #[error("The Assets list has reached its limit, can't add more Assets.")]
     AssetsLimitReached {},
 
@@ -37,9 +103,34 @@ pub enum TreasuryError {
         balance: Uint128,
         requested: Uint128,
     },
+
+    #[error("uluna must be registered as a vault asset to value delegations")]
+    StakingAssetNotRegistered {},
+
+    #[error("uluna can't be removed from the vault assets while luna is delegated or unbonding")]
+    StakingAssetInUse {},
+
+    #[error("Only {delegated} is delegated to {validator}")]
+    InsufficientDelegation {
+        validator: String,
+        delegated: Uint128,
+    },
+
+    #[error("The contract is paused")]
+    Paused {},
 }
 impl From<semver::Error> for TreasuryError {
     fn from(err: semver::Error) -> Self {
         Self::SemVer(err.to_string())
     }
 }
+
+impl From<AdminTransferError> for TreasuryError {
+    fn from(err: AdminTransferError) -> Self {
+        match err {
+            AdminTransferError::Std(err) => Self::Std(err),
+            AdminTransferError::Admin(err) => Self::Admin(err),
+            err => Self::AdminTransfer(err),
+        }
+    }
+}
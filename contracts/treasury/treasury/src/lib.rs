@@ -1,4 +1,5 @@
 pub mod contract;
 mod error;
+mod migrations;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,47 @@
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::Map;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terraswap::asset::Asset;
+
+use white_whale::treasury::state::VAULT_ASSETS;
+use white_whale::treasury::vault_assets::{BreachAction, ValueRef, VaultAsset};
+
+/// First version migrating the stored vault assets to the current VaultAsset format
+pub(crate) const VAULT_ASSETS_MIGRATION_VERSION: &str = "0.2.0";
+
+// Storage of the vault assets before VAULT_ASSETS_MIGRATION_VERSION, the raw keys match VAULT_ASSETS
+const VAULT_ASSETS_V0: Map<&str, VaultAssetV0> = Map::new("vault_assets");
+
+/// VaultAsset as stored by contracts deployed before VAULT_ASSETS_MIGRATION_VERSION
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultAssetV0 {
+    pub asset: Asset,
+    pub value_reference: Option<ValueRef>,
+}
+
+impl From<VaultAssetV0> for VaultAsset {
+    fn from(vault_asset: VaultAssetV0) -> Self {
+        VaultAsset {
+            asset: vault_asset.asset,
+            value_reference: vault_asset.value_reference,
+            max_value: None,
+            max_price_move_per_block: None,
+            breach_action: BreachAction::Clamp,
+        }
+    }
+}
+
+/// Rewrites every stored vault asset in the current VaultAsset format.
+/// Fields added to VaultAsset get their defaults in the From<VaultAssetV0> conversion.
+pub fn migrate_vault_assets(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy_assets: Vec<(Vec<u8>, VaultAssetV0)> = VAULT_ASSETS_V0
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Vec<u8>, VaultAssetV0)>>>()?;
+
+    for (key, vault_asset) in legacy_assets {
+        let identifier = String::from_utf8(key)?;
+        VAULT_ASSETS.save(storage, identifier.as_str(), &vault_asset.into())?;
+    }
+    Ok(())
+}
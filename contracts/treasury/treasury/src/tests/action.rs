@@ -1,13 +1,19 @@
 use std::panic;
 
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, query};
 use crate::error::*;
 use crate::tests::common::TEST_CREATOR;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{to_binary, Addr, QuerierWrapper, ReplyOn, SubMsg, Uint128, WasmMsg};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, QuerierWrapper, ReplyOn, SubMsg,
+    Uint128, WasmMsg,
+};
 use cw20::Cw20ExecuteMsg;
 use terraswap::asset::{Asset, AssetInfo};
-use white_whale::treasury::msg::{ExecuteMsg, InstantiateMsg};
+use white_whale::treasury::msg::{
+    ActionHistoryResponse, ExecuteMsg, InstantiateMsg, OutflowAllowanceResponse, QueryMsg,
+};
+use white_whale::treasury::state::{DAppCapabilities, MsgCategory, OutflowLimit};
 
 const NOT_ALLOWED: &str = "some_other_contract";
 
@@ -117,3 +123,244 @@ fn test_whitelisted() {
         Err(e) => panic!("Unknown error: {}", e),
     }
 }
+
+#[test]
+fn test_dapp_capabilities() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = init_msg();
+    let info = mock_info(TEST_CREATOR, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::AddDApp {
+        dapp: TEST_CREATOR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::SetDAppCapabilities {
+        dapp: TEST_CREATOR.to_string(),
+        capabilities: Some(DAppCapabilities {
+            msg_categories: Some(vec![MsgCategory::Wasm]),
+            contracts: Some(vec![Addr::unchecked("test_token")]),
+        }),
+    };
+    // Only the admin can set capabilities
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(NOT_ALLOWED, &[]),
+        msg.clone(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Admin(_)) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let allowed_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "test_token".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Burn {
+            amount: Uint128::from(1u64),
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+    let msg = ExecuteMsg::DAppAction {
+        msgs: vec![allowed_msg],
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let other_contract_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: NOT_ALLOWED.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Burn {
+            amount: Uint128::from(1u64),
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+    let msg = ExecuteMsg::DAppAction {
+        msgs: vec![other_contract_msg],
+    };
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::ContractNotAllowed(contract)) => assert_eq!(contract, NOT_ALLOWED),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let bank_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: NOT_ALLOWED.to_string(),
+        amount: coins(100, "uusd"),
+    });
+    let msg = ExecuteMsg::DAppAction {
+        msgs: vec![bank_msg.clone()],
+    };
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::MsgCategoryNotAllowed(MsgCategory::Bank)) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    // Clearing the capabilities lifts the restrictions
+    let msg = ExecuteMsg::SetDAppCapabilities {
+        dapp: TEST_CREATOR.to_string(),
+        capabilities: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::DAppAction {
+        msgs: vec![bank_msg],
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+#[test]
+fn test_outflow_limit() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = init_msg();
+    let info = mock_info(TEST_CREATOR, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::AddDApp {
+        dapp: TEST_CREATOR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::SetOutflowLimit {
+        asset: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        limit: Some(OutflowLimit {
+            amount: Uint128::from(1_000u64),
+            period: 100,
+        }),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let send_msg = |amount: u128| ExecuteMsg::DAppAction {
+        msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: NOT_ALLOWED.to_string(),
+            amount: coins(amount, "uusd"),
+        })],
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), send_msg(600)).unwrap();
+
+    let allowance: OutflowAllowanceResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OutflowAllowance {
+                identifier: "uusd".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(allowance.remaining, Some(Uint128::from(400u64)));
+
+    match execute(deps.as_mut(), mock_env(), info.clone(), send_msg(500)) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::OutflowLimitExceeded { asset, remaining }) => {
+            assert_eq!(asset, "uusd");
+            assert_eq!(remaining, Uint128::from(400u64));
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    // Cw20 transfers count towards the limit of the token
+    let msg = ExecuteMsg::SetOutflowLimit {
+        asset: AssetInfo::Token {
+            contract_addr: "test_token".to_string(),
+        },
+        limit: Some(OutflowLimit {
+            amount: Uint128::from(10u64),
+            period: 100,
+        }),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::DAppAction {
+        msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "test_token".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: NOT_ALLOWED.to_string(),
+                amount: Uint128::from(11u64),
+            })
+            .unwrap(),
+            funds: vec![],
+        })],
+    };
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::OutflowLimitExceeded { asset, .. }) => assert_eq!(asset, "test_token"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    // So do cw20 allowances, the spender can transfer the tokens out of the treasury
+    let allowance_msg = |amount: u64| ExecuteMsg::DAppAction {
+        msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "test_token".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: NOT_ALLOWED.to_string(),
+                amount: Uint128::from(amount),
+                expires: None,
+            })
+            .unwrap(),
+            funds: vec![],
+        })],
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), allowance_msg(6)).unwrap();
+    match execute(deps.as_mut(), mock_env(), info.clone(), allowance_msg(5)) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::OutflowLimitExceeded { asset, remaining }) => {
+            assert_eq!(asset, "test_token");
+            assert_eq!(remaining, Uint128::from(4u64));
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    // The allowance resets once the period has passed
+    let mut env = mock_env();
+    env.block.height += 100;
+    execute(deps.as_mut(), env, info, send_msg(500)).unwrap();
+}
+
+#[test]
+fn test_action_history() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = init_msg();
+    let info = mock_info(TEST_CREATOR, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::AddDApp {
+        dapp: TEST_CREATOR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: NOT_ALLOWED.to_string(),
+        amount: coins(100, "uusd"),
+    });
+    let msg = ExecuteMsg::DAppAction {
+        msgs: vec![bank_msg.clone(), bank_msg],
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::DAppAction { msgs: vec![] };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res: ActionHistoryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ActionHistory {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.actions.len(), 2);
+    assert_eq!(res.actions[0].id, 1);
+    assert_eq!(res.actions[0].sender.as_str(), TEST_CREATOR);
+    assert_eq!(res.actions[0].height, mock_env().block.height);
+    assert_eq!(res.actions[0].msg_count, 2);
+    assert_eq!(res.actions[1].msg_count, 0);
+    assert_ne!(res.actions[0].msgs_hash, res.actions[1].msgs_hash);
+}
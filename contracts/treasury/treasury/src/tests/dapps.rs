@@ -1,10 +1,12 @@
-use cosmwasm_std::from_binary;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, to_binary, CosmosMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
 use cw_controllers::AdminError;
 
+use white_whale::admin::AdminTransferError;
 use white_whale::memory::LIST_SIZE_LIMIT;
 use white_whale::treasury::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
-use white_whale::treasury::state::{State, STATE};
+use white_whale::treasury::state::{State, ADMIN, DAPP_ALLOWANCES, STATE};
 
 use crate::contract::{execute, instantiate, query};
 use crate::error::TreasuryError;
@@ -55,6 +57,105 @@ fn test_update_admin() {
     }
 }
 
+#[test]
+fn test_propose_and_accept_admin() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg()).unwrap();
+
+    let msg = ExecuteMsg::ProposeAdmin {
+        admin: String::from("addr0001"),
+        expires_in: 10,
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    // The admin only changes once the transfer is accepted
+    assert_eq!(
+        ADMIN.get(deps.as_ref()).unwrap().unwrap().as_str(),
+        TEST_CREATOR
+    );
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::AcceptAdmin {},
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::AdminTransfer(AdminTransferError::NotPendingAdmin {})) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let mut env = mock_env();
+    env.block.height += 10;
+    match execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0001", &[]),
+        ExecuteMsg::AcceptAdmin {},
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::AdminTransfer(AdminTransferError::Expired(_))) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::AcceptAdmin {},
+    )
+    .unwrap();
+    assert_eq!(
+        ADMIN.get(deps.as_ref()).unwrap().unwrap().as_str(),
+        "addr0001"
+    );
+}
+
+#[test]
+fn test_pause_and_unpause() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg()).unwrap();
+
+    // only the admin can pause
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::Pause {},
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Admin(AdminError::NotAdmin {})) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::AddDApp {
+        dapp: "addr420".to_string(),
+    };
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Paused {}) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
 #[test]
 fn test_add_dapp() {
     let mut deps = mock_dependencies(&[]);
@@ -66,10 +167,15 @@ fn test_add_dapp() {
         dapp: "addr420".to_string(),
     };
 
-    match execute(deps.as_mut(), mock_env(), info, msg) {
-        Ok(_) => (),
-        Err(_) => panic!("Unknown error"),
-    }
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "add_dapp"),
+            attr("module", "treasury"),
+            attr("dapp", "addr420"),
+        ]
+    );
     let config: ConfigResponse =
         from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
     assert_eq!(1, config.dapps.len());
@@ -139,3 +245,63 @@ fn test_remove_dapp() {
         from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
     assert_eq!(0, config.dapps.len());
 }
+
+fn allowance_msg(spender: &str, amount: u128, increase: bool) -> CosmosMsg {
+    let msg = if increase {
+        Cw20ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::from(amount),
+            expires: None,
+        }
+    } else {
+        Cw20ExecuteMsg::DecreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::from(amount),
+            expires: None,
+        }
+    };
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "token".to_string(),
+        msg: to_binary(&msg).unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn test_remove_dapp_revokes_allowances() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg()).unwrap();
+
+    let msg = ExecuteMsg::AddDApp {
+        dapp: "addr420".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::DAppAction {
+        msgs: vec![
+            allowance_msg("pair", 100, true),
+            allowance_msg("pair", 50, true),
+            allowance_msg("pair", 30, false),
+            allowance_msg("other_pair", 10, true),
+            allowance_msg("other_pair", 10, false),
+        ],
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("addr420", &[]), msg).unwrap();
+    let allowances = DAPP_ALLOWANCES.load(&deps.storage, "addr420").unwrap();
+    assert_eq!(allowances.len(), 1);
+    assert_eq!(allowances[0].spender, "pair");
+    assert_eq!(allowances[0].amount, Uint128::from(120u128));
+
+    let msg = ExecuteMsg::RemoveDApp {
+        dapp: "addr420".to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, allowance_msg("pair", 120, false));
+    assert!(res.attributes.contains(&attr("revoked_allowances", "1")));
+    assert!(DAPP_ALLOWANCES
+        .may_load(&deps.storage, "addr420")
+        .unwrap()
+        .is_none());
+}
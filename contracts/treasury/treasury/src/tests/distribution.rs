@@ -0,0 +1,115 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coin, coins, from_binary, Addr, BankMsg, CosmosMsg, Uint128};
+use terraswap::asset::AssetInfo;
+
+use white_whale::treasury::msg::{
+    DistributionConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use white_whale::treasury::state::DistributionRecipient;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::TreasuryError;
+
+use super::common::TEST_CREATOR;
+
+fn recipient(address: &str, weight: u64) -> DistributionRecipient {
+    DistributionRecipient {
+        address: Addr::unchecked(address),
+        weight,
+    }
+}
+
+fn distribute_msg(amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Distribute {
+        asset: AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+        amount: Uint128::from(amount),
+    }
+}
+
+#[test]
+fn distribute_by_weight() {
+    let mut deps = mock_dependencies(&[coin(1000, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+    // Nothing to distribute to yet
+    match execute(deps.as_mut(), mock_env(), info.clone(), distribute_msg(100)) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::NoDistribution {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let recipients = vec![
+        recipient("stakers", 2),
+        recipient("warchest", 1),
+        recipient("burn", 1),
+    ];
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::SetDistribution {
+            recipients: recipients.clone(),
+        },
+    )
+    .unwrap();
+    let config: DistributionConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::DistributionConfig {}).unwrap())
+            .unwrap();
+    assert_eq!(config.recipients, recipients);
+
+    // The last recipient receives the rounding remainder
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), distribute_msg(101)).unwrap();
+    let expected: Vec<CosmosMsg> = vec![("stakers", 50), ("warchest", 25), ("burn", 26)]
+        .into_iter()
+        .map(|(to_address, amount)| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: to_address.to_string(),
+                amount: coins(amount, "uluna"),
+            })
+        })
+        .collect();
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|msg| msg.msg).collect();
+    assert_eq!(msgs, expected);
+
+    match execute(deps.as_mut(), mock_env(), info, distribute_msg(1001)) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Broke { .. }) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn fails_invalid_distribution() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+    for recipients in vec![vec![], vec![recipient("stakers", 1), recipient("burn", 0)]] {
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetDistribution { recipients },
+        ) {
+            Ok(_) => panic!("Must return error"),
+            Err(TreasuryError::InvalidDistribution {}) => (),
+            Err(e) => panic!("Unexpected error: {}", e),
+        }
+    }
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stakers", &[]),
+        ExecuteMsg::SetDistribution {
+            recipients: vec![recipient("stakers", 1)],
+        },
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Admin(_)) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
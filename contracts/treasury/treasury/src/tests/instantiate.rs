@@ -84,6 +84,9 @@ fn successful_asset_update() {
             amount: Uint128::zero(),
         },
         value_reference: None,
+        max_value: None,
+        max_price_move_per_block: None,
+        breach_action: BreachAction::Clamp,
     };
 
     let test_token_asset = VaultAsset {
@@ -94,6 +97,9 @@ fn successful_asset_update() {
             amount: Uint128::zero(),
         },
         value_reference: None,
+        max_value: None,
+        max_price_move_per_block: None,
+        breach_action: BreachAction::Clamp,
     };
 
     let msg = ExecuteMsg::UpdateAssets {
@@ -151,6 +157,9 @@ fn unsuccessful_asset_limit_exceeded() {
                 amount: Uint128::zero(),
             },
             value_reference: None,
+            max_value: None,
+            max_price_move_per_block: None,
+            breach_action: BreachAction::Clamp,
         };
 
         let msg = ExecuteMsg::UpdateAssets {
@@ -0,0 +1,101 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::Uint128;
+use cosmwasm_storage::bucket;
+use cw2::{get_contract_version, set_contract_version};
+use terraswap::asset::{Asset, AssetInfo};
+
+use white_whale::treasury::msg::{InstantiateMsg, MigrateMsg};
+use white_whale::treasury::state::VAULT_ASSETS;
+use white_whale::treasury::vault_assets::{BreachAction, VaultAsset};
+
+use crate::contract::{instantiate, migrate};
+use crate::error::TreasuryError;
+use crate::migrations::VaultAssetV0;
+
+use super::common::TEST_CREATOR;
+
+#[test]
+fn migrate_vault_assets() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    set_contract_version(&mut deps.storage, "crates.io:treasury", "0.1.0").unwrap();
+
+    let asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::zero(),
+    };
+    bucket(&mut deps.storage, b"vault_assets")
+        .save(
+            b"uusd",
+            &VaultAssetV0 {
+                asset: asset.clone(),
+                value_reference: None,
+            },
+        )
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let vault_asset = VAULT_ASSETS.load(&deps.storage, "uusd").unwrap();
+    assert_eq!(
+        vault_asset,
+        VaultAsset {
+            asset,
+            value_reference: None,
+            max_value: None,
+            max_price_move_per_block: None,
+            breach_action: BreachAction::Clamp,
+        }
+    );
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn fails_migrate_other_contract() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    set_contract_version(&mut deps.storage, "crates.io:ww-governance", "0.1.0").unwrap();
+
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::InvalidMigrationContract(contract, _)) => {
+            assert_eq!(contract, "crates.io:ww-governance")
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn fails_migrate_downgrade() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    set_contract_version(&mut deps.storage, "crates.io:treasury", "99.0.0").unwrap();
+
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::MigrationDowngrade(stored, _)) => assert_eq!(stored, "99.0.0"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, "99.0.0");
+}
+
+#[test]
+fn fails_migrate_invalid_stored_version() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    set_contract_version(&mut deps.storage, "crates.io:treasury", "not_semver").unwrap();
+
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::SemVer(_)) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
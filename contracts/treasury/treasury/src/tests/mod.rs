@@ -1,4 +1,10 @@
 mod action;
 mod common;
 mod dapps;
+mod distribution;
 mod instantiate;
+mod migrate;
+mod operator;
+mod spend;
+mod staking;
+mod value;
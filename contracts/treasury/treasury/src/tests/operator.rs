@@ -0,0 +1,96 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_binary, Uint128};
+use cw_controllers::AdminError;
+use terraswap::asset::{Asset, AssetInfo};
+
+use white_whale::treasury::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use white_whale::treasury::vault_assets::{BreachAction, VaultAsset};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::TreasuryError;
+
+use super::common::TEST_CREATOR;
+
+const OPERATOR: &str = "operator";
+
+fn update_assets_msg() -> ExecuteMsg {
+    ExecuteMsg::UpdateAssets {
+        to_add: vec![VaultAsset {
+            asset: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::zero(),
+            },
+            value_reference: None,
+            max_value: None,
+            max_price_move_per_block: None,
+            breach_action: BreachAction::Clamp,
+        }],
+        to_remove: vec![],
+    }
+}
+
+#[test]
+fn operator_can_update_assets() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+    // Only the admin can set the operator
+    let msg = ExecuteMsg::SetOperator {
+        operator: Some(OPERATOR.to_string()),
+    };
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OPERATOR, &[]),
+        msg.clone(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Admin(AdminError::NotAdmin {})) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.operator, Some(OPERATOR.to_string()));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OPERATOR, &[]),
+        update_assets_msg(),
+    )
+    .unwrap();
+
+    // The operator can't call the other admin messages
+    let msg = ExecuteMsg::AddDApp {
+        dapp: "dapp".to_string(),
+    };
+    match execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Admin(AdminError::NotAdmin {})) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    // Once removed the operator can't update the assets anymore
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::SetOperator { operator: None },
+    )
+    .unwrap();
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OPERATOR, &[]),
+        update_assets_msg(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Admin(AdminError::NotAdmin {})) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
@@ -0,0 +1,151 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coin, coins, from_binary, BankMsg, CosmosMsg, Uint128};
+use terraswap::asset::AssetInfo;
+
+use white_whale::treasury::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SpendsResponse};
+use white_whale::treasury::state::Spend;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::TreasuryError;
+
+use super::common::TEST_CREATOR;
+
+const RECIPIENT: &str = "recipient";
+
+fn schedule_msg(amount: u128, execute_after: u64) -> ExecuteMsg {
+    ExecuteMsg::ScheduleSpend {
+        recipient: RECIPIENT.to_string(),
+        asset: AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+        amount: Uint128::from(amount),
+        execute_after,
+    }
+}
+
+#[test]
+fn schedule_and_execute_spend() {
+    let mut deps = mock_dependencies(&[coin(1000, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+    let execute_after = mock_env().block.time.seconds() + 100;
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        schedule_msg(500, execute_after),
+    )
+    .unwrap();
+
+    let spend: Spend =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Spend { id: 1 }).unwrap()).unwrap();
+    assert_eq!(spend.recipient.as_str(), RECIPIENT);
+    assert_eq!(spend.amount, Uint128::from(500u128));
+    assert!(!spend.executed);
+
+    // Still timelocked
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::ExecuteSpend { id: 1 },
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::SpendTimelocked { execute_after: t }) => assert_eq!(t, execute_after),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    // Anyone can execute the spend once the timelock has passed
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(101);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteSpend { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: RECIPIENT.to_string(),
+            amount: coins(500, "uluna"),
+        })
+    );
+
+    match execute(deps.as_mut(), env, info, ExecuteMsg::ExecuteSpend { id: 1 }) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::SpendAlreadyExecuted {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let res: SpendsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Spends {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.spends.len(), 1);
+    assert!(res.spends[0].executed);
+}
+
+#[test]
+fn fails_schedule_spend() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    let execute_after = mock_env().block.time.seconds() + 100;
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        schedule_msg(500, execute_after),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Admin(_)) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        schedule_msg(500, mock_env().block.time.seconds()),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::InvalidSpendTimelock {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn fails_execute_spend_insufficient_balance() {
+    let mut deps = mock_dependencies(&[coin(100, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+    let execute_after = mock_env().block.time.seconds() + 100;
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        schedule_msg(500, execute_after),
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(101);
+    match execute(deps.as_mut(), env, info, ExecuteMsg::ExecuteSpend { id: 1 }) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Broke { .. }) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
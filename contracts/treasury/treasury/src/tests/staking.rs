@@ -0,0 +1,272 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, from_binary, CosmosMsg, Decimal, Deps, DepsMut, DistributionMsg, Env, StakingMsg, Uint128,
+};
+use terraswap::asset::{Asset, AssetInfo};
+
+use white_whale::treasury::msg::{
+    DelegationEntry, ExecuteMsg, InstantiateMsg, QueryMsg, StakingPositionsResponse,
+    TotalValueResponse,
+};
+use white_whale::treasury::state::Unbonding;
+use white_whale::treasury::vault_assets::{BreachAction, ValueRef, VaultAsset};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::TreasuryError;
+
+use super::common::TEST_CREATOR;
+
+const VALIDATOR: &str = "validator";
+const OTHER_VALIDATOR: &str = "other_validator";
+const UNBONDING_PERIOD: u64 = 21 * 24 * 60 * 60;
+
+fn native_vault_asset(denom: &str, value_reference: Option<ValueRef>) -> VaultAsset {
+    VaultAsset {
+        asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            },
+            amount: Uint128::zero(),
+        },
+        value_reference,
+        max_value: None,
+        max_price_move_per_block: None,
+        breach_action: BreachAction::Clamp,
+    }
+}
+
+fn register_luna(deps: DepsMut) {
+    let msg = ExecuteMsg::UpdateAssets {
+        to_add: vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset(
+                "uluna",
+                Some(ValueRef::Proxy {
+                    proxy_asset: AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    multiplier: Decimal::from_ratio(5u64, 1u64),
+                }),
+            ),
+        ],
+        to_remove: vec![],
+    };
+    execute(deps, mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+}
+
+fn staking_positions(deps: Deps, env: Env) -> StakingPositionsResponse {
+    from_binary(&query(deps, env, QueryMsg::StakingPositions {}).unwrap()).unwrap()
+}
+
+fn total_value(deps: Deps, env: Env) -> Uint128 {
+    let res: TotalValueResponse =
+        from_binary(&query(deps, env, QueryMsg::TotalValue {}).unwrap()).unwrap();
+    res.value
+}
+
+#[test]
+fn delegate_and_undelegate() {
+    let mut deps = mock_dependencies(&[coin(1000, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+    let msg = ExecuteMsg::Delegate {
+        validator: VALIDATOR.to_string(),
+        amount: Uint128::from(600u128),
+    };
+    // Delegations can't be valued without a uluna vault asset
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::StakingAssetNotRegistered {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+    register_luna(deps.as_mut());
+
+    // Only the admin can delegate
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("someone", &[]),
+        msg.clone(),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Admin(_)) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Staking(StakingMsg::Delegate {
+            validator: VALIDATOR.to_string(),
+            amount: coin(600, "uluna"),
+        })
+    );
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, vec![coin(400, "uluna")]);
+
+    // Delegated uluna is still part of the treasury value
+    assert_eq!(
+        total_value(deps.as_ref(), mock_env()),
+        Uint128::from(5000u128)
+    );
+
+    // Can't undelegate more than was delegated
+    let msg = ExecuteMsg::Undelegate {
+        validator: VALIDATOR.to_string(),
+        amount: Uint128::from(700u128),
+    };
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::InsufficientDelegation { delegated, .. }) => {
+            assert_eq!(delegated, Uint128::from(600u128))
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let msg = ExecuteMsg::Undelegate {
+        validator: VALIDATOR.to_string(),
+        amount: Uint128::from(200u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Staking(StakingMsg::Undelegate {
+            validator: VALIDATOR.to_string(),
+            amount: coin(200, "uluna"),
+        })
+    );
+
+    let env = mock_env();
+    let positions = staking_positions(deps.as_ref(), env.clone());
+    assert_eq!(
+        positions.delegations,
+        vec![DelegationEntry {
+            validator: VALIDATOR.to_string(),
+            amount: Uint128::from(400u128),
+        }]
+    );
+    assert_eq!(
+        positions.unbondings,
+        vec![Unbonding {
+            validator: VALIDATOR.to_string(),
+            amount: Uint128::from(200u128),
+            release_at: env.block.time.seconds() + UNBONDING_PERIOD,
+        }]
+    );
+    // Unbonding uluna is still part of the treasury value
+    assert_eq!(total_value(deps.as_ref(), env), Uint128::from(5000u128));
+
+    // Once released the unbonded uluna is back in the balance
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(UNBONDING_PERIOD);
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, vec![coin(600, "uluna")]);
+    assert!(staking_positions(deps.as_ref(), env.clone())
+        .unbondings
+        .is_empty());
+    assert_eq!(total_value(deps.as_ref(), env), Uint128::from(5000u128));
+}
+
+#[test]
+fn fails_remove_luna_while_staked() {
+    let mut deps = mock_dependencies(&[coin(1000, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    register_luna(deps.as_mut());
+
+    let msg = ExecuteMsg::Delegate {
+        validator: VALIDATOR.to_string(),
+        amount: Uint128::from(600u128),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::Undelegate {
+        validator: VALIDATOR.to_string(),
+        amount: Uint128::from(600u128),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let remove_luna = ExecuteMsg::UpdateAssets {
+        to_add: vec![],
+        to_remove: vec![AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        }],
+    };
+    // The unbonding uluna still needs the vault asset to be valued
+    match execute(deps.as_mut(), mock_env(), info.clone(), remove_luna.clone()) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::StakingAssetInUse {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(UNBONDING_PERIOD);
+    execute(deps.as_mut(), env.clone(), info, remove_luna).unwrap();
+    assert_eq!(total_value(deps.as_ref(), env), Uint128::from(0u128));
+}
+
+#[test]
+fn fails_delegate_more_than_balance() {
+    let mut deps = mock_dependencies(&[coin(100, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    register_luna(deps.as_mut());
+
+    let msg = ExecuteMsg::Delegate {
+        validator: VALIDATOR.to_string(),
+        amount: Uint128::from(101u128),
+    };
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::Broke { balance, .. }) => assert_eq!(balance, Uint128::from(100u128)),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn redelegate_and_withdraw_rewards() {
+    let mut deps = mock_dependencies(&[coin(1000, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    register_luna(deps.as_mut());
+
+    let msg = ExecuteMsg::Delegate {
+        validator: VALIDATOR.to_string(),
+        amount: Uint128::from(500u128),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::Redelegate {
+        src_validator: VALIDATOR.to_string(),
+        dst_validator: OTHER_VALIDATOR.to_string(),
+        amount: Uint128::from(500u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Staking(StakingMsg::Redelegate {
+            src_validator: VALIDATOR.to_string(),
+            dst_validator: OTHER_VALIDATOR.to_string(),
+            amount: coin(500, "uluna"),
+        })
+    );
+    // The full delegation moved to the other validator
+    assert_eq!(
+        staking_positions(deps.as_ref(), mock_env()).delegations,
+        vec![DelegationEntry {
+            validator: OTHER_VALIDATOR.to_string(),
+            amount: Uint128::from(500u128),
+        }]
+    );
+
+    let msg = ExecuteMsg::WithdrawDelegatorRewards {
+        validator: OTHER_VALIDATOR.to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward {
+            validator: OTHER_VALIDATOR.to_string(),
+        })
+    );
+}
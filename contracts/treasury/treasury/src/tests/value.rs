@@ -0,0 +1,858 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, ContractResult, Decimal, Deps, DepsMut, OwnedDeps,
+    QuerierResult, StdError, SystemResult, Uint128, WasmQuery,
+};
+use terraswap::asset::{Asset, AssetInfo};
+use terraswap::pair::PoolResponse;
+
+use white_whale::query::anchor::EpochStateResponse;
+use white_whale::treasury::holdings::{Holding, HoldingKind, HoldingValuation};
+use white_whale::treasury::msg::{
+    ConfigResponse, ExecuteMsg, HoldingAmountEntry, HoldingAmountsResponse, HoldingValueResponse,
+    InstantiateMsg, OraclePriceResponse, PaginatedTotalValueResponse, PositionsResponse, QueryMsg,
+    TotalValueBreakdownResponse, TotalValueResponse, ValueBreakdownEntry, ValueHistoryResponse,
+    VaultAssetsResponse,
+};
+use white_whale::treasury::twap::TwapState;
+use white_whale::treasury::vault_assets::{BreachAction, ValueRef, VaultAsset};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::TreasuryError;
+
+use super::common::TEST_CREATOR;
+
+fn native_vault_asset(denom: &str, value_reference: Option<ValueRef>) -> VaultAsset {
+    VaultAsset {
+        asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            },
+            amount: Uint128::zero(),
+        },
+        value_reference,
+        max_value: None,
+        max_price_move_per_block: None,
+        breach_action: BreachAction::Clamp,
+    }
+}
+
+fn proxy_to(denom: &str, multiplier: u64) -> Option<ValueRef> {
+    Some(ValueRef::Proxy {
+        proxy_asset: AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        },
+        multiplier: Decimal::from_ratio(multiplier, 1u64),
+    })
+}
+
+fn add_assets(deps: DepsMut, to_add: Vec<VaultAsset>) {
+    let msg = ExecuteMsg::UpdateAssets {
+        to_add,
+        to_remove: vec![],
+    };
+    execute(deps, mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+}
+
+#[test]
+fn total_value_sums_all_assets() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd"), coin(10, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset("uluna", proxy_to("uusd", 5)),
+        ],
+    );
+
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(150u128));
+}
+
+#[test]
+fn total_value_breakdown() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd"), coin(10, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset("uluna", proxy_to("uusd", 5)),
+        ],
+    );
+
+    let res: TotalValueBreakdownResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValueBreakdown {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        res,
+        TotalValueBreakdownResponse {
+            total_value: Uint128::from(150u128),
+            entries: vec![
+                ValueBreakdownEntry {
+                    identifier: "uluna".to_string(),
+                    amount: Uint128::from(10u128),
+                    value: Uint128::from(50u128),
+                    share: Decimal::from_ratio(1u64, 3u64),
+                },
+                ValueBreakdownEntry {
+                    identifier: "uusd".to_string(),
+                    amount: Uint128::from(100u128),
+                    value: Uint128::from(100u128),
+                    share: Decimal::from_ratio(2u64, 3u64),
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn positions_add_to_total_value() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd"), coin(10, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset("uluna", proxy_to("uusd", 5)),
+        ],
+    );
+    let msg = ExecuteMsg::AddDApp {
+        dapp: "dapp".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let position_msg = |denom: &str, amount: u128| ExecuteMsg::UpdatePosition {
+        id: "farm".to_string(),
+        asset: AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        },
+        amount: Uint128::from(amount),
+    };
+
+    // only whitelisted dapps report positions, in vault assets
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("someone", &[]),
+        position_msg("uluna", 20),
+    ) {
+        Err(TreasuryError::SenderNotWhitelisted {}) => (),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dapp", &[]),
+        position_msg("ukrw", 20),
+    ) {
+        Err(TreasuryError::UnknownPositionAsset(identifier)) => assert_eq!(identifier, "ukrw"),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dapp", &[]),
+        position_msg("uluna", 20),
+    )
+    .unwrap();
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(250u128));
+    let res: TotalValueBreakdownResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValueBreakdown {}).unwrap())
+            .unwrap();
+    assert_eq!(res.total_value, Uint128::from(250u128));
+    assert!(res.entries.contains(&ValueBreakdownEntry {
+        identifier: "dapp/farm".to_string(),
+        amount: Uint128::from(20u128),
+        value: Uint128::from(100u128),
+        share: Decimal::percent(40),
+    }));
+
+    // removing the dapp drops its positions
+    let msg = ExecuteMsg::RemoveDApp {
+        dapp: "dapp".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let res: PositionsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Positions { dapp: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(res.positions.is_empty());
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(150u128));
+}
+
+#[test]
+fn paginated_total_value() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd"), coin(10, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset("uluna", proxy_to("uusd", 5)),
+        ],
+    );
+
+    // assets are ordered by identifier, uluna comes first
+    let msg = QueryMsg::PaginatedTotalValue {
+        start_after: None,
+        limit: Some(1),
+    };
+    let res: PaginatedTotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(50u128));
+    assert_eq!(res.last_asset, Some("uluna".to_string()));
+
+    let msg = QueryMsg::PaginatedTotalValue {
+        start_after: res.last_asset,
+        limit: Some(1),
+    };
+    let res: PaginatedTotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(100u128));
+    assert_eq!(res.last_asset, Some("uusd".to_string()));
+
+    let msg = QueryMsg::PaginatedTotalValue {
+        start_after: res.last_asset,
+        limit: None,
+    };
+    let res: PaginatedTotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::zero());
+    assert_eq!(res.last_asset, None);
+}
+
+#[test]
+fn fails_total_value_with_value_ref_cycle() {
+    let mut deps = mock_dependencies(&[coin(100, "ukrw"), coin(10, "umnt")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("ukrw", proxy_to("umnt", 1)),
+            native_vault_asset("umnt", proxy_to("ukrw", 1)),
+        ],
+    );
+
+    match query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}) {
+        Ok(_) => panic!("Must return error"),
+        Err(e) => assert!(e.to_string().contains("Cycle detected")),
+    }
+}
+
+#[test]
+fn query_vault_assets() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset("uluna", proxy_to("uusd", 5)),
+        ],
+    );
+
+    let msg = QueryMsg::VaultAssets {
+        start_after: None,
+        limit: None,
+    };
+    let res: VaultAssetsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.assets.len(), 2);
+    assert_eq!(res.assets[0].identifier, "uluna");
+    assert_eq!(res.assets[0].value_reference, proxy_to("uusd", 5));
+    assert_eq!(
+        res.assets[1].asset_info,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string()
+        }
+    );
+    assert_eq!(res.assets[1].value_reference, None);
+
+    let msg = QueryMsg::VaultAssets {
+        start_after: Some("uluna".to_string()),
+        limit: Some(1),
+    };
+    let res: VaultAssetsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.assets.len(), 1);
+    assert_eq!(res.assets[0].identifier, "uusd");
+}
+
+#[test]
+fn query_holding_amounts() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd"), coin(10, "uluna"), coin(7, "ukrw")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset("uluna", proxy_to("uusd", 5)),
+        ],
+    );
+    let msg = ExecuteMsg::UpdateHoldings {
+        to_add: vec![Holding {
+            kind: HoldingKind::Native {
+                denom: "ukrw".to_string(),
+            },
+            valuation: HoldingValuation::Zero,
+        }],
+        to_remove: vec![],
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // amounts are the raw balances, not the values
+    let msg = QueryMsg::HoldingAmount {
+        identifier: "uluna".to_string(),
+    };
+    let res: Uint128 = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res, Uint128::from(10u128));
+
+    let msg = QueryMsg::HoldingAmount {
+        identifier: "ukrw".to_string(),
+    };
+    let res: Uint128 = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res, Uint128::from(7u128));
+
+    let msg = QueryMsg::HoldingAmounts {
+        start_after: None,
+        limit: None,
+    };
+    let res: HoldingAmountsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.amounts,
+        vec![
+            HoldingAmountEntry {
+                identifier: "uluna".to_string(),
+                asset_info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string()
+                },
+                amount: Uint128::from(10u128),
+            },
+            HoldingAmountEntry {
+                identifier: "uusd".to_string(),
+                asset_info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string()
+                },
+                amount: Uint128::from(100u128),
+            },
+        ]
+    );
+
+    let msg = QueryMsg::HoldingAmounts {
+        start_after: Some("uluna".to_string()),
+        limit: Some(1),
+    };
+    let res: HoldingAmountsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.amounts.len(), 1);
+    assert_eq!(res.amounts[0].identifier, "uusd");
+}
+
+#[test]
+fn snapshot_value_history() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    add_assets(deps.as_mut(), vec![native_vault_asset("uusd", None)]);
+
+    let anyone = mock_info("anyone", &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        anyone.clone(),
+        ExecuteMsg::SnapshotValue {},
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.height += 1;
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        anyone.clone(),
+        ExecuteMsg::SnapshotValue {},
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::SnapshotTooEarly { .. }) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    env.block.height = mock_env().block.height + 600;
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        anyone,
+        ExecuteMsg::SnapshotValue {},
+    )
+    .unwrap();
+
+    let msg = QueryMsg::ValueHistory {
+        start_after: None,
+        limit: None,
+    };
+    let res: ValueHistoryResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.snapshots.len(), 2);
+    assert_eq!(res.snapshots[0].height, mock_env().block.height);
+    assert_eq!(res.snapshots[1].height, env.block.height);
+    assert_eq!(res.snapshots[1].value, Uint128::from(100u128));
+}
+
+#[test]
+fn holdings_add_to_total_value() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd"), coin(10, "ukrw")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    add_assets(deps.as_mut(), vec![native_vault_asset("uusd", None)]);
+
+    let nft_holding = Holding {
+        kind: HoldingKind::Cw721 {
+            contract_addr: "nft_collection".to_string(),
+        },
+        valuation: HoldingValuation::Zero,
+    };
+    let msg = ExecuteMsg::UpdateHoldings {
+        to_add: vec![
+            Holding {
+                kind: HoldingKind::Native {
+                    denom: "ukrw".to_string(),
+                },
+                valuation: HoldingValuation::PerUnit {
+                    value: Uint128::from(3u128),
+                },
+            },
+            nft_holding.clone(),
+        ],
+        to_remove: vec![],
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(130u128));
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.holdings.len(), 2);
+    assert!(config.holdings.contains(&nft_holding));
+
+    // Vault assets can't be registered as holdings as well
+    let msg = ExecuteMsg::UpdateHoldings {
+        to_add: vec![Holding {
+            kind: HoldingKind::Native {
+                denom: "uusd".to_string(),
+            },
+            valuation: HoldingValuation::Zero,
+        }],
+        to_remove: vec![],
+    };
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::AlreadyHolding(id)) => assert_eq!(id, "uusd"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    // Nfts can't be valued through a ValueRef
+    let msg = ExecuteMsg::UpdateHoldings {
+        to_add: vec![Holding {
+            kind: HoldingKind::Cw721 {
+                contract_addr: "nft_collection".to_string(),
+            },
+            valuation: HoldingValuation::Reference(proxy_to("uusd", 1).unwrap()),
+        }],
+        to_remove: vec![],
+    };
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::InvalidHoldingValuation {}) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn oracle_value_ref() {
+    let mut deps = mock_dependencies(&[coin(10, "uluna")]);
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "oracle" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&OraclePriceResponse {
+                    price: Decimal::percent(250),
+                })
+                .unwrap(),
+            ))
+        }
+        _ => panic!("Unexpected query"),
+    });
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![native_vault_asset(
+            "uluna",
+            Some(ValueRef::Oracle {
+                contract_address: Addr::unchecked("oracle"),
+                query: to_binary(&"price").unwrap(),
+            }),
+        )],
+    );
+
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(25u128));
+}
+
+fn mock_oracle_price(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, price: Decimal) {
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "oracle" => SystemResult::Ok(
+            ContractResult::Ok(to_binary(&OraclePriceResponse { price }).unwrap()),
+        ),
+        _ => panic!("Unexpected query"),
+    });
+}
+
+#[test]
+fn vault_asset_value_cap() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    let mut vault_asset = native_vault_asset("uusd", None);
+    vault_asset.max_value = Some(Uint128::from(60u128));
+    add_assets(deps.as_mut(), vec![vault_asset.clone()]);
+
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(60u128));
+
+    vault_asset.breach_action = BreachAction::Error;
+    add_assets(deps.as_mut(), vec![vault_asset]);
+    match query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}) {
+        Ok(_) => panic!("Must return error"),
+        Err(StdError::GenericErr { .. }) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn vault_asset_price_move_limit() {
+    let mut deps = mock_dependencies(&[coin(1000, "uluna")]);
+    mock_oracle_price(&mut deps, Decimal::one());
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    let mut vault_asset = native_vault_asset(
+        "uluna",
+        Some(ValueRef::Oracle {
+            contract_address: Addr::unchecked("oracle"),
+            query: to_binary(&"price").unwrap(),
+        }),
+    );
+    vault_asset.max_price_move_per_block = Some(Decimal::percent(1));
+    add_assets(deps.as_mut(), vec![vault_asset.clone()]);
+
+    // without a price snapshot the price is not limited
+    mock_oracle_price(&mut deps, Decimal::percent(200));
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(2000u128));
+
+    mock_oracle_price(&mut deps, Decimal::one());
+    let anyone = mock_info("anyone", &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        anyone.clone(),
+        ExecuteMsg::SnapshotValue {},
+    )
+    .unwrap();
+
+    // the price can move by 10% over 10 blocks
+    mock_oracle_price(&mut deps, Decimal::percent(200));
+    let mut env = mock_env();
+    env.block.height += 10;
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(1100u128));
+
+    env.block.height += 190;
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(2000u128));
+
+    // re-adding the asset resets its price snapshot
+    mock_oracle_price(&mut deps, Decimal::one());
+    vault_asset.breach_action = BreachAction::Error;
+    add_assets(deps.as_mut(), vec![vault_asset]);
+    let mut env = mock_env();
+    env.block.height += 600;
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        anyone,
+        ExecuteMsg::SnapshotValue {},
+    )
+    .unwrap();
+
+    mock_oracle_price(&mut deps, Decimal::percent(200));
+    env.block.height += 10;
+    match query(deps.as_ref(), env, QueryMsg::TotalValue {}) {
+        Ok(_) => panic!("Must return error"),
+        Err(StdError::GenericErr { .. }) => (),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn anchor_value_ref() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd")]);
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "anchor" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&EpochStateResponse {
+                    exchange_rate: Decimal256::percent(120),
+                    aterra_supply: Uint256::from(1_000_000u64),
+                })
+                .unwrap(),
+            ))
+        }
+        _ => panic!("Unexpected query"),
+    });
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            VaultAsset {
+                asset: Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: "aust".to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                value_reference: Some(ValueRef::Anchor {
+                    money_market_address: Addr::unchecked("anchor"),
+                }),
+                max_value: None,
+                max_price_move_per_block: None,
+                breach_action: BreachAction::Clamp,
+            },
+        ],
+    );
+
+    let msg = QueryMsg::HoldingValue {
+        identifier: "aust".to_string(),
+        amount: Some(Uint128::from(50u128)),
+    };
+    let res: HoldingValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(60u128));
+}
+
+fn mock_uluna_uusd_pool(query: &WasmQuery) -> QuerierResult {
+    match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "pair" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&PoolResponse {
+                    assets: [
+                        Asset {
+                            info: AssetInfo::NativeToken {
+                                denom: "uusd".to_string(),
+                            },
+                            amount: Uint128::from(4_000u128),
+                        },
+                        Asset {
+                            info: AssetInfo::NativeToken {
+                                denom: "uluna".to_string(),
+                            },
+                            amount: Uint128::from(100u128),
+                        },
+                    ],
+                    total_share: Uint128::from(1_000u128),
+                })
+                .unwrap(),
+            ))
+        }
+        _ => panic!("Unexpected query"),
+    }
+}
+
+#[test]
+fn astroport_pool_value_ref() {
+    let mut deps = mock_dependencies(&[coin(10, "uluna")]);
+    deps.querier.update_wasm(mock_uluna_uusd_pool);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset(
+                "uluna",
+                Some(ValueRef::AstroportPool {
+                    pair_address: Addr::unchecked("pair"),
+                }),
+            ),
+        ],
+    );
+
+    let msg = QueryMsg::HoldingValue {
+        identifier: "uluna".to_string(),
+        amount: None,
+    };
+    let res: HoldingValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(400u128));
+
+    let msg = QueryMsg::HoldingValue {
+        identifier: "uluna".to_string(),
+        amount: Some(Uint128::from(5u128)),
+    };
+    let res: HoldingValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(200u128));
+}
+
+#[test]
+fn twap_value_ref() {
+    let mut deps = mock_dependencies(&[coin(10, "uluna")]);
+    deps.querier.update_wasm(mock_uluna_uusd_pool);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset(
+                "uluna",
+                Some(ValueRef::Twap {
+                    pair_address: Addr::unchecked("pair"),
+                    window: 100,
+                }),
+            ),
+        ],
+    );
+
+    let holding_value = |deps: Deps| -> Uint128 {
+        let msg = QueryMsg::HoldingValue {
+            identifier: "uluna".to_string(),
+            amount: None,
+        };
+        let res: HoldingValueResponse =
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+        res.value
+    };
+    // The spot price is used until the TWAP is available
+    assert_eq!(holding_value(deps.as_ref()), Uint128::from(400u128));
+
+    let msg = ExecuteMsg::UpdateTwap {
+        identifier: "uluna".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    execute(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+    let twap: TwapState = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Twap {
+                identifier: "uluna".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(twap.twap, Some(Decimal::from_ratio(40u128, 1u128)));
+    assert_eq!(holding_value(deps.as_ref()), Uint128::from(400u128));
+
+    let msg = ExecuteMsg::UpdateTwap {
+        identifier: "uusd".to_string(),
+    };
+    match execute(deps.as_mut(), mock_env(), info, msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::NotTwapAsset(id)) => assert_eq!(id, "uusd"),
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn value_in_other_denom() {
+    let mut deps = mock_dependencies(&[coin(100, "uusd"), coin(10, "uluna")]);
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+    add_assets(
+        deps.as_mut(),
+        vec![
+            native_vault_asset("uusd", None),
+            native_vault_asset("uluna", proxy_to("uusd", 5)),
+        ],
+    );
+
+    let msg = QueryMsg::ValueIn {
+        identifier: "uusd".to_string(),
+        denom: "uluna".to_string(),
+    };
+    let res: HoldingValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(20u128));
+
+    // the base asset must not have a value reference
+    let luna = AssetInfo::NativeToken {
+        denom: "uluna".to_string(),
+    };
+    let msg = ExecuteMsg::SetBaseAsset {
+        asset: luna.clone(),
+    };
+    match execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()) {
+        Ok(_) => panic!("Must return error"),
+        Err(TreasuryError::InvalidBaseAsset(_)) => (),
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+
+    // value everything in luna instead
+    let mut ust = native_vault_asset("uusd", None);
+    ust.value_reference = Some(ValueRef::Proxy {
+        proxy_asset: luna.clone(),
+        multiplier: Decimal::percent(20),
+    });
+    add_assets(deps.as_mut(), vec![ust, native_vault_asset("uluna", None)]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.base_asset, luna);
+    let res: TotalValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalValue {}).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(30u128));
+    let msg = QueryMsg::ValueIn {
+        identifier: "uluna".to_string(),
+        denom: "uusd".to_string(),
+    };
+    let res: HoldingValueResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.value, Uint128::from(50u128));
+}
@@ -8,6 +8,7 @@ use cosmwasm_std::{
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use std::cmp;
+use white_whale::address::validate_addr;
 use white_whale::vesting::msg::{
     AllocationInfo, AllocationResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
     ReceiveMsg, Schedule, SimulateWithdrawResponse, StateResponse,
@@ -27,9 +28,9 @@ pub fn instantiate(
     CONFIG.save(
         deps.storage,
         &Config {
-            owner: deps.api.addr_validate(&msg.owner)?,
-            refund_recipient: deps.api.addr_validate(&msg.refund_recipient)?,
-            whale_token: deps.api.addr_validate(&msg.whale_token)?,
+            owner: validate_addr(deps.api, "owner", &msg.owner)?,
+            refund_recipient: validate_addr(deps.api, "refund_recipient", &msg.refund_recipient)?,
+            whale_token: validate_addr(deps.api, "whale_token", &msg.whale_token)?,
             default_unlock_schedule: msg.default_unlock_schedule,
         },
     )?;
@@ -98,7 +99,7 @@ fn handle_transfer_ownership(
         return Err(StdError::generic_err("Unauthorized"));
     }
 
-    config.owner = deps.api.addr_validate(&new_owner)?;
+    config.owner = validate_addr(deps.api, "new_owner", &new_owner)?;
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new())
 }
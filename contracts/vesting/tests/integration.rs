@@ -155,6 +155,23 @@ fn test_transfer_ownership() {
         .unwrap_err();
     assert_eq!(err.to_string(), "Generic error: Unauthorized");
 
+    // ######    ERROR :: Invalid address     ######
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.to_string()),
+            vesting_instance.clone(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "x".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Invalid address x provided for new_owner"
+    );
+
     // ######    SUCCESSFULLY TRANSFERS OWNERSHIP    ######
 
     app.execute_contract(
@@ -0,0 +1,34 @@
+use cosmwasm_std::{Addr, Api};
+
+use crate::error::CommonError;
+
+/// Validates an address provided in a message, the error names the `field` it was provided in
+pub fn validate_addr(api: &dyn Api, field: &str, address: &str) -> Result<Addr, CommonError> {
+    api.addr_validate(address)
+        .map_err(|_| CommonError::InvalidAddress {
+            field: field.to_string(),
+            address: address.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockApi;
+
+    #[test]
+    fn invalid_address_names_the_field() {
+        let api = MockApi::default();
+        assert_eq!(
+            validate_addr(&api, "treasury_addr", "treasury").unwrap(),
+            Addr::unchecked("treasury")
+        );
+        assert_eq!(
+            validate_addr(&api, "treasury_addr", "x"),
+            Err(CommonError::InvalidAddress {
+                field: "treasury_addr".to_string(),
+                address: "x".to_string(),
+            })
+        );
+    }
+}
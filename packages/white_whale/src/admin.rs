@@ -0,0 +1,100 @@
+use std::fmt;
+
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdError};
+use cw_controllers::{Admin, AdminError};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Number of blocks an admin proposal made through the deprecated SetAdmin message stays valid, ~1 week
+pub const DEFAULT_ADMIN_PROPOSAL_EXPIRY: u64 = 100_800;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AdminTransferError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("There is no pending admin transfer")]
+    NoPendingAdmin {},
+
+    #[error("Only the proposed admin can accept the admin transfer")]
+    NotPendingAdmin {},
+
+    #[error("The admin transfer expired at height {0}")]
+    Expired(u64),
+}
+
+/// An admin transfer waiting to be accepted by the proposed admin
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAdmin {
+    pub admin: Addr,
+    pub expires_at: u64,
+}
+
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+
+/// Proposes a new admin, who has `expires_in` blocks to accept the transfer.
+/// A new proposal replaces the pending one.
+pub fn propose_admin<C>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    admin_store: &Admin,
+    admin: String,
+    expires_in: u64,
+) -> Result<Response<C>, AdminTransferError>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema,
+{
+    admin_store.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let pending = PendingAdmin {
+        admin: deps.api.addr_validate(&admin)?,
+        expires_at: env.block.height + expires_in,
+    };
+    PENDING_ADMIN.save(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("admin", admin)
+        .add_attribute("expires_at", pending.expires_at.to_string()))
+}
+
+/// Completes a pending admin transfer, must be called by the proposed admin
+pub fn accept_admin<C>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    admin_store: &Admin,
+) -> Result<Response<C>, AdminTransferError>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema,
+{
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(AdminTransferError::NoPendingAdmin {})?;
+    if info.sender != pending.admin {
+        return Err(AdminTransferError::NotPendingAdmin {});
+    }
+    if env.block.height >= pending.expires_at {
+        return Err(AdminTransferError::Expired(pending.expires_at));
+    }
+
+    let previous_admin = admin_store.get(deps.as_ref())?;
+    admin_store.set(deps.branch(), Some(pending.admin.clone()))?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute(
+            "previous admin",
+            previous_admin
+                .map(|addr| addr.to_string())
+                .unwrap_or_default(),
+        )
+        .add_attribute("admin", pending.admin))
+}
@@ -8,7 +8,7 @@ use cw20::Cw20ExecuteMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::denom::UST_DENOM;
+use crate::denom::{LUNA_DENOM, UST_DENOM};
 use crate::tax::deduct_tax;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -16,6 +16,7 @@ use crate::tax::deduct_tax;
 pub enum AnchorMsg {
     DepositStable {},
     RedeemStable {},
+    Bond {},
     Unbond {},
     WithdrawUnbonded {},
 }
@@ -98,6 +99,17 @@ pub fn anchor_withdraw_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
     }))
 }
 
+pub fn anchor_bluna_bond_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    bluna_hub_address: Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: bluna_hub_address.to_string(),
+        msg: to_binary(&AnchorMsg::Bond {})?,
+        funds: vec![Coin::new(amount.u128(), LUNA_DENOM)],
+    }))
+}
+
 pub fn anchor_bluna_unbond_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
     bluna_address: Addr,
     bluna_hub_address: Addr,
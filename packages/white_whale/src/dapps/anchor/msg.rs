@@ -1,5 +1,5 @@
 use crate::treasury::dapp_base::msg::{BaseExecuteMsg, BaseQueryMsg};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -8,10 +8,23 @@ use serde::{Deserialize, Serialize};
 pub enum ExecuteMsg {
     Base(BaseExecuteMsg),
     // Add dapp-specific messages here
-    DepositStable { deposit_amount: Uint128 },
-    RedeemStable { withdraw_amount: Uint128 },
-    Unbond { bluna_amount: Uint128 },
+    DepositStable {
+        deposit_amount: Uint128,
+    },
+    RedeemStable {
+        withdraw_amount: Uint128,
+    },
+    Unbond {
+        bluna_amount: Uint128,
+    },
     WithdrawUnbonded {},
+    /// Sets the share of the treasury UST that is kept liquid, the rest is deposited into Anchor
+    SetLiquidRatio {
+        liquid_ratio: Decimal,
+    },
+    /// Deposits or redeems UST so the liquid share of the treasury UST matches the liquid ratio
+    /// Callable by anyone
+    Rebalance {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -19,4 +32,10 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     Base(BaseQueryMsg),
     // Add dapp-specific queries here
+    LiquidRatio {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LiquidRatioResponse {
+    pub liquid_ratio: Option<Decimal>,
 }
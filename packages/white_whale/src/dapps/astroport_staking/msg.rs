@@ -0,0 +1,62 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terra_rust_script_derive::CosmWasmContract;
+
+use crate::treasury::dapp_base::msg::{BaseExecuteMsg, BaseQueryMsg};
+use cosmwasm_std::{Decimal, Uint128};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Base(BaseExecuteMsg),
+    // Add dapp-specific messages here
+    /// Allows staking the LP token registered in memory as `lp_token_id`
+    RegisterPool {
+        lp_token_id: String,
+    },
+    /// Removes a registered LP token
+    RemovePool {
+        lp_token_id: String,
+    },
+    /// Constructs a generator deposit msg and forwards it to the treasury
+    Stake {
+        lp_token_id: String,
+        amount: Uint128,
+    },
+    /// Constructs a generator withdraw msg and forwards it to the treasury
+    Unstake {
+        lp_token_id: String,
+        amount: Uint128,
+    },
+    /// Claims the ASTRO rewards of the staked LP tokens into the treasury.
+    /// If `swap_pool_id` is set the rewards are swapped through that pair.
+    ClaimRewards {
+        lp_token_id: String,
+        swap_pool_id: Option<String>,
+        max_spread: Option<Decimal>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Base(BaseQueryMsg),
+    // Add dapp-specific queries here
+    /// Returns the registered LP tokens
+    Pools {},
+    /// Returns the LP tokens the treasury staked and its pending rewards
+    Staked {
+        lp_token_id: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolsResponse {
+    pub lp_token_ids: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakedResponse {
+    pub staked: Uint128,
+    pub pending_rewards: Uint128,
+}
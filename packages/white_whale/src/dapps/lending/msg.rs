@@ -0,0 +1,92 @@
+use std::fmt;
+
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terra_rust_script_derive::CosmWasmContract;
+
+use crate::treasury::dapp_base::msg::{BaseExecuteMsg, BaseQueryMsg};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Base(BaseExecuteMsg),
+    // Add dapp-specific messages here
+    /// Sets the memory id of the money market and the lowest health factor borrows and
+    /// withdrawals may leave the treasury position at, only the admin can call this
+    UpdateConfig {
+        market_id: Option<String>,
+        min_health_factor: Option<Decimal>,
+    },
+    /// Deposits the treasury asset registered in memory as `asset_id` as collateral
+    DepositCollateral {
+        asset_id: String,
+        amount: Uint128,
+    },
+    /// Withdraws deposited collateral back into the treasury
+    WithdrawCollateral {
+        asset_id: String,
+        amount: Uint128,
+    },
+    /// Borrows against the treasury collateral, the borrowed asset is sent to the treasury
+    Borrow {
+        asset_id: String,
+        amount: Uint128,
+    },
+    /// Repays debt of the treasury with treasury funds
+    Repay {
+        asset_id: String,
+        amount: Uint128,
+    },
+    /// Internal callback message
+    Callback(CallbackMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Fails if the treasury position is below the minimum health factor
+    AssertHealthFactor {},
+}
+
+impl CallbackMsg {
+    pub fn to_cosmos_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+        self,
+        contract_addr: &Addr,
+    ) -> StdResult<CosmosMsg<T>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from(contract_addr),
+            msg: to_binary(&ExecuteMsg::Callback(self))?,
+            funds: vec![],
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Base(BaseQueryMsg),
+    // Add dapp-specific queries here
+    Config {},
+    /// Returns the collateral, debt and loan-to-value of the treasury position
+    Position {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub market_id: String,
+    pub min_health_factor: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionResponse {
+    /// Value of the collateral in UST
+    pub collateral_value: Uint128,
+    /// Value of the debt in UST
+    pub debt_value: Uint128,
+    /// Debt over collateral value
+    pub ltv: Decimal,
+    /// Liquidation threshold weighted collateral over debt, None without debt
+    pub health_factor: Option<Decimal>,
+    pub min_health_factor: Decimal,
+}
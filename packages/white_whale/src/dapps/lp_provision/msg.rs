@@ -0,0 +1,83 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terra_rust_script_derive::CosmWasmContract;
+use terraswap::asset::Asset;
+
+use crate::treasury::dapp_base::msg::{BaseExecuteMsg, BaseQueryMsg};
+use cosmwasm_std::{Decimal, Uint128};
+
+/// The exchange a pair is deployed on
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Dex {
+    Terraswap,
+    Astroport,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairConfig {
+    pub dex: Dex,
+    /// Memory id of the LP token of the pair
+    pub lp_token_id: String,
+    /// Highest slippage tolerance liquidity can be provided with
+    pub max_slippage: Decimal,
+    /// Maximum amount of LP tokens of the pair the treasury may hold, unlimited if not set
+    pub lp_cap: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Base(BaseExecuteMsg),
+    // Add dapp-specific messages here
+    /// Allows providing liquidity to the pair registered in memory as `pool_id`
+    /// Overwrites the config if the pair is already set
+    SetPair {
+        pool_id: String,
+        config: PairConfig,
+    },
+    /// Removes a configured pair
+    RemovePair {
+        pool_id: String,
+    },
+    /// Constructs a provide liquidity msg and forwards it to the treasury
+    /// `slippage_tolerance` defaults to the max slippage of the pair
+    ProvideLiquidity {
+        pool_id: String,
+        assets: Vec<(String, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    },
+    /// Constructs a withdraw liquidity msg and forwards it to the treasury
+    WithdrawLiquidity {
+        pool_id: String,
+        amount: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Base(BaseQueryMsg),
+    // Add dapp-specific queries here
+    /// Returns the configured pairs
+    Pairs {},
+    /// Returns the LP position of the treasury in the pair
+    Position {
+        pool_id: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairsResponse {
+    pub pairs: Vec<(String, PairConfig)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionResponse {
+    /// LP tokens held by the treasury
+    pub lp_tokens: Uint128,
+    /// The treasury's share of the pool assets
+    pub assets: Vec<Asset>,
+    /// Value of the LP tokens according to the treasury
+    pub value: Uint128,
+}
@@ -1,4 +1,7 @@
 pub mod anchor;
 pub mod astroport;
+pub mod astroport_staking;
+pub mod lending;
+pub mod lp_provision;
 pub mod terraswap;
 pub mod vault;
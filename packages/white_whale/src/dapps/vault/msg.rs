@@ -1,4 +1,4 @@
-use cosmwasm_std::Decimal;
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -6,6 +6,7 @@ use terra_rust_script_derive::CosmWasmContract;
 
 use crate::{
     fee::Fee,
+    pause::PauseScope,
     treasury::dapp_base::msg::{BaseExecuteMsg, BaseInstantiateMsg, BaseQueryMsg},
 };
 use terraswap::asset::Asset;
@@ -25,16 +26,84 @@ pub enum ExecuteMsg {
     Base(BaseExecuteMsg),
     // Add dapp-specific messages here
     Receive(Cw20ReceiveMsg),
+    /// Provides liquidity with the deposit asset or an accepted deposit.
+    /// The LP tokens are minted to `recipient`, the sender if not set.
+    /// Cw20 deposits are sent with a cw20 Send holding DepositHookMsg::ProvideLiquidity instead.
     ProvideLiquidity {
         asset: Asset,
+        recipient: Option<String>,
+    },
+    /// Provides liquidity with several of the vault assets, valued through the treasury.
+    /// Cw20 assets need an allowance for this contract.
+    ProvideLiquidityMulti {
+        assets: Vec<Asset>,
     },
     UpdatePool {
         deposit_asset: Option<String>,
         assets_to_add: Vec<String>,
         assets_to_remove: Vec<String>,
     },
+    /// Sets the withdrawal fee and the address receiving it
     SetFee {
-        fee: Fee,
+        fee: Option<Fee>,
+        fee_recipient: Option<String>,
+    },
+    /// Sets the global and per address deposit caps, None removes a cap
+    SetDepositCaps {
+        global: Option<Uint128>,
+        per_address: Option<Uint128>,
+    },
+    /// Sets the number of blocks withdrawals wait before they can be claimed, 0 disables unbonding
+    SetUnbondingPeriod {
+        blocks: u64,
+    },
+    /// Pays out the withdrawals of the sender that finished unbonding
+    Claim {},
+    /// Allows the provided address to deposit while deposits are not open
+    AddToWhitelist {
+        address: String,
+    },
+    /// Removes the provided address from the depositors whitelist
+    RemoveFromWhitelist {
+        address: String,
+    },
+    /// Opens deposits to everyone, or restricts them to whitelisted addresses
+    SetOpenDeposits {
+        open_deposits: bool,
+    },
+    /// Sets the minimum deposit of a memory asset. Assets other than the deposit asset can only
+    /// be provided with ProvideLiquidity once they are set, they are valued through the treasury.
+    SetAcceptedDeposit {
+        asset_id: String,
+        min_deposit: Uint128,
+    },
+    /// Stops accepting an asset set with SetAcceptedDeposit
+    RemoveAcceptedDeposit {
+        asset_id: String,
+    },
+    /// Enables or disables emergency mode. While enabled deposits are rejected and LP holders
+    /// can withdraw through DepositHookMsg::EmergencyWithdraw.
+    SetEmergencyMode {
+        enabled: bool,
+    },
+    /// Sets the target weights the treasury assets are rebalanced to, None removes the policy
+    SetRebalancePolicy {
+        policy: Option<RebalancePolicy>,
+    },
+    /// Swaps overweight assets into underweight ones once an asset deviates more than the
+    /// tolerance from its target weight, anyone can call this
+    Rebalance {},
+    /// Pauses the contract, only the admin can call this
+    Pause {},
+    /// Unpauses the contract, only the admin can call this
+    Unpause {},
+    /// Pauses the given function scopes while the rest of the contract keeps working, only the admin can call this
+    PauseScopes {
+        scopes: Vec<PauseScope>,
+    },
+    /// Unpauses the given function scopes, only the admin can call this
+    UnpauseScopes {
+        scopes: Vec<PauseScope>,
     },
 }
 
@@ -44,15 +113,142 @@ pub enum QueryMsg {
     Base(BaseQueryMsg),
     // Add dapp-specific queries here
     State {},
+    /// Returns the paused function scopes
+    PausedScopes {},
+    /// Returns the remaining deposit capacity, globally and for the given address
+    DepositCapacity {
+        address: String,
+    },
+    /// Returns the withdrawal fee and the address receiving it
+    Fees {},
+    /// Returns the value of one LP token
+    SharePrice {},
+    /// Returns the assets accepted by ProvideLiquidity and their minimum deposits
+    DepositInfo {},
+    /// Returns the LP tokens minted when providing `asset`, which must be an accepted asset
+    EstimateDeposit {
+        asset: Asset,
+    },
+    /// Returns the assets paid out when withdrawing `amount` LP tokens
+    EstimateWithdraw {
+        amount: Uint128,
+    },
+    /// Returns whether deposits are open and if `address` is whitelisted
+    Whitelisted {
+        address: String,
+    },
+    /// Returns the withdrawals of `address` waiting to be claimed
+    PendingClaims {
+        address: String,
+    },
+    /// Returns whether the vault is in emergency mode
+    EmergencyMode {},
+    /// Returns the rebalance policy
+    RebalancePolicy {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 #[serde(rename_all = "snake_case")]
 pub enum DepositHookMsg {
-    WithdrawLiquidity {},
-    ProvideLiquidity {},
+    /// Withdraws liquidity, reverting if less than `min_out` of any of the given assets is paid out.
+    /// If `payout_asset` is set, the other assets are swapped into it through the pairs registered
+    /// in memory as `<asset>_<payout_asset>_pair` or `<payout_asset>_<asset>_pair`.
+    /// `min_out` is checked against the assets before they are swapped.
+    WithdrawLiquidity {
+        min_out: Option<Vec<Asset>>,
+        payout_asset: Option<String>,
+        max_spread: Option<Decimal>,
+    },
+    /// Provides liquidity with the sent cw20, minting the LP tokens to `recipient`
+    /// or to the sender of the tokens if not set. No allowance is needed, the sent tokens are
+    /// forwarded to the treasury.
+    ProvideLiquidity { recipient: Option<String> },
+    /// Withdraws liquidity while the vault is in emergency mode. Pays out a pro-rata share of the
+    /// treasury balance of every vault asset, without fee, unbonding, swaps or asset valuation.
+    EmergencyWithdraw {},
 }
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 pub struct StateResponse {
     pub liquidity_token: String,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct DepositCapacityResponse {
+    /// None if the cap is not set
+    pub global_remaining: Option<Uint128>,
+    pub address_remaining: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct FeeResponse {
+    pub fee: Fee,
+    pub fee_recipient: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct SharePriceResponse {
+    pub share_price: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct EstimateDepositResponse {
+    pub share: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct EstimateWithdrawResponse {
+    /// Withdrawal fee in LP tokens
+    pub fee: Uint128,
+    pub assets: Vec<Asset>,
+}
+
+/// A withdrawal waiting for the unbonding period to pass
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Claim {
+    pub assets: Vec<Asset>,
+    pub payout_asset: Option<String>,
+    pub max_spread: Option<Decimal>,
+    /// Block height from which the claim can be paid out
+    pub release_height: u64,
+}
+
+/// Target weight of a vault asset in the value of the rebalanced assets
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TargetWeight {
+    /// Memory asset id, it must be one of the vault assets
+    pub asset: String,
+    pub weight: Decimal,
+}
+
+/// Target portfolio of the treasury assets, the weights have to add up to 1
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RebalancePolicy {
+    pub targets: Vec<TargetWeight>,
+    /// Deviation from its target weight an asset can have before the assets are rebalanced
+    pub tolerance: Decimal,
+    /// Maximum value, as valued by the treasury, swapped per Rebalance call
+    pub max_trade_value: Uint128,
+    pub max_spread: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct RebalancePolicyResponse {
+    pub policy: Option<RebalancePolicy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct WhitelistedResponse {
+    pub open_deposits: bool,
+    pub whitelisted: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct EmergencyModeResponse {
+    pub emergency_mode: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
+pub struct PendingClaimsResponse {
+    pub unbonding_period: u64,
+    pub claims: Vec<Claim>,
+}
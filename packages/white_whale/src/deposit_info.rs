@@ -1,27 +1,97 @@
-use cosmwasm_std::{StdError, StdResult};
+use cosmwasm_std::{StdError, StdResult, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use terraswap::asset::AssetInfo;
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::error::CommonError;
 // New type for abstraction
 pub type ArbBaseAsset = DepositInfo;
+
+/// An asset that is accepted next to the main deposit asset
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AcceptedAsset {
+    pub info: AssetInfo,
+    pub min_deposit: Uint128,
+}
+
+/// The assets a contract accepts. `asset_info` is the main asset, `other_assets` can be
+/// used to accept e.g. aUST next to UST. A minimum of zero means any amount is accepted.
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct DepositInfo {
     pub asset_info: AssetInfo,
+    #[serde(default)]
+    pub min_deposit: Uint128,
+    #[serde(default)]
+    pub other_assets: Vec<AcceptedAsset>,
 }
 
 impl DepositInfo {
-    pub fn assert(&self, asset_info: &AssetInfo) -> StdResult<()> {
+    /// Accepts any amount of `asset_info` and nothing else
+    pub fn new(asset_info: AssetInfo) -> Self {
+        DepositInfo {
+            asset_info,
+            min_deposit: Uint128::zero(),
+            other_assets: vec![],
+        }
+    }
+
+    /// Returns the minimum deposit of `asset_info`, None if it's not accepted
+    pub fn min_deposit_of(&self, asset_info: &AssetInfo) -> Option<Uint128> {
         if asset_info == &self.asset_info {
+            return Some(self.min_deposit);
+        }
+        self.other_assets
+            .iter()
+            .find(|accepted| &accepted.info == asset_info)
+            .map(|accepted| accepted.min_deposit)
+    }
+
+    /// Returns whether `asset_info` is the main asset
+    pub fn is_main_asset(&self, asset_info: &AssetInfo) -> bool {
+        asset_info == &self.asset_info
+    }
+
+    pub fn assert(&self, asset_info: &AssetInfo) -> StdResult<()> {
+        if self.min_deposit_of(asset_info).is_some() {
             return Ok(());
         }
 
         Err(StdError::generic_err(format!(
             "Invalid deposit asset. Expected {}, got {}.",
-            self.asset_info, asset_info
+            self.accepted_assets_string(),
+            asset_info
         )))
     }
 
+    /// Asserts the asset is accepted and at least its minimum deposit
+    pub fn assert_deposit(&self, asset: &Asset) -> Result<(), CommonError> {
+        let min_deposit = match self.min_deposit_of(&asset.info) {
+            Some(min_deposit) => min_deposit,
+            None => {
+                return Err(CommonError::InvalidAsset {
+                    expected: self.accepted_assets_string(),
+                    provided: asset.info.to_string(),
+                })
+            }
+        };
+        if asset.amount < min_deposit {
+            return Err(CommonError::InsufficientDeposit {
+                required: min_deposit,
+                provided: asset.amount,
+            });
+        }
+        Ok(())
+    }
+
+    fn accepted_assets_string(&self) -> String {
+        std::iter::once(&self.asset_info)
+            .chain(self.other_assets.iter().map(|accepted| &accepted.info))
+            .map(|info| info.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
     pub fn get_denom(self) -> StdResult<String> {
         match self.asset_info {
             AssetInfo::NativeToken { denom } => Ok(denom),
@@ -43,11 +113,9 @@ mod tests {
 
     #[test]
     fn test_failing_assert_for_native_tokens() {
-        let deposit_info = DepositInfo {
-            asset_info: AssetInfo::NativeToken {
-                denom: TEST_DENOM1.to_string(),
-            },
-        };
+        let deposit_info = DepositInfo::new(AssetInfo::NativeToken {
+            denom: TEST_DENOM1.to_string(),
+        });
         let other_native_token = AssetInfo::NativeToken {
             denom: TEST_DENOM2.to_string(),
         };
@@ -56,11 +124,9 @@ mod tests {
 
     #[test]
     fn test_passing_assert_for_native_tokens() {
-        let deposit_info = DepositInfo {
-            asset_info: AssetInfo::NativeToken {
-                denom: TEST_DENOM1.to_string(),
-            },
-        };
+        let deposit_info = DepositInfo::new(AssetInfo::NativeToken {
+            denom: TEST_DENOM1.to_string(),
+        });
         let other_native_token = AssetInfo::NativeToken {
             denom: TEST_DENOM1.to_string(),
         };
@@ -69,11 +135,9 @@ mod tests {
 
     #[test]
     fn test_failing_assert_for_nonnative_tokens() {
-        let deposit_info = DepositInfo {
-            asset_info: AssetInfo::Token {
-                contract_addr: TEST_ADDR1.to_string(),
-            },
-        };
+        let deposit_info = DepositInfo::new(AssetInfo::Token {
+            contract_addr: TEST_ADDR1.to_string(),
+        });
         let other_native_token = AssetInfo::Token {
             contract_addr: TEST_ADDR2.to_string(),
         };
@@ -82,11 +146,9 @@ mod tests {
 
     #[test]
     fn test_passing_assert_for_nonnative_tokens() {
-        let deposit_info = DepositInfo {
-            asset_info: AssetInfo::Token {
-                contract_addr: TEST_ADDR1.to_string(),
-            },
-        };
+        let deposit_info = DepositInfo::new(AssetInfo::Token {
+            contract_addr: TEST_ADDR1.to_string(),
+        });
         let other_native_token = AssetInfo::Token {
             contract_addr: TEST_ADDR1.to_string(),
         };
@@ -95,14 +157,96 @@ mod tests {
 
     #[test]
     fn test_failing_assert_for_mixed_tokens() {
-        let deposit_info = DepositInfo {
+        let deposit_info = DepositInfo::new(AssetInfo::NativeToken {
+            denom: TEST_DENOM1.to_string(),
+        });
+        let other_native_token = AssetInfo::Token {
+            contract_addr: TEST_DENOM1.to_string(),
+        };
+        assert!(deposit_info.assert(&other_native_token).is_err());
+    }
+
+    fn ust_and_aust() -> DepositInfo {
+        DepositInfo {
             asset_info: AssetInfo::NativeToken {
                 denom: TEST_DENOM1.to_string(),
             },
+            min_deposit: Uint128::from(100u64),
+            other_assets: vec![AcceptedAsset {
+                info: AssetInfo::Token {
+                    contract_addr: TEST_ADDR1.to_string(),
+                },
+                min_deposit: Uint128::from(50u64),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_assert_for_other_assets() {
+        let deposit_info = ust_and_aust();
+        assert!(deposit_info
+            .assert(&AssetInfo::Token {
+                contract_addr: TEST_ADDR1.to_string(),
+            })
+            .is_ok());
+        assert!(deposit_info
+            .assert(&AssetInfo::Token {
+                contract_addr: TEST_ADDR2.to_string(),
+            })
+            .is_err());
+        assert!(deposit_info
+            .assert(&AssetInfo::NativeToken {
+                denom: TEST_DENOM2.to_string(),
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_assert_deposit_min_amounts() {
+        let deposit_info = ust_and_aust();
+        let ust = |amount: u64| Asset {
+            info: AssetInfo::NativeToken {
+                denom: TEST_DENOM1.to_string(),
+            },
+            amount: Uint128::from(amount),
         };
-        let other_native_token = AssetInfo::Token {
-            contract_addr: TEST_DENOM1.to_string(),
+        let aust = |amount: u64| Asset {
+            info: AssetInfo::Token {
+                contract_addr: TEST_ADDR1.to_string(),
+            },
+            amount: Uint128::from(amount),
         };
-        assert!(deposit_info.assert(&other_native_token).is_err());
+        assert!(deposit_info.assert_deposit(&ust(100)).is_ok());
+        assert_eq!(
+            deposit_info.assert_deposit(&ust(99)),
+            Err(CommonError::InsufficientDeposit {
+                required: Uint128::from(100u64),
+                provided: Uint128::from(99u64),
+            })
+        );
+        assert!(deposit_info.assert_deposit(&aust(50)).is_ok());
+        assert!(deposit_info.assert_deposit(&aust(49)).is_err());
+        assert!(matches!(
+            deposit_info.assert_deposit(&Asset {
+                info: AssetInfo::NativeToken {
+                    denom: TEST_DENOM2.to_string(),
+                },
+                amount: Uint128::from(100u64),
+            }),
+            Err(CommonError::InvalidAsset { .. })
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_single_asset() {
+        let deposit_info: DepositInfo =
+            cosmwasm_std::from_slice(br#"{"asset_info":{"native_token":{"denom":"uusd"}}}"#)
+                .unwrap();
+        assert_eq!(
+            deposit_info,
+            DepositInfo::new(AssetInfo::NativeToken {
+                denom: TEST_DENOM1.to_string(),
+            })
+        );
     }
 }
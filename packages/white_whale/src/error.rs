@@ -0,0 +1,76 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_controllers::AdminError;
+use thiserror::Error;
+
+use crate::pause::PauseScope;
+
+/// Errors shared by the contracts. Every contract error wraps them in a `Common` variant so clients
+/// can match on the same variants, and the fields, whichever contract returned them.
+#[derive(Error, Debug, PartialEq)]
+pub enum CommonError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The contract is paused")]
+    Paused {},
+
+    #[error("The {scope} of the contract are paused")]
+    ScopePaused { scope: PauseScope },
+
+    #[error("Invalid address {address} provided for {field}")]
+    InvalidAddress { field: String, address: String },
+
+    #[error("The amount can't be zero")]
+    InvalidZeroAmount {},
+
+    #[error("Invalid asset. Expected {expected}, got {provided}")]
+    InvalidAsset { expected: String, provided: String },
+
+    #[error("Deposit of {provided} is below the required {required}")]
+    InsufficientDeposit {
+        required: Uint128,
+        provided: Uint128,
+    },
+
+    #[error("Not enough funds, {required} is required but only {available} is available")]
+    InsufficientFunds {
+        required: Uint128,
+        available: Uint128,
+    },
+}
+
+impl From<CommonError> for StdError {
+    fn from(err: CommonError) -> Self {
+        match err {
+            CommonError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_error_into_std_error() {
+        let err: StdError = CommonError::InsufficientDeposit {
+            required: Uint128::from(100u64),
+            provided: Uint128::from(99u64),
+        }
+        .into();
+        assert_eq!(
+            err,
+            StdError::generic_err("Deposit of 99 is below the required 100")
+        );
+
+        let err: StdError = CommonError::Std(StdError::not_found("state")).into();
+        assert_eq!(err, StdError::not_found("state"));
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt::Display;
+
+use cosmwasm_std::{Addr, Attribute, Decimal, Event, Response, Uint128};
+
+/// Key of the action attribute, always the first attribute of an event
+pub const ACTION_KEY: &str = "action";
+/// Key of the module attribute, always the second attribute of an event
+pub const MODULE_KEY: &str = "module";
+
+// Module names emitted by the contracts
+pub const GOVERNANCE: &str = "governance";
+pub const TREASURY: &str = "treasury";
+pub const LUNA_VAULT: &str = "luna_vault";
+pub const PEG_ARB: &str = "peg_arb";
+
+/// The attributes of a response in the schema shared by all contracts so indexers can parse
+/// them the same way: the action, the module that emitted it and then the payload fields.
+/// Actions and payload keys are snake_case.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhaleEvent {
+    attributes: Vec<Attribute>,
+}
+
+impl WhaleEvent {
+    pub fn new(module: &str, action: &str) -> Self {
+        WhaleEvent {
+            attributes: vec![
+                Attribute::new(ACTION_KEY, action),
+                Attribute::new(MODULE_KEY, module),
+            ],
+        }
+    }
+
+    /// Adds a payload field
+    pub fn add_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.push(Attribute::new(key, value));
+        self
+    }
+
+    /// Adds several payload fields
+    pub fn add_attributes<A: Into<Attribute>>(
+        mut self,
+        attributes: impl IntoIterator<Item = A>,
+    ) -> Self {
+        self.attributes
+            .extend(attributes.into_iter().map(|attr| attr.into()));
+        self
+    }
+
+    /// Returns a response with only the event attributes
+    pub fn into_response<T>(self) -> Response<T>
+    where
+        T: Clone + std::fmt::Debug + PartialEq + schemars::JsonSchema,
+    {
+        Response::new().add_attributes(self.attributes)
+    }
+}
+
+impl IntoIterator for WhaleEvent {
+    type Item = Attribute;
+    type IntoIter = std::vec::IntoIter<Attribute>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.attributes.into_iter()
+    }
+}
+
+/// Type of the event every vault emits on deposits and withdrawals, indexed as `wasm-vault`
+pub const VAULT_EVENT: &str = "vault";
+
+/// A deposit into or a withdrawal from a vault in the schema shared by all vaults. It is emitted
+/// as a separate event next to the attributes of the module, so a single indexer can follow
+/// every vault. Assets are listed comma separated as `<amount><denom or token address>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VaultEvent {
+    event: Event,
+}
+
+impl VaultEvent {
+    /// `depositor` is the owner of the minted shares
+    pub fn deposit<A: Display>(
+        vault: &Addr,
+        depositor: &Addr,
+        assets_in: &[A],
+        shares_minted: Uint128,
+    ) -> Self {
+        VaultEvent {
+            event: Event::new(VAULT_EVENT)
+                .add_attribute("vault", vault)
+                .add_attribute(ACTION_KEY, "deposit")
+                .add_attribute("depositor", depositor)
+                .add_attribute("assets_in", join_assets(assets_in))
+                .add_attribute("shares_minted", shares_minted),
+        }
+    }
+
+    /// `depositor` is the owner of the burned shares
+    pub fn withdraw<A: Display>(
+        vault: &Addr,
+        depositor: &Addr,
+        assets_out: &[A],
+        shares_burned: Uint128,
+    ) -> Self {
+        VaultEvent {
+            event: Event::new(VAULT_EVENT)
+                .add_attribute("vault", vault)
+                .add_attribute(ACTION_KEY, "withdraw")
+                .add_attribute("depositor", depositor)
+                .add_attribute("assets_out", join_assets(assets_out))
+                .add_attribute("shares_burned", shares_burned),
+        }
+    }
+
+    /// Adds the value of one share in the deposit asset of the vault before the deposit or
+    /// withdrawal. Left out when the vault can't value its assets, i.e. on emergency withdrawals.
+    pub fn exchange_rate(mut self, exchange_rate: Decimal) -> Self {
+        self.event = self
+            .event
+            .add_attribute("exchange_rate", exchange_rate.to_string());
+        self
+    }
+}
+
+impl From<VaultEvent> for Event {
+    fn from(vault_event: VaultEvent) -> Self {
+        vault_event.event
+    }
+}
+
+/// Returns the value of one share, one if there are no shares yet
+pub fn share_exchange_rate(value: Uint128, total_share: Uint128) -> Decimal {
+    if total_share.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(value, total_share)
+    }
+}
+
+fn join_assets<A: Display>(assets: &[A]) -> String {
+    assets
+        .iter()
+        .map(|asset| asset.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{attr, coin, Empty};
+
+    #[test]
+    fn event_starts_with_action_and_module() {
+        let response: Response<Empty> = WhaleEvent::new(TREASURY, "add_dapp")
+            .add_attribute("dapp", "dapp_address")
+            .add_attributes(vec![("amount", "10")])
+            .into_response();
+        assert_eq!(
+            response.attributes,
+            vec![
+                attr("action", "add_dapp"),
+                attr("module", "treasury"),
+                attr("dapp", "dapp_address"),
+                attr("amount", "10"),
+            ]
+        );
+    }
+
+    #[test]
+    fn event_extends_response() {
+        let response: Response<Empty> = Response::new()
+            .add_attribute("existing", "attribute")
+            .add_attributes(WhaleEvent::new(PEG_ARB, "update_config"));
+        assert_eq!(
+            response.attributes,
+            vec![
+                attr("existing", "attribute"),
+                attr("action", "update_config"),
+                attr("module", "peg_arb"),
+            ]
+        );
+    }
+
+    #[test]
+    fn vault_event_schema() {
+        let event: Event = VaultEvent::deposit(
+            &Addr::unchecked("vault"),
+            &Addr::unchecked("depositor"),
+            &[coin(10, "uusd"), coin(5, "uluna")],
+            Uint128::from(20u128),
+        )
+        .exchange_rate(Decimal::percent(75))
+        .into();
+        assert_eq!(event.ty, VAULT_EVENT);
+        assert_eq!(
+            event.attributes,
+            vec![
+                attr("vault", "vault"),
+                attr("action", "deposit"),
+                attr("depositor", "depositor"),
+                attr("assets_in", "10uusd,5uluna"),
+                attr("shares_minted", "20"),
+                attr("exchange_rate", "0.75"),
+            ]
+        );
+
+        let event: Event = VaultEvent::withdraw(
+            &Addr::unchecked("vault"),
+            &Addr::unchecked("depositor"),
+            &[coin(10, "uusd")],
+            Uint128::from(20u128),
+        )
+        .into();
+        assert_eq!(
+            event.attributes,
+            vec![
+                attr("vault", "vault"),
+                attr("action", "withdraw"),
+                attr("depositor", "depositor"),
+                attr("assets_out", "10uusd"),
+                attr("shares_burned", "20"),
+            ]
+        );
+    }
+}
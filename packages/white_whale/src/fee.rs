@@ -3,6 +3,19 @@ use cosmwasm_std::{CosmosMsg, Deps, StdResult};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use terraswap::asset::Asset;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum FeeError {
+    #[error("The provided fee is invalid")]
+    InvalidFee {},
+
+    #[error("The fees combined exceed the maximum of {max_total}")]
+    TotalFeeTooHigh { max_total: Decimal },
+
+    #[error("The fee recipient can't be empty")]
+    InvalidFeeRecipient {},
+}
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Fee {
@@ -17,6 +30,14 @@ impl Fee {
     pub fn msg(&self, deps: Deps, asset: Asset, recipient: Addr) -> StdResult<CosmosMsg> {
         asset.into_msg(&deps.querier, recipient)
     }
+
+    /// Checks the share doesn't exceed `max`
+    pub fn validate(&self, max: Decimal) -> Result<(), FeeError> {
+        if self.share > max {
+            return Err(FeeError::InvalidFee {});
+        }
+        Ok(())
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -27,6 +48,31 @@ pub struct VaultFee {
     pub treasury_addr: Addr,
 }
 
+impl VaultFee {
+    /// Checks every fee is lower than 100%, the fees combined don't exceed `max_total`
+    /// and the treasury address is set
+    pub fn validate(&self, max_total: Decimal) -> Result<(), FeeError> {
+        let fees = [
+            &self.flash_loan_fee,
+            &self.treasury_fee,
+            &self.commission_fee,
+        ];
+        if fees.iter().any(|fee| fee.share >= Decimal::one()) {
+            return Err(FeeError::InvalidFee {});
+        }
+        let total = fees
+            .iter()
+            .fold(Decimal::zero(), |total, fee| total + fee.share);
+        if total > max_total {
+            return Err(FeeError::TotalFeeTooHigh { max_total });
+        }
+        if self.treasury_addr.as_str().is_empty() {
+            return Err(FeeError::InvalidFeeRecipient {});
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,4 +86,40 @@ mod tests {
         let deposit_fee = fee.compute(deposit);
         assert_eq!(deposit_fee, Uint128::from(200000u64));
     }
+
+    #[test]
+    fn test_vault_fee_validate() {
+        let mut fees = VaultFee {
+            flash_loan_fee: Fee {
+                share: Decimal::percent(5u64),
+            },
+            treasury_fee: Fee {
+                share: Decimal::percent(10u64),
+            },
+            commission_fee: Fee {
+                share: Decimal::percent(10u64),
+            },
+            treasury_addr: Addr::unchecked("treasury"),
+        };
+        assert_eq!(fees.validate(Decimal::percent(25u64)), Ok(()));
+        assert_eq!(
+            fees.validate(Decimal::percent(20u64)),
+            Err(FeeError::TotalFeeTooHigh {
+                max_total: Decimal::percent(20u64)
+            })
+        );
+
+        fees.treasury_fee.share = Decimal::percent(100u64);
+        assert_eq!(
+            fees.validate(Decimal::percent(200u64)),
+            Err(FeeError::InvalidFee {})
+        );
+
+        fees.treasury_fee.share = Decimal::percent(10u64);
+        fees.treasury_addr = Addr::unchecked("");
+        assert_eq!(
+            fees.validate(Decimal::percent(25u64)),
+            Err(FeeError::InvalidFeeRecipient {})
+        );
+    }
 }
@@ -1,2 +1 @@
 pub mod msg;
-pub mod terraswap;
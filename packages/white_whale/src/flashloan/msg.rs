@@ -0,0 +1,153 @@
+use std::fmt;
+
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Empty, QueryRequest, StdResult, Uint128, WasmMsg, WasmQuery,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terraswap::asset::Asset;
+
+use crate::fee::VaultFee;
+
+/*
+    Flash loan interface shared by the vaults and the strategies borrowing from them.
+    A borrower sends FlashLoan to a vault. The vault sends the requested asset, executes the
+    callback on the borrower and then checks with AfterTrade that the loan was repaid with its fee.
+    The borrower repays from its Repay callback, the last message of its callback.
+*/
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FlashLoanPayload {
+    pub requested_asset: Asset,
+    /// Message the vault executes on the borrower once the asset is sent
+    pub callback: Binary,
+}
+
+/// Execute message accepted by every flash loan lender
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FlashLoanExecuteMsg {
+    FlashLoan { payload: FlashLoanPayload },
+}
+
+/// Query message accepted by every flash loan lender
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FlashLoanQueryMsg {
+    /// Returns a [FeeResponse], the flash loan fee is charged on the borrowed amount
+    Fees {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeResponse {
+    pub fees: VaultFee,
+}
+
+/// Callback a lender executes on itself after the borrower's callback
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LenderCallbackMsg {
+    /// Reverts the loan if the value of the lender didn't grow by `loan_fee`
+    AfterTrade { loan_fee: Uint128 },
+}
+
+/// Callback a borrower executes on itself after its trades
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BorrowerCallbackMsg {
+    /// Repays `loan_amount` plus the flash loan fee of the lender
+    Repay { loan_amount: Uint128 },
+}
+
+/// Wraps a callback like the `Callback` variant of the contract's ExecuteMsg
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CallbackWrapper<'a, M> {
+    Callback(&'a M),
+}
+
+fn callback_msg<T, M>(msg: &M, contract_addr: &Addr) -> StdResult<CosmosMsg<T>>
+where
+    T: Clone + fmt::Debug + PartialEq + JsonSchema,
+    M: Serialize,
+{
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from(contract_addr),
+        msg: to_binary(&CallbackWrapper::Callback(msg))?,
+        funds: vec![],
+    }))
+}
+
+impl LenderCallbackMsg {
+    pub fn to_cosmos_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+        &self,
+        contract_addr: &Addr,
+    ) -> StdResult<CosmosMsg<T>> {
+        callback_msg(self, contract_addr)
+    }
+}
+
+impl BorrowerCallbackMsg {
+    pub fn to_cosmos_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+        &self,
+        contract_addr: &Addr,
+    ) -> StdResult<CosmosMsg<T>> {
+        callback_msg(self, contract_addr)
+    }
+}
+
+/// Builds the message borrowing `requested_asset` from `lender`, which executes `callback` on
+/// the sender once the asset is sent
+pub fn flash_loan_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    lender: &Addr,
+    requested_asset: Asset,
+    callback: Binary,
+) -> StdResult<CosmosMsg<T>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from(lender),
+        msg: to_binary(&FlashLoanExecuteMsg::FlashLoan {
+            payload: FlashLoanPayload {
+                requested_asset,
+                callback,
+            },
+        })?,
+        funds: vec![],
+    }))
+}
+
+/// Builds the query of the flash loan fees charged by `lender`, answered with a [FeeResponse]
+pub fn fees_query(lender: &Addr) -> StdResult<QueryRequest<Empty>> {
+    Ok(QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: String::from(lender),
+        msg: to_binary(&FlashLoanQueryMsg::Fees {})?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{from_binary, Empty};
+
+    use super::*;
+    use crate::luna_vault::msg::ExecuteMsg as LunaVaultMsg;
+    use crate::ust_vault::msg::ExecuteMsg as VaultMsg;
+
+    #[test]
+    fn callbacks_match_vault_msgs() {
+        let callback = LenderCallbackMsg::AfterTrade {
+            loan_fee: Uint128::new(10),
+        };
+        let msg: CosmosMsg<Empty> = callback.to_cosmos_msg(&Addr::unchecked("vault")).unwrap();
+        let msg = match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => msg,
+            _ => panic!("unexpected"),
+        };
+        assert_eq!(
+            from_binary::<VaultMsg>(&msg).unwrap(),
+            VaultMsg::Callback(callback.clone())
+        );
+        assert_eq!(
+            from_binary::<LunaVaultMsg>(&msg).unwrap(),
+            LunaVaultMsg::Callback(callback)
+        );
+    }
+}
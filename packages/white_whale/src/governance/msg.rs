@@ -1,5 +1,9 @@
-use crate::governance::state::{OrderBy, PollStatus, VoteOption};
-use cosmwasm_std::{Decimal, Uint128};
+use crate::governance::state::{
+    CategoryParams, DepositSlashPolicy, OrderBy, PollCategory, PollExecuteMsg, PollStatus,
+    PollType, QuorumMode, VoteOption,
+};
+use crate::pause::PauseScope;
+use cosmwasm_std::{Coin, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -13,26 +17,99 @@ pub struct InstantiateMsg {
     pub expiration_period: u64,
     pub proposal_deposit: Uint128,
     pub snapshot_period: u64,
+    /// Defaults to [DepositSlashPolicy::DistributeToStakers]
+    pub deposit_slash_policy: Option<DepositSlashPolicy>,
+    pub treasury_addr: Option<String>,
+    /// Defaults to [QuorumMode::EndPoll]
+    pub quorum_mode: Option<QuorumMode>,
+    /// Defaults to zero, meaning forfeited deposits are not shared with voters
+    pub voter_reward_ratio: Option<Decimal>,
+    /// Defaults to `register_contracts`
+    pub protected_methods: Option<Vec<String>>,
+    /// Poll parameters overridden per category, defaults to none
+    pub category_params: Option<Vec<(PollCategory, CategoryParams)>>,
+    /// Address which can veto passed polls during their timelock period, defaults to none
+    pub guardian: Option<String>,
+    /// Native coin which can be deposited instead of WHALE to create polls, defaults to none
+    pub native_proposal_deposit: Option<Coin>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
+    /// Creates a poll funded with the native proposal deposit attached to the message instead of
+    /// WHALE, see [crate::governance::state::Cw20HookMsg::CreatePoll]. The deposit is refunded
+    /// in the same denom.
+    CreatePoll {
+        title: String,
+        description: String,
+        link: Option<String>,
+        metadata_hash: Option<String>,
+        execute_msgs: Option<Vec<PollExecuteMsg>>,
+        poll_type: Option<PollType>,
+        category: Option<PollCategory>,
+    },
     CastVote {
         poll_id: u64,
         vote: VoteOption,
         amount: Uint128,
     },
+    /// Replaces the vote of the sender on a poll in progress, until its snapshot period starts
+    ChangeVote {
+        poll_id: u64,
+        vote: VoteOption,
+        amount: Uint128,
+    },
     EndPoll {
         poll_id: u64,
     },
     ExecutePoll {
         poll_id: u64,
     },
+    /// Executes the messages of a poll. Only callable by the contract itself, see [ExecuteMsg::ExecutePoll]
+    ExecutePollMsgs {
+        poll_id: u64,
+    },
+    /// Executes a poll which is in the failed_execution status again
+    RetryExecution {
+        poll_id: u64,
+    },
     ExpirePoll {
         poll_id: u64,
     },
+    /// Expires the oldest passed polls which are past their expiration period, up to `limit` polls
+    ExpirePolls {
+        limit: Option<u32>,
+    },
+    /// Cancels a poll without votes, refunding the deposit. Only callable by the poll creator
+    CancelPoll {
+        poll_id: u64,
+    },
+    /// Vetoes a passed poll during its timelock period so it can't be executed.
+    /// Only callable by the guardian
+    VetoPoll {
+        poll_id: u64,
+        reason: String,
+    },
+    /// Pauses the given function scopes, callable by the owner or the guardian
+    PauseScopes {
+        scopes: Vec<PauseScope>,
+    },
+    /// Unpauses the given function scopes, callable by the owner or the guardian
+    UnpauseScopes {
+        scopes: Vec<PauseScope>,
+    },
+    /// Delegates the voting power of the sender's stake to another address
+    DelegateVotingPower {
+        delegate: String,
+    },
+    /// Takes back the voting power delegated by the sender
+    UndelegateVotingPower {},
+    /// Claims the voting rewards of the sender
+    ClaimVotingRewards {},
+    /// Claims the staking rewards of the sender, see [crate::governance::state::Cw20HookMsg::DistributeRewards]
+    ClaimRewards {},
     RegisterContracts {
         whale_token: String,
     },
@@ -51,9 +128,23 @@ pub enum ExecuteMsg {
         expiration_period: Option<u64>,
         proposal_deposit: Option<Uint128>,
         snapshot_period: Option<u64>,
+        deposit_slash_policy: Option<DepositSlashPolicy>,
+        treasury_addr: Option<String>,
+        quorum_mode: Option<QuorumMode>,
+        voter_reward_ratio: Option<Decimal>,
+        protected_methods: Option<Vec<String>>,
+        /// Replaces the poll parameters overridden per category
+        category_params: Option<Vec<(PollCategory, CategoryParams)>>,
+        guardian: Option<String>,
+        native_proposal_deposit: Option<Coin>,
     },
 }
 
+/// MigrateMsg allows a privileged contract administrator to run
+/// a migration on the contract, e.g. to upgrade the storage layout.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
@@ -61,10 +152,20 @@ pub enum QueryMsg {
     Config {},
     // State returns the governance state values such as the poll_count and the amount deposited
     State {},
+    // PausedScopes returns the paused function scopes
+    PausedScopes {},
     // Staker returns Staked governance token information for the provided address
     Staker {
         address: String,
     },
+    // PendingVotingRewards returns the voting rewards the provided address can claim
+    PendingVotingRewards {
+        address: String,
+    },
+    // StakerRewards returns the staking rewards the provided address can claim
+    StakerRewards {
+        address: String,
+    },
     // Poll returns the information related to a Poll if that poll exists
     Poll {
         poll_id: u64,
@@ -82,4 +183,20 @@ pub enum QueryMsg {
         limit: Option<u32>,
         order_by: Option<OrderBy>,
     },
+    // Voter returns the vote of an address on a poll, as long as it is stored, and whether the poll has ended
+    Voter {
+        poll_id: u64,
+        address: String,
+    },
+    // PollExecutables returns the messages a poll executes in execution order, with their json decoded
+    PollExecutables {
+        poll_id: u64,
+    },
+    // Delegations returns the delegation of a delegator, the delegators of a delegate, or all delegations
+    Delegations {
+        delegator: Option<String>,
+        delegate: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
@@ -1,23 +1,13 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Binary, CanonicalAddr, Decimal, Order, StdResult, Storage, Uint128};
-use cosmwasm_storage::{
-    bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket, ReadonlySingleton,
-    Singleton,
-};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Binary, CanonicalAddr, Coin, Decimal, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex, U64Key};
 use std::fmt;
 
 use std::cmp::Ordering;
 
-static KEY_CONFIG: &[u8] = b"config";
-static KEY_STATE: &[u8] = b"state";
-
-static PREFIX_POLL_INDEXER: &[u8] = b"poll_indexer";
-static PREFIX_POLL_VOTER: &[u8] = b"poll_voter";
-static PREFIX_POLL: &[u8] = b"poll";
-static PREFIX_BANK: &[u8] = b"bank";
+static PREFIX_POLL: &str = "poll";
 
 const MAX_LIMIT: u32 = 30;
 const DEFAULT_LIMIT: u32 = 10;
@@ -56,6 +46,120 @@ pub struct Config {
     pub expiration_period: u64,
     pub proposal_deposit: Uint128,
     pub snapshot_period: u64,
+    pub deposit_slash_policy: DepositSlashPolicy,
+    pub treasury_addr: Option<CanonicalAddr>,
+    pub quorum_mode: QuorumMode,
+    /// Share of the forfeited deposit of a poll which did not reach quorum that goes to its voters
+    pub voter_reward_ratio: Decimal,
+    /// Methods of the contract itself which poll messages are not allowed to call
+    pub protected_methods: Vec<String>,
+    /// Poll parameters overridden for the polls of a category
+    pub category_params: Vec<(PollCategory, CategoryParams)>,
+    /// Address which can veto passed polls during their timelock period
+    pub guardian: Option<CanonicalAddr>,
+    /// Native coin which can be deposited instead of WHALE to create polls
+    pub native_proposal_deposit: Option<Coin>,
+}
+
+impl Config {
+    /// Returns the poll parameters of a category, the config values apply to the parameters the
+    /// category doesn't override
+    pub fn poll_params(&self, category: &PollCategory) -> PollParams {
+        let overrides = self
+            .category_params
+            .iter()
+            .find(|(params_category, _)| params_category == category)
+            .map(|(_, params)| params.clone())
+            .unwrap_or_default();
+        PollParams {
+            quorum: overrides.quorum.unwrap_or(self.quorum),
+            threshold: overrides.threshold.unwrap_or(self.threshold),
+            voting_period: overrides.voting_period.unwrap_or(self.voting_period),
+            proposal_deposit: overrides.proposal_deposit.unwrap_or(self.proposal_deposit),
+        }
+    }
+}
+
+/// Category of a poll, each category can have its own poll parameters so riskier proposals can
+/// require a higher participation
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PollCategory {
+    /// Changes parameters of the protocol contracts
+    ParameterChange,
+    /// Spends funds of the treasury or the community fund
+    Spend,
+    /// Whitelists dapps or assets
+    Whitelist,
+    /// Signaling proposals, the category of the text polls
+    Text,
+}
+
+impl fmt::Display for PollCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PollCategory::ParameterChange => write!(f, "parameter_change"),
+            PollCategory::Spend => write!(f, "spend"),
+            PollCategory::Whitelist => write!(f, "whitelist"),
+            PollCategory::Text => write!(f, "text"),
+        }
+    }
+}
+
+/// Poll parameters of a category overriding the ones of the config, unset parameters keep the
+/// config value
+#[derive(Default, Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CategoryParams {
+    pub quorum: Option<Decimal>,
+    pub threshold: Option<Decimal>,
+    pub voting_period: Option<u64>,
+    pub proposal_deposit: Option<Uint128>,
+}
+
+/// Poll parameters applying to the polls of a category
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PollParams {
+    pub quorum: Decimal,
+    pub threshold: Decimal,
+    pub voting_period: u64,
+    pub proposal_deposit: Uint128,
+}
+
+/// What happens to the deposit of a poll which did not reach quorum
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositSlashPolicy {
+    /// The deposit is burned
+    Burn,
+    /// The deposit is sent to the treasury_addr stored in the config
+    SendToTreasury,
+    /// The deposit stays in the contract, increasing the balance of all stakers
+    DistributeToStakers,
+}
+
+/// Which staked balance the quorum of a poll is computed against when no snapshot was taken
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QuorumMode {
+    /// The staked balance at the time the poll is ended
+    EndPoll,
+    /// The staked balance at the time the poll was created
+    PollCreation,
+}
+
+/// Voter rewards of a poll, distributed pro-rata to the weight each voter voted with
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoterRewardPool {
+    pub reward: Uint128,
+    pub total_weight: Uint128,
+}
+
+/// Staking rewards of a staker, accrued pro-rata to its share since the reward index was recorded
+#[derive(Default, Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerRewardInfo {
+    /// Global reward index at the last update of the pending rewards
+    pub reward_index: Decimal,
+    pub pending_rewards: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -66,8 +170,6 @@ pub struct State {
     pub total_deposit: Uint128,
 }
 
-pub const STATE: Item<State> = Item::new("state");
-
 // State related to Poll Execution
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -112,15 +214,33 @@ pub struct Poll {
     pub status: PollStatus,
     pub yes_votes: Uint128,
     pub no_votes: Uint128,
+    pub abstain_votes: Uint128,
     pub end_height: u64,
     pub title: String,
     pub description: String,
     pub link: Option<String>,
+    /// Content-addressed hash of an off-chain proposal document, e.g. an IPFS CID
+    pub metadata_hash: Option<String>,
     pub execute_data: Option<Vec<ExecuteData>>,
     pub deposit_amount: Uint128,
     /// Total balance at the end poll
     pub total_balance_at_end_poll: Option<Uint128>,
     pub staked_amount: Option<Uint128>,
+    /// Staked balance at poll creation, only recorded with the poll_creation quorum mode
+    pub staked_amount_at_creation: Option<Uint128>,
+    pub poll_type: PollType,
+    pub category: PollCategory,
+    /// Denom of the deposit when it was made in a native coin, none for a WHALE deposit
+    pub deposit_denom: Option<String>,
+}
+
+/// Text polls are signaling proposals without messages to execute, their lifecycle ends when the
+/// poll is ended
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PollType {
+    Text,
+    Executable,
 }
 
 // State objects here are good candidates to move to the packages module
@@ -132,6 +252,9 @@ pub enum PollStatus {
     Rejected,
     Executed,
     Expired,
+    Canceled,
+    FailedExecution,
+    Vetoed,
 }
 
 impl fmt::Display for PollStatus {
@@ -145,14 +268,15 @@ impl fmt::Display for PollStatus {
 pub enum VoteOption {
     Yes,
     No,
+    Abstain,
 }
 
 impl fmt::Display for VoteOption {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if *self == VoteOption::Yes {
-            write!(f, "yes")
-        } else {
-            write!(f, "no")
+        match self {
+            VoteOption::Yes => write!(f, "yes"),
+            VoteOption::No => write!(f, "no"),
+            VoteOption::Abstain => write!(f, "abstain"),
         }
     }
 }
@@ -162,15 +286,21 @@ pub struct PollResponse {
     pub id: u64,
     pub creator: String,
     pub status: PollStatus,
+    pub poll_type: PollType,
+    pub category: PollCategory,
     pub end_height: u64,
     pub title: String,
     pub description: String,
     pub link: Option<String>,
+    pub metadata_hash: Option<String>,
     pub deposit_amount: Uint128,
+    pub deposit_denom: Option<String>,
     pub execute_data: Option<Vec<PollExecuteMsg>>,
-    pub yes_votes: Uint128, // balance
-    pub no_votes: Uint128,  // balance
+    pub yes_votes: Uint128,     // balance
+    pub no_votes: Uint128,      // balance
+    pub abstain_votes: Uint128, // balance
     pub staked_amount: Option<Uint128>,
+    pub staked_amount_at_creation: Option<Uint128>,
     pub total_balance_at_end_poll: Option<Uint128>,
 }
 
@@ -188,10 +318,12 @@ pub struct PollCountResponse {
 pub struct StakerResponse {
     pub balance: Uint128,
     pub share: Uint128,
+    /// Amount which can not be withdrawn as it is used in polls which are still in progress
+    pub locked_amount: Uint128,
     pub locked_balance: Vec<(u64, VoterInfo)>,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     pub owner: String,
     pub whale_token: String,
@@ -202,9 +334,17 @@ pub struct ConfigResponse {
     pub expiration_period: u64,
     pub proposal_deposit: Uint128,
     pub snapshot_period: u64,
+    pub deposit_slash_policy: DepositSlashPolicy,
+    pub treasury_addr: Option<String>,
+    pub quorum_mode: QuorumMode,
+    pub voter_reward_ratio: Decimal,
+    pub protected_methods: Vec<String>,
+    pub category_params: Vec<(PollCategory, CategoryParams)>,
+    pub guardian: Option<String>,
+    pub native_proposal_deposit: Option<Coin>,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StateResponse {
     pub poll_count: u64,
     pub total_share: Uint128,
@@ -229,73 +369,120 @@ pub struct VotersResponse {
     pub voters: Vec<VotersResponseItem>,
 }
 
-pub fn bank_store(storage: &mut dyn Storage) -> Bucket<TokenManager> {
-    bucket(storage, PREFIX_BANK)
-}
-
-pub fn bank_read(storage: &dyn Storage) -> ReadonlyBucket<TokenManager> {
-    bucket_read(storage, PREFIX_BANK)
-}
-
-pub fn config_store(storage: &mut dyn Storage) -> Singleton<Config> {
-    singleton(storage, KEY_CONFIG)
-}
-
-pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<Config> {
-    singleton_read(storage, KEY_CONFIG)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct VoterResponse {
+    pub poll_id: u64,
+    pub voter: String,
+    pub vote: VoteOption,
+    pub balance: Uint128,
+    pub poll_ended: bool,
 }
 
-pub fn state_store(storage: &mut dyn Storage) -> Singleton<State> {
-    singleton(storage, KEY_STATE)
+/// A message executed by a poll
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct PollExecutable {
+    pub order: u64,
+    pub contract: String,
+    pub msg: Binary,
+    /// The message as json text, None if it is not valid json
+    pub decoded_msg: Option<String>,
 }
 
-pub fn state_read(storage: &dyn Storage) -> ReadonlySingleton<State> {
-    singleton_read(storage, KEY_STATE)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct PollExecutablesResponse {
+    pub poll_id: u64,
+    pub executables: Vec<PollExecutable>,
 }
 
-pub fn poll_store(storage: &mut dyn Storage) -> Bucket<Poll> {
-    bucket(storage, PREFIX_POLL)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct PendingVotingRewardsResponse {
+    pub pending_voting_rewards: Uint128,
 }
 
-pub fn poll_read(storage: &dyn Storage) -> ReadonlyBucket<Poll> {
-    bucket_read(storage, PREFIX_POLL)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct StakerRewardsResponse {
+    pub pending_rewards: Uint128,
 }
 
-pub fn poll_voter_store(storage: &mut dyn Storage, poll_id: u64) -> Bucket<VoterInfo> {
-    Bucket::multilevel(storage, &[PREFIX_POLL_VOTER, &poll_id.to_be_bytes()])
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct DelegationsResponseItem {
+    pub delegator: String,
+    pub delegate: String,
 }
 
-pub fn poll_voter_read(storage: &dyn Storage, poll_id: u64) -> ReadonlyBucket<VoterInfo> {
-    ReadonlyBucket::multilevel(storage, &[PREFIX_POLL_VOTER, &poll_id.to_be_bytes()])
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct DelegationsResponse {
+    pub delegations: Vec<DelegationsResponseItem>,
+}
+
+// Item keys carry the length prefix of the legacy singletons they replace, so the stored state
+// keeps being read from the same raw keys. Maps already share the raw keys of the legacy buckets.
+pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
+pub const STATE: Item<State> = Item::new("\u{0}\u{5}state");
+/// Id of the poll being executed, read by the reply handler when the execution fails
+pub const TMP_POLL_ID: Item<u64> = Item::new("\u{0}\u{b}tmp_poll_id");
+
+pub const BANK: Map<&[u8], TokenManager> = Map::new("bank");
+pub const POLL_VOTERS: Map<(U64Key, &[u8]), VoterInfo> = Map::new("poll_voter");
+/// Maps a delegator to the delegate holding its voting power
+pub const DELEGATIONS: Map<&[u8], CanonicalAddr> = Map::new("delegation");
+/// Indexes the delegators of a delegate
+pub const DELEGATORS: Map<(&[u8], &[u8]), bool> = Map::new("delegator");
+/// Total share delegated to a delegate
+pub const DELEGATED_SHARES: Map<&[u8], Uint128> = Map::new("delegated_share");
+pub const VOTER_REWARD_POOLS: Map<U64Key, VoterRewardPool> = Map::new("voter_reward_pool");
+/// Voting rewards credited to a voter and not claimed yet
+pub const PENDING_VOTING_REWARDS: Map<&[u8], Uint128> = Map::new("pending_voting_rewards");
+/// Staking rewards distributed per share
+pub const REWARD_INDEX: Item<Decimal> = Item::new("reward_index");
+pub const STAKER_REWARDS: Map<&[u8], StakerRewardInfo> = Map::new("staker_rewards");
+
+pub struct PollIndexes<'a> {
+    pub status: MultiIndex<'a, (Vec<u8>, Vec<u8>), Poll>,
+    pub creator: MultiIndex<'a, (Vec<u8>, Vec<u8>), Poll>,
+}
+
+impl<'a> IndexList<Poll> for PollIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Poll>> + '_> {
+        let v: Vec<&dyn Index<Poll>> = vec![&self.status, &self.creator];
+        Box::new(v.into_iter())
+    }
 }
 
-pub fn poll_indexer_store<'a>(
-    storage: &'a mut dyn Storage,
-    status: &PollStatus,
-) -> Bucket<'a, bool> {
-    Bucket::multilevel(
-        storage,
-        &[PREFIX_POLL_INDEXER, status.to_string().as_bytes()],
-    )
+/// Polls by id, indexed by status and creator
+pub fn polls<'a>() -> IndexedMap<'a, U64Key, Poll, PollIndexes<'a>> {
+    let indexes = PollIndexes {
+        status: MultiIndex::new(
+            |poll: &Poll, pk: Vec<u8>| (poll.status.to_string().into_bytes(), pk),
+            PREFIX_POLL,
+            "poll__status",
+        ),
+        creator: MultiIndex::new(
+            |poll: &Poll, pk: Vec<u8>| (poll.creator.as_slice().to_vec(), pk),
+            PREFIX_POLL,
+            "poll__creator",
+        ),
+    };
+    IndexedMap::new(PREFIX_POLL, indexes)
 }
 
-pub fn read_poll_voters<'a>(
-    storage: &'a dyn Storage,
+pub fn read_poll_voters(
+    storage: &dyn Storage,
     poll_id: u64,
     start_after: Option<CanonicalAddr>,
     limit: Option<u32>,
     order_by: Option<OrderBy>,
 ) -> StdResult<Vec<(CanonicalAddr, VoterInfo)>> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|addr| Bound::exclusive(addr.as_slice()));
     let (start, end, order_by) = match order_by {
-        Some(OrderBy::Asc) => (calc_range_start_addr(start_after), None, OrderBy::Asc),
-        _ => (None, calc_range_end_addr(start_after), OrderBy::Desc),
+        Some(OrderBy::Asc) => (start_after, None, OrderBy::Asc),
+        _ => (None, start_after, OrderBy::Desc),
     };
 
-    let voters: ReadonlyBucket<'a, VoterInfo> =
-        ReadonlyBucket::multilevel(storage, &[PREFIX_POLL_VOTER, &poll_id.to_be_bytes()]);
-    voters
-        .range(start.as_deref(), end.as_deref(), order_by.into())
+    POLL_VOTERS
+        .prefix(U64Key::new(poll_id))
+        .range(storage, start, end, order_by.into())
         .take(limit)
         .map(|item| {
             let (k, v) = item?;
@@ -304,72 +491,74 @@ pub fn read_poll_voters<'a>(
         .collect()
 }
 
-pub fn read_polls<'a>(
-    storage: &'a dyn Storage,
-    filter: Option<PollStatus>,
-    start_after: Option<u64>,
+pub fn read_delegations(
+    storage: &dyn Storage,
+    start_after: Option<CanonicalAddr>,
     limit: Option<u32>,
-    order_by: Option<OrderBy>,
-) -> StdResult<Vec<Poll>> {
+) -> StdResult<Vec<(CanonicalAddr, CanonicalAddr)>> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let (start, end, order_by) = match order_by {
-        Some(OrderBy::Asc) => (calc_range_start(start_after), None, OrderBy::Asc),
-        _ => (None, calc_range_end(start_after), OrderBy::Desc),
-    };
+    let start = start_after.map(|addr| Bound::exclusive(addr.as_slice()));
 
-    if let Some(status) = filter {
-        let poll_indexer: ReadonlyBucket<'a, bool> = ReadonlyBucket::multilevel(
-            storage,
-            &[PREFIX_POLL_INDEXER, status.to_string().as_bytes()],
-        );
-        poll_indexer
-            .range(start.as_deref(), end.as_deref(), order_by.into())
-            .take(limit)
-            .map(|item| {
-                let (k, _) = item?;
-                poll_read(storage).load(&k)
-            })
-            .collect()
-    } else {
-        let polls: ReadonlyBucket<'a, Poll> = ReadonlyBucket::new(storage, PREFIX_POLL);
-
-        polls
-            .range(start.as_deref(), end.as_deref(), order_by.into())
-            .take(limit)
-            .map(|item| {
-                let (_, v) = item?;
-                Ok(v)
-            })
-            .collect()
-    }
+    DELEGATIONS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, v) = item?;
+            Ok((CanonicalAddr::from(k), v))
+        })
+        .collect()
 }
 
-// this will set the first key after the provided key, by appending a 1 byte
-fn calc_range_start(start_after: Option<u64>) -> Option<Vec<u8>> {
-    start_after.map(|id| {
-        let mut v = id.to_be_bytes().to_vec();
-        v.push(1);
-        v
-    })
-}
+pub fn read_delegators(
+    storage: &dyn Storage,
+    delegate: &CanonicalAddr,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<CanonicalAddr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|addr| Bound::exclusive(addr.as_slice()));
 
-// this will set the first key after the provided key, by appending a 1 byte
-fn calc_range_end(start_after: Option<u64>) -> Option<Vec<u8>> {
-    start_after.map(|id| id.to_be_bytes().to_vec())
+    DELEGATORS
+        .prefix(delegate.as_slice())
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, _) = item?;
+            Ok(CanonicalAddr::from(k))
+        })
+        .collect()
 }
 
-// this will set the first key after the provided key, by appending a 1 byte
-fn calc_range_start_addr(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
-    start_after.map(|addr| {
-        let mut v = addr.as_slice().to_vec();
-        v.push(1);
-        v
-    })
-}
+pub fn read_polls(
+    storage: &dyn Storage,
+    filter: Option<PollStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order_by: Option<OrderBy>,
+) -> StdResult<Vec<Poll>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Bound::exclusive_int);
+    let (start, end, order_by) = match order_by {
+        Some(OrderBy::Asc) => (start_after, None, OrderBy::Asc),
+        _ => (None, start_after, OrderBy::Desc),
+    };
 
-// this will set the first key after the provided key, by appending a 1 byte
-fn calc_range_end_addr(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
-    start_after.map(|addr| addr.as_slice().to_vec())
+    let polls = polls();
+    let range = match filter {
+        Some(status) => polls
+            .idx
+            .status
+            .prefix(status.to_string().into_bytes())
+            .range(storage, start, end, order_by.into()),
+        None => polls.range(storage, start, end, order_by.into()),
+    };
+    range
+        .take(limit)
+        .map(|item| {
+            let (_, v) = item?;
+            Ok(v)
+        })
+        .collect()
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -382,9 +571,23 @@ pub enum Cw20HookMsg {
         title: String,
         description: String,
         link: Option<String>,
+        /// Content-addressed hash of an off-chain proposal document, e.g. an IPFS CID
+        metadata_hash: Option<String>,
         execute_msgs: Option<Vec<PollExecuteMsg>>,
+        /// Defaults to [PollType::Executable] when execute_msgs are given, [PollType::Text] otherwise
+        poll_type: Option<PollType>,
+        /// Defaults to [PollCategory::ParameterChange] for executable polls, text polls are in
+        /// the [PollCategory::Text] category
+        category: Option<PollCategory>,
     },
+    /// Creates an executable poll which adds `dapp_addr` to the whitelisted dapps of the treasury
+    /// when it passes, so the proposer doesn't have to encode the treasury message.
+    /// The poll is in the [PollCategory::Whitelist] category
+    CreateWhitelistPoll { dapp_addr: String },
     /// StakeVotingTokens a user can stake their mirror token to receive rewards
     /// or do vote on polls
     StakeVotingTokens {},
+    /// Distributes the sent tokens to the stakers pro-rata to their share, e.g. protocol revenue
+    /// sent by the treasury or the vaults
+    DistributeRewards {},
 }
@@ -1,3 +1,5 @@
+pub mod address;
+pub mod admin;
 pub mod anchor;
 pub mod astroport_helper;
 pub mod community_fund;
@@ -5,14 +7,22 @@ pub mod dapps;
 pub mod denom;
 pub mod deposit_info;
 pub mod emissions;
+pub mod error;
+pub mod events;
 pub mod fee;
+pub mod flashloan;
 pub mod governance;
 pub mod luna_vault;
+pub mod mars;
 pub mod memory;
+pub mod pause;
 pub mod peg_arb;
 pub mod prism;
 pub mod query;
+pub mod stader;
+pub mod swap;
 pub mod tax;
+pub mod token;
 pub mod tokenomics;
 pub mod treasury;
 pub mod ust_vault;
@@ -1,4 +1,5 @@
 pub mod luna_unbond_handler;
 pub mod msg;
 pub mod queries;
+pub mod staking_backend;
 pub mod terraswap;
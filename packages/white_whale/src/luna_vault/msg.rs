@@ -1,15 +1,14 @@
-use std::fmt;
-
-use cosmwasm_std::{
-    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg,
-};
+use cosmwasm_std::{Coin, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use terra_rust_script_derive::CosmWasmContract;
 use terraswap::asset::{Asset, AssetInfo};
 
-use crate::fee::{Fee, VaultFee};
+use crate::fee::Fee;
+pub use crate::flashloan::msg::{FeeResponse, FlashLoanPayload, LenderCallbackMsg as CallbackMsg};
+use crate::luna_vault::staking_backend::LiquidStakingBackend;
+use crate::pause::PauseScope;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 pub struct InstantiateMsg {
@@ -29,6 +28,8 @@ pub struct InstantiateMsg {
     pub vault_lp_token_name: Option<String>,
     pub vault_lp_token_symbol: Option<String>,
     pub unbond_handler_code_id: u64,
+    /// Maximum value of the vault in luna, deposits are unlimited if not set
+    pub luna_cap: Option<Uint128>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
@@ -42,14 +43,60 @@ pub enum ExecuteMsg {
     WithdrawUnbonded {},
     /// Withdraws the vault's unbonded bluna or cluna from anchor/prism
     WithdrawUnbondedFlashloan {},
+    /// Claims the vault's matured unbonds from anchor/prism, callable by anyone
+    ProcessBatches {},
+    /// Sets the maximum value of the vault in luna, removes the cap if `cap` is None
+    UpdateLunaCap { cap: Option<Uint128> },
+    /// Enables or disables halting deposits when ProcessBatches detects a drop of the bLuna exchange rate
+    SetSlashingGuard { enabled: bool },
+    /// Updates the unbond handler expiration time in seconds and the minimum relative drop of the
+    /// bLuna exchange rate detected as slashing. Only the admin can call this
+    UpdateConfig {
+        unbond_handler_expiration_time: Option<u64>,
+        slashing_threshold: Option<Decimal>,
+    },
+    /// Resumes deposits halted after a slashing event, only the admin can call this
+    AcknowledgeSlashing {},
+    /// Sets the vLuna token if storing it on instantiation failed, only the admin can call this
+    SetLpToken { address: String },
+    /// Deposits liquid luna of the vault into the passive strategy (Astroport bLuna-Luna LP)
+    DepositIntoLp { amount: Uint128 },
+    /// Withdraws the given amount of LP tokens from the passive strategy
+    WithdrawFromLp { share: Uint128 },
+    /// Registers a liquid staking backend under `name`, only the admin can call this
+    AddStakingBackend {
+        name: String,
+        backend: LiquidStakingBackend,
+        token_address: String,
+        hub_address: String,
+    },
+    /// Removes a staking backend the vault holds no tokens of, only the admin can call this
+    RemoveStakingBackend { name: String },
+    /// Sets the share of bonded luna each staking backend gets, the weights must add up to one.
+    /// Backends not listed get a weight of zero. Only the admin can call this
+    SetStakingBackendWeights { weights: Vec<(String, Decimal)> },
+    /// Bonds liquid luna of the vault with the staking backends according to their weights
+    BondLuna { amount: Uint128 },
     /// Sets the withdraw fee and flash loan fee
     SetFee {
         flash_loan_fee: Option<Fee>,
         treasury_fee: Option<Fee>,
         commission_fee: Option<Fee>,
     },
-    /// Set the admin of the contract
+    /// Deprecated, proposes the admin like ProposeAdmin with the default expiry
     SetAdmin { admin: String },
+    /// Proposes a new admin, who has to accept within `expires_in` blocks
+    ProposeAdmin { admin: String, expires_in: u64 },
+    /// Accepts the pending admin transfer
+    AcceptAdmin {},
+    /// Pauses the contract, only the admin can call this
+    Pause {},
+    /// Unpauses the contract, only the admin can call this
+    Unpause {},
+    /// Pauses the given function scopes while the rest of the contract keeps working, only the admin can call this
+    PauseScopes { scopes: Vec<PauseScope> },
+    /// Unpauses the given function scopes, only the admin can call this
+    UnpauseScopes { scopes: Vec<PauseScope> },
     /// Add provided contract to the whitelisted contracts
     AddToWhitelist { contract_addr: String },
     /// Remove provided contract from the whitelisted contracts
@@ -67,6 +114,10 @@ pub enum ExecuteMsg {
     FlashLoan { payload: FlashLoanPayload },
     /// Swaps the passive strategy token rewards for luna
     SwapRewards {},
+    /// Claims the passive strategy rewards, takes the commission and compounds the rest, callable by anyone
+    ClaimAndCompound {},
+    /// Sends the accrued treasury fees and commissions to the treasury, callable by anyone
+    CollectFees {},
     /// Internal callback message
     Callback(CallbackMsg),
     /// Messages sent by unbond handlers to the vault
@@ -77,12 +128,6 @@ pub enum ExecuteMsg {
     },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct FlashLoanPayload {
-    pub requested_asset: Asset,
-    pub callback: Binary,
-}
-
 /// MigrateMsg allows a privileged contract administrator to run
 /// a migration on the contract. In this case it is just migrating
 /// from one terra code to the same code, but taking advantage of the
@@ -93,12 +138,6 @@ pub struct FlashLoanPayload {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 pub struct MigrateMsg {}
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum CallbackMsg {
-    AfterTrade { loan_fee: Uint128 },
-}
-
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum UnbondHandlerMsg {
@@ -116,28 +155,22 @@ pub enum UnbondActionReply {
     Unbond { bluna_amount: Uint128 },
 }
 
-// Modified from
-// https://github.com/CosmWasm/cosmwasm-plus/blob/v0.2.3/packages/cw20/src/receiver.rs#L15
-impl CallbackMsg {
-    pub fn to_cosmos_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
-        &self,
-        contract_addr: &Addr,
-    ) -> StdResult<CosmosMsg<T>> {
-        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: String::from(contract_addr),
-            msg: to_binary(&ExecuteMsg::Callback(self.clone()))?,
-            funds: vec![],
-        }))
-    }
-}
-
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 #[serde(rename_all = "snake_case")]
 pub enum VaultQueryMsg {
     PoolConfig {},
+    /// Returns the paused function scopes
+    PausedScopes {},
     PoolState {},
     State {},
     Fees {},
+    /// Returns the treasury fees and commissions accrued since the last CollectFees
+    PendingFees {},
+    /// Returns the whitelisted contracts, paginated in the order they were added
+    Whitelist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     EstimateWithdrawFee {
         amount: Uint128,
     },
@@ -148,18 +181,42 @@ pub enum VaultQueryMsg {
     WithdrawableUnbonded {
         address: String,
     },
-    /// queries anchor for unbonded requests
+    /// queries anchor for unbonded requests, paginated by batch id
     UnbondRequests {
         address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// queries the unbond batch history of the anchor bLuna hub
+    AllUnbondHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the luna value of one vLuna
+    ExchangeRate {},
+    /// Returns the luna withdrawn and the fee for unbonding `amount` vLuna
+    EstimateWithdraw {
+        amount: Uint128,
     },
     /// queries the expiration time for unbond handlers
     UnbondHandlerExpirationTime {},
+    /// Returns how much of the luna cap is used
+    VaultUtilization {},
+    /// Returns the detected drops of the bLuna exchange rate and if deposits are halted
+    SlashingEvents {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the registered liquid staking backends and their weights
+    StakingBackends {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
     Unbond {},
+    /// Deposits the sent bLuna into the vault, only accepted from the bLuna token contract
+    ProvideLiquidity {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -181,8 +238,11 @@ pub struct ValueResponse {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct FeeResponse {
-    pub fees: VaultFee,
+pub struct PendingFeesResponse {
+    /// Treasury fee in vLuna
+    pub treasury_fee: Uint128,
+    /// Commission in luna
+    pub commission: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -202,6 +262,67 @@ pub struct StateResponse {
     pub allow_non_whitelisted: bool,
 }
 
+/// A drop of the bLuna exchange rate detected when processing batches
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SlashingEvent {
+    /// Block time in seconds
+    pub time: u64,
+    pub previous_exchange_rate: Decimal,
+    pub exchange_rate: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SlashingEventsResponse {
+    pub events: Vec<(u64, SlashingEvent)>,
+    pub guard_enabled: bool,
+    pub deposits_halted: bool,
+    /// Minimum relative drop of the exchange rate detected as slashing
+    pub slashing_threshold: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WhitelistResponse {
+    pub contracts: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingBackendResponse {
+    pub name: String,
+    pub backend: LiquidStakingBackend,
+    pub token_address: String,
+    pub hub_address: String,
+    pub weight: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingBackendsResponse {
+    pub backends: Vec<StakingBackendResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeRateResponse {
+    /// Luna value of one vLuna
+    pub exchange_rate: Decimal,
+    pub total_value_in_luna: Uint128,
+    pub total_share: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EstimateWithdrawResponse {
+    /// Treasury fee in vLuna
+    pub fee: Uint128,
+    /// Luna value of the unbonded vLuna after fees
+    pub luna_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultUtilizationResponse {
+    pub total_value_in_luna: Uint128,
+    pub luna_cap: Option<Uint128>,
+    /// Share of the cap in use, None if the vault has no cap
+    pub utilization: Option<Decimal>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct LastBalanceResponse {
     pub last_balance: Uint128,
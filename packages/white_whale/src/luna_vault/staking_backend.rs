@@ -0,0 +1,68 @@
+use std::fmt;
+
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Deps, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::anchor::{anchor_bluna_bond_msg, anchor_bluna_unbond_msg};
+use crate::prism::{prism_cluna_bond_msg, prism_cluna_unbond_msg};
+use crate::query::anchor::query_bluna_exchange_rate;
+use crate::query::prism::query_cluna_exchange_rate;
+use crate::query::stader::query_lunax_exchange_rate;
+use crate::stader::{stader_lunax_bond_msg, stader_lunax_unbond_msg};
+
+/// The liquid staking protocol minting a luna derivative
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LiquidStakingBackend {
+    /// Anchor bLuna
+    Anchor,
+    /// Prism cLuna
+    Prism,
+    /// Stader LunaX
+    Stader,
+}
+
+impl LiquidStakingBackend {
+    /// Builds the msg bonding `amount` luna on the hub, the derivative is minted to the sender.
+    pub fn bond_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+        &self,
+        hub_address: Addr,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<T>> {
+        match self {
+            LiquidStakingBackend::Anchor => anchor_bluna_bond_msg(hub_address, amount),
+            LiquidStakingBackend::Prism => prism_cluna_bond_msg(hub_address, amount),
+            LiquidStakingBackend::Stader => stader_lunax_bond_msg(hub_address, amount),
+        }
+    }
+
+    /// Builds the msg unbonding `amount` of the derivative token through the hub.
+    pub fn unbond_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+        &self,
+        token_address: Addr,
+        hub_address: Addr,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<T>> {
+        match self {
+            LiquidStakingBackend::Anchor => {
+                anchor_bluna_unbond_msg(token_address, hub_address, amount)
+            }
+            LiquidStakingBackend::Prism => {
+                prism_cluna_unbond_msg(token_address, hub_address, amount)
+            }
+            LiquidStakingBackend::Stader => {
+                stader_lunax_unbond_msg(token_address, hub_address, amount)
+            }
+        }
+    }
+
+    /// Queries the amount of luna one derivative token is worth.
+    pub fn query_exchange_rate(&self, deps: Deps, hub_address: Addr) -> StdResult<Decimal> {
+        match self {
+            LiquidStakingBackend::Anchor => query_bluna_exchange_rate(deps, hub_address),
+            LiquidStakingBackend::Prism => query_cluna_exchange_rate(deps, hub_address),
+            LiquidStakingBackend::Stader => query_lunax_exchange_rate(deps, hub_address),
+        }
+    }
+}
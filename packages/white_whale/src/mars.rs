@@ -0,0 +1,142 @@
+use std::fmt;
+
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Deps, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::tax::deduct_tax;
+
+/// Asset as identified by the Mars red bank
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MarsAsset {
+    Cw20 { contract_addr: String },
+    Native { denom: String },
+}
+
+impl From<AssetInfo> for MarsAsset {
+    fn from(info: AssetInfo) -> Self {
+        match info {
+            AssetInfo::Token { contract_addr } => MarsAsset::Cw20 { contract_addr },
+            AssetInfo::NativeToken { denom } => MarsAsset::Native { denom },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MarsMsg {
+    DepositNative {
+        denom: String,
+        on_behalf_of: Option<String>,
+    },
+    Withdraw {
+        asset: MarsAsset,
+        amount: Option<Uint128>,
+        recipient: Option<String>,
+    },
+    Borrow {
+        asset: MarsAsset,
+        amount: Uint128,
+        recipient: Option<String>,
+    },
+    RepayNative {
+        denom: String,
+        on_behalf_of: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MarsCw20HookMsg {
+    DepositCw20 { on_behalf_of: Option<String> },
+    RepayCw20 { on_behalf_of: Option<String> },
+}
+
+/// Deposits `asset` into the red bank, the transfer tax of native assets is deducted
+pub fn mars_deposit_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    deps: Deps,
+    red_bank_address: Addr,
+    asset: Asset,
+) -> StdResult<CosmosMsg<T>> {
+    match asset.info {
+        AssetInfo::NativeToken { denom } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: red_bank_address.to_string(),
+            msg: to_binary(&MarsMsg::DepositNative {
+                denom: denom.clone(),
+                on_behalf_of: None,
+            })?,
+            funds: vec![deduct_tax(deps, Coin::new(asset.amount.u128(), denom))?],
+        })),
+        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: red_bank_address.to_string(),
+                amount: asset.amount,
+                msg: to_binary(&MarsCw20HookMsg::DepositCw20 { on_behalf_of: None })?,
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+/// Repays `asset` to the red bank, the transfer tax of native assets is deducted
+pub fn mars_repay_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    deps: Deps,
+    red_bank_address: Addr,
+    asset: Asset,
+) -> StdResult<CosmosMsg<T>> {
+    match asset.info {
+        AssetInfo::NativeToken { denom } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: red_bank_address.to_string(),
+            msg: to_binary(&MarsMsg::RepayNative {
+                denom: denom.clone(),
+                on_behalf_of: None,
+            })?,
+            funds: vec![deduct_tax(deps, Coin::new(asset.amount.u128(), denom))?],
+        })),
+        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: red_bank_address.to_string(),
+                amount: asset.amount,
+                msg: to_binary(&MarsCw20HookMsg::RepayCw20 { on_behalf_of: None })?,
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+pub fn mars_borrow_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    red_bank_address: Addr,
+    asset_info: AssetInfo,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: red_bank_address.to_string(),
+        msg: to_binary(&MarsMsg::Borrow {
+            asset: asset_info.into(),
+            amount,
+            recipient: None,
+        })?,
+        funds: vec![],
+    }))
+}
+
+pub fn mars_withdraw_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    red_bank_address: Addr,
+    asset_info: AssetInfo,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: red_bank_address.to_string(),
+        msg: to_binary(&MarsMsg::Withdraw {
+            asset: asset_info.into(),
+            amount: Some(amount),
+            recipient: None,
+        })?,
+        funds: vec![],
+    }))
+}
@@ -18,4 +18,13 @@ pub enum MemoryError {
 
     #[error("Couldn't find asset or contract in memory")]
     NotFoundInMemory {},
+
+    #[error("{} can't replace a deprecated entry", name)]
+    InvalidReplacement { name: String },
+
+    #[error(
+        "{} can't be removed, other entries are deprecated in favor of it",
+        name
+    )]
+    ReplacementInUse { name: String },
 }
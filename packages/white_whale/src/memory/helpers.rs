@@ -0,0 +1,49 @@
+use cosmwasm_std::{Addr, Binary, Empty, QueryRequest, WasmQuery};
+use cosmwasm_storage::to_length_prefixed;
+
+/// Constructs the raw query of the asset info stored in memory under `asset_name`
+pub fn asset_query(memory_addr: &Addr, asset_name: &str) -> QueryRequest<Empty> {
+    raw_query(memory_addr, b"assets", asset_name)
+}
+
+/// Constructs the raw query of the contract address stored in memory under `contract_name`
+pub fn contract_query(memory_addr: &Addr, contract_name: &str) -> QueryRequest<Empty> {
+    raw_query(memory_addr, b"contracts", contract_name)
+}
+
+/// Returns the storage key of `name` in the memory map stored under `namespace`
+pub(crate) fn map_key(namespace: &[u8], name: &str) -> Binary {
+    let mut key = to_length_prefixed(namespace);
+    key.extend_from_slice(name.as_bytes());
+    Binary::from(key)
+}
+
+fn raw_query(memory_addr: &Addr, namespace: &[u8], name: &str) -> QueryRequest<Empty> {
+    QueryRequest::Wasm(WasmQuery::Raw {
+        contract_addr: memory_addr.to_string(),
+        key: map_key(namespace, name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_target_the_memory_maps() {
+        let memory = Addr::unchecked("memory");
+        match asset_query(&memory, "ust") {
+            QueryRequest::Wasm(WasmQuery::Raw { contract_addr, key }) => {
+                assert_eq!(contract_addr, "memory");
+                assert_eq!(key.as_slice(), b"\x00\x06assetsust");
+            }
+            _ => panic!("unexpected"),
+        }
+        match contract_query(&memory, "treasury") {
+            QueryRequest::Wasm(WasmQuery::Raw { key, .. }) => {
+                assert_eq!(key.as_slice(), b"\x00\x09contractstreasury");
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+}
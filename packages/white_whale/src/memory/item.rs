@@ -7,7 +7,8 @@ use serde::{Deserialize, Serialize};
 use terraswap::asset::AssetInfo;
 
 use super::queries::{
-    query_asset_from_mem, query_assets_from_mem, query_contract_from_mem, query_contracts_from_mem,
+    query_asset_from_mem, query_asset_from_mem_strict, query_assets_from_mem,
+    query_contract_from_mem, query_contract_from_mem_strict, query_contracts_from_mem,
 };
 
 // Struct that holds address
@@ -44,4 +45,14 @@ impl Memory {
     pub fn query_asset(&self, deps: Deps, asset_name: &str) -> StdResult<AssetInfo> {
         query_asset_from_mem(deps, &self.address, asset_name)
     }
+
+    // Raw query of a single contract Addr, errors if the entry is deprecated
+    pub fn query_contract_strict(&self, deps: Deps, contract_name: &str) -> StdResult<Addr> {
+        query_contract_from_mem_strict(deps, &self.address, contract_name)
+    }
+
+    // Raw query of a single AssetInfo, errors if the entry is deprecated
+    pub fn query_asset_strict(&self, deps: Deps, asset_name: &str) -> StdResult<AssetInfo> {
+        query_asset_from_mem_strict(deps, &self.address, asset_name)
+    }
 }
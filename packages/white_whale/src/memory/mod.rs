@@ -1,4 +1,5 @@
 pub mod error;
+pub mod helpers;
 pub mod item;
 pub mod msg;
 pub mod queries;
@@ -19,6 +19,19 @@ pub enum ExecuteMsg {
         to_add: Vec<(String, AssetInfo)>,
         to_remove: Vec<String>,
     },
+    /// Updates the contract and asset addresses in a single message
+    UpdateAddresses {
+        contracts_to_add: Vec<(String, String)>,
+        contracts_to_remove: Vec<String>,
+        assets_to_add: Vec<(String, AssetInfo)>,
+        assets_to_remove: Vec<String>,
+    },
+    /// Deprecates a contract, lookups of `name` resolve to the address of `in_favor_of`
+    /// Updating `name` again clears the deprecation
+    DeprecateContract { name: String, in_favor_of: String },
+    /// Deprecates an asset, lookups of `name` resolve to the asset of `in_favor_of`
+    /// Updating `name` again clears the deprecation
+    DeprecateAsset { name: String, in_favor_of: String },
     /// Sets a new Admin
     SetAdmin { admin: String },
 }
@@ -33,6 +46,29 @@ pub enum QueryMsg {
     QueryContracts {
         names: Vec<String>,
     },
+    /// Returns the names the address is registered under
+    /// Assets match on their contract address or denom
+    ReverseLookup {
+        address: String,
+    },
+    /// Lists the assets ordered by name
+    ListAssets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the contracts ordered by name
+    ListContracts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the version and deprecation of a contract entry
+    ContractEntry {
+        name: String,
+    },
+    /// Returns the version and deprecation of an asset entry
+    AssetEntry {
+        name: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -44,3 +80,17 @@ pub struct AssetQueryResponse {
 pub struct ContractQueryResponse {
     pub contracts: Vec<(String, String)>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct EntryInfo {
+    /// Incremented on every update of the entry
+    pub version: u64,
+    /// Set if the entry is deprecated, the entry resolves to the value of this entry
+    pub deprecated_in_favor_of: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReverseLookupResponse {
+    pub contracts: Vec<String>,
+    pub assets: Vec<String>,
+}
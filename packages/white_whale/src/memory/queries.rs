@@ -1,10 +1,12 @@
 use std::collections::BTreeMap;
 
-use cosmwasm_std::{Addr, Binary, Deps, QueryRequest, StdResult, WasmQuery};
+use cosmwasm_std::{from_slice, Addr, Deps, StdError, StdResult};
 
-use cosmwasm_storage::to_length_prefixed;
 use terraswap::asset::AssetInfo;
 
+use crate::memory::helpers::{asset_query, contract_query, map_key};
+use crate::memory::msg::EntryInfo;
+
 /// Query asset infos from Memory Module asset addresses map.
 pub fn query_assets_from_mem(
     deps: Deps,
@@ -16,11 +18,7 @@ pub fn query_assets_from_mem(
     for asset in asset_names.iter() {
         let result = deps
             .querier
-            .query::<AssetInfo>(&QueryRequest::Wasm(WasmQuery::Raw {
-                contract_addr: memory_addr.to_string(),
-                // query assets map
-                key: Binary::from(concat(&to_length_prefixed(b"assets"), asset.as_bytes())),
-            }))?;
+            .query::<AssetInfo>(&asset_query(memory_addr, asset))?;
         assets.insert(asset.clone(), result);
     }
     Ok(assets)
@@ -32,17 +30,8 @@ pub fn query_asset_from_mem(
     memory_addr: &Addr,
     asset_name: &str,
 ) -> StdResult<AssetInfo> {
-    let result = deps
-        .querier
-        .query::<AssetInfo>(&QueryRequest::Wasm(WasmQuery::Raw {
-            contract_addr: memory_addr.to_string(),
-            // query assets map
-            key: Binary::from(concat(
-                &to_length_prefixed(b"assets"),
-                asset_name.as_bytes(),
-            )),
-        }))?;
-    Ok(result)
+    deps.querier
+        .query::<AssetInfo>(&asset_query(memory_addr, asset_name))
 }
 
 /// Query contract addresses from Memory Module contract addresses map.
@@ -53,19 +42,10 @@ pub fn query_contracts_from_mem(
 ) -> StdResult<BTreeMap<String, Addr>> {
     let mut contracts: BTreeMap<String, Addr> = BTreeMap::new();
 
-    // Query over
     for contract in contract_names.iter() {
-        let result: Addr = deps
+        let result = deps
             .querier
-            .query::<Addr>(&QueryRequest::Wasm(WasmQuery::Raw {
-                contract_addr: memory_addr.to_string(),
-                key: Binary::from(concat(
-                    // Query contracts map
-                    &to_length_prefixed(b"contracts"),
-                    contract.as_bytes(),
-                )),
-            }))?;
-
+            .query::<Addr>(&contract_query(memory_addr, contract))?;
         contracts.insert(contract.clone(), result);
     }
     Ok(contracts)
@@ -79,21 +59,48 @@ pub fn query_contract_from_mem(
 ) -> StdResult<Addr> {
     let result = deps
         .querier
-        .query::<String>(&QueryRequest::Wasm(WasmQuery::Raw {
-            contract_addr: memory_addr.to_string(),
-            // query assets map
-            key: Binary::from(concat(
-                &to_length_prefixed(b"contracts"),
-                contract_name.as_bytes(),
-            )),
-        }))?;
+        .query::<String>(&contract_query(memory_addr, contract_name))?;
     // Addresses are checked when stored.
     Ok(Addr::unchecked(result))
 }
 
-#[inline]
-fn concat(namespace: &[u8], key: &[u8]) -> Vec<u8> {
-    let mut k = namespace.to_vec();
-    k.extend_from_slice(key);
-    k
+/// Query single asset info from mem, erroring if the entry is deprecated
+pub fn query_asset_from_mem_strict(
+    deps: Deps,
+    memory_addr: &Addr,
+    asset_name: &str,
+) -> StdResult<AssetInfo> {
+    assert_not_deprecated(deps, memory_addr, b"asset_entries", asset_name)?;
+    query_asset_from_mem(deps, memory_addr, asset_name)
+}
+
+/// Query single contract address from mem, erroring if the entry is deprecated
+pub fn query_contract_from_mem_strict(
+    deps: Deps,
+    memory_addr: &Addr,
+    contract_name: &str,
+) -> StdResult<Addr> {
+    assert_not_deprecated(deps, memory_addr, b"contract_entries", contract_name)?;
+    query_contract_from_mem(deps, memory_addr, contract_name)
+}
+
+/// Errors if the entry is deprecated, entries without entry info are not deprecated
+fn assert_not_deprecated(
+    deps: Deps,
+    memory_addr: &Addr,
+    namespace: &[u8],
+    name: &str,
+) -> StdResult<()> {
+    let entry = deps
+        .querier
+        .query_wasm_raw(memory_addr, map_key(namespace, name))?;
+    if let Some(entry) = entry {
+        if let Some(replacement) = from_slice::<EntryInfo>(&entry)?.deprecated_in_favor_of {
+            return Err(StdError::generic_err(format!(
+                "{} is deprecated in favor of {}",
+                name, replacement
+            )));
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,200 @@
+use std::fmt;
+
+use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult, Storage};
+use cw_controllers::{Admin, AdminError};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CommonError;
+
+/// Emergency switch, a paused contract only accepts the Unpause message
+pub const PAUSED: Item<bool> = Item::new("paused");
+/// Function scopes paused on their own, as bitflags of [`PauseScope`]
+pub const PAUSED_SCOPES: Item<u8> = Item::new("paused_scopes");
+
+/// Group of functions which can be paused while the rest of the contract keeps working
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseScope {
+    Deposits,
+    Withdrawals,
+    Trading,
+    Polls,
+}
+
+impl PauseScope {
+    pub const ALL: [PauseScope; 4] = [
+        PauseScope::Deposits,
+        PauseScope::Withdrawals,
+        PauseScope::Trading,
+        PauseScope::Polls,
+    ];
+
+    fn flag(&self) -> u8 {
+        match self {
+            PauseScope::Deposits => 1,
+            PauseScope::Withdrawals => 1 << 1,
+            PauseScope::Trading => 1 << 2,
+            PauseScope::Polls => 1 << 3,
+        }
+    }
+}
+
+impl fmt::Display for PauseScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PauseScope::Deposits => write!(f, "deposits"),
+            PauseScope::Withdrawals => write!(f, "withdrawals"),
+            PauseScope::Trading => write!(f, "trading"),
+            PauseScope::Polls => write!(f, "polls"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PausedScopesResponse {
+    /// Whether the whole contract is paused
+    pub paused: bool,
+    pub scopes: Vec<PauseScope>,
+}
+
+pub fn is_paused(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(PAUSED.may_load(storage)?.unwrap_or_default())
+}
+
+/// Fails if the contract or the given scope is paused
+pub fn assert_not_paused(storage: &dyn Storage, scope: PauseScope) -> Result<(), CommonError> {
+    if is_paused(storage)? {
+        return Err(CommonError::Paused {});
+    }
+    if PAUSED_SCOPES.may_load(storage)?.unwrap_or_default() & scope.flag() != 0 {
+        return Err(CommonError::ScopePaused { scope });
+    }
+    Ok(())
+}
+
+/// Returns the paused scopes
+pub fn query_paused_scopes(storage: &dyn Storage) -> StdResult<PausedScopesResponse> {
+    let flags = PAUSED_SCOPES.may_load(storage)?.unwrap_or_default();
+    Ok(PausedScopesResponse {
+        paused: is_paused(storage)?,
+        scopes: PauseScope::ALL
+            .iter()
+            .filter(|scope| flags & scope.flag() != 0)
+            .copied()
+            .collect(),
+    })
+}
+
+/// Pauses or unpauses the contract, only callable by the admin
+pub fn set_paused<C>(
+    deps: DepsMut,
+    info: &MessageInfo,
+    admin_store: &Admin,
+    paused: bool,
+) -> Result<Response<C>, AdminError>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema,
+{
+    admin_store.assert_admin(deps.as_ref(), &info.sender)?;
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::new().add_attribute("action", if paused { "pause" } else { "unpause" }))
+}
+
+/// Pauses or unpauses the given scopes, only callable by the admin
+pub fn set_paused_scopes<C>(
+    deps: DepsMut,
+    info: &MessageInfo,
+    admin_store: &Admin,
+    scopes: Vec<PauseScope>,
+    paused: bool,
+) -> Result<Response<C>, AdminError>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema,
+{
+    admin_store.assert_admin(deps.as_ref(), &info.sender)?;
+    Ok(update_paused_scopes(deps.storage, scopes, paused)?)
+}
+
+/// Pauses or unpauses the given scopes, the caller has to be authorized beforehand
+pub fn update_paused_scopes<C>(
+    storage: &mut dyn Storage,
+    scopes: Vec<PauseScope>,
+    paused: bool,
+) -> StdResult<Response<C>>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema,
+{
+    let mut flags = PAUSED_SCOPES.may_load(storage)?.unwrap_or_default();
+    for scope in scopes.iter() {
+        if paused {
+            flags |= scope.flag();
+        } else {
+            flags &= !scope.flag();
+        }
+    }
+    PAUSED_SCOPES.save(storage, &flags)?;
+
+    Ok(Response::new()
+        .add_attribute(
+            "action",
+            if paused {
+                "pause_scopes"
+            } else {
+                "unpause_scopes"
+            },
+        )
+        .add_attribute(
+            "scopes",
+            scopes
+                .iter()
+                .map(|scope| scope.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::Empty;
+
+    use super::*;
+
+    #[test]
+    fn scopes_pause_independently() {
+        let mut storage = MockStorage::new();
+        assert_eq!(assert_not_paused(&storage, PauseScope::Deposits), Ok(()));
+
+        update_paused_scopes::<Empty>(
+            &mut storage,
+            vec![PauseScope::Deposits, PauseScope::Polls],
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            assert_not_paused(&storage, PauseScope::Deposits),
+            Err(CommonError::ScopePaused {
+                scope: PauseScope::Deposits
+            })
+        );
+        assert_eq!(assert_not_paused(&storage, PauseScope::Withdrawals), Ok(()));
+
+        update_paused_scopes::<Empty>(&mut storage, vec![PauseScope::Deposits], false).unwrap();
+        assert_eq!(
+            query_paused_scopes(&storage).unwrap(),
+            PausedScopesResponse {
+                paused: false,
+                scopes: vec![PauseScope::Polls],
+            }
+        );
+
+        PAUSED.save(&mut storage, &true).unwrap();
+        assert_eq!(
+            assert_not_paused(&storage, PauseScope::Withdrawals),
+            Err(CommonError::Paused {})
+        );
+    }
+}
@@ -1,21 +1,41 @@
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, Decimal, StdResult, WasmMsg};
+use cosmwasm_std::{Decimal, Uint128};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::fmt;
 
 use terraswap::asset::{Asset, AssetInfo};
 
+pub use crate::flashloan::msg::BorrowerCallbackMsg as CallbackMsg;
+use crate::pause::PauseScope;
+pub use crate::swap::Dex;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub vault_address: String,
     pub seignorage_address: String,
     pub asset_info: AssetInfo,
+    /// Receiver of the treasury share of the arb profits
+    pub treasury_address: String,
+    /// Smallest profit, in the base asset, an arb has to make
+    pub min_profit: Uint128,
+    /// How the profit of an arb is split
+    pub profit_split: ProfitSplit,
+}
+
+/// Shares of the arb profit in basis points, they sum up to 10_000. The caller share goes to the
+/// treasury when there is no caller.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProfitSplit {
+    pub caller_bps: u64,
+    pub treasury_bps: u64,
+    pub vault_bps: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    /// Borrows `details.asset` from the vault and arbs it, anyone can call this
+    /// and gets the caller share of the profit
     ExecuteArb {
         details: ArbDetails,
         above_peg: bool,
@@ -26,52 +46,142 @@ pub enum ExecuteMsg {
     BelowPegCallback {
         details: ArbDetails,
     },
+    /// Deprecated, proposes the admin like ProposeAdmin with the default expiry
     SetAdmin {
         admin: String,
     },
+    /// Proposes a new admin, who has to accept within `expires_in` blocks
+    ProposeAdmin {
+        admin: String,
+        expires_in: u64,
+    },
+    /// Accepts the pending admin transfer
+    AcceptAdmin {},
+    /// Pauses the contract, only the admin can call this
+    Pause {},
+    /// Unpauses the contract, only the admin can call this
+    Unpause {},
+    /// Pauses the given function scopes while the rest of the contract keeps working, only the admin can call this
+    PauseScopes {
+        scopes: Vec<PauseScope>,
+    },
+    /// Unpauses the given function scopes, only the admin can call this
+    UnpauseScopes {
+        scopes: Vec<PauseScope>,
+    },
+    /// Adds pools to or removes pools from the registry, only the admin can call this
     UpdatePools {
-        to_add: Option<Vec<(String, String)>>,
+        to_add: Option<Vec<(String, PoolConfig)>>,
         to_remove: Option<Vec<String>>,
     },
     SetVault {
         vault: String,
     },
+    /// Updates the treasury, minimum profit, profit split and the smallest amount that can be
+    /// borrowed for an arb, only the admin can call this
+    UpdateConfig {
+        treasury_address: Option<String>,
+        min_profit: Option<Uint128>,
+        profit_split: Option<ProfitSplit>,
+        min_arb_amount: Option<Uint128>,
+    },
+    /// Switches the arb to another native stable `denom`, swapped with `market_denom`, luna if
+    /// not set, on the market module. The minimum arb amount and profit are in the new denom.
+    /// The pools trading the new pair are registered with UpdatePools, only the admin can call this
+    SetArbAsset {
+        denom: String,
+        market_denom: Option<String>,
+        min_arb_amount: Uint128,
+        min_profit: Uint128,
+    },
     Callback(CallbackMsg),
 }
 
-// Modified from
-// https://github.com/CosmWasm/cosmwasm-plus/blob/v0.2.3/packages/cw20/src/receiver.rs#L15
-impl CallbackMsg {
-    pub fn to_cosmos_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
-        &self,
-        contract_addr: &Addr,
-    ) -> StdResult<CosmosMsg<T>> {
-        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: String::from(contract_addr),
-            msg: to_binary(&ExecuteMsg::Callback(self.clone()))?,
-            funds: vec![],
-        }))
-    }
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum CallbackMsg {
-    AfterSuccessfulTradeCallback {},
-}
-
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ArbDetails {
     pub asset: Asset,
     pub slippage: Decimal,
     pub belief_price: Decimal,
-    pub pool_name: String,
+    /// Registered pool to trade on, the pool with the best quote is used if not set
+    pub pool_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolConfig {
+    pub address: String,
+    pub dex: Dex,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Config {},
+    /// Returns the paused function scopes
+    PausedScopes {},
+    /// Returns the [StateResponse]
+    State {},
+    /// Returns the registered pools
+    Pools {},
+    /// Returns the [ArbStats]
+    ArbStats {},
+    /// Returns the [SimulateArbResponse] of an arb borrowing `amount`,
+    /// the pool with the best quote is used if `pool` is not set
+    SimulateArb {
+        amount: Uint128,
+        above_peg: bool,
+        pool: Option<String>,
+    },
+}
+
+/// Expected outcome of an arb, amounts are in the base asset unless noted otherwise
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateArbResponse {
+    /// Pool the arb trades on
+    pub pool: String,
+    /// Luna, or the market denom, bought in the first swap
+    pub luna_amount: Uint128,
+    /// Amount received from the second swap
+    pub return_amount: Uint128,
+    /// Flash loan fee charged by the vault
+    pub loan_fee: Uint128,
+    /// Profit after repaying the loan, fee and taxes, zero if the arb loses money
+    pub profit: Uint128,
+    /// Part of the profit paid to the caller
+    pub caller_reward: Uint128,
+    /// Part of the profit sent to the treasury
+    pub treasury_profit: Uint128,
+    /// Part of the profit sent to the vault
+    pub vault_profit: Uint128,
+    /// Whether the profit reaches the minimum profit, ExecuteArb fails otherwise
+    pub executable: bool,
+}
+
+/// Totals of all arbs executed, amounts are in the base asset
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ArbStats {
+    pub arb_count: u64,
+    /// Total amount borrowed from the vault
+    pub volume: Uint128,
+    /// Total profit, including the caller rewards
+    pub profit: Uint128,
+    /// Total rewards paid to callers
+    pub caller_rewards: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolsResponse {
+    pub pools: Vec<(String, PoolConfig)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub vault_address: String,
+    pub seignorage_address: String,
+    pub treasury_address: String,
+    pub min_profit: Uint128,
+    pub profit_split: ProfitSplit,
+    /// Denom the arb denom is swapped with on the market module
+    pub market_denom: String,
 }
 
 /// MigrateMsg allows a privileged contract administrator to run
@@ -82,4 +192,11 @@ pub enum QueryMsg {
 /// Note that the contract doesn't enforce permissions here, this is done
 /// by blockchain logic (in the future by blockchain governance)
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// Receiver of the arb profits, required when migrating a state without a treasury
+    pub treasury_address: Option<String>,
+    /// Smallest profit an arb has to make when migrating a state without one, zero if not set
+    pub min_profit: Option<Uint128>,
+    /// Split of the profit when migrating a state without one, all to the treasury if not set
+    pub profit_split: Option<ProfitSplit>,
+}
@@ -1,17 +1,31 @@
 use std::fmt;
 
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
 use cw20::Cw20ExecuteMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::denom::LUNA_DENOM;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PrismMsg {
+    Bond {},
     Unbond {},
     WithdrawUnbonded {},
 }
 
+pub fn prism_cluna_bond_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    cluna_hub_address: Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: cluna_hub_address.to_string(),
+        msg: to_binary(&PrismMsg::Bond {})?,
+        funds: vec![Coin::new(amount.u128(), LUNA_DENOM)],
+    }))
+}
+
 pub fn prism_cluna_unbond_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
     cluna_address: Addr,
     cluna_hub_address: Addr,
@@ -18,6 +18,11 @@ pub enum AnchorQuery {
     WithdrawableUnbonded {
         address: String,
     },
+    AllHistory {
+        start_from: Option<u64>,
+        limit: Option<u32>,
+    },
+    State {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -39,6 +44,28 @@ pub struct WithdrawableUnbondedResponse {
     pub withdrawable: Uint128,
 }
 
+/// State of the bLuna hub, only the fields used by White Whale are listed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HubStateResponse {
+    pub bluna_exchange_rate: Decimal,
+}
+
+/// Unbond batch of the bLuna hub
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondHistory {
+    pub batch_id: u64,
+    pub time: u64,
+    pub amount: Uint128,
+    pub applied_exchange_rate: Decimal,
+    pub withdraw_rate: Decimal,
+    pub released: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllHistoryResponse {
+    pub history: Vec<UnbondHistory>,
+}
+
 pub fn query_aust_exchange_rate(
     env: Env,
     deps: Deps,
@@ -71,6 +98,30 @@ pub fn query_unbond_requests(
     Ok(response)
 }
 
+pub fn query_bluna_exchange_rate(deps: Deps, bluna_hub_address: Addr) -> StdResult<Decimal> {
+    let response: HubStateResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: bluna_hub_address.to_string(),
+        msg: to_binary(&AnchorQuery::State {})?,
+    }))?;
+
+    Ok(response.bluna_exchange_rate)
+}
+
+pub fn query_all_history(
+    deps: Deps,
+    bluna_hub_address: Addr,
+    start_from: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<AllHistoryResponse> {
+    let response: AllHistoryResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: bluna_hub_address.to_string(),
+            msg: to_binary(&AnchorQuery::AllHistory { start_from, limit })?,
+        }))?;
+
+    Ok(response)
+}
+
 pub fn query_withdrawable_unbonded(
     deps: Deps,
     bluna_hub_address: Addr,
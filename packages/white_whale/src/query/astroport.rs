@@ -2,13 +2,17 @@ use cosmwasm_std::{
     to_binary, Addr, Coin, Decimal, Deps, QueryRequest, StdResult, Uint128, WasmQuery,
 };
 use terraswap::asset::{Asset, AssetInfo, PairInfo};
-use terraswap::pair::{PoolResponse, QueryMsg, SimulationResponse};
+use terraswap::pair::{PoolResponse, QueryMsg};
+
+use crate::astroport_helper::{
+    QueryMsg as AstroportQueryMsg, SimulationResponse as AstroportSimulationResponse,
+};
 
 pub fn simulate_swap(deps: Deps, pool_address: Addr, offer_coin: Coin) -> StdResult<Uint128> {
-    let response: SimulationResponse =
+    let response: AstroportSimulationResponse =
         deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: pool_address.to_string(),
-            msg: to_binary(&QueryMsg::Simulation {
+            msg: to_binary(&AstroportQueryMsg::Simulation {
                 offer_asset: Asset {
                     info: AssetInfo::NativeToken {
                         denom: offer_coin.denom,
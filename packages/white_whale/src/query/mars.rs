@@ -0,0 +1,34 @@
+use cosmwasm_std::{to_binary, Addr, Deps, QueryRequest, StdResult, Uint128, WasmQuery};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MarsQuery {
+    UserPosition { address: String },
+}
+
+/// Position of a red bank user, only the fields used by White Whale are listed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserPositionResponse {
+    pub total_collateral_in_uusd: Uint128,
+    pub total_debt_in_uusd: Uint128,
+    /// Collateral value weighted by the liquidation threshold of each asset
+    pub weighted_liquidation_threshold_in_uusd: Uint128,
+}
+
+pub fn query_user_position(
+    deps: Deps,
+    red_bank_address: Addr,
+    address: Addr,
+) -> StdResult<UserPositionResponse> {
+    let response: UserPositionResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: red_bank_address.to_string(),
+            msg: to_binary(&MarsQuery::UserPosition {
+                address: address.to_string(),
+            })?,
+        }))?;
+
+    Ok(response)
+}
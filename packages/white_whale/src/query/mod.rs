@@ -1,5 +1,7 @@
 pub mod anchor;
 pub mod astroport;
+pub mod mars;
 pub mod prism;
+pub mod stader;
 pub mod terraswap;
 pub mod vault;
@@ -1,4 +1,4 @@
-use cosmwasm_std::{to_binary, Addr, Deps, QueryRequest, StdResult, Uint128, WasmQuery};
+use cosmwasm_std::{to_binary, Addr, Decimal, Deps, QueryRequest, StdResult, Uint128, WasmQuery};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 pub enum PrismQuery {
     UnbondRequests { address: String },
     WithdrawableUnbonded { address: String },
+    State {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -22,6 +23,12 @@ pub struct WithdrawableUnbondedResponse {
     pub withdrawable: Uint128,
 }
 
+/// State of the cLuna hub, only the fields used by White Whale are listed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HubStateResponse {
+    pub exchange_rate: Decimal,
+}
+
 pub fn query_unbond_requests(
     deps: Deps,
     cluna_hub_address: Addr,
@@ -53,3 +60,12 @@ pub fn query_withdrawable_unbonded(
 
     Ok(response)
 }
+
+pub fn query_cluna_exchange_rate(deps: Deps, cluna_hub_address: Addr) -> StdResult<Decimal> {
+    let response: HubStateResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: cluna_hub_address.to_string(),
+        msg: to_binary(&PrismQuery::State {})?,
+    }))?;
+
+    Ok(response.exchange_rate)
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::{to_binary, Addr, Decimal, Deps, QueryRequest, StdResult, WasmQuery};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StaderQuery {
+    State {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HubStateResponse {
+    pub state: HubState,
+}
+
+/// State of the LunaX hub, only the fields used by White Whale are listed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HubState {
+    pub exchange_rate: Decimal,
+}
+
+pub fn query_lunax_exchange_rate(deps: Deps, lunax_hub_address: Addr) -> StdResult<Decimal> {
+    let response: HubStateResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: lunax_hub_address.to_string(),
+        msg: to_binary(&StaderQuery::State {})?,
+    }))?;
+
+    Ok(response.state.exchange_rate)
+}
@@ -1,6 +1,6 @@
 use cosmwasm_std::{to_binary, Addr, Deps, QueryRequest, StdResult, Uint128, WasmQuery};
 
-use crate::treasury::msg::{QueryMsg, TotalValueResponse};
+use crate::treasury::msg::{HoldingValueResponse, QueryMsg, TotalValueResponse};
 
 /// Query the total value denominated in the vault base asset
 /// The provided address must implement the TotalValue Query
@@ -13,3 +13,23 @@ pub fn query_total_value(deps: Deps, vault_address: &Addr) -> StdResult<Uint128>
 
     Ok(response.value)
 }
+
+/// Query the value of `amount` of an asset denominated in the vault base asset
+/// The provided address must implement the HoldingValue Query
+pub fn query_holding_value(
+    deps: Deps,
+    vault_address: &Addr,
+    identifier: String,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let response: HoldingValueResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: vault_address.to_string(),
+            msg: to_binary(&QueryMsg::HoldingValue {
+                identifier,
+                amount: Some(amount),
+            })?,
+        }))?;
+
+    Ok(response.value)
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::denom::LUNA_DENOM;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StaderMsg {
+    Deposit {},
+    QueueUndelegate {},
+}
+
+pub fn stader_lunax_bond_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    lunax_hub_address: Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: lunax_hub_address.to_string(),
+        msg: to_binary(&StaderMsg::Deposit {})?,
+        funds: vec![Coin::new(amount.u128(), LUNA_DENOM)],
+    }))
+}
+
+pub fn stader_lunax_unbond_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    lunax_address: Addr,
+    lunax_hub_address: Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: lunax_address.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: lunax_hub_address.to_string(),
+            amount,
+            msg: to_binary(&StaderMsg::QueueUndelegate {})?,
+        })?,
+        funds: vec![],
+    }))
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Decimal, Deps, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terraswap::asset::{Asset, AssetInfo};
+use terraswap::pair::{Cw20HookMsg as TerraswapCw20HookMsg, ExecuteMsg as TerraswapExecuteMsg};
+
+use crate::astroport_helper::{
+    Cw20HookMsg as AstroportCw20HookMsg, ExecuteMsg as AstroportExecuteMsg,
+};
+use crate::query::{astroport as astroport_query, terraswap as terraswap_query};
+
+/// The exchange a pair is deployed on
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Dex {
+    Terraswap,
+    Astroport,
+    /// Loop pairs are terraswap forks and share its interface
+    Loop,
+}
+
+/// Builds the msg swapping `offer_asset` on `pair`.
+/// Native offers are attached as funds, cw20 offers are sent to the pair with a swap hook.
+/// The transfer tax of native offers is not deducted, do so before building the swap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Swap {
+    pub dex: Dex,
+    pub pair: String,
+    pub offer_asset: Asset,
+    pub belief_price: Option<Decimal>,
+    pub max_spread: Option<Decimal>,
+    pub to: Option<String>,
+}
+
+impl Swap {
+    pub fn new(dex: Dex, pair: impl Into<String>, offer_asset: Asset) -> Self {
+        Swap {
+            dex,
+            pair: pair.into(),
+            offer_asset,
+            belief_price: None,
+            max_spread: None,
+            to: None,
+        }
+    }
+
+    /// Swaps a native coin
+    pub fn native(dex: Dex, pair: impl Into<String>, offer: Coin) -> Self {
+        Swap::new(
+            dex,
+            pair,
+            Asset {
+                info: AssetInfo::NativeToken { denom: offer.denom },
+                amount: offer.amount,
+            },
+        )
+    }
+
+    pub fn belief_price(mut self, belief_price: Option<Decimal>) -> Self {
+        self.belief_price = belief_price;
+        self
+    }
+
+    pub fn max_spread(mut self, max_spread: Option<Decimal>) -> Self {
+        self.max_spread = max_spread;
+        self
+    }
+
+    /// Sends the returned asset to `to` instead of the sender
+    pub fn to(mut self, to: impl Into<String>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    pub fn into_msg<C>(self) -> StdResult<CosmosMsg<C>>
+    where
+        C: Clone + fmt::Debug + PartialEq + JsonSchema,
+    {
+        match &self.offer_asset.info {
+            AssetInfo::NativeToken { denom } => {
+                let funds = vec![Coin::new(self.offer_asset.amount.u128(), denom)];
+                let msg = match self.dex {
+                    Dex::Terraswap | Dex::Loop => to_binary(&TerraswapExecuteMsg::Swap {
+                        offer_asset: self.offer_asset,
+                        belief_price: self.belief_price,
+                        max_spread: self.max_spread,
+                        to: self.to,
+                    })?,
+                    Dex::Astroport => to_binary(&AstroportExecuteMsg::Swap {
+                        offer_asset: self.offer_asset,
+                        belief_price: self.belief_price,
+                        max_spread: self.max_spread,
+                        to: self.to,
+                    })?,
+                };
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: self.pair,
+                    msg,
+                    funds,
+                }))
+            }
+            AssetInfo::Token { contract_addr } => {
+                let hook = match self.dex {
+                    Dex::Terraswap | Dex::Loop => to_binary(&TerraswapCw20HookMsg::Swap {
+                        belief_price: self.belief_price,
+                        max_spread: self.max_spread,
+                        to: self.to,
+                    })?,
+                    Dex::Astroport => to_binary(&AstroportCw20HookMsg::Swap {
+                        belief_price: self.belief_price,
+                        max_spread: self.max_spread,
+                        to: self.to,
+                    })?,
+                };
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Send {
+                        contract: self.pair,
+                        amount: self.offer_asset.amount,
+                        msg: hook,
+                    })?,
+                    funds: vec![],
+                }))
+            }
+        }
+    }
+}
+
+/// Returns the amount swapping the native `offer_coin` on `pair` returns, quoted with the
+/// simulation query of `dex`
+pub fn simulate_native_swap(
+    deps: Deps,
+    dex: &Dex,
+    pair: Addr,
+    offer_coin: Coin,
+) -> StdResult<Uint128> {
+    match dex {
+        Dex::Terraswap | Dex::Loop => terraswap_query::simulate_swap(deps, pair, offer_coin),
+        Dex::Astroport => astroport_query::simulate_swap(deps, pair, offer_coin),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{Empty, Uint128};
+
+    const PAIR: &str = "pair";
+
+    #[test]
+    fn test_native_swap() {
+        let msg: CosmosMsg<Empty> = Swap::native(Dex::Terraswap, PAIR, Coin::new(100, "uusd"))
+            .belief_price(Some(Decimal::percent(50)))
+            .max_spread(Some(Decimal::percent(1)))
+            .into_msg()
+            .unwrap();
+
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: PAIR.to_string(),
+                msg: to_binary(&TerraswapExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uusd".to_string(),
+                        },
+                        amount: Uint128::from(100u64),
+                    },
+                    belief_price: Some(Decimal::percent(50)),
+                    max_spread: Some(Decimal::percent(1)),
+                    to: None,
+                })
+                .unwrap(),
+                funds: vec![Coin::new(100, "uusd")],
+            })
+        );
+    }
+
+    #[test]
+    fn test_cw20_swap() {
+        let offer_asset = Asset {
+            info: AssetInfo::Token {
+                contract_addr: "token".to_string(),
+            },
+            amount: Uint128::from(100u64),
+        };
+        let msg: CosmosMsg<Empty> = Swap::new(Dex::Astroport, PAIR, offer_asset)
+            .to("receiver")
+            .into_msg()
+            .unwrap();
+
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: PAIR.to_string(),
+                    amount: Uint128::from(100u64),
+                    msg: to_binary(&AstroportCw20HookMsg::Swap {
+                        belief_price: None,
+                        max_spread: None,
+                        to: Some("receiver".to_string()),
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn test_dexes_share_the_pair_interface() {
+        let offer = Coin::new(100, "uluna");
+        let terraswap: CosmosMsg<Empty> = Swap::native(Dex::Terraswap, PAIR, offer.clone())
+            .into_msg()
+            .unwrap();
+        let astroport: CosmosMsg<Empty> = Swap::native(Dex::Astroport, PAIR, offer.clone())
+            .into_msg()
+            .unwrap();
+        let loop_msg: CosmosMsg<Empty> = Swap::native(Dex::Loop, PAIR, offer).into_msg().unwrap();
+
+        assert_eq!(terraswap, astroport);
+        assert_eq!(terraswap, loop_msg);
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+
+/// Constructs the message minting `amount` of `token` to `recipient`
+pub fn mint_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    token: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    token_msg(
+        token,
+        &Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount,
+        },
+    )
+}
+
+/// Constructs the message burning `amount` of `token` held by the sender
+pub fn burn_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    token: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    token_msg(token, &Cw20ExecuteMsg::Burn { amount })
+}
+
+/// Constructs the message transferring `amount` of `token` from `owner` to `recipient` using the
+/// allowance `owner` gave the sender
+pub fn transfer_from_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    token: &Addr,
+    owner: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    token_msg(
+        token,
+        &Cw20ExecuteMsg::TransferFrom {
+            owner: owner.to_string(),
+            recipient: recipient.to_string(),
+            amount,
+        },
+    )
+}
+
+fn token_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
+    token: &Addr,
+    msg: &Cw20ExecuteMsg,
+) -> StdResult<CosmosMsg<T>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(msg)?,
+        funds: vec![],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{from_binary, Empty};
+
+    use super::*;
+
+    #[test]
+    fn token_msgs_target_the_token() {
+        let token = Addr::unchecked("token");
+        let msg: CosmosMsg<Empty> = burn_msg(&token, Uint128::new(10)).unwrap();
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                assert_eq!(contract_addr, "token");
+                assert!(funds.is_empty());
+                assert_eq!(
+                    from_binary::<Cw20ExecuteMsg>(&msg).unwrap(),
+                    Cw20ExecuteMsg::Burn {
+                        amount: Uint128::new(10)
+                    }
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Deps, DepsMut, Env, Response, StdResult};
+use terraswap::asset::AssetInfo;
+
+use crate::memory::item::Memory;
+use crate::treasury::dapp_base::common::{BaseDAppResult, ASSET_CACHE_TTL};
+use crate::treasury::dapp_base::state::{CachedAsset, ASSET_CACHE};
+
+/// Returns the info of a memory asset, from the cache if it was refreshed within ASSET_CACHE_TTL
+/// blocks. A stale cache entry is used if memory can't be queried.
+pub fn query_cached_asset(
+    deps: Deps,
+    env: &Env,
+    memory: &Memory,
+    asset_name: &str,
+) -> StdResult<AssetInfo> {
+    let cached = ASSET_CACHE.may_load(deps.storage, asset_name)?;
+    match cached {
+        Some(cached) if env.block.height <= cached.cached_at + ASSET_CACHE_TTL => Ok(cached.info),
+        cached => match memory.query_asset(deps, asset_name) {
+            Ok(info) => Ok(info),
+            Err(err) => cached.map(|cached| cached.info).ok_or(err),
+        },
+    }
+}
+
+/// Returns the infos of several memory assets, see [query_cached_asset]
+pub fn query_cached_assets(
+    deps: Deps,
+    env: &Env,
+    memory: &Memory,
+    asset_names: &[String],
+) -> StdResult<BTreeMap<String, AssetInfo>> {
+    asset_names
+        .iter()
+        .map(|name| Ok((name.clone(), query_cached_asset(deps, env, memory, name)?)))
+        .collect()
+}
+
+/// Queries the given assets from memory and caches them at the current height
+pub fn refresh_cache(
+    deps: DepsMut,
+    env: &Env,
+    memory: &Memory,
+    keys: Vec<String>,
+) -> BaseDAppResult {
+    for key in keys.iter() {
+        let info = memory.query_asset(deps.as_ref(), key)?;
+        ASSET_CACHE.save(
+            deps.storage,
+            key,
+            &CachedAsset {
+                info,
+                cached_at: env.block.height,
+            },
+        )?;
+    }
+    Ok(Response::new().add_attribute("Refreshed:", keys.join(",")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::treasury::dapp_base::common_test::MEMORY_CONTRACT;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::Addr;
+
+    fn whale() -> AssetInfo {
+        AssetInfo::Token {
+            contract_addr: "whale_token".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cached_asset_fallback() {
+        let mut deps = mock_dependencies(&[]);
+        // The mock memory contract doesn't exist so every memory query fails
+        let memory = Memory {
+            address: Addr::unchecked(MEMORY_CONTRACT),
+        };
+        let mut env = mock_env();
+        assert!(query_cached_asset(deps.as_ref(), &env, &memory, "whale").is_err());
+
+        ASSET_CACHE
+            .save(
+                deps.as_mut().storage,
+                "whale",
+                &CachedAsset {
+                    info: whale(),
+                    cached_at: env.block.height,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            query_cached_asset(deps.as_ref(), &env, &memory, "whale").unwrap(),
+            whale()
+        );
+
+        // Stale entries are still used when memory can't be queried
+        env.block.height += ASSET_CACHE_TTL + 1;
+        assert_eq!(
+            query_cached_asset(deps.as_ref(), &env, &memory, "whale").unwrap(),
+            whale()
+        );
+        assert!(refresh_cache(deps.as_mut(), &env, &memory, vec!["whale".to_string()]).is_err());
+    }
+}
@@ -1,7 +1,10 @@
-use cosmwasm_std::{Deps, DepsMut, MessageInfo, Response, StdResult};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
 
+use crate::address::validate_addr;
 use crate::memory::item::Memory;
+use crate::treasury::dapp_base::cache::refresh_cache;
 use crate::treasury::dapp_base::common::BaseDAppResult;
+use crate::treasury::dapp_base::error::BaseDAppError;
 use crate::treasury::dapp_base::msg::{BaseExecuteMsg, BaseInstantiateMsg};
 use crate::treasury::dapp_base::state::{ADMIN, BASESTATE};
 
@@ -10,6 +13,7 @@ use super::state::BaseState;
 /// Handles the common base execute messages
 pub fn handle_base_message(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     message: BaseExecuteMsg,
 ) -> BaseDAppResult {
@@ -20,19 +24,23 @@ pub fn handle_base_message(
             memory,
         } => update_config(deps, info, treasury_address, trader, memory),
         BaseExecuteMsg::SetAdmin { admin } => set_admin(deps, info, admin),
+        BaseExecuteMsg::RefreshCache { keys } => {
+            let memory = BASESTATE.load(deps.storage)?.memory;
+            refresh_cache(deps, &env, &memory, keys)
+        }
     }
 }
 
 /// Handles creates the State and Memory object and returns them.
-pub fn handle_base_init(deps: Deps, msg: BaseInstantiateMsg) -> StdResult<BaseState> {
+pub fn handle_base_init(deps: Deps, msg: BaseInstantiateMsg) -> Result<BaseState, BaseDAppError> {
     // Memory
     let memory = Memory {
-        address: deps.api.addr_validate(&msg.memory_addr)?,
+        address: validate_addr(deps.api, "memory_addr", &msg.memory_addr)?,
     };
     // Base state
     let state = BaseState {
-        treasury_address: deps.api.addr_validate(&msg.treasury_address)?,
-        trader: deps.api.addr_validate(&msg.trader)?,
+        treasury_address: validate_addr(deps.api, "treasury_address", &msg.treasury_address)?,
+        trader: validate_addr(deps.api, "trader", &msg.trader)?,
         memory,
     };
 
@@ -57,15 +65,15 @@ pub fn update_config(
     let mut state = BASESTATE.load(deps.storage)?;
 
     if let Some(treasury_address) = treasury_address {
-        state.treasury_address = deps.api.addr_validate(treasury_address.as_str())?;
+        state.treasury_address = validate_addr(deps.api, "treasury_address", &treasury_address)?;
     }
 
     if let Some(trader) = trader {
-        state.trader = deps.api.addr_validate(trader.as_str())?;
+        state.trader = validate_addr(deps.api, "trader", &trader)?;
     }
 
     if let Some(memory) = memory {
-        state.memory.address = deps.api.addr_validate(memory.as_str())?;
+        state.memory.address = validate_addr(deps.api, "memory", &memory)?;
     }
 
     BASESTATE.save(deps.storage, &state)?;
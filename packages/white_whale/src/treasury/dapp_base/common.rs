@@ -5,5 +5,10 @@ use cosmwasm_std::Response;
 pub const PAIR_POSTFIX: &str = "_pair";
 pub const ANCHOR_MONEY_MARKET_ID: &str = "anchor_money_market";
 pub const AUST_TOKEN_ID: &str = "aUST";
+pub const ASTROPORT_GENERATOR_ID: &str = "astroport_generator";
+pub const ASTRO_TOKEN_ID: &str = "astro";
+pub const MARS_RED_BANK_ID: &str = "mars_red_bank";
+/// Number of blocks a cached memory asset is used before it is queried again, about a day
+pub const ASSET_CACHE_TTL: u64 = 14_400;
 
 pub type BaseDAppResult = Result<Response, BaseDAppError>;
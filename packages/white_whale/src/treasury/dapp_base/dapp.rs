@@ -0,0 +1,63 @@
+use cosmwasm_std::{
+    Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+
+use crate::treasury::dapp_base::commands::{handle_base_init, handle_base_message};
+use crate::treasury::dapp_base::error::BaseDAppError;
+use crate::treasury::dapp_base::msg::{BaseExecuteMsg, BaseInstantiateMsg, BaseQueryMsg};
+use crate::treasury::dapp_base::queries::handle_base_query;
+use crate::treasury::dapp_base::state::{BaseState, ADMIN, BASESTATE};
+use crate::treasury::helpers::trader_action;
+
+/// Standard routing of the base messages shared by all treasury dapps.
+/// A dapp implements this on a unit struct and calls the provided functions from its entry points,
+/// only the error type has to be specified.
+pub trait DAppBase {
+    type Error: From<BaseDAppError> + From<StdError>;
+
+    /// Saves the base state and sets the sender as admin
+    fn instantiate_base(
+        deps: DepsMut,
+        info: &MessageInfo,
+        msg: BaseInstantiateMsg,
+    ) -> Result<BaseState, Self::Error> {
+        let base_state = handle_base_init(deps.as_ref(), msg)?;
+        BASESTATE.save(deps.storage, &base_state)?;
+        ADMIN.set(deps, Some(info.sender.clone()))?;
+        Ok(base_state)
+    }
+
+    /// Handles SetAdmin, UpdateConfig and RefreshCache
+    fn execute_base(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: BaseExecuteMsg,
+    ) -> Result<Response, Self::Error> {
+        Ok(handle_base_message(deps, env, info, msg)?)
+    }
+
+    /// Handles the base queries
+    fn query_base(deps: Deps, msg: BaseQueryMsg) -> StdResult<Binary> {
+        handle_base_query(deps, msg)
+    }
+
+    /// Returns the base state if the sender is the trader
+    fn assert_trader(deps: Deps, info: &MessageInfo) -> Result<BaseState, Self::Error> {
+        let state = BASESTATE.load(deps.storage)?;
+        if info.sender != state.trader {
+            return Err(BaseDAppError::Unauthorized {}.into());
+        }
+        Ok(state)
+    }
+
+    /// Forwards `msgs` to the treasury for execution if the sender is the trader
+    fn trader_action(
+        deps: Deps,
+        info: &MessageInfo,
+        msgs: Vec<CosmosMsg>,
+    ) -> Result<Response, Self::Error> {
+        let state = Self::assert_trader(deps, info)?;
+        Ok(Response::new().add_message(trader_action(msgs, &state.treasury_address)?))
+    }
+}
@@ -2,11 +2,16 @@ use cosmwasm_std::StdError;
 use cw_controllers::AdminError;
 use thiserror::Error;
 
+use crate::error::CommonError;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum BaseDAppError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Admin(#[from] AdminError),
 
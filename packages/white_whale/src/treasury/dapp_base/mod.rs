@@ -1,6 +1,8 @@
+pub mod cache;
 pub mod commands;
 pub mod common;
 pub mod common_test;
+pub mod dapp;
 pub mod error;
 pub mod msg;
 pub mod queries;
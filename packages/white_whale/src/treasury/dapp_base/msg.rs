@@ -20,6 +20,8 @@ pub enum BaseExecuteMsg {
     },
     /// Sets a new Admin
     SetAdmin { admin: String },
+    /// Caches the given memory assets at the current height, anyone can call this
+    RefreshCache { keys: Vec<String> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
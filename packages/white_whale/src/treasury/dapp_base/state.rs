@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Addr;
 use cw_controllers::Admin;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
+use terraswap::asset::AssetInfo;
 
 use crate::memory::item::Memory;
 
@@ -15,6 +16,16 @@ pub struct BaseState {
     pub memory: Memory,
 }
 
+/// Memory asset cached by the dapp, see [crate::treasury::dapp_base::cache]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CachedAsset {
+    pub info: AssetInfo,
+    /// Block height at which the asset was queried from memory
+    pub cached_at: u64,
+}
+
 // Every DApp should use the provide memory contract for token/contract address resolution
 pub const BASESTATE: Item<BaseState> = Item::new("\u{0}{10}base_state");
 pub const ADMIN: Admin = Admin::new("admin");
+/// Memory assets by name, refreshed with BaseExecuteMsg::RefreshCache
+pub const ASSET_CACHE: Map<&str, CachedAsset> = Map::new("asset_cache");
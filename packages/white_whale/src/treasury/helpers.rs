@@ -0,0 +1,44 @@
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, Empty, StdResult, WasmMsg};
+
+use crate::treasury::msg::ExecuteMsg;
+
+/// Constructs the DAppAction message executing `msgs` through the treasury. Only the traders of
+/// the treasury can send it.
+pub fn trader_action(msgs: Vec<CosmosMsg>, treasury_address: &Addr) -> StdResult<CosmosMsg<Empty>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: treasury_address.to_string(),
+        msg: to_binary(&ExecuteMsg::DAppAction { msgs })?,
+        funds: vec![],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{from_binary, BankMsg, Coin};
+
+    use super::*;
+
+    #[test]
+    fn trader_action_targets_the_treasury() {
+        let inner: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: vec![Coin::new(10, "uusd")],
+        });
+        let msg = trader_action(vec![inner.clone()], &Addr::unchecked("treasury")).unwrap();
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                assert_eq!(contract_addr, "treasury");
+                assert!(funds.is_empty());
+                assert_eq!(
+                    from_binary::<ExecuteMsg>(&msg).unwrap(),
+                    ExecuteMsg::DAppAction { msgs: vec![inner] }
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use cosmwasm_std::{
+    to_binary, Addr, Deps, Env, QueryRequest, StdError, StdResult, Uint128, WasmQuery,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::query::terraswap::query_asset_balance;
+use crate::treasury::vault_assets::{BreachAction, ValueRef, VaultAsset};
+
+// Page size used when counting the NFTs held by the treasury
+const CW721_PAGE_LIMIT: u32 = 30;
+
+/// The kind of holding, determines how the held amount is queried.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HoldingKind {
+    Native { denom: String },
+    Cw20 { contract_addr: String },
+    // Every NFT of the collection counts as one unit
+    Cw721 { contract_addr: String },
+    // LP token, value it through ValueRef::Liquidity
+    Lp { contract_addr: String },
+}
+
+/// How the value of a holding is determined.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HoldingValuation {
+    /// The holding is registered but does not add to the treasury value
+    Zero,
+    /// Each unit is worth a fixed amount of the base asset
+    PerUnit { value: Uint128 },
+    /// The holding is valued like a VaultAsset, not available for Cw721 holdings
+    Reference(ValueRef),
+}
+
+/// A holding of the treasury that is not tracked in VAULT_ASSETS.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Holding {
+    pub kind: HoldingKind,
+    pub valuation: HoldingValuation,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum Cw721QueryMsg {
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct TokensResponse {
+    tokens: Vec<String>,
+}
+
+impl HoldingKind {
+    /// Gets the identifier of the holding (either its denom or contract address)
+    pub fn identifier(&self) -> &String {
+        match self {
+            HoldingKind::Native { denom } => denom,
+            HoldingKind::Cw20 { contract_addr }
+            | HoldingKind::Cw721 { contract_addr }
+            | HoldingKind::Lp { contract_addr } => contract_addr,
+        }
+    }
+
+    /// Returns the terraswap asset info of fungible holdings
+    pub fn asset_info(&self) -> Option<AssetInfo> {
+        match self {
+            HoldingKind::Native { denom } => Some(AssetInfo::NativeToken {
+                denom: denom.clone(),
+            }),
+            HoldingKind::Cw20 { contract_addr } | HoldingKind::Lp { contract_addr } => {
+                Some(AssetInfo::Token {
+                    contract_addr: contract_addr.clone(),
+                })
+            }
+            HoldingKind::Cw721 { .. } => None,
+        }
+    }
+
+    /// Queries the amount of this holding owned by `holder`
+    pub fn amount(&self, deps: Deps, holder: &Addr) -> StdResult<Uint128> {
+        match self {
+            HoldingKind::Cw721 { contract_addr } => query_cw721_count(deps, contract_addr, holder),
+            _ => match self.asset_info() {
+                Some(info) => query_asset_balance(deps, &info, holder.clone()),
+                None => Ok(Uint128::zero()),
+            },
+        }
+    }
+}
+
+impl Holding {
+    /// Calculates the value of the treasury's holding
+    pub fn value(&self, deps: Deps, env: &Env) -> StdResult<Uint128> {
+        match &self.valuation {
+            HoldingValuation::Zero => Ok(Uint128::zero()),
+            HoldingValuation::PerUnit { value } => {
+                let amount = self.kind.amount(deps, &env.contract.address)?;
+                Ok(amount.checked_mul(*value)?)
+            }
+            HoldingValuation::Reference(value_reference) => {
+                let info = self.kind.asset_info().ok_or_else(|| {
+                    StdError::generic_err("Cw721 holdings can't be valued through a ValueRef")
+                })?;
+                let mut vault_asset = VaultAsset {
+                    asset: Asset {
+                        info,
+                        amount: Uint128::zero(),
+                    },
+                    value_reference: Some(value_reference.clone()),
+                    max_value: None,
+                    max_price_move_per_block: None,
+                    breach_action: BreachAction::Clamp,
+                };
+                vault_asset.value(deps, env, None)
+            }
+        }
+    }
+}
+
+/// Counts the NFTs of the collection owned by `owner`
+fn query_cw721_count(deps: Deps, contract_addr: &str, owner: &Addr) -> StdResult<Uint128> {
+    let mut count = 0u128;
+    let mut start_after: Option<String> = None;
+    loop {
+        let response: TokensResponse =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw721QueryMsg::Tokens {
+                    owner: owner.to_string(),
+                    start_after: start_after.clone(),
+                    limit: Some(CW721_PAGE_LIMIT),
+                })?,
+            }))?;
+        count += response.tokens.len() as u128;
+        if response.tokens.len() < CW721_PAGE_LIMIT as usize {
+            return Ok(Uint128::from(count));
+        }
+        start_after = response.tokens.last().cloned();
+    }
+}
@@ -1,4 +1,7 @@
 pub mod dapp_base;
+pub mod helpers;
+pub mod holdings;
 pub mod msg;
 pub mod state;
+pub mod twap;
 pub mod vault_assets;
@@ -1,9 +1,15 @@
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, Empty, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{CosmosMsg, Decimal, Empty, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use terra_rust_script_derive::CosmWasmContract;
 
-use crate::treasury::vault_assets::VaultAsset;
+use crate::pause::PauseScope;
+use crate::treasury::holdings::Holding;
+use crate::treasury::state::{
+    ActionLog, DAppCapabilities, DistributionRecipient, OutflowLimit, Position, Spend, Unbonding,
+    ValueSnapshot,
+};
+use crate::treasury::vault_assets::{ValueRef, VaultAsset};
 use terraswap::asset::AssetInfo;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 pub struct InstantiateMsg {}
@@ -11,19 +17,91 @@ pub struct InstantiateMsg {}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    /// Sets the admin
+    /// Deprecated, proposes the admin like ProposeAdmin with the default expiry
     SetAdmin { admin: String },
+    /// Proposes a new admin, who has to accept within `expires_in` blocks
+    ProposeAdmin { admin: String, expires_in: u64 },
+    /// Accepts the pending admin transfer
+    AcceptAdmin {},
+    /// Pauses the contract, only the admin can call this
+    Pause {},
+    /// Unpauses the contract, only the admin can call this
+    Unpause {},
+    /// Pauses the given function scopes while the rest of the contract keeps working, only the admin can call this
+    PauseScopes { scopes: Vec<PauseScope> },
+    /// Unpauses the given function scopes, only the admin can call this
+    UnpauseScopes { scopes: Vec<PauseScope> },
+    /// Sets the operator, who can call UpdateAssets and UpdateHoldings next to the admin.
+    /// None removes the operator, only the admin can call this
+    SetOperator { operator: Option<String> },
     /// Executes the provided messages if sender is whitelisted
     DAppAction { msgs: Vec<CosmosMsg<Empty>> },
     /// Adds the provided address to whitelisted dapps
     AddDApp { dapp: String },
-    /// Removes the provided address from the whitelisted dapps
+    /// Removes the provided address from the whitelisted dapps, clears its capabilities and
+    /// revokes the cw20 allowances the treasury granted through it
     RemoveDApp { dapp: String },
-    /// Updates the VAULT_ASSETS map
+    /// Sets the capabilities of a whitelisted dapp, None removes all restrictions
+    SetDAppCapabilities {
+        dapp: String,
+        capabilities: Option<DAppCapabilities>,
+    },
+    /// Reports the amount of an external position of the calling dApp, e.g. LP tokens staked in a
+    /// farm, so it's included in the total value. A zero amount removes the position. Only
+    /// whitelisted dApps can call this
+    UpdatePosition {
+        id: String,
+        asset: AssetInfo,
+        amount: Uint128,
+    },
+    /// Announces a transfer out of the treasury that can be executed after `execute_after`
+    ScheduleSpend {
+        recipient: String,
+        asset: AssetInfo,
+        amount: Uint128,
+        execute_after: u64,
+    },
+    /// Executes a scheduled spend once its timelock has passed
+    ExecuteSpend { id: u64 },
+    /// Sets the outflow limit of an asset, None removes the limit
+    SetOutflowLimit {
+        asset: AssetInfo,
+        limit: Option<OutflowLimit>,
+    },
+    /// Records the current total value, can be called once every snapshot interval
+    SnapshotValue {},
+    /// Updates the HOLDINGS map, `to_remove` holds identifiers. Callable by the admin and the operator
+    UpdateHoldings {
+        to_add: Vec<Holding>,
+        to_remove: Vec<String>,
+    },
+    /// Updates the TWAP of a vault asset valued through ValueRef::Twap
+    UpdateTwap { identifier: String },
+    /// Updates the VAULT_ASSETS map. Callable by the admin and the operator
     UpdateAssets {
         to_add: Vec<VaultAsset>,
         to_remove: Vec<AssetInfo>,
     },
+    /// Sets the asset values are denominated in, it must be a vault asset without a value reference
+    SetBaseAsset { asset: AssetInfo },
+    /// Sets the recipients of distributed assets and their weights
+    SetDistribution {
+        recipients: Vec<DistributionRecipient>,
+    },
+    /// Splits `amount` of the asset over the distribution recipients according to their weights
+    Distribute { asset: AssetInfo, amount: Uint128 },
+    /// Delegates `amount` uluna to a validator
+    Delegate { validator: String, amount: Uint128 },
+    /// Undelegates `amount` uluna from a validator, it returns after the unbonding period
+    Undelegate { validator: String, amount: Uint128 },
+    /// Moves `amount` of delegated uluna from one validator to another
+    Redelegate {
+        src_validator: String,
+        dst_validator: String,
+        amount: Uint128,
+    },
+    /// Withdraws the staking rewards accrued with a validator
+    WithdrawDelegatorRewards { validator: String },
 }
 
 /// MigrateMsg allows a privileged contract administrator to run
@@ -41,19 +119,127 @@ pub struct MigrateMsg {}
 pub enum QueryMsg {
     /// Returns the treasury Config
     Config {},
+    /// Returns the paused function scopes
+    PausedScopes {},
     /// Returns the total value of all held assets
     TotalValue {},
-    /// Returns the value of one specific asset
-    HoldingValue { identifier: String },
-    /// Returns the amount of specified tokens this contract holds
+    /// Returns the amount, value and share of the total value of every asset, holding and of the
+    /// staked uluna
+    TotalValueBreakdown {},
+    /// Returns the summed value of a page of VAULT_ASSETS, ordered by identifier
+    PaginatedTotalValue {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the value of one specific asset in the base asset, valued at `amount` instead of
+    /// the held amount if provided
+    HoldingValue {
+        identifier: String,
+        amount: Option<Uint128>,
+    },
+    /// Returns the value of one specific asset in `denom`, the identifier of a vault asset
+    ValueIn { identifier: String, denom: String },
+    /// Returns the amount of specified tokens this contract holds, the identifier can be
+    /// a vault asset or a holding
     HoldingAmount { identifier: String },
+    /// Returns the raw balances of a page of VAULT_ASSETS, ordered by identifier
+    HoldingAmounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns the VAULT_ASSETS value for the specified key
     VaultAssetConfig { identifier: String },
+    /// Returns the capabilities of a whitelisted dapp
+    DAppCapabilities { dapp: String },
+    /// Returns the scheduled spend with the given id
+    Spend { id: u64 },
+    /// Returns the scheduled spends, ordered by id
+    Spends {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the outflow limit of an asset and the amount that can still leave the treasury this period
+    OutflowAllowance { identifier: String },
+    /// Returns the recorded total value snapshots, ordered by block height
+    ValueHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the logged DAppActions, ordered by id
+    ActionHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the TWAP state of a vault asset valued through ValueRef::Twap
+    Twap { identifier: String },
+    /// Returns the registered VAULT_ASSETS entries, ordered by identifier
+    VaultAssets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the distribution recipients and their weights
+    DistributionConfig {},
+    /// Returns the tracked delegations and the unbondings that have not completed yet
+    StakingPositions {},
+    /// Returns the external positions reported by dApps, optionally only those of `dapp`
+    Positions { dapp: Option<String> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     pub dapps: Vec<String>,
+    pub holdings: Vec<Holding>,
+    pub base_asset: AssetInfo,
+    pub operator: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DAppCapabilitiesResponse {
+    pub capabilities: Option<DAppCapabilities>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributionConfigResponse {
+    pub recipients: Vec<DistributionRecipient>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegationEntry {
+    pub validator: String,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingPositionsResponse {
+    pub delegations: Vec<DelegationEntry>,
+    pub unbondings: Vec<Unbonding>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionsResponse {
+    pub positions: Vec<Position>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SpendsResponse {
+    pub spends: Vec<Spend>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OutflowAllowanceResponse {
+    pub limit: Option<OutflowLimit>,
+    /// None if the asset has no outflow limit
+    pub remaining: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ValueHistoryResponse {
+    pub snapshots: Vec<ValueSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActionHistoryResponse {
+    pub actions: Vec<ActionLog>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -61,6 +247,52 @@ pub struct TotalValueResponse {
     pub value: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ValueBreakdownEntry {
+    pub identifier: String,
+    pub amount: Uint128,
+    pub value: Uint128,
+    /// Share of the total value
+    pub share: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalValueBreakdownResponse {
+    pub total_value: Uint128,
+    pub entries: Vec<ValueBreakdownEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PaginatedTotalValueResponse {
+    pub value: Uint128,
+    /// Identifier of the last asset in this page, used as `start_after` for the next page
+    pub last_asset: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultAssetEntry {
+    pub identifier: String,
+    pub asset_info: AssetInfo,
+    pub value_reference: Option<ValueRef>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultAssetsResponse {
+    pub assets: Vec<VaultAssetEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HoldingAmountEntry {
+    pub identifier: String,
+    pub asset_info: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HoldingAmountsResponse {
+    pub amounts: Vec<HoldingAmountEntry>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct HoldingValueResponse {
     pub value: Uint128,
@@ -78,14 +310,8 @@ pub struct ValueQueryMsg {
 pub struct ExternalValueResponse {
     pub value: Uint128,
 }
-/// Constructs the treasury dapp action message used by all dApps.
-pub fn send_to_treasury(
-    msgs: Vec<CosmosMsg>,
-    treasury_address: &Addr,
-) -> StdResult<CosmosMsg<Empty>> {
-    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: treasury_address.to_string(),
-        msg: to_binary(&ExecuteMsg::DAppAction { msgs })?,
-        funds: vec![],
-    }))
+/// Price oracle response, the price of one unit of the asset in the base asset
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OraclePriceResponse {
+    pub price: Decimal,
 }
@@ -2,10 +2,13 @@ use cw_controllers::Admin;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Decimal, Deps, Env, StdResult, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, Decimal, Deps, Env, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
 
+use crate::denom::UST_DENOM;
 use crate::query::terraswap::query_pool;
+use crate::treasury::holdings::Holding;
+use crate::treasury::twap::TwapState;
 use crate::treasury::vault_assets::{get_identifier, VaultAsset};
 use terraswap::asset::AssetInfo;
 use terraswap::pair::PoolResponse;
@@ -17,11 +20,159 @@ pub struct State {
     pub dapps: Vec<Addr>,
 }
 
+/// Categories of messages a dApp can forward through the treasury
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MsgCategory {
+    Bank,
+    Wasm,
+    Staking,
+    Distribution,
+    Custom,
+    Other,
+}
+
+/// Optional restrictions on the messages a whitelisted dApp can forward.
+/// A `None` field leaves that dimension unrestricted.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DAppCapabilities {
+    pub msg_categories: Option<Vec<MsgCategory>>,
+    /// Contracts the dApp can target with Wasm messages
+    pub contracts: Option<Vec<Addr>>,
+}
+
+/// A transfer out of the treasury that can be executed once its timelock has passed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Spend {
+    pub id: u64,
+    pub recipient: Addr,
+    pub asset: AssetInfo,
+    pub amount: Uint128,
+    /// Block time in seconds after which the spend can be executed
+    pub execute_after: u64,
+    pub executed: bool,
+}
+
+/// Maximum amount of an asset that can leave the treasury through dApp actions per `period` blocks,
+/// cw20 allowances granted by dApp actions count towards it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OutflowLimit {
+    pub amount: Uint128,
+    pub period: u64,
+}
+
+/// Outflow of an asset in the period starting at `period_start`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Outflow {
+    pub period_start: u64,
+    pub amount: Uint128,
+}
+
+/// Total value of the treasury at a block height
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ValueSnapshot {
+    pub height: u64,
+    pub value: Uint128,
+}
+
+/// Value of PRICE_SNAPSHOT_UNIT units of a vault asset at a block height
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceSnapshot {
+    pub height: u64,
+    pub unit_value: Uint128,
+}
+
+/// Record of a DAppAction executed through the treasury
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActionLog {
+    pub id: u64,
+    pub sender: Addr,
+    pub height: u64,
+    pub msg_count: u64,
+    /// Sha256 hash of the json encoded messages
+    pub msgs_hash: Binary,
+}
+
+/// Recipient of a share of the distributed assets, proportional to its weight
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributionRecipient {
+    pub address: Addr,
+    pub weight: u64,
+}
+
+/// Cw20 allowance the treasury granted to `spender` through a dApp action. `amount` is the
+/// net amount granted by the dApp, i.e. increases minus decreases.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DAppAllowance {
+    pub token: Addr,
+    pub spender: String,
+    pub amount: Uint128,
+}
+
+/// Funds a dApp moved out of the treasury into an external protocol, reported by the dApp.
+/// The amount is valued through the vault asset of `asset`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Position {
+    pub dapp: Addr,
+    pub id: String,
+    pub asset: AssetInfo,
+    pub amount: Uint128,
+    /// Block height of the last update
+    pub updated_at: u64,
+}
+
+/// LUNA undelegated from a validator that returns to the treasury at `release_at`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Unbonding {
+    pub validator: String,
+    pub amount: Uint128,
+    /// Block time in seconds at which the unbonding completes
+    pub release_at: u64,
+}
+
 pub const STATE: Item<State> = Item::new("\u{0}{5}state");
 pub const ADMIN: Admin = Admin::new("admin");
+/// Address which can register assets and holdings without the admin, e.g. an operations key when
+/// the admin is a cw3 multisig or governance
+pub const OPERATOR: Admin = Admin::new("operator");
+pub const BASE_ASSET: Item<AssetInfo> = Item::new("base_asset");
 pub const VAULT_ASSETS: Map<&str, VaultAsset> = Map::new("vault_assets");
+pub const HOLDINGS: Map<&str, Holding> = Map::new("holdings");
+pub const TWAPS: Map<&str, TwapState> = Map::new("twaps");
+pub const DAPP_CAPABILITIES: Map<&str, DAppCapabilities> = Map::new("dapp_capabilities");
+/// Outstanding cw20 allowances granted through each dApp, revoked when the dApp is removed
+pub const DAPP_ALLOWANCES: Map<&str, Vec<DAppAllowance>> = Map::new("dapp_allowances");
+/// External positions keyed by (dApp address, position id)
+pub const POSITIONS: Map<(&[u8], &[u8]), Position> = Map::new("positions");
+pub const SPEND_COUNT: Item<u64> = Item::new("spend_count");
+pub const SPENDS: Map<U64Key, Spend> = Map::new("spends");
+pub const OUTFLOW_LIMITS: Map<&str, OutflowLimit> = Map::new("outflow_limits");
+pub const OUTFLOWS: Map<&str, Outflow> = Map::new("outflows");
+pub const VALUE_SNAPSHOT_COUNT: Item<u64> = Item::new("value_snapshot_count");
+pub const VALUE_SNAPSHOTS: Map<U64Key, ValueSnapshot> = Map::new("value_snapshots");
+pub const PRICE_SNAPSHOTS: Map<&str, PriceSnapshot> = Map::new("price_snapshots");
+pub const ACTION_LOG_COUNT: Item<u64> = Item::new("action_log_count");
+pub const ACTION_LOGS: Map<U64Key, ActionLog> = Map::new("action_logs");
+pub const DISTRIBUTION: Item<Vec<DistributionRecipient>> = Item::new("distribution");
+pub const DELEGATIONS: Map<&str, Uint128> = Map::new("delegations");
+pub const UNBONDINGS: Item<Vec<Unbonding>> = Item::new("unbondings");
 
-pub fn lp_value(deps: Deps, env: &Env, pool_addr: &Addr, holdings: &Uint128) -> StdResult<Uint128> {
+/// Returns the asset all values are denominated in, UST unless configured otherwise
+pub fn load_base_asset(storage: &dyn Storage) -> StdResult<AssetInfo> {
+    Ok(BASE_ASSET
+        .may_load(storage)?
+        .unwrap_or_else(|| AssetInfo::NativeToken {
+            denom: UST_DENOM.to_string(),
+        }))
+}
+
+pub fn lp_value(
+    deps: Deps,
+    env: &Env,
+    pool_addr: &Addr,
+    holdings: &Uint128,
+    path: &mut Vec<String>,
+) -> StdResult<Uint128> {
     // Get LP pool info
     let pool_info: PoolResponse = query_pool(deps, pool_addr)?;
 
@@ -42,8 +193,10 @@ pub fn lp_value(deps: Deps, env: &Env, pool_addr: &Addr, holdings: &Uint128) ->
     let vault_asset_1_amount = share * asset_1.amount;
     let vault_asset_2_amount = share * asset_2.amount;
     // Call value on these assets.
-    Ok(vault_asset_1.value(deps, env, Some(vault_asset_1_amount))?
-        + vault_asset_2.value(deps, env, Some(vault_asset_2_amount))?)
+    Ok(
+        vault_asset_1.value_along_path(deps, env, Some(vault_asset_1_amount), path)?
+            + vault_asset_2.value_along_path(deps, env, Some(vault_asset_2_amount), path)?,
+    )
 }
 
 pub fn proxy_value(
@@ -52,11 +205,12 @@ pub fn proxy_value(
     proxy_asset_info: &AssetInfo,
     multiplier: &Decimal,
     holding: Uint128,
+    path: &mut Vec<String>,
 ) -> StdResult<Uint128> {
     // Get the proxy asset
     let mut proxy_vault_asset: VaultAsset =
         VAULT_ASSETS.load(deps.storage, get_identifier(proxy_asset_info).as_str())?;
 
     // call value on proxy asset with adjusted multiplier.
-    proxy_vault_asset.value(deps, env, Some(holding * *multiplier))
+    proxy_vault_asset.value_along_path(deps, env, Some(holding * *multiplier), path)
 }
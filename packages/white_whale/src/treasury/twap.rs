@@ -0,0 +1,60 @@
+use cosmwasm_std::{Decimal, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terraswap::asset::AssetInfo;
+
+// Precision of the accumulated prices
+const PRICE_PRECISION: u128 = 1_000_000;
+
+/// Time weighted average price of an asset in `quote_asset`.
+/// The TWAP is recomputed every time a full window has passed since the previous one.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TwapState {
+    pub quote_asset: AssetInfo,
+    pub last_price: Decimal,
+    /// Block time in seconds of the last update
+    pub last_update: u64,
+    /// Sum of the price multiplied by the seconds it was active, scaled by PRICE_PRECISION
+    pub price_cumulative: Uint128,
+    pub window_start: u64,
+    pub window_start_cumulative: Uint128,
+    /// None until a full window has been observed
+    pub twap: Option<Decimal>,
+}
+
+impl TwapState {
+    pub fn new(quote_asset: AssetInfo, price: Decimal, now: u64) -> Self {
+        Self {
+            quote_asset,
+            last_price: price,
+            last_update: now,
+            price_cumulative: Uint128::zero(),
+            window_start: now,
+            window_start_cumulative: Uint128::zero(),
+            twap: None,
+        }
+    }
+
+    /// Accumulates the last price up to `now` and stores the new spot price
+    pub fn update(&mut self, price: Decimal, now: u64, window: u64) -> StdResult<()> {
+        let elapsed = now.saturating_sub(self.last_update);
+        let scaled_price = Uint128::from(PRICE_PRECISION) * self.last_price;
+        self.price_cumulative = self
+            .price_cumulative
+            .checked_add(scaled_price.checked_mul(Uint128::from(elapsed))?)?;
+        self.last_price = price;
+        self.last_update = now;
+
+        let window_elapsed = now.saturating_sub(self.window_start);
+        if window_elapsed >= window && window_elapsed > 0 {
+            let cumulative_delta = self.price_cumulative - self.window_start_cumulative;
+            self.twap = Some(Decimal::from_ratio(
+                cumulative_delta,
+                Uint128::from(window_elapsed as u128 * PRICE_PRECISION),
+            ));
+            self.window_start = now;
+            self.window_start_cumulative = self.price_cumulative;
+        }
+        Ok(())
+    }
+}
@@ -1,16 +1,24 @@
 use cosmwasm_std::{
-    to_binary, Addr, Decimal, Deps, Env, QueryRequest, StdError, StdResult, Uint128, WasmQuery,
+    to_binary, Addr, Binary, Decimal, Deps, Env, QueryRequest, StdError, StdResult, Uint128,
+    WasmQuery,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::denom::UST_DENOM;
+use crate::query::anchor::query_aust_exchange_rate;
+use crate::query::astroport;
 use crate::query::terraswap::{query_asset_balance, query_pool};
 use crate::tax::reverse_decimal;
-use crate::treasury::msg::{ExternalValueResponse, ValueQueryMsg};
+use crate::treasury::msg::{ExternalValueResponse, OraclePriceResponse, ValueQueryMsg};
 use crate::treasury::state::*;
+use crate::treasury::twap::TwapState;
 use terraswap::asset::{Asset, AssetInfo};
 use terraswap::pair::PoolResponse;
 
+/// Amount of an asset valued to record its price in a price snapshot
+pub const PRICE_SNAPSHOT_UNIT: u128 = 1_000_000;
+
 /// Every VaultAsset provides a way to determine its value recursivly relative to
 /// a base asset.
 /// This is subject to change as Chainlink an/or TWAP implementations roll out on terra.
@@ -21,6 +29,33 @@ pub struct VaultAsset {
     // The value reference provides the tooling to get the value of the holding
     // relative to the base asset.
     pub value_reference: Option<ValueRef>,
+    /// Maximum value the asset is counted for
+    #[serde(default)]
+    pub max_value: Option<Uint128>,
+    /// Maximum relative move of the price per block since the last price snapshot, taken on
+    /// SnapshotValue
+    #[serde(default)]
+    pub max_price_move_per_block: Option<Decimal>,
+    /// What happens when the value exceeds `max_value` or the price moves beyond
+    /// `max_price_move_per_block`
+    #[serde(default)]
+    pub breach_action: BreachAction,
+}
+
+/// Action taken when a vault asset breaches its value cap or price move limit
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BreachAction {
+    /// The value is clamped to the cap and the price to the allowed move
+    Clamp,
+    /// The valuation fails
+    Error,
+}
+
+impl Default for BreachAction {
+    fn default() -> Self {
+        BreachAction::Clamp
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -45,6 +80,28 @@ pub enum ValueRef {
     External {
         contract_address: Addr,
     },
+    /// An Astroport pair address of an asset/asset pair
+    /// Both assets must be defined in the Vault_assets state
+    AstroportPool {
+        pair_address: Addr,
+    },
+    /// A price oracle, `query` is sent as is and must be answered with an OraclePriceResponse
+    /// holding the price of one unit of the asset in the base asset
+    Oracle {
+        contract_address: Addr,
+        query: Binary,
+    },
+    /// A pool address of an asset/asset pair, valued at the TWAP over `window` seconds
+    /// The TWAP is kept up to date by UpdateTwap calls, the spot price is used until it is available
+    Twap {
+        pair_address: Addr,
+        window: u64,
+    },
+    /// aUST valued at the exchange rate of the Anchor money market epoch state
+    /// UST must be defined in the Vault_assets state
+    Anchor {
+        money_market_address: Addr,
+    },
 }
 
 impl VaultAsset {
@@ -54,6 +111,109 @@ impl VaultAsset {
         deps: Deps,
         env: &Env,
         set_holding: Option<Uint128>,
+    ) -> StdResult<Uint128> {
+        self.value_along_path(deps, env, set_holding, &mut vec![])
+    }
+
+    /// Applies the value cap and the price move limit to `value`, the value of the amount set by
+    /// the last value() call
+    pub fn guard_value(&self, deps: Deps, env: &Env, value: Uint128) -> StdResult<Uint128> {
+        let mut value = value;
+        if self.max_price_move_per_block.is_some() && !self.asset.amount.is_zero() {
+            let unit = Uint128::from(PRICE_SNAPSHOT_UNIT);
+            let unit_value = value.multiply_ratio(unit, self.asset.amount);
+            let bounded_unit_value = self.bound_price_move(deps, env, unit_value)?;
+            if bounded_unit_value != unit_value {
+                value = self.asset.amount.multiply_ratio(bounded_unit_value, unit);
+            }
+        }
+
+        if let Some(max_value) = self.max_value {
+            if value > max_value {
+                match self.breach_action {
+                    BreachAction::Clamp => value = max_value,
+                    BreachAction::Error => {
+                        return Err(StdError::generic_err(format!(
+                            "The value of {} exceeds its cap of {}",
+                            get_identifier(&self.asset.info),
+                            max_value
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    /// Value of PRICE_SNAPSHOT_UNIT units of the asset within the price move limit, as recorded in
+    /// its price snapshot
+    pub fn unit_value(&self, deps: Deps, env: &Env) -> StdResult<Uint128> {
+        let unit_value = self
+            .clone()
+            .value(deps, env, Some(Uint128::from(PRICE_SNAPSHOT_UNIT)))?;
+        self.bound_price_move(deps, env, unit_value)
+    }
+
+    /// Bounds the value of PRICE_SNAPSHOT_UNIT units to the move allowed since the price snapshot
+    fn bound_price_move(&self, deps: Deps, env: &Env, unit_value: Uint128) -> StdResult<Uint128> {
+        let max_move = match self.max_price_move_per_block {
+            Some(max_move) => max_move,
+            None => return Ok(unit_value),
+        };
+        let identifier = get_identifier(&self.asset.info);
+        let snapshot = match PRICE_SNAPSHOTS.may_load(deps.storage, identifier)? {
+            Some(snapshot) => snapshot,
+            None => return Ok(unit_value),
+        };
+
+        let blocks = env.block.height.saturating_sub(snapshot.height).max(1);
+        let allowed_move = max_move * snapshot.unit_value.checked_mul(Uint128::from(blocks))?;
+        let lower_bound = snapshot
+            .unit_value
+            .checked_sub(allowed_move)
+            .unwrap_or_default();
+        let upper_bound = snapshot.unit_value.checked_add(allowed_move)?;
+        if unit_value >= lower_bound && unit_value <= upper_bound {
+            return Ok(unit_value);
+        }
+
+        match self.breach_action {
+            BreachAction::Clamp => Ok(unit_value.max(lower_bound).min(upper_bound)),
+            BreachAction::Error => Err(StdError::generic_err(format!(
+                "The price of {} moved beyond its limit since height {}",
+                identifier, snapshot.height
+            ))),
+        }
+    }
+
+    /// Calculates the value of the asset, `path` holds the identifiers of the assets currently being
+    /// valued through this asset. Finding the asset on its own path means its ValueRefs form a cycle.
+    pub fn value_along_path(
+        &mut self,
+        deps: Deps,
+        env: &Env,
+        set_holding: Option<Uint128>,
+        path: &mut Vec<String>,
+    ) -> StdResult<Uint128> {
+        let identifier = get_identifier(&self.asset.info);
+        if path.contains(identifier) {
+            return Err(StdError::generic_err(format!(
+                "Cycle detected in the value reference of {}",
+                identifier
+            )));
+        }
+        path.push(identifier.to_string());
+        let value = self.compute_value(deps, env, set_holding, path)?;
+        path.pop();
+        Ok(value)
+    }
+
+    fn compute_value(
+        &mut self,
+        deps: Deps,
+        env: &Env,
+        set_holding: Option<Uint128>,
+        path: &mut Vec<String>,
     ) -> StdResult<Uint128> {
         // Query how many of these tokens are held in the contract if not set.
 
@@ -68,13 +228,18 @@ impl VaultAsset {
             match value_reference {
                 // A Pool refers to a swap pair that recursively leads to an asset/base_asset pool.
                 ValueRef::Pool { pair_address } => {
-                    return self.asset_value(deps, env, pair_address)
+                    return self.asset_value(deps, env, pair_address, path)
+                }
+                // Same as Pool, through an Astroport pair
+                ValueRef::AstroportPool { pair_address } => {
+                    let pool_info = astroport::query_pool(deps, pair_address.clone())?;
+                    return self.pool_value(deps, env, pool_info, path);
                 }
                 // Liquidity is an LP token, value() fn is called recursively on both assets in the pool
                 ValueRef::Liquidity { pool_address } => {
                     // Check if we have a Token
                     if let AssetInfo::Token { .. } = &self.asset.info {
-                        return lp_value(deps, env, pool_address, &holding);
+                        return lp_value(deps, env, pool_address, &holding, path);
                     } else {
                         return Err(StdError::generic_err("Can't have a native LP token"));
                     }
@@ -83,7 +248,7 @@ impl VaultAsset {
                 ValueRef::Proxy {
                     proxy_asset,
                     multiplier,
-                } => return proxy_value(deps, env, proxy_asset, multiplier, holding),
+                } => return proxy_value(deps, env, proxy_asset, multiplier, holding, path),
                 ValueRef::External { contract_address } => {
                     let response: ExternalValueResponse =
                         deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
@@ -95,6 +260,50 @@ impl VaultAsset {
                         }))?;
                     return Ok(response.value);
                 }
+                ValueRef::Twap { pair_address, .. } => {
+                    return match TWAPS.may_load(deps.storage, get_identifier(&self.asset.info))? {
+                        Some(TwapState {
+                            twap: Some(twap),
+                            quote_asset,
+                            ..
+                        }) => {
+                            let mut quote_vault_asset: VaultAsset =
+                                VAULT_ASSETS.load(deps.storage, get_identifier(&quote_asset))?;
+                            quote_vault_asset.value_along_path(
+                                deps,
+                                env,
+                                Some(holding * twap),
+                                path,
+                            )
+                        }
+                        _ => self.asset_value(deps, env, pair_address, path),
+                    };
+                }
+                ValueRef::Oracle {
+                    contract_address,
+                    query,
+                } => {
+                    let response: OraclePriceResponse =
+                        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                            contract_addr: contract_address.to_string(),
+                            msg: query.clone(),
+                        }))?;
+                    return Ok(holding * response.price);
+                }
+                // aUST is valued as UST at the current exchange rate
+                ValueRef::Anchor {
+                    money_market_address,
+                } => {
+                    let exchange_rate = query_aust_exchange_rate(
+                        env.clone(),
+                        deps,
+                        money_market_address.to_string(),
+                    )?;
+                    let ust = AssetInfo::NativeToken {
+                        denom: UST_DENOM.to_string(),
+                    };
+                    return proxy_value(deps, env, &ust, &exchange_rate, holding, path);
+                }
             }
         }
 
@@ -103,8 +312,25 @@ impl VaultAsset {
     }
 
     /// Calculates the value of an asset compared to some base asset throug the provided trading pair.
-    pub fn asset_value(&self, deps: Deps, env: &Env, pool_addr: &Addr) -> StdResult<Uint128> {
+    pub fn asset_value(
+        &self,
+        deps: Deps,
+        env: &Env,
+        pool_addr: &Addr,
+        path: &mut Vec<String>,
+    ) -> StdResult<Uint128> {
         let pool_info: PoolResponse = query_pool(deps, pool_addr)?;
+        self.pool_value(deps, env, pool_info, path)
+    }
+
+    /// Calculates the value of the asset through the price of the provided pool.
+    fn pool_value(
+        &self,
+        deps: Deps,
+        env: &Env,
+        pool_info: PoolResponse,
+        path: &mut Vec<String>,
+    ) -> StdResult<Uint128> {
         // Get price
         let ratio = Decimal::from_ratio(pool_info.assets[0].amount, pool_info.assets[1].amount);
 
@@ -121,7 +347,7 @@ impl VaultAsset {
             amount_in_other_denom = self.asset.amount * ratio;
         }
         // Call value on this other asset.
-        recursive_vault_asset.value(deps, env, Some(amount_in_other_denom))
+        recursive_vault_asset.value_along_path(deps, env, Some(amount_in_other_denom), path)
     }
 }
 
@@ -148,6 +374,24 @@ impl Proxy {
     }
 }
 
+/// Returns the spot price of the asset in the other asset of the pool, together with that other asset
+pub fn pool_price(
+    deps: Deps,
+    asset_info: &AssetInfo,
+    pool_addr: &Addr,
+) -> StdResult<(Decimal, AssetInfo)> {
+    let pool_info: PoolResponse = query_pool(deps, pool_addr)?;
+    let (asset, other_asset) = if *asset_info == pool_info.assets[0].info {
+        (&pool_info.assets[0], &pool_info.assets[1])
+    } else {
+        (&pool_info.assets[1], &pool_info.assets[0])
+    };
+    Ok((
+        Decimal::from_ratio(other_asset.amount, asset.amount),
+        other_asset.info.clone(),
+    ))
+}
+
 /// Gets the identifier of the asset (either its denom or contract address)
 pub fn get_identifier(asset_info: &AssetInfo) -> &String {
     match asset_info {
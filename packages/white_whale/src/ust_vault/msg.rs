@@ -1,14 +1,13 @@
-use crate::fee::{Fee, VaultFee};
-use cosmwasm_std::{
-    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg,
-};
+use crate::fee::Fee;
+use cosmwasm_std::{Coin, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::fmt;
 use terra_rust_script_derive::CosmWasmContract;
 use terraswap::asset::{Asset, AssetInfo};
 
+pub use crate::flashloan::msg::{FeeResponse, FlashLoanPayload, LenderCallbackMsg as CallbackMsg};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 pub struct InstantiateMsg {
     pub anchor_money_market_address: String,
@@ -57,12 +56,6 @@ pub enum ExecuteMsg {
     Callback(CallbackMsg),
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct FlashLoanPayload {
-    pub requested_asset: Asset,
-    pub callback: Binary,
-}
-
 /// MigrateMsg allows a privileged contract administrator to run
 /// a migration on the contract. In this case it is just migrating
 /// from one terra code to the same code, but taking advantage of the
@@ -73,27 +66,6 @@ pub struct FlashLoanPayload {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 pub struct MigrateMsg {}
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum CallbackMsg {
-    AfterTrade { loan_fee: Uint128 },
-}
-
-// Modified from
-// https://github.com/CosmWasm/cosmwasm-plus/blob/v0.2.3/packages/cw20/src/receiver.rs#L15
-impl CallbackMsg {
-    pub fn to_cosmos_msg<T: Clone + fmt::Debug + PartialEq + JsonSchema>(
-        &self,
-        contract_addr: &Addr,
-    ) -> StdResult<CosmosMsg<T>> {
-        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: String::from(contract_addr),
-            msg: to_binary(&ExecuteMsg::Callback(self.clone()))?,
-            funds: vec![],
-        }))
-    }
-}
-
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, CosmWasmContract)]
 #[serde(rename_all = "snake_case")]
 pub enum VaultQueryMsg {
@@ -124,11 +96,6 @@ pub struct ValueResponse {
     pub total_ust_value: Uint128,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct FeeResponse {
-    pub fees: VaultFee,
-}
-
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct EstimateDepositFeeResponse {
     pub fee: Vec<Coin>,